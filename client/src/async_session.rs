@@ -0,0 +1,623 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! Non-blocking access to the services of a session. An [`AsyncSession`] sends each request
+//! without waiting and returns a future that completes with the response, so many requests can be
+//! in flight at once over the session's secure channel and the futures can be driven by any
+//! executor.
+//!
+//! [`AsyncSession`]: ./struct.AsyncSession.html
+
+use std::sync::{Arc, Mutex, RwLock};
+
+use futures::{Async, Future, Poll, sync::{mpsc::UnboundedSender, oneshot}};
+
+use opcua_core::supported_message::SupportedMessage;
+use opcua_types::{
+    *,
+    status_code::StatusCode,
+};
+
+use crate::{
+    callbacks::OnSubscriptionNotification,
+    session_state::SessionState,
+    subscription::{self, Subscription},
+    subscription_state::SubscriptionState,
+    subscription_timer::SubscriptionTimerCommand,
+};
+
+/// A future that completes with the response to a request. It fails with `BadTimeout` if no
+/// response arrives within the session's request timeout, with `BadNotConnected` if the request
+/// could not be sent, and with `BadConnectionClosed` if the session is reset before the response
+/// arrives.
+#[must_use = "futures do nothing unless polled"]
+pub struct ResponseFuture {
+    receiver: Option<oneshot::Receiver<Result<SupportedMessage, StatusCode>>>,
+    status_code: StatusCode,
+}
+
+/// The parameters of a subscription created by [`AsyncSession::create_subscription()`]. They
+/// are described by [`Session::create_subscription()`].
+///
+/// [`AsyncSession::create_subscription()`]: ./struct.AsyncSession.html#method.create_subscription
+/// [`Session::create_subscription()`]: ../session/struct.Session.html#method.create_subscription
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubscriptionParams {
+    pub publishing_interval: f64,
+    pub lifetime_count: u32,
+    pub max_keep_alive_count: u32,
+    pub max_notifications_per_publish: u32,
+    pub priority: u8,
+    pub publishing_enabled: bool,
+}
+
+impl Default for SubscriptionParams {
+    fn default() -> Self {
+        SubscriptionParams {
+            publishing_interval: 1000.0,
+            lifetime_count: 60,
+            max_keep_alive_count: 10,
+            max_notifications_per_publish: 0,
+            priority: 0,
+            publishing_enabled: true,
+        }
+    }
+}
+
+impl ResponseFuture {
+    pub(crate) fn new(receiver: oneshot::Receiver<Result<SupportedMessage, StatusCode>>) -> ResponseFuture {
+        ResponseFuture {
+            receiver: Some(receiver),
+            status_code: StatusCode::Good,
+        }
+    }
+
+    /// Makes a future for a request that could not be sent
+    pub(crate) fn failed(status_code: StatusCode) -> ResponseFuture {
+        ResponseFuture {
+            receiver: None,
+            status_code,
+        }
+    }
+}
+
+impl Future for ResponseFuture {
+    type Item = SupportedMessage;
+    type Error = StatusCode;
+
+    fn poll(&mut self) -> Poll<SupportedMessage, StatusCode> {
+        match self.receiver {
+            Some(ref mut receiver) => match receiver.poll() {
+                Ok(Async::Ready(Ok(response))) => Ok(Async::Ready(response)),
+                Ok(Async::Ready(Err(status_code))) => Err(status_code),
+                Ok(Async::NotReady) => Ok(Async::NotReady),
+                // The sender was dropped because the session was reset
+                Err(_) => Err(StatusCode::BadConnectionClosed),
+            },
+            None => Err(self.status_code),
+        }
+    }
+}
+
+/// Turns the response to a request into the result the caller wants, failing for a bad service
+/// result, a service fault or an unexpected response.
+macro_rules! response_result {
+    ($response: expr, $response_type: ident, |$r: pat| $result: expr) => {
+        match $response {
+            SupportedMessage::$response_type(response) => {
+                crate::process_service_result(&response.response_header)?;
+                let $r = *response;
+                Ok($result)
+            }
+            response => Err(crate::process_unexpected_response(response))
+        }
+    }
+}
+
+/// A handle to a connected [`Session`] whose services return futures instead of blocking. It is
+/// obtained from [`Session::async_session()`] and is cheap to clone, so it can be shared between
+/// tasks and threads without locking the `Session`.
+///
+/// The requests are sent over the session's connection, so the session must remain connected,
+/// e.g. by running it with [`Session::run_async()`], while the futures are pending.
+///
+/// [`Session`]: ../session/struct.Session.html
+/// [`Session::async_session()`]: ../session/struct.Session.html#method.async_session
+/// [`Session::run_async()`]: ../session/struct.Session.html#method.run_async
+#[derive(Clone)]
+pub struct AsyncSession {
+    session_state: Arc<RwLock<SessionState>>,
+    /// The subscriptions of the session, which are updated as their responses arrive
+    subscription_state: Arc<RwLock<SubscriptionState>>,
+    /// Starts the publishing timers of subscriptions that are created
+    timer_command_queue: UnboundedSender<SubscriptionTimerCommand>,
+}
+
+impl AsyncSession {
+    pub(crate) fn new(session_state: Arc<RwLock<SessionState>>, subscription_state: Arc<RwLock<SubscriptionState>>, timer_command_queue: UnboundedSender<SubscriptionTimerCommand>) -> AsyncSession {
+        AsyncSession { session_state, subscription_state, timer_command_queue }
+    }
+
+    /// Makes the header of a request for the session, with the next request handle and the
+    /// session's authentication token. Requests passed to `send()` must have a header from here.
+    pub fn make_request_header(&self) -> RequestHeader {
+        let mut session_state = trace_write_lock_unwrap!(self.session_state);
+        session_state.make_request_header()
+    }
+
+    /// Sends any request of the session and returns a future of its response. The other functions
+    /// of `AsyncSession` are built on this and are easier to use for the services they cover.
+    pub fn send<T>(&self, request: T) -> ResponseFuture where T: Into<SupportedMessage> {
+        let mut session_state = trace_write_lock_unwrap!(self.session_state);
+        session_state.send_request_for_future(request)
+    }
+
+    /// Fails unless the subscription is one of the session's
+    fn check_subscription(&self, subscription_id: u32, service: &str) -> Result<(), StatusCode> {
+        let subscription_state = trace_read_lock_unwrap!(self.subscription_state);
+        if subscription_id == 0 || !subscription_state.subscription_exists(subscription_id) {
+            error!("{}, subscription id {} does not exist", service, subscription_id);
+            Err(StatusCode::BadInvalidArgument)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Sends a request unless the request could not be made
+    fn send_checked<T>(&self, request: Result<T, StatusCode>) -> ResponseFuture where T: Into<SupportedMessage> {
+        match request {
+            Ok(request) => self.send(request),
+            Err(status_code) => ResponseFuture::failed(status_code),
+        }
+    }
+
+    /// Reads the value of nodes by sending a [`ReadRequest`] to the server, see [`Session::read()`].
+    ///
+    /// [`ReadRequest`]: ./struct.ReadRequest.html
+    /// [`Session::read()`]: ../session/struct.Session.html#method.read
+    pub fn read(&self, nodes_to_read: &[ReadValueId]) -> impl Future<Item=Vec<DataValue>, Error=StatusCode> + Send {
+        let request = if nodes_to_read.is_empty() {
+            error!("read(), was not supplied with any nodes to read");
+            Err(StatusCode::BadNothingToDo)
+        } else {
+            Ok(ReadRequest {
+                request_header: self.make_request_header(),
+                max_age: 1f64,
+                timestamps_to_return: TimestampsToReturn::Server,
+                nodes_to_read: Some(nodes_to_read.to_vec()),
+            })
+        };
+        self.send_checked(request).and_then(|response| {
+            response_result!(response, ReadResponse, |r| r.results.unwrap_or_default())
+        })
+    }
+
+    /// Reads historical values or events of nodes by sending a [`HistoryReadRequest`] to the
+    /// server, see [`Session::history_read()`].
+    ///
+    /// [`HistoryReadRequest`]: ./struct.HistoryReadRequest.html
+    /// [`Session::history_read()`]: ../session/struct.Session.html#method.history_read
+    pub fn history_read(&self, history_read_details: ExtensionObject, timestamps_to_return: TimestampsToReturn, release_continuation_points: bool, nodes_to_read: &[HistoryReadValueId]) -> impl Future<Item=Vec<HistoryReadResult>, Error=StatusCode> + Send {
+        let request = HistoryReadRequest {
+            request_header: self.make_request_header(),
+            history_read_details,
+            timestamps_to_return,
+            release_continuation_points,
+            nodes_to_read: if nodes_to_read.is_empty() { None } else { Some(nodes_to_read.to_vec()) },
+        };
+        self.send(request).and_then(|response| {
+            response_result!(response, HistoryReadResponse, |r| r.results.unwrap_or_default())
+        })
+    }
+
+    /// Writes values to nodes by sending a [`WriteRequest`] to the server, see [`Session::write()`].
+    ///
+    /// [`WriteRequest`]: ./struct.WriteRequest.html
+    /// [`Session::write()`]: ../session/struct.Session.html#method.write
+    pub fn write(&self, nodes_to_write: &[WriteValue]) -> impl Future<Item=Vec<StatusCode>, Error=StatusCode> + Send {
+        let request = if nodes_to_write.is_empty() {
+            error!("write() was not supplied with any nodes to write");
+            Err(StatusCode::BadNothingToDo)
+        } else {
+            Ok(WriteRequest {
+                request_header: self.make_request_header(),
+                nodes_to_write: Some(nodes_to_write.to_vec()),
+            })
+        };
+        self.send_checked(request).and_then(|response| {
+            response_result!(response, WriteResponse, |r| r.results.unwrap_or_default())
+        })
+    }
+
+    /// Discovers the references of nodes by sending a [`BrowseRequest`] to the server, see
+    /// [`Session::browse()`].
+    ///
+    /// [`BrowseRequest`]: ./struct.BrowseRequest.html
+    /// [`Session::browse()`]: ../session/struct.Session.html#method.browse
+    pub fn browse(&self, nodes_to_browse: &[BrowseDescription]) -> impl Future<Item=Vec<BrowseResult>, Error=StatusCode> + Send {
+        let request = if nodes_to_browse.is_empty() {
+            error!("browse, was not supplied with any nodes to browse");
+            Err(StatusCode::BadNothingToDo)
+        } else {
+            Ok(BrowseRequest {
+                request_header: self.make_request_header(),
+                view: ViewDescription {
+                    view_id: NodeId::null(),
+                    timestamp: DateTime::null(),
+                    view_version: 0,
+                },
+                requested_max_references_per_node: 1000,
+                nodes_to_browse: Some(nodes_to_browse.to_vec()),
+            })
+        };
+        self.send_checked(request).and_then(|response| {
+            response_result!(response, BrowseResponse, |r| r.results.unwrap_or_default())
+        })
+    }
+
+    /// Continues browsing from continuation points by sending a [`BrowseNextRequest`] to the
+    /// server, see [`Session::browse_next()`].
+    ///
+    /// [`BrowseNextRequest`]: ./struct.BrowseNextRequest.html
+    /// [`Session::browse_next()`]: ../session/struct.Session.html#method.browse_next
+    pub fn browse_next(&self, release_continuation_points: bool, continuation_points: &[ByteString]) -> impl Future<Item=Vec<BrowseResult>, Error=StatusCode> + Send {
+        let request = if continuation_points.is_empty() {
+            error!("browse_next, was not supplied with any continuation points");
+            Err(StatusCode::BadNothingToDo)
+        } else {
+            Ok(BrowseNextRequest {
+                request_header: self.make_request_header(),
+                continuation_points: Some(continuation_points.to_vec()),
+                release_continuation_points,
+            })
+        };
+        self.send_checked(request).and_then(|response| {
+            response_result!(response, BrowseNextResponse, |r| r.results.unwrap_or_default())
+        })
+    }
+
+    /// Registers nodes for efficient access by sending a [`RegisterNodesRequest`] to the server,
+    /// see [`Session::register_nodes()`].
+    ///
+    /// [`RegisterNodesRequest`]: ./struct.RegisterNodesRequest.html
+    /// [`Session::register_nodes()`]: ../session/struct.Session.html#method.register_nodes
+    pub fn register_nodes(&self, nodes_to_register: &[NodeId]) -> impl Future<Item=Vec<NodeId>, Error=StatusCode> + Send {
+        let request = if nodes_to_register.is_empty() {
+            error!("register_nodes, was not supplied with any nodes to register");
+            Err(StatusCode::BadNothingToDo)
+        } else {
+            Ok(RegisterNodesRequest {
+                request_header: self.make_request_header(),
+                nodes_to_register: Some(nodes_to_register.to_vec()),
+            })
+        };
+        self.send_checked(request).and_then(|response| {
+            response_result!(response, RegisterNodesResponse, |r| r.registered_node_ids.unwrap_or_default())
+        })
+    }
+
+    /// Releases registered nodes by sending an [`UnregisterNodesRequest`] to the server, see
+    /// [`Session::unregister_nodes()`].
+    ///
+    /// [`UnregisterNodesRequest`]: ./struct.UnregisterNodesRequest.html
+    /// [`Session::unregister_nodes()`]: ../session/struct.Session.html#method.unregister_nodes
+    pub fn unregister_nodes(&self, nodes_to_unregister: &[NodeId]) -> impl Future<Item=(), Error=StatusCode> + Send {
+        let request = if nodes_to_unregister.is_empty() {
+            error!("unregister_nodes, was not supplied with any nodes to unregister");
+            Err(StatusCode::BadNothingToDo)
+        } else {
+            Ok(UnregisterNodesRequest {
+                request_header: self.make_request_header(),
+                nodes_to_unregister: Some(nodes_to_unregister.to_vec()),
+            })
+        };
+        self.send_checked(request).and_then(|response| {
+            response_result!(response, UnregisterNodesResponse, |_| ())
+        })
+    }
+
+    /// Calls a method by sending a [`CallRequest`] to the server, see [`Session::call()`].
+    ///
+    /// [`CallRequest`]: ./struct.CallRequest.html
+    /// [`Session::call()`]: ../session/struct.Session.html#method.call
+    pub fn call<T>(&self, method: T) -> impl Future<Item=CallMethodResult, Error=StatusCode> + Send where T: Into<CallMethodRequest> {
+        let request = CallRequest {
+            request_header: self.make_request_header(),
+            methods_to_call: Some(vec![method.into()]),
+        };
+        self.send(request).and_then(|response| {
+            let mut results = response_result!(response, CallResponse, |r| r.results.unwrap_or_default())?;
+            if results.len() != 1 {
+                error!("call(), expecting a result from the call to the server, got {} results", results.len());
+                Err(StatusCode::BadUnexpectedError)
+            } else {
+                Ok(results.remove(0))
+            }
+        })
+    }
+
+    /// Translates browse paths to node ids by sending a [`TranslateBrowsePathsToNodeIdsRequest`]
+    /// to the server, see [`Session::translate_browse_paths_to_node_ids()`].
+    ///
+    /// [`TranslateBrowsePathsToNodeIdsRequest`]: ./struct.TranslateBrowsePathsToNodeIdsRequest.html
+    /// [`Session::translate_browse_paths_to_node_ids()`]: ../session/struct.Session.html#method.translate_browse_paths_to_node_ids
+    pub fn translate_browse_paths_to_node_ids(&self, browse_paths: &[BrowsePath]) -> impl Future<Item=Vec<BrowsePathResult>, Error=StatusCode> + Send {
+        let request = if browse_paths.is_empty() {
+            error!("translate_browse_paths_to_node_ids, was not supplied with any browse paths");
+            Err(StatusCode::BadNothingToDo)
+        } else {
+            Ok(TranslateBrowsePathsToNodeIdsRequest {
+                request_header: self.make_request_header(),
+                browse_paths: Some(browse_paths.to_vec()),
+            })
+        };
+        self.send_checked(request).and_then(|response| {
+            response_result!(response, TranslateBrowsePathsToNodeIdsResponse, |r| r.results.unwrap_or_default())
+        })
+    }
+
+    /// Creates a subscription by sending a [`CreateSubscriptionRequest`] to the server, see
+    /// [`Session::create_subscription()`]. The future completes with the id of the subscription,
+    /// which is then published like those that the `Session` creates.
+    ///
+    /// [`CreateSubscriptionRequest`]: ./struct.CreateSubscriptionRequest.html
+    /// [`Session::create_subscription()`]: ../session/struct.Session.html#method.create_subscription
+    pub fn create_subscription<CB>(&self, params: SubscriptionParams, callback: CB) -> impl Future<Item=u32, Error=StatusCode> + Send
+        where CB: OnSubscriptionNotification + Send + Sync + 'static {
+        let SubscriptionParams {
+            publishing_interval, lifetime_count, max_keep_alive_count, max_notifications_per_publish, priority, publishing_enabled
+        } = params;
+        let request = CreateSubscriptionRequest {
+            request_header: self.make_request_header(),
+            requested_publishing_interval: publishing_interval,
+            requested_lifetime_count: lifetime_count,
+            requested_max_keep_alive_count: max_keep_alive_count,
+            max_notifications_per_publish,
+            publishing_enabled,
+            priority,
+        };
+        let callback: Arc<Mutex<dyn OnSubscriptionNotification + Send + Sync>> = Arc::new(Mutex::new(callback));
+        let subscription_state = self.subscription_state.clone();
+        let timer_command_queue = self.timer_command_queue.clone();
+        self.send(request).and_then(move |response| {
+            let response = response_result!(response, CreateSubscriptionResponse, |r| r)?;
+            let subscription_id = response.subscription_id;
+            let subscription = Subscription::new(subscription_id, response.revised_publishing_interval,
+                                                 response.revised_lifetime_count,
+                                                 response.revised_max_keep_alive_count,
+                                                 max_notifications_per_publish,
+                                                 publishing_enabled,
+                                                 priority,
+                                                 callback);
+            {
+                let mut subscription_state = trace_write_lock_unwrap!(subscription_state);
+                subscription_state.add_subscription(subscription);
+            }
+            let _ = timer_command_queue.unbounded_send(SubscriptionTimerCommand::CreateTimer(subscription_id));
+            debug!("create_subscription, created a subscription with id {}", subscription_id);
+            Ok(subscription_id)
+        })
+    }
+
+    /// Modifies a subscription by sending a [`ModifySubscriptionRequest`] to the server, see
+    /// [`Session::modify_subscription()`].
+    ///
+    /// [`ModifySubscriptionRequest`]: ./struct.ModifySubscriptionRequest.html
+    /// [`Session::modify_subscription()`]: ../session/struct.Session.html#method.modify_subscription
+    pub fn modify_subscription(&self, subscription_id: u32, publishing_interval: f64, lifetime_count: u32, max_keep_alive_count: u32, max_notifications_per_publish: u32, priority: u8) -> impl Future<Item=(), Error=StatusCode> + Send {
+        let request = self.check_subscription(subscription_id, "modify_subscription").map(|_| {
+            ModifySubscriptionRequest {
+                request_header: self.make_request_header(),
+                subscription_id,
+                requested_publishing_interval: publishing_interval,
+                requested_lifetime_count: lifetime_count,
+                requested_max_keep_alive_count: max_keep_alive_count,
+                max_notifications_per_publish,
+                priority,
+            }
+        });
+        let subscription_state = self.subscription_state.clone();
+        self.send_checked(request).and_then(move |response| {
+            let response = response_result!(response, ModifySubscriptionResponse, |r| r)?;
+            let mut subscription_state = trace_write_lock_unwrap!(subscription_state);
+            subscription_state.modify_subscription(subscription_id,
+                                                   response.revised_publishing_interval,
+                                                   response.revised_lifetime_count,
+                                                   response.revised_max_keep_alive_count,
+                                                   max_notifications_per_publish,
+                                                   priority);
+            Ok(())
+        })
+    }
+
+    /// Enables or disables publishing of subscriptions by sending a [`SetPublishingModeRequest`]
+    /// to the server, see [`Session::set_publishing_mode()`].
+    ///
+    /// [`SetPublishingModeRequest`]: ./struct.SetPublishingModeRequest.html
+    /// [`Session::set_publishing_mode()`]: ../session/struct.Session.html#method.set_publishing_mode
+    pub fn set_publishing_mode(&self, subscription_ids: &[u32], publishing_enabled: bool) -> impl Future<Item=Vec<StatusCode>, Error=StatusCode> + Send {
+        let request = if subscription_ids.is_empty() {
+            error!("set_publishing_mode, no subscription ids were provided");
+            Err(StatusCode::BadNothingToDo)
+        } else {
+            Ok(SetPublishingModeRequest {
+                request_header: self.make_request_header(),
+                publishing_enabled,
+                subscription_ids: Some(subscription_ids.to_vec()),
+            })
+        };
+        let subscription_ids = subscription_ids.to_vec();
+        let subscription_state = self.subscription_state.clone();
+        self.send_checked(request).and_then(move |response| {
+            let results = response_result!(response, SetPublishingModeResponse, |r| r.results.unwrap_or_default())?;
+            let mut subscription_state = trace_write_lock_unwrap!(subscription_state);
+            subscription_state.set_publishing_mode(&subscription_ids, publishing_enabled);
+            Ok(results)
+        })
+    }
+
+    /// Deletes subscriptions by sending a [`DeleteSubscriptionsRequest`] to the server, see
+    /// [`Session::delete_subscriptions()`].
+    ///
+    /// [`DeleteSubscriptionsRequest`]: ./struct.DeleteSubscriptionsRequest.html
+    /// [`Session::delete_subscriptions()`]: ../session/struct.Session.html#method.delete_subscriptions
+    pub fn delete_subscriptions(&self, subscription_ids: &[u32]) -> impl Future<Item=Vec<StatusCode>, Error=StatusCode> + Send {
+        let request = if subscription_ids.is_empty() {
+            error!("delete_subscriptions, no subscription ids were provided");
+            Err(StatusCode::BadNothingToDo)
+        } else {
+            Ok(DeleteSubscriptionsRequest {
+                request_header: self.make_request_header(),
+                subscription_ids: Some(subscription_ids.to_vec()),
+            })
+        };
+        let subscription_ids = subscription_ids.to_vec();
+        let subscription_state = self.subscription_state.clone();
+        self.send_checked(request).and_then(move |response| {
+            let results = response_result!(response, DeleteSubscriptionsResponse, |r| r.results.unwrap_or_default())?;
+            let mut subscription_state = trace_write_lock_unwrap!(subscription_state);
+            subscription_ids.iter().for_each(|id| {
+                let _ = subscription_state.delete_subscription(*id);
+            });
+            Ok(results)
+        })
+    }
+
+    /// Creates monitored items in a subscription by sending a [`CreateMonitoredItemsRequest`] to
+    /// the server, see [`Session::create_monitored_items()`].
+    ///
+    /// [`CreateMonitoredItemsRequest`]: ./struct.CreateMonitoredItemsRequest.html
+    /// [`Session::create_monitored_items()`]: ../session/struct.Session.html#method.create_monitored_items
+    pub fn create_monitored_items(&self, subscription_id: u32, timestamps_to_return: TimestampsToReturn, items_to_create: &[MonitoredItemCreateRequest]) -> impl Future<Item=Vec<MonitoredItemCreateResult>, Error=StatusCode> + Send {
+        // Assign each item a unique client handle if it does not have one
+        let mut items_to_create = items_to_create.to_vec();
+        {
+            let mut session_state = trace_write_lock_unwrap!(self.session_state);
+            items_to_create.iter_mut()
+                .filter(|i| i.requested_parameters.client_handle == 0)
+                .for_each(|i| i.requested_parameters.client_handle = session_state.next_monitored_item_handle());
+        }
+        let request = self.check_subscription(subscription_id, "create_monitored_items").and_then(|_| {
+            if items_to_create.is_empty() {
+                error!("create_monitored_items, called with no items to create");
+                Err(StatusCode::BadNothingToDo)
+            } else {
+                Ok(CreateMonitoredItemsRequest {
+                    request_header: self.make_request_header(),
+                    subscription_id,
+                    timestamps_to_return,
+                    items_to_create: Some(items_to_create.clone()),
+                })
+            }
+        });
+        let subscription_state = self.subscription_state.clone();
+        self.send_checked(request).and_then(move |response| {
+            let results = response_result!(response, CreateMonitoredItemsResponse, |r| r.results.unwrap_or_default())?;
+            let created = items_to_create.iter()
+                .zip(results.iter())
+                .map(|(i, r)| subscription::CreateMonitoredItem {
+                    id: r.monitored_item_id,
+                    client_handle: i.requested_parameters.client_handle,
+                    discard_oldest: i.requested_parameters.discard_oldest,
+                    item_to_monitor: i.item_to_monitor.clone(),
+                    monitoring_mode: i.monitoring_mode,
+                    queue_size: r.revised_queue_size,
+                    sampling_interval: r.revised_sampling_interval,
+                    filter: i.requested_parameters.filter.clone(),
+                })
+                .collect::<Vec<_>>();
+            let mut subscription_state = trace_write_lock_unwrap!(subscription_state);
+            subscription_state.insert_monitored_items(subscription_id, &created);
+            Ok(results)
+        })
+    }
+
+    /// Modifies monitored items of a subscription by sending a [`ModifyMonitoredItemsRequest`] to
+    /// the server, see [`Session::modify_monitored_items()`].
+    ///
+    /// [`ModifyMonitoredItemsRequest`]: ./struct.ModifyMonitoredItemsRequest.html
+    /// [`Session::modify_monitored_items()`]: ../session/struct.Session.html#method.modify_monitored_items
+    pub fn modify_monitored_items(&self, subscription_id: u32, timestamps_to_return: TimestampsToReturn, items_to_modify: &[MonitoredItemModifyRequest]) -> impl Future<Item=Vec<MonitoredItemModifyResult>, Error=StatusCode> + Send {
+        let request = self.check_subscription(subscription_id, "modify_monitored_items").and_then(|_| {
+            if items_to_modify.is_empty() {
+                error!("modify_monitored_items, called with no items to modify");
+                Err(StatusCode::BadNothingToDo)
+            } else {
+                Ok(ModifyMonitoredItemsRequest {
+                    request_header: self.make_request_header(),
+                    subscription_id,
+                    timestamps_to_return,
+                    items_to_modify: Some(items_to_modify.to_vec()),
+                })
+            }
+        });
+        let items_to_modify = items_to_modify.to_vec();
+        let subscription_state = self.subscription_state.clone();
+        self.send_checked(request).and_then(move |response| {
+            let results = response_result!(response, ModifyMonitoredItemsResponse, |r| r.results.unwrap_or_default())?;
+            let modified = items_to_modify.iter()
+                .zip(results.iter())
+                .map(|(i, r)| subscription::ModifyMonitoredItem {
+                    id: i.monitored_item_id,
+                    queue_size: r.revised_queue_size,
+                    sampling_interval: r.revised_sampling_interval,
+                    filter: i.requested_parameters.filter.clone(),
+                })
+                .collect::<Vec<_>>();
+            let mut subscription_state = trace_write_lock_unwrap!(subscription_state);
+            subscription_state.modify_monitored_items(subscription_id, &modified);
+            Ok(results)
+        })
+    }
+
+    /// Sets the monitoring mode of monitored items by sending a [`SetMonitoringModeRequest`] to
+    /// the server, see [`Session::set_monitoring_mode()`].
+    ///
+    /// [`SetMonitoringModeRequest`]: ./struct.SetMonitoringModeRequest.html
+    /// [`Session::set_monitoring_mode()`]: ../session/struct.Session.html#method.set_monitoring_mode
+    pub fn set_monitoring_mode(&self, subscription_id: u32, monitoring_mode: MonitoringMode, monitored_item_ids: &[u32]) -> impl Future<Item=Vec<StatusCode>, Error=StatusCode> + Send {
+        let request = if monitored_item_ids.is_empty() {
+            error!("set_monitoring_mode, called with nothing to do");
+            Err(StatusCode::BadNothingToDo)
+        } else {
+            Ok(SetMonitoringModeRequest {
+                request_header: self.make_request_header(),
+                subscription_id,
+                monitoring_mode,
+                monitored_item_ids: Some(monitored_item_ids.to_vec()),
+            })
+        };
+        self.send_checked(request).and_then(|response| {
+            response_result!(response, SetMonitoringModeResponse, |r| r.results.unwrap_or_default())
+        })
+    }
+
+    /// Deletes monitored items from a subscription by sending a [`DeleteMonitoredItemsRequest`] to
+    /// the server, see [`Session::delete_monitored_items()`].
+    ///
+    /// [`DeleteMonitoredItemsRequest`]: ./struct.DeleteMonitoredItemsRequest.html
+    /// [`Session::delete_monitored_items()`]: ../session/struct.Session.html#method.delete_monitored_items
+    pub fn delete_monitored_items(&self, subscription_id: u32, items_to_delete: &[u32]) -> impl Future<Item=Vec<StatusCode>, Error=StatusCode> + Send {
+        let request = self.check_subscription(subscription_id, "delete_monitored_items").and_then(|_| {
+            if items_to_delete.is_empty() {
+                error!("delete_monitored_items, called with no items to delete");
+                Err(StatusCode::BadNothingToDo)
+            } else {
+                Ok(DeleteMonitoredItemsRequest {
+                    request_header: self.make_request_header(),
+                    subscription_id,
+                    monitored_item_ids: Some(items_to_delete.to_vec()),
+                })
+            }
+        });
+        let items_to_delete = items_to_delete.to_vec();
+        let subscription_state = self.subscription_state.clone();
+        self.send_checked(request).and_then(move |response| {
+            let results = response_result!(response, DeleteMonitoredItemsResponse, |r| r.results.unwrap_or_default())?;
+            let mut subscription_state = trace_write_lock_unwrap!(subscription_state);
+            subscription_state.delete_monitored_items(subscription_id, &items_to_delete);
+            Ok(results)
+        })
+    }
+}
@@ -0,0 +1,407 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! Recursive browsing of a server's address space. A [`Browser`] walks the hierarchy from a start
+//! node, following continuation points and skipping nodes it has already visited, and returns
+//! the nodes it finds as a tree or one at a time from an iterator.
+//!
+//! [`Browser`]: ./struct.Browser.html
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use futures::{Future, future};
+
+use opcua_types::{
+    *,
+    node_ids::ReferenceTypeId,
+    status_code::StatusCode,
+};
+
+use crate::async_session::AsyncSession;
+
+/// A node found by a [`Browser`].
+///
+/// [`Browser`]: ./struct.Browser.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct BrowsedNode {
+    /// The id of the node
+    pub node_id: NodeId,
+    /// The node it was reached from, or `None` for the start node
+    pub parent_node_id: Option<NodeId>,
+    /// The reference from the parent to the node, or `None` for the start node
+    pub reference: Option<ReferenceDescription>,
+    /// The number of references from the start node, which has a depth of 0
+    pub depth: usize,
+    /// The values of the attributes the browser was asked to read, in the same order
+    pub values: Vec<DataValue>,
+}
+
+/// A node and the nodes found beneath it by a [`Browser`].
+///
+/// [`Browser`]: ./struct.Browser.html
+#[derive(Debug, Clone, PartialEq)]
+pub struct BrowseTree {
+    pub node: BrowsedNode,
+    pub children: Vec<BrowseTree>,
+}
+
+impl BrowseTree {
+    /// Builds the tree from nodes in the order they were browsed, where the first node is the
+    /// start node and every other node comes after its parent.
+    pub(crate) fn from_nodes(nodes: Vec<BrowsedNode>) -> Option<BrowseTree> {
+        let mut children: HashMap<NodeId, Vec<BrowsedNode>> = HashMap::new();
+        let mut nodes = nodes.into_iter();
+        let root = nodes.next()?;
+        nodes.for_each(|node| {
+            if let Some(ref parent_node_id) = node.parent_node_id {
+                children.entry(parent_node_id.clone()).or_default().push(node);
+            }
+        });
+        Some(Self::assemble(root, &mut children))
+    }
+
+    fn assemble(node: BrowsedNode, children: &mut HashMap<NodeId, Vec<BrowsedNode>>) -> BrowseTree {
+        let child_nodes = children.remove(&node.node_id).unwrap_or_default();
+        BrowseTree {
+            node,
+            children: child_nodes.into_iter().map(|child| Self::assemble(child, children)).collect(),
+        }
+    }
+
+    /// Counts the nodes in the tree, including this one
+    pub fn node_count(&self) -> usize {
+        1 + self.children.iter().map(|c| c.node_count()).sum::<usize>()
+    }
+
+    /// Finds the node with the id in the tree
+    pub fn find(&self, node_id: &NodeId) -> Option<&BrowseTree> {
+        if self.node.node_id == *node_id {
+            Some(self)
+        } else {
+            self.children.iter().find_map(|c| c.find(node_id))
+        }
+    }
+}
+
+/// Walks the address space of a server from a start node. By default it follows hierarchical
+/// references forwards to any depth, with up to 4 browse requests in flight at a time.
+///
+/// A node that is reachable by more than one path is only returned once, for the first path
+/// that is browsed, so cycles in the address space do not matter. Nodes on other servers are
+/// returned but not browsed.
+///
+/// The browser sends its requests through an [`AsyncSession`], so the session must be
+/// connected while it runs. The browser blocks the calling thread while it waits for responses.
+///
+/// ```no_run
+/// use opcua_client::prelude::*;
+///
+/// fn print_objects(session: &Session) -> Result<(), StatusCode> {
+///     let browser = session.browser()
+///         .node_class_mask(NodeClass::Object as u32 | NodeClass::Variable as u32)
+///         .max_depth(3)
+///         .read_attributes(&[AttributeId::Value]);
+///     for node in browser.browse_iter(ObjectId::ObjectsFolder) {
+///         let node = node?;
+///         println!("{} {} = {:?}", node.depth, node.node_id, node.values[0].value);
+///     }
+///     Ok(())
+/// }
+/// ```
+///
+/// [`AsyncSession`]: ../async_session/struct.AsyncSession.html
+#[derive(Clone)]
+pub struct Browser {
+    session: AsyncSession,
+    browse_direction: BrowseDirection,
+    reference_type_id: NodeId,
+    include_subtypes: bool,
+    node_class_mask: u32,
+    max_depth: Option<usize>,
+    concurrency: usize,
+    nodes_per_request: usize,
+    attributes: Vec<AttributeId>,
+}
+
+impl Browser {
+    /// The most nodes browsed or read in one request
+    const DEFAULT_NODES_PER_REQUEST: usize = 100;
+
+    /// Creates a browser that sends its requests through the session
+    pub fn new(session: AsyncSession) -> Browser {
+        Browser {
+            session,
+            browse_direction: BrowseDirection::Forward,
+            reference_type_id: ReferenceTypeId::HierarchicalReferences.into(),
+            include_subtypes: true,
+            node_class_mask: 0,
+            max_depth: None,
+            concurrency: 4,
+            nodes_per_request: Self::DEFAULT_NODES_PER_REQUEST,
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Sets the direction of the references to follow
+    pub fn browse_direction(mut self, browse_direction: BrowseDirection) -> Self {
+        self.browse_direction = browse_direction;
+        self
+    }
+
+    /// Sets the type of the references to follow and whether its subtypes are followed too
+    pub fn reference_type<T>(mut self, reference_type_id: T, include_subtypes: bool) -> Self where T: Into<NodeId> {
+        self.reference_type_id = reference_type_id.into();
+        self.include_subtypes = include_subtypes;
+        self
+    }
+
+    /// Sets the classes of nodes to follow as a mask of `NodeClass` values, where 0 means every
+    /// class. The server only returns references to nodes in the mask, so nodes beneath a node
+    /// of another class are not found either.
+    pub fn node_class_mask(mut self, node_class_mask: u32) -> Self {
+        self.node_class_mask = node_class_mask;
+        self
+    }
+
+    /// Sets the depth of the deepest nodes to return, where the start node has a depth of 0
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Sets the number of requests that may be in flight at a time
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Sets the number of nodes browsed or read by each request
+    pub fn nodes_per_request(mut self, nodes_per_request: usize) -> Self {
+        self.nodes_per_request = nodes_per_request.max(1);
+        self
+    }
+
+    /// Sets the attributes to read from each node found. Their values are in the `values` of
+    /// the [`BrowsedNode`].
+    ///
+    /// [`BrowsedNode`]: ./struct.BrowsedNode.html
+    pub fn read_attributes(mut self, attributes: &[AttributeId]) -> Self {
+        self.attributes = attributes.to_vec();
+        self
+    }
+
+    /// Returns an iterator over the nodes from the start node, which comes first, breadth first.
+    /// Nodes are browsed in batches as the iterator is advanced. If a request fails, the
+    /// iterator returns the error and then ends.
+    pub fn browse_iter<T>(&self, start_node_id: T) -> BrowseIter<'_> where T: Into<NodeId> {
+        BrowseIter::new(self, start_node_id.into())
+    }
+
+    /// Browses everything from the start node and returns the nodes found as a tree
+    pub fn browse_tree<T>(&self, start_node_id: T) -> Result<BrowseTree, StatusCode> where T: Into<NodeId> {
+        let nodes = self.browse_iter(start_node_id).collect::<Result<Vec<_>, StatusCode>>()?;
+        BrowseTree::from_nodes(nodes).ok_or(StatusCode::BadUnexpectedError)
+    }
+
+    fn browse_description(&self, node_id: &NodeId) -> BrowseDescription {
+        BrowseDescription {
+            node_id: node_id.clone(),
+            browse_direction: self.browse_direction,
+            reference_type_id: self.reference_type_id.clone(),
+            include_subtypes: self.include_subtypes,
+            node_class_mask: self.node_class_mask,
+            result_mask: BrowseDescriptionResultMask::all().bits(),
+        }
+    }
+
+    /// Browses the nodes with requests in flight together and returns the references of each
+    /// node, following continuation points until every node is complete. A node that cannot be
+    /// browsed has no references.
+    fn browse_nodes(&self, node_ids: &[NodeId]) -> Result<Vec<Vec<ReferenceDescription>>, StatusCode> {
+        let requests = node_ids.chunks(self.nodes_per_request).map(|chunk| {
+            let nodes_to_browse = chunk.iter().map(|node_id| self.browse_description(node_id)).collect::<Vec<_>>();
+            self.session.browse(&nodes_to_browse)
+        }).collect::<Vec<_>>();
+        let results = self.wait_all(requests)?;
+
+        let mut references = vec![Vec::new(); node_ids.len()];
+        let mut continuation_points = Vec::new();
+        self.take_results(node_ids, results, (0..node_ids.len()).collect(), &mut references, &mut continuation_points);
+
+        while !continuation_points.is_empty() {
+            let pending: Vec<(usize, ByteString)> = std::mem::take(&mut continuation_points);
+            let requests = pending.chunks(self.nodes_per_request).map(|chunk| {
+                let continuation_points = chunk.iter().map(|(_, c)| c.clone()).collect::<Vec<_>>();
+                self.session.browse_next(false, &continuation_points)
+            }).collect::<Vec<_>>();
+            let results = self.wait_all(requests)?;
+            let indexes = pending.into_iter().map(|(i, _)| i).collect();
+            self.take_results(node_ids, results, indexes, &mut references, &mut continuation_points);
+        }
+        Ok(references)
+    }
+
+    /// Adds the references from browse results to the nodes they are for, and saves any
+    /// continuation points to carry on from
+    fn take_results(&self, node_ids: &[NodeId], results: Vec<BrowseResult>, indexes: Vec<usize>, references: &mut [Vec<ReferenceDescription>], continuation_points: &mut Vec<(usize, ByteString)>) {
+        results.into_iter().zip(indexes).for_each(|(result, i)| {
+            if result.status_code.is_bad() {
+                debug!("Browse of node {} failed, status = {}", node_ids[i], result.status_code);
+                return;
+            }
+            if let Some(r) = result.references {
+                references[i].extend(r);
+            }
+            if !result.continuation_point.is_null_or_empty() {
+                continuation_points.push((i, result.continuation_point));
+            }
+        });
+    }
+
+    /// Reads the attributes of the nodes with requests in flight together, filling in their values
+    fn read_values(&self, nodes: &mut [BrowsedNode]) -> Result<(), StatusCode> {
+        if self.attributes.is_empty() || nodes.is_empty() {
+            return Ok(());
+        }
+        let nodes_to_read = nodes.iter().flat_map(|node| {
+            self.attributes.iter().map(move |attribute_id| ReadValueId {
+                node_id: node.node_id.clone(),
+                attribute_id: *attribute_id as u32,
+                index_range: UAString::null(),
+                data_encoding: QualifiedName::null(),
+            })
+        }).collect::<Vec<_>>();
+        let requests = nodes_to_read.chunks(self.nodes_per_request)
+            .map(|chunk| self.session.read(chunk))
+            .collect::<Vec<_>>();
+        let mut values = self.wait_all(requests)?.into_iter();
+        for node in nodes.iter_mut() {
+            node.values = values.by_ref().take(self.attributes.len()).collect();
+            if node.values.len() != self.attributes.len() {
+                error!("Server returned fewer values than attributes were read");
+                return Err(StatusCode::BadUnexpectedError);
+            }
+        }
+        Ok(())
+    }
+
+    /// Waits for requests, sending no more than the concurrency limit at a time, and joins
+    /// their results in order
+    fn wait_all<F, T>(&self, requests: Vec<F>) -> Result<Vec<T>, StatusCode> where F: Future<Item=Vec<T>, Error=StatusCode> {
+        let mut results = Vec::new();
+        let mut requests = requests.into_iter().peekable();
+        while requests.peek().is_some() {
+            let in_flight = requests.by_ref().take(self.concurrency).collect::<Vec<_>>();
+            future::join_all(in_flight).wait()?.into_iter().for_each(|r| results.extend(r));
+        }
+        Ok(results)
+    }
+}
+
+/// An iterator over the nodes found by a [`Browser`], created by `Browser::browse_iter()`.
+///
+/// [`Browser`]: ./struct.Browser.html
+pub struct BrowseIter<'a> {
+    browser: &'a Browser,
+    /// The start node, until it has been returned
+    start_node_id: Option<NodeId>,
+    /// Every node found so far
+    visited: HashSet<NodeId>,
+    /// Nodes waiting to be browsed and their depth
+    to_browse: VecDeque<(NodeId, usize)>,
+    /// Nodes found but not yet returned
+    found: VecDeque<BrowsedNode>,
+}
+
+impl<'a> BrowseIter<'a> {
+    fn new(browser: &'a Browser, start_node_id: NodeId) -> BrowseIter<'a> {
+        BrowseIter {
+            browser,
+            start_node_id: Some(start_node_id),
+            visited: HashSet::new(),
+            to_browse: VecDeque::new(),
+            found: VecDeque::new(),
+        }
+    }
+
+    fn can_browse(&self, depth: usize) -> bool {
+        self.browser.max_depth.map(|max_depth| depth < max_depth).unwrap_or(true)
+    }
+
+    fn start(&mut self, start_node_id: NodeId) -> Result<(), StatusCode> {
+        let mut start = vec![BrowsedNode {
+            node_id: start_node_id.clone(),
+            parent_node_id: None,
+            reference: None,
+            depth: 0,
+            values: Vec::new(),
+        }];
+        self.browser.read_values(&mut start)?;
+        self.visited.insert(start_node_id.clone());
+        if self.can_browse(0) {
+            self.to_browse.push_back((start_node_id, 0));
+        }
+        self.found.extend(start);
+        Ok(())
+    }
+
+    /// Browses the next batch of nodes, i.e. as many as fit in the requests that may be in
+    /// flight at once
+    fn browse_next_batch(&mut self) -> Result<(), StatusCode> {
+        let batch_size = self.browser.concurrency * self.browser.nodes_per_request;
+        let batch = self.to_browse.drain(..batch_size.min(self.to_browse.len())).collect::<Vec<_>>();
+        let node_ids = batch.iter().map(|(node_id, _)| node_id.clone()).collect::<Vec<_>>();
+        let references = self.browser.browse_nodes(&node_ids)?;
+
+        let mut found = Vec::new();
+        for ((parent_node_id, depth), references) in batch.into_iter().zip(references) {
+            for reference in references {
+                let target = &reference.node_id;
+                let local = target.server_index == 0 && target.namespace_uri.is_null();
+                if !self.visited.insert(target.node_id.clone()) {
+                    continue;
+                }
+                if local && self.can_browse(depth + 1) {
+                    self.to_browse.push_back((target.node_id.clone(), depth + 1));
+                }
+                found.push(BrowsedNode {
+                    node_id: target.node_id.clone(),
+                    parent_node_id: Some(parent_node_id.clone()),
+                    reference: Some(reference),
+                    depth: depth + 1,
+                    values: Vec::new(),
+                });
+            }
+        }
+        self.browser.read_values(&mut found)?;
+        self.found.extend(found);
+        Ok(())
+    }
+}
+
+impl<'a> Iterator for BrowseIter<'a> {
+    type Item = Result<BrowsedNode, StatusCode>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = if let Some(start_node_id) = self.start_node_id.take() {
+            self.start(start_node_id)
+        } else {
+            Ok(())
+        };
+        if let Err(status_code) = result {
+            return Some(Err(status_code));
+        }
+        loop {
+            if let Some(node) = self.found.pop_front() {
+                return Some(Ok(node));
+            } else if self.to_browse.is_empty() {
+                return None;
+            } else if let Err(status_code) = self.browse_next_batch() {
+                // Stop after the error
+                self.to_browse.clear();
+                return Some(Err(status_code));
+            }
+        }
+    }
+}
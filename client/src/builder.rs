@@ -6,7 +6,7 @@ use std::path::PathBuf;
 
 use opcua_core::config::Config;
 
-use crate::{client::*, config::*};
+use crate::{client::*, config::*, session_retry::RetryBackoff};
 
 /// The `ClientBuilder` is a builder for producing a [`Client`]. It is an alternative to constructing
 /// a [`ClientConfig`] from file or from scratch.
@@ -198,11 +198,44 @@ impl ClientBuilder {
         self
     }
 
+    /// Sets how the retry interval grows as attempts to reconnect fail
+    pub fn session_retry_backoff(mut self, session_retry_backoff: RetryBackoff) -> Self {
+        self.config.session_retry_backoff = session_retry_backoff;
+        self
+    }
+
+    /// Sets a retry limit, interval and backoff for the first connection to the server, which
+    /// otherwise retries the same way as reconnecting
+    pub fn connect_retry(mut self, retry_limit: i32, retry_interval: u32, backoff: RetryBackoff) -> Self {
+        if retry_limit < 0 && retry_limit != -1 {
+            panic!("Connect retry limit must be -1, 0 or a positive number");
+        }
+        self.config.connect_retry = Some(ConnectRetry {
+            retry_limit,
+            retry_interval,
+            backoff,
+        });
+        self
+    }
+
     /// Sets the session timeout period
     pub fn session_timeout(mut self, session_timeout: u32) -> Self {
         self.config.session_timeout = session_timeout;
         self
     }
+
+    /// Sets the interval in milliseconds between keep-alive reads of the server's state. A value
+    /// of 0 turns the keep-alive off.
+    pub fn keep_alive_interval(mut self, keep_alive_interval: u32) -> Self {
+        self.config.keep_alive_interval = keep_alive_interval;
+        self
+    }
+
+    /// Sets the number of keep-alives that can be missed before the connection is dropped
+    pub fn keep_alive_max_missed(mut self, keep_alive_max_missed: u32) -> Self {
+        self.config.keep_alive_max_missed = keep_alive_max_missed;
+        self
+    }
 }
 
 #[test]
@@ -223,7 +256,11 @@ fn client_builder() {
         .default_endpoint("http://default")
         .session_retry_interval(1234)
         .session_retry_limit(999)
+        .session_retry_backoff(RetryBackoff::Exponential { max_interval: 60000 })
+        .connect_retry(3, 500, RetryBackoff::DecorrelatedJitter { max_interval: 5000 })
         .session_timeout(777)
+        .keep_alive_interval(2000)
+        .keep_alive_max_missed(5)
         // TODO user tokens, endpoints
         ;
 
@@ -241,5 +278,9 @@ fn client_builder() {
     assert_eq!(c.default_endpoint, "http://default");
     assert_eq!(c.session_retry_interval, 1234);
     assert_eq!(c.session_retry_limit, 999);
+    assert_eq!(c.session_retry_backoff, RetryBackoff::Exponential { max_interval: 60000 });
+    assert_eq!(c.connect_retry, Some(ConnectRetry { retry_limit: 3, retry_interval: 500, backoff: RetryBackoff::DecorrelatedJitter { max_interval: 5000 } }));
     assert_eq!(c.session_timeout, 777);
+    assert_eq!(c.keep_alive_interval, 2000);
+    assert_eq!(c.keep_alive_max_missed, 5);
 }
@@ -15,11 +15,14 @@
 use std::fmt;
 
 use opcua_types::{
-    service_types::EventNotificationList,
+    service_types::{EventNotificationList, ServerState},
     status_code::StatusCode,
 };
 
-use crate::subscription::MonitoredItem;
+use crate::{
+    connection_manager::ConnectionStatus,
+    subscription::{MonitoredItem, SubscriptionRecovery},
+};
 
 /// The `OnSubscriptionNotification` trait is the callback registered along with a new subscription to
 /// receive subscription notification callbacks.
@@ -42,6 +45,21 @@ pub trait OnSubscriptionNotification {
     /// are individual `EventFieldList` structs filled from the select clause criteria from when the
     /// event was constructed. The default implementation does nothing.
     fn on_event(&mut self, _events: &EventNotificationList) {}
+
+    /// Called after the session has reconnected to the server and recovered the subscription,
+    /// saying how it was recovered. A subscription that was recreated has a new id and the ids of
+    /// its monitored items have changed. The default implementation does nothing.
+    fn on_subscription_recovered(&mut self, _subscription_id: u32, _recovery: &SubscriptionRecovery) {}
+
+    /// Called when notification messages were missed and could not be republished by the server,
+    /// so their data is lost. The sequence numbers are those of the lost messages. The default
+    /// implementation does nothing.
+    fn on_data_lost(&mut self, _subscription_id: u32, _sequence_numbers: &[u32]) {}
+
+    /// Called when the sequence number of a notification message is too far ahead of the last
+    /// one for the messages in between to be republished, so their data is lost, or the server
+    /// has started numbering again. The default implementation does nothing.
+    fn on_sequence_number_jump(&mut self, _subscription_id: u32, _last_sequence_number: u32, _sequence_number: u32) {}
 }
 
 /// The `OnConnectionStatusChange` trait can be used to register on the session to be notified
@@ -49,8 +67,25 @@ pub trait OnSubscriptionNotification {
 pub trait OnConnectionStatusChange {
     /// Called when the connection status changes from connected to disconnected or vice versa
     fn on_connection_status_change(&mut self, connected: bool);
+
+    /// Called when the keep-alive finds that the state of the server has changed, e.g. to
+    /// `Shutdown` or `Failed`, and when the state is first read. The default implementation does
+    /// nothing.
+    fn on_server_state_change(&mut self, _server_state: ServerState) {}
+
+    /// Called after each attempt to connect or reconnect to the server with `Good`, or the reason
+    /// the attempt failed. `retry_count` is the number of attempts that have failed in a row and
+    /// `retry_in` is how many milliseconds the session waits before trying again, or `None` if it
+    /// has connected or given up. The default implementation does nothing.
+    fn on_connect_attempt(&mut self, _status_code: StatusCode, _retry_count: u32, _retry_in: Option<u32>) {}
 }
 
+/// The `OnManagedConnectionStatusChange` trait can be registered on a `ConnectionManager` to be
+/// notified when the status of one of its connections changes.
+pub trait OnManagedConnectionStatusChange {
+    /// Called with the id of the connection and its new status
+    fn on_managed_connection_status_change(&mut self, id: &str, status: ConnectionStatus);
+}
 
 /// The `OnSessionClosed` trait can be used to register on a session and called to notify the client
 /// that the session has closed.
@@ -140,6 +175,29 @@ impl ConnectionStatusCallback {
     }
 }
 
+/// This is a concrete implementation of [`OnManagedConnectionStatusChange`] that calls the
+/// supplied function.
+pub struct ManagedConnectionStatusCallback {
+    cb: Box<ManagedConnectionStatusFn>,
+}
+
+type ManagedConnectionStatusFn = dyn FnMut(&str, ConnectionStatus) + Send + Sync + 'static;
+
+impl OnManagedConnectionStatusChange for ManagedConnectionStatusCallback {
+    fn on_managed_connection_status_change(&mut self, id: &str, status: ConnectionStatus) {
+        (self.cb)(id, status);
+    }
+}
+
+impl ManagedConnectionStatusCallback {
+    // Constructor
+    pub fn new<CB>(cb: CB) -> Self where CB: FnMut(&str, ConnectionStatus) + Send + Sync + 'static {
+        Self {
+            cb: Box::new(cb)
+        }
+    }
+}
+
 /// This is a concrete implementation of `OnSessionClosed` that will call the supplied
 /// function.
 pub struct SessionClosedCallback {
@@ -28,6 +28,8 @@ use opcua_types::{
 
 use crate::{
     config::{ANONYMOUS_USER_TOKEN_ID, ClientConfig, ClientEndpoint},
+    keep_alive::KeepAliveConfig,
+    reverse_connect::{ReverseConnect, ReverseConnectListener},
     session::{Session, SessionInfo},
     session_retry::SessionRetryPolicy,
 };
@@ -68,6 +70,8 @@ pub struct Client {
     certificate_store: Arc<RwLock<CertificateStore>>,
     /// The session retry policy for new sessions
     session_retry_policy: SessionRetryPolicy,
+    /// The keep-alive of new sessions
+    keep_alive_config: KeepAliveConfig,
 }
 
 impl Drop for Client {
@@ -132,7 +136,7 @@ impl Client {
 
         // The session retry policy dictates how many times to retry if connection to the server goes down
         // and on what interval
-        let session_retry_policy = match config.session_retry_limit {
+        let mut session_retry_policy = match config.session_retry_limit {
             // Try forever
             -1 => SessionRetryPolicy::infinity(session_timeout, config.session_retry_interval),
             // Never try
@@ -140,10 +144,17 @@ impl Client {
             // Try this many times
             session_retry_limit => SessionRetryPolicy::new(session_timeout, session_retry_limit as u32, config.session_retry_interval)
         };
+        session_retry_policy.set_backoff(config.session_retry_backoff);
+        if let Some(ref connect_retry) = config.connect_retry {
+            let retry_limit = if connect_retry.retry_limit == -1 { None } else { Some(connect_retry.retry_limit as u32) };
+            session_retry_policy.set_connect_retry(retry_limit, connect_retry.retry_interval, connect_retry.backoff);
+        }
+        let keep_alive_config = KeepAliveConfig::new(config.keep_alive_interval, config.keep_alive_max_missed);
 
         Client {
             config,
             session_retry_policy,
+            keep_alive_config,
             certificate_store: Arc::new(RwLock::new(certificate_store)),
         }
     }
@@ -213,13 +224,44 @@ impl Client {
     /// [`Session`]: ../session/struct.Session.html
     ///
     pub fn connect_to_endpoint<T>(&mut self, endpoint: T, user_identity_token: IdentityToken) -> Result<Arc<RwLock<Session>>, StatusCode> where T: Into<EndpointDescription> {
-        let endpoint = endpoint.into();
+        let session = self.new_session_for_endpoint(&endpoint.into(), user_identity_token, self.session_retry_policy.clone())?;
+
+        {
+            // Connect to the server
+            let mut session = session.write().unwrap();
+            if let Err(result) = session.connect_and_activate() {
+                error!("Got an error while creating the default session - {}", result);
+            }
+        }
+
+        Ok(session)
+    }
 
+    /// Asks the server for its endpoints and creates a [`Session`], without connecting it, for the
+    /// one that matches the desired endpoint. The retry policy is used to connect for the endpoints
+    /// and is given to the session.
+    ///
+    /// [`Session`]: ../session/struct.Session.html
+    ///
+    pub(crate) fn new_session_for_endpoint(&self, endpoint: &EndpointDescription, user_identity_token: IdentityToken, session_retry_policy: SessionRetryPolicy) -> Result<Arc<RwLock<Session>>, StatusCode> {
+        let server_endpoint = self.server_endpoint_for(endpoint, &session_retry_policy)?;
+
+        // Create a session
+        let session_info = (server_endpoint, user_identity_token).into();
+        Ok(Arc::new(RwLock::new(Session::new(self.application_description(), self.certificate_store.clone(), session_info, session_retry_policy, self.keep_alive_config, None))))
+    }
+
+    /// Asks the server for its endpoints and returns the one that matches the desired endpoint,
+    /// which holds the server's certificate. The retry policy is used to connect for the endpoints.
+    pub(crate) fn server_endpoint_for(&self, endpoint: &EndpointDescription, session_retry_policy: &SessionRetryPolicy) -> Result<EndpointDescription, StatusCode> {
         // Get the server endpoints
         let server_url = server_url_from_endpoint_url(endpoint.endpoint_url.as_ref())
             .map_err(|_| StatusCode::BadTcpEndpointUrlInvalid)?;
+        if !is_valid_opc_ua_url(&server_url) {
+            return Err(StatusCode::BadTcpEndpointUrlInvalid);
+        }
 
-        let server_endpoints = self.get_server_endpoints_from_url(server_url)
+        let server_endpoints = self.get_endpoints(&server_url, None, session_retry_policy)
             .map_err(|status_code| {
                 error!("Cannot get endpoints for server, error - {}", status_code);
                 status_code
@@ -235,8 +277,62 @@ impl Client {
                 status_code
             })?;
 
-        // Create a session
-        let session = self.new_session_from_info((server_endpoint, user_identity_token)).unwrap();
+        if !is_valid_opc_ua_url(server_endpoint.endpoint_url.as_ref()) {
+            error!("Endpoint url {}, is not a valid / supported url", server_endpoint.endpoint_url);
+            return Err(StatusCode::BadTcpEndpointUrlInvalid);
+        }
+        Ok(server_endpoint)
+    }
+
+    /// The retry policy that new sessions are given
+    pub(crate) fn session_retry_policy(&self) -> &SessionRetryPolicy {
+        &self.session_retry_policy
+    }
+
+    /// Connects to a server that uses Reverse Connect and creates / activates a [`Session`] for
+    /// the endpoint. The server connects to the client instead of the client connecting to it, so
+    /// it must be configured with a url that reaches the listener. Connections from the server
+    /// are matched by its application uri and by the endpoint url, ignoring the host name.
+    ///
+    /// `GetEndpoints` is first called over one connection from the server and the session is
+    /// created over the next. The session also takes its connections from the listener if it
+    /// has to reconnect.
+    ///
+    /// Returns with the session that has been established or an error.
+    ///
+    /// [`Session`]: ../session/struct.Session.html
+    ///
+    pub fn connect_to_endpoint_reverse<T>(&mut self, listener: Arc<ReverseConnectListener>, server_uri: &str, endpoint: T, user_identity_token: IdentityToken) -> Result<Arc<RwLock<Session>>, StatusCode> where T: Into<EndpointDescription> {
+        let endpoint = endpoint.into();
+        if !is_opc_ua_binary_url(endpoint.endpoint_url.as_ref()) {
+            error!("Reverse connect endpoint url {} is not an opc.tcp url", endpoint.endpoint_url);
+            return Err(StatusCode::BadTcpEndpointUrlInvalid);
+        }
+        let reverse_connect = ReverseConnect {
+            listener,
+            server_uri: server_uri.to_string(),
+        };
+
+        let server_endpoints = match self.get_endpoints(endpoint.endpoint_url.as_ref(), Some(reverse_connect.clone()), &self.session_retry_policy) {
+            Ok(server_endpoints) => server_endpoints,
+            Err(status_code) => {
+                error!("Cannot get endpoints for server, error - {}", status_code);
+                return Err(status_code);
+            }
+        };
+
+        // Find the server endpoint that matches the one desired
+        let security_policy = SecurityPolicy::from_str(endpoint.security_policy_uri.as_ref())
+            .map_err(|_| StatusCode::BadSecurityPolicyRejected)?;
+        let server_endpoint = Client::find_server_endpoint(&server_endpoints, endpoint.endpoint_url.as_ref(), security_policy, endpoint.security_mode)
+            .ok_or_else(|| {
+                error!("Cannot find matching endpoint for {}", endpoint.endpoint_url.as_ref());
+                StatusCode::BadTcpEndpointUrlInvalid
+            })?;
+
+        // Create a session that takes its connections from the listener
+        let session_info = (server_endpoint, user_identity_token).into();
+        let session = Arc::new(RwLock::new(Session::new(self.application_description(), self.certificate_store.clone(), session_info, self.session_retry_policy.clone(), self.keep_alive_config, Some(reverse_connect))));
 
         {
             // Connect to the server
@@ -316,10 +412,10 @@ impl Client {
     ///
     pub fn new_session_from_info<T>(&mut self, session_info: T) -> Result<Arc<RwLock<Session>>, String> where T: Into<SessionInfo> {
         let session_info = session_info.into();
-        if !is_opc_ua_binary_url(session_info.endpoint.endpoint_url.as_ref()) {
+        if !is_valid_opc_ua_url(session_info.endpoint.endpoint_url.as_ref()) {
             Err(format!("Endpoint url {}, is not a valid / supported url", session_info.endpoint.endpoint_url))
         } else {
-            let session = Arc::new(RwLock::new(Session::new(self.application_description(), self.certificate_store.clone(), session_info, self.session_retry_policy.clone())));
+            let session = Arc::new(RwLock::new(Session::new(self.application_description(), self.certificate_store.clone(), session_info, self.session_retry_policy.clone(), self.keep_alive_config, None)));
             Ok(session)
         }
     }
@@ -369,25 +465,32 @@ impl Client {
         where T: Into<String>
     {
         let server_url = server_url.into();
-        if !is_opc_ua_binary_url(&server_url) {
+        if !is_valid_opc_ua_url(&server_url) {
             Err(StatusCode::BadTcpEndpointUrlInvalid)
         } else {
-            let preferred_locales = Vec::new();
-            // Most of these fields mean nothing when getting endpoints
-            let endpoint = EndpointDescription::from(server_url.as_ref());
-            let session_info = SessionInfo {
-                endpoint,
-                user_identity_token: IdentityToken::Anonymous,
-                preferred_locales,
-            };
-            let mut session = Session::new(self.application_description(), self.certificate_store.clone(), session_info, self.session_retry_policy.clone());
-            session.connect()?;
-            let result = session.get_endpoints()?;
-            session.disconnect();
-            Ok(result)
+            self.get_endpoints(&server_url, None, &self.session_retry_policy)
         }
     }
 
+    /// Connects to the url with a None/None connection and asks for the endpoints, taking the
+    /// connection from the reverse connect listener if one is supplied. The retry policy says how
+    /// many times to try connecting.
+    fn get_endpoints(&self, url: &str, reverse_connect: Option<ReverseConnect>, session_retry_policy: &SessionRetryPolicy) -> Result<Vec<EndpointDescription>, StatusCode> {
+        let preferred_locales = Vec::new();
+        // Most of these fields mean nothing when getting endpoints
+        let endpoint = EndpointDescription::from(url);
+        let session_info = SessionInfo {
+            endpoint,
+            user_identity_token: IdentityToken::Anonymous,
+            preferred_locales,
+        };
+        let mut session = Session::new(self.application_description(), self.certificate_store.clone(), session_info, session_retry_policy.clone(), KeepAliveConfig::off(), reverse_connect);
+        session.connect()?;
+        let result = session.get_endpoints()?;
+        session.disconnect();
+        Ok(result)
+    }
+
     /// Connects to a discovery server and asks the server for a list of
     /// available server [`ApplicationDescription`].
     ///
@@ -0,0 +1,289 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! The OPC UA HTTPS transport client module (https-uabinary), OPC UA Part 6 7.4. Each request is
+//! POSTed to the server as a binary encoded body over TLS, and the body of the HTTP response holds
+//! the service response. There is no secure channel, TLS protects the messages instead.
+//!
+//! Requests are taken from the message queue and each is sent on its own thread so that publish
+//! requests, which the server holds until it has notifications, do not block other requests.
+//! Connections are kept alive and reused between requests.
+
+use std::io::BufReader;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::result::Result;
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+use std::time::Duration;
+
+use futures::Stream;
+
+use opcua_core::{
+    comms::{
+        https::{self, HttpMessage},
+        url::{hostname_port_from_url, path_from_url},
+    },
+    prelude::*,
+    RUNTIME,
+};
+use opcua_crypto::{CertificateStore, TlsConnector, TlsStream};
+use opcua_types::{
+    constants, service_types::ServiceFault, status_code::StatusCode,
+};
+
+use crate::{
+    callbacks::OnSessionClosed,
+    comms::transport::Transport,
+    message_queue::{self, MessageQueue},
+    session_state::{ConnectionState, SessionState},
+};
+
+macro_rules! connection_state {( $s:expr ) => { *trace_read_lock_unwrap!($s) } }
+macro_rules! set_connection_state {( $s:expr, $v:expr ) => { *trace_write_lock_unwrap!($s) = $v } }
+
+/// The largest response body that the client will read
+const MAX_RESPONSE_BODY_SIZE: usize = 16 * 1024 * 1024;
+/// Time to wait for a TCP connection to the server
+const CONNECT_TIMEOUT_MS: u64 = 5000;
+
+type Connection = BufReader<TlsStream<TcpStream>>;
+
+/// The target of requests and the means to open connections to it
+struct HttpsTarget {
+    /// Socket address of the server
+    addr: SocketAddr,
+    /// Host name used for TLS and the Host header
+    host: String,
+    /// Value of the Host header
+    host_header: String,
+    /// Path the requests are POSTed to
+    path: String,
+    connector: TlsConnector,
+    /// Connections that are open and not in use
+    idle_connections: Mutex<Vec<Connection>>,
+}
+
+impl HttpsTarget {
+    fn open_connection(&self) -> Result<Connection, StatusCode> {
+        let socket = TcpStream::connect_timeout(&self.addr, Duration::from_millis(CONNECT_TIMEOUT_MS)).map_err(|err| {
+            error!("Cannot connect to {}, {}", self.addr, err);
+            StatusCode::BadCommunicationError
+        })?;
+        let stream = self.connector.connect(&self.host, socket)?;
+        Ok(BufReader::new(stream))
+    }
+
+    /// Posts the body and returns the body of the response. An idle connection is used if there
+    /// is one, falling back to a new connection if the server has since closed it.
+    fn post(&self, body: Vec<u8>, security_policy_uri: &str) -> Result<Vec<u8>, StatusCode> {
+        let request = HttpMessage::post(&self.host_header, &self.path, body, security_policy_uri);
+        let idle_connection = {
+            let mut idle_connections = trace_lock_unwrap!(self.idle_connections);
+            idle_connections.pop()
+        };
+        let result = if let Some(connection) = idle_connection {
+            self.post_on_connection(connection, &request)
+                .or_else(|_| self.post_on_connection(self.open_connection()?, &request))
+        } else {
+            self.post_on_connection(self.open_connection()?, &request)
+        }?;
+        match result.status() {
+            Some(200) => Ok(result.body),
+            status => {
+                error!("Server responded to https request with status {:?}", status);
+                Err(StatusCode::BadCommunicationError)
+            }
+        }
+    }
+
+    fn post_on_connection(&self, mut connection: Connection, request: &HttpMessage) -> Result<HttpMessage, StatusCode> {
+        request.write(connection.get_mut()).map_err(|_| StatusCode::BadCommunicationError)?;
+        let response = HttpMessage::read(&mut connection, MAX_RESPONSE_BODY_SIZE)?
+            .ok_or(StatusCode::BadConnectionClosed)?;
+        if response.keep_alive() {
+            let mut idle_connections = trace_lock_unwrap!(self.idle_connections);
+            idle_connections.push(connection);
+        }
+        Ok(response)
+    }
+}
+
+/// This is the OPC UA HTTPS client transport layer
+pub(crate) struct HttpsTransport {
+    /// Session state
+    session_state: Arc<RwLock<SessionState>>,
+    /// Secure channel information. It holds the security policy and decoding limits but no
+    /// channel is opened.
+    secure_channel: Arc<RwLock<SecureChannel>>,
+    /// Connection state
+    connection_state: Arc<RwLock<ConnectionState>>,
+    /// Message queue for requests / responses
+    message_queue: Arc<RwLock<MessageQueue>>,
+    /// Certificate store used to trust the server's TLS certificate
+    certificate_store: Arc<RwLock<CertificateStore>>,
+}
+
+impl Drop for HttpsTransport {
+    fn drop(&mut self) {
+        info!("HttpsTransport has dropped");
+    }
+}
+
+impl Transport for HttpsTransport {
+    /// Connects to the specified endpoint. A connection is opened to prove that the server can
+    /// be reached and that its certificate is trusted.
+    fn connect(&mut self, endpoint_url: &str) -> Result<(), StatusCode> {
+        if self.is_connected() {
+            panic!("Should not try to connect when already connected");
+        }
+
+        let (host, port) = hostname_port_from_url(endpoint_url, constants::DEFAULT_OPC_UA_HTTPS_PORT)?;
+        let path = path_from_url(endpoint_url)?;
+
+        // Resolve the host name into a socket address
+        let addr = {
+            let addr = format!("{}:{}", host, port);
+            let addrs = addr.to_socket_addrs();
+            if let Ok(mut addrs) = addrs {
+                // Take the first resolved ip addr for the hostname
+                if let Some(addr) = addrs.next() {
+                    addr
+                } else {
+                    error!("Invalid address {}, does not resolve to any socket", addr);
+                    return Err(StatusCode::BadTcpEndpointUrlInvalid);
+                }
+            } else {
+                error!("Invalid address {}, cannot be parsed {:?}", addr, addrs.unwrap_err());
+                return Err(StatusCode::BadTcpEndpointUrlInvalid);
+            }
+        };
+
+        set_connection_state!(self.connection_state, ConnectionState::Connecting);
+        let connector = TlsConnector::new(self.certificate_store.clone(), &host).map_err(|_| {
+            set_connection_state!(self.connection_state, ConnectionState::Finished(StatusCode::BadCommunicationError));
+            StatusCode::BadCommunicationError
+        })?;
+        let target = HttpsTarget {
+            addr,
+            host_header: format!("{}:{}", host, port),
+            host,
+            path,
+            connector,
+            idle_connections: Mutex::new(Vec::new()),
+        };
+        match target.open_connection() {
+            Ok(connection) => {
+                let mut idle_connections = trace_lock_unwrap!(target.idle_connections);
+                idle_connections.push(connection);
+            }
+            Err(status_code) => {
+                error!("Connect failed with status {}", status_code);
+                set_connection_state!(self.connection_state, ConnectionState::Finished(status_code));
+                return Err(StatusCode::BadConnectionClosed);
+            }
+        }
+        set_connection_state!(self.connection_state, ConnectionState::Processing);
+
+        let receiver = {
+            let mut message_queue = trace_write_lock_unwrap!(self.message_queue);
+            message_queue.make_request_channel().1
+        };
+
+        let target = Arc::new(target);
+        let connection_state = self.connection_state.clone();
+        let session_state = self.session_state.clone();
+        let secure_channel = self.secure_channel.clone();
+        let message_queue = self.message_queue.clone();
+        thread::spawn(move || {
+            let thread_id = format!("client-https-thread-{:?}", thread::current().id());
+            register_runtime_component!(thread_id.clone());
+
+            for message in receiver.wait() {
+                match message {
+                    Ok(message_queue::Message::SupportedMessage(request)) => {
+                        let target = target.clone();
+                        let secure_channel = secure_channel.clone();
+                        let message_queue = message_queue.clone();
+                        thread::spawn(move || Self::send_request(&target, &secure_channel, &message_queue, request));
+                    }
+                    Ok(message_queue::Message::Quit) => {
+                        debug!("Https transport received a quit");
+                        break;
+                    }
+                    Err(_) => {
+                        break;
+                    }
+                }
+            }
+
+            // Tell the session that the connection is finished.
+            set_connection_state!(connection_state, ConnectionState::Finished(StatusCode::Good));
+            {
+                let mut session_state = trace_write_lock_unwrap!(session_state);
+                session_state.on_session_closed(StatusCode::Good);
+            }
+            deregister_runtime_component!(thread_id);
+        });
+        Ok(())
+    }
+
+    fn wait_for_disconnect(&mut self) {
+        debug!("Waiting for a disconnect");
+        loop {
+            match connection_state!(self.connection_state) {
+                ConnectionState::NotStarted | ConnectionState::Finished(_) => {
+                    debug!("Disconnected");
+                    break;
+                }
+                _ => {}
+            }
+            thread::sleep(Duration::from_millis(Self::WAIT_POLLING_TIMEOUT))
+        }
+    }
+
+    fn is_connected(&self) -> bool {
+        !matches!(connection_state!(self.connection_state),
+            ConnectionState::NotStarted | ConnectionState::Connecting | ConnectionState::Finished(_))
+    }
+
+    fn uses_secure_channel(&self) -> bool {
+        false
+    }
+}
+
+impl HttpsTransport {
+    const WAIT_POLLING_TIMEOUT: u64 = 100;
+
+    /// Create a new HTTPS transport layer for the session
+    pub fn new(certificate_store: Arc<RwLock<CertificateStore>>, secure_channel: Arc<RwLock<SecureChannel>>, session_state: Arc<RwLock<SessionState>>, message_queue: Arc<RwLock<MessageQueue>>) -> HttpsTransport {
+        let connection_state = {
+            let session_state = trace_read_lock_unwrap!(session_state);
+            session_state.connection_state()
+        };
+        HttpsTransport {
+            session_state,
+            secure_channel,
+            connection_state,
+            message_queue,
+            certificate_store,
+        }
+    }
+
+    /// Posts the request and stores its response. A failure is stored as a service fault so the
+    /// caller learns of it without waiting for its request to time out.
+    fn send_request(target: &HttpsTarget, secure_channel: &Arc<RwLock<SecureChannel>>, message_queue: &Arc<RwLock<MessageQueue>>, request: SupportedMessage) {
+        let (security_policy_uri, decoding_limits) = {
+            let secure_channel = trace_read_lock_unwrap!(secure_channel);
+            (secure_channel.security_policy().to_uri(), secure_channel.decoding_limits())
+        };
+        let response = target.post(https::encode_message(&request), security_policy_uri)
+            .and_then(|body| https::decode_message(&body, &decoding_limits))
+            .unwrap_or_else(|status_code| {
+                error!("Https request {:?} failed, {}", request.node_id(), status_code);
+                ServiceFault::new(request.request_header(), status_code).into()
+            });
+        let mut message_queue = trace_write_lock_unwrap!(message_queue);
+        message_queue.store_response(response);
+    }
+}
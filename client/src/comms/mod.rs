@@ -4,6 +4,7 @@
 
 //! Client side communications
 
-mod transport;
+pub(crate) mod transport;
 
 pub(crate) mod tcp_transport;
+pub(crate) mod https_transport;
@@ -3,7 +3,8 @@
 // Copyright (C) 2017-2020 Adam Lock
 
 //! The OPC UA TCP transport client module. The transport is responsible for establishing a connection
-//! with the server and processing requests.
+//! with the server and processing requests. It also serves opc.wss urls, where the same messages
+//! travel in WebSocket frames over TLS.
 //!
 //! Internally this uses Tokio to process requests and responses supplied by the session via the
 //! session state.
@@ -18,6 +19,7 @@ use futures::future::{self};
 use futures::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use tokio;
 use tokio::net::TcpStream;
+use tokio::reactor::Handle;
 use tokio_codec::FramedRead;
 use tokio_io::{AsyncRead, AsyncWrite};
 use tokio_io::io::{self, ReadHalf, WriteHalf};
@@ -28,12 +30,14 @@ use opcua_core::{
         message_writer::MessageWriter,
         tcp_codec::{Message, TcpCodec},
         tcp_types::HelloMessage,
-        url::hostname_port_from_url,
-        wrapped_tcp_stream::WrappedTcpStream,
+        transport_stream::TransportStream,
+        url::{hostname_port_from_url, is_opc_ua_wss_url, path_from_url},
+        websocket,
     },
     prelude::*,
     RUNTIME,
 };
+use opcua_crypto::{CertificateStore, TlsConnector};
 use opcua_types::{
     status_code::StatusCode,
 };
@@ -42,12 +46,40 @@ use crate::{
     callbacks::OnSessionClosed,
     comms::transport::Transport,
     message_queue::{self, MessageQueue},
+    reverse_connect::ReverseConnect,
     session_state::{ConnectionState, SessionState},
 };
 
 macro_rules! connection_state {( $s:expr ) => { *trace_read_lock_unwrap!($s) } }
 macro_rules! set_connection_state {( $s:expr, $v:expr ) => { *trace_write_lock_unwrap!($s) = $v } }
 
+/// What is needed to open a WebSocket to the server over TLS
+struct WebSocketTarget {
+    connector: TlsConnector,
+    /// Host name used for TLS
+    host: String,
+    /// Value of the Host header
+    host_header: String,
+    /// Path of the upgrade request
+    path: String,
+}
+
+/// Where the socket for a connection comes from
+enum SocketSource {
+    /// Connect to the server at the address
+    Address(SocketAddr),
+    /// Use the socket that the server opened to the client with reverse connect
+    Reverse(SocketAddr, std::net::TcpStream),
+}
+
+impl SocketSource {
+    fn addr(&self) -> SocketAddr {
+        match *self {
+            SocketSource::Address(addr) | SocketSource::Reverse(addr, _) => addr,
+        }
+    }
+}
+
 struct ReadState {
     pub state: Arc<RwLock<ConnectionState>>,
     pub secure_channel: Arc<RwLock<SecureChannel>>,
@@ -104,7 +136,7 @@ struct WriteState {
     /// The url to connect to
     pub secure_channel: Arc<RwLock<SecureChannel>>,
     pub message_queue: Arc<RwLock<MessageQueue>>,
-    pub writer: Option<WriteHalf<WrappedTcpStream>>,
+    pub writer: Option<WriteHalf<TransportStream>>,
     /// The send buffer
     pub send_buffer: MessageWriter,
 }
@@ -147,6 +179,10 @@ pub(crate) struct TcpTransport {
     connection_state: Arc<RwLock<ConnectionState>>,
     /// Message queue for requests / responses
     message_queue: Arc<RwLock<MessageQueue>>,
+    /// Certificate store used to trust the server's TLS certificate over opc.wss
+    certificate_store: Arc<RwLock<CertificateStore>>,
+    /// Where connections come from if the server connects to the client
+    reverse_connect: Option<ReverseConnect>,
 }
 
 impl Drop for TcpTransport {
@@ -155,57 +191,66 @@ impl Drop for TcpTransport {
     }
 }
 
-impl Transport for TcpTransport {}
-
-impl TcpTransport {
-    const WAIT_POLLING_TIMEOUT: u64 = 100;
-
-    /// Create a new TCP transport layer for the session
-    pub fn new(secure_channel: Arc<RwLock<SecureChannel>>, session_state: Arc<RwLock<SessionState>>, message_queue: Arc<RwLock<MessageQueue>>) -> TcpTransport {
-        let connection_state = {
-            let session_state = trace_read_lock_unwrap!(session_state);
-            session_state.connection_state()
-        };
-        TcpTransport {
-            session_state,
-            secure_channel,
-            connection_state,
-            message_queue,
-        }
-    }
-
+impl Transport for TcpTransport {
     /// Connects the stream to the specified endpoint
-    pub fn connect(&mut self, endpoint_url: &str) -> Result<(), StatusCode> {
+    fn connect(&mut self, endpoint_url: &str) -> Result<(), StatusCode> {
         if self.is_connected() {
             panic!("Should not try to connect when already connected");
         }
 
-        let (host, port) = hostname_port_from_url(&endpoint_url, constants::DEFAULT_OPC_UA_SERVER_PORT)?;
-
-        // Resolve the host name into a socket address
-        let addr = {
-            let addr = format!("{}:{}", host, port);
-            let addrs = addr.to_socket_addrs();
-            if let Ok(mut addrs) = addrs {
-                // Take the first resolved ip addr for the hostname
-                if let Some(addr) = addrs.next() {
-                    addr
+        // With reverse connect the server has connected to the client so there is nothing to
+        // resolve, the socket is taken from the listener instead
+        let (socket_source, websocket_target) = if let Some(ref reverse_connect) = self.reverse_connect {
+            if is_opc_ua_wss_url(endpoint_url) {
+                error!("Reverse connect is only supported over opc.tcp");
+                return Err(StatusCode::BadTcpEndpointUrlInvalid);
+            }
+            let socket = reverse_connect.listener.accept(&reverse_connect.server_uri, Some(endpoint_url))?;
+            let addr = socket.peer_addr().map_err(|_| StatusCode::BadCommunicationError)?;
+            (SocketSource::Reverse(addr, socket), None)
+        } else {
+            let (host, port) = hostname_port_from_url(endpoint_url, constants::DEFAULT_OPC_UA_SERVER_PORT)?;
+
+            // Resolve the host name into a socket address
+            let addr = {
+                let addr = format!("{}:{}", host, port);
+                let addrs = addr.to_socket_addrs();
+                if let Ok(mut addrs) = addrs {
+                    // Take the first resolved ip addr for the hostname
+                    if let Some(addr) = addrs.next() {
+                        addr
+                    } else {
+                        error!("Invalid address {}, does not resolve to any socket", addr);
+                        return Err(StatusCode::BadTcpEndpointUrlInvalid);
+                    }
                 } else {
-                    error!("Invalid address {}, does not resolve to any socket", addr);
+                    error!("Invalid address {}, cannot be parsed {:?}", addr, addrs.unwrap_err());
                     return Err(StatusCode::BadTcpEndpointUrlInvalid);
                 }
+            };
+            assert_eq!(addr.port(), port);
+
+            // Over opc.wss the socket is wrapped in TLS and upgraded to a WebSocket before the HELLO
+            let websocket_target = if is_opc_ua_wss_url(endpoint_url) {
+                let path = path_from_url(endpoint_url)?;
+                let connector = TlsConnector::new(self.certificate_store.clone(), &host).map_err(|_| StatusCode::BadCommunicationError)?;
+                Some(WebSocketTarget {
+                    connector,
+                    host_header: format!("{}:{}", host, port),
+                    host,
+                    path,
+                })
             } else {
-                error!("Invalid address {}, cannot be parsed {:?}", addr, addrs.unwrap_err());
-                return Err(StatusCode::BadTcpEndpointUrlInvalid);
-            }
+                None
+            };
+            (SocketSource::Address(addr), websocket_target)
         };
-        assert_eq!(addr.port(), port);
 
         // The connection will be serviced on its own thread. When the thread terminates, the connection
         // has also terminated.
 
         {
-            let connection_task = Self::connection_task(addr, self.connection_state.clone(), endpoint_url.to_string(),
+            let connection_task = Self::connection_task(socket_source, websocket_target, self.connection_state.clone(), endpoint_url.to_string(),
                                                         self.session_state.clone(), self.secure_channel.clone(), self.message_queue.clone());
 
             let connection_state = self.connection_state.clone();
@@ -255,7 +300,7 @@ impl TcpTransport {
     }
 
     /// Disconnects the stream from the server (if it is connected)
-    pub fn wait_for_disconnect(&mut self) {
+    fn wait_for_disconnect(&mut self) {
         debug!("Waiting for a disconnect");
         loop {
             match connection_state!(self.connection_state) {
@@ -270,20 +315,43 @@ impl TcpTransport {
     }
 
     /// Tests if the transport is connected
-    pub fn is_connected(&self) -> bool {
+    fn is_connected(&self) -> bool {
         match connection_state!(self.connection_state) {
             ConnectionState::NotStarted | ConnectionState::Connecting |
             ConnectionState::Finished(_) => false,
             _ => true,
         }
     }
+}
+
+impl TcpTransport {
+    const WAIT_POLLING_TIMEOUT: u64 = 100;
+
+    /// Create a new TCP transport layer for the session. If reverse connect is supplied, the
+    /// transport takes connections from the listener instead of connecting to the server.
+    pub fn new(certificate_store: Arc<RwLock<CertificateStore>>, secure_channel: Arc<RwLock<SecureChannel>>, session_state: Arc<RwLock<SessionState>>, message_queue: Arc<RwLock<MessageQueue>>, reverse_connect: Option<ReverseConnect>) -> TcpTransport {
+        let connection_state = {
+            let session_state = trace_read_lock_unwrap!(session_state);
+            session_state.connection_state()
+        };
+        TcpTransport {
+            session_state,
+            secure_channel,
+            connection_state,
+            message_queue,
+            certificate_store,
+            reverse_connect,
+        }
+    }
 
     /// This is the main connection task for a connection.
-    fn connection_task(addr: SocketAddr, connection_state: Arc<RwLock<ConnectionState>>, endpoint_url: String, session_state: Arc<RwLock<SessionState>>, secure_channel: Arc<RwLock<SecureChannel>>, message_queue: Arc<RwLock<MessageQueue>>) -> impl Future<Item=(), Error=()> {
+    fn connection_task(socket_source: SocketSource, websocket_target: Option<WebSocketTarget>, connection_state: Arc<RwLock<ConnectionState>>, endpoint_url: String, session_state: Arc<RwLock<SessionState>>, secure_channel: Arc<RwLock<SecureChannel>>, message_queue: Arc<RwLock<MessageQueue>>) -> impl Future<Item=(), Error=()> {
+        let addr = socket_source.addr();
         debug!("Creating a connection task to connect to {} with url {}", addr, endpoint_url);
 
         let connection_state_for_error = connection_state.clone();
         let connection_state_for_error2 = connection_state.clone();
+        let connection_state_for_error3 = connection_state.clone();
 
         let hello = {
             let session_state = trace_read_lock_unwrap!(session_state);
@@ -293,28 +361,45 @@ impl TcpTransport {
                               session_state.max_message_size())
         };
 
-        let id = {
+        let (id, receive_buffer_size) = {
             let session_state = trace_read_lock_unwrap!(session_state);
-            session_state.id()
+            (session_state.id(), session_state.receive_buffer_size())
         };
 
         let connection_task_id = format!("connection-task, {}", id);
         register_runtime_component!(connection_task_id.clone());
 
         set_connection_state!(connection_state, ConnectionState::Connecting);
-        TcpStream::connect(&addr).map_err(move |err| {
+        let socket = match socket_source {
+            SocketSource::Address(addr) => future::Either::A(TcpStream::connect(&addr)),
+            SocketSource::Reverse(_, socket) => future::Either::B(future::lazy(move || TcpStream::from_std(socket, &Handle::default()))),
+        };
+        socket.map_err(move |err| {
             error!("Could not connect to host {}, {:?}", addr, err);
             set_connection_state!(connection_state_for_error, ConnectionState::Finished(StatusCode::BadCommunicationError));
         }).and_then(move |socket| {
+            if let Some(WebSocketTarget { connector, host, host_header, path }) = websocket_target {
+                let stream = websocket::tls_connect(connector, host, socket)
+                    .and_then(move |stream| websocket::connect(stream, &host_header, &path, receive_buffer_size))
+                    .map(TransportStream::WebSocket)
+                    .map_err(move |status_code| {
+                        error!("Could not open a WebSocket to host {}, {}", addr, status_code);
+                        set_connection_state!(connection_state_for_error3, ConnectionState::Finished(status_code));
+                    });
+                future::Either::A(stream)
+            } else {
+                future::Either::B(future::ok(TransportStream::from(socket)))
+            }
+        }).and_then(move |stream| {
             set_connection_state!(connection_state, ConnectionState::Connected);
-            let (reader, writer) = WrappedTcpStream(socket).split();
+            let (reader, writer) = stream.split();
             Ok((connection_state, reader, writer))
         }).and_then(move |(connection_state, reader, writer)| {
             debug! {"Sending HELLO"};
-            io::write_all(writer, hello.encode_to_vec()).map_err(move |err| {
+            io::write_all(writer, hello.encode_to_vec()).and_then(|(writer, _)| io::flush(writer)).map_err(move |err| {
                 error!("Cannot send hello to server, err = {:?}", err);
                 set_connection_state!(connection_state_for_error2, ConnectionState::Finished(StatusCode::BadCommunicationError));
-            }).map(move |(writer, _)| {
+            }).map(move |writer| {
                 (reader, writer)
             }).and_then(move |(reader, writer)| {
                 Self::spawn_looping_tasks(reader, writer, connection_state, session_state, secure_channel, message_queue);
@@ -333,9 +418,9 @@ impl TcpTransport {
         };
 
         let connection_for_and_then = connection.clone();
-        io::write_all(writer, bytes_to_write).map_err(move |err| {
+        io::write_all(writer, bytes_to_write).and_then(|(writer, _)| io::flush(writer)).map_err(move |err| {
             error!("Write bytes task IO error {:?}", err);
-        }).map(move |(writer, _)| {
+        }).map(move |writer| {
             trace!("Write bytes task finished");
             // Reinstate writer
             let mut connection = trace_lock_unwrap!(connection);
@@ -357,9 +442,10 @@ impl TcpTransport {
         })
     }
 
-    fn spawn_finished_monitor_task(state: Arc<RwLock<ConnectionState>>, finished_flag: Arc<RwLock<bool>>, id: u32) {
+    fn spawn_finished_monitor_task(state: Arc<RwLock<ConnectionState>>, finished_flag: Arc<RwLock<bool>>, message_queue: Arc<RwLock<MessageQueue>>, id: u32) {
         // This task just spins around waiting for the connection to become finished. When it
-        // does it, sets a flag.
+        // does it, sets a flag. Along the way it times out requests whose responses complete
+        // futures, and fails those still waiting when the connection finishes.
 
         let finished_monitor_task_id = format!("finished-monitor-task, {}", id);
         let finished_monitor_task_id_for_err = finished_monitor_task_id.clone();
@@ -375,6 +461,14 @@ impl TcpTransport {
                         false
                     }
                 };
+                {
+                    let mut message_queue = trace_write_lock_unwrap!(message_queue);
+                    if finished {
+                        message_queue.fail_requests(StatusCode::BadConnectionClosed);
+                    } else {
+                        message_queue.expire_requests(Instant::now());
+                    }
+                }
                 if finished {
                     // Set the flag
                     let mut finished_flag = trace_write_lock_unwrap!(finished_flag);
@@ -395,7 +489,7 @@ impl TcpTransport {
         tokio::spawn(finished_monitor_task);
     }
 
-    fn spawn_reading_task(reader: ReadHalf<WrappedTcpStream>, writer_tx: UnboundedSender<message_queue::Message>, finished_flag: Arc<RwLock<bool>>, _receive_buffer_size: usize, connection: ReadState, id: u32) {
+    fn spawn_reading_task(reader: ReadHalf<TransportStream>, writer_tx: UnboundedSender<message_queue::Message>, finished_flag: Arc<RwLock<bool>>, _receive_buffer_size: usize, connection: ReadState, id: u32) {
         // This is the main processing loop that receives and sends messages
         let decoding_limits = {
             let secure_channel = trace_read_lock_unwrap!(connection.secure_channel);
@@ -450,6 +544,10 @@ impl TcpTransport {
                     };
                     error!("Expecting a chunk, got an error message {}", session_status_code);
                 }
+                Message::ReverseHello(_) => {
+                    error!("Got a reverse hello on a connection that is already open");
+                    session_status_code = StatusCode::BadTcpMessageTypeInvalid;
+                }
                 _ => {
                     panic!("Expected a recognized message");
                 }
@@ -498,6 +596,7 @@ impl TcpTransport {
 
     fn spawn_writing_task(receiver: UnboundedReceiver<message_queue::Message>, connection: WriteState, id: u32) {
         let connection = Arc::new(Mutex::new(connection));
+        let connection_for_finish = connection.clone();
         let connection_for_error = connection.clone();
 
         let write_task_id = format!("write-task, {}", id);
@@ -574,6 +673,13 @@ impl TcpTransport {
             })
             .map(move |_| {
                 debug!("Writer loop is finished");
+                // The writer may have been told to quit while the socket is still open, e.g. by
+                // the keep-alive when a server stops answering, so shut it down to let the server
+                // and the reader know the connection is over.
+                let mut connection = trace_lock_unwrap!(connection_for_finish);
+                if let Some(mut writer) = connection.writer.take() {
+                    let _ = writer.shutdown();
+                }
                 deregister_runtime_component!(write_task_id);
             })
             .map_err(move |_| {
@@ -588,7 +694,7 @@ impl TcpTransport {
 
     /// This is the main processing loop for the connection. It writes requests and reads responses
     /// over the socket to the server.
-    fn spawn_looping_tasks(reader: ReadHalf<WrappedTcpStream>, writer: WriteHalf<WrappedTcpStream>, connection_state: Arc<RwLock<ConnectionState>>, session_state: Arc<RwLock<SessionState>>, secure_channel: Arc<RwLock<SecureChannel>>, message_queue: Arc<RwLock<MessageQueue>>) {
+    fn spawn_looping_tasks(reader: ReadHalf<TransportStream>, writer: WriteHalf<TransportStream>, connection_state: Arc<RwLock<ConnectionState>>, session_state: Arc<RwLock<SessionState>>, secure_channel: Arc<RwLock<SecureChannel>>, message_queue: Arc<RwLock<MessageQueue>>) {
         let (receive_buffer_size, send_buffer_size, id) = {
             let session_state = trace_read_lock_unwrap!(session_state);
             (session_state.receive_buffer_size(), session_state.send_buffer_size(), session_state.id())
@@ -605,7 +711,7 @@ impl TcpTransport {
 
         // Abort monitor
         let finished_flag = Arc::new(RwLock::new(false));
-        Self::spawn_finished_monitor_task(connection_state.clone(), finished_flag.clone(), id);
+        Self::spawn_finished_monitor_task(connection_state.clone(), finished_flag.clone(), message_queue.clone(), id);
 
         // Spawn the reading task loop
         {
@@ -2,7 +2,18 @@
 // SPDX-License-Identifier: MPL-2.0
 // Copyright (C) 2017-2020 Adam Lock
 
+use opcua_types::status_code::StatusCode;
+
 /// A trait common to all transport implementations
 pub(crate) trait Transport {
-    // Common functions will go here
-}
\ No newline at end of file
+    /// Connects to the server at the endpoint url
+    fn connect(&mut self, endpoint_url: &str) -> Result<(), StatusCode>;
+    /// Waits for the connection to finish after the session has been told to quit
+    fn wait_for_disconnect(&mut self);
+    /// Tests if the transport is connected
+    fn is_connected(&self) -> bool;
+    /// Tests if a secure channel must be opened over the transport before a session is created
+    fn uses_secure_channel(&self) -> bool {
+        true
+    }
+}
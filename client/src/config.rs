@@ -15,7 +15,10 @@ use opcua_core::config::Config;
 use opcua_crypto::SecurityPolicy;
 use opcua_types::{ApplicationType, MessageSecurityMode, UAString};
 
-use crate::session_retry::SessionRetryPolicy;
+use crate::{
+    keep_alive::KeepAliveConfig,
+    session_retry::{RetryBackoff, SessionRetryPolicy},
+};
 
 pub const ANONYMOUS_USER_TOKEN_ID: &str = "ANONYMOUS";
 
@@ -117,6 +120,18 @@ impl ClientEndpoint {
     }
 }
 
+/// Retry policy for the first connection of a session to its server
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct ConnectRetry {
+    /// Max retry limit -1, 0 or number
+    pub retry_limit: i32,
+    /// Retry interval in milliseconds
+    pub retry_interval: u32,
+    /// How the retry interval grows as attempts fail
+    #[serde(default)]
+    pub backoff: RetryBackoff,
+}
+
 /// Client OPC UA configuration
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct ClientConfig {
@@ -150,8 +165,20 @@ pub struct ClientConfig {
     pub session_retry_limit: i32,
     /// Retry interval in milliseconds
     pub session_retry_interval: u32,
+    /// How the retry interval grows as attempts to reconnect fail
+    #[serde(default)]
+    pub session_retry_backoff: RetryBackoff,
+    /// Retry policy for the first connection to the server, if it differs from reconnecting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_retry: Option<ConnectRetry>,
     /// Session timeout period in milliseconds
     pub session_timeout: u32,
+    /// Interval in milliseconds between keep-alive reads of the server's state, 0 for none
+    #[serde(default = "ClientConfig::default_keep_alive_interval")]
+    pub keep_alive_interval: u32,
+    /// Number of keep-alives that can be missed before the connection is dropped
+    #[serde(default = "ClientConfig::default_keep_alive_max_missed")]
+    pub keep_alive_max_missed: u32,
 }
 
 impl Config for ClientConfig {
@@ -209,6 +236,12 @@ impl Config for ClientConfig {
             error!("Session retry limit of {} is invalid - must be -1 (infinite), 0 (never) or a positive value", self.session_retry_limit);
             valid = false;
         }
+        if let Some(ref connect_retry) = self.connect_retry {
+            if connect_retry.retry_limit < 0 && connect_retry.retry_limit != -1 {
+                error!("Connect retry limit of {} is invalid - must be -1 (infinite), 0 (never) or a positive value", connect_retry.retry_limit);
+                valid = false;
+            }
+        }
         valid
     }
 
@@ -250,8 +283,20 @@ impl ClientConfig {
             endpoints: BTreeMap::new(),
             session_retry_limit: SessionRetryPolicy::DEFAULT_RETRY_LIMIT as i32,
             session_retry_interval: SessionRetryPolicy::DEFAULT_RETRY_INTERVAL_MS,
+            session_retry_backoff: RetryBackoff::Fixed,
+            connect_retry: None,
             session_timeout: 0,
+            keep_alive_interval: KeepAliveConfig::DEFAULT_INTERVAL_MS,
+            keep_alive_max_missed: KeepAliveConfig::DEFAULT_MAX_MISSED,
         }
     }
+
+    fn default_keep_alive_interval() -> u32 {
+        KeepAliveConfig::DEFAULT_INTERVAL_MS
+    }
+
+    fn default_keep_alive_max_missed() -> u32 {
+        KeepAliveConfig::DEFAULT_MAX_MISSED
+    }
 }
 
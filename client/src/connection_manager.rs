@@ -0,0 +1,491 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! Connections to many servers that are maintained by a shared pool of worker threads.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, RwLock},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use chrono::Utc;
+
+use opcua_types::{service_types::EndpointDescription, status_code::StatusCode};
+
+use crate::{
+    async_session::AsyncSession,
+    callbacks::OnManagedConnectionStatusChange,
+    client::{Client, IdentityToken},
+    session::Session,
+    session_retry::{Answer, SessionRetryPolicy},
+};
+
+/// The status of a connection held by a [`ConnectionManager`].
+///
+/// [`ConnectionManager`]: ./struct.ConnectionManager.html
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ConnectionStatus {
+    /// The session is being established for the first time, or is reconnecting after the
+    /// connection was lost
+    Connecting,
+    /// The session is connected and activated
+    Connected,
+    /// The retry policy gave up on the connection, with the reason for the last failure
+    Failed(StatusCode),
+}
+
+/// The aggregated health of the connections held by a [`ConnectionManager`].
+///
+/// [`ConnectionManager`]: ./struct.ConnectionManager.html
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct ConnectionHealth {
+    /// The number of connections
+    pub connections: usize,
+    /// The number of connections whose session is connected
+    pub connected: usize,
+    /// The number of connections that are connecting or reconnecting
+    pub connecting: usize,
+    /// The number of connections that have failed
+    pub failed: usize,
+}
+
+impl ConnectionHealth {
+    /// Tests if there is at least one connection and every connection is connected
+    pub fn is_healthy(&self) -> bool {
+        self.connections > 0 && self.connected == self.connections
+    }
+}
+
+struct Connection {
+    /// The endpoint to connect to
+    endpoint: EndpointDescription,
+    /// The identity to activate the session with
+    identity_token: IdentityToken,
+    /// The retry policy of the connection, also given to its session
+    session_retry_policy: SessionRetryPolicy,
+    /// The session, once it has been established
+    session: Option<Arc<RwLock<Session>>>,
+    /// The reason the last attempt to establish the session failed
+    last_error: Option<StatusCode>,
+    /// The status of the connection
+    status: ConnectionStatus,
+    /// The worker thread that maintains the connection
+    worker: usize,
+    /// Set while a thread is connecting or reconnecting the session
+    attempting: bool,
+    /// Set when the connection has been removed while a thread was connecting it, which then
+    /// disconnects the session when it is done
+    removed: bool,
+}
+
+type Connections = Arc<RwLock<HashMap<String, Arc<Mutex<Connection>>>>>;
+
+type StatusCallback = Arc<Mutex<Option<Box<dyn OnManagedConnectionStatusChange + Send + Sync>>>>;
+
+/// A `ConnectionManager` owns the sessions of a client to many servers and keeps them connected.
+/// Each connection is identified by an id chosen by the caller, has its own
+/// [`SessionRetryPolicy`] and is maintained by one of a fixed number of worker threads, which
+/// replace the `Session::run()` thread that each session would otherwise need.
+///
+/// A connection's session is established in the background, asking the server for its
+/// endpoints, connecting, creating and activating the session, and is retried according to the
+/// policy when this fails. Once established, the worker polls the session and it is reconnected
+/// according to the same policy if the connection is lost. A connection fails when the policy
+/// gives up, and is not tried again.
+///
+/// Connecting blocks until the server answers or the connection times out, so each attempt runs
+/// on a thread of its own rather than on the worker, which carries on polling its other
+/// connections. A connection has at most one attempt at a time. Note that each session also
+/// still has its own transport for socket I/O, only the polling is shared.
+///
+/// [`SessionRetryPolicy`]: ../session_retry/struct.SessionRetryPolicy.html
+pub struct ConnectionManager {
+    /// The client that sessions are created from
+    client: Arc<Client>,
+    /// The connections by their id
+    connections: Connections,
+    /// Called when the status of a connection changes
+    status_callback: StatusCallback,
+    /// Tells the worker threads to stop
+    quit: Arc<RwLock<bool>>,
+    /// The worker threads
+    workers: Vec<JoinHandle<()>>,
+    /// The worker that the next connection is given to
+    next_worker: Mutex<usize>,
+}
+
+impl Drop for ConnectionManager {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+impl ConnectionManager {
+    /// Internal constant for the sleep interval of a worker that had nothing to do
+    const POLL_SLEEP_INTERVAL: u64 = 10;
+
+    /// Creates a connection manager whose sessions are made by the client and maintained by the
+    /// number of worker threads, which is at least one. New connections take their retry policy
+    /// from the client unless they are given one.
+    pub fn new(client: Client, worker_threads: usize) -> ConnectionManager {
+        let client = Arc::new(client);
+        let connections: Connections = Arc::new(RwLock::new(HashMap::new()));
+        let status_callback: StatusCallback = Arc::new(Mutex::new(None));
+        let quit = Arc::new(RwLock::new(false));
+        let workers = (0..worker_threads.max(1)).map(|worker| {
+            let client = client.clone();
+            let connections = connections.clone();
+            let status_callback = status_callback.clone();
+            let quit = quit.clone();
+            thread::spawn(move || Self::run_worker(worker, client, connections, status_callback, quit))
+        }).collect();
+        ConnectionManager {
+            client,
+            connections,
+            status_callback,
+            quit,
+            workers,
+            next_worker: Mutex::new(0),
+        }
+    }
+
+    /// Adds a connection to a server endpoint, using the client's retry policy. The connection
+    /// is established in the background. Fails with `BadInvalidArgument` if there is already a
+    /// connection with the id.
+    pub fn add_connection<S, T>(&self, id: S, endpoint: T, identity_token: IdentityToken) -> Result<(), StatusCode>
+        where S: Into<String>, T: Into<EndpointDescription>
+    {
+        let session_retry_policy = self.client.session_retry_policy().clone();
+        self.add_connection_with_policy(id, endpoint, identity_token, session_retry_policy)
+    }
+
+    /// Adds a connection to a server endpoint with its own retry policy. The connection is
+    /// established in the background. Fails with `BadInvalidArgument` if there is already a
+    /// connection with the id.
+    pub fn add_connection_with_policy<S, T>(&self, id: S, endpoint: T, identity_token: IdentityToken, session_retry_policy: SessionRetryPolicy) -> Result<(), StatusCode>
+        where S: Into<String>, T: Into<EndpointDescription>
+    {
+        let id = id.into();
+        if self.workers.is_empty() {
+            error!("Cannot add connection {} because the connection manager has stopped", id);
+            return Err(StatusCode::BadShutdown);
+        }
+        let mut connections = trace_write_lock_unwrap!(self.connections);
+        if connections.contains_key(&id) {
+            error!("Cannot add connection {} because there is already a connection with that id", id);
+            return Err(StatusCode::BadInvalidArgument);
+        }
+        let worker = {
+            let mut next_worker = self.next_worker.lock().unwrap();
+            let worker = *next_worker;
+            *next_worker = (worker + 1) % self.workers.len();
+            worker
+        };
+        info!("Adding connection {} to worker {}", id, worker);
+        connections.insert(id, Arc::new(Mutex::new(Connection {
+            endpoint: endpoint.into(),
+            identity_token,
+            session_retry_policy,
+            session: None,
+            last_error: None,
+            status: ConnectionStatus::Connecting,
+            worker,
+            attempting: false,
+            removed: false,
+        })));
+        Ok(())
+    }
+
+    /// Sets the callback that is told when the status of a connection changes, e.g. when it has
+    /// connected or the retry policy has given up on it. The callback is called from the worker
+    /// threads and the threads that connect sessions.
+    pub fn set_status_change_callback<CB>(&self, status_change_callback: CB) where CB: OnManagedConnectionStatusChange + Send + Sync + 'static {
+        let mut status_callback = self.status_callback.lock().unwrap();
+        *status_callback = Some(Box::new(status_change_callback));
+    }
+
+    /// Removes a connection and disconnects its session. Fails with `BadNotFound` if there is no
+    /// connection with the id.
+    pub fn remove_connection(&self, id: &str) -> Result<(), StatusCode> {
+        let connection = {
+            let mut connections = trace_write_lock_unwrap!(self.connections);
+            connections.remove(id).ok_or(StatusCode::BadNotFound)?
+        };
+        info!("Removing connection {}", id);
+        // The connection is taken from the worker's lock so it won't be polled again. If it is
+        // being connected then the attempt disconnects the session when it is done.
+        let session = {
+            let mut connection = connection.lock().unwrap();
+            connection.removed = true;
+            if connection.attempting { None } else { connection.session.take() }
+        };
+        if let Some(session) = session {
+            let mut session = trace_write_lock_unwrap!(session);
+            session.disconnect();
+        }
+        Ok(())
+    }
+
+    /// Returns the ids of the connections
+    pub fn connection_ids(&self) -> Vec<String> {
+        let connections = trace_read_lock_unwrap!(self.connections);
+        connections.keys().cloned().collect()
+    }
+
+    /// Returns the session of a connection, or `None` if there is no connection with the id or
+    /// its session has not been established yet.
+    pub fn session(&self, id: &str) -> Option<Arc<RwLock<Session>>> {
+        self.connection(id).and_then(|c| c.lock().unwrap().session.clone())
+    }
+
+    /// Returns an [`AsyncSession`] for the session of a connection, or `None` if there is no
+    /// connection with the id or its session has not been established yet.
+    ///
+    /// [`AsyncSession`]: ../async_session/struct.AsyncSession.html
+    pub fn async_session(&self, id: &str) -> Option<AsyncSession> {
+        self.session(id).map(|session| {
+            let session = trace_read_lock_unwrap!(session);
+            session.async_session()
+        })
+    }
+
+    /// Returns the status of a connection, or `None` if there is no connection with the id
+    pub fn connection_status(&self, id: &str) -> Option<ConnectionStatus> {
+        self.connection(id).map(|c| c.lock().unwrap().status)
+    }
+
+    /// Returns the aggregated health of the connections
+    pub fn health(&self) -> ConnectionHealth {
+        let connections = trace_read_lock_unwrap!(self.connections);
+        connections.values().fold(ConnectionHealth::default(), |mut health, connection| {
+            health.connections += 1;
+            match connection.lock().unwrap().status {
+                ConnectionStatus::Connecting => health.connecting += 1,
+                ConnectionStatus::Connected => health.connected += 1,
+                ConnectionStatus::Failed(_) => health.failed += 1,
+            }
+            health
+        })
+    }
+
+    /// Stops the worker threads and disconnects every session. Sessions that are still being
+    /// connected are disconnected when their attempt is done. The connection manager cannot be
+    /// used afterwards.
+    pub fn stop(&mut self) {
+        {
+            let mut quit = trace_write_lock_unwrap!(self.quit);
+            *quit = true;
+        }
+        self.workers.drain(..).for_each(|worker| {
+            let _ = worker.join();
+        });
+        let ids = self.connection_ids();
+        ids.iter().for_each(|id| {
+            let _ = self.remove_connection(id);
+        });
+    }
+
+    fn connection(&self, id: &str) -> Option<Arc<Mutex<Connection>>> {
+        let connections = trace_read_lock_unwrap!(self.connections);
+        connections.get(id).cloned()
+    }
+
+    /// The loop of a worker thread, which polls each of its connections in turn
+    fn run_worker(worker: usize, client: Arc<Client>, connections: Connections, status_callback: StatusCallback, quit: Arc<RwLock<bool>>) {
+        debug!("Connection manager worker {} is running", worker);
+        loop {
+            if *trace_read_lock_unwrap!(quit) {
+                break;
+            }
+            let worker_connections = {
+                let connections = trace_read_lock_unwrap!(connections);
+                connections.iter()
+                    .filter(|(_, c)| c.lock().unwrap().worker == worker)
+                    .map(|(id, c)| (id.clone(), c.clone()))
+                    .collect::<Vec<_>>()
+            };
+            let mut did_something = false;
+            worker_connections.iter().for_each(|(id, connection)| {
+                if Self::poll_connection(id, &client, connection, &status_callback) {
+                    did_something = true;
+                }
+            });
+            // If the worker did nothing, then sleep for a moment to save some CPU
+            if !did_something {
+                thread::sleep(Duration::from_millis(Self::POLL_SLEEP_INTERVAL));
+            }
+        }
+        debug!("Connection manager worker {} has stopped", worker);
+    }
+
+    /// Polls the session of a connection if it is connected, or starts an attempt to establish
+    /// or reconnect it when the retry policy says to. The worker does not wait for the attempt.
+    fn poll_connection(id: &str, client: &Arc<Client>, connection: &Arc<Mutex<Connection>>, status_callback: &StatusCallback) -> bool {
+        let session = {
+            let mut c = connection.lock().unwrap();
+            if c.attempting {
+                return false;
+            }
+            if let ConnectionStatus::Failed(_) = c.status {
+                return false;
+            }
+            match c.session {
+                Some(ref session) => session.clone(),
+                None => {
+                    // The first attempt is always made, then the policy is asked after each failure
+                    let now = Utc::now();
+                    let answer = if c.last_error.is_none() { Answer::Retry } else { c.session_retry_policy.should_retry_connect(now) };
+                    match answer {
+                        Answer::GiveUp => {
+                            error!("Connection {} has given up trying to connect to the server after {} retries", id, c.session_retry_policy.retry_count());
+                            let status = ConnectionStatus::Failed(c.last_error.unwrap_or(StatusCode::BadNotConnected));
+                            c.status = status;
+                            drop(c);
+                            Self::notify_status_change(id, status, status_callback);
+                            return true;
+                        }
+                        Answer::WaitFor(_) => return false,
+                        Answer::Retry => {
+                            c.session_retry_policy.set_last_attempt(now);
+                            c.attempting = true;
+                            info!("Connection {} is connecting to {}", id, c.endpoint.endpoint_url);
+                            let (endpoint, identity_token, session_retry_policy) = (c.endpoint.clone(), c.identity_token.clone(), c.session_retry_policy.clone());
+                            let (id, client, connection, status_callback) = (id.to_string(), client.clone(), connection.clone(), status_callback.clone());
+                            thread::spawn(move || {
+                                let result = Self::connect(&client, &endpoint, identity_token, session_retry_policy);
+                                Self::on_connect(&id, &connection, &status_callback, result);
+                            });
+                            return true;
+                        }
+                    }
+                }
+            }
+        };
+
+        // A connected session is polled here because that does not block, but reconnecting does
+        let (connected, reconnect) = {
+            let session = trace_read_lock_unwrap!(session);
+            (session.is_connected(), session.is_reconnect_due())
+        };
+        if connected {
+            let result = {
+                let mut session = trace_write_lock_unwrap!(session);
+                session.poll()
+            };
+            Self::on_poll(id, connection, &session, status_callback, result)
+        } else if reconnect {
+            {
+                // The session of a connection removed since it was looked up stays disconnected
+                let mut connection = connection.lock().unwrap();
+                if connection.removed {
+                    return false;
+                }
+                connection.attempting = true;
+            }
+            let (id, connection, status_callback) = (id.to_string(), connection.clone(), status_callback.clone());
+            thread::spawn(move || {
+                let result = {
+                    let mut session = trace_write_lock_unwrap!(session);
+                    session.poll()
+                };
+                Self::on_poll(&id, &connection, &session, &status_callback, result);
+                Self::end_attempt(&connection);
+            });
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Updates the status of a connection after its session was polled
+    fn on_poll(id: &str, connection: &Mutex<Connection>, session: &RwLock<Session>, status_callback: &StatusCallback, result: Result<bool, ()>) -> bool {
+        let connected = trace_read_lock_unwrap!(session).is_connected();
+        let status = match result {
+            Ok(_) if connected => ConnectionStatus::Connected,
+            Ok(_) => ConnectionStatus::Connecting,
+            Err(_) => ConnectionStatus::Failed(StatusCode::BadNotConnected),
+        };
+        let changed = {
+            let mut connection = connection.lock().unwrap();
+            if !connection.removed && connection.status != status {
+                info!("Connection {} status has changed to {:?}", id, status);
+                connection.status = status;
+                true
+            } else {
+                false
+            }
+        };
+        if changed {
+            Self::notify_status_change(id, status, status_callback);
+        }
+        result.unwrap_or(true)
+    }
+
+    /// Stores the outcome of an attempt to establish the session of a connection
+    fn on_connect(id: &str, connection: &Mutex<Connection>, status_callback: &StatusCallback, result: Result<Arc<RwLock<Session>>, StatusCode>) {
+        let connected = {
+            let mut connection = connection.lock().unwrap();
+            match result {
+                Ok(session) => {
+                    info!("Connection {} is connected", id);
+                    connection.session_retry_policy.reset_retry_count();
+                    connection.last_error = None;
+                    connection.session = Some(session);
+                    connection.status = ConnectionStatus::Connected;
+                    !connection.removed
+                }
+                Err(status_code) => {
+                    connection.session_retry_policy.increment_retry_count();
+                    warn!("Connection {} was unsuccessful, error = {}, retries = {}", id, status_code, connection.session_retry_policy.retry_count());
+                    connection.last_error = Some(status_code);
+                    false
+                }
+            }
+        };
+        Self::end_attempt(connection);
+        if connected {
+            Self::notify_status_change(id, ConnectionStatus::Connected, status_callback);
+        }
+    }
+
+    /// Tells the status change callback, if there is one, that a connection has a new status
+    fn notify_status_change(id: &str, status: ConnectionStatus, status_callback: &StatusCallback) {
+        let mut status_callback = status_callback.lock().unwrap();
+        if let Some(ref mut status_callback) = *status_callback {
+            status_callback.on_managed_connection_status_change(id, status);
+        }
+    }
+
+    /// Ends an attempt to connect, disconnecting the session if the connection was removed
+    /// in the meantime
+    fn end_attempt(connection: &Mutex<Connection>) {
+        let session = {
+            let mut connection = connection.lock().unwrap();
+            connection.attempting = false;
+            if connection.removed { connection.session.take() } else { None }
+        };
+        if let Some(session) = session {
+            let mut session = trace_write_lock_unwrap!(session);
+            session.disconnect();
+        }
+    }
+
+    /// Establishes a session with the server, without retrying
+    pub(crate) fn connect(client: &Client, endpoint: &EndpointDescription, identity_token: IdentityToken, mut session_retry_policy: SessionRetryPolicy) -> Result<Arc<RwLock<Session>>, StatusCode> {
+        // The manager does the retrying, so the endpoints are asked for only once
+        let never = SessionRetryPolicy::never(session_retry_policy.session_timeout());
+        let session = client.new_session_for_endpoint(endpoint, identity_token, never)?;
+        {
+            let mut session = trace_write_lock_unwrap!(session);
+            session_retry_policy.reset_retry_count();
+            session.set_session_retry_policy(session_retry_policy);
+            session.connect_no_retry()?;
+            session.create_session()?;
+            session.activate_session()?;
+        }
+        Ok(session)
+    }
+}
@@ -0,0 +1,340 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! The keep-alive watchdog of a session. It reads the state of the server periodically to check
+//! that the server is still answering, which a hung server that keeps its TCP connection open
+//! would otherwise only show by user requests timing out. If too many keep-alives in a row go
+//! unanswered, or the server says it is no longer running, the connection is dropped so the
+//! session reconnects according to its retry policy. The watchdog is driven by the session's
+//! `poll()`, so it needs no thread or timer of its own.
+
+use std::{
+    convert::TryFrom,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use futures::sync::oneshot;
+
+use opcua_core::supported_message::SupportedMessage;
+use opcua_types::{
+    *,
+    node_ids::VariableId,
+    status_code::StatusCode,
+};
+
+use crate::session_state::{ConnectionState, SessionState};
+
+/// How often a session checks that its server is still responsive, and how many of these
+/// keep-alives can go unanswered before the connection is dropped and the session reconnects.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct KeepAliveConfig {
+    /// Interval in milliseconds between keep-alive reads of the server's state, or 0 for none.
+    interval: u32,
+    /// The number of keep-alives in a row that can go unanswered before the connection is
+    /// considered dead.
+    max_missed: u32,
+}
+
+impl Default for KeepAliveConfig {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_INTERVAL_MS, Self::DEFAULT_MAX_MISSED)
+    }
+}
+
+impl KeepAliveConfig {
+    /// The default interval between keep-alives in millis
+    pub const DEFAULT_INTERVAL_MS: u32 = 5000;
+    /// The default number of keep-alives that can be missed before the connection is dropped
+    pub const DEFAULT_MAX_MISSED: u32 = 3;
+
+    /// Creates a `KeepAliveConfig` with an interval and the number of keep-alives that can be
+    /// missed, which is at least 1. An interval of 0 turns the keep-alive off.
+    pub fn new(interval: u32, max_missed: u32) -> Self {
+        KeepAliveConfig {
+            interval,
+            max_missed: if max_missed == 0 { 1 } else { max_missed },
+        }
+    }
+
+    /// Creates a `KeepAliveConfig` that turns the keep-alive off
+    pub fn off() -> Self {
+        Self::new(0, Self::DEFAULT_MAX_MISSED)
+    }
+
+    pub fn interval(&self) -> u32 {
+        self.interval
+    }
+
+    pub fn max_missed(&self) -> u32 {
+        self.max_missed
+    }
+}
+
+/// What the watchdog should do when its timer fires
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum KeepAliveAction {
+    /// Send a keep-alive
+    Send,
+    /// Wait for the keep-alive that is still outstanding
+    Wait,
+    /// Too many keep-alives were missed, so the server is considered dead
+    Dead,
+}
+
+/// Counts the keep-alives that go unanswered. A keep-alive is missed if it fails, or has not
+/// been answered by the time the next one is due.
+#[derive(Debug)]
+pub(crate) struct KeepAliveCounter {
+    max_missed: u32,
+    missed: u32,
+    sent: bool,
+    outstanding: bool,
+    answered: bool,
+}
+
+impl KeepAliveCounter {
+    pub fn new(max_missed: u32) -> KeepAliveCounter {
+        KeepAliveCounter {
+            max_missed,
+            missed: 0,
+            sent: false,
+            outstanding: false,
+            answered: false,
+        }
+    }
+
+    /// Called each time a keep-alive is due, saying what to do
+    pub fn on_timer(&mut self) -> KeepAliveAction {
+        if self.sent {
+            if self.answered {
+                self.missed = 0;
+            } else {
+                self.missed += 1;
+                warn!("Keep-alive was missed, {} of {} in a row", self.missed, self.max_missed);
+            }
+        }
+        if self.missed >= self.max_missed {
+            KeepAliveAction::Dead
+        } else if self.outstanding {
+            KeepAliveAction::Wait
+        } else {
+            self.sent = true;
+            self.outstanding = true;
+            self.answered = false;
+            KeepAliveAction::Send
+        }
+    }
+
+    /// Called when the outstanding keep-alive is answered or fails
+    pub fn on_response(&mut self, answered: bool) {
+        self.outstanding = false;
+        self.answered = answered;
+    }
+
+    #[cfg(test)]
+    pub fn missed(&self) -> u32 {
+        self.missed
+    }
+}
+
+/// The watchdog of the current connection
+struct Watchdog {
+    /// How often a keep-alive is due
+    interval: Duration,
+    /// When the next keep-alive is due
+    next: Instant,
+    counter: KeepAliveCounter,
+    /// The response to the outstanding keep-alive
+    response: Option<oneshot::Receiver<Result<SupportedMessage, StatusCode>>>,
+}
+
+/// Starts and stops the keep-alive watchdog of a session, and remembers the last state that the
+/// server reported across reconnects.
+pub(crate) struct KeepAlive {
+    /// The watchdog, while it is running
+    watchdog: Option<Watchdog>,
+    /// The state the server last reported
+    server_state: Option<ServerState>,
+}
+
+impl KeepAlive {
+    pub fn new() -> KeepAlive {
+        KeepAlive {
+            watchdog: None,
+            server_state: None,
+        }
+    }
+
+    /// Returns the state the server last reported to the watchdog
+    pub fn server_state(&self) -> Option<ServerState> {
+        self.server_state
+    }
+
+    /// Stops the watchdog, if it is running
+    pub fn stop(&mut self) {
+        self.watchdog = None;
+    }
+
+    /// Starts a watchdog for the current connection of the session, stopping any previous one.
+    /// The first keep-alive is due after the interval.
+    pub fn start(&mut self, keep_alive_config: &KeepAliveConfig) {
+        let (interval, max_missed) = (keep_alive_config.interval(), keep_alive_config.max_missed());
+        if interval == 0 {
+            debug!("Keep-alive is off");
+            self.watchdog = None;
+            return;
+        }
+        debug!("Keep-alive every {} ms, giving up after {} are missed", interval, max_missed);
+        let interval = Duration::from_millis(u64::from(interval));
+        self.watchdog = Some(Watchdog {
+            interval,
+            next: Instant::now() + interval,
+            counter: KeepAliveCounter::new(max_missed),
+            response: None,
+        });
+    }
+
+    /// Takes the response to the outstanding keep-alive if it has arrived, and sends the next
+    /// keep-alive if it is due. The watchdog stops when the connection has finished or is
+    /// dropped. Returns `true` if it did something.
+    pub fn poll(&mut self, session_state: &Arc<RwLock<SessionState>>) -> bool {
+        let finished = {
+            let session_state = trace_read_lock_unwrap!(session_state);
+            let connection_state = session_state.connection_state();
+            let connection_state = trace_read_lock_unwrap!(connection_state);
+            matches!(*connection_state, ConnectionState::Finished(_))
+        };
+        if finished {
+            if self.watchdog.take().is_some() {
+                debug!("Keep-alive is finished");
+            }
+            return false;
+        }
+        let watchdog = if let Some(ref mut watchdog) = self.watchdog { watchdog } else { return false; };
+
+        let mut did_something = false;
+        let mut server_state = None;
+        if let Some(mut response) = watchdog.response.take() {
+            let result = match response.try_recv() {
+                Ok(None) => {
+                    watchdog.response = Some(response);
+                    None
+                }
+                Ok(Some(result)) => Some(result),
+                // The sender was dropped because the session was reset
+                Err(_) => Some(Err(StatusCode::BadConnectionClosed)),
+            };
+            if let Some(result) = result {
+                let state = match result {
+                    Ok(SupportedMessage::ReadResponse(response)) => response.results
+                        .and_then(|results| results.into_iter().next())
+                        .and_then(|data_value| data_value.value)
+                        .and_then(|value| Self::server_state_from_variant(&value)),
+                    Ok(response) => {
+                        debug!("Keep-alive failed, status = {}", crate::process_unexpected_response(response));
+                        None
+                    }
+                    Err(status_code) => {
+                        debug!("Keep-alive failed, status = {}", status_code);
+                        None
+                    }
+                };
+                watchdog.counter.on_response(state.is_some());
+                server_state = state;
+                did_something = true;
+            }
+        }
+
+        let now = Instant::now();
+        let mut dead = false;
+        if now >= watchdog.next {
+            watchdog.next = now + watchdog.interval;
+            did_something = true;
+            match watchdog.counter.on_timer() {
+                KeepAliveAction::Send => {
+                    let mut session_state = trace_write_lock_unwrap!(session_state);
+                    let node_id: NodeId = VariableId::Server_ServerStatus_State.into();
+                    let request = ReadRequest {
+                        request_header: session_state.make_request_header(),
+                        max_age: 1f64,
+                        timestamps_to_return: TimestampsToReturn::Server,
+                        nodes_to_read: Some(vec![ReadValueId::from(node_id)]),
+                    };
+                    match session_state.send_request_with_receiver(request) {
+                        Ok(response) => watchdog.response = Some(response),
+                        Err(status_code) => {
+                            debug!("Keep-alive could not be sent, status = {}", status_code);
+                            watchdog.counter.on_response(false);
+                        }
+                    }
+                }
+                KeepAliveAction::Wait => {}
+                KeepAliveAction::Dead => {
+                    error!("Server has not answered the last {} keep-alives, so the connection is dropped", watchdog.counter.max_missed);
+                    dead = true;
+                }
+            }
+        }
+
+        if let Some(server_state) = server_state {
+            self.on_server_state(session_state, server_state);
+        }
+        if dead {
+            Self::drop_connection(session_state, StatusCode::BadTimeout);
+        }
+        did_something
+    }
+
+    /// Reports a change to the state of the server, and drops the connection if the server is
+    /// no longer running normally.
+    fn on_server_state(&mut self, session_state: &Arc<RwLock<SessionState>>, server_state: ServerState) {
+        if self.server_state != Some(server_state) {
+            self.server_state = Some(server_state);
+            info!("Server state is {:?}", server_state);
+            let mut session_state = trace_write_lock_unwrap!(session_state);
+            session_state.on_server_state_change(server_state);
+        }
+        match server_state {
+            ServerState::Running | ServerState::Suspended | ServerState::Test => {}
+            ServerState::Shutdown => {
+                error!("Server is shutting down, so the connection is dropped");
+                Self::drop_connection(session_state, StatusCode::BadShutdown);
+            }
+            server_state => {
+                error!("Server state is {:?}, so the connection is dropped", server_state);
+                Self::drop_connection(session_state, StatusCode::BadServerHalted);
+            }
+        }
+    }
+
+    /// Finishes the connection, so the session will reconnect the next time it is polled
+    fn drop_connection(session_state: &Arc<RwLock<SessionState>>, status_code: StatusCode) {
+        let mut session_state = trace_write_lock_unwrap!(session_state);
+        {
+            let connection_state = session_state.connection_state();
+            let mut connection_state = trace_write_lock_unwrap!(connection_state);
+            if let ConnectionState::Finished(_) = *connection_state {
+                return;
+            }
+            *connection_state = ConnectionState::Finished(status_code);
+        }
+        session_state.quit();
+        session_state.on_connection_status_change(false);
+    }
+
+    fn server_state_from_variant(value: &Variant) -> Option<ServerState> {
+        match i32::try_from(value).ok()? {
+            0 => Some(ServerState::Running),
+            1 => Some(ServerState::Failed),
+            2 => Some(ServerState::NoConfiguration),
+            3 => Some(ServerState::Suspended),
+            4 => Some(ServerState::Shutdown),
+            5 => Some(ServerState::Test),
+            6 => Some(ServerState::CommunicationFault),
+            7 => Some(ServerState::Unknown),
+            _ => None,
+        }
+    }
+}
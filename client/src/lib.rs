@@ -104,6 +104,7 @@ extern crate serde_derive;
 use opcua_types::{response_header::ResponseHeader, status_code::StatusCode};
 use opcua_core::supported_message::SupportedMessage;
 
+mod async_session;
 mod comms;
 mod subscription;
 mod subscription_state;
@@ -118,6 +119,11 @@ mod session;
 mod callbacks;
 mod builder;
 mod session_retry;
+mod keep_alive;
+mod reverse_connect;
+mod connection_manager;
+mod redundancy;
+mod browser;
 
 /// Process the service result, i.e. where the request "succeeded" but the response
 /// contains a failure status code.
@@ -149,12 +155,19 @@ pub mod prelude {
     pub use opcua_types::{service_types::*, status_code::StatusCode};
 
     pub use crate::{
+        async_session::{AsyncSession, ResponseFuture, SubscriptionParams},
+        browser::{Browser, BrowseIter, BrowsedNode, BrowseTree},
         builder::*,
         callbacks::*,
         client::*,
         config::*,
+        connection_manager::{ConnectionHealth, ConnectionManager, ConnectionStatus},
+        keep_alive::KeepAliveConfig,
+        redundancy::{FailoverMode, RedundantSession},
+        reverse_connect::ReverseConnectListener,
         session::*,
-        subscription::MonitoredItem,
+        session_retry::RetryBackoff,
+        subscription::{MonitoredItem, SubscriptionRecovery},
     };
 }
 
@@ -2,11 +2,21 @@
 // SPDX-License-Identifier: MPL-2.0
 // Copyright (C) 2017-2020 Adam Lock
 
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    time::{Duration, Instant},
+};
 
-use futures::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use futures::sync::{
+    mpsc::{self, UnboundedReceiver, UnboundedSender},
+    oneshot,
+};
 
 use opcua_core::supported_message::SupportedMessage;
+use opcua_types::status_code::StatusCode;
+
+/// Completes the future of a request with its response or the reason there is none
+pub(crate) type ResponseSender = oneshot::Sender<Result<SupportedMessage, StatusCode>>;
 
 pub(crate) struct MessageQueue {
     /// The requests that are in-flight, defined by their request handle and an async flag. Basically,
@@ -15,6 +25,10 @@ pub(crate) struct MessageQueue {
     inflight_requests: HashSet<(u32, bool)>,
     /// A map of incoming responses waiting to be processed
     responses: HashMap<u32, (SupportedMessage, bool)>,
+    /// Requests whose responses complete a future rather than being taken from `responses`
+    response_senders: HashMap<u32, ResponseSender>,
+    /// The time each of those requests times out
+    response_deadlines: VecDeque<(Instant, u32)>,
     /// This is the queue that messages will be sent onto the transport for sending
     sender: Option<UnboundedSender<Message>>,
 }
@@ -29,6 +43,8 @@ impl MessageQueue {
         MessageQueue {
             inflight_requests: HashSet::new(),
             responses: HashMap::new(),
+            response_senders: HashMap::new(),
+            response_deadlines: VecDeque::new(),
             sender: None,
         }
     }
@@ -36,6 +52,9 @@ impl MessageQueue {
     pub(crate) fn clear(&mut self) {
         self.inflight_requests.clear();
         self.responses.clear();
+        // Dropping the senders fails their futures
+        self.response_senders.clear();
+        self.response_deadlines.clear();
     }
 
     // Creates the transmission queue that outgoing requests will be sent over
@@ -63,6 +82,55 @@ impl MessageQueue {
         self.send_message(Message::SupportedMessage(request));
     }
 
+    /// Called by the session to add a request whose response is sent to the sender. The request
+    /// fails if it cannot be queued for the transport, and times out if no response arrives in
+    /// the timeout.
+    pub(crate) fn add_request_with_sender(&mut self, request: SupportedMessage, timeout: Duration, sender: ResponseSender) -> Result<(), StatusCode> {
+        let request_handle = request.request_handle();
+        let queued = self.sender.as_ref()
+            .map(|s| s.unbounded_send(Message::SupportedMessage(request)).is_ok())
+            .unwrap_or(false);
+        if !queued {
+            debug!("Request {} cannot be sent because there is no connection", request_handle);
+            return Err(StatusCode::BadNotConnected);
+        }
+        let now = Instant::now();
+        self.response_senders.insert(request_handle, sender);
+        self.response_deadlines.push_back((now + timeout, request_handle));
+        self.expire_requests(now);
+        Ok(())
+    }
+
+    /// Fails the futures of requests whose timeout has passed
+    pub(crate) fn expire_requests(&mut self, now: Instant) {
+        // Requests can have different timeouts so every deadline is checked. Deadlines of requests
+        // that have already completed are dropped along the way.
+        let response_senders = &mut self.response_senders;
+        self.response_deadlines.retain(|(deadline, request_handle)| {
+            if !response_senders.contains_key(request_handle) {
+                false
+            } else if *deadline > now {
+                true
+            } else {
+                info!("Request {} has timed out", request_handle);
+                let sender = response_senders.remove(request_handle).unwrap();
+                let _ = sender.send(Err(StatusCode::BadTimeout));
+                false
+            }
+        });
+    }
+
+    /// Fails the futures of all requests awaiting a response, e.g. because the connection is gone
+    pub(crate) fn fail_requests(&mut self, status_code: StatusCode) {
+        if !self.response_senders.is_empty() {
+            info!("{} requests awaiting a response have failed with {}", self.response_senders.len(), status_code);
+        }
+        self.response_deadlines.clear();
+        self.response_senders.drain().for_each(|(_, sender)| {
+            let _ = sender.send(Err(status_code));
+        });
+    }
+
     pub(crate) fn quit(&mut self) {
         debug!("Sending a quit to the message receiver");
         self.send_message(Message::Quit);
@@ -80,6 +148,12 @@ impl MessageQueue {
     pub(crate) fn store_response(&mut self, response: SupportedMessage) {
         // Remove corresponding request handle from inflight queue, add to responses
         let request_handle = response.request_handle();
+        if let Some(sender) = self.response_senders.remove(&request_handle) {
+            debug!("Response to Request {} completes its future", request_handle);
+            let _ = sender.send(Ok(response));
+            self.expire_requests(Instant::now());
+            return;
+        }
         debug!("Response to Request {} has been stored", request_handle);
         // Remove the inflight request
         // This true / false is slightly clunky.
@@ -0,0 +1,689 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! Failover between the servers of a non-transparent redundant server set, OPC UA Part 4 6.6.2.
+
+use std::{
+    cmp::Reverse,
+    convert::TryFrom,
+    sync::{Arc, RwLock},
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use futures::Future;
+
+use opcua_types::{
+    node_id::NodeId,
+    node_ids::VariableId,
+    service_types::{ApplicationDescription, EndpointDescription, ReadValueId, RedundancySupport},
+    status_code::StatusCode,
+};
+
+use crate::{
+    async_session::AsyncSession,
+    client::{Client, IdentityToken},
+    connection_manager::ConnectionManager,
+    session::Session,
+    session_retry::{Answer, SessionRetryPolicy},
+};
+
+/// How a [`RedundantSession`] fails over between servers, named after the failover modes of OPC UA
+/// Part 4 6.6.2.4.5. In every mode the session moves to another server when the one it is on is
+/// lost, or its `ServiceLevel` drops below the healthy range and another server is healthier.
+///
+/// The mode is read from the `RedundancySupport` of the servers. There is only ever one session
+/// with subscriptions, so no mode keeps subscriptions on the backup servers as the Hot failover of
+/// Part 4 does. The subscriptions are transferred or recreated when the session moves.
+///
+/// [`RedundantSession`]: ./struct.RedundantSession.html
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum FailoverMode {
+    /// The backup servers may not be running until they are needed, so they are only looked at
+    /// when the active server is lost or degraded, and then tried in turn. While the active
+    /// server is degraded the backups are probed at most once per cold probe interval.
+    Cold,
+    /// The backup servers are running and are monitored through sessions of their own that read
+    /// their service level, so the session fails over to the healthiest of them.
+    Warm,
+    /// As `Warm`, and the session also moves whenever a backup server is healthier than the
+    /// active one, so it is always on the healthiest server. This is the mode for servers that
+    /// support Hot redundancy, but the backup servers have no subscriptions until the session
+    /// moves to them.
+    Hot,
+}
+
+impl FailoverMode {
+    /// Returns the failover mode for the redundancy that a server supports, or `None` if it is
+    /// not part of a non-transparent redundant server set.
+    pub fn from_redundancy_support(redundancy_support: RedundancySupport) -> Option<FailoverMode> {
+        match redundancy_support {
+            RedundancySupport::Cold => Some(FailoverMode::Cold),
+            RedundancySupport::Warm => Some(FailoverMode::Warm),
+            RedundancySupport::Hot | RedundancySupport::HotAndMirrored => Some(FailoverMode::Hot),
+            RedundancySupport::None | RedundancySupport::Transparent => None,
+        }
+    }
+
+    /// Tests if the backup servers are monitored all the time
+    fn monitors_backups(self) -> bool {
+        self != FailoverMode::Cold
+    }
+}
+
+/// The state of the redundant server set that the application can see
+struct RedundancyStatus {
+    /// The index of the server the session is on
+    active: usize,
+    /// The last service level read from each server
+    service_levels: Vec<Option<u8>>,
+    /// The number of times the session has moved to another server
+    failovers: u32,
+    /// Set when the retry policy gives up on finding a server
+    given_up: bool,
+    /// Service levels below this are degraded
+    min_service_level: u8,
+    /// Interval in milliseconds between service level checks
+    check_interval: u32,
+    /// Interval in milliseconds between probes of the backup servers in the cold mode
+    cold_probe_interval: u32,
+    /// The server the monitor thread wants the session to move to
+    fail_over_to: Option<usize>,
+}
+
+struct RedundantServer {
+    /// The endpoint to connect to
+    endpoint: EndpointDescription,
+    /// A session that reads the service level of the server while it is a backup
+    monitor: Option<Arc<RwLock<Session>>>,
+}
+
+/// A `RedundantSession` keeps a single [`Session`] connected to one server of a non-transparent
+/// redundant server set, and moves it to another server of the set when the server it is on is
+/// lost or degraded. The application holds on to the same session throughout, while its
+/// subscriptions are transferred to the new server, or recreated there if the servers do not
+/// mirror them, the same way as when a session reconnects.
+///
+/// A worker thread polls the session, which replaces `Session::run()`, and moves it. A monitor
+/// thread reads the `ServiceLevel` of the servers on an interval to decide where the session
+/// should be, so a backup server that is slow to answer does not hold up the session. When the
+/// session is lost, the servers are tried according to the client's retry policy, the healthiest
+/// first.
+///
+/// [`Session`]: ../session/struct.Session.html
+pub struct RedundantSession {
+    /// The session
+    session: Arc<RwLock<Session>>,
+    /// The failover mode
+    mode: FailoverMode,
+    /// The endpoints of the servers
+    endpoints: Vec<EndpointDescription>,
+    /// The state of the servers
+    status: Arc<RwLock<RedundancyStatus>>,
+    /// Tells the worker thread to stop
+    quit: Arc<RwLock<bool>>,
+    /// The worker thread
+    worker: Option<JoinHandle<()>>,
+}
+
+impl Drop for RedundantSession {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+impl RedundantSession {
+    /// Internal constant for the sleep interval of the worker when it had nothing to do
+    const POLL_SLEEP_INTERVAL: u64 = 10;
+    /// The default lowest service level of a healthy server, OPC UA Part 4 6.6.2.4.2
+    pub const DEFAULT_MIN_SERVICE_LEVEL: u8 = 200;
+    /// The default interval between service level checks in millis
+    pub const DEFAULT_CHECK_INTERVAL_MS: u32 = 1000;
+    /// The default interval between probes of the backup servers in the cold mode in millis
+    pub const DEFAULT_COLD_PROBE_INTERVAL_MS: u32 = 30000;
+
+    /// Connects a session to a server of a redundant server set and starts failing it over
+    /// between the servers of the set. The endpoints are tried in order, and the first server
+    /// that answers says what the failover mode is from its `RedundancySupport`, and which other
+    /// servers are in the set from its `ServerUriArray`. Servers in the set that none of the
+    /// endpoints reach are found with `FindServers` on that server and connected to through their
+    /// discovery urls, with the same security as the first endpoint that answered.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(RedundantSession)` - the session is connected and activated on one of the servers
+    /// * `Err(StatusCode)` - `BadInvalidArgument` if there are no endpoints, `BadNotSupported` if
+    ///   the server is not part of a non-transparent redundant server set, or the reason the last
+    ///   server could not be connected to
+    ///
+    pub fn new(client: Client, endpoints: Vec<EndpointDescription>, identity_token: IdentityToken) -> Result<RedundantSession, StatusCode> {
+        if endpoints.is_empty() {
+            error!("Cannot create a redundant session without any endpoints");
+            return Err(StatusCode::BadInvalidArgument);
+        }
+
+        let mut result = Err(StatusCode::BadNotConnected);
+        for (server, endpoint) in endpoints.iter().enumerate() {
+            info!("Redundant session is connecting to {}", endpoint.endpoint_url);
+            result = ConnectionManager::connect(&client, endpoint, identity_token.clone(), client.session_retry_policy().clone())
+                .map(|session| (server, session));
+            match result {
+                Ok(_) => break,
+                Err(status_code) => warn!("Redundant session cannot connect to {}, error = {}", endpoint.endpoint_url, status_code)
+            }
+        }
+        let (active, session) = match result {
+            Ok(result) => result,
+            Err(status_code) => {
+                error!("Redundant session cannot connect to any of its servers");
+                return Err(status_code);
+            }
+        };
+
+        let (mode, endpoints) = {
+            let mut session = trace_write_lock_unwrap!(session);
+            match Self::read_server_set(&mut session, &endpoints, active) {
+                Ok(server_set) => server_set,
+                Err(status_code) => {
+                    session.disconnect();
+                    return Err(status_code);
+                }
+            }
+        };
+        let mut servers = endpoints.iter().map(|endpoint| RedundantServer {
+            endpoint: endpoint.clone(),
+            monitor: None,
+        }).collect::<Vec<_>>();
+
+        // The session reads the service level of the server it is on
+        let mut service_levels = vec![None; servers.len()];
+        if mode.monitors_backups() {
+            servers.iter_mut().enumerate().filter(|(i, _)| *i != active).for_each(|(i, server)| {
+                service_levels[i] = RedundancyMonitor::monitor(&client, &identity_token, server);
+            });
+        }
+
+        let status = Arc::new(RwLock::new(RedundancyStatus {
+            active,
+            service_levels,
+            failovers: 0,
+            given_up: false,
+            min_service_level: Self::DEFAULT_MIN_SERVICE_LEVEL,
+            check_interval: Self::DEFAULT_CHECK_INTERVAL_MS,
+            cold_probe_interval: Self::DEFAULT_COLD_PROBE_INTERVAL_MS,
+            fail_over_to: None,
+        }));
+        let quit = Arc::new(RwLock::new(false));
+        let client = Arc::new(client);
+        {
+            let monitor = RedundancyMonitor {
+                client: client.clone(),
+                identity_token,
+                mode,
+                servers,
+                session: session.clone(),
+                status: status.clone(),
+                next_check: Instant::now(),
+                next_cold_probe: Instant::now(),
+            };
+            let quit = quit.clone();
+            // The monitor is not waited for when the redundant session stops because it may be
+            // connecting to a server that does not answer. It stops after its current check.
+            let _ = thread::spawn(move || monitor.run(quit));
+        }
+        let worker = {
+            let retry_policy = client.session_retry_policy().clone();
+            let worker = RedundancyWorker {
+                client,
+                endpoints: endpoints.clone(),
+                session: session.clone(),
+                status: status.clone(),
+                retry_policy,
+            };
+            let quit = quit.clone();
+            thread::spawn(move || worker.run(quit))
+        };
+
+        Ok(RedundantSession {
+            session,
+            mode,
+            endpoints,
+            status,
+            quit,
+            worker: Some(worker),
+        })
+    }
+
+    /// Reads the failover mode and the endpoints of the servers in the redundant server set from
+    /// the server a session is on
+    fn read_server_set(session: &mut Session, endpoints: &[EndpointDescription], active: usize) -> Result<(FailoverMode, Vec<EndpointDescription>), StatusCode> {
+        let endpoint = &endpoints[active];
+        let redundancy_support = session.read_redundancy_support()?;
+        let mode = FailoverMode::from_redundancy_support(redundancy_support).ok_or_else(|| {
+            error!("Server {} has a redundancy support of {:?}, so it is not part of a non-transparent redundant server set", endpoint.endpoint_url, redundancy_support);
+            StatusCode::BadNotSupported
+        })?;
+        let server_uris = session.read_server_uri_array().unwrap_or_else(|status_code| {
+            warn!("Cannot read the server uris of {}, error = {}", endpoint.endpoint_url, status_code);
+            Vec::new()
+        });
+        let servers = if server_uris.is_empty() {
+            Vec::new()
+        } else {
+            session.find_servers_with_uris(endpoint.endpoint_url.clone(), &server_uris).unwrap_or_else(|status_code| {
+                warn!("Cannot find the servers of the redundant server set on {}, error = {}", endpoint.endpoint_url, status_code);
+                Vec::new()
+            })
+        };
+        let endpoints = server_set_endpoints(endpoints, endpoint, &server_uris, &servers);
+        info!("Redundant session is on {} in {:?} mode, with {} servers in its set", endpoint.endpoint_url, mode, endpoints.len());
+        Ok((mode, endpoints))
+    }
+
+    /// Returns the session, which stays the same whichever server it is on
+    pub fn session(&self) -> Arc<RwLock<Session>> {
+        self.session.clone()
+    }
+
+    /// Returns the failover mode, which is read from the server
+    pub fn mode(&self) -> FailoverMode {
+        self.mode
+    }
+
+    /// Returns the endpoints of the servers, which are the endpoints the session was created with
+    /// followed by those of the other servers in the set
+    pub fn endpoints(&self) -> &[EndpointDescription] {
+        &self.endpoints
+    }
+
+    /// Returns the index into the endpoints of the server the session is on, or was on last if
+    /// it is failing over
+    pub fn active_server(&self) -> usize {
+        trace_read_lock_unwrap!(self.status).active
+    }
+
+    /// Returns the service level last read from each server, or `None` for a server that could
+    /// not be read or is not monitored
+    pub fn service_levels(&self) -> Vec<Option<u8>> {
+        trace_read_lock_unwrap!(self.status).service_levels.clone()
+    }
+
+    /// Returns the number of times the session has moved to another server
+    pub fn failover_count(&self) -> u32 {
+        trace_read_lock_unwrap!(self.status).failovers
+    }
+
+    /// Tests if the retry policy has given up on finding a server to connect to
+    pub fn has_given_up(&self) -> bool {
+        trace_read_lock_unwrap!(self.status).given_up
+    }
+
+    /// Sets the lowest service level of a healthy server. The session fails over from a server
+    /// whose service level is lower if another server is healthier.
+    pub fn set_min_service_level(&self, min_service_level: u8) {
+        trace_write_lock_unwrap!(self.status).min_service_level = min_service_level;
+    }
+
+    /// Sets the interval in milliseconds between reads of the servers' service levels
+    pub fn set_check_interval(&self, check_interval: u32) {
+        trace_write_lock_unwrap!(self.status).check_interval = check_interval;
+    }
+
+    /// Sets the interval in milliseconds between probes of the backup servers in the cold mode,
+    /// each of which connects a session to every backup server to read its service level
+    pub fn set_cold_probe_interval(&self, cold_probe_interval: u32) {
+        trace_write_lock_unwrap!(self.status).cold_probe_interval = cold_probe_interval;
+    }
+
+    /// Stops the worker threads and disconnects the session. The redundant session cannot be used
+    /// afterwards.
+    pub fn stop(&mut self) {
+        if let Some(worker) = self.worker.take() {
+            {
+                let mut quit = trace_write_lock_unwrap!(self.quit);
+                *quit = true;
+            }
+            let _ = worker.join();
+            let mut session = trace_write_lock_unwrap!(self.session);
+            session.disconnect();
+        }
+    }
+}
+
+/// The worker thread of a redundant session, which polls the session and moves it
+struct RedundancyWorker {
+    /// The client that endpoints are found with
+    client: Arc<Client>,
+    /// The endpoints of the servers
+    endpoints: Vec<EndpointDescription>,
+    /// The session
+    session: Arc<RwLock<Session>>,
+    /// The state of the servers
+    status: Arc<RwLock<RedundancyStatus>>,
+    /// Paces the attempts to find a server when the session is lost
+    retry_policy: SessionRetryPolicy,
+}
+
+impl RedundancyWorker {
+    fn run(mut self, quit: Arc<RwLock<bool>>) {
+        debug!("Redundant session worker is running");
+        loop {
+            if *trace_read_lock_unwrap!(quit) {
+                break;
+            }
+            // If the worker did nothing, then sleep for a moment to save some CPU
+            if !self.poll() {
+                thread::sleep(Duration::from_millis(RedundantSession::POLL_SLEEP_INTERVAL));
+            }
+        }
+        debug!("Redundant session worker has stopped");
+    }
+
+    /// Polls the session, moves it if the monitor has found a healthier server, and finds
+    /// another server if the session has been lost
+    fn poll(&mut self) -> bool {
+        let (mut did_something, connected) = {
+            let mut session = trace_write_lock_unwrap!(self.session);
+            if session.is_connected() {
+                (session.poll().unwrap_or(true), session.is_connected())
+            } else {
+                (false, false)
+            }
+        };
+        let fail_over_to = trace_write_lock_unwrap!(self.status).fail_over_to.take();
+        if let Some(server) = fail_over_to {
+            if connected {
+                let _ = self.fail_over(server);
+                did_something = true;
+            }
+        }
+        let connected = trace_read_lock_unwrap!(self.session).is_connected();
+        if !connected && self.recover() {
+            did_something = true;
+        }
+        did_something
+    }
+
+    /// Tries the next server when the retry policy says to. Returns `true` if it did something.
+    fn recover(&mut self) -> bool {
+        let (active, service_levels, given_up) = {
+            let status = trace_read_lock_unwrap!(self.status);
+            (status.active, status.service_levels.clone(), status.given_up)
+        };
+        if given_up {
+            return false;
+        }
+        let now = Utc::now();
+        match self.retry_policy.should_retry_connect(now) {
+            Answer::GiveUp => {
+                error!("Redundant session has given up trying to connect to a server after {} retries", self.retry_policy.retry_count());
+                trace_write_lock_unwrap!(self.status).given_up = true;
+                true
+            }
+            Answer::WaitFor(_) => false,
+            Answer::Retry => {
+                self.retry_policy.set_last_attempt(now);
+                // The servers are tried in turn, the healthiest first and the lost server last
+                let candidates = candidates(&service_levels, Some(active));
+                let server = candidates[self.retry_policy.retry_count() as usize % candidates.len()];
+                if let Err(status_code) = self.fail_over(server) {
+                    self.retry_policy.increment_retry_count();
+                    warn!("Redundant session cannot connect to server {}, error = {}, retries = {}", server, status_code, self.retry_policy.retry_count());
+                }
+                true
+            }
+        }
+    }
+
+    /// Moves the session to a server
+    fn fail_over(&mut self, server: usize) -> Result<(), StatusCode> {
+        let never = SessionRetryPolicy::never(self.retry_policy.session_timeout());
+        let endpoint = self.client.server_endpoint_for(&self.endpoints[server], &never)?;
+        {
+            let mut session = trace_write_lock_unwrap!(self.session);
+            session.fail_over(endpoint)?;
+        }
+        self.retry_policy.reset_retry_count();
+        let mut status = trace_write_lock_unwrap!(self.status);
+        if status.active != server {
+            info!("Redundant session has failed over from server {} to server {}", status.active, server);
+            status.active = server;
+            status.failovers += 1;
+        }
+        Ok(())
+    }
+}
+
+/// The monitor thread of a redundant session, which reads the service levels of the servers
+struct RedundancyMonitor {
+    /// The client that monitor sessions are created from
+    client: Arc<Client>,
+    /// The identity to activate monitor sessions with
+    identity_token: IdentityToken,
+    /// The failover mode
+    mode: FailoverMode,
+    /// The servers
+    servers: Vec<RedundantServer>,
+    /// The session
+    session: Arc<RwLock<Session>>,
+    /// The state of the servers
+    status: Arc<RwLock<RedundancyStatus>>,
+    /// When the service levels are next read
+    next_check: Instant,
+    /// When the backup servers may next be probed in the cold mode
+    next_cold_probe: Instant,
+}
+
+impl RedundancyMonitor {
+    fn run(mut self, quit: Arc<RwLock<bool>>) {
+        debug!("Redundant session monitor is running");
+        loop {
+            if *trace_read_lock_unwrap!(quit) {
+                break;
+            }
+            let now = Instant::now();
+            if now >= self.next_check {
+                let check_interval = trace_read_lock_unwrap!(self.status).check_interval;
+                self.next_check = now + Duration::from_millis(u64::from(check_interval));
+                self.check();
+            } else {
+                thread::sleep(Duration::from_millis(RedundantSession::POLL_SLEEP_INTERVAL));
+            }
+        }
+        self.servers.iter_mut().for_each(Self::stop_monitor);
+        debug!("Redundant session monitor has stopped");
+    }
+
+    /// Reads the service levels of the servers and asks the worker to fail over from a degraded
+    /// server, or in the hot mode from any server that is less healthy than a backup
+    fn check(&mut self) {
+        let (active, min_service_level, cold_probe_interval, mut service_levels) = {
+            let status = trace_read_lock_unwrap!(self.status);
+            (status.active, status.min_service_level, status.cold_probe_interval, status.service_levels.clone())
+        };
+        // The active server is read without locking the session, so the session is still polled
+        let async_session = {
+            let session = trace_read_lock_unwrap!(self.session);
+            if session.is_connected() { Some(session.async_session()) } else { None }
+        };
+        let active_level = async_session.and_then(|async_session| Self::read_service_level(&async_session));
+        let degraded = active_level.map(|level| level < min_service_level).unwrap_or(false);
+        service_levels[active] = active_level;
+        // The session reads the service level of the server it is on
+        Self::stop_monitor(&mut self.servers[active]);
+
+        // Cold backups are only looked at while the active server is degraded, and not every time
+        let now = Instant::now();
+        let monitor_backups = self.mode.monitors_backups();
+        if monitor_backups || (degraded && now >= self.next_cold_probe) {
+            if !monitor_backups {
+                self.next_cold_probe = now + Duration::from_millis(u64::from(cold_probe_interval));
+            }
+            let (client, identity_token) = (&self.client, &self.identity_token);
+            self.servers.iter_mut().enumerate().filter(|(i, _)| *i != active).for_each(|(i, server)| {
+                service_levels[i] = Self::monitor(client, identity_token, server);
+                if !monitor_backups {
+                    Self::stop_monitor(server);
+                }
+            });
+        }
+
+        let fail_over_to = fail_over_target(self.mode, &service_levels, active, min_service_level);
+        let mut status = trace_write_lock_unwrap!(self.status);
+        status.service_levels = service_levels;
+        // The session may have moved while the servers were read
+        if let Some(server) = fail_over_to {
+            if status.active == active {
+                warn!("Server {} has a service level of {:?} and server {} of {:?}, so the session is failing over", active, active_level, server, status.service_levels[server]);
+                status.fail_over_to = Some(server);
+            }
+        }
+    }
+
+    /// Reads the service level of the server a session is on
+    fn read_service_level(async_session: &AsyncSession) -> Option<u8> {
+        let node_id: NodeId = VariableId::Server_ServiceLevel.into();
+        let result = async_session.read(&[ReadValueId::from(node_id)]).wait();
+        match result {
+            Ok(results) => results.into_iter().next()
+                .filter(|data_value| !data_value.status.map(|s| s.is_bad()).unwrap_or(false))
+                .and_then(|data_value| data_value.value)
+                .and_then(|value| u8::try_from(&value).ok()),
+            Err(status_code) => {
+                debug!("Cannot read the service level of the active server, error = {}", status_code);
+                None
+            }
+        }
+    }
+
+    /// Reads the service level of a backup server through its monitor session, connecting the
+    /// session first if it is not connected. Returns `None` if the server cannot be read.
+    fn monitor(client: &Client, identity_token: &IdentityToken, server: &mut RedundantServer) -> Option<u8> {
+        let connected = server.monitor.as_ref().map(|monitor| trace_read_lock_unwrap!(monitor).is_connected()).unwrap_or(false);
+        if !connected {
+            Self::stop_monitor(server);
+            match ConnectionManager::connect(client, &server.endpoint, identity_token.clone(), client.session_retry_policy().clone()) {
+                Ok(monitor) => server.monitor = Some(monitor),
+                Err(status_code) => {
+                    debug!("Cannot monitor server {}, error = {}", server.endpoint.endpoint_url, status_code);
+                    return None;
+                }
+            }
+        }
+        server.monitor.as_ref().and_then(|monitor| {
+            let mut monitor = trace_write_lock_unwrap!(monitor);
+            monitor.read_service_level().ok()
+        })
+    }
+
+    fn stop_monitor(server: &mut RedundantServer) {
+        if let Some(monitor) = server.monitor.take() {
+            let mut monitor = trace_write_lock_unwrap!(monitor);
+            monitor.disconnect();
+        }
+    }
+}
+
+/// Returns the endpoints with one added for each server of the redundant server set that they do
+/// not reach. The servers found with `FindServers` that are in the set are reached through their
+/// first discovery url, with the same security as the endpoint the session is on.
+fn server_set_endpoints(endpoints: &[EndpointDescription], active: &EndpointDescription, server_uris: &[String], servers: &[ApplicationDescription]) -> Vec<EndpointDescription> {
+    let added = servers.iter()
+        .filter(|server| server_uris.iter().any(|uri| uri.as_str() == server.application_uri.as_ref()))
+        .filter_map(|server| server.discovery_urls.as_ref())
+        .filter(|discovery_urls| !discovery_urls.iter().any(|url| endpoints.iter().any(|endpoint| endpoint.endpoint_url == *url)))
+        .filter_map(|discovery_urls| discovery_urls.first())
+        .map(|url| EndpointDescription::from((url.as_ref(), active.security_policy_uri.as_ref(), active.security_mode)));
+    endpoints.iter().cloned().chain(added).collect()
+}
+
+/// Orders the servers to try connecting to, those with the highest service level first, then
+/// those whose service level is unknown, in turn from the active server, and the active server
+/// last.
+fn candidates(service_levels: &[Option<u8>], active: Option<usize>) -> Vec<usize> {
+    let servers = service_levels.len();
+    let first = active.map(|active| active + 1).unwrap_or(0);
+    let mut candidates = (0..servers).map(|i| (first + i) % servers).collect::<Vec<_>>();
+    candidates.sort_by_key(|server| {
+        if Some(*server) == active {
+            (2, Reverse(0))
+        } else if let Some(level) = service_levels[*server] {
+            (0, Reverse(level))
+        } else {
+            (1, Reverse(0))
+        }
+    });
+    candidates
+}
+
+/// Returns the server that the session should move to from the active one, which is the
+/// healthiest backup if it is healthier than the active server and either the active server is
+/// degraded or the mode is hot. The session stays where it is while the active server's service
+/// level is unknown, because the session is then being recovered.
+fn fail_over_target(mode: FailoverMode, service_levels: &[Option<u8>], active: usize, min_service_level: u8) -> Option<usize> {
+    let active_level = service_levels[active]?;
+    let degraded = active_level < min_service_level;
+    candidates(service_levels, Some(active)).into_iter().next()
+        .filter(|server| *server != active)
+        .and_then(|server| service_levels[server].map(|level| (server, level)))
+        .filter(|(_, level)| *level > active_level && (degraded || mode == FailoverMode::Hot))
+        .map(|(server, _)| server)
+}
+
+#[test]
+fn redundancy_candidates() {
+    // The healthiest servers first, then unknown servers in turn, and the active server last
+    assert_eq!(candidates(&[Some(255), None, Some(100), None], Some(0)), vec![2, 1, 3, 0]);
+    assert_eq!(candidates(&[None, Some(10), Some(250), None], Some(2)), vec![1, 3, 0, 2]);
+    assert_eq!(candidates(&[None, None, None], Some(1)), vec![2, 0, 1]);
+    assert_eq!(candidates(&[Some(200), Some(200), Some(255)], None), vec![2, 0, 1]);
+    assert_eq!(candidates(&[None, None], None), vec![0, 1]);
+
+    // A healthy server is only left in the hot mode, and only for a healthier one
+    assert_eq!(fail_over_target(FailoverMode::Warm, &[Some(250), Some(255)], 0, 200), None);
+    assert_eq!(fail_over_target(FailoverMode::Hot, &[Some(250), Some(255)], 0, 200), Some(1));
+    assert_eq!(fail_over_target(FailoverMode::Hot, &[Some(255), Some(250)], 0, 200), None);
+    assert_eq!(fail_over_target(FailoverMode::Cold, &[Some(100), None, Some(150)], 0, 200), Some(2));
+    assert_eq!(fail_over_target(FailoverMode::Warm, &[Some(100), Some(50)], 0, 200), None);
+    assert_eq!(fail_over_target(FailoverMode::Warm, &[None, Some(255)], 0, 200), None);
+
+    assert_eq!(FailoverMode::from_redundancy_support(RedundancySupport::HotAndMirrored), Some(FailoverMode::Hot));
+    assert_eq!(FailoverMode::from_redundancy_support(RedundancySupport::Transparent), None);
+}
+
+#[test]
+fn redundancy_server_set_endpoints() {
+    use opcua_crypto::SecurityPolicy;
+    use opcua_types::{ApplicationType, LocalizedText, MessageSecurityMode, UAString};
+
+    let server = |uri: &str, urls: &[&str]| ApplicationDescription {
+        application_uri: UAString::from(uri),
+        product_uri: UAString::null(),
+        application_name: LocalizedText::null(),
+        application_type: ApplicationType::Server,
+        gateway_server_uri: UAString::null(),
+        discovery_profile_uri: UAString::null(),
+        discovery_urls: Some(urls.iter().map(|url| UAString::from(*url)).collect()),
+    };
+    let endpoint = |url: &str| EndpointDescription::from((url, SecurityPolicy::Basic256Sha256.to_uri(), MessageSecurityMode::SignAndEncrypt));
+    let endpoints = vec![endpoint("opc.tcp://primary:4855/"), endpoint("opc.tcp://backup1:4855/")];
+    let server_uris = ["urn:primary".to_string(), "urn:backup1".to_string(), "urn:backup2".to_string()];
+    let servers = [
+        server("urn:primary", &["opc.tcp://primary:4855/"]),
+        server("urn:backup1", &["opc.tcp://backup1:4855/"]),
+        server("urn:backup2", &["opc.tcp://backup2:4855/", "https://backup2:443/"]),
+        server("urn:other", &["opc.tcp://other:4855/"]),
+    ];
+
+    // Servers outside the set and those the endpoints reach already are not added
+    let set_endpoints = server_set_endpoints(&endpoints, &endpoints[0], &server_uris, &servers);
+    assert_eq!(set_endpoints.len(), 3);
+    assert_eq!(&set_endpoints[..2], &endpoints[..]);
+    assert_eq!(set_endpoints[2].endpoint_url.as_ref(), "opc.tcp://backup2:4855/");
+    assert_eq!(set_endpoints[2].security_policy_uri, endpoints[0].security_policy_uri);
+    assert_eq!(set_endpoints[2].security_mode, MessageSecurityMode::SignAndEncrypt);
+
+    assert_eq!(server_set_endpoints(&endpoints, &endpoints[0], &[], &servers), endpoints);
+}
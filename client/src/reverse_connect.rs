@@ -0,0 +1,156 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! Provides the [`ReverseConnectListener`] which accepts connections from servers that use
+//! Reverse Connect, OPC UA Part 6 7.1.3. The server opens the connection and sends a ReverseHello
+//! naming itself and its endpoint, after which the client continues with the usual Hello and
+//! OpenSecureChannel as if it had opened the connection itself.
+//!
+//! [`ReverseConnectListener`]: ./struct.ReverseConnectListener.html
+
+use std::io::{Cursor, Read};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use opcua_core::comms::tcp_types::{MESSAGE_HEADER_LEN, MessageHeader, MessageType, ReverseHelloMessage};
+use opcua_types::{encoding::{BinaryEncoder, DecodingLimits}, status_code::StatusCode};
+
+/// Listens for connections from servers that use Reverse Connect. Each connection begins with a
+/// ReverseHello that says which server it is from. A session asks the listener for a connection
+/// from the server it wants and connections from other servers are held until a session asks for
+/// them, so one listener can serve sessions to many servers.
+///
+/// A server keeps one connection waiting for the client and opens another once it is used, so
+/// a session that reconnects receives a fresh connection from the same server.
+pub struct ReverseConnectListener {
+    listener: TcpListener,
+    /// Time to wait for a server to connect
+    timeout: Duration,
+    /// Connections from servers that no session has asked for yet
+    pending: Mutex<Vec<(ReverseHelloMessage, TcpStream)>>,
+}
+
+impl ReverseConnectListener {
+    /// Default time in milliseconds to wait for a server to connect
+    pub const DEFAULT_TIMEOUT_MS: u64 = 30000;
+    /// Time in milliseconds for a server to send its ReverseHello after it connects
+    const REVERSE_HELLO_TIMEOUT_MS: u64 = 5000;
+    const POLLING_INTERVAL_MS: u64 = 50;
+
+    /// Binds a listener to the address, e.g. "0.0.0.0:4841". The client url that servers are
+    /// configured with must resolve to this address.
+    pub fn bind<T>(address: T) -> Result<ReverseConnectListener, StatusCode> where T: ToSocketAddrs {
+        let listener = TcpListener::bind(address).map_err(|err| {
+            error!("Cannot bind reverse connect listener, {}", err);
+            StatusCode::BadResourceUnavailable
+        })?;
+        // Accepting polls so that the wait can time out
+        listener.set_nonblocking(true).map_err(|_| StatusCode::BadResourceUnavailable)?;
+        Ok(ReverseConnectListener {
+            listener,
+            timeout: Duration::from_millis(Self::DEFAULT_TIMEOUT_MS),
+            pending: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Sets how long a session waits for its server to connect before the attempt fails
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    /// Returns the address that the listener is bound to
+    pub fn local_addr(&self) -> Result<SocketAddr, StatusCode> {
+        self.listener.local_addr().map_err(|_| StatusCode::BadUnexpectedError)
+    }
+
+    /// Waits for a connection from the server with the uri. If an endpoint url is supplied, the
+    /// ReverseHello must also name that endpoint, ignoring the host name. Connections from other
+    /// servers that arrive in the meantime are held for later.
+    pub(crate) fn accept(&self, server_uri: &str, endpoint_url: Option<&str>) -> Result<TcpStream, StatusCode> {
+        let deadline = Instant::now() + self.timeout;
+        loop {
+            if let Some(socket) = self.take_pending(server_uri, endpoint_url) {
+                return Ok(socket);
+            }
+            match self.listener.accept() {
+                Ok((socket, addr)) => {
+                    match Self::read_reverse_hello(&socket) {
+                        Ok(reverse_hello) => {
+                            info!("Server {} connected from {} with endpoint {}", reverse_hello.server_uri, addr, reverse_hello.endpoint_url);
+                            if reverse_hello.matches(server_uri, endpoint_url) {
+                                return Ok(socket);
+                            }
+                            self.add_pending(reverse_hello, socket);
+                        }
+                        Err(status_code) => {
+                            error!("Connection from {} did not begin with a valid reverse hello, {}", addr, status_code);
+                        }
+                    }
+                }
+                Err(ref err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                    if Instant::now() >= deadline {
+                        error!("Server {} did not connect within {} ms", server_uri, self.timeout.as_millis());
+                        return Err(StatusCode::BadTimeout);
+                    }
+                    thread::sleep(Duration::from_millis(Self::POLLING_INTERVAL_MS));
+                }
+                Err(err) => {
+                    error!("Reverse connect listener cannot accept a connection, {}", err);
+                    return Err(StatusCode::BadCommunicationError);
+                }
+            }
+        }
+    }
+
+    /// Takes a held connection from the server, if there is one
+    fn take_pending(&self, server_uri: &str, endpoint_url: Option<&str>) -> Option<TcpStream> {
+        let mut pending = trace_lock_unwrap!(self.pending);
+        let idx = pending.iter().position(|(reverse_hello, _)| reverse_hello.matches(server_uri, endpoint_url))?;
+        Some(pending.remove(idx).1)
+    }
+
+    /// Holds the connection for a later session. A server only opens another connection once its
+    /// last one has closed or been used, so a held connection from the same server and endpoint
+    /// is replaced rather than kept.
+    fn add_pending(&self, reverse_hello: ReverseHelloMessage, socket: TcpStream) {
+        let mut pending = trace_lock_unwrap!(self.pending);
+        pending.retain(|(r, _)| r.server_uri != reverse_hello.server_uri || r.endpoint_url != reverse_hello.endpoint_url);
+        pending.push((reverse_hello, socket));
+    }
+
+    /// Reads the ReverseHello which must be the first message on the connection
+    fn read_reverse_hello(mut socket: &TcpStream) -> Result<ReverseHelloMessage, StatusCode> {
+        let decoding_limits = DecodingLimits::default();
+        socket.set_nonblocking(false).map_err(|_| StatusCode::BadCommunicationError)?;
+        socket.set_read_timeout(Some(Duration::from_millis(Self::REVERSE_HELLO_TIMEOUT_MS))).map_err(|_| StatusCode::BadCommunicationError)?;
+
+        let mut buf = vec![0u8; MESSAGE_HEADER_LEN];
+        socket.read_exact(&mut buf).map_err(|_| StatusCode::BadCommunicationError)?;
+        let message_header = MessageHeader::decode(&mut Cursor::new(&buf), &decoding_limits)?;
+        // Header, then a server uri and endpoint url of up to 4096 bytes each
+        let message_size = message_header.message_size as usize;
+        if message_header.message_type != MessageType::ReverseHello || !(MESSAGE_HEADER_LEN..=MESSAGE_HEADER_LEN + 2 * (4 + 4096)).contains(&message_size) {
+            return Err(StatusCode::BadTcpMessageTypeInvalid);
+        }
+        buf.resize(message_size, 0u8);
+        socket.read_exact(&mut buf[MESSAGE_HEADER_LEN..]).map_err(|_| StatusCode::BadCommunicationError)?;
+        socket.set_read_timeout(None).map_err(|_| StatusCode::BadCommunicationError)?;
+
+        let reverse_hello = ReverseHelloMessage::decode(&mut Cursor::new(&buf), &decoding_limits)?;
+        if reverse_hello.is_valid() {
+            Ok(reverse_hello)
+        } else {
+            Err(StatusCode::BadTcpMessageTypeInvalid)
+        }
+    }
+}
+
+/// The listener and server that a session takes its connections from instead of connecting out
+#[derive(Clone)]
+pub(crate) struct ReverseConnect {
+    pub listener: Arc<ReverseConnectListener>,
+    pub server_uri: String,
+}
@@ -8,7 +8,7 @@
 //! The session also has async functionality but that is reserved for publish requests on subscriptions
 //! and events.
 use std::{
-    cmp, collections::HashSet, convert::TryFrom, result::Result, str::FromStr, sync::{Arc, mpsc, Mutex, RwLock},
+    cmp, collections::{HashMap, HashSet}, convert::TryFrom, result::Result, str::FromStr, sync::{Arc, mpsc, Mutex, RwLock},
     thread,
     time::{Duration, Instant},
 };
@@ -31,18 +31,22 @@ use opcua_core::{
 use opcua_crypto::{self as crypto, CertificateStore, SecurityPolicy, user_identity::make_user_name_identity_token, X509};
 use opcua_types::{
     *,
-    node_ids::{MethodId, ObjectId},
+    node_ids::{MethodId, ObjectId, VariableId},
     status_code::StatusCode,
 };
 
 use crate::{
+    async_session::AsyncSession,
+    browser::Browser,
     callbacks::{OnConnectionStatusChange, OnSessionClosed, OnSubscriptionNotification},
     client,
-    comms::tcp_transport::TcpTransport,
+    comms::{https_transport::HttpsTransport, tcp_transport::TcpTransport, transport::Transport},
+    keep_alive::{KeepAlive, KeepAliveConfig},
     message_queue::MessageQueue,
+    reverse_connect::ReverseConnect,
     session_retry::{Answer, SessionRetryPolicy},
     session_state::{ConnectionState, SessionState},
-    subscription::{self, Subscription},
+    subscription::{self, SequenceNumberCheck, Subscription, SubscriptionRecovery},
     subscription_state::SubscriptionState,
     subscription_timer::{SubscriptionTimer, SubscriptionTimerCommand},
 };
@@ -99,6 +103,16 @@ impl Into<SessionInfo> for (EndpointDescription, client::IdentityToken) {
     }
 }
 
+/// The type of the value of a variable, looked up before a value is written to it.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ValueType {
+    /// The built-in type that values are converted to, or `None` if the data type is abstract or
+    /// a structure and values are written as they are.
+    pub variant_type: Option<VariantTypeId>,
+    /// The value rank of the variable, i.e. whether it holds a scalar or an array
+    pub value_rank: i32,
+}
+
 /// A `Session` runs in a loop, which can be terminated by sending it a `SessionCommand`.
 pub enum SessionCommand {
     /// Stop running as soon as possible
@@ -124,8 +138,8 @@ pub struct Session {
     subscription_state: Arc<RwLock<SubscriptionState>>,
     /// Subscription timer command.
     timer_command_queue: UnboundedSender<SubscriptionTimerCommand>,
-    /// Transport layer.
-    transport: TcpTransport,
+    /// Transport layer, opc.tcp or https according to the endpoint url.
+    transport: Box<dyn Transport + Send + Sync>,
     /// Certificate store.
     certificate_store: Arc<RwLock<CertificateStore>>,
     /// Secure channel information.
@@ -134,6 +148,20 @@ pub struct Session {
     message_queue: Arc<RwLock<MessageQueue>>,
     /// Session retry policy.
     session_retry_policy: SessionRetryPolicy,
+    /// How often the keep-alive checks that the server is answering.
+    keep_alive_config: KeepAliveConfig,
+    /// Data types learned from the server for decoding and encoding dynamic structures.
+    dynamic_types: DynamicTypes,
+    /// The value types of variables that have been written with `write_value()`.
+    value_types: HashMap<NodeId, ValueType>,
+    /// The nodes that paths looked up with `node_id_for_path()` lead to.
+    path_node_ids: HashMap<String, NodeId>,
+    /// The registered node ids of paths registered with `register_paths()`.
+    registered_path_node_ids: HashMap<String, NodeId>,
+    /// The server's namespace table, read from its `NamespaceArray`.
+    namespace_map: NamespaceMap,
+    /// Watchdog that checks the server is still answering.
+    keep_alive: KeepAlive,
 }
 
 impl Drop for Session {
@@ -152,19 +180,20 @@ impl Session {
     /// * `application_description` - information about the client that will be provided to the server
     /// * `certificate_store` - certificate management on disk
     /// * `session_info` - information required to establish a new session.
+    /// * `reverse_connect` - where to take connections from if the server connects to the client
     ///
     /// # Returns
     ///
     /// * `Session` - the interface that shall be used to communicate between the client and the server.
     ///
-    pub(crate) fn new(application_description: ApplicationDescription, certificate_store: Arc<RwLock<CertificateStore>>, session_info: SessionInfo, session_retry_policy: SessionRetryPolicy) -> Session {
+    pub(crate) fn new(application_description: ApplicationDescription, certificate_store: Arc<RwLock<CertificateStore>>, session_info: SessionInfo, session_retry_policy: SessionRetryPolicy, keep_alive_config: KeepAliveConfig, reverse_connect: Option<ReverseConnect>) -> Session {
         // TODO take these from the client config
         let decoding_limits = DecodingLimits::default();
 
         let secure_channel = Arc::new(RwLock::new(SecureChannel::new(certificate_store.clone(), Role::Client, decoding_limits)));
         let message_queue = Arc::new(RwLock::new(MessageQueue::new()));
         let session_state = Arc::new(RwLock::new(SessionState::new(secure_channel.clone(), message_queue.clone())));
-        let transport = TcpTransport::new(secure_channel.clone(), session_state.clone(), message_queue.clone());
+        let transport = Self::new_transport(session_info.endpoint.endpoint_url.as_ref(), &certificate_store, &secure_channel, &session_state, &message_queue, reverse_connect);
         let subscription_state = Arc::new(RwLock::new(SubscriptionState::new()));
         let timer_command_queue = SubscriptionTimer::make_timer_command_queue(session_state.clone(), subscription_state.clone());
         Session {
@@ -178,6 +207,22 @@ impl Session {
             secure_channel,
             message_queue,
             session_retry_policy,
+            keep_alive_config,
+            dynamic_types: DynamicTypes::new(),
+            value_types: HashMap::new(),
+            path_node_ids: HashMap::new(),
+            registered_path_node_ids: HashMap::new(),
+            namespace_map: NamespaceMap::new(),
+            keep_alive: KeepAlive::new(),
+        }
+    }
+
+    /// Creates the transport for the url, opc.tcp or https
+    fn new_transport(endpoint_url: &str, certificate_store: &Arc<RwLock<CertificateStore>>, secure_channel: &Arc<RwLock<SecureChannel>>, session_state: &Arc<RwLock<SessionState>>, message_queue: &Arc<RwLock<MessageQueue>>, reverse_connect: Option<ReverseConnect>) -> Box<dyn Transport + Send + Sync> {
+        if is_opc_ua_https_url(endpoint_url) {
+            Box::new(HttpsTransport::new(certificate_store.clone(), secure_channel.clone(), session_state.clone(), message_queue.clone()))
+        } else {
+            Box::new(TcpTransport::new(certificate_store.clone(), secure_channel.clone(), session_state.clone(), message_queue.clone(), reverse_connect))
         }
     }
 
@@ -209,6 +254,13 @@ impl Session {
         self.session_retry_policy = session_retry_policy;
     }
 
+    /// Sets how often the session checks that the server is still answering, and how many
+    /// keep-alives can be missed before it reconnects. It takes effect when the session is next
+    /// activated.
+    pub fn set_keep_alive_config(&mut self, keep_alive_config: KeepAliveConfig) {
+        self.keep_alive_config = keep_alive_config;
+    }
+
     /// Register a callback to be notified when the session has been closed.
     ///
     /// # Arguments
@@ -236,8 +288,9 @@ impl Session {
     /// is a failure, it will be communicated by the status code in the result. You should not
     /// call this if there is a session retry policy associated with the session.
     ///
-    /// Reconnecting will attempt to transfer or recreate subscriptions that were on the old
-    /// session before it terminated.
+    /// Reconnecting will attempt to recover the subscriptions that were on the old session before
+    /// it terminated, by carrying on with them in the reactivated session, transferring them to a
+    /// new session or recreating them, and will republish notifications that were missed.
     ///
     /// # Returns
     ///
@@ -262,11 +315,18 @@ impl Session {
                 subscription_state.cancel_subscription_timers();
             }
 
+            // Publish requests that were outstanding went with the old connection, so any
+            // throttling they caused no longer applies
+            {
+                let mut session_state = trace_write_lock_unwrap!(self.session_state);
+                session_state.set_wait_for_publish_response(false);
+            }
+
             // Connect to server (again)
             self.connect_no_retry()?;
 
             // Attempt to reactivate the existing session
-            match self.activate_session() {
+            let reactivated = match self.activate_session() {
                 Err(status_code) => {
                     // Activation didn't work, so create a new session
                     info!("Session activation failed on reconnect, error = {}, so creating a new session", status_code);
@@ -280,122 +340,293 @@ impl Session {
                     session_debug!(self, "activate_session");
                     self.activate_session()?;
                     session_debug!(self, "reconnect should be complete");
+                    false
                 }
                 Ok(_) => {
                     info!("Activation succeeded");
+                    true
                 }
-            }
-            session_debug!(self, "transfer_subscriptions_from_old_session");
-            self.transfer_subscriptions_from_old_session()?;
+            };
+            session_debug!(self, "recover_subscriptions");
+            self.recover_subscriptions(reactivated)?;
             Ok(())
         }
     }
 
-    /// This code attempts to take the existing subscriptions created by a previous session and
-    /// either transfer them to this session, or construct them from scratch.
-    fn transfer_subscriptions_from_old_session(&mut self) -> Result<(), StatusCode> {
-        let subscription_state = self.subscription_state.clone();
+    /// Moves the session to another server of a non-transparent redundant server set. The
+    /// connection to the current server is closed without closing the session, so a server that
+    /// mirrors sessions can still activate it, then the session connects to the endpoint and
+    /// recovers its subscriptions the same way as `reconnect_and_activate()` does, transferring
+    /// them where the server mirrors them and recreating them where it does not.
+    ///
+    /// The endpoint should be one the server returned from `GetEndpoints`, so it holds the
+    /// server's certificate.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - the session is activated on the server of the endpoint
+    /// * `Err(StatusCode)` - reason for failure
+    ///
+    pub(crate) fn fail_over(&mut self, endpoint: EndpointDescription) -> Result<(), StatusCode> {
+        self.keep_alive.stop();
+        if self.is_connected() {
+            if self.transport.uses_secure_channel() {
+                let _ = self.close_secure_channel();
+            }
+            {
+                let mut session_state = trace_write_lock_unwrap!(self.session_state);
+                session_state.quit();
+            }
+            self.transport.wait_for_disconnect();
+            self.on_connection_status_change(false);
+        }
+        session_warn!(self, "Failing over to {}", endpoint.endpoint_url);
+        if is_opc_ua_https_url(endpoint.endpoint_url.as_ref()) != is_opc_ua_https_url(self.session_info.endpoint.endpoint_url.as_ref()) {
+            self.transport = Self::new_transport(endpoint.endpoint_url.as_ref(), &self.certificate_store, &self.secure_channel, &self.session_state, &self.message_queue, None);
+        }
+        self.session_info.endpoint = endpoint;
+        self.reconnect_and_activate()
+    }
 
+    /// This code recovers the subscriptions of the previous session after a reconnect. If the
+    /// session was reactivated, the subscriptions carry on and only the notifications that were
+    /// missed are republished. Otherwise the subscriptions are transferred to the new session and
+    /// their missed notifications republished, and any that cannot be transferred are created
+    /// again from scratch. The callback of each subscription is told how it was recovered.
+    fn recover_subscriptions(&mut self, reactivated: bool) -> Result<(), StatusCode> {
         let subscription_ids = {
-            let subscription_state = trace_read_lock_unwrap!(subscription_state);
+            let subscription_state = trace_read_lock_unwrap!(self.subscription_state);
             subscription_state.subscription_ids()
         };
 
-        // Start by getting the subscription ids
         if let Some(subscription_ids) = subscription_ids {
-            // Try to use TransferSubscriptions to move subscriptions_ids over. If this
-            // works then there is nothing else to do.
-            let mut subscription_ids_to_recreate = subscription_ids.iter().map(|s| *s).collect::<HashSet<u32>>();
-            if let Ok(transfer_results) = self.transfer_subscriptions(&subscription_ids, true) {
-                session_debug!(self, "transfer_results = {:?}", transfer_results);
-                transfer_results.iter().enumerate().for_each(|(i, r)| {
-                    if r.status_code.is_good() {
-                        // Subscription was transferred so it does not need to be recreated
-                        subscription_ids_to_recreate.remove(&subscription_ids[i]);
+            let mut recovered = Vec::with_capacity(subscription_ids.len());
+            let mut subscription_ids_to_recreate = Vec::new();
+
+            if reactivated {
+                // The subscriptions still belong to the session, unless the server has lost them
+                subscription_ids.iter().for_each(|subscription_id| {
+                    match self.republish_missed_notifications(*subscription_id, None) {
+                        Ok(republished) => recovered.push((*subscription_id, SubscriptionRecovery::Reactivated { republished })),
+                        Err(StatusCode::BadSubscriptionIdInvalid) => subscription_ids_to_recreate.push(*subscription_id),
+                        Err(status_code) => {
+                            session_warn!(self, "Missed notifications of subscription {} could not be republished, error = {}", subscription_id, status_code);
+                            recovered.push((*subscription_id, SubscriptionRecovery::Reactivated { republished: 0 }));
+                        }
                     }
                 });
+            } else {
+                // Try to use TransferSubscriptions to move the subscriptions over. The server
+                // does not send initial values since the missed notifications are republished.
+                match self.transfer_subscriptions(&subscription_ids, false) {
+                    Ok(transfer_results) => {
+                        session_debug!(self, "transfer_results = {:?}", transfer_results);
+                        subscription_ids.iter().enumerate().for_each(|(i, subscription_id)| {
+                            match transfer_results.get(i) {
+                                Some(r) if r.status_code.is_good() => {
+                                    let available_sequence_numbers = r.available_sequence_numbers.clone().unwrap_or_default();
+                                    let republished = self.republish_missed_notifications(*subscription_id, Some(&available_sequence_numbers)).unwrap_or(0);
+                                    recovered.push((*subscription_id, SubscriptionRecovery::Transferred { republished }));
+                                }
+                                _ => subscription_ids_to_recreate.push(*subscription_id)
+                            }
+                        });
+                    }
+                    Err(_) => subscription_ids_to_recreate.extend(subscription_ids.iter())
+                }
             }
 
-            // But if it didn't work, then some or all subscriptions have to be remade.
             if !subscription_ids_to_recreate.is_empty() {
-                session_warn!(self, "Some or all of the existing subscriptions could not be transferred and must be created manually");
+                session_warn!(self, "Some or all of the existing subscriptions could not be recovered and must be created again");
+            }
+
+            // Recovered subscriptions need their publish timers back. Recreated subscriptions are
+            // given a timer when they are created.
+            for (subscription_id, _) in &recovered {
+                let _ = self.timer_command_queue.unbounded_send(SubscriptionTimerCommand::CreateTimer(*subscription_id));
             }
 
-            // Now create any subscriptions that could not be transferred
+            // Now create any subscriptions that could not be recovered
             subscription_ids_to_recreate.iter().for_each(|subscription_id| {
-                info!("Recreating subscription {}", subscription_id);
-                // Remove the subscription data, create it again from scratch
-                let deleted_subscription = {
-                    let mut subscription_state = trace_write_lock_unwrap!(subscription_state);
-                    subscription_state.delete_subscription(*subscription_id)
-                };
+                match self.recreate_subscription(*subscription_id) {
+                    Ok(recovery) => recovered.push(recovery),
+                    Err(status_code) => {
+                        session_error!(self, "Subscription {} could not be created again, error = {}", subscription_id, status_code);
+                    }
+                }
+            });
 
-                if let Some(subscription) = deleted_subscription {
-                    // Attempt to replicate the subscription (subscription id will be new)
-                    if let Ok(subscription_id) = self.create_subscription_inner(
-                        subscription.publishing_interval(),
-                        subscription.lifetime_count(),
-                        subscription.max_keep_alive_count(),
-                        subscription.max_notifications_per_publish(),
-                        subscription.priority(),
-                        subscription.publishing_enabled(),
-                        subscription.notification_callback()) {
-                        info!("New subscription created with id {}", subscription_id);
-
-                        // For each monitored item
-                        let items_to_create = subscription.monitored_items().iter().map(|(_, item)| {
-                            MonitoredItemCreateRequest {
-                                item_to_monitor: item.item_to_monitor().clone(),
-                                monitoring_mode: item.monitoring_mode(),
-                                requested_parameters: MonitoringParameters {
-                                    client_handle: item.client_handle(),
-                                    sampling_interval: item.sampling_interval(),
-                                    filter: ExtensionObject::null(),
-                                    queue_size: item.queue_size(),
-                                    discard_oldest: true,
-                                },
+            // Tell each subscription how it was recovered
+            let mut subscription_state = trace_write_lock_unwrap!(self.subscription_state);
+            recovered.iter().for_each(|(subscription_id, recovery)| {
+                subscription_state.on_recovered(*subscription_id, recovery);
+            });
+        }
+        Ok(())
+    }
+
+    /// Creates a subscription again from scratch with the same parameters and monitored items,
+    /// returning the id of the new subscription, the ids that its monitored items were given and
+    /// the monitored items that could not be created again. The existing `MonitoredItem` objects
+    /// are kept, with their new ids, so their last values are retained.
+    fn recreate_subscription(&mut self, subscription_id: u32) -> Result<(u32, SubscriptionRecovery), StatusCode> {
+        info!("Recreating subscription {}", subscription_id);
+        // Remove the subscription data, create it again from scratch
+        let deleted_subscription = {
+            let mut subscription_state = trace_write_lock_unwrap!(self.subscription_state);
+            subscription_state.delete_subscription(subscription_id)
+        };
+        let mut subscription = if let Some(subscription) = deleted_subscription {
+            subscription
+        } else {
+            session_error!(self, "Subscription {} cannot be recreated because it doesn't exist", subscription_id);
+            return Err(StatusCode::BadSubscriptionIdInvalid);
+        };
+
+        // Attempt to replicate the subscription (subscription id will be new)
+        let new_subscription_id = match self.create_subscription_inner(
+            subscription.publishing_interval(),
+            subscription.lifetime_count(),
+            subscription.max_keep_alive_count(),
+            subscription.max_notifications_per_publish(),
+            subscription.priority(),
+            subscription.publishing_enabled(),
+            subscription.notification_callback()) {
+            Ok(new_subscription_id) => new_subscription_id,
+            Err(status_code) => {
+                session_warn!(self, "Could not create a subscription from the existing subscription {}", subscription_id);
+                return Err(status_code);
+            }
+        };
+        info!("New subscription created with id {}", new_subscription_id);
+
+        // Create each monitored item again
+        let mut monitored_items = subscription.take_monitored_items();
+        let mut monitored_item_ids = Vec::with_capacity(monitored_items.len());
+        let mut failed_monitored_items = Vec::new();
+        if !monitored_items.is_empty() {
+            let items_to_create = monitored_items.iter().map(|item| {
+                MonitoredItemCreateRequest {
+                    item_to_monitor: item.item_to_monitor().clone(),
+                    monitoring_mode: item.monitoring_mode(),
+                    requested_parameters: MonitoringParameters {
+                        client_handle: item.client_handle(),
+                        sampling_interval: item.sampling_interval(),
+                        filter: item.filter().clone(),
+                        queue_size: item.queue_size(),
+                        discard_oldest: item.discard_oldest(),
+                    },
+                }
+            }).collect::<Vec<MonitoredItemCreateRequest>>();
+            match self.create_monitored_items(new_subscription_id, TimestampsToReturn::Both, &items_to_create) {
+                Ok(results) => {
+                    // Map the new ids back onto the existing monitored items, and report any the
+                    // server rejected
+                    monitored_items = monitored_items.into_iter().enumerate()
+                        .filter_map(|(i, mut item)| match results.get(i) {
+                            Some(r) if r.status_code.is_good() => {
+                                monitored_item_ids.push((item.id(), r.monitored_item_id));
+                                item.set_id(r.monitored_item_id);
+                                item.set_sampling_interval(r.revised_sampling_interval);
+                                item.set_queue_size(r.revised_queue_size);
+                                Some(item)
                             }
-                        }).collect::<Vec<MonitoredItemCreateRequest>>();
-                        let _ = self.create_monitored_items(subscription_id, TimestampsToReturn::Both, &items_to_create);
-
-                        // Recreate any triggers for the monitored item. This code assumes monitored item
-                        // ids are the same value as they were in the previous subscription.
-                        subscription.monitored_items().iter().for_each(|(_, item)| {
-                            let triggered_items = item.triggered_items();
-                            if !triggered_items.is_empty() {
-                                let links_to_add = triggered_items.iter().map(|i| *i).collect::<Vec<u32>>();
-                                let _ = self.set_triggering(subscription_id, item.id(), links_to_add.as_slice(), &[]);
+                            r => {
+                                let status_code = r.map(|r| r.status_code).unwrap_or(StatusCode::BadUnexpectedError);
+                                session_warn!(self, "Monitored item {} of subscription {} could not be created again, error = {}", item.id(), subscription_id, status_code);
+                                failed_monitored_items.push((item.id(), status_code));
+                                None
                             }
-                        });
-                    } else {
-                        session_warn!(self, "Could not create a subscription from the existing subscription {}", subscription_id);
-                    }
-                } else {
-                    panic!("Subscription {}, doesn't exist although it should", subscription_id);
+                        })
+                        .collect();
+                }
+                Err(status_code) => {
+                    session_warn!(self, "Monitored items of subscription {} could not be created again, error = {}", subscription_id, status_code);
+                    failed_monitored_items.extend(monitored_items.iter().map(|item| (item.id(), status_code)));
+                    monitored_items.clear();
+                }
+            }
+            if !monitored_items.is_empty() {
+                let new_id = |id: &u32| monitored_item_ids.iter().find(|(old, _)| old == id).map(|(_, new)| *new);
+                monitored_items.iter_mut().for_each(|item| {
+                    let triggered_items = item.triggered_items().iter().filter_map(new_id).collect();
+                    item.set_triggered_items(triggered_items);
+                });
+                let triggers = monitored_items.iter()
+                    .filter(|item| !item.triggered_items().is_empty())
+                    .map(|item| (item.id(), item.triggered_items().iter().cloned().collect::<Vec<u32>>()))
+                    .collect::<Vec<_>>();
+                {
+                    let mut subscription_state = trace_write_lock_unwrap!(self.subscription_state);
+                    subscription_state.restore_monitored_items(new_subscription_id, monitored_items);
                 }
-            });
 
-            // Now all the subscriptions should have been recreated, it should be possible
-            // to kick off the publish timers.
-            let subscription_ids = {
-                let subscription_state = trace_read_lock_unwrap!(subscription_state);
-                subscription_state.subscription_ids().unwrap()
+                // Recreate any triggers for the monitored items, using their new ids
+                triggers.iter().for_each(|(triggering_item_id, links_to_add)| {
+                    let _ = self.set_triggering(new_subscription_id, *triggering_item_id, links_to_add, &[]);
+                });
+            }
+        }
+
+        Ok((new_subscription_id, SubscriptionRecovery::Recreated {
+            previous_subscription_id: subscription_id,
+            monitored_item_ids,
+            failed_monitored_items,
+        }))
+    }
+
+    /// Republishes the notification messages of a subscription that were sent after the last one
+    /// the client received, and processes them as if they had been published. The sequence numbers
+    /// available for republishing are either supplied, or are tried in turn until the server has
+    /// no more. Returns the number of messages that were republished.
+    fn republish_missed_notifications(&mut self, subscription_id: u32, available_sequence_numbers: Option<&[u32]>) -> Result<usize, StatusCode> {
+        let last_sequence_number = {
+            let subscription_state = trace_read_lock_unwrap!(self.subscription_state);
+            subscription_state.get(subscription_id).map(|s| s.last_sequence_number()).unwrap_or(0)
+        };
+        let mut republished = 0;
+        if let Some(available_sequence_numbers) = available_sequence_numbers {
+            let sequence_numbers = {
+                let subscription_state = trace_read_lock_unwrap!(self.subscription_state);
+                subscription_state.sequence_numbers_after_last(subscription_id, available_sequence_numbers)
             };
-            for subscription_id in &subscription_ids {
-                let _ = self.timer_command_queue.unbounded_send(SubscriptionTimerCommand::CreateTimer(*subscription_id));
+            for sequence_number in sequence_numbers {
+                let notification_message = self.republish(subscription_id, sequence_number)?;
+                self.process_notification_message(subscription_id, &notification_message);
+                republished += 1;
+            }
+        } else {
+            let mut sequence_number = last_sequence_number;
+            loop {
+                // Sequence numbers wrap around to 1
+                sequence_number = if sequence_number == u32::MAX { 1 } else { sequence_number + 1 };
+                match self.republish(subscription_id, sequence_number) {
+                    Ok(notification_message) => {
+                        self.process_notification_message(subscription_id, &notification_message);
+                        republished += 1;
+                    }
+                    Err(StatusCode::BadMessageNotAvailable) => break,
+                    Err(status_code) => return Err(status_code),
+                }
             }
         }
-        Ok(())
+        if republished > 0 {
+            session_debug!(self, "Republished {} missed notification messages for subscription {}", republished, subscription_id);
+        }
+        Ok(republished)
     }
 
     /// Connects to the server using the retry policy to repeat connecting until such time as it
     /// succeeds or the policy says to give up. If there is a failure, it will be
     /// communicated by the status code in the result.
     pub fn connect(&mut self) -> Result<(), StatusCode> {
+        use chrono::Utc;
         loop {
+            self.session_retry_policy.set_last_attempt(Utc::now());
             match self.connect_no_retry() {
                 Ok(_) => {
                     info!("Connect was successful");
+                    self.on_connect_attempt(StatusCode::Good, None);
                     self.session_retry_policy.reset_retry_count();
                     return Ok(());
                 }
@@ -403,15 +634,15 @@ impl Session {
                     self.session_retry_policy.increment_retry_count();
                     session_warn!(self, "Connect was unsuccessful, error = {}, retries = {}", status_code, self.session_retry_policy.retry_count());
 
-                    use chrono::Utc;
-                    match self.session_retry_policy.should_retry_connect(Utc::now()) {
+                    let answer = self.session_retry_policy.should_retry_connect(Utc::now());
+                    self.on_connect_attempt(status_code, Some(&answer));
+                    match answer {
                         Answer::GiveUp => {
                             session_error!(self, "Session has given up trying to connect to the server after {} retries", self.session_retry_policy.retry_count());
                             return Err(StatusCode::BadNotConnected);
                         }
                         Answer::Retry => {
                             info!("Retrying to connect to server...");
-                            self.session_retry_policy.set_last_attempt(Utc::now());
                         }
                         Answer::WaitFor(sleep_for) => {
                             // Sleep for the instructed interval before looping around and trying
@@ -424,6 +655,18 @@ impl Session {
         }
     }
 
+    /// Tells the connection status callback the outcome of an attempt to connect, and what the
+    /// retry policy will do next if it failed.
+    fn on_connect_attempt(&self, status_code: StatusCode, answer: Option<&Answer>) {
+        let retry_in = match answer {
+            Some(Answer::Retry) => Some(0),
+            Some(Answer::WaitFor(wait_for)) => Some(*wait_for),
+            Some(Answer::GiveUp) | None => None,
+        };
+        let mut session_state = trace_write_lock_unwrap!(self.session_state);
+        session_state.on_connect_attempt(status_code, self.session_retry_policy.retry_count(), retry_in);
+    }
+
     /// Connects to the server using the configured session arguments. No attempt is made to retry
     /// the connection if the attempt fails. If there is a failure, it will be communicated by the
     /// status code in the result.
@@ -457,7 +700,10 @@ impl Session {
                 info!("Security mode = {:?}", self.session_info.endpoint.security_mode);
             }
             self.transport.connect(endpoint_url.as_ref())?;
-            self.open_secure_channel()?;
+            // The https transport relies on TLS instead of a secure channel
+            if self.transport.uses_secure_channel() {
+                self.open_secure_channel()?;
+            }
             self.on_connection_status_change(true);
             Ok(())
         }
@@ -467,13 +713,44 @@ impl Session {
         self.session_state.clone()
     }
 
+    /// Returns an [`AsyncSession`] for calling the services of this session without blocking.
+    /// Each call returns a future of the response, so many requests can be in flight at once.
+    /// The session should be connected and activated first, and kept running, e.g. with
+    /// `run_async()`, while requests are pending.
+    ///
+    /// [`AsyncSession`]: ../async_session/struct.AsyncSession.html
+    pub fn async_session(&self) -> AsyncSession {
+        AsyncSession::new(self.session_state.clone(), self.subscription_state.clone(), self.timer_command_queue.clone())
+    }
+
+    /// Returns a [`Browser`] for walking the address space of the server from a start node,
+    /// which sends its requests through the `async_session()` of this session.
+    ///
+    /// [`Browser`]: ../browser/struct.Browser.html
+    pub fn browser(&self) -> Browser {
+        Browser::new(self.async_session())
+    }
+
     /// Disconnect from the server. Disconnect is an explicit command to drop the socket and throw
     /// away all state information. If you disconnect you cannot reconnect to your existing session
     /// or retrieve any existing subscriptions.
     pub fn disconnect(&mut self) {
+        self.keep_alive.stop();
         if self.is_connected() {
             let _ = self.delete_all_subscriptions();
-            let _ = self.close_secure_channel();
+            if self.transport.uses_secure_channel() {
+                let _ = self.close_secure_channel();
+            } else {
+                // Without a secure channel to close, the server is told that the session is over
+                let request_header = self.make_request_header();
+                if !request_header.authentication_token.is_null() {
+                    let request = CloseSessionRequest {
+                        request_header,
+                        delete_subscriptions: true,
+                    };
+                    let _ = self.async_send_request(request, false);
+                }
+            }
 
             {
                 let mut session_state = trace_write_lock_unwrap!(self.session_state);
@@ -485,6 +762,12 @@ impl Session {
         }
     }
 
+    /// Returns the state of the server, e.g. `Running` or `Shutdown`, as it was last read by the
+    /// keep-alive. Returns `None` if the keep-alive has not read it yet or is turned off.
+    pub fn server_state(&self) -> Option<ServerState> {
+        self.keep_alive.server_state()
+    }
+
     /// Test if the session is in a connected state
     ///
     /// # Returns
@@ -584,6 +867,15 @@ impl Session {
         }
     }
 
+    /// Tests if the session is disconnected and its retry policy says that it is time to try to
+    /// reconnect, or to give up, which the next `poll()` will do
+    pub(crate) fn is_reconnect_due(&self) -> bool {
+        !self.is_connected() && match self.session_retry_policy.should_retry_connect(chrono::Utc::now()) {
+            Answer::WaitFor(_) => false,
+            Answer::Retry | Answer::GiveUp => true,
+        }
+    }
+
     /// Polls on the session which basically dispatches any pending
     /// async responses, attempts to reconnect if the client is disconnected from the client and
     /// sleeps a little bit if nothing needed to be done.
@@ -599,8 +891,19 @@ impl Session {
     /// * `false` - if no action was performed during the poll and the poll slept
     ///
     pub fn poll(&mut self) -> Result<bool, ()> {
+        {
+            let mut message_queue = trace_write_lock_unwrap!(self.message_queue);
+            message_queue.expire_requests(Instant::now());
+        }
         let did_something = if self.is_connected() {
-            self.handle_publish_responses()
+            {
+                // Renewal of the token does not wait for its response, so the response is
+                // applied here if no other request has come along to apply it
+                let mut session_state = trace_write_lock_unwrap!(self.session_state);
+                session_state.ensure_secure_channel_token();
+            }
+            let keep_alive = self.keep_alive.poll(&self.session_state);
+            self.handle_publish_responses() || keep_alive
         } else {
             use chrono::Utc;
             match self.session_retry_policy.should_retry_connect(Utc::now()) {
@@ -611,12 +914,18 @@ impl Session {
                 Answer::Retry => {
                     info!("Retrying to reconnect to server...");
                     self.session_retry_policy.set_last_attempt(Utc::now());
-                    if self.reconnect_and_activate().is_ok() {
-                        info!("Retry to connect was successful");
-                        self.session_retry_policy.reset_retry_count();
-                    } else {
-                        self.session_retry_policy.increment_retry_count();
-                        session_warn!(self, "Reconnect was unsuccessful, retries = {}", self.session_retry_policy.retry_count());
+                    match self.reconnect_and_activate() {
+                        Ok(_) => {
+                            info!("Retry to connect was successful");
+                            self.on_connect_attempt(StatusCode::Good, None);
+                            self.session_retry_policy.reset_retry_count();
+                        }
+                        Err(status_code) => {
+                            self.session_retry_policy.increment_retry_count();
+                            session_warn!(self, "Reconnect was unsuccessful, retries = {}", self.session_retry_policy.retry_count());
+                            let answer = self.session_retry_policy.should_retry_connect(Utc::now());
+                            self.on_connect_attempt(status_code, Some(&answer));
+                        }
                     }
                     true
                 }
@@ -651,11 +960,38 @@ impl Session {
     /// [`ApplicationDescription`]: ./struct.ApplicationDescription.html
     ///
     pub fn find_servers<T>(&mut self, endpoint_url: T) -> Result<Vec<ApplicationDescription>, StatusCode> where T: Into<UAString> {
+        self.find_servers_with_uris(endpoint_url, &[])
+    }
+
+    /// Sends a [`FindServersRequest`] to the server denoted by the discovery url, asking only for
+    /// the servers with the given application uris, or for all servers if there are none.
+    ///
+    /// See OPC UA Part 4 - Services 5.4.2 for complete description of the service and error responses.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoint_url` - The network address that the Client used to access the Discovery Endpoint.
+    /// * `server_uris` - The application uris of the servers to return.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<ApplicationDescription>)` - A list of [`ApplicationDescription`] that meet criteria specified in the request.
+    /// * `Err(StatusCode)` - Request failed, status code is the reason for failure
+    ///
+    /// [`FindServersRequest`]: ./struct.FindServersRequest.html
+    /// [`ApplicationDescription`]: ./struct.ApplicationDescription.html
+    ///
+    pub fn find_servers_with_uris<T>(&mut self, endpoint_url: T, server_uris: &[String]) -> Result<Vec<ApplicationDescription>, StatusCode> where T: Into<UAString> {
+        let server_uris = if server_uris.is_empty() {
+            None
+        } else {
+            Some(server_uris.iter().map(|uri| UAString::from(uri.as_str())).collect())
+        };
         let request = FindServersRequest {
             request_header: self.make_request_header(),
             endpoint_url: endpoint_url.into(),
             locale_ids: None,
-            server_uris: None,
+            server_uris,
         };
         let response = self.send_request(request)?;
         if let SupportedMessage::FindServersResponse(response) = response {
@@ -847,6 +1183,9 @@ impl Session {
         if let SupportedMessage::CreateSessionResponse(response) = response {
             crate::process_service_result(&response.response_header)?;
 
+            // Registered nodes belong to the old session
+            self.registered_path_node_ids.clear();
+
             let session_id = {
                 let mut session_state = trace_write_lock_unwrap!(self.session_state);
                 session_state.set_session_id(response.session_id.clone());
@@ -1062,6 +1401,11 @@ impl Session {
         if let SupportedMessage::ActivateSessionResponse(response) = response {
             // trace!("ActivateSessionResponse = {:#?}", response);
             crate::process_service_result(&response.response_header)?;
+            // The server may have changed its namespaces since the session was last active
+            if let Err(status_code) = self.refresh_namespace_map() {
+                session_warn!(self, "Cannot read the namespaces of the server, status = {}", status_code);
+            }
+            self.keep_alive.start(&self.keep_alive_config);
             Ok(())
         } else {
             Err(crate::process_unexpected_response(response))
@@ -1407,6 +1751,334 @@ impl Session {
         }
     }
 
+    /// Finds the nodes that browse paths lead to by sending a [`TranslateBrowsePathsToNodeIdsRequest`]
+    /// to the server. Each browse path is a starting node and a relative path of references and
+    /// browse names to follow from it.
+    ///
+    /// See OPC UA Part 4 - Services 5.8.4 for complete description of the service and error responses.
+    ///
+    /// # Arguments
+    ///
+    /// * `browse_paths` - A list of [`BrowsePath`] to translate
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<BrowsePathResult>)` - A list of [`BrowsePathResult`] corresponding to each browse path,
+    ///   holding the status and the targets of the path
+    /// * `Err(StatusCode)` - Request failed, status code is the reason for failure
+    ///
+    /// [`TranslateBrowsePathsToNodeIdsRequest`]: ./struct.TranslateBrowsePathsToNodeIdsRequest.html
+    /// [`BrowsePath`]: ./struct.BrowsePath.html
+    /// [`BrowsePathResult`]: ./struct.BrowsePathResult.html
+    ///
+    pub fn translate_browse_paths_to_node_ids(&mut self, browse_paths: &[BrowsePath]) -> Result<Vec<BrowsePathResult>, StatusCode> {
+        if browse_paths.is_empty() {
+            session_error!(self, "translate_browse_paths_to_node_ids, was not supplied with any browse paths");
+            Err(StatusCode::BadNothingToDo)
+        } else {
+            let request = TranslateBrowsePathsToNodeIdsRequest {
+                request_header: self.make_request_header(),
+                browse_paths: Some(browse_paths.to_vec()),
+            };
+            let response = self.send_request(request)?;
+            if let SupportedMessage::TranslateBrowsePathsToNodeIdsResponse(response) = response {
+                session_debug!(self, "translate_browse_paths_to_node_ids, success");
+                crate::process_service_result(&response.response_header)?;
+                Ok(response.results.unwrap_or_default())
+            } else {
+                session_error!(self, "translate_browse_paths_to_node_ids failed {:?}", response);
+                Err(crate::process_unexpected_response(response))
+            }
+        }
+    }
+
+    /// Finds the node at the end of a path of browse names from the root folder, e.g.
+    /// `/Objects/2:Boiler/2:Temperature`. The path is a relative path in the text format of OPC UA
+    /// Part 4, Appendix A, where each browse name may have a namespace index. The node is
+    /// remembered for the rest of the session, so looking it up again needs no request. If the
+    /// path was registered with `register_paths()`, the registered node id is returned.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(NodeId)` - The node the path leads to
+    /// * `Err(StatusCode)` - Status code reason for failure, e.g. `BadNoMatch` if no node matches
+    ///   the path, or `BadBrowseNameInvalid` if the path is not valid
+    ///
+    pub fn node_id_for_path(&mut self, path: &str) -> Result<NodeId, StatusCode> {
+        self.node_ids_for_paths(&[path])?
+            .pop()
+            .unwrap_or(Err(StatusCode::BadUnexpectedError))
+    }
+
+    /// Finds the nodes at the end of paths from the root folder, as `node_id_for_path()` does.
+    /// Paths that are not already known are translated in one request.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Result<NodeId, StatusCode>>)` - The node each path leads to, or the reason it could
+    ///   not be found, in the same order as the paths
+    /// * `Err(StatusCode)` - Status code reason for failure of the request
+    ///
+    pub fn node_ids_for_paths(&mut self, paths: &[&str]) -> Result<Vec<Result<NodeId, StatusCode>>, StatusCode> {
+        let mut results = paths.iter().map(|path| {
+            if let Some(node_id) = self.registered_path_node_ids.get(*path).or_else(|| self.path_node_ids.get(*path)) {
+                Some(Ok(node_id.clone()))
+            } else {
+                Self::browse_path_from_str(path).err().map(|status_code| {
+                    session_error!(self, "node_ids_for_paths, path {} is not a valid relative path", path);
+                    Err(status_code)
+                })
+            }
+        }).collect::<Vec<Option<Result<NodeId, StatusCode>>>>();
+
+        // Translate the paths that are not known yet
+        let unknown = results.iter().enumerate()
+            .filter(|(_, result)| result.is_none())
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>();
+        if !unknown.is_empty() {
+            let browse_paths = unknown.iter()
+                .map(|i| Self::browse_path_from_str(paths[*i]).unwrap())
+                .collect::<Vec<_>>();
+            let browse_path_results = self.translate_browse_paths_to_node_ids(&browse_paths)?;
+            if browse_path_results.len() != unknown.len() {
+                session_error!(self, "node_ids_for_paths, server returned {} results for {} paths", browse_path_results.len(), unknown.len());
+                return Err(StatusCode::BadUnexpectedError);
+            }
+            for (i, browse_path_result) in unknown.into_iter().zip(browse_path_results) {
+                let result = Self::target_of_browse_path(browse_path_result);
+                match result {
+                    Ok(ref node_id) => {
+                        self.path_node_ids.insert(paths[i].to_string(), node_id.clone());
+                    }
+                    Err(status_code) => {
+                        session_error!(self, "node_ids_for_paths, path {} cannot be translated, status = {}", paths[i], status_code);
+                    }
+                }
+                results[i] = Some(result);
+            }
+        }
+        Ok(results.into_iter().map(|result| result.unwrap()).collect())
+    }
+
+    /// Finds the nodes at the end of paths from the root folder and registers them with the
+    /// server by calling `register_nodes()`, so the server can access them more efficiently.
+    /// Afterwards `node_id_for_path()` returns the node ids the server registered them as, until
+    /// they are unregistered with `unregister_paths()` or a new session is created.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<NodeId>)` - The registered node ids, in the same order as the paths
+    /// * `Err(StatusCode)` - Status code reason for failure, including the reason the first path
+    ///   that cannot be found was not found
+    ///
+    pub fn register_paths(&mut self, paths: &[&str]) -> Result<Vec<NodeId>, StatusCode> {
+        let node_ids = self.node_ids_for_paths(paths)?.into_iter().collect::<Result<Vec<_>, StatusCode>>()?;
+        let registered_node_ids = self.register_nodes(&node_ids)?;
+        if registered_node_ids.len() != paths.len() {
+            session_error!(self, "register_paths, server registered {} nodes for {} paths", registered_node_ids.len(), paths.len());
+            return Err(StatusCode::BadUnexpectedError);
+        }
+        paths.iter().zip(registered_node_ids.iter()).for_each(|(path, node_id)| {
+            self.registered_path_node_ids.insert(path.to_string(), node_id.clone());
+        });
+        Ok(registered_node_ids)
+    }
+
+    /// Unregisters the nodes of paths that were registered with `register_paths()` by calling
+    /// `unregister_nodes()`. Paths that are not registered are ignored.
+    pub fn unregister_paths(&mut self, paths: &[&str]) -> Result<(), StatusCode> {
+        let node_ids = paths.iter()
+            .filter_map(|path| self.registered_path_node_ids.remove(*path))
+            .collect::<Vec<_>>();
+        if node_ids.is_empty() {
+            Ok(())
+        } else {
+            self.unregister_nodes(&node_ids)
+        }
+    }
+
+    /// Forgets the nodes of all paths that have been looked up, e.g. if the server's address
+    /// space has changed. Registered paths are forgotten too but are not unregistered.
+    pub fn clear_path_cache(&mut self) {
+        self.path_node_ids.clear();
+        self.registered_path_node_ids.clear();
+    }
+
+    /// Returns the server's namespace table, which is read from its `NamespaceArray` whenever
+    /// the session is activated.
+    pub fn namespace_map(&self) -> &NamespaceMap {
+        &self.namespace_map
+    }
+
+    /// Reads the server's `NamespaceArray` into the namespace table. If the namespaces have
+    /// changed, the nodes that paths lead to and the value types of written variables are
+    /// forgotten, since their namespace indexes may be out of date.
+    pub fn refresh_namespace_map(&mut self) -> Result<(), StatusCode> {
+        let uris = self.read_string_array(VariableId::Server_NamespaceArray)?;
+        let namespace_map = NamespaceMap::from_uris(&uris);
+        if namespace_map != self.namespace_map {
+            session_debug!(self, "Server namespaces are {:?}", uris);
+            self.path_node_ids.clear();
+            self.value_types.clear();
+            self.namespace_map = namespace_map;
+        }
+        Ok(())
+    }
+
+    /// Resolves an expanded node id that may name its namespace by uri to a node id of the
+    /// server, using the namespace table. The table is refreshed once if the uri is not in it,
+    /// in case the server has registered the namespace since.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(NodeId)` - The node id
+    /// * `Err(StatusCode)` - Status code reason for failure, e.g. `BadNodeIdUnknown` if the server
+    ///   has no namespace with the uri or the node is on another server
+    ///
+    pub fn resolve_node_id(&mut self, node_id: &ExpandedNodeId) -> Result<NodeId, StatusCode> {
+        if let Some(resolved) = self.namespace_map.resolve(node_id) {
+            return Ok(resolved);
+        }
+        if node_id.server_index == 0 {
+            self.refresh_namespace_map()?;
+            if let Some(resolved) = self.namespace_map.resolve(node_id) {
+                return Ok(resolved);
+            }
+        }
+        session_error!(self, "resolve_node_id, node {} is not in a namespace of the server", node_id);
+        Err(StatusCode::BadNodeIdUnknown)
+    }
+
+    /// Parses a node id such as `ns=2;s=Tag` or `nsu=urn:MyServer;s=Tag` and resolves it to a node
+    /// id of the server, as `resolve_node_id()` does. Node ids that name their namespace by uri
+    /// stay valid when the server's namespace indexes change, so they are better to keep in
+    /// configuration.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(NodeId)` - The node id
+    /// * `Err(StatusCode)` - Status code reason for failure, e.g. `BadNodeIdInvalid` if the text is
+    ///   not a node id, or `BadNodeIdUnknown` if the server has no namespace with the uri
+    ///
+    pub fn parse_node_id(&mut self, node_id: &str) -> Result<NodeId, StatusCode> {
+        match self.namespace_map.parse_node_id(node_id) {
+            Err(StatusCode::BadNodeIdUnknown) => {
+                self.refresh_namespace_map()?;
+                let result = self.namespace_map.parse_node_id(node_id);
+                if result.is_err() {
+                    session_error!(self, "parse_node_id, node {} is not in a namespace of the server", node_id);
+                }
+                result
+            }
+            result => result
+        }
+    }
+
+    /// Reads a variable of the server object that holds an array of strings
+    fn read_string_array(&mut self, variable_id: VariableId) -> Result<Vec<String>, StatusCode> {
+        let node_id: NodeId = variable_id.into();
+        let data_value = self.read(&[(&node_id).into()])?
+            .pop()
+            .ok_or(StatusCode::BadUnexpectedError)?;
+        let status_code = data_value.status.unwrap_or(StatusCode::Good);
+        if status_code.is_bad() {
+            session_error!(self, "read_string_array, cannot read {:?}, status = {}", variable_id, status_code);
+            return Err(status_code);
+        }
+        let values = if let Some(Variant::Array(ref array)) = data_value.value {
+            array.values.iter().map(|value| {
+                if let Variant::String(ref value) = value {
+                    Some(value.as_ref().to_string())
+                } else {
+                    None
+                }
+            }).collect::<Option<Vec<String>>>()
+        } else {
+            None
+        };
+        values.ok_or_else(|| {
+            session_error!(self, "read_string_array, {:?} value {:?} is not an array of strings", variable_id, data_value.value);
+            StatusCode::BadTypeMismatch
+        })
+    }
+
+    /// Reads the `ServiceLevel` of the server, from 0 for a server that is in maintenance or has
+    /// no data, through 2-199 for one that is degraded, to 200-255 for a healthy server.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u8)` - The service level
+    /// * `Err(StatusCode)` - Status code reason for failure
+    ///
+    pub fn read_service_level(&mut self) -> Result<u8, StatusCode> {
+        self.read_value::<u8>(&VariableId::Server_ServiceLevel.into())
+    }
+
+    /// Reads what redundancy the server supports from its `ServerRedundancy` object.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(RedundancySupport)` - The redundancy support of the server
+    /// * `Err(StatusCode)` - Status code reason for failure, `BadTypeMismatch` if the value is not
+    ///   a `RedundancySupport`
+    ///
+    pub fn read_redundancy_support(&mut self) -> Result<RedundancySupport, StatusCode> {
+        match self.read_value::<i32>(&VariableId::Server_ServerRedundancy_RedundancySupport.into())? {
+            0 => Ok(RedundancySupport::None),
+            1 => Ok(RedundancySupport::Cold),
+            2 => Ok(RedundancySupport::Warm),
+            3 => Ok(RedundancySupport::Hot),
+            4 => Ok(RedundancySupport::Transparent),
+            5 => Ok(RedundancySupport::HotAndMirrored),
+            value => {
+                session_error!(self, "read_redundancy_support, {} is not a RedundancySupport", value);
+                Err(StatusCode::BadTypeMismatch)
+            }
+        }
+    }
+
+    /// Reads the application uris of the servers in the same non-transparent redundant server
+    /// set as this one, from the `ServerUriArray` of its `ServerRedundancy` object.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<String>)` - The uris of the servers
+    /// * `Err(StatusCode)` - Status code reason for failure, e.g. `BadNodeIdUnknown` if the
+    ///   server is not part of a non-transparent redundant server set
+    ///
+    pub fn read_server_uri_array(&mut self) -> Result<Vec<String>, StatusCode> {
+        self.read_string_array(VariableId::Server_ServerRedundancy_ServerUriArray)
+    }
+
+    /// Parses a path from the root folder into a browse path
+    pub(crate) fn browse_path_from_str(path: &str) -> Result<BrowsePath, StatusCode> {
+        let relative_path = path.parse::<RelativePath>()?;
+        if relative_path.elements.as_ref().map(|e| e.is_empty()).unwrap_or(true) {
+            return Err(StatusCode::BadBrowseNameInvalid);
+        }
+        Ok(BrowsePath {
+            starting_node: ObjectId::RootFolder.into(),
+            relative_path,
+        })
+    }
+
+    /// Returns the node that a translated browse path leads to. Only targets on this server that
+    /// match the whole path count.
+    fn target_of_browse_path(browse_path_result: BrowsePathResult) -> Result<NodeId, StatusCode> {
+        if browse_path_result.status_code.is_bad() {
+            return Err(browse_path_result.status_code);
+        }
+        browse_path_result.targets
+            .unwrap_or_default()
+            .into_iter()
+            .find(|target| {
+                target.remaining_path_index == u32::MAX && target.target_id.server_index == 0 && target.target_id.namespace_uri.is_null()
+            })
+            .map(|target| target.target_id.node_id)
+            .ok_or(StatusCode::BadNoMatch)
+    }
+
     ////////////////////////////////////////////////////////////////////////////////////////////////
     // Attribute Service set
     ////////////////////////////////////////////////////////////////////////////////////////////////
@@ -1560,6 +2232,211 @@ impl Session {
         }
     }
 
+    /// Reads the value of a variable and converts it to the type asked for, e.g.
+    /// `session.read_value::<f64>(&node_id)`. A value of another type is converted if the
+    /// conversion is implicit, so an `Int32` value can be read as an `f64` but a `Double` cannot
+    /// be read as an `i32`.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(T)` - The value
+    /// * `Err(StatusCode)` - Status code reason for failure, which is the status of the value if
+    ///   it is bad, or `BadTypeMismatch` if the value cannot be converted
+    ///
+    pub fn read_value<T>(&mut self, node_id: &NodeId) -> Result<T, StatusCode> where T: for<'a> TryFrom<&'a Variant> {
+        self.read_values(std::slice::from_ref(node_id))?
+            .pop()
+            .unwrap_or(Err(StatusCode::BadUnexpectedError))
+    }
+
+    /// Reads the values of variables in one request and converts them to the type asked for, as
+    /// `read_value()` does.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Vec<Result<T, StatusCode>>)` - The value of each node, or the reason it could not be
+    ///   read or converted, in the same order as the nodes
+    /// * `Err(StatusCode)` - Status code reason for failure of the request
+    ///
+    pub fn read_values<T>(&mut self, node_ids: &[NodeId]) -> Result<Vec<Result<T, StatusCode>>, StatusCode> where T: for<'a> TryFrom<&'a Variant> {
+        let nodes_to_read = node_ids.iter().map(|node_id| node_id.into()).collect::<Vec<ReadValueId>>();
+        let results = self.read(&nodes_to_read)?;
+        if results.len() != node_ids.len() {
+            session_error!(self, "read_values, server returned {} values for {} nodes", results.len(), node_ids.len());
+            return Err(StatusCode::BadUnexpectedError);
+        }
+        Ok(node_ids.iter().zip(results).map(|(node_id, data_value)| {
+            let status_code = data_value.status.unwrap_or(StatusCode::Good);
+            if status_code.is_bad() {
+                session_error!(self, "read_values, value of node {} is bad, status = {}", node_id, status_code);
+                return Err(status_code);
+            }
+            let value = data_value.value.unwrap_or(Variant::Empty);
+            T::try_from(&value).map_err(|_| {
+                session_error!(self, "read_values, value {:?} of node {} cannot be converted to {}", value, node_id, std::any::type_name::<T>());
+                StatusCode::BadTypeMismatch
+            })
+        }).collect())
+    }
+
+    /// Writes a value to a variable, converting it to the variable's data type first, e.g.
+    /// `session.write_value(&node_id, 42)` to a `Double` variable writes `42.0`. The data type and
+    /// value rank of the variable are read from the server the first time it is written and
+    /// remembered for the rest of the session.
+    ///
+    /// A value is only converted if nothing is lost, so `300` cannot be written to a `Byte`
+    /// variable and `1.5` cannot be written to an `Int32` variable. Values written to a variable
+    /// whose data type is abstract, such as `BaseDataType` or `Number`, or a structure are not
+    /// converted.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - The value was written
+    /// * `Err(StatusCode)` - Status code reason for failure, e.g. `BadTypeMismatch` if the value
+    ///   cannot be converted, or the status of the write
+    ///
+    pub fn write_value<V>(&mut self, node_id: &NodeId, value: V) -> Result<(), StatusCode> where V: Into<Variant> {
+        let value = value.into();
+        let value_type = self.value_type(node_id)?;
+        let value = match Self::value_for_write(&value, &value_type) {
+            Ok(value) => value,
+            Err(status_code) => {
+                session_error!(self, "write_value, value {:?} cannot be written to node {} of type {:?} with value rank {}", value, node_id, value_type.variant_type, value_type.value_rank);
+                return Err(status_code);
+            }
+        };
+        let results = self.write(&[WriteValue {
+            node_id: node_id.clone(),
+            attribute_id: AttributeId::Value as u32,
+            index_range: UAString::null(),
+            value: value.into(),
+        }])?;
+        let status_code = results
+            .and_then(|results| results.into_iter().next())
+            .unwrap_or(StatusCode::BadUnexpectedError);
+        if status_code.is_bad() {
+            // The variable may have changed, so look up its type again next time
+            self.value_types.remove(node_id);
+            session_error!(self, "write_value to node {} failed, status = {}", node_id, status_code);
+            Err(status_code)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns the type of the value of a variable, reading it from the server if it isn't known
+    fn value_type(&mut self, node_id: &NodeId) -> Result<ValueType, StatusCode> {
+        if let Some(value_type) = self.value_types.get(node_id) {
+            return Ok(value_type.clone());
+        }
+        let nodes_to_read = [AttributeId::DataType, AttributeId::ValueRank].iter().map(|attribute_id| ReadValueId {
+            node_id: node_id.clone(),
+            attribute_id: *attribute_id as u32,
+            index_range: UAString::null(),
+            data_encoding: QualifiedName::null(),
+        }).collect::<Vec<_>>();
+        let results = self.read(&nodes_to_read)?;
+        if results.len() != 2 {
+            session_error!(self, "value_type, server returned {} values for 2 attributes", results.len());
+            return Err(StatusCode::BadUnexpectedError);
+        }
+        for result in &results {
+            let status_code = result.status.unwrap_or(StatusCode::Good);
+            if status_code.is_bad() {
+                session_error!(self, "value_type, cannot read the type of node {}, status = {}", node_id, status_code);
+                return Err(status_code);
+            }
+        }
+        let data_type_id = if let Some(Variant::NodeId(ref data_type_id)) = results[0].value {
+            (**data_type_id).clone()
+        } else {
+            session_error!(self, "value_type, node {} has no data type", node_id);
+            return Err(StatusCode::BadDataTypeIdUnknown);
+        };
+        let value_rank = results[1].value.as_ref()
+            .and_then(|value_rank| i32::try_from(value_rank).ok())
+            .unwrap_or(-2);
+        let value_type = ValueType {
+            variant_type: self.built_in_type(&data_type_id)?,
+            value_rank,
+        };
+        self.value_types.insert(node_id.clone(), value_type.clone());
+        Ok(value_type)
+    }
+
+    /// Finds the built-in type that values of a data type are encoded as by following its
+    /// supertypes, e.g. `Duration` is a `Double` and enumerations are an `Int32`. Abstract types
+    /// and structures have no built-in type.
+    fn built_in_type(&mut self, data_type_id: &NodeId) -> Result<Option<VariantTypeId>, StatusCode> {
+        let no_built_in_type = [
+            DataTypeId::BaseDataType, DataTypeId::Number, DataTypeId::Integer, DataTypeId::UInteger,
+            DataTypeId::Decimal, DataTypeId::Structure, DataTypeId::DataValue, DataTypeId::DiagnosticInfo,
+        ];
+        let mut data_type_id = data_type_id.clone();
+        loop {
+            if let Ok(variant_type) = VariantTypeId::try_from(&data_type_id) {
+                return Ok(Some(variant_type));
+            } else if data_type_id == DataTypeId::Enumeration.into() {
+                return Ok(Some(VariantTypeId::Int32));
+            } else if no_built_in_type.iter().any(|t| data_type_id == (*t).into()) {
+                return Ok(None);
+            }
+            data_type_id = self.find_related_node(&data_type_id, ReferenceTypeId::HasSubtype)?;
+        }
+    }
+
+    /// Converts a value to be written to a variable of the value type, checking that a scalar or
+    /// array is allowed by its value rank.
+    pub(crate) fn value_for_write(value: &Variant, value_type: &ValueType) -> Result<Variant, StatusCode> {
+        let dimensions = match value {
+            Variant::Array(array) => Some(array.dimensions.len().max(1)),
+            _ => None,
+        };
+        let shape_allowed = match (value_type.value_rank, dimensions) {
+            // Scalar
+            (-1, dimensions) => dimensions.is_none(),
+            // Scalar or one dimension
+            (-3, dimensions) => dimensions.map(|d| d == 1).unwrap_or(true),
+            // Any
+            (-2, _) => true,
+            // One or more dimensions
+            (0, dimensions) => dimensions.is_some(),
+            (value_rank, dimensions) => dimensions == Some(value_rank as usize),
+        };
+        if !shape_allowed {
+            return Err(StatusCode::BadTypeMismatch);
+        }
+        let variant_type = if let Some(variant_type) = value_type.variant_type {
+            variant_type
+        } else {
+            return Ok(value.clone());
+        };
+        if let Variant::Array(array) = value {
+            let values = array.values.iter()
+                .map(|v| Self::convert_scalar(v, variant_type))
+                .collect::<Result<Vec<_>, StatusCode>>()?;
+            Ok(Variant::from(Array {
+                values,
+                dimensions: array.dimensions.clone(),
+            }))
+        } else {
+            Self::convert_scalar(value, variant_type)
+        }
+    }
+
+    /// Converts a scalar to a built-in type if it can be converted back to the same value
+    fn convert_scalar(value: &Variant, variant_type: VariantTypeId) -> Result<Variant, StatusCode> {
+        if value.type_id() == variant_type {
+            return Ok(value.clone());
+        }
+        let converted = value.cast(variant_type);
+        if converted == Variant::Empty || converted.cast(value.type_id()) != *value {
+            Err(StatusCode::BadTypeMismatch)
+        } else {
+            Ok(converted)
+        }
+    }
+
     /// Updates historical values. The caller is expected to encode history update operations into
     /// extension objects which must be one of the following:
     ///
@@ -1774,6 +2651,7 @@ impl Session {
                                 monitoring_mode: i.monitoring_mode,
                                 queue_size: r.revised_queue_size,
                                 sampling_interval: r.revised_sampling_interval,
+                                filter: i.requested_parameters.filter.clone(),
                             }
                         })
                         .collect::<Vec<subscription::CreateMonitoredItem>>();
@@ -1824,9 +2702,6 @@ impl Session {
             session_error!(self, "modify_monitored_items, called with no items to modify");
             Err(StatusCode::BadNothingToDo)
         } else {
-            let monitored_item_ids = items_to_modify.iter()
-                .map(|i| i.monitored_item_id)
-                .collect::<Vec<u32>>();
             let request = ModifyMonitoredItemsRequest {
                 request_header: self.make_request_header(),
                 subscription_id,
@@ -1838,13 +2713,14 @@ impl Session {
                 crate::process_service_result(&response.response_header)?;
                 if let Some(ref results) = response.results {
                     // Set the items in our internal state
-                    let items_to_modify = monitored_item_ids.iter()
+                    let items_to_modify = items_to_modify.iter()
                         .zip(results.iter())
-                        .map(|(id, r)| {
+                        .map(|(i, r)| {
                             subscription::ModifyMonitoredItem {
-                                id: *id,
+                                id: i.monitored_item_id,
                                 queue_size: r.revised_queue_size,
                                 sampling_interval: r.revised_sampling_interval,
+                                filter: i.requested_parameters.filter.clone(),
                             }
                         })
                         .collect::<Vec<subscription::ModifyMonitoredItem>>();
@@ -2242,6 +3118,42 @@ impl Session {
         }
     }
 
+    /// Asks the server to republish a notification message of a subscription that the client
+    /// missed, by sending a [`RepublishRequest`] to the server. The message is returned to the
+    /// caller, it is not passed to the subscription's callback.
+    ///
+    /// See OPC UA Part 4 - Services 5.13.6 for complete description of the service and error responses.
+    ///
+    /// # Arguments
+    ///
+    /// * `subscription_id` - subscription identifier returned from `create_subscription`.
+    /// * `retransmit_sequence_number` - the sequence number of the notification message to republish.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(NotificationMessage)` - The notification message with the sequence number
+    /// * `Err(StatusCode)` - Status code reason for failure, `BadMessageNotAvailable` if the server
+    ///   no longer has the message
+    ///
+    /// [`RepublishRequest`]: ./struct.RepublishRequest.html
+    ///
+    pub fn republish(&mut self, subscription_id: u32, retransmit_sequence_number: u32) -> Result<NotificationMessage, StatusCode> {
+        let request = RepublishRequest {
+            request_header: self.make_request_header(),
+            subscription_id,
+            retransmit_sequence_number,
+        };
+        let response = self.send_request(request)?;
+        if let SupportedMessage::RepublishResponse(response) = response {
+            crate::process_service_result(&response.response_header)?;
+            session_debug!(self, "republish success");
+            Ok(response.notification_message)
+        } else {
+            session_debug!(self, "republish failed {:?}", response);
+            Err(crate::process_unexpected_response(response))
+        }
+    }
+
     /// Deletes a subscription by sending a [`DeleteSubscriptionsRequest`] to the server.
     ///
     /// See OPC UA Part 4 - Services 5.13.8 for complete description of the service and error responses.
@@ -2343,6 +3255,122 @@ impl Session {
         }
     }
 
+    /// Decodes an extension object holding a structure that is not compiled into `opcua-types`,
+    /// such as a vendor specific type, into a `DynamicStructure`. The data type of the object's
+    /// encoding and the types of its fields are read from the server's `DataTypeDefinition`
+    /// attributes the first time they are seen and remembered for the rest of the session.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(DynamicStructure)` - The fields of the structure
+    /// * `Err(StatusCode)` - Status code reason for failure, e.g. `BadDataTypeIdUnknown` if the
+    ///   server does not describe the data type
+    ///
+    pub fn decode_extension_object(&mut self, value: &ExtensionObject) -> Result<DynamicStructure, StatusCode> {
+        let data_type_id = if let Some(data_type_id) = self.dynamic_types.data_type_of_encoding(&value.node_id) {
+            data_type_id.clone()
+        } else {
+            let data_type_id = self.find_related_node(&value.node_id, ReferenceTypeId::HasEncoding)?;
+            self.dynamic_types.add_encoding(value.node_id.clone(), data_type_id.clone());
+            data_type_id
+        };
+        self.load_data_types(&data_type_id)?;
+        let decoding_limits = {
+            let secure_channel = trace_read_lock_unwrap!(self.secure_channel);
+            secure_channel.decoding_limits()
+        };
+        self.dynamic_types.decode(value, &decoding_limits)
+    }
+
+    /// Encodes a `DynamicStructure` into an extension object that can be written to a variable or
+    /// passed as a method argument. The data type of the structure is read from the server if it
+    /// is not already known.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(ExtensionObject)` - The structure in its default binary encoding
+    /// * `Err(StatusCode)` - Status code reason for failure
+    ///
+    pub fn encode_dynamic_structure(&mut self, value: &DynamicStructure) -> Result<ExtensionObject, StatusCode> {
+        self.load_data_types(&value.data_type_id)?;
+        self.dynamic_types.encode(value)
+    }
+
+    /// Returns the data types used to decode and encode dynamic structures. Types can be added
+    /// here to avoid reading them from the server.
+    pub fn dynamic_types_mut(&mut self) -> &mut DynamicTypes {
+        &mut self.dynamic_types
+    }
+
+    /// Reads the data type, and any unknown types it uses, from the server. A type without a
+    /// `DataTypeDefinition` is treated as its supertype, so subtypes of built-in types and enums
+    /// from servers that predate OPC UA 1.04 can still be decoded.
+    fn load_data_types(&mut self, data_type_id: &NodeId) -> Result<(), StatusCode> {
+        let mut unknown = self.dynamic_types.unknown_types(data_type_id);
+        while !unknown.is_empty() {
+            let nodes_to_read = unknown.iter().map(|node_id| ReadValueId {
+                node_id: node_id.clone(),
+                attribute_id: AttributeId::DataTypeDefinition as u32,
+                index_range: UAString::null(),
+                data_encoding: QualifiedName::null(),
+            }).collect::<Vec<_>>();
+            let results = self.read(&nodes_to_read)?;
+            let decoding_limits = {
+                let secure_channel = trace_read_lock_unwrap!(self.secure_channel);
+                secure_channel.decoding_limits()
+            };
+            for (node_id, result) in unknown.iter().zip(results) {
+                let definition = match result.value {
+                    Some(Variant::ExtensionObject(ref value)) => DataTypeDefinition::from_extension_object(value, &decoding_limits).ok(),
+                    _ => None
+                };
+                if let Some(definition) = definition {
+                    self.dynamic_types.add_definition(node_id.clone(), definition);
+                } else {
+                    // Look for the nearest supertype that is known
+                    let mut super_type = node_id.clone();
+                    loop {
+                        super_type = self.find_related_node(&super_type, ReferenceTypeId::HasSubtype)?;
+                        if let Some(DataTypeDefinition::Structure(_)) = self.dynamic_types.definition(&super_type) {
+                            session_error!(self, "Data type {} is a structure with no DataTypeDefinition", node_id);
+                            return Err(StatusCode::BadDataTypeIdUnknown);
+                        } else if self.dynamic_types.is_known(&super_type) {
+                            break;
+                        }
+                    }
+                    if super_type == DataTypeId::Structure.into() || super_type == DataTypeId::Union.into() {
+                        session_error!(self, "Data type {} is a structure with no DataTypeDefinition", node_id);
+                        return Err(StatusCode::BadDataTypeIdUnknown);
+                    }
+                    self.dynamic_types.add_simple_type(node_id.clone(), super_type);
+                }
+            }
+            unknown = self.dynamic_types.unknown_types(data_type_id);
+        }
+        Ok(())
+    }
+
+    /// Returns the source of an inverse reference of the given type to the node, e.g. the data
+    /// type of an encoding or the supertype of a type.
+    fn find_related_node(&mut self, node_id: &NodeId, reference_type_id: ReferenceTypeId) -> Result<NodeId, StatusCode> {
+        let results = self.browse(&[BrowseDescription {
+            node_id: node_id.clone(),
+            browse_direction: BrowseDirection::Inverse,
+            reference_type_id: reference_type_id.into(),
+            include_subtypes: false,
+            node_class_mask: NodeClassMask::DATA_TYPE.bits(),
+            result_mask: 0,
+        }])?;
+        results.and_then(|results| results.into_iter().next())
+            .and_then(|result| result.references)
+            .and_then(|references| references.into_iter().next())
+            .map(|reference| reference.node_id.node_id)
+            .ok_or_else(|| {
+                session_error!(self, "Node {} has no inverse {:?} reference to a data type", node_id, reference_type_id);
+                StatusCode::BadDataTypeIdUnknown
+            })
+    }
+
     /// Returns the subscription state object
     pub fn subscription_state(&self) -> Arc<RwLock<SubscriptionState>> {
         self.subscription_state.clone()
@@ -2507,35 +3535,33 @@ impl Session {
             SupportedMessage::PublishResponse(response) => {
                 session_debug!(self, "PublishResponse");
 
-                // Update subscriptions based on response
-                // Queue acknowledgements for next request
-                let notification_message = response.notification_message.clone();
+                // Check the message follows on from the last one, recovering any that were missed
                 let subscription_id = response.subscription_id;
-
-                // Queue an acknowledgement for this request
-                {
-                    let mut session_state = trace_write_lock_unwrap!(self.session_state);
-                    session_state.add_subscription_acknowledgement(SubscriptionAcknowledgement {
-                        subscription_id,
-                        sequence_number: notification_message.sequence_number,
-                    });
-                }
-
-                let decoding_limits = {
-                    let secure_channel = trace_read_lock_unwrap!(self.secure_channel);
-                    secure_channel.decoding_limits()
+                let notification_message = &response.notification_message;
+                let sequence_number_check = {
+                    let subscription_state = trace_read_lock_unwrap!(self.subscription_state);
+                    subscription_state.check_sequence_number(subscription_id, notification_message.sequence_number)
                 };
-
-                // Process data change notifications
-                if let Some((data_change_notifications, events)) = notification_message.notifications(&decoding_limits) {
-                    session_debug!(self, "Received notifications, data changes = {}, events = {}", data_change_notifications.len(), events.len());
-                    if !data_change_notifications.is_empty() {
-                        let mut subscription_state = trace_write_lock_unwrap!(self.subscription_state);
-                        subscription_state.on_data_change(subscription_id, &data_change_notifications);
+                match sequence_number_check {
+                    SequenceNumberCheck::InOrder => {
+                        self.process_notification_message(subscription_id, notification_message);
                     }
-                    if !events.is_empty() {
-                        let mut subscription_state = trace_write_lock_unwrap!(self.subscription_state);
-                        subscription_state.on_event(subscription_id, &events);
+                    SequenceNumberCheck::Gap(missing_sequence_numbers) => {
+                        session_warn!(self, "Subscription {} missed notification messages {:?}", subscription_id, missing_sequence_numbers);
+                        self.recover_missing_notifications(subscription_id, &missing_sequence_numbers, response.available_sequence_numbers.as_deref());
+                        self.process_notification_message(subscription_id, notification_message);
+                    }
+                    SequenceNumberCheck::Duplicate => {
+                        session_debug!(self, "Ignoring notification message {} of subscription {} that was already received", notification_message.sequence_number, subscription_id);
+                    }
+                    SequenceNumberCheck::Jump => {
+                        session_error!(self, "Subscription {} jumped to notification message {}, any messages in between are lost", subscription_id, notification_message.sequence_number);
+                        {
+                            // The callback is told before the last sequence number moves on
+                            let mut subscription_state = trace_write_lock_unwrap!(self.subscription_state);
+                            subscription_state.on_sequence_number_jump(subscription_id, notification_message.sequence_number);
+                        }
+                        self.process_notification_message(subscription_id, notification_message);
                     }
                 }
             }
@@ -2561,6 +3587,76 @@ impl Session {
         }
     }
 
+    /// Republishes the notification messages of a subscription that were missed and processes them
+    /// in order. Only the messages that the server lists as available for retransmission are
+    /// asked for. The rest, and any it cannot republish, are lost and the subscription's callback
+    /// is told of them.
+    fn recover_missing_notifications(&mut self, subscription_id: u32, missing_sequence_numbers: &[u32], available_sequence_numbers: Option<&[u32]>) {
+        let available_sequence_numbers = available_sequence_numbers.unwrap_or(&[]).iter().collect::<HashSet<_>>();
+        let mut lost_sequence_numbers = Vec::new();
+        for sequence_number in missing_sequence_numbers {
+            if available_sequence_numbers.contains(sequence_number) {
+                match self.republish(subscription_id, *sequence_number) {
+                    Ok(notification_message) => {
+                        self.process_notification_message(subscription_id, &notification_message);
+                        continue;
+                    }
+                    Err(status_code) => {
+                        session_warn!(self, "Notification message {} of subscription {} could not be republished, error = {}", sequence_number, subscription_id, status_code);
+                    }
+                }
+            }
+            lost_sequence_numbers.push(*sequence_number);
+        }
+        if !lost_sequence_numbers.is_empty() {
+            session_error!(self, "Notification messages {:?} of subscription {} are lost", lost_sequence_numbers, subscription_id);
+            let mut subscription_state = trace_write_lock_unwrap!(self.subscription_state);
+            subscription_state.on_data_lost(subscription_id, &lost_sequence_numbers);
+        }
+    }
+
+    /// Processes a notification message of a subscription that was published or republished. It
+    /// queues an acknowledgement for the next publish request and sends the data change and event
+    /// notifications to the subscription.
+    fn process_notification_message(&mut self, subscription_id: u32, notification_message: &NotificationMessage) {
+        // A keep alive message carries no notifications and reuses the next sequence number, so
+        // there is nothing to acknowledge
+        if notification_message.is_keep_alive() {
+            return;
+        }
+
+        // Queue an acknowledgement for this request
+        {
+            let mut session_state = trace_write_lock_unwrap!(self.session_state);
+            session_state.add_subscription_acknowledgement(SubscriptionAcknowledgement {
+                subscription_id,
+                sequence_number: notification_message.sequence_number,
+            });
+        }
+
+        let decoding_limits = {
+            let secure_channel = trace_read_lock_unwrap!(self.secure_channel);
+            secure_channel.decoding_limits()
+        };
+
+        {
+            let mut subscription_state = trace_write_lock_unwrap!(self.subscription_state);
+            subscription_state.set_last_sequence_number(subscription_id, notification_message.sequence_number);
+        }
+
+        // Process data change notifications
+        if let Some((data_change_notifications, events)) = notification_message.notifications(&decoding_limits) {
+            session_debug!(self, "Received notifications, data changes = {}, events = {}", data_change_notifications.len(), events.len());
+            let mut subscription_state = trace_write_lock_unwrap!(self.subscription_state);
+            if !data_change_notifications.is_empty() {
+                subscription_state.on_data_change(subscription_id, &data_change_notifications);
+            }
+            if !events.is_empty() {
+                subscription_state.on_event(subscription_id, &events);
+            }
+        }
+    }
+
     /// Test if the supplied node id matches one of the supplied object ids. i.e. it must be in namespace 0,
     /// and have a numeric value that matches the scalar value of the supplied enums.
     pub(crate) fn node_id_is_one_of(node_id: &NodeId, object_ids: &[ObjectId]) -> bool {
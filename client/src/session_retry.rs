@@ -5,6 +5,8 @@
 use chrono::{DateTime, TimeZone, Utc};
 use time::Duration;
 
+use opcua_crypto::random;
+
 #[derive(PartialEq, Debug)]
 pub enum Answer {
     /// Retry immediately
@@ -15,6 +17,54 @@ pub enum Answer {
     GiveUp,
 }
 
+/// How the wait between retries grows as attempts to connect fail in a row.
+#[derive(Debug, PartialEq, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum RetryBackoff {
+    /// Wait the retry interval between every attempt
+    #[default]
+    Fixed,
+    /// Double the wait after each failed attempt, starting at the retry interval, up to a
+    /// maximum interval in milliseconds
+    Exponential { max_interval: u32 },
+    /// Wait a random time between the retry interval and three times the last wait, up to a
+    /// maximum interval in milliseconds. Clients that lose the same server at the same moment
+    /// spread their attempts out instead of all reconnecting together.
+    DecorrelatedJitter { max_interval: u32 },
+}
+
+impl RetryBackoff {
+    /// Returns the wait in milliseconds after a number of failed attempts in a row, given the
+    /// retry interval and the last wait, or 0 if there was none.
+    pub fn next_interval(&self, retry_interval: u32, retry_count: u32, last_interval: u32) -> u32 {
+        match *self {
+            RetryBackoff::Fixed => retry_interval,
+            RetryBackoff::Exponential { max_interval } => {
+                let factor = 1u32.checked_shl(retry_count.saturating_sub(1)).unwrap_or(u32::MAX);
+                retry_interval.saturating_mul(factor).min(max_interval.max(retry_interval))
+            }
+            RetryBackoff::DecorrelatedJitter { max_interval } => {
+                let low = u64::from(retry_interval);
+                let high = u64::from(last_interval.max(retry_interval)) * 3;
+                let mut bytes = [0u8; 4];
+                random::bytes(&mut bytes);
+                let interval = low + u64::from(u32::from_le_bytes(bytes)) % (high - low + 1);
+                interval.min(u64::from(max_interval.max(retry_interval))) as u32
+            }
+        }
+    }
+}
+
+/// When to give up and how long to wait between attempts
+#[derive(Debug, PartialEq, Clone)]
+struct RetrySchedule {
+    /// The maximum number of times to retry, or `None` for no limit
+    retry_limit: Option<u32>,
+    /// Interval between retries in milliseconds, or the first interval of a backoff
+    retry_interval: u32,
+    /// How the interval grows as attempts fail
+    backoff: RetryBackoff,
+}
+
 /// The session retry policy determines what to if the connection fails. In these circumstances,
 /// the client needs to re-establish a connection and the policy says how many times to try between
 /// failure and at what interval.
@@ -23,20 +73,28 @@ pub enum Answer {
 /// a `retry_interval` for the period of time in MS between each retry. Note that the policy retains
 /// its own minimum retry interval and will not retry any faster than that.
 ///
+/// The interval may grow as attempts fail according to a `RetryBackoff`. The first connection to
+/// the server can have a schedule of its own, e.g. to give up sooner when a server is not there
+/// at all than when a server that was there goes down.
+///
 /// Once a connection succeeds, the retry limit is reset.
 #[derive(Debug, PartialEq, Clone)]
 pub struct SessionRetryPolicy {
     /// The session timeout period in milliseconds. Used by client to run a keep-alive operation. Initially this
     /// will contain your desired timeout period, but it will be adjusted when the session is created.
     session_timeout: f64,
-    /// The maximum number of times to retry between failures before giving up. A value of 0 means
-    /// no retries, i.e. give up on first fail, None means no limit, i.e. infinity
-    retry_limit: Option<u32>,
-    /// Interval between retries in milliseconds
-    retry_interval: u32,
+    /// The schedule for reconnecting. A retry limit of 0 means no retries, i.e. give up on first
+    /// fail, None means no limit, i.e. infinity
+    reconnect: RetrySchedule,
+    /// The schedule for the first connection, if it differs from reconnecting
+    connect: Option<RetrySchedule>,
+    /// Set once a connection has succeeded, after which the reconnect schedule is used
+    connected: bool,
     /// The number of failed attempts so far since the last connection. When the connection succeeds
     /// this value is reset.
     retry_count: u32,
+    /// The wait in milliseconds after the last failed attempt, or 0 if none has failed
+    retry_wait: u32,
     /// The last retry attempt timestamp.
     last_attempt: DateTime<Utc>,
 }
@@ -63,10 +121,16 @@ impl SessionRetryPolicy {
         let retry_interval = if retry_interval < Self::MIN_RETRY_INTERVAL_MS { Self::MIN_RETRY_INTERVAL_MS } else { retry_interval };
         SessionRetryPolicy {
             session_timeout,
+            reconnect: RetrySchedule {
+                retry_limit: Some(retry_limit),
+                retry_interval,
+                backoff: RetryBackoff::Fixed,
+            },
+            connect: None,
+            connected: false,
             retry_count: 0,
+            retry_wait: 0,
             last_attempt: Self::last_attempt_default(),
-            retry_limit: Some(retry_limit),
-            retry_interval,
         }
     }
 
@@ -76,10 +140,16 @@ impl SessionRetryPolicy {
         let retry_interval = if retry_interval < Self::MIN_RETRY_INTERVAL_MS { Self::MIN_RETRY_INTERVAL_MS } else { retry_interval };
         SessionRetryPolicy {
             session_timeout,
+            reconnect: RetrySchedule {
+                retry_limit: None,
+                retry_interval,
+                backoff: RetryBackoff::Fixed,
+            },
+            connect: None,
+            connected: false,
             retry_count: 0,
+            retry_wait: 0,
             last_attempt: Self::last_attempt_default(),
-            retry_limit: None,
-            retry_interval,
         }
     }
 
@@ -96,16 +166,50 @@ impl SessionRetryPolicy {
         self.session_timeout
     }
 
+    /// Sets how the interval between reconnect attempts grows as they fail
+    pub fn set_backoff(&mut self, backoff: RetryBackoff) {
+        self.reconnect.backoff = backoff;
+    }
+
+    pub fn backoff(&self) -> RetryBackoff {
+        self.reconnect.backoff
+    }
+
+    /// Sets a schedule of its own for the first connection to the server, which is used until a
+    /// connection succeeds. A retry limit of `None` means no limit.
+    pub fn set_connect_retry(&mut self, retry_limit: Option<u32>, retry_interval: u32, backoff: RetryBackoff) {
+        let retry_interval = if retry_interval < Self::MIN_RETRY_INTERVAL_MS { Self::MIN_RETRY_INTERVAL_MS } else { retry_interval };
+        self.connect = Some(RetrySchedule {
+            retry_limit,
+            retry_interval,
+            backoff,
+        });
+    }
+
     pub fn retry_count(&self) -> u32 {
         self.retry_count
     }
 
+    /// Counts a failed attempt and works out how long to wait before the next one
     pub fn increment_retry_count(&mut self) {
         self.retry_count += 1;
+        let schedule = self.schedule();
+        self.retry_wait = schedule.backoff.next_interval(schedule.retry_interval, self.retry_count, self.retry_wait);
     }
 
+    /// Resets the count after a connection succeeds. From then on the reconnect schedule is used.
     pub fn reset_retry_count(&mut self) {
         self.retry_count = 0;
+        self.retry_wait = 0;
+        self.connected = true;
+    }
+
+    /// The schedule for the next attempt
+    fn schedule(&self) -> &RetrySchedule {
+        match self.connect {
+            Some(ref connect) if !self.connected => connect,
+            _ => &self.reconnect
+        }
     }
 
     pub fn set_last_attempt(&mut self, last_attempt: DateTime<Utc>) {
@@ -115,21 +219,23 @@ impl SessionRetryPolicy {
     /// Asks the policy, given the last retry attempt, should we try to connect again, wait a period of time
     /// or give up entirely.
     pub fn should_retry_connect(&self, now: DateTime<Utc>) -> Answer {
-        if let Some(retry_limit) = self.retry_limit {
+        let schedule = self.schedule();
+        if let Some(retry_limit) = schedule.retry_limit {
             if self.retry_count >= retry_limit {
                 // Number of retries have been exceeded
                 return Answer::GiveUp;
             }
         }
 
-        if self.retry_interval < Self::MIN_RETRY_INTERVAL_MS {
+        if schedule.retry_interval < Self::MIN_RETRY_INTERVAL_MS {
             // The constructors don't allow for this
             panic!("Retry interval is less than the minimum permitted.");
         }
 
         // Look at how much time has elapsed since the last attempt
         let elapsed = now - self.last_attempt;
-        let retry_interval = Duration::milliseconds(self.retry_interval as i64);
+        let retry_wait = if self.retry_wait == 0 { schedule.retry_interval } else { self.retry_wait };
+        let retry_interval = Duration::milliseconds(retry_wait as i64);
         if retry_interval > elapsed {
             // Wait a bit
             Answer::WaitFor((retry_interval - elapsed).num_milliseconds() as u32)
@@ -179,3 +285,59 @@ fn session_retry_never() {
     assert_eq!(session_retry.should_retry_connect(now), Answer::GiveUp);
     assert_eq!(session_retry.session_timeout(), 987.123);
 }
+
+#[test]
+fn session_retry_backoff() {
+    let fixed = RetryBackoff::Fixed;
+    assert_eq!(fixed.next_interval(1000, 1, 0), 1000);
+    assert_eq!(fixed.next_interval(1000, 20, 1000), 1000);
+
+    let exponential = RetryBackoff::Exponential { max_interval: 10000 };
+    assert_eq!(exponential.next_interval(1000, 1, 0), 1000);
+    assert_eq!(exponential.next_interval(1000, 2, 1000), 2000);
+    assert_eq!(exponential.next_interval(1000, 4, 4000), 8000);
+    assert_eq!(exponential.next_interval(1000, 5, 8000), 10000);
+    assert_eq!(exponential.next_interval(1000, 100, 10000), 10000);
+
+    let jitter = RetryBackoff::DecorrelatedJitter { max_interval: 10000 };
+    let mut last_interval = 0;
+    for retry_count in 1..50 {
+        let interval = jitter.next_interval(1000, retry_count, last_interval);
+        assert!((1000..=10000).contains(&interval));
+        assert!(interval <= last_interval.max(1000) * 3);
+        last_interval = interval;
+    }
+
+    // The policy waits longer after each failure and starts again after a connection
+    let mut session_retry = SessionRetryPolicy::infinity(0.0, 1000);
+    session_retry.set_backoff(exponential);
+    assert_eq!(session_retry.backoff(), exponential);
+    let now = Utc::now();
+    session_retry.set_last_attempt(now);
+    session_retry.increment_retry_count();
+    assert_eq!(session_retry.should_retry_connect(now), Answer::WaitFor(1000));
+    session_retry.increment_retry_count();
+    session_retry.increment_retry_count();
+    assert_eq!(session_retry.should_retry_connect(now), Answer::WaitFor(4000));
+    session_retry.reset_retry_count();
+    session_retry.increment_retry_count();
+    assert_eq!(session_retry.should_retry_connect(now), Answer::WaitFor(1000));
+}
+
+#[test]
+fn session_retry_connect() {
+    // The first connection gives up after 2 retries, reconnects never give up
+    let mut session_retry = SessionRetryPolicy::infinity(0.0, 5000);
+    session_retry.set_connect_retry(Some(2), 1000, RetryBackoff::Fixed);
+    let now = Utc::now();
+    session_retry.set_last_attempt(now);
+    session_retry.increment_retry_count();
+    assert_eq!(session_retry.should_retry_connect(now), Answer::WaitFor(1000));
+    session_retry.increment_retry_count();
+    assert_eq!(session_retry.should_retry_connect(now), Answer::GiveUp);
+
+    session_retry.reset_retry_count();
+    session_retry.increment_retry_count();
+    session_retry.increment_retry_count();
+    assert_eq!(session_retry.should_retry_connect(now), Answer::WaitFor(5000));
+}
@@ -8,10 +8,12 @@ use std::{
         Arc, atomic::{AtomicU32, Ordering},
         RwLock,
     },
+    time::Duration,
     u32,
 };
 
 use chrono;
+use futures::sync::oneshot;
 
 use opcua_core::{
     comms::secure_channel::SecureChannel,
@@ -25,6 +27,7 @@ use opcua_types::{
 };
 
 use crate::{
+    async_session::ResponseFuture,
     callbacks::{OnConnectionStatusChange, OnSessionClosed},
     message_queue::MessageQueue,
 };
@@ -82,6 +85,8 @@ pub(crate) struct SessionState {
     wait_for_publish_response: bool,
     /// The message queue
     message_queue: Arc<RwLock<MessageQueue>>,
+    /// Receives the response to a request to renew the secure channel's token that is in flight
+    security_token_renewal: Option<oneshot::Receiver<Result<SupportedMessage, StatusCode>>>,
     /// Connection closed callback
     session_closed_callback: Option<Box<dyn OnSessionClosed + Send + Sync + 'static>>,
     /// Connection status callback
@@ -130,6 +135,7 @@ impl SessionState {
             authentication_token: NodeId::null(),
             monitored_item_handle: Handle::new(Self::FIRST_MONITORED_ITEM_HANDLE),
             message_queue,
+            security_token_renewal: None,
             subscription_acknowledgements: Vec::new(),
             wait_for_publish_response: false,
             session_closed_callback: None,
@@ -195,6 +201,18 @@ impl SessionState {
         }
     }
 
+    pub(crate) fn on_server_state_change(&mut self, server_state: ServerState) {
+        if let Some(ref mut connection_status) = self.connection_status_callback {
+            connection_status.on_server_state_change(server_state);
+        }
+    }
+
+    pub(crate) fn on_connect_attempt(&mut self, status_code: StatusCode, retry_count: u32, retry_in: Option<u32>) {
+        if let Some(ref mut connection_status) = self.connection_status_callback {
+            connection_status.on_connect_attempt(status_code, retry_count, retry_in);
+        }
+    }
+
     pub(crate) fn connection_state(&self) -> Arc<RwLock<ConnectionState>> {
         self.connection_state.clone()
     }
@@ -254,11 +272,12 @@ impl SessionState {
         self.request_handle.reset();
         self.monitored_item_handle.reset();
 
-        // Clear the message queue
+        // Clear the message queue, which fails any renewal of the token that is in flight
         {
             let mut message_queue = trace_write_lock_unwrap!(self.message_queue);
             message_queue.clear();
         };
+        self.security_token_renewal = None;
     }
 
     /// Asynchronously sends a request. The return value is the request handle of the request
@@ -268,7 +287,7 @@ impl SessionState {
             SupportedMessage::OpenSecureChannelRequest(_) | SupportedMessage::CloseSecureChannelRequest(_) => {}
             _ => {
                 // Make sure secure channel token hasn't expired
-                let _ = self.ensure_secure_channel_token();
+                self.ensure_secure_channel_token();
             }
         }
 
@@ -281,6 +300,26 @@ impl SessionState {
         Ok(request_handle)
     }
 
+    /// Sends a request without waiting. The returned future completes with the response, or fails
+    /// if the request cannot be sent or no response arrives within the request timeout.
+    pub(crate) fn send_request_for_future<T>(&mut self, request: T) -> ResponseFuture where T: Into<SupportedMessage> {
+        match self.send_request_with_receiver(request) {
+            Ok(receiver) => ResponseFuture::new(receiver),
+            Err(status_code) => ResponseFuture::failed(status_code),
+        }
+    }
+
+    /// Sends a request without waiting for its response, which is delivered to the receiver
+    pub(crate) fn send_request_with_receiver<T>(&mut self, request: T) -> Result<oneshot::Receiver<Result<SupportedMessage, StatusCode>>, StatusCode> where T: Into<SupportedMessage> {
+        // Make sure secure channel token hasn't expired
+        self.ensure_secure_channel_token();
+        let (sender, receiver) = oneshot::channel();
+        let timeout = Duration::from_millis(u64::from(self.request_timeout));
+        let mut message_queue = trace_write_lock_unwrap!(self.message_queue);
+        message_queue.add_request_with_sender(request.into(), timeout, sender)?;
+        Ok(receiver)
+    }
+
     pub(crate) fn quit(&mut self) {
         let mut message_queue = trace_write_lock_unwrap!(self.message_queue);
         message_queue.quit();
@@ -331,22 +370,56 @@ impl SessionState {
         message_queue.add_request(request, is_async)
     }
 
-    /// Checks if secure channel token needs to be renewed and renews it
-    fn ensure_secure_channel_token(&mut self) -> Result<(), StatusCode> {
+    /// Starts to renew the secure channel's token if it is close to expiring, and applies the
+    /// token of a renewal whose response has arrived. It does not wait for the response, so the
+    /// old token is used until the new one has been applied by a later call.
+    pub(crate) fn ensure_secure_channel_token(&mut self) {
+        self.apply_security_token_renewal();
+        if self.security_token_renewal.is_some() {
+            return;
+        }
         let should_renew_security_token = {
             let secure_channel = trace_read_lock_unwrap!(self.secure_channel);
             secure_channel.should_renew_security_token()
         };
         if should_renew_security_token {
-            self.issue_or_renew_secure_channel(SecurityTokenRequestType::Renew)
-        } else {
-            Ok(())
+            let request = self.make_open_secure_channel_request(SecurityTokenRequestType::Renew);
+            let (sender, receiver) = oneshot::channel();
+            let timeout = Duration::from_millis(u64::from(self.request_timeout));
+            let mut message_queue = trace_write_lock_unwrap!(self.message_queue);
+            match message_queue.add_request_with_sender(request.into(), timeout, sender) {
+                Ok(()) => self.security_token_renewal = Some(receiver),
+                Err(status_code) => error!("Cannot send a request to renew the secure channel token, {}", status_code),
+            }
+        }
+    }
+
+    /// Applies the token of a renewal whose response has arrived. A renewal that failed is
+    /// forgotten so the next call to `ensure_secure_channel_token()` tries again.
+    fn apply_security_token_renewal(&mut self) {
+        if let Some(mut receiver) = self.security_token_renewal.take() {
+            match receiver.try_recv() {
+                Ok(None) => self.security_token_renewal = Some(receiver),
+                Ok(Some(Ok(response))) => {
+                    if let Err(status_code) = self.set_security_token(response) {
+                        error!("Cannot apply the renewed secure channel token, {}", status_code);
+                    }
+                }
+                Ok(Some(Err(status_code))) => error!("Renewal of the secure channel token failed, {}", status_code),
+                Err(_) => debug!("Renewal of the secure channel token was cancelled"),
+            }
         }
     }
 
     pub(crate) fn issue_or_renew_secure_channel(&mut self, request_type: SecurityTokenRequestType) -> Result<(), StatusCode> {
         trace!("issue_or_renew_secure_channel({:?})", request_type);
+        let request = self.make_open_secure_channel_request(request_type);
+        let response = self.send_request(request)?;
+        self.set_security_token(response)
+    }
 
+    /// Makes a request to issue or renew the secure channel's token with a new client nonce
+    fn make_open_secure_channel_request(&mut self, request_type: SecurityTokenRequestType) -> OpenSecureChannelRequest {
         const REQUESTED_LIFETIME: u32 = 60000; // TODO
 
         let (security_mode, security_policy, client_nonce) = {
@@ -360,26 +433,27 @@ impl SessionState {
         info!("security_mode = {:?}", security_mode);
         info!("security_policy = {:?}", security_policy);
 
-        let requested_lifetime = REQUESTED_LIFETIME;
-        let request = OpenSecureChannelRequest {
+        OpenSecureChannelRequest {
             request_header: self.make_request_header(),
             client_protocol_version: 0,
             request_type,
             security_mode,
             client_nonce,
-            requested_lifetime,
-        };
-        let response = self.send_request(request)?;
+            requested_lifetime: REQUESTED_LIFETIME,
+        }
+    }
+
+    /// Sets the token from the response to an OpenSecureChannel request and derives the keys
+    /// of the channel from the nonces
+    fn set_security_token(&mut self, response: SupportedMessage) -> Result<(), StatusCode> {
         if let SupportedMessage::OpenSecureChannelResponse(response) = response {
             debug!("Setting transport's security token");
-            {
-                let mut secure_channel = trace_write_lock_unwrap!(self.secure_channel);
-                secure_channel.set_security_token(response.security_token.clone());
-
-                if security_policy != SecurityPolicy::None && (security_mode == MessageSecurityMode::Sign || security_mode == MessageSecurityMode::SignAndEncrypt) {
-                    secure_channel.set_remote_nonce_from_byte_string(&response.server_nonce)?;
-                    secure_channel.derive_keys();
-                }
+            let mut secure_channel = trace_write_lock_unwrap!(self.secure_channel);
+            secure_channel.set_security_token(response.security_token.clone());
+            let (security_mode, security_policy) = (secure_channel.security_mode(), secure_channel.security_policy());
+            if security_policy != SecurityPolicy::None && (security_mode == MessageSecurityMode::Sign || security_mode == MessageSecurityMode::SignAndEncrypt) {
+                secure_channel.set_remote_nonce_from_byte_string(&response.server_nonce)?;
+                secure_channel.derive_keys();
             }
             Ok(())
         } else {
@@ -18,7 +18,7 @@ use std::{
     sync::{Arc, Mutex},
 };
 
-use opcua_types::{*, service_types::{DataChangeNotification, ReadValueId}};
+use opcua_types::{*, service_types::{DataChangeNotification, ReadValueId}, status_code::StatusCode};
 
 use crate::callbacks::OnSubscriptionNotification;
 
@@ -30,12 +30,14 @@ pub(crate) struct CreateMonitoredItem {
     pub queue_size: u32,
     pub discard_oldest: bool,
     pub sampling_interval: f64,
+    pub filter: ExtensionObject,
 }
 
 pub(crate) struct ModifyMonitoredItem {
     pub id: u32,
     pub sampling_interval: f64,
     pub queue_size: u32,
+    pub filter: ExtensionObject,
 }
 
 #[derive(Debug)]
@@ -54,6 +56,8 @@ pub struct MonitoredItem {
     monitoring_mode: MonitoringMode,
     /// Sampling interval
     sampling_interval: f64,
+    /// Filter of the data changes or events that are reported
+    filter: ExtensionObject,
     /// Last value of the item
     value: DataValue,
     /// Triggered items
@@ -66,6 +70,7 @@ impl MonitoredItem {
             id: 0,
             queue_size: 0,
             sampling_interval: 0.0,
+            filter: ExtensionObject::null(),
             item_to_monitor: ReadValueId {
                 node_id: NodeId::null(),
                 attribute_id: 0,
@@ -100,6 +105,8 @@ impl MonitoredItem {
 
     pub fn discard_oldest(&self) -> bool { self.discard_oldest }
 
+    pub fn filter(&self) -> &ExtensionObject { &self.filter }
+
     pub(crate) fn set_id(&mut self, value: u32) {
         self.id = value;
     }
@@ -124,6 +131,10 @@ impl MonitoredItem {
         self.discard_oldest = discard_oldest;
     }
 
+    pub(crate) fn set_filter(&mut self, filter: ExtensionObject) {
+        self.filter = filter;
+    }
+
     pub(crate) fn set_triggering(&mut self, links_to_add: &[u32], links_to_remove: &[u32]) {
         links_to_remove.iter().for_each(|i| { self.triggered_items.remove(i); });
         links_to_add.iter().for_each(|i| { self.triggered_items.insert(*i); });
@@ -132,6 +143,45 @@ impl MonitoredItem {
     pub(crate) fn triggered_items(&self) -> &BTreeSet<u32> {
         &self.triggered_items
     }
+
+    pub(crate) fn set_triggered_items(&mut self, triggered_items: BTreeSet<u32>) {
+        self.triggered_items = triggered_items;
+    }
+}
+
+/// How a subscription was recovered after the session reconnected to the server. The
+/// subscription's [`OnSubscriptionNotification`] is told through `on_subscription_recovered()`.
+///
+/// [`OnSubscriptionNotification`]: ../callbacks/trait.OnSubscriptionNotification.html
+#[derive(Debug, Clone, PartialEq)]
+pub enum SubscriptionRecovery {
+    /// The existing session was reactivated so the subscription carried on. The number of
+    /// notification messages that were missed and have been republished is given.
+    Reactivated { republished: usize },
+    /// The subscription was transferred to a new session, keeping its id and monitored items. The
+    /// number of notification messages that were missed and have been republished is given.
+    Transferred { republished: usize },
+    /// The subscription could not be recovered so it was created again. It has a new id and its
+    /// monitored items have new ids, which are given as pairs of the previous id and the new id.
+    /// Monitored items that could not be created again are given as pairs of their previous id and
+    /// the reason, and are no longer part of the subscription. Notifications sent while the client
+    /// was disconnected are lost.
+    Recreated { previous_subscription_id: u32, monitored_item_ids: Vec<(u32, u32)>, failed_monitored_items: Vec<(u32, StatusCode)> },
+}
+
+/// What the sequence number of a notification message received for a subscription says about the
+/// messages before it
+#[derive(Debug, PartialEq)]
+pub(crate) enum SequenceNumberCheck {
+    /// The message follows on from the last one, or is the first
+    InOrder,
+    /// The messages with these sequence numbers were missed before this one
+    Gap(Vec<u32>),
+    /// The message was received already, e.g. it was republished before it arrived
+    Duplicate,
+    /// The message is further ahead of the last one than messages are recovered for, so those in
+    /// between are lost or the server has started numbering again
+    Jump,
 }
 
 pub struct Subscription {
@@ -156,6 +206,8 @@ pub struct Subscription {
     monitored_items: HashMap<u32, MonitoredItem>,
     /// A map of client handle to monitored item id
     client_handles: HashMap<u32, u32>,
+    /// The sequence number of the last notification message received for the subscription
+    last_sequence_number: u32,
 }
 
 impl Subscription {
@@ -175,6 +227,7 @@ impl Subscription {
             notification_callback,
             monitored_items: HashMap::new(),
             client_handles: HashMap::new(),
+            last_sequence_number: 0,
         }
     }
 
@@ -196,6 +249,55 @@ impl Subscription {
 
     pub fn notification_callback(&self) -> Arc<Mutex<dyn OnSubscriptionNotification + Send + Sync>> { self.notification_callback.clone() }
 
+    pub fn last_sequence_number(&self) -> u32 { self.last_sequence_number }
+
+    pub(crate) fn set_last_sequence_number(&mut self, last_sequence_number: u32) { self.last_sequence_number = last_sequence_number; }
+
+    /// The largest gap in sequence numbers that is recovered as missed messages. A larger jump is
+    /// reported to the callback without trying to recover anything.
+    const MAX_SEQUENCE_NUMBER_GAP: u32 = 1000;
+
+    /// Checks the sequence number of a notification message against the last one received. A
+    /// keep-alive has the sequence number of the next message, so it is checked the same way and
+    /// also reveals messages that were missed. Sequence numbers wrap around from `u32::MAX` to 1.
+    pub(crate) fn check_sequence_number(&self, sequence_number: u32) -> SequenceNumberCheck {
+        let next_sequence_number = |n: u32| if n == u32::MAX { 1 } else { n + 1 };
+        if self.last_sequence_number == 0 {
+            return SequenceNumberCheck::InOrder;
+        }
+        // How far the message is ahead of the last one, skipping 0 if the numbers wrapped
+        let mut distance = sequence_number.wrapping_sub(self.last_sequence_number);
+        if sequence_number < self.last_sequence_number {
+            distance = distance.wrapping_sub(1);
+        }
+        if distance == 1 {
+            SequenceNumberCheck::InOrder
+        } else if distance == 0 || distance > u32::MAX / 2 {
+            SequenceNumberCheck::Duplicate
+        } else if distance > Self::MAX_SEQUENCE_NUMBER_GAP + 1 {
+            SequenceNumberCheck::Jump
+        } else {
+            let mut missing = Vec::with_capacity(distance as usize - 1);
+            let mut n = next_sequence_number(self.last_sequence_number);
+            while n != sequence_number {
+                missing.push(n);
+                n = next_sequence_number(n);
+            }
+            SequenceNumberCheck::Gap(missing)
+        }
+    }
+
+    /// Returns those of the sequence numbers that come after the last one received, in the order
+    /// the messages were sent. Sequence numbers wrap around from `u32::MAX` to 1.
+    pub(crate) fn sequence_numbers_after_last(&self, sequence_numbers: &[u32]) -> Vec<u32> {
+        let mut sequence_numbers = sequence_numbers.iter()
+            .filter(|n| self.check_sequence_number(**n) != SequenceNumberCheck::Duplicate)
+            .cloned()
+            .collect::<Vec<u32>>();
+        sequence_numbers.sort_by_key(|n| n.wrapping_sub(self.last_sequence_number));
+        sequence_numbers
+    }
+
     pub(crate) fn set_publishing_interval(&mut self, publishing_interval: f64) { self.publishing_interval = publishing_interval; }
 
     pub(crate) fn set_lifetime_count(&mut self, lifetime_count: u32) { self.lifetime_count = lifetime_count; }
@@ -217,6 +319,7 @@ impl Subscription {
             monitored_item.set_sampling_interval(i.sampling_interval);
             monitored_item.set_queue_size(i.queue_size);
             monitored_item.set_item_to_monitor(i.item_to_monitor.clone());
+            monitored_item.set_filter(i.filter.clone());
 
             let client_handle = monitored_item.client_handle();
             let monitored_item_id = monitored_item.id();
@@ -230,6 +333,7 @@ impl Subscription {
             if let Some(ref mut monitored_item) = self.monitored_items.get_mut(&i.id) {
                 monitored_item.set_sampling_interval(i.sampling_interval);
                 monitored_item.set_queue_size(i.queue_size);
+                monitored_item.set_filter(i.filter.clone());
             }
         });
     }
@@ -243,6 +347,22 @@ impl Subscription {
         })
     }
 
+    /// Takes the monitored items out of the subscription, ordered by their id
+    pub(crate) fn take_monitored_items(&mut self) -> Vec<MonitoredItem> {
+        self.client_handles.clear();
+        let mut monitored_items = self.monitored_items.drain().map(|(_, v)| v).collect::<Vec<_>>();
+        monitored_items.sort_by_key(|i| i.id());
+        monitored_items
+    }
+
+    /// Puts monitored items back into the subscription, replacing any with the same id
+    pub(crate) fn restore_monitored_items(&mut self, monitored_items: Vec<MonitoredItem>) {
+        monitored_items.into_iter().for_each(|monitored_item| {
+            self.client_handles.insert(monitored_item.client_handle(), monitored_item.id());
+            self.monitored_items.insert(monitored_item.id(), monitored_item);
+        });
+    }
+
     pub(crate) fn set_triggering(&mut self, triggering_item_id: u32, links_to_add: &[u32], links_to_remove: &[u32]) {
         if let Some(ref mut monitored_item) = self.monitored_items.get_mut(&triggering_item_id) {
             monitored_item.set_triggering(links_to_add, links_to_remove);
@@ -253,6 +373,21 @@ impl Subscription {
         self.client_handles.get(&client_handle).map(|monitored_item_id| *monitored_item_id)
     }
 
+    pub(crate) fn on_recovered(&mut self, recovery: &SubscriptionRecovery) {
+        let mut cb = trace_lock_unwrap!(self.notification_callback);
+        cb.on_subscription_recovered(self.subscription_id, recovery);
+    }
+
+    pub(crate) fn on_data_lost(&mut self, sequence_numbers: &[u32]) {
+        let mut cb = trace_lock_unwrap!(self.notification_callback);
+        cb.on_data_lost(self.subscription_id, sequence_numbers);
+    }
+
+    pub(crate) fn on_sequence_number_jump(&mut self, sequence_number: u32) {
+        let mut cb = trace_lock_unwrap!(self.notification_callback);
+        cb.on_sequence_number_jump(self.subscription_id, self.last_sequence_number, sequence_number);
+    }
+
     pub(crate) fn on_event(&mut self, events: &[EventNotificationList]) {
         let mut cb = trace_lock_unwrap!(self.notification_callback);
         events.iter().for_each(|event| {
@@ -84,6 +84,52 @@ impl SubscriptionState {
         });
     }
 
+    pub(crate) fn set_last_sequence_number(&mut self, subscription_id: u32, last_sequence_number: u32) {
+        if let Some(ref mut subscription) = self.subscriptions.get_mut(&subscription_id) {
+            subscription.set_last_sequence_number(last_sequence_number);
+        }
+    }
+
+    pub(crate) fn check_sequence_number(&self, subscription_id: u32, sequence_number: u32) -> SequenceNumberCheck {
+        if let Some(subscription) = self.subscriptions.get(&subscription_id) {
+            subscription.check_sequence_number(sequence_number)
+        } else {
+            SequenceNumberCheck::InOrder
+        }
+    }
+
+    pub(crate) fn sequence_numbers_after_last(&self, subscription_id: u32, sequence_numbers: &[u32]) -> Vec<u32> {
+        if let Some(subscription) = self.subscriptions.get(&subscription_id) {
+            subscription.sequence_numbers_after_last(sequence_numbers)
+        } else {
+            Vec::new()
+        }
+    }
+
+    pub(crate) fn on_data_lost(&mut self, subscription_id: u32, sequence_numbers: &[u32]) {
+        if let Some(ref mut subscription) = self.subscriptions.get_mut(&subscription_id) {
+            subscription.on_data_lost(sequence_numbers);
+        }
+    }
+
+    pub(crate) fn on_sequence_number_jump(&mut self, subscription_id: u32, sequence_number: u32) {
+        if let Some(ref mut subscription) = self.subscriptions.get_mut(&subscription_id) {
+            subscription.on_sequence_number_jump(sequence_number);
+        }
+    }
+
+    pub(crate) fn on_recovered(&mut self, subscription_id: u32, recovery: &SubscriptionRecovery) {
+        if let Some(ref mut subscription) = self.subscriptions.get_mut(&subscription_id) {
+            subscription.on_recovered(recovery);
+        }
+    }
+
+    pub(crate) fn restore_monitored_items(&mut self, subscription_id: u32, monitored_items: Vec<MonitoredItem>) {
+        if let Some(ref mut subscription) = self.subscriptions.get_mut(&subscription_id) {
+            subscription.restore_monitored_items(monitored_items);
+        }
+    }
+
     pub(crate) fn on_data_change(&mut self, subscription_id: u32, data_change_notifications: &[DataChangeNotification]) {
         if let Some(ref mut subscription) = self.subscriptions.get_mut(&subscription_id) {
             subscription.on_data_change(data_change_notifications);
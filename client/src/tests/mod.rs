@@ -165,3 +165,420 @@ fn node_id_is_one_of() {
     assert!(Session::node_id_is_one_of(&NodeId::from(&ObjectId::UpdateDataDetails_Encoding_DefaultBinary), &object_ids));
     assert!(Session::node_id_is_one_of(&NodeId::from(&ObjectId::DeleteEventDetails_Encoding_DefaultBinary), &object_ids));
 }
+
+#[test]
+fn reverse_connect_listener() {
+    use std::{io::Write, net::TcpStream, time::Duration};
+
+    use opcua_core::comms::tcp_types::{HelloMessage, ReverseHelloMessage};
+    use opcua_types::status_code::StatusCode;
+
+    use crate::reverse_connect::ReverseConnectListener;
+
+    let mut listener = ReverseConnectListener::bind("127.0.0.1:0").unwrap();
+    listener.set_timeout(Duration::from_millis(500));
+    let addr = listener.local_addr().unwrap();
+
+    let connect = |server_uri: &str, endpoint_url: &str| {
+        let mut socket = TcpStream::connect(addr).unwrap();
+        socket.write_all(&ReverseHelloMessage::new(server_uri, endpoint_url).encode_to_vec()).unwrap();
+        socket
+    };
+
+    // A connection from another server is held while waiting for the one asked for
+    let _a = connect("urn:a", "opc.tcp://a:4840/");
+    let _b = connect("urn:b", "opc.tcp://b:4840/");
+    assert!(listener.accept("urn:b", Some("opc.tcp://localhost:4840/")).is_ok());
+    assert!(listener.accept("urn:a", None).is_ok());
+
+    // The endpoint must match too
+    let _c = connect("urn:c", "opc.tcp://c:4840/");
+    assert_eq!(listener.accept("urn:c", Some("opc.tcp://c:4841/")).unwrap_err(), StatusCode::BadTimeout);
+    assert!(listener.accept("urn:c", Some("opc.tcp://c:4840/")).is_ok());
+
+    // Connections that do not begin with a reverse hello are dropped
+    let mut d = TcpStream::connect(addr).unwrap();
+    d.write_all(&HelloMessage::new("opc.tcp://d:4840/", 8196, 8196, 0).encode_to_vec()).unwrap();
+    assert_eq!(listener.accept("urn:d", None).unwrap_err(), StatusCode::BadTimeout);
+}
+
+#[test]
+fn async_responses() {
+    use std::time::{Duration, Instant};
+
+    use futures::{Future, Stream, sync::oneshot};
+    use opcua_core::supported_message::SupportedMessage;
+    use opcua_types::status_code::StatusCode;
+
+    use crate::{async_session::ResponseFuture, message_queue::MessageQueue};
+
+    let request = |request_handle: u32| -> SupportedMessage {
+        let mut request_header = RequestHeader::dummy();
+        request_header.request_handle = request_handle;
+        ReadRequest {
+            request_header,
+            max_age: 0f64,
+            timestamps_to_return: TimestampsToReturn::Both,
+            nodes_to_read: None,
+        }.into()
+    };
+    let add_request = |message_queue: &mut MessageQueue, request_handle: u32, timeout: Duration| {
+        let (sender, receiver) = oneshot::channel();
+        message_queue.add_request_with_sender(request(request_handle), timeout, sender).unwrap();
+        ResponseFuture::new(receiver)
+    };
+
+    let mut message_queue = MessageQueue::new();
+    let (sender, _) = oneshot::channel();
+    assert_eq!(message_queue.add_request_with_sender(request(1), Duration::from_secs(10), sender).unwrap_err(), StatusCode::BadNotConnected);
+    let (_, mut receiver) = message_queue.make_request_channel();
+
+    // Responses complete futures in any order
+    let first = add_request(&mut message_queue, 1, Duration::from_secs(10));
+    let second = add_request(&mut message_queue, 2, Duration::from_secs(10));
+    assert_eq!(receiver.by_ref().take(2).collect().wait().unwrap().len(), 2);
+    let response = |request_handle: u32| -> SupportedMessage {
+        ReadResponse {
+            response_header: ResponseHeader::new_good(&RequestHeader::new(&NodeId::null(), &DateTime::now(), request_handle)),
+            results: None,
+            diagnostic_infos: None,
+        }.into()
+    };
+    message_queue.store_response(response(2));
+    message_queue.store_response(response(1));
+    assert_eq!(second.wait().unwrap().request_handle(), 2);
+    assert_eq!(first.wait().unwrap().request_handle(), 1);
+
+    // Requests without a response time out, or fail when the session is reset or the connection
+    // goes
+    let timed_out = add_request(&mut message_queue, 3, Duration::from_millis(0));
+    let reset = add_request(&mut message_queue, 4, Duration::from_secs(10));
+    message_queue.expire_requests(Instant::now());
+    assert_eq!(timed_out.wait().unwrap_err(), StatusCode::BadTimeout);
+    message_queue.clear();
+    assert_eq!(reset.wait().unwrap_err(), StatusCode::BadConnectionClosed);
+    let closed = add_request(&mut message_queue, 5, Duration::from_secs(10));
+    message_queue.fail_requests(StatusCode::BadConnectionClosed);
+    assert_eq!(closed.wait().unwrap_err(), StatusCode::BadConnectionClosed);
+    assert_eq!(ResponseFuture::failed(StatusCode::BadNothingToDo).wait().unwrap_err(), StatusCode::BadNothingToDo);
+}
+
+#[test]
+fn connection_manager() {
+    use std::{net::TcpListener, sync::{mpsc, Mutex}, time::Duration};
+
+    use opcua_types::status_code::StatusCode;
+
+    use crate::{
+        callbacks::ManagedConnectionStatusCallback,
+        client::{Client, IdentityToken},
+        connection_manager::{ConnectionManager, ConnectionStatus},
+        session_retry::SessionRetryPolicy,
+    };
+
+    // Nothing listens on the port once the listener is dropped, so connections to it are refused
+    // straight away and fail once their policy gives up
+    let port = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+    let endpoint_url = format!("opc.tcp://127.0.0.1:{}/", port);
+    let endpoint = || EndpointDescription::from((endpoint_url.as_ref(), SecurityPolicy::None.to_str(), MessageSecurityMode::None));
+
+    let mut connection_manager = ConnectionManager::new(Client::new(default_sample_config()), 2);
+    let (tx, rx) = mpsc::channel();
+    let tx = Mutex::new(tx);
+    connection_manager.set_status_change_callback(ManagedConnectionStatusCallback::new(move |id, status| {
+        let _ = tx.lock().unwrap().send((id.to_string(), status));
+    }));
+    assert!(!connection_manager.health().is_healthy());
+    connection_manager.add_connection_with_policy("never", endpoint(), IdentityToken::Anonymous, SessionRetryPolicy::never(0.0)).unwrap();
+    connection_manager.add_connection_with_policy("once", endpoint(), IdentityToken::Anonymous, SessionRetryPolicy::new(0.0, 1, 500)).unwrap();
+    assert_eq!(connection_manager.add_connection("once", endpoint(), IdentityToken::Anonymous).unwrap_err(), StatusCode::BadInvalidArgument);
+
+    let health = connection_manager.health();
+    assert_eq!(health.connections, 2);
+    assert!(!health.is_healthy());
+
+    let mut failed = Vec::new();
+    while failed.len() < 2 {
+        match rx.recv_timeout(Duration::from_secs(5)).expect("connections did not fail") {
+            (id, ConnectionStatus::Failed(_)) => failed.push(id),
+            (id, status) => panic!("unexpected status {:?} of {}", status, id),
+        }
+    }
+    failed.sort();
+    assert_eq!(failed, vec!["never".to_string(), "once".to_string()]);
+    assert_eq!(connection_manager.health().failed, 2);
+    match connection_manager.connection_status("once") {
+        Some(ConnectionStatus::Failed(status_code)) => assert!(status_code.is_bad()),
+        status => panic!("unexpected status {:?}", status),
+    }
+    assert!(connection_manager.session("once").is_none());
+    assert!(connection_manager.connection_status("other").is_none());
+
+    connection_manager.remove_connection("never").unwrap();
+    assert_eq!(connection_manager.remove_connection("never").unwrap_err(), StatusCode::BadNotFound);
+    assert_eq!(connection_manager.connection_ids(), vec!["once".to_string()]);
+
+    // A connection removed while it is connecting is cleaned up by its attempt
+    connection_manager.add_connection_with_policy("removed", endpoint(), IdentityToken::Anonymous, SessionRetryPolicy::never(0.0)).unwrap();
+    connection_manager.remove_connection("removed").unwrap();
+
+    connection_manager.stop();
+    assert!(connection_manager.connection_ids().is_empty());
+    assert_eq!(connection_manager.add_connection("never", endpoint(), IdentityToken::Anonymous).unwrap_err(), StatusCode::BadShutdown);
+}
+
+#[test]
+fn subscription_recovery() {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{
+        callbacks::OnSubscriptionNotification,
+        subscription::{CreateMonitoredItem, MonitoredItem, Subscription, SubscriptionRecovery},
+        subscription_state::SubscriptionState,
+    };
+
+    struct RecoveryCallback(Arc<Mutex<Vec<(u32, SubscriptionRecovery)>>>);
+
+    impl OnSubscriptionNotification for RecoveryCallback {
+        fn on_subscription_recovered(&mut self, subscription_id: u32, recovery: &SubscriptionRecovery) {
+            self.0.lock().unwrap().push((subscription_id, recovery.clone()));
+        }
+    }
+
+    let recoveries = Arc::new(Mutex::new(Vec::new()));
+    let callback = Arc::new(Mutex::new(RecoveryCallback(recoveries.clone())));
+    let mut subscription = Subscription::new(1, 100.0, 30, 10, 0, true, 0, callback.clone());
+    let items_to_create = (1..=3).map(|i| CreateMonitoredItem {
+        id: i,
+        client_handle: 100 + i,
+        item_to_monitor: NodeId::new(2, i).into(),
+        monitoring_mode: MonitoringMode::Reporting,
+        queue_size: 1,
+        discard_oldest: true,
+        sampling_interval: 100.0,
+        filter: ExtensionObject::null(),
+    }).collect::<Vec<_>>();
+    subscription.insert_monitored_items(&items_to_create);
+    subscription.set_triggering(1, &[2, 3], &[]);
+
+    // The items come out in id order and go back in with new ids, keeping their client handles
+    // and triggered items
+    let monitored_items = subscription.take_monitored_items();
+    assert!(subscription.monitored_items().is_empty());
+    assert_eq!(monitored_items.iter().map(|i| i.id()).collect::<Vec<_>>(), vec![1, 2, 3]);
+    let monitored_items = monitored_items.into_iter().map(|mut i| {
+        i.set_id(i.id() + 10);
+        i
+    }).collect::<Vec<MonitoredItem>>();
+
+    let mut subscription_state = SubscriptionState::new();
+    subscription_state.add_subscription(Subscription::new(2, 100.0, 30, 10, 0, true, 0, callback));
+    subscription_state.restore_monitored_items(2, monitored_items);
+    subscription_state.set_last_sequence_number(2, 5);
+    let recreated = subscription_state.get(2).unwrap();
+    assert_eq!(recreated.last_sequence_number(), 5);
+    assert_eq!(recreated.monitored_items().get(&12).unwrap().client_handle(), 102);
+    assert_eq!(recreated.monitored_items().get(&11).unwrap().triggered_items().len(), 2);
+
+    let recovery = SubscriptionRecovery::Recreated { previous_subscription_id: 1, monitored_item_ids: vec![(1, 11), (2, 12), (3, 13)], failed_monitored_items: vec![] };
+    subscription_state.on_recovered(2, &recovery);
+    subscription_state.on_recovered(3, &SubscriptionRecovery::Reactivated { republished: 0 });
+    assert_eq!(*recoveries.lock().unwrap(), vec![(2, recovery)]);
+}
+
+#[test]
+fn subscription_sequence_numbers() {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{
+        callbacks::OnSubscriptionNotification,
+        subscription::{SequenceNumberCheck, Subscription},
+        subscription_state::SubscriptionState,
+    };
+
+    type Lost = Arc<Mutex<Vec<(u32, Vec<u32>)>>>;
+    type Jumps = Arc<Mutex<Vec<(u32, u32, u32)>>>;
+
+    struct DataLostCallback(Lost, Jumps);
+
+    impl OnSubscriptionNotification for DataLostCallback {
+        fn on_data_lost(&mut self, subscription_id: u32, sequence_numbers: &[u32]) {
+            self.0.lock().unwrap().push((subscription_id, sequence_numbers.to_vec()));
+        }
+
+        fn on_sequence_number_jump(&mut self, subscription_id: u32, last_sequence_number: u32, sequence_number: u32) {
+            self.1.lock().unwrap().push((subscription_id, last_sequence_number, sequence_number));
+        }
+    }
+
+    let lost = Arc::new(Mutex::new(Vec::new()));
+    let jumps = Arc::new(Mutex::new(Vec::new()));
+    let callback = Arc::new(Mutex::new(DataLostCallback(lost.clone(), jumps.clone())));
+    let mut subscription = Subscription::new(1, 100.0, 30, 10, 0, true, 0, callback);
+
+    // Nothing received yet, so anything is in order
+    assert_eq!(subscription.check_sequence_number(7), SequenceNumberCheck::InOrder);
+
+    subscription.set_last_sequence_number(7);
+    assert_eq!(subscription.check_sequence_number(8), SequenceNumberCheck::InOrder);
+    assert_eq!(subscription.check_sequence_number(11), SequenceNumberCheck::Gap(vec![8, 9, 10]));
+    assert_eq!(subscription.check_sequence_number(7), SequenceNumberCheck::Duplicate);
+    assert_eq!(subscription.check_sequence_number(3), SequenceNumberCheck::Duplicate);
+    // A big jump is too far to recover the messages in between
+    assert_eq!(subscription.check_sequence_number(1008), SequenceNumberCheck::Gap((8..1008).collect()));
+    assert_eq!(subscription.check_sequence_number(1009), SequenceNumberCheck::Jump);
+    assert_eq!(subscription.check_sequence_number(100_000), SequenceNumberCheck::Jump);
+
+    // Sequence numbers wrap around to 1, skipping 0
+    subscription.set_last_sequence_number(u32::MAX - 1);
+    assert_eq!(subscription.check_sequence_number(u32::MAX), SequenceNumberCheck::InOrder);
+    assert_eq!(subscription.check_sequence_number(2), SequenceNumberCheck::Gap(vec![u32::MAX, 1]));
+    subscription.set_last_sequence_number(u32::MAX);
+    assert_eq!(subscription.check_sequence_number(1), SequenceNumberCheck::InOrder);
+    assert_eq!(subscription.check_sequence_number(u32::MAX - 1), SequenceNumberCheck::Duplicate);
+
+    // Messages to republish come after the last one received, in the order they were sent
+    subscription.set_last_sequence_number(u32::MAX - 2);
+    assert_eq!(subscription.sequence_numbers_after_last(&[2, u32::MAX - 3, 1, u32::MAX, u32::MAX - 2, u32::MAX - 1]),
+               vec![u32::MAX - 1, u32::MAX, 1, 2]);
+    subscription.set_last_sequence_number(u32::MAX);
+
+    let mut subscription_state = SubscriptionState::new();
+    subscription_state.add_subscription(subscription);
+    assert_eq!(subscription_state.check_sequence_number(1, 3), SequenceNumberCheck::Gap(vec![1, 2]));
+    assert_eq!(subscription_state.check_sequence_number(2, 3), SequenceNumberCheck::InOrder);
+    subscription_state.on_data_lost(1, &[1, 2]);
+    subscription_state.on_data_lost(2, &[5]);
+    assert_eq!(*lost.lock().unwrap(), vec![(1, vec![1, 2])]);
+    subscription_state.on_sequence_number_jump(1, 5000);
+    subscription_state.on_sequence_number_jump(2, 5000);
+    assert_eq!(*jumps.lock().unwrap(), vec![(1, u32::MAX, 5000)]);
+}
+
+#[test]
+fn browse_tree_from_nodes() {
+    use crate::browser::{BrowsedNode, BrowseTree};
+
+    let node = |id: u32, parent: Option<u32>, depth: usize| BrowsedNode {
+        node_id: NodeId::new(2, id),
+        parent_node_id: parent.map(|p| NodeId::new(2, p)),
+        reference: None,
+        depth,
+        values: Vec::new(),
+    };
+
+    assert!(BrowseTree::from_nodes(Vec::new()).is_none());
+
+    // Nodes arrive breadth first, each after its parent
+    let tree = BrowseTree::from_nodes(vec![
+        node(1, None, 0),
+        node(2, Some(1), 1),
+        node(3, Some(1), 1),
+        node(4, Some(2), 2),
+        node(5, Some(3), 2),
+        node(6, Some(2), 2),
+    ]).unwrap();
+    assert_eq!(tree.node_count(), 6);
+    assert_eq!(tree.node.node_id, NodeId::new(2, 1));
+    assert_eq!(tree.children.iter().map(|c| c.node.node_id.clone()).collect::<Vec<_>>(), vec![NodeId::new(2, 2), NodeId::new(2, 3)]);
+    let branch = tree.find(&NodeId::new(2, 2)).unwrap();
+    assert_eq!(branch.children.iter().map(|c| c.node.node_id.clone()).collect::<Vec<_>>(), vec![NodeId::new(2, 4), NodeId::new(2, 6)]);
+    assert_eq!(tree.find(&NodeId::new(2, 5)).unwrap().node.depth, 2);
+    assert!(tree.find(&NodeId::new(2, 7)).is_none());
+}
+
+#[test]
+fn value_for_write() {
+    use opcua_types::status_code::StatusCode;
+
+    use crate::session::ValueType;
+
+    let scalar = |variant_type: VariantTypeId| ValueType { variant_type: Some(variant_type), value_rank: -1 };
+
+    // Converted when nothing is lost
+    assert_eq!(Session::value_for_write(&Variant::Int32(42), &scalar(VariantTypeId::Double)), Ok(Variant::Double(42.0)));
+    assert_eq!(Session::value_for_write(&Variant::Int32(42), &scalar(VariantTypeId::Byte)), Ok(Variant::Byte(42)));
+    assert_eq!(Session::value_for_write(&Variant::Double(2.0), &scalar(VariantTypeId::Int32)), Ok(Variant::Int32(2)));
+    assert_eq!(Session::value_for_write(&Variant::Int32(7), &scalar(VariantTypeId::Int32)), Ok(Variant::Int32(7)));
+
+    // Rejected when the value would change
+    assert_eq!(Session::value_for_write(&Variant::Int32(300), &scalar(VariantTypeId::Byte)), Err(StatusCode::BadTypeMismatch));
+    assert_eq!(Session::value_for_write(&Variant::Double(1.5), &scalar(VariantTypeId::Int32)), Err(StatusCode::BadTypeMismatch));
+    assert_eq!(Session::value_for_write(&Variant::from("hello"), &scalar(VariantTypeId::Int32)), Err(StatusCode::BadTypeMismatch));
+
+    // Abstract types are written as they are
+    let any = ValueType { variant_type: None, value_rank: -2 };
+    assert_eq!(Session::value_for_write(&Variant::from("hello"), &any), Ok(Variant::from("hello")));
+
+    // Arrays are converted element by element, and the value rank decides between scalar and array
+    let array = Variant::from(vec![1i32, 2, 3]);
+    let double_array = ValueType { variant_type: Some(VariantTypeId::Double), value_rank: 1 };
+    assert_eq!(Session::value_for_write(&array, &double_array), Ok(Variant::from(vec![1f64, 2.0, 3.0])));
+    assert_eq!(Session::value_for_write(&array, &scalar(VariantTypeId::Int32)), Err(StatusCode::BadTypeMismatch));
+    assert_eq!(Session::value_for_write(&Variant::Int32(1), &double_array), Err(StatusCode::BadTypeMismatch));
+    let scalar_or_array = ValueType { variant_type: Some(VariantTypeId::Int32), value_rank: -3 };
+    assert!(Session::value_for_write(&array, &scalar_or_array).is_ok());
+    assert!(Session::value_for_write(&Variant::Int32(1), &scalar_or_array).is_ok());
+}
+
+#[test]
+fn browse_path_from_str() {
+    use opcua_types::{node_ids::ReferenceTypeId, status_code::StatusCode};
+
+    let browse_path = Session::browse_path_from_str("/Objects/2:Boiler/2:Temperature").unwrap();
+    assert_eq!(browse_path.starting_node, ObjectId::RootFolder.into());
+    let elements = browse_path.relative_path.elements.unwrap();
+    assert_eq!(elements.iter().map(|e| e.target_name.clone()).collect::<Vec<_>>(), vec![
+        QualifiedName::new(0, "Objects"),
+        QualifiedName::new(2, "Boiler"),
+        QualifiedName::new(2, "Temperature"),
+    ]);
+    assert!(elements.iter().all(|e| e.reference_type_id == ReferenceTypeId::HierarchicalReferences.into() && e.include_subtypes && !e.is_inverse));
+
+    // Properties and other references can be followed too
+    let browse_path = Session::browse_path_from_str("/Objects/Server.ServerStatus").unwrap();
+    let elements = browse_path.relative_path.elements.unwrap();
+    assert_eq!(elements[2].reference_type_id, ReferenceTypeId::Aggregates.into());
+
+    assert_eq!(Session::browse_path_from_str("").unwrap_err(), StatusCode::BadBrowseNameInvalid);
+    assert_eq!(Session::browse_path_from_str("Objects").unwrap_err(), StatusCode::BadBrowseNameInvalid);
+}
+
+#[test]
+fn keep_alive_config() {
+    use crate::keep_alive::KeepAliveConfig;
+
+    let keep_alive_config = KeepAliveConfig::default();
+    assert_eq!(keep_alive_config.interval(), KeepAliveConfig::DEFAULT_INTERVAL_MS);
+    assert_eq!(keep_alive_config.max_missed(), KeepAliveConfig::DEFAULT_MAX_MISSED);
+    let keep_alive_config = KeepAliveConfig::new(1000, 0);
+    assert_eq!(keep_alive_config.interval(), 1000);
+    assert_eq!(keep_alive_config.max_missed(), 1);
+    assert_eq!(KeepAliveConfig::off().interval(), 0);
+}
+
+#[test]
+fn keep_alive_counter() {
+    use crate::keep_alive::{KeepAliveAction, KeepAliveCounter};
+
+    let mut counter = KeepAliveCounter::new(2);
+    assert_eq!(counter.on_timer(), KeepAliveAction::Send);
+    counter.on_response(true);
+    assert_eq!(counter.on_timer(), KeepAliveAction::Send);
+    assert_eq!(counter.missed(), 0);
+
+    // A keep-alive that is still outstanding when the next is due is missed
+    assert_eq!(counter.on_timer(), KeepAliveAction::Wait);
+    assert_eq!(counter.missed(), 1);
+    // An answer resets the count
+    counter.on_response(true);
+    assert_eq!(counter.on_timer(), KeepAliveAction::Send);
+    assert_eq!(counter.missed(), 0);
+
+    // Failed keep-alives are missed too
+    counter.on_response(false);
+    assert_eq!(counter.on_timer(), KeepAliveAction::Send);
+    assert_eq!(counter.missed(), 1);
+    assert_eq!(counter.on_timer(), KeepAliveAction::Dead);
+    assert_eq!(counter.missed(), 2);
+}
@@ -0,0 +1,387 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! Provides the HTTP framing used by the HTTPS transport profile (https-uabinary), OPC UA Part 6
+//! 7.4. Each service request is the body of an HTTP POST and its response is the body of the
+//! HTTP response. Bodies hold the node id of the message's binary encoding followed by the
+//! binary encoded message. There is no secure channel, the TLS connection that the caller wraps
+//! around the socket protects the messages.
+
+use std::io::{self, BufRead, Cursor, Read, Write};
+
+use bytes::BytesMut;
+use tokio_io::codec::{Decoder, Encoder};
+
+use opcua_types::{
+    BinaryEncoder, DecodingLimits, node_id::NodeId, status_code::StatusCode,
+};
+
+use crate::supported_message::SupportedMessage;
+
+/// Content type of a binary encoded request or response
+pub const CONTENT_TYPE_UA_BINARY: &str = "application/octet-stream";
+/// Header holding the uri of the security policy that the client uses for the session
+pub const SECURITY_POLICY_HEADER: &str = "OPCUA-SecurityPolicy";
+
+const MAX_LINE_LENGTH: usize = 8192;
+const MAX_HEADER_COUNT: usize = 64;
+/// The largest header, i.e. the start line and the header lines, that will be read
+const MAX_HEADER_SIZE: usize = 65536;
+
+/// An HTTP/1.1 request or response
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpMessage {
+    /// The request line, e.g. "POST /path HTTP/1.1", or status line, e.g. "HTTP/1.1 200 OK"
+    pub start_line: String,
+    /// Headers in the order they were read or added
+    pub headers: Vec<(String, String)>,
+    /// The body
+    pub body: Vec<u8>,
+}
+
+impl HttpMessage {
+    /// Creates a POST request holding a binary encoded message
+    pub fn post(host: &str, path: &str, body: Vec<u8>, security_policy_uri: &str) -> HttpMessage {
+        let path = if path.is_empty() { "/" } else { path };
+        HttpMessage {
+            start_line: format!("POST {} HTTP/1.1", path),
+            headers: vec![
+                ("Host".to_string(), host.to_string()),
+                ("Content-Type".to_string(), CONTENT_TYPE_UA_BINARY.to_string()),
+                (SECURITY_POLICY_HEADER.to_string(), security_policy_uri.to_string()),
+            ],
+            body,
+        }
+    }
+
+    /// Creates a response. A 200 response holds a binary encoded message, any other status an
+    /// empty body.
+    pub fn response(status: u16, reason: &str, body: Vec<u8>) -> HttpMessage {
+        HttpMessage {
+            start_line: format!("HTTP/1.1 {} {}", status, reason),
+            headers: vec![("Content-Type".to_string(), CONTENT_TYPE_UA_BINARY.to_string())],
+            body,
+        }
+    }
+
+    /// Returns the value of the header, ignoring the case of its name
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn set_header(&mut self, name: &str, value: &str) {
+        self.headers.retain(|(n, _)| !n.eq_ignore_ascii_case(name));
+        self.headers.push((name.to_string(), value.to_string()));
+    }
+
+    /// Returns the method of a request
+    pub fn method(&self) -> Option<&str> {
+        self.start_line.split(' ').next().filter(|m| !m.starts_with("HTTP/"))
+    }
+
+    /// Returns the path of a request
+    pub fn path(&self) -> Option<&str> {
+        if self.method().is_some() {
+            self.start_line.split(' ').nth(1)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the status of a response
+    pub fn status(&self) -> Option<u16> {
+        if self.start_line.starts_with("HTTP/") {
+            self.start_line.split(' ').nth(1).and_then(|s| s.parse().ok())
+        } else {
+            None
+        }
+    }
+
+    /// Tests if the connection stays open after this message
+    pub fn keep_alive(&self) -> bool {
+        match self.header("Connection") {
+            Some(connection) => !connection.eq_ignore_ascii_case("close"),
+            None => !self.start_line.contains("HTTP/1.0"),
+        }
+    }
+
+    /// Reads a message from the stream. Returns `None` if the stream is closed before the message
+    /// begins.
+    pub fn read<R: BufRead>(reader: &mut R, max_body_size: usize) -> Result<Option<HttpMessage>, StatusCode> {
+        let mut message = match Self::read_header(reader)? {
+            Some(message) => message,
+            None => return Ok(None),
+        };
+        message.body = if message.is_chunked() {
+            read_chunked_body(reader, max_body_size)?
+        } else if let Some(content_length) = message.content_length(max_body_size)? {
+            let mut body = vec![0u8; content_length];
+            reader.read_exact(&mut body).map_err(|_| StatusCode::BadCommunicationError)?;
+            body
+        } else {
+            Vec::new()
+        };
+        Ok(Some(message))
+    }
+
+    /// Reads the start line and headers of a message, leaving its body unread
+    fn read_header<R: BufRead>(reader: &mut R) -> Result<Option<HttpMessage>, StatusCode> {
+        let start_line = match read_line(reader)? {
+            Some(line) => line,
+            None => return Ok(None),
+        };
+        if start_line.split(' ').count() < 2 {
+            error!("HTTP start line \"{}\" is invalid", start_line);
+            return Err(StatusCode::BadDecodingError);
+        }
+        let mut headers = Vec::new();
+        loop {
+            let line = read_line(reader)?.ok_or(StatusCode::BadCommunicationError)?;
+            if line.is_empty() {
+                break;
+            }
+            if headers.len() == MAX_HEADER_COUNT {
+                error!("HTTP message has too many headers");
+                return Err(StatusCode::BadDecodingError);
+            }
+            let mut parts = line.splitn(2, ':');
+            let name = parts.next().unwrap().trim();
+            let value = parts.next().ok_or_else(|| {
+                error!("HTTP header \"{}\" is invalid", line);
+                StatusCode::BadDecodingError
+            })?.trim();
+            headers.push((name.to_string(), value.to_string()));
+        }
+        Ok(Some(HttpMessage { start_line, headers, body: Vec::new() }))
+    }
+
+    fn is_chunked(&self) -> bool {
+        matches!(self.header("Transfer-Encoding"), Some(v) if v.eq_ignore_ascii_case("chunked"))
+    }
+
+    /// Returns the length of the body from the Content-Length header, if there is one
+    fn content_length(&self, max_body_size: usize) -> Result<Option<usize>, StatusCode> {
+        if let Some(content_length) = self.header("Content-Length") {
+            let content_length: usize = content_length.parse().map_err(|_| {
+                error!("HTTP content length \"{}\" is invalid", content_length);
+                StatusCode::BadDecodingError
+            })?;
+            if content_length > max_body_size {
+                error!("HTTP body of {} bytes exceeds the limit of {}", content_length, max_body_size);
+                Err(StatusCode::BadRequestTooLarge)
+            } else {
+                Ok(Some(content_length))
+            }
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Returns the length of the message at the start of the buffer, or `None` if the buffer does
+    /// not hold all of it yet
+    fn message_len(buf: &[u8], max_body_size: usize) -> Result<Option<usize>, StatusCode> {
+        let header_end = match buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            Some(pos) => pos + 4,
+            None if buf.len() > MAX_HEADER_SIZE => {
+                error!("HTTP header is too long");
+                return Err(StatusCode::BadDecodingError);
+            }
+            None => return Ok(None),
+        };
+        let message = Self::read_header(&mut Cursor::new(&buf[..header_end]))?.ok_or(StatusCode::BadDecodingError)?;
+        if message.is_chunked() {
+            // Walk the chunks to the blank line that ends the trailers
+            let mut pos = header_end;
+            let mut body_len = 0;
+            loop {
+                let line_end = match next_line_end(buf, pos)? {
+                    Some(line_end) => line_end,
+                    None => return Ok(None),
+                };
+                let size = parse_chunk_size(&String::from_utf8_lossy(&buf[pos..line_end]))?;
+                pos = line_end;
+                if size == 0 {
+                    loop {
+                        let line_end = match next_line_end(buf, pos)? {
+                            Some(line_end) => line_end,
+                            None => return Ok(None),
+                        };
+                        let is_blank = buf[pos..line_end].iter().all(|b| *b == b'\r' || *b == b'\n');
+                        pos = line_end;
+                        if is_blank {
+                            return Ok(Some(pos));
+                        }
+                    }
+                }
+                if size > max_body_size.saturating_sub(body_len) {
+                    error!("HTTP body exceeds the limit of {}", max_body_size);
+                    return Err(StatusCode::BadRequestTooLarge);
+                }
+                body_len += size;
+                // Each chunk is followed by a CRLF
+                if buf.len() < pos + size {
+                    return Ok(None);
+                }
+                pos = match next_line_end(buf, pos + size)? {
+                    Some(line_end) => line_end,
+                    None => return Ok(None),
+                };
+            }
+        } else if let Some(content_length) = message.content_length(max_body_size)? {
+            let message_len = header_end + content_length;
+            Ok(if buf.len() >= message_len { Some(message_len) } else { None })
+        } else {
+            Ok(Some(header_end))
+        }
+    }
+
+    /// Writes the message to the stream with a Content-Length header for its body
+    pub fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let mut out = Vec::with_capacity(256 + self.body.len());
+        write!(&mut out, "{}\r\n", self.start_line)?;
+        for (name, value) in self.headers.iter().filter(|(n, _)| !n.eq_ignore_ascii_case("Content-Length")) {
+            write!(&mut out, "{}: {}\r\n", name, value)?;
+        }
+        write!(&mut out, "Content-Length: {}\r\n\r\n", self.body.len())?;
+        out.extend_from_slice(&self.body);
+        writer.write_all(&out)?;
+        writer.flush()
+    }
+}
+
+fn read_line<R: BufRead>(reader: &mut R) -> Result<Option<String>, StatusCode> {
+    let mut line = Vec::new();
+    let bytes_read = reader.by_ref().take(MAX_LINE_LENGTH as u64).read_until(b'\n', &mut line)
+        .map_err(|_| StatusCode::BadCommunicationError)?;
+    if bytes_read == 0 {
+        Ok(None)
+    } else if line.last() != Some(&b'\n') {
+        error!("HTTP line is too long or truncated");
+        Err(StatusCode::BadDecodingError)
+    } else {
+        let line = String::from_utf8(line).map_err(|_| StatusCode::BadDecodingError)?;
+        Ok(Some(line.trim_end_matches(&['\r', '\n'][..]).to_string()))
+    }
+}
+
+/// Returns the position after the end of the line that starts at the position, or `None` if
+/// the buffer does not hold all of it yet
+fn next_line_end(buf: &[u8], pos: usize) -> Result<Option<usize>, StatusCode> {
+    match buf[pos..].iter().position(|b| *b == b'\n') {
+        Some(len) => Ok(Some(pos + len + 1)),
+        None if buf.len() - pos > MAX_LINE_LENGTH => {
+            error!("HTTP line is too long");
+            Err(StatusCode::BadDecodingError)
+        }
+        None => Ok(None),
+    }
+}
+
+/// Parses the size from the line that starts a chunk, ignoring any extensions
+fn parse_chunk_size(line: &str) -> Result<usize, StatusCode> {
+    let size = line.split(';').next().unwrap().trim();
+    usize::from_str_radix(size, 16).map_err(|_| {
+        error!("HTTP chunk size \"{}\" is invalid", line.trim_end());
+        StatusCode::BadDecodingError
+    })
+}
+
+fn read_chunked_body<R: BufRead>(reader: &mut R, max_body_size: usize) -> Result<Vec<u8>, StatusCode> {
+    let mut body = Vec::new();
+    loop {
+        let line = read_line(reader)?.ok_or(StatusCode::BadCommunicationError)?;
+        let size = parse_chunk_size(&line)?;
+        if size == 0 {
+            // Skip any trailers up to the blank line
+            while !read_line(reader)?.ok_or(StatusCode::BadCommunicationError)?.is_empty() {}
+            return Ok(body);
+        }
+        if size > max_body_size.saturating_sub(body.len()) {
+            error!("HTTP body exceeds the limit of {}", max_body_size);
+            return Err(StatusCode::BadRequestTooLarge);
+        }
+        let start = body.len();
+        body.resize(start + size, 0u8);
+        reader.read_exact(&mut body[start..]).map_err(|_| StatusCode::BadCommunicationError)?;
+        // Each chunk is followed by a CRLF
+        let _ = read_line(reader)?;
+    }
+}
+
+/// Implements a tokio codec that reads HTTP messages from a non-blocking stream and writes them
+/// to it. A message that cannot be read is decoded as the status code that says why, e.g.
+/// `BadRequestTooLarge` for a body over the limit, so that the caller can answer it before it
+/// closes the connection.
+pub struct HttpCodec {
+    max_body_size: usize,
+}
+
+impl Decoder for HttpCodec {
+    type Item = Result<HttpMessage, StatusCode>;
+    type Error = io::Error;
+
+    fn decode(&mut self, buf: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        let message = match HttpMessage::message_len(buf, self.max_body_size) {
+            Ok(Some(message_len)) => {
+                let message = buf.split_to(message_len);
+                HttpMessage::read(&mut Cursor::new(&message[..]), self.max_body_size)
+                    .and_then(|message| message.ok_or(StatusCode::BadDecodingError))
+            }
+            Ok(None) => return Ok(None),
+            Err(status_code) => Err(status_code),
+        };
+        if message.is_err() {
+            // Nothing more can be read from the stream once a message is bad
+            buf.clear();
+        }
+        Ok(Some(message))
+    }
+}
+
+impl Encoder for HttpCodec {
+    type Item = HttpMessage;
+    type Error = io::Error;
+
+    fn encode(&mut self, message: Self::Item, buf: &mut BytesMut) -> Result<(), io::Error> {
+        let mut out = Vec::with_capacity(256 + message.body.len());
+        message.write(&mut out)?;
+        buf.extend_from_slice(&out);
+        Ok(())
+    }
+}
+
+impl HttpCodec {
+    /// Constructs a codec that rejects messages whose body is larger than the limit
+    pub fn new(max_body_size: usize) -> HttpCodec {
+        HttpCodec { max_body_size }
+    }
+}
+
+/// Encodes a message as the body of an HTTP request or response
+pub fn encode_message(message: &SupportedMessage) -> Vec<u8> {
+    let node_id = message.node_id();
+    let mut stream = Cursor::new(Vec::with_capacity(node_id.byte_len() + message.byte_len()));
+    let _ = node_id.encode(&mut stream);
+    let _ = message.encode(&mut stream);
+    stream.into_inner()
+}
+
+/// Decodes a message from the body of an HTTP request or response
+pub fn decode_message(body: &[u8], decoding_limits: &DecodingLimits) -> Result<SupportedMessage, StatusCode> {
+    let mut stream = Cursor::new(body);
+    let node_id = NodeId::decode(&mut stream, decoding_limits)?;
+    let object_id = node_id.as_object_id().map_err(|_| {
+        error!("The node id {} of the HTTP body is not a message", node_id);
+        StatusCode::BadDecodingError
+    })?;
+    match SupportedMessage::decode_by_object_id(&mut stream, object_id, decoding_limits)? {
+        SupportedMessage::Invalid(object_id) => {
+            error!("Message {:?} is unsupported", object_id);
+            Err(StatusCode::BadServiceUnsupported)
+        }
+        message => Ok(message)
+    }
+}
@@ -6,6 +6,7 @@
 //! and turning those messages into and out of chunks.
 
 pub mod chunker;
+pub mod https;
 pub mod message_chunk;
 pub mod message_chunk_info;
 pub mod secure_channel;
@@ -14,7 +15,9 @@ pub mod message_writer;
 pub mod tcp_codec;
 pub mod wrapped_tcp_stream;
 pub mod tcp_types;
+pub mod transport_stream;
 pub mod url;
+pub mod websocket;
 
 pub mod prelude {
     pub use super::chunker::*;
@@ -23,6 +26,7 @@ pub mod prelude {
     pub use super::security_header::*;
     pub use super::tcp_codec::*;
     pub use super::tcp_types::*;
+    pub use super::transport_stream::TransportStream;
     pub use super::url::*;
     pub use super::wrapped_tcp_stream::WrappedTcpStream;
 }
@@ -8,6 +8,7 @@
 //! * HEL - Hello message
 //! * ACK - Acknowledge message
 //! * ERR - Error message
+//! * RHE - Reverse Hello message
 //! * MSG - Message chunk
 //! * OPN - Open Secure Channel message
 //! * CLO - Close Secure Channel message
@@ -25,7 +26,7 @@ use opcua_types::{
 use crate::{
     comms::{
         message_chunk::MessageChunk,
-        tcp_types::{AcknowledgeMessage, ErrorMessage, HelloMessage, MESSAGE_HEADER_LEN, MessageHeader, MessageType},
+        tcp_types::{AcknowledgeMessage, ErrorMessage, HelloMessage, MESSAGE_HEADER_LEN, MessageHeader, MessageType, ReverseHelloMessage},
     }
 };
 
@@ -34,6 +35,7 @@ pub enum Message {
     Hello(HelloMessage),
     Acknowledge(AcknowledgeMessage),
     Error(ErrorMessage),
+    ReverseHello(ReverseHelloMessage),
     Chunk(MessageChunk),
 }
 
@@ -96,6 +98,7 @@ impl Encoder for TcpCodec {
             Message::Hello(msg) => self.write(msg, buf),
             Message::Acknowledge(msg) => self.write(msg, buf),
             Message::Error(msg) => self.write(msg, buf),
+            Message::ReverseHello(msg) => self.write(msg, buf),
             Message::Chunk(msg) => self.write(msg, buf),
         }
     }
@@ -140,6 +143,9 @@ impl TcpCodec {
             MessageType::Error => {
                 Ok(Message::Error(ErrorMessage::decode(&mut buf, decoding_limits)?))
             }
+            MessageType::ReverseHello => {
+                Ok(Message::ReverseHello(ReverseHelloMessage::decode(&mut buf, decoding_limits)?))
+            }
             MessageType::Chunk => {
                 Ok(Message::Chunk(MessageChunk::decode(&mut buf, decoding_limits)?))
             }
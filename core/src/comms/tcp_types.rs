@@ -22,6 +22,7 @@ pub const CLOSE_SECURE_CHANNEL_MESSAGE: &[u8] = b"CLO";
 const HELLO_MESSAGE: &[u8] = b"HEL";
 const ACKNOWLEDGE_MESSAGE: &[u8] = b"ACK";
 const ERROR_MESSAGE: &[u8] = b"ERR";
+const REVERSE_HELLO_MESSAGE: &[u8] = b"RHE";
 
 pub const CHUNK_FINAL: u8 = b'F';
 pub const CHUNK_INTERMEDIATE: u8 = b'C';
@@ -43,6 +44,7 @@ pub enum MessageType {
     Acknowledge,
     Chunk,
     Error,
+    ReverseHello,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -62,6 +64,7 @@ impl BinaryEncoder<MessageHeader> for MessageHeader {
             MessageType::Hello => stream.write(HELLO_MESSAGE),
             MessageType::Acknowledge => stream.write(ACKNOWLEDGE_MESSAGE),
             MessageType::Error => stream.write(ERROR_MESSAGE),
+            MessageType::ReverseHello => stream.write(REVERSE_HELLO_MESSAGE),
             MessageType::Chunk => {
                 panic!("Don't write chunks to stream with this call, use Chunk and Chunker");
             }
@@ -138,6 +141,7 @@ impl MessageHeader {
                 HELLO_MESSAGE => MessageType::Hello,
                 ACKNOWLEDGE_MESSAGE => MessageType::Acknowledge,
                 ERROR_MESSAGE => MessageType::Error,
+                REVERSE_HELLO_MESSAGE => MessageType::ReverseHello,
                 CHUNK_MESSAGE | OPEN_SECURE_CHANNEL_MESSAGE | CLOSE_SECURE_CHANNEL_MESSAGE => MessageType::Chunk,
                 _ => {
                     error!("message type doesn't match anything");
@@ -266,6 +270,78 @@ impl HelloMessage {
     }
 }
 
+/// Implementation of the RHE message in OPC UA. A server sends it after it has opened the
+/// connection to a client for Reverse Connect, to say which server it is. The client then continues
+/// with a HEL as it would on a connection that it opened itself.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReverseHelloMessage {
+    pub message_header: MessageHeader,
+    pub server_uri: UAString,
+    pub endpoint_url: UAString,
+}
+
+impl BinaryEncoder<ReverseHelloMessage> for ReverseHelloMessage {
+    fn byte_len(&self) -> usize {
+        self.message_header.byte_len() + self.server_uri.byte_len() + self.endpoint_url.byte_len()
+    }
+
+    fn encode<S: Write>(&self, stream: &mut S) -> EncodingResult<usize> {
+        let mut size: usize = 0;
+        size += self.message_header.encode(stream)?;
+        size += self.server_uri.encode(stream)?;
+        size += self.endpoint_url.encode(stream)?;
+        Ok(size)
+    }
+
+    fn decode<S: Read>(stream: &mut S, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let message_header = MessageHeader::decode(stream, decoding_limits)?;
+        let server_uri = UAString::decode(stream, decoding_limits)?;
+        let endpoint_url = UAString::decode(stream, decoding_limits)?;
+        Ok(ReverseHelloMessage {
+            message_header,
+            server_uri,
+            endpoint_url,
+        })
+    }
+}
+
+impl ReverseHelloMessage {
+    const MAX_URI_LEN: usize = 4096;
+
+    /// Creates a RHE message
+    pub fn new(server_uri: &str, endpoint_url: &str) -> ReverseHelloMessage {
+        let mut msg = ReverseHelloMessage {
+            message_header: MessageHeader::new(MessageType::ReverseHello),
+            server_uri: UAString::from(server_uri),
+            endpoint_url: UAString::from(endpoint_url),
+        };
+        msg.message_header.message_size = msg.byte_len() as u32;
+        msg
+    }
+
+    /// Tests that the server uri and endpoint url are present and no longer than the spec allows
+    pub fn is_valid(&self) -> bool {
+        let valid_length = |v: &UAString| v.value().as_ref().map(|v| !v.is_empty() && v.len() <= ReverseHelloMessage::MAX_URI_LEN).unwrap_or(false);
+        if !valid_length(&self.server_uri) {
+            error!("Reverse hello message has a missing or invalid server uri");
+            false
+        } else if !valid_length(&self.endpoint_url) {
+            error!("Reverse hello message has a missing or invalid endpoint url");
+            false
+        } else {
+            true
+        }
+    }
+
+    /// Tests if the reverse hello came from the server and names the endpoint. The host of
+    /// the endpoint url is ignored since the server may know itself by a different name.
+    pub fn matches(&self, server_uri: &str, endpoint_url: Option<&str>) -> bool {
+        self.server_uri.as_ref() == server_uri && endpoint_url.map(|endpoint_url| {
+            url_matches_except_host(endpoint_url, self.endpoint_url.as_ref())
+        }).unwrap_or(true)
+    }
+}
+
 /// Implementation of the ACK message in OPC UA
 #[derive(Debug, Clone, PartialEq)]
 pub struct AcknowledgeMessage {
@@ -0,0 +1,94 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, SocketAddr};
+
+use futures::{Poll, try_ready};
+use tokio::net::TcpStream;
+use tokio_io::{AsyncRead, AsyncWrite};
+
+use opcua_crypto::TlsStream;
+
+use crate::comms::{websocket::WebSocketStream, wrapped_tcp_stream::WrappedTcpStream};
+
+/// The stream that the UA-TCP codec reads messages from and writes them to. Over opc.tcp it is
+/// the socket, over opc.wss the messages travel in WebSocket frames over TLS. The HTTP codec of
+/// the https transport reads and writes directly over TLS.
+pub enum TransportStream {
+    Tcp(WrappedTcpStream),
+    WebSocket(WebSocketStream<TlsStream<TcpStream>>),
+    Tls(TlsStream<TcpStream>),
+}
+
+impl TransportStream {
+    /// Returns the underlying socket
+    pub fn socket(&self) -> &TcpStream {
+        match self {
+            TransportStream::Tcp(stream) => &stream.0,
+            TransportStream::WebSocket(stream) => stream.get_ref().get_ref(),
+            TransportStream::Tls(stream) => stream.get_ref(),
+        }
+    }
+
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        self.socket().peer_addr()
+    }
+}
+
+impl From<TcpStream> for TransportStream {
+    fn from(socket: TcpStream) -> Self {
+        TransportStream::Tcp(WrappedTcpStream(socket))
+    }
+}
+
+impl Read for TransportStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            TransportStream::Tcp(stream) => stream.read(buf),
+            TransportStream::WebSocket(stream) => stream.read(buf),
+            TransportStream::Tls(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for TransportStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            TransportStream::Tcp(stream) => stream.write(buf),
+            TransportStream::WebSocket(stream) => stream.write(buf),
+            TransportStream::Tls(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            TransportStream::Tcp(stream) => stream.flush(),
+            TransportStream::WebSocket(stream) => stream.flush(),
+            TransportStream::Tls(stream) => stream.flush(),
+        }
+    }
+}
+
+impl AsyncRead for TransportStream {}
+
+impl AsyncWrite for TransportStream {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        match self {
+            TransportStream::Tcp(stream) => stream.shutdown(),
+            TransportStream::WebSocket(stream) => {
+                // Close the WebSocket, then TLS, then the socket as WrappedTcpStream does
+                try_ready!(stream.close());
+                let _ = stream.get_mut().shutdown();
+                TcpStream::shutdown(stream.get_mut().get_mut(), Shutdown::Write)?;
+                Ok(().into())
+            }
+            TransportStream::Tls(stream) => {
+                let _ = stream.shutdown();
+                TcpStream::shutdown(stream.get_mut(), Shutdown::Write)?;
+                Ok(().into())
+            }
+        }
+    }
+}
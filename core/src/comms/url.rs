@@ -9,17 +9,41 @@ use std;
 use ::url::Url;
 
 use opcua_types::{
-    constants::DEFAULT_OPC_UA_SERVER_PORT,
+    constants::{DEFAULT_OPC_UA_HTTPS_PORT, DEFAULT_OPC_UA_SERVER_PORT},
     status_code::StatusCode,
 };
 
 pub const OPC_TCP_SCHEME: &str = "opc.tcp";
+pub const OPC_HTTPS_SCHEME: &str = "opc.https";
+pub const HTTPS_SCHEME: &str = "https";
+pub const OPC_WSS_SCHEME: &str = "opc.wss";
+pub const WSS_SCHEME: &str = "wss";
+pub const OPC_UDP_SCHEME: &str = "opc.udp";
+
+fn is_https_scheme(scheme: &str) -> bool {
+    scheme == HTTPS_SCHEME || scheme == OPC_HTTPS_SCHEME
+}
+
+fn is_wss_scheme(scheme: &str) -> bool {
+    scheme == WSS_SCHEME || scheme == OPC_WSS_SCHEME
+}
+
+/// Tests if the scheme is for a transport over TLS, whose default port is 443
+fn is_tls_scheme(scheme: &str) -> bool {
+    is_https_scheme(scheme) || is_wss_scheme(scheme)
+}
 
 /// Creates a `Url` from the input string, supplying a default port if necessary.
 fn opc_url_from_str(s: &str) -> Result<Url, ()> {
     Url::parse(s)
         .map(|mut url| {
-            if url.port().is_none() {
+            if is_tls_scheme(url.scheme()) {
+                // The url crate omits the port from https and wss urls when it is 443 so it is
+                // only set for opc.https and opc.wss urls
+                if url.port_or_known_default().is_none() {
+                    let _ = url.set_port(Some(DEFAULT_OPC_UA_HTTPS_PORT));
+                }
+            } else if url.port().is_none() {
                 // If no port is supplied, then treat it as the default port 4840
                 let _ = url.set_port(Some(DEFAULT_OPC_UA_SERVER_PORT));
             }
@@ -79,7 +103,7 @@ pub fn server_url_from_endpoint_url(endpoint_url: &str) -> std::result::Result<S
             url.set_query(None);
             if let Some(port) = url.port() {
                 // If the port is the default, strip it so the url string omits it.
-                if port == DEFAULT_OPC_UA_SERVER_PORT {
+                if port == DEFAULT_OPC_UA_SERVER_PORT && url.scheme() == OPC_TCP_SCHEME {
                     let _ = url.set_port(None);
                 }
             }
@@ -88,7 +112,7 @@ pub fn server_url_from_endpoint_url(endpoint_url: &str) -> std::result::Result<S
 }
 
 pub fn is_valid_opc_ua_url(url: &str) -> bool {
-    is_opc_ua_binary_url(url)
+    is_opc_ua_binary_url(url) || is_opc_ua_https_url(url) || is_opc_ua_wss_url(url)
 }
 
 pub fn is_opc_ua_binary_url(url: &str) -> bool {
@@ -99,6 +123,33 @@ pub fn is_opc_ua_binary_url(url: &str) -> bool {
     }
 }
 
+/// Test if the url is for the HTTPS transport, i.e. it has an https:// or opc.https:// scheme
+pub fn is_opc_ua_https_url(url: &str) -> bool {
+    if let Ok(url) = opc_url_from_str(url) {
+        is_https_scheme(url.scheme())
+    } else {
+        false
+    }
+}
+
+/// Test if the url is a PubSub UDP address, i.e. it has an opc.udp:// scheme
+pub fn is_opc_ua_udp_url(url: &str) -> bool {
+    if let Ok(url) = opc_url_from_str(url) {
+        url.scheme() == OPC_UDP_SCHEME
+    } else {
+        false
+    }
+}
+
+/// Test if the url is for the WebSocket transport, i.e. it has an opc.wss:// or wss:// scheme
+pub fn is_opc_ua_wss_url(url: &str) -> bool {
+    if let Ok(url) = opc_url_from_str(url) {
+        is_wss_scheme(url.scheme())
+    } else {
+        false
+    }
+}
+
 pub fn hostname_from_url(url: &str) -> Result<String, ()> {
     // Validate and split out the endpoint we have
     if let Ok(url) = Url::parse(url) {
@@ -112,16 +163,27 @@ pub fn hostname_from_url(url: &str) -> Result<String, ()> {
     }
 }
 
+/// Returns the path of the url, e.g. "/UA/Server" for "opc.https://host:443/UA/Server"
+pub fn path_from_url(url: &str) -> Result<String, StatusCode> {
+    opc_url_from_str(url)
+        .map(|url| url.path().to_string())
+        .map_err(|_| StatusCode::BadTcpEndpointUrlInvalid)
+}
+
 pub fn hostname_port_from_url(url: &str, default_port: u16) -> Result<(String, u16), StatusCode> {
     // Validate and split out the endpoint we have
     let url = Url::parse(url)
         .map_err(|_| StatusCode::BadTcpEndpointUrlInvalid)?;
 
-    if url.scheme() != OPC_TCP_SCHEME || !url.has_host() {
+    if !(url.scheme() == OPC_TCP_SCHEME || url.scheme() == OPC_UDP_SCHEME || is_tls_scheme(url.scheme())) || !url.has_host() {
         Err(StatusCode::BadTcpEndpointUrlInvalid)
     } else {
         let host = url.host_str().unwrap();
-        let port = url.port().unwrap_or(default_port);
+        let port = if is_tls_scheme(url.scheme()) {
+            url.port_or_known_default().unwrap_or(DEFAULT_OPC_UA_HTTPS_PORT)
+        } else {
+            url.port().unwrap_or(default_port)
+        };
         Ok((host.to_string(), port))
     }
 }
@@ -136,6 +198,29 @@ mod tests {
         assert!(is_opc_ua_binary_url("opc.tcp://foo/xyz"));
         assert!(is_opc_ua_binary_url("opc.tcp://[FEDC:BA98:7654:3210:FEDC:BA98:7654:3210]:80/xyz"));
         assert!(!is_opc_ua_binary_url("http://foo/xyz"));
+        assert!(is_opc_ua_https_url("https://foo/xyz"));
+        assert!(is_opc_ua_https_url("opc.https://foo:4843/xyz"));
+        assert!(!is_opc_ua_https_url("opc.tcp://foo/xyz"));
+        assert!(is_valid_opc_ua_url("https://foo/xyz"));
+        assert!(is_opc_ua_wss_url("opc.wss://foo:8443/xyz"));
+        assert!(is_opc_ua_wss_url("wss://foo/xyz"));
+        assert!(!is_opc_ua_wss_url("https://foo/xyz"));
+        assert!(is_valid_opc_ua_url("opc.wss://foo/xyz"));
+        assert!(is_opc_ua_udp_url("opc.udp://239.0.0.1:4840"));
+        assert!(!is_opc_ua_udp_url("opc.tcp://foo/xyz"));
+        assert!(!is_valid_opc_ua_url("opc.udp://239.0.0.1:4840"));
+        assert_eq!(hostname_port_from_url("opc.udp://239.0.0.1", DEFAULT_OPC_UA_SERVER_PORT).unwrap(), ("239.0.0.1".to_string(), 4840));
+    }
+
+    #[test]
+    fn https_url_port() {
+        assert!(url_matches("https://foo/", "https://foo:443/"));
+        assert!(url_matches("opc.https://foo/", "opc.https://foo:443/"));
+        assert_eq!(hostname_port_from_url("https://foo/xyz", DEFAULT_OPC_UA_SERVER_PORT).unwrap(), ("foo".to_string(), 443));
+        assert_eq!(hostname_port_from_url("opc.https://foo:4843/xyz", DEFAULT_OPC_UA_SERVER_PORT).unwrap(), ("foo".to_string(), 4843));
+        assert_eq!(hostname_port_from_url("opc.tcp://foo/xyz", DEFAULT_OPC_UA_SERVER_PORT).unwrap(), ("foo".to_string(), 4840));
+        assert_eq!(hostname_port_from_url("opc.wss://foo/xyz", DEFAULT_OPC_UA_SERVER_PORT).unwrap(), ("foo".to_string(), 443));
+        assert!(url_matches("opc.wss://foo/", "opc.wss://foo:443/"));
     }
 
     #[test]
@@ -0,0 +1,550 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! Provides the WebSocket framing used by the WebSocket secure transport profile
+//! (wss-uasc-uabinary), OPC UA Part 6 7.5. The client opens the connection with an HTTP upgrade
+//! request asking for the `opcua+uacp` subprotocol, after which every UA-TCP message, i.e. the
+//! HEL / ACK / ERR messages and the message chunks, travels in its own binary frame. The secure
+//! channel runs over the frames exactly as it does over a socket. The caller wraps the socket in
+//! TLS before the upgrade.
+
+use std::io::{self, Cursor, Read, Write};
+use std::sync::Arc;
+
+use futures::{Async, Future, Poll, future, try_ready};
+use tokio_io::{AsyncRead, AsyncWrite};
+
+use opcua_crypto::{hash, random, TlsAcceptor, TlsConnector, TlsHandshake, TlsStream};
+use opcua_types::status_code::StatusCode;
+
+use crate::comms::{https::HttpMessage, secure_channel::Role};
+
+/// The subprotocol that carries UA-TCP messages in binary frames
+pub const SUBPROTOCOL_UACP: &str = "opcua+uacp";
+
+/// Appended to the client's key to make the accept key, RFC 6455 1.3
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+/// The largest upgrade request or response header that will be read
+const MAX_UPGRADE_HEADER_SIZE: usize = 16384;
+/// Size of the UA-TCP message header, the last 4 bytes of which hold the size of the message
+const MESSAGE_HEADER_SIZE: usize = 8;
+/// Status code sent in a close frame, normal closure
+const CLOSE_NORMAL: u16 = 1000;
+
+const FIN_BIT: u8 = 0x80;
+const MASK_BIT: u8 = 0x80;
+const OPCODE_CONTINUATION: u8 = 0x0;
+const OPCODE_TEXT: u8 = 0x1;
+const OPCODE_BINARY: u8 = 0x2;
+const OPCODE_CLOSE: u8 = 0x8;
+const OPCODE_PING: u8 = 0x9;
+const OPCODE_PONG: u8 = 0xa;
+
+fn invalid_data(message: String) -> io::Error {
+    error!("{}", message);
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Turns a non-blocking result into a poll
+fn poll_io<T>(result: io::Result<T>) -> Poll<T, io::Error> {
+    match result {
+        Ok(value) => Ok(Async::Ready(value)),
+        Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => Ok(Async::NotReady),
+        Err(err) => Err(err),
+    }
+}
+
+/// Returns the value of the `Sec-WebSocket-Accept` header that answers the client's
+/// `Sec-WebSocket-Key`
+pub fn accept_key(key: &str) -> String {
+    base64::encode(hash::sha1(format!("{}{}", key, WEBSOCKET_GUID).as_bytes()))
+}
+
+/// A WebSocket frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    /// Set on the last frame of a message
+    pub fin: bool,
+    pub opcode: u8,
+    /// Set if the payload was masked, as frames sent by clients must be
+    pub masked: bool,
+    /// The unmasked payload
+    pub payload: Vec<u8>,
+}
+
+impl Frame {
+    pub fn binary(payload: Vec<u8>) -> Frame {
+        Frame { fin: true, opcode: OPCODE_BINARY, masked: false, payload }
+    }
+
+    pub fn close(status: u16) -> Frame {
+        Frame { fin: true, opcode: OPCODE_CLOSE, masked: false, payload: status.to_be_bytes().to_vec() }
+    }
+
+    pub fn is_binary(&self) -> bool {
+        self.opcode == OPCODE_BINARY
+    }
+
+    /// Encodes the frame, masking the payload with the key if one is supplied
+    pub fn encode(&self, mask: Option<[u8; 4]>) -> Vec<u8> {
+        let len = self.payload.len();
+        let mut out = Vec::with_capacity(len + 14);
+        out.push(if self.fin { FIN_BIT } else { 0 } | self.opcode);
+        let mask_bit = if mask.is_some() { MASK_BIT } else { 0 };
+        if len < 126 {
+            out.push(mask_bit | len as u8);
+        } else if len <= 0xffff {
+            out.push(mask_bit | 126);
+            out.extend_from_slice(&(len as u16).to_be_bytes());
+        } else {
+            out.push(mask_bit | 127);
+            out.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+        if let Some(mask) = mask {
+            out.extend_from_slice(&mask);
+            out.extend(self.payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]));
+        } else {
+            out.extend_from_slice(&self.payload);
+        }
+        out
+    }
+
+    /// Decodes the frame at the start of the buffer, returning it and the number of bytes it
+    /// occupies. Returns `None` if the buffer does not yet hold the whole frame.
+    pub fn decode(buf: &[u8], max_payload_size: usize) -> io::Result<Option<(Frame, usize)>> {
+        if buf.len() < 2 {
+            return Ok(None);
+        }
+        let fin = buf[0] & FIN_BIT != 0;
+        if buf[0] & 0x70 != 0 {
+            return Err(invalid_data("WebSocket frame has reserved bits set".to_string()));
+        }
+        let opcode = buf[0] & 0x0f;
+        let masked = buf[1] & MASK_BIT != 0;
+        let (len, mut pos) = match buf[1] & 0x7f {
+            126 => {
+                if buf.len() < 4 {
+                    return Ok(None);
+                }
+                (u16::from_be_bytes([buf[2], buf[3]]) as u64, 4)
+            }
+            127 => {
+                if buf.len() < 10 {
+                    return Ok(None);
+                }
+                let mut len = [0u8; 8];
+                len.copy_from_slice(&buf[2..10]);
+                (u64::from_be_bytes(len), 10)
+            }
+            len => (len as u64, 2)
+        };
+        if len > max_payload_size as u64 {
+            return Err(invalid_data(format!("WebSocket frame payload of {} bytes exceeds the limit of {}", len, max_payload_size)));
+        }
+        let len = len as usize;
+        let mask = if masked {
+            if buf.len() < pos + 4 {
+                return Ok(None);
+            }
+            let mut mask = [0u8; 4];
+            mask.copy_from_slice(&buf[pos..pos + 4]);
+            pos += 4;
+            Some(mask)
+        } else {
+            None
+        };
+        if buf.len() < pos + len {
+            return Ok(None);
+        }
+        let payload = &buf[pos..pos + len];
+        let payload = if let Some(mask) = mask {
+            payload.iter().enumerate().map(|(i, b)| b ^ mask[i % 4]).collect()
+        } else {
+            payload.to_vec()
+        };
+        Ok(Some((Frame { fin, opcode, masked, payload }, pos + len)))
+    }
+}
+
+/// A WebSocket connection carrying the `opcua+uacp` subprotocol over a stream that has completed
+/// the upgrade handshake.
+///
+/// Reading returns the payload of binary frames as a byte stream, so the UA-TCP codec reads it as
+/// it would a socket. Writing accepts UA-TCP messages and sends each one in its own binary frame.
+/// Data is buffered so the stream may be non-blocking. Writes should be flushed so the
+/// buffered frames are sent.
+pub struct WebSocketStream<S> {
+    stream: S,
+    role: Role,
+    max_payload_size: usize,
+    /// Bytes read from the stream that do not yet make a whole frame
+    in_buf: Vec<u8>,
+    /// Payload of binary frames that has not been read yet
+    payload: Vec<u8>,
+    /// Bytes written that do not yet make a whole UA-TCP message
+    pending: Vec<u8>,
+    /// Encoded frames that have not been written to the stream yet
+    out_buf: Vec<u8>,
+    close_received: bool,
+    close_sent: bool,
+}
+
+impl<S> WebSocketStream<S> where S: Read + Write {
+    /// Creates the stream. Any bytes that were read beyond the end of the upgrade handshake are
+    /// supplied as the start of the first frame.
+    pub fn new(stream: S, role: Role, max_payload_size: usize, initial_bytes: Vec<u8>) -> WebSocketStream<S> {
+        WebSocketStream {
+            stream,
+            role,
+            max_payload_size,
+            in_buf: initial_bytes,
+            payload: Vec::new(),
+            pending: Vec::new(),
+            out_buf: Vec::new(),
+            close_received: false,
+            close_sent: false,
+        }
+    }
+
+    pub fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.stream
+    }
+
+    /// Sends a close frame, if one has not been sent already, and waits for the buffered frames
+    /// to be written
+    pub fn close(&mut self) -> Poll<(), io::Error> {
+        if !self.close_sent {
+            self.queue_frame(Frame::close(CLOSE_NORMAL));
+            self.close_sent = true;
+        }
+        poll_io(self.drain())
+    }
+
+    fn queue_frame(&mut self, frame: Frame) {
+        // Clients mask every frame they send, servers none
+        let mask = if self.role == Role::Client {
+            let mut mask = [0u8; 4];
+            random::bytes(&mut mask);
+            Some(mask)
+        } else {
+            None
+        };
+        self.out_buf.extend(frame.encode(mask));
+    }
+
+    /// Writes buffered frames to the stream
+    fn drain(&mut self) -> io::Result<()> {
+        while !self.out_buf.is_empty() {
+            let written = self.stream.write(&self.out_buf)?;
+            if written == 0 {
+                return Err(io::ErrorKind::WriteZero.into());
+            }
+            self.out_buf.drain(..written);
+        }
+        Ok(())
+    }
+
+    /// Frames every whole UA-TCP message that has been written
+    fn frame_pending(&mut self) -> io::Result<()> {
+        while self.pending.len() >= MESSAGE_HEADER_SIZE {
+            let message_size = u32::from_le_bytes([self.pending[4], self.pending[5], self.pending[6], self.pending[7]]) as usize;
+            if message_size < MESSAGE_HEADER_SIZE {
+                return Err(invalid_data(format!("UA-TCP message size {} is invalid", message_size)));
+            }
+            if self.pending.len() < message_size {
+                break;
+            }
+            let message: Vec<u8> = self.pending.drain(..message_size).collect();
+            self.queue_frame(Frame::binary(message));
+        }
+        Ok(())
+    }
+
+    fn process_frame(&mut self, frame: Frame) -> io::Result<()> {
+        // Frames from a client must be masked, frames from a server must not be
+        if frame.masked != (self.role == Role::Server) {
+            return Err(invalid_data(format!("WebSocket frame masking is invalid for a {:?} to receive", self.role)));
+        }
+        match frame.opcode {
+            OPCODE_BINARY | OPCODE_CONTINUATION => {
+                self.payload.extend(frame.payload);
+            }
+            OPCODE_CLOSE => {
+                debug!("WebSocket close frame received");
+                self.close_received = true;
+                if !self.close_sent {
+                    self.queue_frame(Frame::close(CLOSE_NORMAL));
+                    self.close_sent = true;
+                }
+            }
+            OPCODE_PING => {
+                // The pong is sent along with the next write
+                self.queue_frame(Frame { fin: true, opcode: OPCODE_PONG, masked: false, payload: frame.payload });
+            }
+            OPCODE_PONG => {}
+            OPCODE_TEXT => {
+                return Err(invalid_data("WebSocket text frames are not used by the opcua+uacp subprotocol".to_string()));
+            }
+            opcode => {
+                return Err(invalid_data(format!("WebSocket frame opcode {} is invalid", opcode)));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<S> Read for WebSocketStream<S> where S: Read + Write {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if !self.payload.is_empty() {
+                let len = usize::min(buf.len(), self.payload.len());
+                buf[..len].copy_from_slice(&self.payload[..len]);
+                self.payload.drain(..len);
+                return Ok(len);
+            }
+            if self.close_received {
+                return Ok(0);
+            }
+            if let Some((frame, frame_len)) = Frame::decode(&self.in_buf, self.max_payload_size)? {
+                self.in_buf.drain(..frame_len);
+                self.process_frame(frame)?;
+                continue;
+            }
+            let mut chunk = [0u8; 8192];
+            let bytes_read = self.stream.read(&mut chunk)?;
+            if bytes_read == 0 {
+                return Ok(0);
+            }
+            self.in_buf.extend_from_slice(&chunk[..bytes_read]);
+        }
+    }
+}
+
+impl<S> Write for WebSocketStream<S> where S: Read + Write {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // Frames from earlier writes must be sent before more are accepted
+        self.drain()?;
+        self.pending.extend_from_slice(buf);
+        self.frame_pending()?;
+        match self.drain() {
+            Err(ref err) if err.kind() == io::ErrorKind::WouldBlock => {}
+            result => result?
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.drain()?;
+        self.stream.flush()
+    }
+}
+
+impl<S> AsyncRead for WebSocketStream<S> where S: Read + Write {}
+
+impl<S> AsyncWrite for WebSocketStream<S> where S: Read + Write {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        self.close()
+    }
+}
+
+/// Returns the upgrade request a client sends to open a connection to the path
+pub fn upgrade_request(host: &str, path: &str, key: &str) -> Vec<u8> {
+    let path = if path.is_empty() { "/" } else { path };
+    format!("GET {} HTTP/1.1\r\nHost: {}\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Key: {}\r\nSec-WebSocket-Version: 13\r\nSec-WebSocket-Protocol: {}\r\n\r\n",
+            path, host, key, SUBPROTOCOL_UACP).into_bytes()
+}
+
+fn has_token(value: Option<&str>, token: &str) -> bool {
+    matches!(value, Some(value) if value.split(',').any(|v| v.trim().eq_ignore_ascii_case(token)))
+}
+
+/// Reads an HTTP message header, ending in a blank line, that has been received whole
+fn parse_header(header: &[u8]) -> Result<HttpMessage, StatusCode> {
+    match HttpMessage::read(&mut Cursor::new(header), 0) {
+        Ok(Some(message)) => Ok(message),
+        _ => {
+            error!("WebSocket upgrade header cannot be parsed");
+            Err(StatusCode::BadDecodingError)
+        }
+    }
+}
+
+/// Validates the upgrade request header a server receives. Returns the response which accepts
+/// the upgrade, or else the response which rejects it.
+pub fn upgrade_response(request_header: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+    let reject = |reason: &str| {
+        error!("WebSocket upgrade request is rejected, {}", reason);
+        b"HTTP/1.1 400 Bad Request\r\nSec-WebSocket-Version: 13\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_vec()
+    };
+    let request = parse_header(request_header).map_err(|_| reject("the request is invalid"))?;
+    if request.method() != Some("GET") {
+        return Err(reject("the method is not GET"));
+    }
+    if !has_token(request.header("Upgrade"), "websocket") || !has_token(request.header("Connection"), "upgrade") {
+        return Err(reject("it is not a websocket upgrade"));
+    }
+    if request.header("Sec-WebSocket-Version") != Some("13") {
+        return Err(reject("the version is not 13"));
+    }
+    if !has_token(request.header("Sec-WebSocket-Protocol"), SUBPROTOCOL_UACP) {
+        return Err(reject("the client does not offer the opcua+uacp subprotocol"));
+    }
+    let key = request.header("Sec-WebSocket-Key").ok_or_else(|| reject("there is no key"))?;
+    Ok(format!("HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\nSec-WebSocket-Protocol: {}\r\n\r\n",
+               accept_key(key), SUBPROTOCOL_UACP).into_bytes())
+}
+
+/// Validates the response a client receives to its upgrade request
+pub fn validate_upgrade_response(response_header: &[u8], key: &str) -> Result<(), StatusCode> {
+    let response = parse_header(response_header)?;
+    if response.status() != Some(101) {
+        error!("Server did not accept the WebSocket upgrade, \"{}\"", response.start_line);
+        Err(StatusCode::BadConnectionRejected)
+    } else if response.header("Sec-WebSocket-Accept") != Some(accept_key(key).as_str()) {
+        error!("Server's WebSocket accept key is invalid");
+        Err(StatusCode::BadCommunicationError)
+    } else if !matches!(response.header("Sec-WebSocket-Protocol"), Some(protocol) if protocol.eq_ignore_ascii_case(SUBPROTOCOL_UACP)) {
+        error!("Server did not select the opcua+uacp subprotocol");
+        Err(StatusCode::BadCommunicationError)
+    } else {
+        Ok(())
+    }
+}
+
+/// Writes a message to a non-blocking stream and then, if asked, reads the header of the
+/// message that answers it. Resolves to the stream, the header and any bytes read beyond it.
+struct HttpExchange<S> {
+    stream: Option<S>,
+    out_buf: Vec<u8>,
+    read_header: bool,
+    in_buf: Vec<u8>,
+}
+
+impl<S> HttpExchange<S> where S: Read + Write {
+    fn new(stream: S, out_buf: Vec<u8>, read_header: bool) -> HttpExchange<S> {
+        HttpExchange { stream: Some(stream), out_buf, read_header, in_buf: Vec::new() }
+    }
+}
+
+/// Returns the length of the header, up to and including the blank line that ends it
+fn header_len(buf: &[u8]) -> Option<usize> {
+    buf.windows(4).position(|w| w == b"\r\n\r\n").map(|pos| pos + 4)
+}
+
+impl<S> Future for HttpExchange<S> where S: Read + Write {
+    type Item = (S, Vec<u8>, Vec<u8>);
+    type Error = io::Error;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let header_end = {
+            let stream = self.stream.as_mut().expect("poll called after the exchange completed");
+            while !self.out_buf.is_empty() {
+                let written = try_ready!(poll_io(stream.write(&self.out_buf)));
+                if written == 0 {
+                    return Err(io::ErrorKind::WriteZero.into());
+                }
+                self.out_buf.drain(..written);
+            }
+            try_ready!(poll_io(stream.flush()));
+            if self.read_header {
+                loop {
+                    if let Some(header_end) = header_len(&self.in_buf) {
+                        break header_end;
+                    }
+                    if self.in_buf.len() > MAX_UPGRADE_HEADER_SIZE {
+                        return Err(invalid_data("WebSocket upgrade header is too long".to_string()));
+                    }
+                    let mut chunk = [0u8; 1024];
+                    let bytes_read = try_ready!(poll_io(stream.read(&mut chunk)));
+                    if bytes_read == 0 {
+                        return Err(io::ErrorKind::UnexpectedEof.into());
+                    }
+                    self.in_buf.extend_from_slice(&chunk[..bytes_read]);
+                }
+            } else {
+                0
+            }
+        };
+        let remaining = self.in_buf.split_off(header_end);
+        let header = std::mem::replace(&mut self.in_buf, remaining);
+        Ok(Async::Ready((self.stream.take().unwrap(), header, std::mem::take(&mut self.in_buf))))
+    }
+}
+
+/// Performs the server side of the upgrade handshake
+pub fn accept<S>(stream: S, max_payload_size: usize) -> impl Future<Item=WebSocketStream<S>, Error=StatusCode> where S: Read + Write {
+    HttpExchange::new(stream, Vec::new(), true).map_err(|err| {
+        error!("Cannot read WebSocket upgrade request, {}", err);
+        StatusCode::BadCommunicationError
+    }).and_then(move |(stream, header, initial_bytes)| {
+        let (response, accepted) = match upgrade_response(&header) {
+            Ok(response) => (response, true),
+            Err(response) => (response, false),
+        };
+        HttpExchange::new(stream, response, false).map_err(|err| {
+            error!("Cannot write WebSocket upgrade response, {}", err);
+            StatusCode::BadCommunicationError
+        }).and_then(move |(stream, _, _)| {
+            if accepted {
+                Ok(WebSocketStream::new(stream, Role::Server, max_payload_size, initial_bytes))
+            } else {
+                Err(StatusCode::BadConnectionRejected)
+            }
+        })
+    })
+}
+
+/// Performs the client side of the upgrade handshake, requesting the path from the host
+pub fn connect<S>(stream: S, host: &str, path: &str, max_payload_size: usize) -> impl Future<Item=WebSocketStream<S>, Error=StatusCode> where S: Read + Write {
+    let mut key = [0u8; 16];
+    random::bytes(&mut key);
+    let key = base64::encode(key);
+    HttpExchange::new(stream, upgrade_request(host, path, &key), true).map_err(|err| {
+        error!("WebSocket upgrade request failed, {}", err);
+        StatusCode::BadCommunicationError
+    }).and_then(move |(stream, header, initial_bytes)| {
+        validate_upgrade_response(&header, &key)?;
+        Ok(WebSocketStream::new(stream, Role::Client, max_payload_size, initial_bytes))
+    })
+}
+
+/// Completes a TLS handshake on a non-blocking stream
+struct TlsHandshakeFuture<S>(Option<TlsHandshake<S>>);
+
+impl<S> Future for TlsHandshakeFuture<S> where S: Read + Write + std::fmt::Debug {
+    type Item = TlsStream<S>;
+    type Error = StatusCode;
+
+    fn poll(&mut self) -> Poll<Self::Item, Self::Error> {
+        let handshake = match self.0.take().expect("poll called after the handshake completed") {
+            TlsHandshake::Pending(stream) => TlsHandshake::continue_handshake(stream)?,
+            handshake => handshake,
+        };
+        match handshake {
+            TlsHandshake::Complete(stream) => Ok(Async::Ready(stream)),
+            handshake => {
+                self.0 = Some(handshake);
+                Ok(Async::NotReady)
+            }
+        }
+    }
+}
+
+/// Performs the server side of the TLS handshake on a non-blocking stream
+pub fn tls_accept<S>(acceptor: Arc<TlsAcceptor>, stream: S) -> impl Future<Item=TlsStream<S>, Error=StatusCode> where S: Read + Write + std::fmt::Debug {
+    // The handshake starts when the future is first polled so the stream's readiness is
+    // registered with the task
+    future::lazy(move || acceptor.start_accept(stream))
+        .and_then(|handshake| TlsHandshakeFuture(Some(handshake)))
+}
+
+/// Performs the client side of the TLS handshake on a non-blocking stream
+pub fn tls_connect<S>(connector: TlsConnector, hostname: String, stream: S) -> impl Future<Item=TlsStream<S>, Error=StatusCode> where S: Read + Write + std::fmt::Debug {
+    future::lazy(move || connector.start_connect(&hostname, stream))
+        .and_then(|handshake| TlsHandshakeFuture(Some(handshake)))
+}
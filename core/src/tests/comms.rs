@@ -53,6 +53,40 @@ pub fn acknowledge() {
     assert_eq!(ack.max_chunk_count, 65535);
 }
 
+#[test]
+pub fn reverse_hello() {
+    let data = vec![
+        0x52, 0x48, 0x45, 0x46, 0x2f, 0x00, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00, 0x75, 0x72, 0x6e,
+        0x3a, 0x73, 0x65, 0x72, 0x76, 0x65, 0x72, 0x15, 0x00, 0x00, 0x00, 0x6f, 0x70, 0x63, 0x2e,
+        0x74, 0x63, 0x70, 0x3a, 0x2f, 0x2f, 0x70, 0x6c, 0x61, 0x6e, 0x74, 0x3a, 0x34, 0x38, 0x34,
+        0x30, 0x2f];
+
+    let mut stream = Cursor::new(data.clone());
+    let decoding_limits = DecodingLimits::default();
+    let rhe = ReverseHelloMessage::decode(&mut stream, &decoding_limits).unwrap();
+    assert_eq!(rhe.message_header.message_type, MessageType::ReverseHello);
+    assert_eq!(rhe.message_header.message_size, 47);
+    assert_eq!(rhe.server_uri, UAString::from("urn:server"));
+    assert_eq!(rhe.endpoint_url, UAString::from("opc.tcp://plant:4840/"));
+    assert!(rhe.is_valid());
+
+    // Encoding produces the same bytes
+    let rhe = ReverseHelloMessage::new("urn:server", "opc.tcp://plant:4840/");
+    let mut stream = Cursor::new(Vec::new());
+    rhe.encode(&mut stream).unwrap();
+    assert_eq!(stream.into_inner(), data);
+
+    // The host of the endpoint is not compared
+    assert!(rhe.matches("urn:server", None));
+    assert!(rhe.matches("urn:server", Some("opc.tcp://10.0.0.1:4840/")));
+    assert!(!rhe.matches("urn:server", Some("opc.tcp://plant:4841/")));
+    assert!(!rhe.matches("urn:other", None));
+
+    // Server uri and endpoint url are required
+    assert!(!ReverseHelloMessage::new("", "opc.tcp://plant:4840/").is_valid());
+    assert!(!ReverseHelloMessage::new("urn:server", "").is_valid());
+}
+
 #[test]
 pub fn secure_channel_nonce() {
     let mut sc = SecureChannel::new_no_certificate_store();
@@ -67,3 +101,205 @@ pub fn secure_channel_nonce() {
     // Nonce which is 32 bytes long is good
     assert!(sc.set_remote_nonce_from_byte_string(&ByteString::from(b"01234567890123456789012345678901")).is_ok());
 }
+
+#[test]
+pub fn https_request() {
+    use opcua_types::status_code::StatusCode;
+
+    use crate::{comms::https::*, supported_message::SupportedMessage};
+
+    let request: SupportedMessage = super::make_sample_message();
+    let body = encode_message(&request);
+    let post = HttpMessage::post("localhost:4843", "/ua", body, SecurityPolicy::None.to_uri());
+
+    let mut bytes = Vec::new();
+    post.write(&mut bytes).unwrap();
+    let text = String::from_utf8_lossy(&bytes);
+    assert!(text.starts_with("POST /ua HTTP/1.1\r\nHost: localhost:4843\r\n"));
+
+    let read = HttpMessage::read(&mut Cursor::new(bytes), 65536).unwrap().unwrap();
+    assert_eq!(read.method(), Some("POST"));
+    assert_eq!(read.path(), Some("/ua"));
+    assert_eq!(read.header("opcua-securitypolicy"), Some(SecurityPolicy::None.to_uri()));
+    assert!(read.keep_alive());
+    let decoded = decode_message(&read.body, &DecodingLimits::default()).unwrap();
+    assert_eq!(format!("{:?}", decoded), format!("{:?}", request));
+
+    // A body larger than the limit is rejected
+    let mut bytes = Vec::new();
+    post.write(&mut bytes).unwrap();
+    assert_eq!(HttpMessage::read(&mut Cursor::new(bytes), 10).unwrap_err(), StatusCode::BadRequestTooLarge);
+
+    // A closed stream has no message
+    assert!(HttpMessage::read(&mut Cursor::new(Vec::new()), 65536).unwrap().is_none());
+}
+
+#[test]
+pub fn https_chunked_response() {
+    use opcua_types::status_code::StatusCode;
+
+    use crate::comms::https::*;
+
+    let bytes = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\nConnection: close\r\n\r\n3\r\nabc\r\n2;x=y\r\nde\r\n0\r\n\r\n".to_vec();
+    let response = HttpMessage::read(&mut Cursor::new(bytes), 65536).unwrap().unwrap();
+    assert_eq!(response.status(), Some(200));
+    assert_eq!(response.method(), None);
+    assert_eq!(response.body, b"abcde".to_vec());
+    assert!(!response.keep_alive());
+
+    // Chunks beyond the limit are rejected, including a size that would overflow the total
+    let bytes = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc\r\nffffffffffffffff\r\n".to_vec();
+    assert_eq!(HttpMessage::read(&mut Cursor::new(bytes), 65536).unwrap_err(), StatusCode::BadRequestTooLarge);
+    let bytes = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nabc\r\n3\r\ndef\r\n0\r\n\r\n".to_vec();
+    assert_eq!(HttpMessage::read(&mut Cursor::new(bytes), 5).unwrap_err(), StatusCode::BadRequestTooLarge);
+}
+
+#[test]
+pub fn https_codec() {
+    use bytes::BytesMut;
+    use tokio_io::codec::{Decoder, Encoder};
+
+    use opcua_types::status_code::StatusCode;
+
+    use crate::comms::https::*;
+
+    let mut codec = HttpCodec::new(65536);
+    let mut bytes = BytesMut::new();
+    codec.encode(HttpMessage::response(200, "OK", b"abc".to_vec()), &mut bytes).unwrap();
+    let message = bytes.to_vec();
+
+    // A message is only decoded once it has arrived whole, and what follows it is kept
+    let mut buf = BytesMut::new();
+    for b in &message[..message.len() - 1] {
+        buf.extend_from_slice(&[*b]);
+        assert!(codec.decode(&mut buf).unwrap().is_none());
+    }
+    buf.extend_from_slice(&message[message.len() - 1..]);
+    buf.extend_from_slice(b"POST / HTTP/1.1\r\n");
+    let response = codec.decode(&mut buf).unwrap().unwrap().unwrap();
+    assert_eq!(response.status(), Some(200));
+    assert_eq!(response.body, b"abc".to_vec());
+    assert_eq!(&buf[..], b"POST / HTTP/1.1\r\n");
+
+    // Chunked bodies are read to the blank line after the last chunk
+    let chunked = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n3\r\na\nc\r\n0\r\nX-Trailer: 1\r\n\r\n";
+    let mut buf = BytesMut::new();
+    for b in &chunked[..chunked.len() - 1] {
+        buf.extend_from_slice(&[*b]);
+        assert!(codec.decode(&mut buf).unwrap().is_none());
+    }
+    buf.extend_from_slice(&chunked[chunked.len() - 1..]);
+    let response = codec.decode(&mut buf).unwrap().unwrap().unwrap();
+    assert_eq!(response.body, b"a\nc".to_vec());
+    assert!(buf.is_empty());
+
+    // A body over the limit is rejected as soon as its length is known
+    let mut codec = HttpCodec::new(2);
+    let mut buf = BytesMut::from(&b"POST / HTTP/1.1\r\nContent-Length: 3\r\n\r\n"[..]);
+    assert_eq!(codec.decode(&mut buf).unwrap().unwrap().unwrap_err(), StatusCode::BadRequestTooLarge);
+    let mut buf = BytesMut::from(&b"POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n3\r\n"[..]);
+    assert_eq!(codec.decode(&mut buf).unwrap().unwrap().unwrap_err(), StatusCode::BadRequestTooLarge);
+}
+
+#[test]
+pub fn websocket_accept_key() {
+    use crate::comms::websocket::*;
+
+    // The example from RFC 6455 1.3
+    assert_eq!(accept_key("dGhlIHNhbXBsZSBub25jZQ=="), "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+
+    let request = upgrade_request("localhost:443", "/", "dGhlIHNhbXBsZSBub25jZQ==");
+    let response = upgrade_response(&request).unwrap();
+    assert!(validate_upgrade_response(&response, "dGhlIHNhbXBsZSBub25jZQ==").is_ok());
+    assert!(validate_upgrade_response(&response, "AAAAAAAAAAAAAAAAAAAAAA==").is_err());
+
+    // The subprotocol is required
+    let request = String::from_utf8(request).unwrap().replace("opcua+uacp", "mqtt");
+    let response = upgrade_response(request.as_bytes()).unwrap_err();
+    assert!(response.starts_with(b"HTTP/1.1 400"));
+}
+
+#[test]
+pub fn websocket_frame() {
+    use crate::comms::websocket::*;
+
+    for len in &[0usize, 125, 126, 65535, 65536] {
+        let frame = Frame::binary(vec![0xa5; *len]);
+        let bytes = frame.encode(None);
+        // A partial frame is not decoded
+        assert!(Frame::decode(&bytes[..bytes.len() - 1], 1 << 20).unwrap().is_none());
+        let (decoded, used) = Frame::decode(&bytes, 1 << 20).unwrap().unwrap();
+        assert_eq!(used, bytes.len());
+        assert_eq!(decoded, frame);
+
+        // Masked frames decode to the same payload
+        let bytes = frame.encode(Some([1, 2, 3, 4]));
+        let (decoded, _) = Frame::decode(&bytes, 1 << 20).unwrap().unwrap();
+        assert!(decoded.masked);
+        assert_eq!(decoded.payload, frame.payload);
+    }
+
+    // Payloads over the limit are an error
+    let bytes = Frame::binary(vec![0; 100]).encode(None);
+    assert!(Frame::decode(&bytes, 99).is_err());
+}
+
+/// An in-memory stream for the WebSocket to read from and write to
+struct MemoryStream {
+    input: Cursor<Vec<u8>>,
+    output: Vec<u8>,
+}
+
+impl Read for MemoryStream {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.input.read(buf)
+    }
+}
+
+impl Write for MemoryStream {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.output.write(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+pub fn websocket_stream() {
+    use crate::comms::websocket::*;
+
+    // The client's hello arrives in two masked frames, then a ping
+    let hello = hello_data();
+    let mut input = Frame::binary(hello[..10].to_vec()).encode(Some([9, 8, 7, 6]));
+    let mut continuation = Frame::binary(hello[10..].to_vec());
+    continuation.opcode = 0;
+    input.extend(continuation.encode(Some([1, 2, 3, 4])));
+    input.extend(Frame { fin: true, opcode: 0x9, masked: false, payload: b"ping".to_vec() }.encode(Some([0, 0, 0, 1])));
+    let stream = MemoryStream { input: Cursor::new(input), output: Vec::new() };
+    let mut stream = WebSocketStream::new(stream, Role::Server, 65536, Vec::new());
+
+    let mut read = Vec::new();
+    stream.read_to_end(&mut read).unwrap();
+    assert_eq!(read, hello);
+
+    // The ack is written in pieces but sent as one unmasked frame, after the pong
+    let ack = ack_data();
+    stream.write_all(&ack[..5]).unwrap();
+    stream.write_all(&ack[5..]).unwrap();
+    stream.flush().unwrap();
+    let output = stream.get_ref().output.clone();
+    let (pong, used) = Frame::decode(&output, 65536).unwrap().unwrap();
+    assert_eq!(pong.opcode, 0xa);
+    assert_eq!(pong.payload, b"ping".to_vec());
+    let (frame, _) = Frame::decode(&output[used..], 65536).unwrap().unwrap();
+    assert!(frame.is_binary() && !frame.masked);
+    assert_eq!(frame.payload, ack);
+
+    // Unmasked frames from a client are an error
+    let input = Frame::binary(hello.clone()).encode(None);
+    let stream = MemoryStream { input: Cursor::new(input), output: Vec::new() };
+    let mut stream = WebSocketStream::new(stream, Role::Server, 65536, Vec::new());
+    assert!(stream.read_to_end(&mut Vec::new()).is_err());
+}
@@ -110,6 +110,28 @@ impl AesKey {
     }
 }
 
+/// Encrypts or decrypts data with AES in counter mode, as used by the PubSub security policies.
+/// The length of the key chooses AES-128 or AES-256 and the counter block is the initial 16 byte
+/// counter. Counter mode is symmetric so the same call encrypts and decrypts.
+pub fn aes_ctr(key: &[u8], counter_block: &[u8], src: &[u8]) -> Result<Vec<u8>, StatusCode> {
+    let cipher = match key.len() {
+        16 => Cipher::aes_128_ctr(),
+        32 => Cipher::aes_256_ctr(),
+        len => {
+            error!("AES-CTR key length {} is not supported", len);
+            return Err(StatusCode::BadSecurityChecksFailed);
+        }
+    };
+    if counter_block.len() != 16 {
+        error!("AES-CTR counter block is not 16 bytes, len = {}", counter_block.len());
+        return Err(StatusCode::BadSecurityChecksFailed);
+    }
+    openssl::symm::encrypt(cipher, key, Some(counter_block), src).map_err(|e| {
+        error!("AES-CTR error {:?}", e);
+        StatusCode::BadSecurityChecksFailed
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use std::thread;
@@ -125,4 +147,18 @@ mod tests {
         });
         let _ = child.join();
     }
+
+    #[test]
+    fn test_aes_ctr() {
+        // NIST SP 800-38A F.5.1 CTR-AES128.Encrypt, first block
+        let key = [0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c];
+        let counter = [0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, 0xf9, 0xfa, 0xfb, 0xfc, 0xfd, 0xfe, 0xff];
+        let plaintext = [0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17, 0x2a];
+        let ciphertext = aes_ctr(&key, &counter, &plaintext).unwrap();
+        assert_eq!(ciphertext, vec![0x87, 0x4d, 0x61, 0x91, 0xb6, 0x20, 0xe3, 0x26, 0x1b, 0xef, 0x68, 0x64, 0x99, 0x0d, 0xb6, 0xce]);
+        assert_eq!(aes_ctr(&key, &counter, &ciphertext).unwrap(), plaintext.to_vec());
+        // Messages need not be a whole number of blocks
+        assert_eq!(aes_ctr(&key, &counter, &plaintext[..5]).unwrap(), ciphertext[..5].to_vec());
+        assert!(aes_ctr(&key[..15], &counter, &plaintext).is_err());
+    }
 }
\ No newline at end of file
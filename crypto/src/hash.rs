@@ -6,7 +6,7 @@
 
 use std::result::Result;
 
-use openssl::{hash, pkey, sign};
+use openssl::{hash, pkey, sha, sign};
 
 use opcua_types::status_code::StatusCode;
 
@@ -116,3 +116,8 @@ pub fn verify_hmac_sha256(key: &[u8], data: &[u8], signature: &[u8]) -> bool {
         }
     }
 }
+
+/// Returns the SHA-1 digest of the data
+pub fn sha1(data: &[u8]) -> [u8; SHA1_SIZE] {
+    sha::sha1(data)
+}
@@ -18,6 +18,7 @@ pub use {
     pkey::*,
     security_policy::*,
     thumbprint::*,
+    tls::*,
     user_identity::*,
     x509::*,
 };
@@ -39,6 +40,7 @@ pub mod hash;
 pub mod security_policy;
 pub mod user_identity;
 pub mod random;
+pub mod tls;
 
 // Size of a SHA1 hash value in bytes
 pub const SHA1_SIZE: usize = 20;
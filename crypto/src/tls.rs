@@ -0,0 +1,145 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! TLS support for the HTTPS transport profile. The server presents its application instance
+//! certificate, or a separately configured one. The client accepts a server certificate that
+//! chains to a system root, or one that the certificate store trusts.
+
+use std::{
+    fmt::Debug,
+    io::{Read, Write},
+    sync::{Arc, RwLock},
+};
+
+use openssl::ssl::{self, HandshakeError, SslAcceptor, SslConnector, SslMethod, SslVerifyMode};
+
+use opcua_types::status_code::StatusCode;
+
+use crate::{
+    certificate_store::CertificateStore,
+    pkey::PrivateKey,
+    security_policy::SecurityPolicy,
+    x509::X509,
+};
+
+/// A TLS stream over a socket
+pub type TlsStream<S> = ssl::SslStream<S>;
+
+/// A TLS handshake on a non-blocking socket that is waiting for the socket to become ready
+pub type MidHandshakeTlsStream<S> = ssl::MidHandshakeSslStream<S>;
+
+/// The result of a step of a TLS handshake on a non-blocking socket
+pub enum TlsHandshake<S> {
+    /// The handshake is complete
+    Complete(TlsStream<S>),
+    /// The handshake must be continued with `continue_handshake` when the socket is ready
+    Pending(MidHandshakeTlsStream<S>),
+}
+
+impl<S> TlsHandshake<S> where S: Read + Write + Debug {
+    fn from_result(result: Result<TlsStream<S>, HandshakeError<S>>) -> Result<TlsHandshake<S>, StatusCode> {
+        match result {
+            Ok(stream) => Ok(TlsHandshake::Complete(stream)),
+            Err(HandshakeError::WouldBlock(stream)) => Ok(TlsHandshake::Pending(stream)),
+            Err(err) => {
+                error!("TLS handshake failed, {}", err);
+                Err(StatusCode::BadSecurityChecksFailed)
+            }
+        }
+    }
+
+    /// Continues a pending handshake
+    pub fn continue_handshake(stream: MidHandshakeTlsStream<S>) -> Result<TlsHandshake<S>, StatusCode> {
+        Self::from_result(stream.handshake())
+    }
+}
+
+/// Accepts TLS connections on the server side
+pub struct TlsAcceptor {
+    acceptor: SslAcceptor,
+}
+
+impl TlsAcceptor {
+    pub fn new(cert: &X509, pkey: &PrivateKey) -> Result<TlsAcceptor, StatusCode> {
+        let mut builder = SslAcceptor::mozilla_intermediate_v5(SslMethod::tls()).map_err(|err| {
+            error!("Cannot create a TLS acceptor, {}", err);
+            StatusCode::BadInternalError
+        })?;
+        builder.set_private_key(&pkey.value)
+            .and_then(|_| builder.set_certificate(&cert.value))
+            .and_then(|_| builder.check_private_key())
+            .map_err(|err| {
+                error!("Cannot use the certificate and private key for TLS, {}", err);
+                StatusCode::BadCertificateInvalid
+            })?;
+        Ok(TlsAcceptor { acceptor: builder.build() })
+    }
+
+    /// Performs the server side of the TLS handshake
+    pub fn accept<S>(&self, stream: S) -> Result<TlsStream<S>, StatusCode> where S: Read + Write + Debug {
+        self.acceptor.accept(stream).map_err(|err| {
+            error!("TLS handshake with client failed, {}", err);
+            StatusCode::BadSecurityChecksFailed
+        })
+    }
+
+    /// Starts the server side of the TLS handshake on a non-blocking socket
+    pub fn start_accept<S>(&self, stream: S) -> Result<TlsHandshake<S>, StatusCode> where S: Read + Write + Debug {
+        TlsHandshake::from_result(self.acceptor.accept(stream))
+    }
+}
+
+/// Makes TLS connections on the client side
+pub struct TlsConnector {
+    connector: SslConnector,
+}
+
+impl TlsConnector {
+    /// Creates a connector that accepts server certificates which chain to a system root, or
+    /// which are trusted by the certificate store. Certificates the store does not know are
+    /// rejected or trusted according to its `trust_unknown_certs` setting.
+    pub fn new(certificate_store: Arc<RwLock<CertificateStore>>, hostname: &str) -> Result<TlsConnector, StatusCode> {
+        let mut builder = SslConnector::builder(SslMethod::tls()).map_err(|err| {
+            error!("Cannot create a TLS connector, {}", err);
+            StatusCode::BadInternalError
+        })?;
+        let hostname = hostname.to_string();
+        builder.set_verify_callback(SslVerifyMode::PEER, move |preverify_ok, ctx| {
+            if preverify_ok {
+                true
+            } else if let Some(cert) = ctx.chain().and_then(|chain| chain.get(0)) {
+                // The certificate of the server is not signed by a known authority so it must be
+                // trusted by the store instead
+                let cert = X509::from(cert.to_owned());
+                let certificate_store = certificate_store.read().unwrap_or_else(|err| err.into_inner());
+                certificate_store.validate_or_reject_application_instance_cert(&cert, SecurityPolicy::Basic256Sha256, Some(&hostname), None).is_good()
+            } else {
+                false
+            }
+        });
+        Ok(TlsConnector { connector: builder.build() })
+    }
+
+    /// Creates a connector that only accepts server certificates which chain to a system root
+    pub fn with_system_roots() -> Result<TlsConnector, StatusCode> {
+        let builder = SslConnector::builder(SslMethod::tls()).map_err(|err| {
+            error!("Cannot create a TLS connector, {}", err);
+            StatusCode::BadInternalError
+        })?;
+        Ok(TlsConnector { connector: builder.build() })
+    }
+
+    /// Performs the client side of the TLS handshake
+    pub fn connect<S>(&self, hostname: &str, stream: S) -> Result<TlsStream<S>, StatusCode> where S: Read + Write + Debug {
+        self.connector.connect(hostname, stream).map_err(|err| {
+            error!("TLS handshake with server {} failed, {}", hostname, err);
+            StatusCode::BadSecurityChecksFailed
+        })
+    }
+
+    /// Starts the client side of the TLS handshake on a non-blocking socket
+    pub fn start_connect<S>(&self, hostname: &str, stream: S) -> Result<TlsHandshake<S>, StatusCode> where S: Read + Write + Debug {
+        TlsHandshake::from_result(self.connector.connect(hostname, stream))
+    }
+}
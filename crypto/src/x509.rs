@@ -145,7 +145,7 @@ impl X509Data {
 /// This is a wrapper around the `OpenSSL` `X509` cert
 #[derive(Clone)]
 pub struct X509 {
-    value: x509::X509,
+    pub(crate) value: x509::X509,
 }
 
 impl Debug for X509 {
@@ -50,8 +50,33 @@ pub fn endpoint_url(port: u16, path: &str) -> String {
     format!("opc.tcp://{}:{}{}", hostname(), port, path)
 }
 
+fn https_port(port: u16) -> u16 {
+    // The https listener sits on a port of its own, clear of the opc.tcp ports handed out to tests
+    port + 1000
+}
+
+pub fn https_endpoint_url(port: u16, path: &str) -> String {
+    format!("https://{}:{}{}", hostname(), https_port(port), path)
+}
+
+fn wss_port(port: u16) -> u16 {
+    port + 2000
+}
+
+pub fn wss_endpoint_url(port: u16, path: &str) -> String {
+    format!("opc.wss://{}:{}{}", hostname(), wss_port(port), path)
+}
+
+fn reverse_connect_port(port: u16) -> u16 {
+    port + 3000
+}
+
 fn v1_node_id() -> NodeId { NodeId::new(2, "v1") }
 
+pub fn point_data_type_id() -> NodeId { NodeId::new(2, "Point") }
+
+pub fn point_node_id() -> NodeId { NodeId::new(2, "point") }
+
 pub fn stress_node_id(idx: usize) -> NodeId {
     NodeId::new(2, format!("v{:04}", idx))
 }
@@ -154,6 +179,35 @@ pub fn new_server(port: u16) -> Server {
             v.set_value_getter(Arc::new(Mutex::new(getter)));
         }
 
+        // Add a custom structure and a variable holding a value of it. The server's application
+        // uri is namespace 1 so this is namespace 2.
+        let _ = address_space.register_namespace("urn:integration_server:types");
+        let point_field = |name: &str| StructureField {
+            name: UAString::from(name),
+            description: LocalizedText::null(),
+            data_type: DataTypeId::Double.into(),
+            value_rank: -1,
+            array_dimensions: None,
+            max_string_length: 0,
+            is_optional: false,
+        };
+        let point_definition = StructureDefinition {
+            default_encoding_id: NodeId::new(2, "Point.DefaultBinary"),
+            base_data_type: NodeId::null(),
+            structure_type: StructureType::Structure,
+            fields: Some(vec![point_field("X"), point_field("Y")]),
+        };
+        address_space.add_structure_data_type(&point_data_type_id(), "Point", "Point", point_definition.clone());
+        let mut point_types = DynamicTypes::new();
+        point_types.add_definition(point_data_type_id(), point_definition.into());
+        let point = DynamicStructure::new(point_data_type_id()).with_field("X", 1.0f64).with_field("Y", 2.0f64);
+        VariableBuilder::new(&point_node_id(), "point", "point")
+            .data_type(point_data_type_id())
+            .value(point_types.encode(&point).unwrap())
+            .writable()
+            .organized_by(&sample_folder_id)
+            .insert(&mut address_space);
+
         // Add a bunch of sequential vars too, similar to demo-server
         let node_ids = (0..1000).map(|i| stress_node_id(i)).collect::<Vec<NodeId>>();
         let folder_id = address_space
@@ -451,6 +505,75 @@ pub fn connect_with_invalid_active_session(port: u16, mut client_endpoint: Endpo
     });
 }
 
+pub fn connect_with_https(port: u16, mut client_endpoint: EndpointDescription, identity_token: IdentityToken) {
+    client_endpoint.endpoint_url = UAString::from(https_endpoint_url(port, client_endpoint.endpoint_url.as_ref()));
+    let (client, server) = new_client_server(port);
+    {
+        // Offer the endpoints over https too
+        let server_state = server.server_state();
+        let server_state = server_state.read().unwrap();
+        let mut config = server_state.config.write().unwrap();
+        config.https_config = Some(opcua_server::config::HttpsConfig {
+            host: hostname(),
+            port: https_port(port),
+            certificate_path: None,
+            private_key_path: None,
+            max_connections: opcua_server::config::HttpsConfig::DEFAULT_MAX_CONNECTIONS,
+        });
+    }
+    perform_test(client, server, Some(move |rx_client_command: mpsc::Receiver<ClientCommand>, client: Client| {
+        regular_client_test(client_endpoint, identity_token, rx_client_command, client);
+    }), regular_server_test);
+}
+
+pub fn connect_with_wss(port: u16, mut client_endpoint: EndpointDescription, identity_token: IdentityToken) {
+    client_endpoint.endpoint_url = UAString::from(wss_endpoint_url(port, client_endpoint.endpoint_url.as_ref()));
+    let (client, server) = new_client_server(port);
+    {
+        // Offer the endpoints over wss too
+        let server_state = server.server_state();
+        let server_state = server_state.read().unwrap();
+        let mut config = server_state.config.write().unwrap();
+        config.wss_config = Some(opcua_server::config::WssConfig {
+            host: hostname(),
+            port: wss_port(port),
+            certificate_path: None,
+            private_key_path: None,
+        });
+    }
+    perform_test(client, server, Some(move |rx_client_command: mpsc::Receiver<ClientCommand>, client: Client| {
+        regular_client_test(client_endpoint, identity_token, rx_client_command, client);
+    }), regular_server_test);
+}
+
+pub fn connect_with_reverse_connect(port: u16, mut client_endpoint: EndpointDescription, identity_token: IdentityToken) {
+    client_endpoint.endpoint_url = UAString::from(endpoint_url(port, client_endpoint.endpoint_url.as_ref()));
+    let (client, server) = new_client_server(port);
+    {
+        // The server connects out to the client
+        let server_state = server.server_state();
+        let server_state = server_state.read().unwrap();
+        let mut config = server_state.config.write().unwrap();
+        config.reverse_connect_config = Some(opcua_server::config::ReverseConnectConfig {
+            client_urls: vec![endpoint_url(reverse_connect_port(port), "/")],
+            retry_interval: 500,
+        });
+    }
+    perform_test(client, server, Some(move |_rx_client_command: mpsc::Receiver<ClientCommand>, mut client: Client| {
+        let listener = ReverseConnectListener::bind((hostname().as_str(), reverse_connect_port(port))).unwrap();
+        let session = client.connect_to_endpoint_reverse(Arc::new(listener), "urn:integration_server", client_endpoint, identity_token).unwrap();
+        let mut session = session.write().unwrap();
+        assert!(session.is_connected());
+
+        // Read the variable
+        let read_nodes = vec![ReadValueId::from(v1_node_id())];
+        let values = session.read(&read_nodes).unwrap();
+        assert_eq!(values[0].value, Some(Variant::from(100)));
+
+        session.disconnect();
+    }), regular_server_test);
+}
+
 pub fn connect_with(port: u16, mut client_endpoint: EndpointDescription, identity_token: IdentityToken) {
     client_endpoint.endpoint_url = UAString::from(endpoint_url(port, client_endpoint.endpoint_url.as_ref()));
     connect_with_client_test(port, move |rx_client_command: mpsc::Receiver<ClientCommand>, client: Client| {
@@ -1,6 +1,7 @@
 use chrono::Utc;
+use futures::Future;
 use log::*;
-use opcua_client::prelude::*;
+use opcua_client::prelude::{*, ServerState as ServerStateType};
 use opcua_console_logging;
 use opcua_server::{
     self,
@@ -9,9 +10,10 @@ use opcua_server::{
 use std::{
     sync::{
         Arc, mpsc, mpsc::channel,
-        RwLock,
+        Mutex, RwLock,
     },
     thread,
+    time::{Duration, Instant},
 };
 
 use crate::harness::*;
@@ -174,6 +176,48 @@ fn connect_none() {
     connect_with(next_port(), endpoint_none(), IdentityToken::Anonymous);
 }
 
+/// Connect to the server over https using no encryption, anonymous
+#[test]
+#[ignore]
+fn connect_https_none() {
+    connect_with_https(next_port(), endpoint_none(), IdentityToken::Anonymous);
+}
+
+/// Connect to the server over https using Basic256Sha256 + SignAndEncrypt, user name and password
+#[test]
+#[ignore]
+fn connect_https_basic256sha256_sign_and_encrypt_user_pass() {
+    connect_with_https(next_port(), endpoint_basic256sha256_sign_encrypt(), client_user_token());
+}
+
+/// Connect to the server over wss using no encryption, anonymous
+#[test]
+#[ignore]
+fn connect_wss_none() {
+    connect_with_wss(next_port(), endpoint_none(), IdentityToken::Anonymous);
+}
+
+/// Connect to the server over wss using Basic256Sha256 + SignAndEncrypt inside the TLS connection
+#[test]
+#[ignore]
+fn connect_wss_basic256sha256_sign_and_encrypt() {
+    connect_with_wss(next_port(), endpoint_basic256sha256_sign_encrypt(), IdentityToken::Anonymous);
+}
+
+/// Connect to the server with reverse connect using no encryption, anonymous
+#[test]
+#[ignore]
+fn connect_reverse_connect_none() {
+    connect_with_reverse_connect(next_port(), endpoint_none(), IdentityToken::Anonymous);
+}
+
+/// Connect to the server with reverse connect using Basic256Sha256 + SignAndEncrypt
+#[test]
+#[ignore]
+fn connect_reverse_connect_basic256sha256_sign_and_encrypt() {
+    connect_with_reverse_connect(next_port(), endpoint_basic256sha256_sign_encrypt(), IdentityToken::Anonymous);
+}
+
 /// Connect to the server using Basic128Rsa15 + Sign
 #[test]
 #[ignore]
@@ -325,6 +369,511 @@ fn read_write_read() {
     });
 }
 
+/// Connect to a server, pipeline a write and many reads through the async session, and wait for
+/// all of the responses together
+#[test]
+#[ignore]
+fn read_write_async() {
+    let mut client_endpoint = endpoint_none();
+    let port = next_port();
+    let identity_token = IdentityToken::Anonymous;
+
+    client_endpoint.endpoint_url = UAString::from(endpoint_url(port, client_endpoint.endpoint_url.as_ref()));
+    connect_with_client_test(port, move |_rx_client_command: mpsc::Receiver<ClientCommand>, mut client: Client| {
+        info!("Client will try to connect to endpoint {:?}", client_endpoint);
+        let session = client.connect_to_endpoint(client_endpoint, identity_token).unwrap();
+        let async_session = session.read().unwrap().async_session();
+
+        let results = async_session.write(&[WriteValue {
+            node_id: stress_node_id(1),
+            attribute_id: AttributeId::Value as u32,
+            index_range: UAString::null(),
+            value: Variant::Int32(1).into(),
+        }]).wait().unwrap();
+        assert_eq!(results[0], StatusCode::Good);
+
+        // Send all the reads before waiting for any of them
+        let reads = (0..100)
+            .map(|i| async_session.read(&[stress_node_id(i).into()]))
+            .collect::<Vec<_>>();
+        let results = futures::future::join_all(reads).wait().unwrap();
+        assert_eq!(results.len(), 100);
+        results.iter().enumerate().for_each(|(i, values)| {
+            let expected = if i == 1 { 1 } else { 0 };
+            assert_eq!(*values[0].value.as_ref().unwrap(), Variant::Int32(expected));
+        });
+
+        {
+            let mut session = session.write().unwrap();
+            session.disconnect();
+        }
+    });
+}
+
+/// Connect to a server and use the async session to translate a browse path, subscribe to a
+/// variable and change its monitored item, then delete them again
+#[test]
+#[ignore]
+fn subscribe_async() {
+    let mut client_endpoint = endpoint_none();
+    let port = next_port();
+    let identity_token = IdentityToken::Anonymous;
+
+    client_endpoint.endpoint_url = UAString::from(endpoint_url(port, client_endpoint.endpoint_url.as_ref()));
+    connect_with_client_test(port, move |_rx_client_command: mpsc::Receiver<ClientCommand>, mut client: Client| {
+        let session = client.connect_to_endpoint(client_endpoint, identity_token).unwrap();
+        let async_session = session.read().unwrap().async_session();
+        // The run loop delivers the notifications to the callback
+        let session_tx = Session::run_async(session.clone());
+
+        let element = |name: &str| RelativePathElement {
+            reference_type_id: ReferenceTypeId::HierarchicalReferences.into(),
+            is_inverse: false,
+            include_subtypes: true,
+            target_name: QualifiedName::new(0, name),
+        };
+        let results = async_session.translate_browse_paths_to_node_ids(&[BrowsePath {
+            starting_node: ObjectId::RootFolder.into(),
+            relative_path: RelativePath { elements: Some(vec![element("Objects"), element("Server")]) },
+        }]).wait().unwrap();
+        assert!(results[0].status_code.is_good());
+        assert_eq!(results[0].targets.as_ref().unwrap()[0].target_id, ObjectId::Server.into());
+
+        let values = Arc::new(Mutex::new(Vec::new()));
+        let subscription_id = {
+            let values = values.clone();
+            let params = SubscriptionParams {
+                publishing_interval: 100.0,
+                lifetime_count: 100,
+                ..Default::default()
+            };
+            async_session.create_subscription(params, DataChangeCallback::new(move |items| {
+                let mut values = values.lock().unwrap();
+                items.iter().for_each(|item| values.push(item.value().value.clone()));
+            })).wait().unwrap()
+        };
+        let items_to_create = vec![MonitoredItemCreateRequest::new(stress_node_id(1).into(), MonitoringMode::Reporting, MonitoringParameters {
+            client_handle: 1,
+            sampling_interval: 100.0,
+            filter: ExtensionObject::null(),
+            queue_size: 1,
+            discard_oldest: true,
+        })];
+        let results = async_session.create_monitored_items(subscription_id, TimestampsToReturn::Both, &items_to_create).wait().unwrap();
+        assert!(results[0].status_code.is_good());
+        let monitored_item_id = results[0].monitored_item_id;
+        assert!(wait_until(|| values.lock().unwrap().contains(&Some(Variant::Int32(0)))));
+
+        let items_to_modify = vec![MonitoredItemModifyRequest {
+            monitored_item_id,
+            requested_parameters: MonitoringParameters {
+                client_handle: 1,
+                sampling_interval: 200.0,
+                filter: ExtensionObject::null(),
+                queue_size: 1,
+                discard_oldest: true,
+            },
+        }];
+        let results = async_session.modify_monitored_items(subscription_id, TimestampsToReturn::Both, &items_to_modify).wait().unwrap();
+        assert!(results[0].status_code.is_good());
+
+        async_session.write(&[WriteValue {
+            node_id: stress_node_id(1),
+            attribute_id: AttributeId::Value as u32,
+            index_range: UAString::null(),
+            value: Variant::Int32(5).into(),
+        }]).wait().unwrap();
+        assert!(wait_until(|| values.lock().unwrap().contains(&Some(Variant::Int32(5)))));
+
+        assert_eq!(async_session.set_monitoring_mode(subscription_id, MonitoringMode::Disabled, &[monitored_item_id]).wait().unwrap(), vec![StatusCode::Good]);
+        assert_eq!(async_session.delete_monitored_items(subscription_id, &[monitored_item_id]).wait().unwrap(), vec![StatusCode::Good]);
+        assert_eq!(async_session.delete_subscriptions(&[subscription_id]).wait().unwrap(), vec![StatusCode::Good]);
+        // The subscription is gone from the session too
+        assert_eq!(async_session.delete_monitored_items(subscription_id, &[monitored_item_id]).wait().unwrap_err(), StatusCode::BadInvalidArgument);
+
+        let _ = session_tx.send(SessionCommand::Stop);
+        thread::sleep(Duration::from_millis(500));
+        session.write().unwrap().disconnect();
+    });
+}
+
+/// Connect to a server through a connection manager, read a value over the managed session and
+/// check the health of the connections
+#[test]
+#[ignore]
+fn connection_manager() {
+    let mut client_endpoint = endpoint_none();
+    let port = next_port();
+
+    client_endpoint.endpoint_url = UAString::from(endpoint_url(port, client_endpoint.endpoint_url.as_ref()));
+    connect_with_client_test(port, move |_rx_client_command: mpsc::Receiver<ClientCommand>, client: Client| {
+        let connection_manager = ConnectionManager::new(client, 1);
+        let (tx, rx) = mpsc::channel();
+        let tx = Mutex::new(tx);
+        connection_manager.set_status_change_callback(ManagedConnectionStatusCallback::new(move |id, status| {
+            let _ = tx.lock().unwrap().send((id.to_string(), status));
+        }));
+        connection_manager.add_connection("server", client_endpoint, IdentityToken::Anonymous).unwrap();
+
+        let status = rx.recv_timeout(Duration::from_secs(5)).expect("connection was not established");
+        assert_eq!(status, ("server".to_string(), ConnectionStatus::Connected));
+        assert!(connection_manager.health().is_healthy());
+
+        let values = connection_manager.async_session("server").unwrap()
+            .read(&[stress_node_id(1).into()]).wait().unwrap();
+        assert_eq!(*values[0].value.as_ref().unwrap(), Variant::Int32(0));
+
+        connection_manager.remove_connection("server").unwrap();
+        assert_eq!(connection_manager.health().connections, 0);
+    });
+}
+
+/// Connect to a server and browse the objects folder recursively, reading the value of every
+/// variable found on the way
+#[test]
+#[ignore]
+fn browse_recursive() {
+    let mut client_endpoint = endpoint_none();
+    let port = next_port();
+    let identity_token = IdentityToken::Anonymous;
+
+    client_endpoint.endpoint_url = UAString::from(endpoint_url(port, client_endpoint.endpoint_url.as_ref()));
+    connect_with_client_test(port, move |_rx_client_command: mpsc::Receiver<ClientCommand>, mut client: Client| {
+        let session = client.connect_to_endpoint(client_endpoint, identity_token).unwrap();
+        let browser = session.read().unwrap().browser()
+            .max_depth(2)
+            .nodes_per_request(10)
+            .read_attributes(&[AttributeId::NodeClass, AttributeId::Value]);
+
+        let tree = browser.browse_tree(ObjectId::ObjectsFolder).unwrap();
+        assert!(tree.find(&ObjectId::Server.into()).is_some());
+        let stress_folder = tree.children.iter().find(|c| {
+            c.node.reference.as_ref().unwrap().browse_name == QualifiedName::from("Stress")
+        }).unwrap();
+        assert_eq!(stress_folder.children.len(), 1000);
+        let stress_node = tree.find(&stress_node_id(10)).unwrap();
+        assert_eq!(stress_node.node.depth, 2);
+        assert_eq!(*stress_node.node.values[1].value.as_ref().unwrap(), Variant::Int32(0));
+
+        // Nothing is returned twice, and nothing is deeper than the limit
+        let nodes = browser.browse_iter(ObjectId::ObjectsFolder).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(nodes.len(), tree.node_count());
+        assert!(nodes.iter().all(|n| n.depth <= 2));
+        let node_ids = nodes.iter().map(|n| n.node_id.clone()).collect::<std::collections::HashSet<_>>();
+        assert_eq!(node_ids.len(), nodes.len());
+
+        {
+            let mut session = session.write().unwrap();
+            session.disconnect();
+        }
+    });
+}
+
+/// Connect to a server, write values of other types to a variable, which are converted to its
+/// data type, and read them back as the types wanted
+#[test]
+#[ignore]
+fn read_write_typed() {
+    let mut client_endpoint = endpoint_none();
+    let port = next_port();
+    let identity_token = IdentityToken::Anonymous;
+
+    client_endpoint.endpoint_url = UAString::from(endpoint_url(port, client_endpoint.endpoint_url.as_ref()));
+    connect_with_client_test(port, move |_rx_client_command: mpsc::Receiver<ClientCommand>, mut client: Client| {
+        let session = client.connect_to_endpoint(client_endpoint, identity_token).unwrap();
+        let mut session = session.write().unwrap();
+
+        // The variable is an Int32
+        let node_id = stress_node_id(3);
+        session.write_value(&node_id, 42.0f64).unwrap();
+        assert_eq!(session.read_value::<i32>(&node_id), Ok(42));
+        assert_eq!(session.read_value::<f64>(&node_id), Ok(42.0));
+        assert_eq!(session.write_value(&node_id, 1.5f64), Err(StatusCode::BadTypeMismatch));
+        assert_eq!(session.read_value::<String>(&node_id), Err(StatusCode::BadTypeMismatch));
+
+        let values = session.read_values::<i32>(&[stress_node_id(3), stress_node_id(4), NodeId::new(2, "unknown")]).unwrap();
+        assert_eq!(values, vec![Ok(42), Ok(0), Err(StatusCode::BadNodeIdUnknown)]);
+
+        session.disconnect();
+    });
+}
+
+/// Connect to a server, find nodes from their paths, and register them for repeated access
+#[test]
+#[ignore]
+fn browse_paths() {
+    let mut client_endpoint = endpoint_none();
+    let port = next_port();
+    let identity_token = IdentityToken::Anonymous;
+
+    client_endpoint.endpoint_url = UAString::from(endpoint_url(port, client_endpoint.endpoint_url.as_ref()));
+    connect_with_client_test(port, move |_rx_client_command: mpsc::Receiver<ClientCommand>, mut client: Client| {
+        let session = client.connect_to_endpoint(client_endpoint, identity_token).unwrap();
+        let mut session = session.write().unwrap();
+
+        assert_eq!(session.node_id_for_path("/Objects/Server/ServerStatus"), Ok(VariableId::Server_ServerStatus.into()));
+        let results = session.node_ids_for_paths(&["/Objects/Stress/stress node v0005", "/Objects/Missing", "Objects"]).unwrap();
+        assert_eq!(results, vec![Ok(stress_node_id(5)), Err(StatusCode::BadNoMatch), Err(StatusCode::BadBrowseNameInvalid)]);
+
+        // The registered node id can be used in place of the node id
+        let path = "/Objects/Stress/stress node v0006";
+        let registered = session.register_paths(&[path]).unwrap();
+        assert_eq!(session.node_id_for_path(path), Ok(registered[0].clone()));
+        assert_eq!(session.read_value::<i32>(&registered[0]), Ok(0));
+        session.unregister_paths(&[path]).unwrap();
+        assert_eq!(session.node_id_for_path(path), Ok(stress_node_id(6)));
+
+        session.disconnect();
+    });
+}
+
+/// Connect to a server and find nodes from ids that name their namespace by uri
+#[test]
+#[ignore]
+fn namespace_uri_node_ids() {
+    let mut client_endpoint = endpoint_none();
+    let port = next_port();
+    let identity_token = IdentityToken::Anonymous;
+
+    client_endpoint.endpoint_url = UAString::from(endpoint_url(port, client_endpoint.endpoint_url.as_ref()));
+    connect_with_client_test(port, move |_rx_client_command: mpsc::Receiver<ClientCommand>, mut client: Client| {
+        let session = client.connect_to_endpoint(client_endpoint, identity_token).unwrap();
+        let mut session = session.write().unwrap();
+
+        // The namespace table is read when the session is activated
+        assert_eq!(session.namespace_map().uri(1), Some("urn:integration_server"));
+        assert_eq!(session.namespace_map().index_of("urn:integration_server:types"), Some(2));
+
+        let node_id = session.parse_node_id("nsu=urn:integration_server:types;s=v0005").unwrap();
+        assert_eq!(node_id, stress_node_id(5));
+        assert_eq!(session.read_value::<i32>(&node_id), Ok(0));
+        assert_eq!(session.parse_node_id("i=2256"), Ok(VariableId::Server_ServerStatus.into()));
+        assert_eq!(session.parse_node_id("nsu=urn:missing;s=v0005"), Err(StatusCode::BadNodeIdUnknown));
+
+        let expanded = session.namespace_map().to_expanded_node_id(&stress_node_id(7));
+        assert_eq!(expanded.to_string(), "svr=0;nsu=urn:integration_server:types;s=v0007");
+        assert_eq!(session.resolve_node_id(&expanded), Ok(stress_node_id(7)));
+
+        session.disconnect();
+    });
+}
+
+/// The connection and server state changes that a session has reported
+type ConnectionEventLog = Arc<Mutex<Vec<(bool, Option<ServerStateType>)>>>;
+
+/// Records the connection and server state changes that a session reports
+struct ConnectionEvents(ConnectionEventLog);
+
+impl OnConnectionStatusChange for ConnectionEvents {
+    fn on_connection_status_change(&mut self, connected: bool) {
+        self.0.lock().unwrap().push((connected, None));
+    }
+
+    fn on_server_state_change(&mut self, server_state: ServerStateType) {
+        self.0.lock().unwrap().push((true, Some(server_state)));
+    }
+}
+
+/// Waits up to 10s for the condition to be true
+fn wait_until<F>(condition: F) -> bool where F: Fn() -> bool {
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while Instant::now() < deadline {
+        if condition() {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    false
+}
+
+/// Connect to a server with a keep-alive, and check that the session drops the connection when
+/// the server says it has failed, and reconnects once it is running again
+#[test]
+#[ignore]
+fn keep_alive_server_state() {
+    let mut client_endpoint = endpoint_none();
+    let port = next_port();
+    client_endpoint.endpoint_url = UAString::from(endpoint_url(port, client_endpoint.endpoint_url.as_ref()));
+
+    let client = ClientBuilder::new()
+        .application_name("integration_client")
+        .application_uri("x")
+        .pki_dir("./pki-client")
+        .create_sample_keypair(true)
+        .trust_server_certs(true)
+        .session_retry_limit(-1)
+        .session_retry_interval(500)
+        .keep_alive_interval(500)
+        .keep_alive_max_missed(2)
+        .client().unwrap();
+    let server = new_server(port);
+    let server_state = server.server_state();
+
+    perform_test(client, server, Some(move |_rx_client_command: mpsc::Receiver<ClientCommand>, mut client: Client| {
+        let session = client.connect_to_endpoint(client_endpoint, IdentityToken::Anonymous).unwrap();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        session.write().unwrap().set_connection_status_callback(ConnectionEvents(events.clone()));
+        let session_tx = Session::run_async(session.clone());
+
+        assert!(wait_until(|| session.read().unwrap().server_state() == Some(ServerStateType::Running)));
+
+        // The session drops the connection when the server fails
+        server_state.write().unwrap().set_state(ServerStateType::Failed);
+        assert!(wait_until(|| {
+            let events = events.lock().unwrap();
+            events.contains(&(true, Some(ServerStateType::Failed))) && events.contains(&(false, None))
+        }));
+
+        // And reconnects when it is running again
+        events.lock().unwrap().clear();
+        server_state.write().unwrap().set_state(ServerStateType::Running);
+        assert!(wait_until(|| {
+            let running = events.lock().unwrap().contains(&(true, Some(ServerStateType::Running)));
+            running && session.read().unwrap().is_connected()
+        }));
+        assert_eq!(session.write().unwrap().read_value::<i32>(&stress_node_id(1)), Ok(0));
+
+        // Let the run loop stop before disconnecting so it does not reconnect
+        let _ = session_tx.send(SessionCommand::Stop);
+        thread::sleep(Duration::from_millis(500));
+        session.write().unwrap().disconnect();
+    }), regular_server_test);
+}
+
+/// Connect a redundant session to two servers in a warm redundant server set and subscribe to a
+/// variable, then degrade the server the session is on and check that the session fails over to
+/// the other server, where its subscription carries on
+#[test]
+#[ignore]
+fn redundant_session_failover() {
+    let port1 = next_port();
+    let port2 = next_port();
+    let endpoints = [port1, port2].iter().map(|port| {
+        let mut endpoint = endpoint_none();
+        endpoint.endpoint_url = UAString::from(endpoint_url(*port, endpoint.endpoint_url.as_ref()));
+        endpoint
+    }).collect::<Vec<_>>();
+
+    let client = ClientBuilder::new()
+        .application_name("integration_client")
+        .application_uri("x")
+        .pki_dir("./pki-client")
+        .create_sample_keypair(true)
+        .trust_server_certs(true)
+        .session_retry_limit(-1)
+        .session_retry_interval(500)
+        .client().unwrap();
+    let server1 = new_server(port1);
+    let server2 = new_server(port2);
+    let address_space1 = server1.address_space();
+    let address_space2 = server2.address_space();
+    // Both servers have the same application uri, so the server uris do not add another endpoint
+    let server_uris = vec!["urn:integration_server".to_string()];
+    [&address_space1, &address_space2].iter().for_each(|address_space| {
+        address_space.write().unwrap().set_server_redundancy(RedundancySupport::Warm, &server_uris, &DateTime::now());
+    });
+
+    perform_test(client, server1, Some(move |_rx_client_command: mpsc::Receiver<ClientCommand>, client: Client| {
+        let mut redundant_session = RedundantSession::new(client, endpoints.clone(), IdentityToken::Anonymous).unwrap();
+        redundant_session.set_check_interval(500);
+        assert_eq!(redundant_session.mode(), FailoverMode::Warm);
+        assert_eq!(redundant_session.endpoints(), &endpoints[..]);
+        assert_eq!(redundant_session.active_server(), 0);
+        assert!(wait_until(|| redundant_session.service_levels() == vec![Some(255), Some(255)]));
+
+        let session = redundant_session.session();
+        assert_eq!(session.write().unwrap().read_server_uri_array(), Ok(vec!["urn:integration_server".to_string()]));
+
+        let values = Arc::new(Mutex::new(Vec::new()));
+        {
+            let values = values.clone();
+            let mut session = session.write().unwrap();
+            let subscription_id = session.create_subscription(100.0, 100, 10, 0, 0, true, DataChangeCallback::new(move |items| {
+                let mut values = values.lock().unwrap();
+                items.iter().for_each(|item| values.push(item.value().value.clone()));
+            })).unwrap();
+            let items_to_create = vec![MonitoredItemCreateRequest::new(stress_node_id(1).into(), MonitoringMode::Reporting, MonitoringParameters {
+                client_handle: 1,
+                sampling_interval: 100.0,
+                filter: ExtensionObject::null(),
+                queue_size: 1,
+                discard_oldest: true,
+            })];
+            let results = session.create_monitored_items(subscription_id, TimestampsToReturn::Both, &items_to_create).unwrap();
+            assert!(results[0].status_code.is_good());
+        }
+        assert!(wait_until(|| values.lock().unwrap().contains(&Some(Variant::Int32(0)))));
+
+        // The session fails over when the server it is on degrades
+        address_space1.write().unwrap().set_service_level(100, &DateTime::now());
+        assert!(wait_until(|| redundant_session.active_server() == 1));
+        assert_eq!(redundant_session.failover_count(), 1);
+        assert!(session.read().unwrap().is_connected());
+
+        // The subscription carries on with the other server
+        let now = DateTime::now();
+        address_space2.write().unwrap().set_variable_value(stress_node_id(1), 42, &now, &now);
+        assert!(wait_until(|| values.lock().unwrap().contains(&Some(Variant::Int32(42)))));
+
+        redundant_session.stop();
+    }), move |rx_server_command: mpsc::Receiver<ServerCommand>, server: Server| {
+        // The second server runs alongside the first
+        let server2 = Arc::new(RwLock::new(server2));
+        let server2_thread = {
+            let server2 = server2.clone();
+            thread::spawn(move || Server::run_server(server2))
+        };
+        regular_server_test(rx_server_command, server);
+        server2.write().unwrap().abort();
+        let _ = server2_thread.join();
+    });
+}
+
+/// Connect to a server, read a variable holding a custom structure, decode it from the data type
+/// definition, then write a modified value back
+#[test]
+#[ignore]
+fn read_write_dynamic_structure() {
+    let mut client_endpoint = endpoint_none();
+    let port = next_port();
+    let identity_token = IdentityToken::Anonymous;
+
+    client_endpoint.endpoint_url = UAString::from(endpoint_url(port, client_endpoint.endpoint_url.as_ref()));
+    connect_with_client_test(port, move |_rx_client_command: mpsc::Receiver<ClientCommand>, mut client: Client| {
+        info!("Client will try to connect to endpoint {:?}", client_endpoint);
+        let session = client.connect_to_endpoint(client_endpoint, identity_token).unwrap();
+        let mut session = session.write().unwrap();
+
+        let read_point = |session: &mut Session| {
+            let results = session.read(&[point_node_id().into()]).unwrap();
+            if let Some(Variant::ExtensionObject(ref value)) = results[0].value {
+                session.decode_extension_object(value).unwrap()
+            } else {
+                panic!("Expected an extension object");
+            }
+        };
+
+        let point = read_point(&mut session);
+        assert_eq!(point.data_type_id, point_data_type_id());
+        assert_eq!(point.field("X"), Some(&DynamicValue::from(1.0f64)));
+        assert_eq!(point.field("Y"), Some(&DynamicValue::from(2.0f64)));
+
+        let point = point.with_field("X", 5.0f64);
+        let value = session.encode_dynamic_structure(&point).unwrap();
+        let results = session.write(&[WriteValue {
+            node_id: point_node_id(),
+            attribute_id: AttributeId::Value as u32,
+            index_range: UAString::null(),
+            value: Variant::from(value).into(),
+        }]).unwrap().unwrap();
+        assert_eq!(results[0], StatusCode::Good);
+
+        assert_eq!(read_point(&mut session).field("X"), Some(&DynamicValue::from(5.0f64)));
+
+        session.disconnect();
+    });
+}
+
 /// Connect with the server and attempt to subscribe and monitor 1000 variables
 #[test]
 #[ignore]
@@ -0,0 +1,254 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! The PubSub configuration of Part 14 is a `PubSubConfigurationDataType` describing the
+//! PublishedDataSets and the PubSubConnections with their groups, writers and readers. It can be
+//! loaded from and saved to a file, and a connection of it turned into a [`Publisher`] and a
+//! [`Subscriber`].
+//!
+//! [`Publisher`]: ../publisher/struct.Publisher.html
+//! [`Subscriber`]: ../subscriber/struct.Subscriber.html
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use opcua_types::{
+    *,
+    service_types::{
+        BrokerDataSetReaderTransportDataType, BrokerDataSetWriterTransportDataType, BrokerWriterGroupTransportDataType,
+        DataSetFieldContentMask, DataSetReaderDataType, DataSetWriterDataType, MessageSecurityMode,
+        NetworkAddressUrlDataType, PubSubConfigurationDataType, PubSubConnectionDataType,
+        PublishedDataItemsDataType, PublishedDataSetDataType, ReaderGroupDataType, TargetVariablesDataType,
+        WriterGroupDataType,
+    },
+    status_code::StatusCode,
+};
+
+use crate::{
+    data_set::{DataSetField, DataSetSource, DataSetTarget, PublishedDataSet},
+    publisher::{DataSetWriter, MessageEncoding, Publisher, WriterGroup},
+    security::KeyProvider,
+    subscriber::{DataSetReader, ReaderGroup, Subscriber},
+    uadp::{FieldEncoding, PublisherId},
+};
+
+/// Transport profile of UADP messages over UDP
+pub const TRANSPORT_PROFILE_UDP_UADP: &str = "http://opcfoundation.org/UA-Profile/Transport/pubsub-udp-uadp";
+/// Transport profile of UADP messages over MQTT
+pub const TRANSPORT_PROFILE_MQTT_UADP: &str = "http://opcfoundation.org/UA-Profile/Transport/pubsub-mqtt-uadp";
+/// Transport profile of JSON messages over MQTT
+pub const TRANSPORT_PROFILE_MQTT_JSON: &str = "http://opcfoundation.org/UA-Profile/Transport/pubsub-mqtt-json";
+
+/// The key providers of the security groups that a configuration refers to, by security group id
+pub type SecurityGroups = HashMap<String, Arc<dyn KeyProvider>>;
+
+/// Loads a configuration from a file holding the binary encoded `PubSubConfigurationDataType`
+pub fn load_configuration<P>(path: P) -> Result<PubSubConfigurationDataType, StatusCode> where P: AsRef<Path> {
+    let path = path.as_ref();
+    let file = File::open(path).map_err(|err| {
+        error!("Cannot open PubSub configuration file {}, {}", path.display(), err);
+        StatusCode::BadNotFound
+    })?;
+    let configuration = PubSubConfigurationDataType::decode(&mut BufReader::new(file), &DecodingLimits::default());
+    if let Err(status_code) = configuration {
+        error!("PubSub configuration file {} cannot be decoded, {}", path.display(), status_code);
+    }
+    configuration
+}
+
+/// Saves a configuration to a file as the binary encoded `PubSubConfigurationDataType`
+pub fn save_configuration<P>(path: P, configuration: &PubSubConfigurationDataType) -> Result<(), StatusCode> where P: AsRef<Path> {
+    let path = path.as_ref();
+    let file = File::create(path).map_err(|err| {
+        error!("Cannot create PubSub configuration file {}, {}", path.display(), err);
+        StatusCode::BadUnexpectedError
+    })?;
+    let mut stream = BufWriter::new(file);
+    configuration.encode(&mut stream)?;
+    stream.flush().map_err(|err| {
+        error!("Cannot write PubSub configuration file {}, {}", path.display(), err);
+        StatusCode::BadUnexpectedError
+    })
+}
+
+/// Decodes transport or message settings, returning nothing if they are empty or of another type
+fn settings<T>(settings: &ExtensionObject, object_id: ObjectId) -> Result<Option<T>, StatusCode> where T: BinaryEncoder<T> {
+    if settings.is_empty() || settings.node_id != object_id.into() {
+        Ok(None)
+    } else {
+        settings.decode_inner(&DecodingLimits::default()).map(Some)
+    }
+}
+
+/// Returns the string, or nothing if it is null or empty
+fn non_empty(value: &UAString) -> Option<String> {
+    if value.is_empty() { None } else { Some(value.as_ref().to_string()) }
+}
+
+fn security_key_provider(security_mode: MessageSecurityMode, security_group_id: &UAString, security_groups: &SecurityGroups) -> Result<Option<Arc<dyn KeyProvider>>, StatusCode> {
+    match security_mode {
+        MessageSecurityMode::None | MessageSecurityMode::Invalid => Ok(None),
+        _ => {
+            if let Some(key_provider) = security_groups.get(security_group_id.as_ref()) {
+                Ok(Some(key_provider.clone()))
+            } else {
+                error!("Security group {} is not known", security_group_id);
+                Err(StatusCode::BadConfigurationError)
+            }
+        }
+    }
+}
+
+/// Returns the url of a connection's address
+pub fn connection_address(connection: &PubSubConnectionDataType) -> Result<String, StatusCode> {
+    settings::<NetworkAddressUrlDataType>(&connection.address, ObjectId::NetworkAddressUrlDataType_Encoding_DefaultBinary)?
+        .and_then(|address| non_empty(&address.url))
+        .ok_or_else(|| {
+            error!("Connection {} does not have a network address url", connection.name);
+            StatusCode::BadConfigurationError
+        })
+}
+
+/// Makes the PublishedDataSet of a configured one, which must be a PublishedDataItems DataSet.
+/// Fields are named from the DataSet's metadata.
+pub fn published_data_set(data_set: &PublishedDataSetDataType) -> Result<PublishedDataSet, StatusCode> {
+    let published_data_items = settings::<PublishedDataItemsDataType>(&data_set.data_set_source, ObjectId::PublishedDataItemsDataType_Encoding_DefaultBinary)?
+        .ok_or_else(|| {
+            error!("Published data set {} does not publish data items", data_set.name);
+            StatusCode::BadConfigurationError
+        })?;
+    let meta_data = &data_set.data_set_meta_data;
+    let mut published_data_set = PublishedDataSet::new(data_set.name.as_ref());
+    if meta_data.configuration_version.major_version != 0 {
+        published_data_set.configuration_version = meta_data.configuration_version.clone();
+    }
+    let field_meta_data = meta_data.fields.as_deref().unwrap_or(&[]);
+    let published_variables = published_data_items.published_data.unwrap_or_default();
+    published_data_set.fields = published_variables.into_iter().enumerate().map(|(i, published_variable)| {
+        let mut field = DataSetField::new(format!("Field{}", i + 1), published_variable.published_variable);
+        if let Some(field_meta_data) = field_meta_data.get(i) {
+            field.name = field_meta_data.name.as_ref().to_string();
+            field.data_set_field_id = field_meta_data.data_set_field_id.clone();
+        }
+        field
+    }).collect();
+    Ok(published_data_set)
+}
+
+fn data_set_writer(data_type: &DataSetWriterDataType) -> Result<DataSetWriter, StatusCode> {
+    let field_encoding = match data_type.data_set_field_content_mask {
+        DataSetFieldContentMask::None => FieldEncoding::Variant,
+        DataSetFieldContentMask::RawData => {
+            error!("Writer {} uses the RawData field encoding which is not supported", data_type.name);
+            return Err(StatusCode::BadConfigurationError);
+        }
+        _ => FieldEncoding::DataValue,
+    };
+    let mut writer = DataSetWriter::new(data_type.name.as_ref(), data_type.data_set_writer_id, data_type.data_set_name.as_ref())
+        .key_frame_count(data_type.key_frame_count.max(1))
+        .field_encoding(field_encoding);
+    let transport_settings = settings::<BrokerDataSetWriterTransportDataType>(&data_type.transport_settings, ObjectId::BrokerDataSetWriterTransportDataType_Encoding_DefaultBinary)?;
+    writer.meta_data_queue_name = transport_settings.and_then(|t| non_empty(&t.meta_data_queue_name));
+    Ok(writer)
+}
+
+/// Makes the WriterGroup of a configured one, with its enabled writers
+pub fn writer_group(data_type: &WriterGroupDataType, security_groups: &SecurityGroups) -> Result<WriterGroup, StatusCode> {
+    let message_encoding = if data_type.message_settings.node_id == ObjectId::JsonWriterGroupMessageDataType_Encoding_DefaultBinary.into() {
+        MessageEncoding::Json
+    } else {
+        MessageEncoding::Uadp
+    };
+    let mut writer_group = WriterGroup::new(data_type.name.as_ref(), data_type.writer_group_id)
+        .publishing_interval(data_type.publishing_interval)
+        .keep_alive_time(data_type.keep_alive_time)
+        .message_encoding(message_encoding);
+    if let Some(security_key_provider) = security_key_provider(data_type.security_mode, &data_type.security_group_id, security_groups)? {
+        writer_group = writer_group.security(data_type.security_mode, security_key_provider);
+    }
+    let transport_settings = settings::<BrokerWriterGroupTransportDataType>(&data_type.transport_settings, ObjectId::BrokerWriterGroupTransportDataType_Encoding_DefaultBinary)?;
+    writer_group.queue_name = transport_settings.and_then(|t| non_empty(&t.queue_name));
+    if let Some(ref writers) = data_type.data_set_writers {
+        for writer in writers.iter().filter(|w| w.enabled) {
+            writer_group = writer_group.writer(data_set_writer(writer)?);
+        }
+    }
+    Ok(writer_group)
+}
+
+fn data_set_reader(data_type: &DataSetReaderDataType) -> Result<DataSetReader, StatusCode> {
+    let publisher_id = PublisherId::from_variant(&data_type.publisher_id).ok_or_else(|| {
+        error!("Reader {} has an invalid publisher id {:?}", data_type.name, data_type.publisher_id);
+        StatusCode::BadConfigurationError
+    })?;
+    let mut reader = DataSetReader::new(data_type.name.as_ref(), publisher_id, data_type.writer_group_id, data_type.data_set_writer_id);
+    if data_type.message_receive_timeout > 0.0 {
+        reader = reader.message_receive_timeout(data_type.message_receive_timeout);
+    }
+    if data_type.data_set_meta_data.fields.as_ref().map(|f| !f.is_empty()).unwrap_or(false) {
+        reader = reader.meta_data(data_type.data_set_meta_data.clone());
+    }
+    if let Some(target_variables) = settings::<TargetVariablesDataType>(&data_type.subscribed_data_set, ObjectId::TargetVariablesDataType_Encoding_DefaultBinary)? {
+        for target_variable in target_variables.target_variables.unwrap_or_default() {
+            reader = reader.target_variable(target_variable.target_node_id);
+        }
+    }
+    if let Some(transport_settings) = settings::<BrokerDataSetReaderTransportDataType>(&data_type.transport_settings, ObjectId::BrokerDataSetReaderTransportDataType_Encoding_DefaultBinary)? {
+        reader.queue_name = non_empty(&transport_settings.queue_name);
+        reader.meta_data_queue_name = non_empty(&transport_settings.meta_data_queue_name);
+    }
+    Ok(reader)
+}
+
+/// Makes the ReaderGroup of a configured one, with its enabled readers
+pub fn reader_group(data_type: &ReaderGroupDataType, security_groups: &SecurityGroups) -> Result<ReaderGroup, StatusCode> {
+    let mut reader_group = ReaderGroup::new(data_type.name.as_ref());
+    if let Some(security_key_provider) = security_key_provider(data_type.security_mode, &data_type.security_group_id, security_groups)? {
+        reader_group = reader_group.security(data_type.security_mode, security_key_provider);
+    }
+    if let Some(ref readers) = data_type.data_set_readers {
+        for reader in readers.iter().filter(|r| r.enabled) {
+            reader_group = reader_group.reader(data_set_reader(reader)?);
+        }
+    }
+    Ok(reader_group)
+}
+
+/// Makes the publisher of a connection's enabled writer groups, or nothing if the connection is
+/// disabled or has no enabled writer groups
+pub fn publisher(connection: &PubSubConnectionDataType, published_data_sets: &[PublishedDataSetDataType], source: Arc<dyn DataSetSource>, security_groups: &SecurityGroups) -> Result<Option<Publisher>, StatusCode> {
+    let writer_groups = connection.writer_groups.as_deref().unwrap_or(&[]);
+    if !connection.enabled || !writer_groups.iter().any(|g| g.enabled) {
+        return Ok(None);
+    }
+    let publisher_id = PublisherId::from_variant(&connection.publisher_id).ok_or_else(|| {
+        error!("Connection {} has an invalid publisher id {:?}", connection.name, connection.publisher_id);
+        StatusCode::BadConfigurationError
+    })?;
+    let mut publisher = Publisher::new(publisher_id, connection_address(connection)?, source);
+    for data_set in published_data_sets {
+        publisher = publisher.published_data_set(published_data_set(data_set)?);
+    }
+    for writer_group_data_type in writer_groups.iter().filter(|g| g.enabled) {
+        publisher = publisher.writer_group(writer_group(writer_group_data_type, security_groups)?);
+    }
+    Ok(Some(publisher))
+}
+
+/// Makes the subscriber of a connection's enabled reader groups, or nothing if the connection is
+/// disabled or has no enabled reader groups
+pub fn subscriber(connection: &PubSubConnectionDataType, target: Arc<dyn DataSetTarget>, security_groups: &SecurityGroups) -> Result<Option<Subscriber>, StatusCode> {
+    let reader_groups = connection.reader_groups.as_deref().unwrap_or(&[]);
+    if !connection.enabled || !reader_groups.iter().any(|g| g.enabled) {
+        return Ok(None);
+    }
+    let mut subscriber = Subscriber::new(connection_address(connection)?, target);
+    for reader_group_data_type in reader_groups.iter().filter(|g| g.enabled) {
+        subscriber = subscriber.reader_group(reader_group(reader_group_data_type, security_groups)?);
+    }
+    Ok(Some(subscriber))
+}
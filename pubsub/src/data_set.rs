@@ -0,0 +1,206 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! PublishedDataSets and where their values come from and go to. A publisher reads the fields of
+//! a DataSet from a [`DataSetSource`] and a subscriber writes the fields it receives to a
+//! [`DataSetTarget`]. Both are implemented for the server's address space so variables can be
+//! published and updated directly.
+//!
+//! [`DataSetSource`]: ./trait.DataSetSource.html
+//! [`DataSetTarget`]: ./trait.DataSetTarget.html
+
+use std::sync::{Arc, RwLock};
+
+use opcua_server::address_space::AddressSpace;
+use opcua_types::{
+    *,
+    service_types::{ConfigurationVersionDataType, DataSetMetaDataType, DataSetFieldFlags, FieldMetaData},
+    status_code::StatusCode,
+};
+
+/// Returns the current VersionTime, the seconds since the start of the year 2000, which is what
+/// configuration versions are made from
+pub fn version_time() -> u32 {
+    let seconds = (DateTime::now().ticks() - DateTime::ymd(2000, 1, 1).ticks()) / 10_000_000;
+    seconds.max(0) as u32
+}
+
+/// The type information of a field, i.e. the data type and value rank of its variable
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldType {
+    pub data_type: NodeId,
+    pub value_rank: i32,
+    pub array_dimensions: Option<Vec<u32>>,
+}
+
+impl Default for FieldType {
+    fn default() -> Self {
+        FieldType {
+            data_type: DataTypeId::BaseDataType.into(),
+            value_rank: -2,
+            array_dimensions: None,
+        }
+    }
+}
+
+/// A field of a PublishedDataSet, which is the value of a variable
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataSetField {
+    pub name: String,
+    pub node_id: NodeId,
+    /// Identifies the field in the metadata of the DataSet
+    pub data_set_field_id: Guid,
+}
+
+impl DataSetField {
+    pub fn new<T, N>(name: T, node_id: N) -> DataSetField where T: Into<String>, N: Into<NodeId> {
+        DataSetField {
+            name: name.into(),
+            node_id: node_id.into(),
+            data_set_field_id: Guid::new(),
+        }
+    }
+}
+
+/// A named list of fields that DataSetWriters publish
+#[derive(Debug, Clone, PartialEq)]
+pub struct PublishedDataSet {
+    pub name: String,
+    pub fields: Vec<DataSetField>,
+    /// Changes whenever the fields change so subscribers can tell if their metadata is current
+    pub configuration_version: ConfigurationVersionDataType,
+}
+
+impl PublishedDataSet {
+    pub fn new<T>(name: T) -> PublishedDataSet where T: Into<String> {
+        let version_time = version_time();
+        PublishedDataSet {
+            name: name.into(),
+            fields: Vec::new(),
+            configuration_version: ConfigurationVersionDataType {
+                major_version: version_time,
+                minor_version: version_time,
+            },
+        }
+    }
+
+    /// Adds a field to the DataSet
+    pub fn field<T, N>(mut self, name: T, node_id: N) -> Self where T: Into<String>, N: Into<NodeId> {
+        self.fields.push(DataSetField::new(name, node_id));
+        self
+    }
+
+    /// Returns the names of the fields in order
+    pub fn field_names(&self) -> Vec<String> {
+        self.fields.iter().map(|f| f.name.clone()).collect()
+    }
+
+    /// Describes the DataSet, with the type of each field obtained from the source
+    pub fn meta_data(&self, source: &dyn DataSetSource) -> DataSetMetaDataType {
+        self.meta_data_with(|field| source.field_type(field))
+    }
+
+    pub(crate) fn meta_data_with<F>(&self, field_type: F) -> DataSetMetaDataType where F: Fn(&DataSetField) -> FieldType {
+        let fields = self.fields.iter().map(|field| {
+            let field_type = field_type(field);
+            // Types in namespace 0 up to 25 are the built-in types, anything else is described
+            // by its data type
+            let built_in_type = match field_type.data_type.identifier {
+                Identifier::Numeric(id) if field_type.data_type.namespace == 0 && id <= 25 => id as u8,
+                _ => 0,
+            };
+            FieldMetaData {
+                name: UAString::from(field.name.as_str()),
+                description: LocalizedText::null(),
+                field_flags: DataSetFieldFlags::None,
+                built_in_type,
+                data_type: field_type.data_type,
+                value_rank: field_type.value_rank,
+                array_dimensions: field_type.array_dimensions,
+                max_string_length: 0,
+                data_set_field_id: field.data_set_field_id.clone(),
+                properties: None,
+            }
+        }).collect();
+        DataSetMetaDataType {
+            namespaces: None,
+            structure_data_types: None,
+            enum_data_types: None,
+            simple_data_types: None,
+            name: UAString::from(self.name.as_str()),
+            description: LocalizedText::null(),
+            fields: Some(fields),
+            data_set_class_id: Guid::null(),
+            configuration_version: self.configuration_version.clone(),
+        }
+    }
+}
+
+/// Supplies the values of the fields of a DataSet when a publisher sends it
+pub trait DataSetSource: Send + Sync {
+    /// Reads the current value of each field, in the same order as the fields
+    fn read(&self, fields: &[DataSetField]) -> Vec<DataValue>;
+
+    /// Returns the type of the field for the metadata of the DataSet
+    fn field_type(&self, _field: &DataSetField) -> FieldType {
+        FieldType::default()
+    }
+}
+
+/// Receives the values of a DataSet that a subscriber has received
+pub trait DataSetTarget: Send + Sync {
+    /// Writes the values to the target variables
+    fn write(&self, values: Vec<(NodeId, DataValue)>);
+}
+
+impl DataSetSource for Arc<RwLock<AddressSpace>> {
+    fn read(&self, fields: &[DataSetField]) -> Vec<DataValue> {
+        let address_space = trace_read_lock_unwrap!(self.as_ref());
+        fields.iter().map(|field| {
+            if let Some(variable) = address_space.find_variable_by_ref(&field.node_id) {
+                variable.value(TimestampsToReturn::Source, NumericRange::None, &QualifiedName::null(), 0.0)
+            } else {
+                DataValue {
+                    status: Some(StatusCode::BadNodeIdUnknown),
+                    ..Default::default()
+                }
+            }
+        }).collect()
+    }
+
+    fn field_type(&self, field: &DataSetField) -> FieldType {
+        let address_space = trace_read_lock_unwrap!(self.as_ref());
+        address_space_field_type(&address_space, field)
+    }
+}
+
+/// Returns the type of the field's variable in the address space
+pub(crate) fn address_space_field_type(address_space: &AddressSpace, field: &DataSetField) -> FieldType {
+    if let Some(variable) = address_space.find_variable_by_ref(&field.node_id) {
+        FieldType {
+            data_type: variable.data_type(),
+            value_rank: variable.value_rank(),
+            array_dimensions: variable.array_dimensions(),
+        }
+    } else {
+        FieldType::default()
+    }
+}
+
+impl DataSetTarget for Arc<RwLock<AddressSpace>> {
+    fn write(&self, values: Vec<(NodeId, DataValue)>) {
+        let now = DateTime::now();
+        let mut address_space = trace_write_lock_unwrap!(self.as_ref());
+        values.into_iter().for_each(|(node_id, data_value)| {
+            if let Some(variable) = address_space.find_variable_mut_by_ref(&node_id) {
+                let value = data_value.value.unwrap_or(Variant::Empty);
+                let status = data_value.status.unwrap_or(StatusCode::Good);
+                let source_timestamp = data_value.source_timestamp.unwrap_or_else(|| now.clone());
+                let _ = variable.set_value_direct(value, status, &now, &source_timestamp);
+            } else {
+                error!("Target variable {} for DataSet field does not exist", node_id);
+            }
+        });
+    }
+}
@@ -0,0 +1,930 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! The PubSub information model exposes the PubSub configuration of a server through the
+//! `PublishSubscribe` object of its address space. The PublishedDataSets, connections, groups,
+//! writers and readers of the configuration appear as objects with a `Status` each, and clients
+//! change the configuration by calling the `AddConnection`, `RemoveConnection`,
+//! `AddPublishedDataItems` and `AddWriterGroup` methods and the `Enable` and `Disable` methods of
+//! each `Status`. The publishers and subscribers of the connections are started and stopped to
+//! match.
+//!
+//! Each `Status` has a `State` following the PubSubState state machine. An element is
+//! `Disabled` until it is enabled, `Paused` while its parent is not `Operational`, and
+//! `Operational` otherwise. A connection whose publisher or subscriber cannot start is in `Error`.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, Mutex, RwLock, Weak};
+use std::thread;
+
+use opcua_server::{
+    address_space::{AddressSpace, types::*},
+    callbacks::AddressSpaceMethod,
+    session::Session,
+};
+use opcua_types::{
+    *,
+    service_types::{
+        Argument, CallMethodRequest, CallMethodResult, ConfigurationVersionDataType, DataSetReaderDataType,
+        DataSetWriterDataType, PubSubConfigurationDataType, PubSubConnectionDataType,
+        PubSubState, PublishedDataItemsDataType, PublishedDataSetDataType, PublishedVariableDataType,
+        ReaderGroupDataType, WriterGroupDataType,
+    },
+    status_code::StatusCode,
+};
+
+use crate::{
+    configuration::{self, SecurityGroups, TRANSPORT_PROFILE_MQTT_JSON, TRANSPORT_PROFILE_MQTT_UADP, TRANSPORT_PROFILE_UDP_UADP},
+    data_set::{self, DataSetField, DataSetSource, PublishedDataSet},
+    PubSubHandle,
+    security::KeyProvider,
+    uadp::PublisherId,
+};
+
+/// An element of the configuration which has a status
+#[derive(Debug, Clone, PartialEq)]
+enum Element {
+    PublishSubscribe,
+    Connection(String),
+    WriterGroup(String, String),
+    DataSetWriter(String, String, String),
+    ReaderGroup(String, String),
+    DataSetReader(String, String, String),
+}
+
+/// A method of the information model
+#[derive(Debug, Clone)]
+enum ModelMethodKind {
+    AddConnection,
+    RemoveConnection,
+    AddPublishedDataItems,
+    AddWriterGroup(String),
+    Enable(Element),
+    Disable(Element),
+}
+
+/// The state that is shared between the information model and the handlers of its methods
+struct Shared {
+    address_space: Arc<RwLock<AddressSpace>>,
+    source: Arc<dyn DataSetSource>,
+    target: Arc<dyn data_set::DataSetTarget>,
+    security_groups: SecurityGroups,
+    model: Mutex<Model>,
+    runtime: Mutex<Runtime>,
+}
+
+/// A connection that is running, with the configuration it was started from
+struct RunningConnection {
+    connection: PubSubConnectionDataType,
+    published_data_sets: Vec<PublishedDataSetDataType>,
+    handles: Vec<PubSubHandle>,
+}
+
+#[derive(Default)]
+struct Runtime {
+    stopped: bool,
+    connections: HashMap<String, RunningConnection>,
+}
+
+/// The configuration and the nodes that represent it
+struct Model {
+    configuration: PubSubConfigurationDataType,
+    namespace: u16,
+    /// The nodes made for the configuration, which are deleted when it is rebuilt
+    node_ids: Vec<NodeId>,
+    /// Connections whose publisher or subscriber could not be started
+    failed_connections: HashSet<String>,
+}
+
+/// Returns the state of an element from whether it is enabled and the state of its parent
+fn child_state(enabled: bool, parent_state: PubSubState) -> PubSubState {
+    if !enabled {
+        PubSubState::Disabled
+    } else if parent_state == PubSubState::Operational {
+        PubSubState::Operational
+    } else {
+        PubSubState::Paused
+    }
+}
+
+fn extension_object<T>(object_id: ObjectId, value: &T) -> Variant where T: BinaryEncoder<T> {
+    Variant::from(ExtensionObject::from_encodable(object_id, value))
+}
+
+fn argument(name: &str, data_type: DataTypeId, value_rank: i32) -> Argument {
+    Argument {
+        name: UAString::from(name),
+        data_type: data_type.into(),
+        value_rank,
+        array_dimensions: None,
+        description: LocalizedText::null(),
+    }
+}
+
+fn input_arguments(request: &CallMethodRequest, expected: usize) -> Result<&[Variant], StatusCode> {
+    let input_arguments = request.input_arguments.as_deref().unwrap_or(&[]);
+    if input_arguments.len() < expected {
+        Err(StatusCode::BadArgumentsMissing)
+    } else if input_arguments.len() > expected {
+        Err(StatusCode::BadTooManyArguments)
+    } else {
+        Ok(input_arguments)
+    }
+}
+
+/// Decodes an argument holding an extension object of the type
+fn structure_argument<T>(argument: &Variant) -> Result<T, StatusCode> where T: BinaryEncoder<T> {
+    match argument {
+        Variant::ExtensionObject(value) => value.decode_inner(&DecodingLimits::default()).map_err(|_| StatusCode::BadInvalidArgument),
+        _ => Err(StatusCode::BadInvalidArgument)
+    }
+}
+
+/// Returns the values of an array argument, where an empty argument is an empty array
+fn array_argument(argument: &Variant) -> Result<&[Variant], StatusCode> {
+    match argument {
+        Variant::Array(array) => Ok(&array.values),
+        Variant::Empty => Ok(&[]),
+        _ => Err(StatusCode::BadInvalidArgument)
+    }
+}
+
+fn method_result(output_arguments: Vec<Variant>) -> CallMethodResult {
+    CallMethodResult {
+        status_code: StatusCode::Good,
+        input_argument_results: None,
+        input_argument_diagnostic_infos: None,
+        output_arguments: if output_arguments.is_empty() { None } else { Some(output_arguments) },
+    }
+}
+
+impl Model {
+    fn connections(&self) -> &[PubSubConnectionDataType] {
+        self.configuration.connections.as_deref().unwrap_or(&[])
+    }
+
+    fn published_data_sets(&self) -> &[PublishedDataSetDataType] {
+        self.configuration.published_data_sets.as_deref().unwrap_or(&[])
+    }
+
+    fn connection_mut(&mut self, name: &str) -> Option<&mut PubSubConnectionDataType> {
+        self.configuration.connections.as_mut().and_then(|c| c.iter_mut().find(|c| c.name.as_ref() == name))
+    }
+
+    fn node_id(&self, path: &[&str]) -> NodeId {
+        NodeId::new(self.namespace, format!("PubSub.{}", path.join(".")))
+    }
+
+    fn connection_node_id(&self, connection: &str) -> NodeId {
+        self.node_id(&["Connection", connection])
+    }
+
+    fn group_node_id(&self, connection: &str, group: &str) -> NodeId {
+        self.node_id(&["Connection", connection, group])
+    }
+
+    fn data_set_node_id(&self, name: &str) -> NodeId {
+        self.node_id(&["DataSet", name])
+    }
+
+    fn element_node_id(&self, element: &Element) -> NodeId {
+        match element {
+            Element::PublishSubscribe => ObjectId::PublishSubscribe.into(),
+            Element::Connection(c) => self.connection_node_id(c),
+            Element::WriterGroup(c, g) | Element::ReaderGroup(c, g) => self.group_node_id(c, g),
+            Element::DataSetWriter(c, g, e) | Element::DataSetReader(c, g, e) => self.node_id(&["Connection", c, g, e]),
+        }
+    }
+
+    fn state_node_id(&self, element: &Element) -> NodeId {
+        if let Element::PublishSubscribe = element {
+            VariableId::PublishSubscribe_Status_State.into()
+        } else if let Identifier::String(ref id) = self.element_node_id(element).identifier {
+            NodeId::new(self.namespace, format!("{}.Status.State", id.as_ref()))
+        } else {
+            unreachable!()
+        }
+    }
+
+    /// Returns the state of every element of the configuration
+    fn states(&self) -> Vec<(Element, PubSubState)> {
+        let mut states = Vec::new();
+        let root_state = if self.configuration.enabled { PubSubState::Operational } else { PubSubState::Disabled };
+        states.push((Element::PublishSubscribe, root_state));
+        for connection in self.connections() {
+            let c = connection.name.as_ref().to_string();
+            let mut connection_state = child_state(connection.enabled, root_state);
+            if connection_state == PubSubState::Operational && self.failed_connections.contains(&c) {
+                connection_state = PubSubState::Error;
+            }
+            states.push((Element::Connection(c.clone()), connection_state));
+            for writer_group in connection.writer_groups.iter().flatten() {
+                let g = writer_group.name.as_ref().to_string();
+                let group_state = child_state(writer_group.enabled, connection_state);
+                states.push((Element::WriterGroup(c.clone(), g.clone()), group_state));
+                for writer in writer_group.data_set_writers.iter().flatten() {
+                    states.push((Element::DataSetWriter(c.clone(), g.clone(), writer.name.as_ref().to_string()), child_state(writer.enabled, group_state)));
+                }
+            }
+            for reader_group in connection.reader_groups.iter().flatten() {
+                let g = reader_group.name.as_ref().to_string();
+                let group_state = child_state(reader_group.enabled, connection_state);
+                states.push((Element::ReaderGroup(c.clone(), g.clone()), group_state));
+                for reader in reader_group.data_set_readers.iter().flatten() {
+                    states.push((Element::DataSetReader(c.clone(), g.clone(), reader.name.as_ref().to_string()), child_state(reader.enabled, group_state)));
+                }
+            }
+        }
+        states
+    }
+
+    fn state(&self, element: &Element) -> Option<PubSubState> {
+        self.states().into_iter().find(|(e, _)| e == element).map(|(_, state)| state)
+    }
+
+    /// Sets the State variable of every element
+    fn update_states(&self, address_space: &mut AddressSpace) {
+        let now = DateTime::now();
+        for (element, state) in self.states() {
+            let _ = address_space.set_variable_value(self.state_node_id(&element), state as i32, &now, &now);
+        }
+    }
+
+    /// Sets whether an element is enabled. Enabling an element that is not disabled, or disabling
+    /// one that is, is an invalid state transition.
+    fn set_enabled(&mut self, element: &Element, enabled: bool) -> Result<(), StatusCode> {
+        let state = self.state(element).ok_or(StatusCode::BadNodeIdUnknown)?;
+        if (state == PubSubState::Disabled) != enabled {
+            return Err(StatusCode::BadInvalidState);
+        }
+        fn find_group<'a>(connections: &'a mut Option<Vec<PubSubConnectionDataType>>, c: &str) -> Option<&'a mut PubSubConnectionDataType> {
+            connections.as_mut().and_then(|connections| connections.iter_mut().find(|x| x.name.as_ref() == c))
+        }
+        let configuration = &mut self.configuration;
+        let enabled_flag = match element {
+            Element::PublishSubscribe => Some(&mut configuration.enabled),
+            Element::Connection(c) => find_group(&mut configuration.connections, c).map(|c| &mut c.enabled),
+            Element::WriterGroup(c, g) => find_group(&mut configuration.connections, c)
+                .and_then(|c| c.writer_groups.as_mut())
+                .and_then(|groups| groups.iter_mut().find(|x| x.name.as_ref() == g))
+                .map(|g| &mut g.enabled),
+            Element::DataSetWriter(c, g, w) => find_group(&mut configuration.connections, c)
+                .and_then(|c| c.writer_groups.as_mut())
+                .and_then(|groups| groups.iter_mut().find(|x| x.name.as_ref() == g))
+                .and_then(|g| g.data_set_writers.as_mut())
+                .and_then(|writers| writers.iter_mut().find(|x| x.name.as_ref() == w))
+                .map(|w| &mut w.enabled),
+            Element::ReaderGroup(c, g) => find_group(&mut configuration.connections, c)
+                .and_then(|c| c.reader_groups.as_mut())
+                .and_then(|groups| groups.iter_mut().find(|x| x.name.as_ref() == g))
+                .map(|g| &mut g.enabled),
+            Element::DataSetReader(c, g, r) => find_group(&mut configuration.connections, c)
+                .and_then(|c| c.reader_groups.as_mut())
+                .and_then(|groups| groups.iter_mut().find(|x| x.name.as_ref() == g))
+                .and_then(|g| g.data_set_readers.as_mut())
+                .and_then(|readers| readers.iter_mut().find(|x| x.name.as_ref() == r))
+                .map(|r| &mut r.enabled),
+        };
+        *enabled_flag.ok_or(StatusCode::BadNodeIdUnknown)? = enabled;
+        Ok(())
+    }
+
+    /// Checks that a writer group may be added to the connection
+    fn validate_writer_group(&self, connection: &PubSubConnectionDataType, writer_group: &WriterGroupDataType, security_groups: &SecurityGroups) -> Result<(), StatusCode> {
+        if writer_group.name.is_empty() {
+            error!("Writer group has no name");
+            return Err(StatusCode::BadInvalidArgument);
+        }
+        let writer_groups = connection.writer_groups.iter().flatten();
+        let reader_groups = connection.reader_groups.iter().flatten();
+        if writer_groups.clone().map(|g| &g.name).chain(reader_groups.map(|g| &g.name)).any(|name| *name == writer_group.name) {
+            error!("Connection {} already has a group named {}", connection.name, writer_group.name);
+            return Err(StatusCode::BadBrowseNameDuplicated);
+        }
+        if writer_groups.clone().any(|g| g.writer_group_id == writer_group.writer_group_id) {
+            error!("Connection {} already has a writer group with id {}", connection.name, writer_group.writer_group_id);
+            return Err(StatusCode::BadInvalidArgument);
+        }
+        if PublisherId::from_variant(&connection.publisher_id).is_none() {
+            error!("Connection {} cannot have writer groups without a valid publisher id", connection.name);
+            return Err(StatusCode::BadInvalidArgument);
+        }
+        let mut writer_names = HashSet::new();
+        for writer in writer_group.data_set_writers.iter().flatten() {
+            if writer.name.is_empty() || !writer_names.insert(writer.name.as_ref()) {
+                error!("Writer group {} has a writer without a name or with a duplicate name", writer_group.name);
+                return Err(StatusCode::BadBrowseNameDuplicated);
+            }
+            if !self.published_data_sets().iter().any(|p| p.name == writer.data_set_name) {
+                error!("Writer {} writes data set {} which does not exist", writer.name, writer.data_set_name);
+                return Err(StatusCode::BadInvalidArgument);
+            }
+            if writer_groups.clone().flat_map(|g| g.data_set_writers.iter().flatten()).any(|w| w.data_set_writer_id == writer.data_set_writer_id) {
+                error!("Connection {} already has a writer with id {}", connection.name, writer.data_set_writer_id);
+                return Err(StatusCode::BadInvalidArgument);
+            }
+        }
+        // The group must convert into one that can be published
+        configuration::writer_group(writer_group, security_groups).map(|_| ()).map_err(|_| StatusCode::BadInvalidArgument)
+    }
+
+    /// Checks that a reader group may be added to the connection
+    fn validate_reader_group(&self, connection: &PubSubConnectionDataType, reader_group: &ReaderGroupDataType, security_groups: &SecurityGroups) -> Result<(), StatusCode> {
+        let writer_groups = connection.writer_groups.iter().flatten();
+        let reader_groups = connection.reader_groups.iter().flatten();
+        if reader_group.name.is_empty() || writer_groups.map(|g| &g.name).chain(reader_groups.map(|g| &g.name)).any(|name| *name == reader_group.name) {
+            error!("Reader group of connection {} has no name or a duplicate name", connection.name);
+            return Err(StatusCode::BadBrowseNameDuplicated);
+        }
+        let mut reader_names = HashSet::new();
+        if reader_group.data_set_readers.iter().flatten().any(|r| r.name.is_empty() || !reader_names.insert(r.name.as_ref())) {
+            error!("Reader group {} has a reader without a name or with a duplicate name", reader_group.name);
+            return Err(StatusCode::BadBrowseNameDuplicated);
+        }
+        configuration::reader_group(reader_group, security_groups).map(|_| ()).map_err(|_| StatusCode::BadInvalidArgument)
+    }
+
+    /// Adds a connection with its groups, returning the node id of the connection
+    fn add_connection(&mut self, mut connection: PubSubConnectionDataType, security_groups: &SecurityGroups) -> Result<NodeId, StatusCode> {
+        if connection.name.is_empty() {
+            error!("Connection has no name");
+            return Err(StatusCode::BadInvalidArgument);
+        }
+        if self.connections().iter().any(|c| c.name == connection.name) {
+            error!("Connection {} already exists", connection.name);
+            return Err(StatusCode::BadBrowseNameDuplicated);
+        }
+        configuration::connection_address(&connection).map_err(|_| StatusCode::BadInvalidArgument)?;
+        // Groups are added one by one so each is checked against those before it
+        let writer_groups = connection.writer_groups.take().unwrap_or_default();
+        let reader_groups = connection.reader_groups.take().unwrap_or_default();
+        for writer_group in writer_groups {
+            self.validate_writer_group(&connection, &writer_group, security_groups)?;
+            connection.writer_groups.get_or_insert_with(Vec::new).push(writer_group);
+        }
+        for reader_group in reader_groups {
+            self.validate_reader_group(&connection, &reader_group, security_groups)?;
+            connection.reader_groups.get_or_insert_with(Vec::new).push(reader_group);
+        }
+        let node_id = self.connection_node_id(connection.name.as_ref());
+        self.configuration.connections.get_or_insert_with(Vec::new).push(connection);
+        Ok(node_id)
+    }
+
+    fn remove_connection(&mut self, node_id: &NodeId) -> Result<(), StatusCode> {
+        let index = self.connections().iter()
+            .position(|c| self.connection_node_id(c.name.as_ref()) == *node_id)
+            .ok_or(StatusCode::BadNodeIdUnknown)?;
+        self.configuration.connections.as_mut().unwrap().remove(index);
+        Ok(())
+    }
+
+    fn add_writer_group(&mut self, connection_name: &str, writer_group: WriterGroupDataType, security_groups: &SecurityGroups) -> Result<NodeId, StatusCode> {
+        let connection = self.connections().iter().find(|c| c.name.as_ref() == connection_name).ok_or(StatusCode::BadNodeIdUnknown)?;
+        self.validate_writer_group(connection, &writer_group, security_groups)?;
+        let node_id = self.group_node_id(connection_name, writer_group.name.as_ref());
+        self.connection_mut(connection_name).unwrap().writer_groups.get_or_insert_with(Vec::new).push(writer_group);
+        Ok(node_id)
+    }
+
+    /// Adds a PublishedDataSet of the variables that exist, returning the node id of the DataSet,
+    /// its configuration version and the result of adding each variable
+    fn add_published_data_items(&mut self, address_space: &AddressSpace, name: UAString, field_names: Vec<UAString>, variables: Vec<PublishedVariableDataType>) -> Result<(NodeId, ConfigurationVersionDataType, Vec<StatusCode>), StatusCode> {
+        if name.is_empty() {
+            error!("Published data set has no name");
+            return Err(StatusCode::BadInvalidArgument);
+        }
+        if self.published_data_sets().iter().any(|p| p.name == name) {
+            error!("Published data set {} already exists", name);
+            return Err(StatusCode::BadBrowseNameDuplicated);
+        }
+        if field_names.len() != variables.len() {
+            error!("Published data set {} has {} field names for {} variables", name, field_names.len(), variables.len());
+            return Err(StatusCode::BadInvalidArgument);
+        }
+        let mut published_data_set = PublishedDataSet::new(name.as_ref());
+        let mut published_data = Vec::with_capacity(variables.len());
+        let results = field_names.into_iter().zip(variables).map(|(field_name, variable)| {
+            if address_space.find_variable_by_ref(&variable.published_variable).is_none() {
+                StatusCode::BadNodeIdUnknown
+            } else if field_name.is_empty() || published_data_set.fields.iter().any(|f| f.name == field_name.as_ref()) {
+                StatusCode::BadBrowseNameDuplicated
+            } else {
+                published_data_set.fields.push(DataSetField::new(field_name.as_ref(), variable.published_variable.clone()));
+                published_data.push(variable);
+                StatusCode::Good
+            }
+        }).collect();
+        let data_set_meta_data = published_data_set.meta_data_with(|field| data_set::address_space_field_type(address_space, field));
+        self.configuration.published_data_sets.get_or_insert_with(Vec::new).push(PublishedDataSetDataType {
+            name,
+            data_set_folder: None,
+            data_set_meta_data,
+            extension_fields: None,
+            data_set_source: ExtensionObject::from_encodable(ObjectId::PublishedDataItemsDataType_Encoding_DefaultBinary, &PublishedDataItemsDataType {
+                published_data: Some(published_data),
+            }),
+        });
+        Ok((self.data_set_node_id(published_data_set.name.as_str()), published_data_set.configuration_version, results))
+    }
+
+    fn add_property(&self, address_space: &mut AddressSpace, parent_id: &NodeId, name: &str, data_type: DataTypeId, value: Variant) {
+        let node_id = match parent_id.identifier {
+            Identifier::String(ref id) => NodeId::new(self.namespace, format!("{}.{}", id.as_ref(), name)),
+            _ => NodeId::next_numeric(self.namespace),
+        };
+        VariableBuilder::new(&node_id, name, name)
+            .property_of(parent_id.clone())
+            .has_type_definition(VariableTypeId::PropertyType)
+            .data_type(data_type)
+            .value(value)
+            .insert(address_space);
+    }
+
+    fn method(parent_id: &NodeId, method_id: &NodeId, name: &str, shared: &Weak<Shared>, kind: ModelMethodKind) -> MethodBuilder {
+        MethodBuilder::new(method_id, name, name)
+            .component_of(parent_id.clone())
+            .address_space_callback(Box::new(ModelMethod { shared: shared.clone(), kind }))
+    }
+
+    /// Adds the Status object of an element with its State and the Enable and Disable methods
+    fn add_status(&mut self, address_space: &mut AddressSpace, parent_id: &NodeId, element: &Element, shared: &Weak<Shared>) {
+        let id = match parent_id.identifier {
+            Identifier::String(ref id) => id.as_ref().to_string(),
+            _ => unreachable!(),
+        };
+        let status_id = NodeId::new(self.namespace, format!("{}.Status", id));
+        ObjectBuilder::new(&status_id, "Status", "Status")
+            .component_of(parent_id.clone())
+            .has_type_definition(ObjectTypeId::PubSubStatusType)
+            .insert(address_space);
+        VariableBuilder::new(&self.state_node_id(element), "State", "State")
+            .component_of(status_id.clone())
+            .has_type_definition(VariableTypeId::BaseDataVariableType)
+            .data_type(DataTypeId::PubSubState)
+            .value(PubSubState::Disabled as i32)
+            .insert(address_space);
+        self.add_status_methods(address_space, &status_id, element, shared);
+    }
+
+    fn add_status_methods(&mut self, address_space: &mut AddressSpace, status_id: &NodeId, element: &Element, shared: &Weak<Shared>) {
+        let id = match status_id.identifier {
+            Identifier::String(ref id) => id.as_ref().to_string(),
+            _ => "PubSub.Status".to_string(),
+        };
+        for (name, kind) in &[("Enable", ModelMethodKind::Enable(element.clone())), ("Disable", ModelMethodKind::Disable(element.clone()))] {
+            let method_id = NodeId::new(self.namespace, format!("{}.{}", id, name));
+            Model::method(status_id, &method_id, name, shared, kind.clone())
+                .insert(address_space);
+        }
+    }
+
+    /// Removes the nodes of the configuration and makes them again from the current configuration
+    fn rebuild(&mut self, address_space: &mut AddressSpace, shared: &Weak<Shared>) {
+        self.node_ids.drain(..).collect::<Vec<_>>().iter().for_each(|node_id| {
+            let _ = address_space.delete(node_id, true);
+        });
+
+        let published_data_sets = self.published_data_sets().to_vec();
+        for data_set in &published_data_sets {
+            let node_id = self.data_set_node_id(data_set.name.as_ref());
+            ObjectBuilder::new(&node_id, data_set.name.as_ref(), data_set.name.as_ref())
+                .component_of(ObjectId::PublishSubscribe_PublishedDataSets)
+                .has_type_definition(ObjectTypeId::PublishedDataItemsType)
+                .insert(address_space);
+            self.node_ids.push(node_id.clone());
+            self.add_property(address_space, &node_id, "ConfigurationVersion", DataTypeId::ConfigurationVersionDataType,
+                              extension_object(ObjectId::ConfigurationVersionDataType_Encoding_DefaultBinary, &data_set.data_set_meta_data.configuration_version));
+            self.add_property(address_space, &node_id, "DataSetMetaData", DataTypeId::DataSetMetaDataType,
+                              extension_object(ObjectId::DataSetMetaDataType_Encoding_DefaultBinary, &data_set.data_set_meta_data));
+            let published_data = data_set.data_set_source.decode_inner::<PublishedDataItemsDataType>(&DecodingLimits::default())
+                .ok()
+                .and_then(|p| p.published_data)
+                .unwrap_or_default()
+                .iter()
+                .map(|p| extension_object(ObjectId::PublishedVariableDataType_Encoding_DefaultBinary, p))
+                .collect::<Vec<_>>();
+            self.add_property(address_space, &node_id, "PublishedData", DataTypeId::PublishedVariableDataType, Variant::from(published_data));
+        }
+
+        let connections = self.connections().to_vec();
+        for connection in &connections {
+            self.add_connection_nodes(address_space, connection, shared);
+        }
+        self.update_states(address_space);
+    }
+
+    fn add_connection_nodes(&mut self, address_space: &mut AddressSpace, connection: &PubSubConnectionDataType, shared: &Weak<Shared>) {
+        let c = connection.name.as_ref().to_string();
+        let connection_id = self.connection_node_id(&c);
+        ObjectBuilder::new(&connection_id, c.as_str(), c.as_str())
+            .reference(ObjectId::PublishSubscribe, ReferenceTypeId::HasPubSubConnection, ReferenceDirection::Inverse)
+            .has_type_definition(ObjectTypeId::PubSubConnectionType)
+            .insert(address_space);
+        self.node_ids.push(connection_id.clone());
+        self.add_property(address_space, &connection_id, "PublisherId", DataTypeId::BaseDataType, connection.publisher_id.clone());
+        self.add_property(address_space, &connection_id, "TransportProfileUri", DataTypeId::String, Variant::from(connection.transport_profile_uri.clone()));
+        let address_id = NodeId::new(self.namespace, format!("PubSub.Connection.{}.Address", c));
+        ObjectBuilder::new(&address_id, "Address", "Address")
+            .component_of(connection_id.clone())
+            .has_type_definition(ObjectTypeId::NetworkAddressUrlType)
+            .insert(address_space);
+        let url = configuration::connection_address(connection).unwrap_or_default();
+        VariableBuilder::new(&NodeId::new(self.namespace, format!("PubSub.Connection.{}.Address.Url", c)), "Url", "Url")
+            .component_of(address_id)
+            .has_type_definition(VariableTypeId::BaseDataVariableType)
+            .data_type(DataTypeId::String)
+            .value(url)
+            .insert(address_space);
+        self.add_status(address_space, &connection_id, &Element::Connection(c.clone()), shared);
+        let method_id = NodeId::new(self.namespace, format!("PubSub.Connection.{}.AddWriterGroup", c));
+        Model::method(&connection_id, &method_id, "AddWriterGroup", shared, ModelMethodKind::AddWriterGroup(c.clone()))
+            .input_args(address_space, &[argument("Configuration", DataTypeId::WriterGroupDataType, -1)])
+            .output_args(address_space, &[argument("GroupId", DataTypeId::NodeId, -1)])
+            .insert(address_space);
+
+        for writer_group in connection.writer_groups.iter().flatten() {
+            let g = writer_group.name.as_ref().to_string();
+            let group_id = self.group_node_id(&c, &g);
+            ObjectBuilder::new(&group_id, g.as_str(), g.as_str())
+                .component_of(connection_id.clone())
+                .has_type_definition(ObjectTypeId::WriterGroupType)
+                .insert(address_space);
+            self.node_ids.push(group_id.clone());
+            self.add_property(address_space, &group_id, "WriterGroupId", DataTypeId::UInt16, Variant::from(writer_group.writer_group_id));
+            self.add_property(address_space, &group_id, "PublishingInterval", DataTypeId::Duration, Variant::from(writer_group.publishing_interval));
+            self.add_property(address_space, &group_id, "KeepAliveTime", DataTypeId::Duration, Variant::from(writer_group.keep_alive_time));
+            self.add_property(address_space, &group_id, "SecurityMode", DataTypeId::MessageSecurityMode, Variant::from(writer_group.security_mode as i32));
+            self.add_status(address_space, &group_id, &Element::WriterGroup(c.clone(), g.clone()), shared);
+            for writer in writer_group.data_set_writers.iter().flatten() {
+                self.add_writer_nodes(address_space, &c, &g, writer, shared);
+            }
+        }
+
+        for reader_group in connection.reader_groups.iter().flatten() {
+            let g = reader_group.name.as_ref().to_string();
+            let group_id = self.group_node_id(&c, &g);
+            ObjectBuilder::new(&group_id, g.as_str(), g.as_str())
+                .component_of(connection_id.clone())
+                .has_type_definition(ObjectTypeId::ReaderGroupType)
+                .insert(address_space);
+            self.node_ids.push(group_id.clone());
+            self.add_property(address_space, &group_id, "SecurityMode", DataTypeId::MessageSecurityMode, Variant::from(reader_group.security_mode as i32));
+            self.add_status(address_space, &group_id, &Element::ReaderGroup(c.clone(), g.clone()), shared);
+            for reader in reader_group.data_set_readers.iter().flatten() {
+                self.add_reader_nodes(address_space, &c, &g, reader, shared);
+            }
+        }
+    }
+
+    fn add_writer_nodes(&mut self, address_space: &mut AddressSpace, c: &str, g: &str, writer: &DataSetWriterDataType, shared: &Weak<Shared>) {
+        let element = Element::DataSetWriter(c.to_string(), g.to_string(), writer.name.as_ref().to_string());
+        let writer_id = self.element_node_id(&element);
+        let mut builder = ObjectBuilder::new(&writer_id, writer.name.as_ref(), writer.name.as_ref())
+            .reference(self.group_node_id(c, g), ReferenceTypeId::HasDataSetWriter, ReferenceDirection::Inverse)
+            .has_type_definition(ObjectTypeId::DataSetWriterType);
+        if self.published_data_sets().iter().any(|p| p.name == writer.data_set_name) {
+            builder = builder.reference(self.data_set_node_id(writer.data_set_name.as_ref()), ReferenceTypeId::DataSetToWriter, ReferenceDirection::Inverse);
+        }
+        builder.insert(address_space);
+        self.node_ids.push(writer_id.clone());
+        self.add_property(address_space, &writer_id, "DataSetWriterId", DataTypeId::UInt16, Variant::from(writer.data_set_writer_id));
+        self.add_property(address_space, &writer_id, "DataSetFieldContentMask", DataTypeId::DataSetFieldContentMask, Variant::from(writer.data_set_field_content_mask as u32));
+        self.add_property(address_space, &writer_id, "KeyFrameCount", DataTypeId::UInt32, Variant::from(writer.key_frame_count));
+        self.add_status(address_space, &writer_id, &element, shared);
+    }
+
+    fn add_reader_nodes(&mut self, address_space: &mut AddressSpace, c: &str, g: &str, reader: &DataSetReaderDataType, shared: &Weak<Shared>) {
+        let element = Element::DataSetReader(c.to_string(), g.to_string(), reader.name.as_ref().to_string());
+        let reader_id = self.element_node_id(&element);
+        ObjectBuilder::new(&reader_id, reader.name.as_ref(), reader.name.as_ref())
+            .reference(self.group_node_id(c, g), ReferenceTypeId::HasDataSetReader, ReferenceDirection::Inverse)
+            .has_type_definition(ObjectTypeId::DataSetReaderType)
+            .insert(address_space);
+        self.node_ids.push(reader_id.clone());
+        self.add_property(address_space, &reader_id, "PublisherId", DataTypeId::BaseDataType, reader.publisher_id.clone());
+        self.add_property(address_space, &reader_id, "WriterGroupId", DataTypeId::UInt16, Variant::from(reader.writer_group_id));
+        self.add_property(address_space, &reader_id, "DataSetWriterId", DataTypeId::UInt16, Variant::from(reader.data_set_writer_id));
+        self.add_property(address_space, &reader_id, "DataSetMetaData", DataTypeId::DataSetMetaDataType,
+                          extension_object(ObjectId::DataSetMetaDataType_Encoding_DefaultBinary, &reader.data_set_meta_data));
+        self.add_property(address_space, &reader_id, "MessageReceiveTimeout", DataTypeId::Duration, Variant::from(reader.message_receive_timeout));
+        self.add_status(address_space, &reader_id, &element, shared);
+    }
+
+    /// Returns each connection that should be running with the PublishedDataSets its enabled
+    /// writers write
+    fn operational_connections(&self) -> Vec<(PubSubConnectionDataType, Vec<PublishedDataSetDataType>)> {
+        if !self.configuration.enabled {
+            return Vec::new();
+        }
+        self.connections().iter().filter(|c| c.enabled).map(|connection| {
+            let data_set_names = connection.writer_groups.iter().flatten()
+                .filter(|g| g.enabled)
+                .flat_map(|g| g.data_set_writers.iter().flatten())
+                .filter(|w| w.enabled)
+                .map(|w| &w.data_set_name)
+                .collect::<HashSet<_>>();
+            let published_data_sets = self.published_data_sets().iter()
+                .filter(|p| data_set_names.contains(&p.name))
+                .cloned()
+                .collect();
+            (connection.clone(), published_data_sets)
+        }).collect()
+    }
+}
+
+impl Shared {
+    /// Starts and stops publishers and subscribers so that the operational connections, and only
+    /// those, are running with their current configuration
+    fn reconcile(&self) {
+        let mut runtime = trace_lock_unwrap!(self.runtime);
+        if runtime.stopped {
+            return;
+        }
+        let operational_connections = {
+            let model = trace_lock_unwrap!(self.model);
+            model.operational_connections()
+        };
+
+        // Stop connections that have gone or changed
+        let names = runtime.connections.keys().cloned().collect::<Vec<_>>();
+        for name in names {
+            let unchanged = operational_connections.iter().any(|(connection, published_data_sets)| {
+                let running = &runtime.connections[&name];
+                running.connection == *connection && running.published_data_sets == *published_data_sets
+            });
+            if !unchanged {
+                info!("Stopping PubSub connection {}", name);
+                let running = runtime.connections.remove(&name).unwrap();
+                running.handles.into_iter().for_each(|handle| handle.stop());
+            }
+        }
+
+        // Start the connections that are not running
+        let mut failed_connections = HashSet::new();
+        for (connection, published_data_sets) in operational_connections {
+            let name = connection.name.as_ref().to_string();
+            if runtime.connections.contains_key(&name) {
+                continue;
+            }
+            info!("Starting PubSub connection {}", name);
+            match self.start_connection(&connection, &published_data_sets) {
+                Ok(handles) => {
+                    runtime.connections.insert(name, RunningConnection { connection, published_data_sets, handles });
+                }
+                Err(status_code) => {
+                    error!("PubSub connection {} cannot start, {}", name, status_code);
+                    failed_connections.insert(name);
+                }
+            }
+        }
+
+        let mut address_space = trace_write_lock_unwrap!(self.address_space);
+        let mut model = trace_lock_unwrap!(self.model);
+        model.failed_connections = failed_connections;
+        model.update_states(&mut address_space);
+    }
+
+    fn start_connection(&self, connection: &PubSubConnectionDataType, published_data_sets: &[PublishedDataSetDataType]) -> Result<Vec<PubSubHandle>, StatusCode> {
+        let publisher = configuration::publisher(connection, published_data_sets, self.source.clone(), &self.security_groups)?;
+        let subscriber = configuration::subscriber(connection, self.target.clone(), &self.security_groups)?;
+        let mut handles = Vec::with_capacity(2);
+        if let Some(publisher) = publisher {
+            handles.push(publisher.start()?);
+        }
+        if let Some(subscriber) = subscriber {
+            match subscriber.start() {
+                Ok(handle) => handles.push(handle),
+                Err(status_code) => {
+                    handles.into_iter().for_each(|handle| handle.stop());
+                    return Err(status_code);
+                }
+            }
+        }
+        Ok(handles)
+    }
+}
+
+/// The handler of a method of the information model
+struct ModelMethod {
+    shared: Weak<Shared>,
+    kind: ModelMethodKind,
+}
+
+impl AddressSpaceMethod for ModelMethod {
+    fn call(&mut self, address_space: &mut AddressSpace, _session: &mut Session, request: &CallMethodRequest) -> Result<CallMethodResult, StatusCode> {
+        debug!("PubSub method handler for {:?}", self.kind);
+        let shared = self.shared.upgrade().ok_or_else(|| {
+            error!("PubSub method {:?} was called after the information model stopped", self.kind);
+            StatusCode::BadInvalidState
+        })?;
+        let security_groups = &shared.security_groups;
+        let mut model = trace_lock_unwrap!(shared.model);
+        let output_arguments = match self.kind {
+            ModelMethodKind::AddConnection => {
+                // AddConnection([in] PubSubConnectionDataType Configuration, [out] NodeId ConnectionId)
+                let input_arguments = input_arguments(request, 1)?;
+                let connection = structure_argument(&input_arguments[0])?;
+                vec![Variant::from(model.add_connection(connection, security_groups)?)]
+            }
+            ModelMethodKind::RemoveConnection => {
+                // RemoveConnection([in] NodeId ConnectionId)
+                let input_arguments = input_arguments(request, 1)?;
+                match input_arguments[0] {
+                    Variant::NodeId(ref connection_id) => model.remove_connection(connection_id)?,
+                    _ => return Err(StatusCode::BadInvalidArgument),
+                }
+                vec![]
+            }
+            ModelMethodKind::AddPublishedDataItems => {
+                // AddPublishedDataItems([in] String Name, [in] String[] FieldNameAliases,
+                //   [in] DataSetFieldFlags[] FieldFlags, [in] PublishedVariableDataType[] VariablesToAdd,
+                //   [out] NodeId DataSetNodeId, [out] ConfigurationVersionDataType ConfigurationVersion,
+                //   [out] StatusCode[] AddResults)
+                let input_arguments = input_arguments(request, 4)?;
+                let name = match input_arguments[0] {
+                    Variant::String(ref name) => name.clone(),
+                    _ => return Err(StatusCode::BadInvalidArgument),
+                };
+                let field_names = array_argument(&input_arguments[1])?.iter().map(|v| match v {
+                    Variant::String(v) => Ok(v.clone()),
+                    _ => Err(StatusCode::BadInvalidArgument),
+                }).collect::<Result<Vec<_>, _>>()?;
+                let variables = array_argument(&input_arguments[3])?.iter()
+                    .map(structure_argument::<PublishedVariableDataType>)
+                    .collect::<Result<Vec<_>, _>>()?;
+                let (node_id, configuration_version, results) = model.add_published_data_items(address_space, name, field_names, variables)?;
+                vec![
+                    Variant::from(node_id),
+                    extension_object(ObjectId::ConfigurationVersionDataType_Encoding_DefaultBinary, &configuration_version),
+                    Variant::from(results.into_iter().map(Variant::from).collect::<Vec<_>>()),
+                ]
+            }
+            ModelMethodKind::AddWriterGroup(ref connection) => {
+                // AddWriterGroup([in] WriterGroupDataType Configuration, [out] NodeId GroupId)
+                let input_arguments = input_arguments(request, 1)?;
+                let writer_group = structure_argument(&input_arguments[0])?;
+                vec![Variant::from(model.add_writer_group(connection, writer_group, security_groups)?)]
+            }
+            ModelMethodKind::Enable(ref element) => {
+                input_arguments(request, 0)?;
+                model.set_enabled(element, true)?;
+                vec![]
+            }
+            ModelMethodKind::Disable(ref element) => {
+                input_arguments(request, 0)?;
+                model.set_enabled(element, false)?;
+                vec![]
+            }
+        };
+        model.rebuild(address_space, &self.shared);
+        // The publishers and subscribers are changed once the address space is unlocked since
+        // those being stopped may be waiting to read it
+        let shared = self.shared.clone();
+        thread::spawn(move || {
+            if let Some(shared) = shared.upgrade() {
+                shared.reconcile();
+            }
+        });
+        Ok(method_result(output_arguments))
+    }
+}
+
+/// Exposes and runs the PubSub configuration of a server. See the [module documentation] for
+/// what appears in the address space.
+///
+/// [module documentation]: ./index.html
+pub struct PubSubInformationModel {
+    address_space: Arc<RwLock<AddressSpace>>,
+    security_groups: SecurityGroups,
+    shared: Option<Arc<Shared>>,
+}
+
+impl PubSubInformationModel {
+    /// Creates the information model for the address space. The DataSets of the configuration are
+    /// read from and written to the same address space.
+    pub fn new(address_space: Arc<RwLock<AddressSpace>>) -> PubSubInformationModel {
+        PubSubInformationModel {
+            address_space,
+            security_groups: SecurityGroups::new(),
+            shared: None,
+        }
+    }
+
+    /// Supplies the keys of a security group that groups of the configuration refer to by id
+    pub fn security_group<T>(mut self, security_group_id: T, key_provider: Arc<dyn KeyProvider>) -> Self where T: Into<String> {
+        self.security_groups.insert(security_group_id.into(), key_provider);
+        self
+    }
+
+    /// Adds the configuration to the address space and starts publishing and subscribing. Every
+    /// connection and PublishedDataSet of the configuration must be valid.
+    pub fn start(mut self, configuration: PubSubConfigurationDataType) -> Result<Self, StatusCode> {
+        let address_space = self.address_space.clone();
+        let shared = Arc::new(Shared {
+            address_space: address_space.clone(),
+            source: Arc::new(address_space.clone()),
+            target: Arc::new(address_space.clone()),
+            security_groups: self.security_groups.clone(),
+            model: Mutex::new(Model {
+                configuration: PubSubConfigurationDataType {
+                    published_data_sets: configuration.published_data_sets.clone(),
+                    connections: None,
+                    enabled: configuration.enabled,
+                },
+                namespace: 0,
+                node_ids: Vec::new(),
+                failed_connections: HashSet::new(),
+            }),
+            runtime: Mutex::new(Runtime::default()),
+        });
+        {
+            let mut address_space = trace_write_lock_unwrap!(address_space);
+            let mut model = trace_lock_unwrap!(shared.model);
+            model.namespace = address_space.default_namespace();
+            for data_set in model.published_data_sets() {
+                configuration::published_data_set(data_set)?;
+            }
+            for connection in configuration.connections.unwrap_or_default() {
+                model.add_connection(connection, &self.security_groups)?;
+            }
+            let weak = Arc::downgrade(&shared);
+            Self::add_methods(&mut address_space, &mut model, &weak);
+            model.rebuild(&mut address_space, &weak);
+        }
+        shared.reconcile();
+        self.shared = Some(shared);
+        Ok(self)
+    }
+
+    /// Adds the methods of the PublishSubscribe object
+    fn add_methods(address_space: &mut AddressSpace, model: &mut Model, shared: &Weak<Shared>) {
+        let now = DateTime::now();
+        let transport_profiles = [TRANSPORT_PROFILE_UDP_UADP, TRANSPORT_PROFILE_MQTT_UADP, TRANSPORT_PROFILE_MQTT_JSON].iter()
+            .map(|profile| Variant::from(*profile))
+            .collect::<Vec<_>>();
+        let _ = address_space.set_variable_value(VariableId::PublishSubscribe_SupportedTransportProfiles, transport_profiles, &now, &now);
+        address_space.register_address_space_method_handler(MethodId::PublishSubscribe_AddConnection,
+                                                             Box::new(ModelMethod { shared: shared.clone(), kind: ModelMethodKind::AddConnection }));
+        address_space.register_address_space_method_handler(MethodId::PublishSubscribe_RemoveConnection,
+                                                             Box::new(ModelMethod { shared: shared.clone(), kind: ModelMethodKind::RemoveConnection }));
+        let published_data_sets_id: NodeId = ObjectId::PublishSubscribe_PublishedDataSets.into();
+        let method_id = model.node_id(&["PublishedDataSets", "AddPublishedDataItems"]);
+        if !address_space.node_exists(&method_id) {
+            Model::method(&published_data_sets_id, &method_id, "AddPublishedDataItems", shared, ModelMethodKind::AddPublishedDataItems)
+                .input_args(address_space, &[
+                    argument("Name", DataTypeId::String, -1),
+                    argument("FieldNameAliases", DataTypeId::String, 1),
+                    argument("FieldFlags", DataTypeId::DataSetFieldFlags, 1),
+                    argument("VariablesToAdd", DataTypeId::PublishedVariableDataType, 1),
+                ])
+                .output_args(address_space, &[
+                    argument("DataSetNodeId", DataTypeId::NodeId, -1),
+                    argument("ConfigurationVersion", DataTypeId::ConfigurationVersionDataType, -1),
+                    argument("AddResults", DataTypeId::StatusCode, 1),
+                ])
+                .insert(address_space);
+            let status_id: NodeId = ObjectId::PublishSubscribe_Status.into();
+            model.add_status_methods(address_space, &status_id, &Element::PublishSubscribe, shared);
+        } else {
+            // The nodes are already there from an earlier model so the handlers are replaced
+            let handler = |kind| Box::new(ModelMethod { shared: shared.clone(), kind });
+            address_space.register_address_space_method_handler(method_id, handler(ModelMethodKind::AddPublishedDataItems));
+            address_space.register_address_space_method_handler(model.node_id(&["Status", "Enable"]), handler(ModelMethodKind::Enable(Element::PublishSubscribe)));
+            address_space.register_address_space_method_handler(model.node_id(&["Status", "Disable"]), handler(ModelMethodKind::Disable(Element::PublishSubscribe)));
+        }
+    }
+
+    /// Returns the current configuration including any changes made by clients
+    pub fn configuration(&self) -> PubSubConfigurationDataType {
+        match self.shared {
+            Some(ref shared) => trace_lock_unwrap!(shared.model).configuration.clone(),
+            None => PubSubConfigurationDataType {
+                published_data_sets: None,
+                connections: None,
+                enabled: false,
+            }
+        }
+    }
+
+    /// Saves the current configuration to a file, see [`save_configuration`]
+    ///
+    /// [`save_configuration`]: ../configuration/fn.save_configuration.html
+    pub fn save_configuration<P>(&self, path: P) -> Result<(), StatusCode> where P: AsRef<Path> {
+        configuration::save_configuration(path, &self.configuration())
+    }
+
+    /// Stops publishing and subscribing. The methods of the PublishSubscribe object fail
+    /// afterwards.
+    pub fn stop(self) {
+        if let Some(shared) = self.shared {
+            let mut runtime = trace_lock_unwrap!(shared.runtime);
+            runtime.stopped = true;
+            runtime.connections.drain().for_each(|(_, running)| {
+                running.handles.into_iter().for_each(|handle| handle.stop());
+            });
+        }
+    }
+}
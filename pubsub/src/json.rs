@@ -0,0 +1,319 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! Encoding and decoding of JSON NetworkMessages, OPC UA Part 14 7.2.3. A NetworkMessage holds
+//! the DataSetMessages of a WriterGroup, each with its DataSetWriterId and its fields as members
+//! of a payload object named after the fields. MetaData messages describe the fields of the
+//! DataSet of a writer so that subscribers know what they are receiving.
+//!
+//! Fields are written in the reversible form of the OPC UA JSON encoding so they decode back to
+//! the values that were sent.
+
+use std::str::FromStr;
+
+use opcua_types::{
+    *,
+    service_types::{ConfigurationVersionDataType, DataSetMetaDataType},
+    status_code::StatusCode,
+};
+
+use crate::uadp::{DataSetMessage, DataSetMessagePayload, FieldEncoding};
+
+pub const MESSAGE_TYPE_DATA: &str = "ua-data";
+pub const MESSAGE_TYPE_META_DATA: &str = "ua-metadata";
+
+const DATA_SET_MESSAGE_TYPE_KEY_FRAME: &str = "ua-keyframe";
+const DATA_SET_MESSAGE_TYPE_DELTA_FRAME: &str = "ua-deltaframe";
+const DATA_SET_MESSAGE_TYPE_KEEP_ALIVE: &str = "ua-keepalive";
+
+/// The kind of a JSON DataSetMessage
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum JsonDataSetMessageType {
+    KeyFrame,
+    DeltaFrame,
+    KeepAlive,
+}
+
+/// A DataSetMessage whose fields are named rather than numbered
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonDataSetMessage {
+    pub data_set_writer_id: u16,
+    pub sequence_number: Option<u32>,
+    pub meta_data_version: Option<ConfigurationVersionDataType>,
+    pub timestamp: Option<DateTime>,
+    pub status: Option<StatusCode>,
+    pub message_type: JsonDataSetMessageType,
+    pub field_encoding: FieldEncoding,
+    /// The fields and their names, in the order of the DataSet
+    pub payload: Vec<(String, DataValue)>,
+}
+
+impl JsonDataSetMessage {
+    /// Creates the JSON form of a DataSetMessage. The field names are those of the DataSet in
+    /// order.
+    pub fn from_data_set_message(message: &DataSetMessage, field_names: &[String]) -> JsonDataSetMessage {
+        let field_name = |i: usize| field_names.get(i).cloned().unwrap_or_else(|| format!("Field{}", i));
+        let (message_type, payload) = match message.payload {
+            DataSetMessagePayload::KeyFrame(ref fields) => {
+                (JsonDataSetMessageType::KeyFrame, fields.iter().enumerate().map(|(i, v)| (field_name(i), v.clone())).collect())
+            }
+            DataSetMessagePayload::DeltaFrame(ref fields) => {
+                (JsonDataSetMessageType::DeltaFrame, fields.iter().map(|(i, v)| (field_name(*i as usize), v.clone())).collect())
+            }
+            DataSetMessagePayload::KeepAlive => (JsonDataSetMessageType::KeepAlive, Vec::new()),
+        };
+        JsonDataSetMessage {
+            data_set_writer_id: message.data_set_writer_id,
+            sequence_number: message.sequence_number.map(|v| v as u32),
+            meta_data_version: message.configuration_version.clone(),
+            timestamp: message.timestamp.clone(),
+            status: message.status,
+            message_type,
+            field_encoding: message.field_encoding,
+            payload,
+        }
+    }
+
+    /// Turns the message back into a DataSetMessage, numbering the fields by their place in the
+    /// field names of the DataSet. Without the field names, a key frame is numbered in the order
+    /// of its fields and a delta frame cannot be numbered at all.
+    pub fn to_data_set_message(&self, field_names: Option<&[String]>) -> Result<DataSetMessage, StatusCode> {
+        let field_index = |name: &str| -> Result<u16, StatusCode> {
+            field_names
+                .and_then(|field_names| field_names.iter().position(|n| n == name))
+                .map(|i| i as u16)
+                .ok_or_else(|| {
+                    error!("Field {} from writer {} is not in the DataSet", name, self.data_set_writer_id);
+                    StatusCode::BadDecodingError
+                })
+        };
+        let payload = match self.message_type {
+            JsonDataSetMessageType::KeyFrame => {
+                let mut fields = self.payload.clone();
+                if let Some(field_names) = field_names {
+                    // Put the fields into the order of the DataSet, a missing field is empty
+                    fields = field_names.iter()
+                        .map(|name| {
+                            let value = self.payload.iter().find(|(n, _)| n == name).map(|(_, v)| v.clone()).unwrap_or_default();
+                            (name.clone(), value)
+                        })
+                        .collect();
+                }
+                DataSetMessagePayload::KeyFrame(fields.into_iter().map(|(_, v)| v).collect())
+            }
+            JsonDataSetMessageType::DeltaFrame => {
+                let fields = self.payload.iter()
+                    .map(|(name, value)| Ok((field_index(name)?, value.clone())))
+                    .collect::<Result<Vec<_>, StatusCode>>()?;
+                DataSetMessagePayload::DeltaFrame(fields)
+            }
+            JsonDataSetMessageType::KeepAlive => DataSetMessagePayload::KeepAlive,
+        };
+        Ok(DataSetMessage {
+            data_set_writer_id: self.data_set_writer_id,
+            sequence_number: self.sequence_number.map(|v| v as u16),
+            timestamp: self.timestamp.clone(),
+            status: self.status,
+            configuration_version: self.meta_data_version.clone(),
+            field_encoding: self.field_encoding,
+            payload,
+        })
+    }
+
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "DataSetWriterId", &self.data_set_writer_id, ctx);
+        if let Some(ref v) = self.sequence_number { write_json_field(&mut object, "SequenceNumber", v, ctx); }
+        if let Some(ref v) = self.meta_data_version { write_json_field(&mut object, "MetaDataVersion", v, ctx); }
+        if let Some(ref v) = self.timestamp { write_json_field(&mut object, "Timestamp", v, ctx); }
+        if let Some(ref v) = self.status { write_json_field(&mut object, "Status", v, ctx); }
+        let message_type = match self.message_type {
+            JsonDataSetMessageType::KeyFrame => DATA_SET_MESSAGE_TYPE_KEY_FRAME,
+            JsonDataSetMessageType::DeltaFrame => DATA_SET_MESSAGE_TYPE_DELTA_FRAME,
+            JsonDataSetMessageType::KeepAlive => DATA_SET_MESSAGE_TYPE_KEEP_ALIVE,
+        };
+        object.insert("MessageType".to_string(), JsonValue::from(message_type));
+        if self.message_type != JsonDataSetMessageType::KeepAlive {
+            let mut payload = JsonObject::new();
+            self.payload.iter().for_each(|(name, value)| {
+                let value = match self.field_encoding {
+                    FieldEncoding::Variant => value.value.as_ref().map(|v| v.encode_json(ctx)).unwrap_or(JsonValue::Null),
+                    FieldEncoding::DataValue => value.encode_json(ctx),
+                };
+                payload.insert(name.clone(), value);
+            });
+            object.insert("Payload".to_string(), JsonValue::Object(payload));
+        }
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<JsonDataSetMessage> {
+        let object = json_object(value)?;
+        let message_type = match read_json_field::<UAString>(object, "MessageType", decoding_limits)?.as_ref() {
+            // The message type was added in a later version of the spec so a missing one is a key frame
+            "" | DATA_SET_MESSAGE_TYPE_KEY_FRAME => JsonDataSetMessageType::KeyFrame,
+            DATA_SET_MESSAGE_TYPE_DELTA_FRAME => JsonDataSetMessageType::DeltaFrame,
+            DATA_SET_MESSAGE_TYPE_KEEP_ALIVE => JsonDataSetMessageType::KeepAlive,
+            message_type => {
+                error!("DataSetMessage type {} is not supported", message_type);
+                return Err(StatusCode::BadDecodingError);
+            }
+        };
+        // A field is a variant, which has a type, or else a data value. Null is an empty variant.
+        let mut field_encoding = FieldEncoding::Variant;
+        let payload = json_object(object.get("Payload").unwrap_or(&JsonValue::Null))?;
+        if payload.len() > decoding_limits.max_array_length {
+            error!("DataSetMessage field count {} exceeds decoding limit {}", payload.len(), decoding_limits.max_array_length);
+            return Err(StatusCode::BadDecodingError);
+        }
+        let payload = payload.iter().map(|(name, value)| {
+            let value = match value {
+                JsonValue::Object(field) if !field.contains_key("Type") => {
+                    field_encoding = FieldEncoding::DataValue;
+                    DataValue::decode_json(value, decoding_limits)?
+                }
+                _ => DataValue::value_only(Variant::decode_json(value, decoding_limits)?),
+            };
+            Ok((name.clone(), value))
+        }).collect::<EncodingResult<Vec<_>>>()?;
+        Ok(JsonDataSetMessage {
+            data_set_writer_id: read_json_field(object, "DataSetWriterId", decoding_limits)?,
+            sequence_number: read_json_optional(object, "SequenceNumber", decoding_limits)?,
+            meta_data_version: read_json_optional(object, "MetaDataVersion", decoding_limits)?,
+            timestamp: read_json_optional(object, "Timestamp", decoding_limits)?,
+            status: read_json_optional(object, "Status", decoding_limits)?,
+            message_type,
+            field_encoding,
+            payload,
+        })
+    }
+}
+
+/// A JSON NetworkMessage
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonNetworkMessage {
+    pub message_id: String,
+    pub publisher_id: Option<String>,
+    pub data_set_class_id: Option<Guid>,
+    pub messages: Vec<JsonDataSetMessage>,
+}
+
+impl JsonNetworkMessage {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        object.insert("MessageId".to_string(), JsonValue::from(self.message_id.as_str()));
+        object.insert("MessageType".to_string(), JsonValue::from(MESSAGE_TYPE_DATA));
+        if let Some(ref v) = self.publisher_id { object.insert("PublisherId".to_string(), JsonValue::from(v.as_str())); }
+        if let Some(ref v) = self.data_set_class_id { write_json_field(&mut object, "DataSetClassId", v, ctx); }
+        let messages = self.messages.iter().map(|m| m.encode_json(ctx)).collect::<Vec<_>>();
+        object.insert("Messages".to_string(), JsonValue::Array(messages));
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(object: &JsonObject, decoding_limits: &DecodingLimits) -> EncodingResult<JsonNetworkMessage> {
+        let messages = match object.get("Messages") {
+            Some(JsonValue::Array(messages)) => {
+                if messages.len() > decoding_limits.max_array_length {
+                    error!("NetworkMessage has too many DataSetMessages {}", messages.len());
+                    return Err(StatusCode::BadDecodingError);
+                }
+                messages.iter().map(|m| JsonDataSetMessage::decode_json(m, decoding_limits)).collect::<EncodingResult<Vec<_>>>()?
+            }
+            // A single DataSetMessage may be sent without a NetworkMessage around it
+            Some(message) => vec![JsonDataSetMessage::decode_json(message, decoding_limits)?],
+            None => Vec::new(),
+        };
+        Ok(JsonNetworkMessage {
+            message_id: read_json_string(object, "MessageId", decoding_limits)?.unwrap_or_default(),
+            publisher_id: read_json_string(object, "PublisherId", decoding_limits)?,
+            data_set_class_id: read_json_optional(object, "DataSetClassId", decoding_limits)?,
+            messages,
+        })
+    }
+}
+
+/// A MetaData message which describes the DataSet of a writer
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonMetaDataMessage {
+    pub message_id: String,
+    pub publisher_id: Option<String>,
+    pub data_set_writer_id: u16,
+    pub meta_data: DataSetMetaDataType,
+}
+
+impl JsonMetaDataMessage {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        object.insert("MessageId".to_string(), JsonValue::from(self.message_id.as_str()));
+        object.insert("MessageType".to_string(), JsonValue::from(MESSAGE_TYPE_META_DATA));
+        if let Some(ref v) = self.publisher_id { object.insert("PublisherId".to_string(), JsonValue::from(v.as_str())); }
+        write_json_field(&mut object, "DataSetWriterId", &self.data_set_writer_id, ctx);
+        write_json_field(&mut object, "MetaData", &self.meta_data, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(object: &JsonObject, decoding_limits: &DecodingLimits) -> EncodingResult<JsonMetaDataMessage> {
+        Ok(JsonMetaDataMessage {
+            message_id: read_json_string(object, "MessageId", decoding_limits)?.unwrap_or_default(),
+            publisher_id: read_json_string(object, "PublisherId", decoding_limits)?,
+            data_set_writer_id: read_json_field(object, "DataSetWriterId", decoding_limits)?,
+            meta_data: read_json_field(object, "MetaData", decoding_limits)?,
+        })
+    }
+}
+
+/// A JSON message as it is sent to a queue
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonMessage {
+    Data(JsonNetworkMessage),
+    MetaData(JsonMetaDataMessage),
+}
+
+impl JsonMessage {
+    /// Encodes the message as a JSON string
+    pub fn encode(&self) -> String {
+        let ctx = JsonContext::reversible();
+        match self {
+            JsonMessage::Data(message) => message.encode_json(&ctx),
+            JsonMessage::MetaData(message) => message.encode_json(&ctx),
+        }.to_string()
+    }
+
+    /// Decodes a message from its JSON string
+    pub fn decode(data: &[u8], decoding_limits: &DecodingLimits) -> EncodingResult<JsonMessage> {
+        let value = std::str::from_utf8(data).ok()
+            .and_then(|data| JsonValue::from_str(data).ok())
+            .ok_or_else(|| {
+                error!("NetworkMessage is not valid JSON");
+                StatusCode::BadDecodingError
+            })?;
+        let object = json_object(&value)?;
+        match read_json_string(object, "MessageType", decoding_limits)?.as_deref() {
+            Some(MESSAGE_TYPE_DATA) => Ok(JsonMessage::Data(JsonNetworkMessage::decode_json(object, decoding_limits)?)),
+            Some(MESSAGE_TYPE_META_DATA) => Ok(JsonMessage::MetaData(JsonMetaDataMessage::decode_json(object, decoding_limits)?)),
+            message_type => {
+                error!("NetworkMessage type {:?} is not supported", message_type);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+
+    /// Test if the data looks like a JSON message rather than a UADP one. A UADP message begins
+    /// with its version in the lower bits, which is never a brace.
+    pub fn is_json(data: &[u8]) -> bool {
+        data.iter().find(|b| !b.is_ascii_whitespace()) == Some(&b'{')
+    }
+}
+
+fn read_json_optional<T>(object: &JsonObject, name: &str, decoding_limits: &DecodingLimits) -> EncodingResult<Option<T>> where T: JsonEncoder<T> {
+    match object.get(name) {
+        None | Some(JsonValue::Null) => Ok(None),
+        Some(value) => T::decode_json(value, decoding_limits).map(Some),
+    }
+}
+
+fn read_json_string(object: &JsonObject, name: &str, decoding_limits: &DecodingLimits) -> EncodingResult<Option<String>> {
+    let value: UAString = read_json_field(object, name, decoding_limits)?;
+    Ok(value.value().clone())
+}
@@ -0,0 +1,91 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! The OPC UA PubSub module implements publishers and subscribers of OPC UA Part 14. A
+//! [`Publisher`] sends the values of variables as DataSets in UADP NetworkMessages over UDP
+//! unicast or multicast, or in UADP or JSON NetworkMessages to an MQTT broker, and a
+//! [`Subscriber`] receives them and writes the values into variables. UADP messages may be signed
+//! and encrypted with keys from a Security Key Service. A [`PubSubInformationModel`] exposes a
+//! PubSub configuration in a server's address space so clients can change it.
+//!
+//! [`PubSubInformationModel`]: ./information_model/struct.PubSubInformationModel.html
+//! [`Publisher`]: ./publisher/struct.Publisher.html
+//! [`Subscriber`]: ./subscriber/struct.Subscriber.html
+//!
+//! # Example
+//!
+//! This publishes a variable of a server's address space to a multicast group every 10ms.
+//!
+//! ```no_run
+//! use std::sync::Arc;
+//! use opcua_pubsub::prelude::*;
+//! use opcua_server::prelude::*;
+//!
+//! fn main() {
+//!     let server: Server = ServerBuilder::new_sample().server().unwrap();
+//!     let address_space = server.address_space();
+//!     let publisher = Publisher::new(PublisherId::UInt16(1), "opc.udp://239.0.0.1:4840", Arc::new(address_space))
+//!         .published_data_set(PublishedDataSet::new("Data").field("Status", NodeId::new(2, "status")))
+//!         .writer_group(WriterGroup::new("Group", 1)
+//!             .publishing_interval(10.0)
+//!             .writer(DataSetWriter::new("Writer", 1, "Data")))
+//!         .start()
+//!         .unwrap();
+//!     server.run();
+//!     publisher.stop();
+//! }
+//! ```
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate opcua_core;
+
+use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
+use std::thread::JoinHandle;
+
+pub mod configuration;
+pub mod data_set;
+pub mod information_model;
+pub mod json;
+pub mod publisher;
+pub mod security;
+pub mod subscriber;
+pub mod transport;
+pub mod uadp;
+
+/// Returned by a started publisher or subscriber and used to stop it
+pub struct PubSubHandle {
+    abort: Arc<AtomicBool>,
+    threads: Vec<JoinHandle<()>>,
+}
+
+impl PubSubHandle {
+    pub(crate) fn new(abort: Arc<AtomicBool>, threads: Vec<JoinHandle<()>>) -> PubSubHandle {
+        PubSubHandle { abort, threads }
+    }
+
+    /// Stops publishing or subscribing and waits for the threads to finish
+    pub fn stop(self) {
+        self.abort.store(true, Ordering::Relaxed);
+        for thread in self.threads {
+            let _ = thread.join();
+        }
+    }
+}
+
+pub mod prelude {
+    pub use crate::{
+        configuration::*,
+        data_set::*,
+        information_model::PubSubInformationModel,
+        publisher::*,
+        PubSubHandle,
+        security::*,
+        subscriber::*,
+        uadp::{FieldEncoding, PublisherId},
+    };
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,447 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! The publisher sends the PublishedDataSets of its WriterGroups in NetworkMessages. Each
+//! WriterGroup runs on its own thread which wakes at the publishing interval, reads the DataSets
+//! of its writers and sends them in one message.
+//!
+//! Messages are encoded as UADP or JSON. Over a broker transport, each WriterGroup sends to a
+//! queue and the metadata of each DataSetWriter is sent to a queue of its own where the broker
+//! retains it.
+
+use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use opcua_types::{*, service_types::MessageSecurityMode, status_code::StatusCode};
+
+use crate::{
+    data_set::{DataSetSource, PublishedDataSet},
+    json::{JsonDataSetMessage, JsonMessage, JsonMetaDataMessage, JsonNetworkMessage},
+    PubSubHandle,
+    security::KeyProvider,
+    transport::{self, NetworkTransport},
+    uadp::{DataSetMessage, DataSetMessagePayload, FieldEncoding, GroupHeader, NetworkMessage, PublisherId, SecurityHeader},
+};
+
+/// Writes one PublishedDataSet into the messages of its WriterGroup
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataSetWriter {
+    pub name: String,
+    pub data_set_writer_id: u16,
+    /// Name of the PublishedDataSet that is written
+    pub data_set_name: String,
+    /// Number of messages until the next key frame. A value of 1 sends every message as a key
+    /// frame, otherwise the messages in between are delta frames holding the changed fields.
+    pub key_frame_count: u32,
+    pub field_encoding: FieldEncoding,
+    /// Queue that the metadata of the DataSet is sent to over a broker transport. If not set, it
+    /// is `opcua/json/metadata/<publisher id>/<writer group name>/<writer name>`.
+    pub meta_data_queue_name: Option<String>,
+}
+
+impl DataSetWriter {
+    pub fn new<T, D>(name: T, data_set_writer_id: u16, data_set_name: D) -> DataSetWriter where T: Into<String>, D: Into<String> {
+        DataSetWriter {
+            name: name.into(),
+            data_set_writer_id,
+            data_set_name: data_set_name.into(),
+            key_frame_count: 1,
+            field_encoding: FieldEncoding::Variant,
+            meta_data_queue_name: None,
+        }
+    }
+
+    pub fn key_frame_count(mut self, key_frame_count: u32) -> Self {
+        self.key_frame_count = key_frame_count;
+        self
+    }
+
+    pub fn field_encoding(mut self, field_encoding: FieldEncoding) -> Self {
+        self.field_encoding = field_encoding;
+        self
+    }
+
+    pub fn meta_data_queue_name<T>(mut self, meta_data_queue_name: T) -> Self where T: Into<String> {
+        self.meta_data_queue_name = Some(meta_data_queue_name.into());
+        self
+    }
+}
+
+/// How the NetworkMessages of a WriterGroup are encoded
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MessageEncoding {
+    /// The UADP binary encoding
+    Uadp,
+    /// The JSON encoding, which is only sent over broker transports
+    Json,
+}
+
+impl MessageEncoding {
+    /// The name of the encoding in default queue names
+    fn queue_name_part(&self) -> &'static str {
+        match self {
+            MessageEncoding::Uadp => "uadp",
+            MessageEncoding::Json => "json",
+        }
+    }
+}
+
+/// A group of DataSetWriters whose DataSets are sent together in one NetworkMessage at the
+/// publishing interval
+#[derive(Clone)]
+pub struct WriterGroup {
+    pub name: String,
+    pub writer_group_id: u16,
+    /// Publishing interval in milliseconds
+    pub publishing_interval: f64,
+    /// Time in milliseconds after which a writer whose DataSet has not changed sends a keep alive
+    pub keep_alive_time: f64,
+    pub security_mode: MessageSecurityMode,
+    /// Supplies the keys that messages are secured with when the security mode is not None
+    pub security_key_provider: Option<Arc<dyn KeyProvider>>,
+    pub message_encoding: MessageEncoding,
+    /// Queue that messages are sent to over a broker transport. If not set, it is
+    /// `opcua/<encoding>/data/<publisher id>/<writer group name>`.
+    pub queue_name: Option<String>,
+    pub writers: Vec<DataSetWriter>,
+}
+
+impl WriterGroup {
+    pub fn new<T>(name: T, writer_group_id: u16) -> WriterGroup where T: Into<String> {
+        WriterGroup {
+            name: name.into(),
+            writer_group_id,
+            publishing_interval: 1000.0,
+            keep_alive_time: 5000.0,
+            security_mode: MessageSecurityMode::None,
+            security_key_provider: None,
+            message_encoding: MessageEncoding::Uadp,
+            queue_name: None,
+            writers: Vec::new(),
+        }
+    }
+
+    pub fn publishing_interval(mut self, publishing_interval: f64) -> Self {
+        self.publishing_interval = publishing_interval;
+        self
+    }
+
+    pub fn keep_alive_time(mut self, keep_alive_time: f64) -> Self {
+        self.keep_alive_time = keep_alive_time;
+        self
+    }
+
+    /// Secures the messages of the group with keys from the provider
+    pub fn security(mut self, security_mode: MessageSecurityMode, security_key_provider: Arc<dyn KeyProvider>) -> Self {
+        self.security_mode = security_mode;
+        self.security_key_provider = Some(security_key_provider);
+        self
+    }
+
+    pub fn message_encoding(mut self, message_encoding: MessageEncoding) -> Self {
+        self.message_encoding = message_encoding;
+        self
+    }
+
+    pub fn queue_name<T>(mut self, queue_name: T) -> Self where T: Into<String> {
+        self.queue_name = Some(queue_name.into());
+        self
+    }
+
+    pub fn writer(mut self, writer: DataSetWriter) -> Self {
+        self.writers.push(writer);
+        self
+    }
+}
+
+/// Publishes DataSets read from a source to the address of a PubSubConnection
+pub struct Publisher {
+    publisher_id: PublisherId,
+    address: String,
+    source: Arc<dyn DataSetSource>,
+    published_data_sets: Vec<PublishedDataSet>,
+    writer_groups: Vec<WriterGroup>,
+}
+
+impl Publisher {
+    /// Creates a publisher which sends to the address url, e.g. `opc.udp://239.0.0.1:4840` or
+    /// `mqtt://broker:1883`, and reads DataSets from the source, e.g. a server's address space
+    pub fn new<T>(publisher_id: PublisherId, address: T, source: Arc<dyn DataSetSource>) -> Publisher where T: Into<String> {
+        Publisher {
+            publisher_id,
+            address: address.into(),
+            source,
+            published_data_sets: Vec::new(),
+            writer_groups: Vec::new(),
+        }
+    }
+
+    pub fn published_data_set(mut self, published_data_set: PublishedDataSet) -> Self {
+        self.published_data_sets.push(published_data_set);
+        self
+    }
+
+    pub fn writer_group(mut self, writer_group: WriterGroup) -> Self {
+        self.writer_groups.push(writer_group);
+        self
+    }
+
+    /// Starts publishing. Each WriterGroup publishes from its own thread until the returned
+    /// handle is stopped.
+    pub fn start(self) -> Result<PubSubHandle, StatusCode> {
+        let Publisher { publisher_id, address, source, published_data_sets, writer_groups } = self;
+        let abort = Arc::new(AtomicBool::new(false));
+        let mut group_tasks = Vec::with_capacity(writer_groups.len());
+        for writer_group in writer_groups {
+            if writer_group.publishing_interval <= 0.0 {
+                error!("Writer group {} has an invalid publishing interval {}", writer_group.name, writer_group.publishing_interval);
+                return Err(StatusCode::BadConfigurationError);
+            }
+            if writer_group.security_mode != MessageSecurityMode::None && writer_group.security_key_provider.is_none() {
+                error!("Writer group {} is secured but has no security key provider", writer_group.name);
+                return Err(StatusCode::BadConfigurationError);
+            }
+            if writer_group.message_encoding == MessageEncoding::Json {
+                if !transport::is_broker_url(&address) {
+                    error!("Writer group {} uses the JSON encoding which needs a broker transport", writer_group.name);
+                    return Err(StatusCode::BadConfigurationError);
+                }
+                if writer_group.security_mode != MessageSecurityMode::None {
+                    error!("Writer group {} uses the JSON encoding which cannot be signed or encrypted", writer_group.name);
+                    return Err(StatusCode::BadConfigurationError);
+                }
+            }
+            let writers = writer_group.writers.iter().map(|writer| {
+                let published_data_set = published_data_sets.iter()
+                    .find(|p| p.name == writer.data_set_name)
+                    .cloned()
+                    .ok_or_else(|| {
+                        error!("Writer {} writes data set {} which does not exist", writer.name, writer.data_set_name);
+                        StatusCode::BadConfigurationError
+                    })?;
+                Ok(WriterState::new(writer.clone(), published_data_set))
+            }).collect::<Result<Vec<_>, StatusCode>>()?;
+            let transport = transport::publisher_transport(&address)?;
+            let queue_name = writer_group.queue_name.clone().unwrap_or_else(|| {
+                format!("opcua/{}/data/{}/{}", writer_group.message_encoding.queue_name_part(), publisher_id, writer_group.name)
+            });
+            group_tasks.push(WriterGroupTask {
+                publisher_id: publisher_id.clone(),
+                source: source.clone(),
+                broker: transport::is_broker_url(&address),
+                queue_name,
+                writer_group,
+                writers,
+                transport,
+                sequence_number: 0,
+                nonce_sequence_number: 0,
+            });
+        }
+
+        let threads = group_tasks.into_iter().map(|mut task| {
+            let abort = abort.clone();
+            thread::spawn(move || task.run(abort))
+        }).collect();
+        Ok(PubSubHandle::new(abort, threads))
+    }
+}
+
+/// What a writer last sent
+struct WriterState {
+    writer: DataSetWriter,
+    published_data_set: PublishedDataSet,
+    field_names: Vec<String>,
+    sequence_number: u16,
+    /// Messages sent since the last key frame
+    messages_since_key_frame: u32,
+    last_values: Vec<DataValue>,
+    last_sent: Option<Instant>,
+}
+
+impl WriterState {
+    fn new(writer: DataSetWriter, published_data_set: PublishedDataSet) -> WriterState {
+        WriterState {
+            field_names: published_data_set.field_names(),
+            writer,
+            published_data_set,
+            sequence_number: 0,
+            messages_since_key_frame: 0,
+            last_values: Vec::new(),
+            last_sent: None,
+        }
+    }
+
+    /// Makes the writer's next message, which is a key frame, a delta frame of the changed fields
+    /// or a keep alive. Returns nothing if nothing changed and it is not time for a keep alive.
+    fn next_message(&mut self, values: Vec<DataValue>, keep_alive_time: Duration, now: Instant) -> Option<DataSetMessage> {
+        let key_frame = self.messages_since_key_frame == 0 || self.last_values.len() != values.len();
+        let payload = if key_frame {
+            DataSetMessagePayload::KeyFrame(values.clone())
+        } else {
+            let changes = values.iter().zip(self.last_values.iter()).enumerate()
+                .filter(|(_, (value, last_value))| value.value != last_value.value || value.status != last_value.status)
+                .map(|(i, (value, _))| (i as u16, value.clone()))
+                .collect::<Vec<_>>();
+            if !changes.is_empty() {
+                DataSetMessagePayload::DeltaFrame(changes)
+            } else if self.last_sent.map(|last_sent| now.duration_since(last_sent) >= keep_alive_time).unwrap_or(true) {
+                DataSetMessagePayload::KeepAlive
+            } else {
+                return None;
+            }
+        };
+        // A keep alive carries the sequence number of the next message without using it up
+        let sequence_number = self.sequence_number.wrapping_add(1);
+        if let DataSetMessagePayload::KeepAlive = payload {} else {
+            self.messages_since_key_frame += 1;
+            if self.messages_since_key_frame >= self.writer.key_frame_count {
+                self.messages_since_key_frame = 0;
+            }
+            self.last_values = values;
+            self.sequence_number = sequence_number;
+        }
+        self.last_sent = Some(now);
+        Some(DataSetMessage {
+            data_set_writer_id: self.writer.data_set_writer_id,
+            sequence_number: Some(sequence_number),
+            timestamp: Some(DateTime::now()),
+            status: None,
+            configuration_version: Some(self.published_data_set.configuration_version.clone()),
+            field_encoding: self.writer.field_encoding,
+            payload,
+        })
+    }
+}
+
+/// Publishes a WriterGroup
+struct WriterGroupTask {
+    publisher_id: PublisherId,
+    source: Arc<dyn DataSetSource>,
+    /// True if the transport sends to queues of a broker
+    broker: bool,
+    queue_name: String,
+    writer_group: WriterGroup,
+    writers: Vec<WriterState>,
+    transport: Box<dyn NetworkTransport>,
+    sequence_number: u16,
+    nonce_sequence_number: u32,
+}
+
+impl WriterGroupTask {
+    fn run(&mut self, abort: Arc<AtomicBool>) {
+        let publishing_interval = Duration::from_micros((self.writer_group.publishing_interval * 1000.0) as u64);
+        let keep_alive_time = Duration::from_micros((self.writer_group.keep_alive_time.max(0.0) * 1000.0) as u64);
+        info!("Writer group {} is publishing every {} ms", self.writer_group.name, self.writer_group.publishing_interval);
+        if self.broker {
+            self.publish_meta_data();
+        }
+        let mut next_publish = Instant::now();
+        while !abort.load(Ordering::Relaxed) {
+            let now = Instant::now();
+            if next_publish > now {
+                thread::sleep(next_publish - now);
+            }
+            // The next time is from the last so the interval does not drift, unless publishing
+            // has fallen behind in which case the missed cycles are skipped
+            next_publish += publishing_interval;
+            let now = Instant::now();
+            if next_publish < now {
+                next_publish = now;
+            }
+            self.publish(keep_alive_time, now);
+        }
+        info!("Writer group {} has stopped publishing", self.writer_group.name);
+    }
+
+    /// Sends the metadata of each writer to its queue, where the broker retains it for
+    /// subscribers that arrive later
+    fn publish_meta_data(&mut self) {
+        for writer in &self.writers {
+            let message = JsonMessage::MetaData(JsonMetaDataMessage {
+                message_id: Guid::new().to_string(),
+                publisher_id: Some(self.publisher_id.to_string()),
+                data_set_writer_id: writer.writer.data_set_writer_id,
+                meta_data: writer.published_data_set.meta_data(self.source.as_ref()),
+            });
+            let queue_name = writer.writer.meta_data_queue_name.clone().unwrap_or_else(|| {
+                format!("opcua/json/metadata/{}/{}/{}", self.publisher_id, self.writer_group.name, writer.writer.name)
+            });
+            if let Err(status_code) = self.transport.send(message.encode().as_bytes(), Some(&queue_name), true) {
+                error!("Writer {} cannot send its metadata, {}", writer.writer.name, status_code);
+            }
+        }
+    }
+
+    fn publish(&mut self, keep_alive_time: Duration, now: Instant) {
+        let source = &self.source;
+        let messages = self.writers.iter_mut().filter_map(|writer| {
+            let values = source.read(&writer.published_data_set.fields);
+            writer.next_message(values, keep_alive_time, now)
+        }).collect::<Vec<_>>();
+        if messages.is_empty() {
+            return;
+        }
+        let result = match self.writer_group.message_encoding {
+            MessageEncoding::Uadp => self.publish_uadp(messages),
+            MessageEncoding::Json => self.publish_json(messages),
+        };
+        if let Err(status_code) = result {
+            error!("Writer group {} cannot send its message, {}", self.writer_group.name, status_code);
+        }
+    }
+
+    fn publish_json(&mut self, messages: Vec<DataSetMessage>) -> Result<(), StatusCode> {
+        let writers = &self.writers;
+        let messages = messages.iter().map(|message| {
+            let field_names = writers.iter()
+                .find(|w| w.writer.data_set_writer_id == message.data_set_writer_id)
+                .map(|w| &w.field_names[..])
+                .unwrap_or(&[]);
+            JsonDataSetMessage::from_data_set_message(message, field_names)
+        }).collect();
+        let message = JsonMessage::Data(JsonNetworkMessage {
+            message_id: Guid::new().to_string(),
+            publisher_id: Some(self.publisher_id.to_string()),
+            data_set_class_id: None,
+            messages,
+        });
+        self.transport.send(message.encode().as_bytes(), Some(&self.queue_name), false)
+    }
+
+    fn publish_uadp(&mut self, messages: Vec<DataSetMessage>) -> Result<(), StatusCode> {
+
+        self.sequence_number = self.sequence_number.wrapping_add(1);
+        let keys = if self.writer_group.security_mode != MessageSecurityMode::None {
+            match self.writer_group.security_key_provider.as_ref().and_then(|p| p.current_keys()) {
+                Some(keys) => Some(keys),
+                None => {
+                    error!("Writer group {} has no security keys to secure its message", self.writer_group.name);
+                    return Err(StatusCode::BadSecurityChecksFailed);
+                }
+            }
+        } else {
+            None
+        };
+        let security_header = keys.as_ref().map(|keys| {
+            self.nonce_sequence_number = self.nonce_sequence_number.wrapping_add(1);
+            SecurityHeader::new(self.writer_group.security_mode, keys, self.nonce_sequence_number)
+        });
+        let message = NetworkMessage {
+            publisher_id: Some(self.publisher_id.clone()),
+            data_set_class_id: None,
+            group_header: Some(GroupHeader {
+                writer_group_id: Some(self.writer_group.writer_group_id),
+                group_version: None,
+                network_message_number: Some(1),
+                sequence_number: Some(self.sequence_number),
+            }),
+            timestamp: None,
+            security_header,
+            messages,
+        };
+        let data = message.encode(keys.as_ref())?;
+        self.transport.send(&data, Some(&self.queue_name), false)
+    }
+}
@@ -0,0 +1,218 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! Message security for PubSub, OPC UA Part 14 8. NetworkMessages are signed with HMAC-SHA256 and
+//! encrypted with AES in counter mode, using keys that a Security Key Service (SKS) hands out to
+//! the members of a security group. Keys are identified by a token id which is sent in each
+//! secured NetworkMessage so that readers know which key to use.
+
+use std::sync::RwLock;
+
+use opcua_crypto::{aes_ctr, hmac_sha256, random, verify_hmac_sha256, SHA256_SIZE};
+use opcua_types::{ByteString, status_code::StatusCode};
+
+/// The PubSub security policies
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PubSubSecurityPolicy {
+    /// PubSub-Aes128-CTR
+    Aes128Ctr,
+    /// PubSub-Aes256-CTR
+    Aes256Ctr,
+}
+
+impl PubSubSecurityPolicy {
+    pub const AES128_CTR_URI: &'static str = "http://opcfoundation.org/UA/SecurityPolicy#PubSub-Aes128-CTR";
+    pub const AES256_CTR_URI: &'static str = "http://opcfoundation.org/UA/SecurityPolicy#PubSub-Aes256-CTR";
+
+    /// Length of the nonce that is sent in each message
+    pub const MESSAGE_NONCE_LENGTH: usize = 8;
+    /// Length of the nonce that is part of the key data
+    pub const KEY_NONCE_LENGTH: usize = 4;
+
+    pub fn to_uri(&self) -> &'static str {
+        match self {
+            PubSubSecurityPolicy::Aes128Ctr => Self::AES128_CTR_URI,
+            PubSubSecurityPolicy::Aes256Ctr => Self::AES256_CTR_URI,
+        }
+    }
+
+    pub fn from_uri(uri: &str) -> Option<PubSubSecurityPolicy> {
+        match uri {
+            Self::AES128_CTR_URI => Some(PubSubSecurityPolicy::Aes128Ctr),
+            Self::AES256_CTR_URI => Some(PubSubSecurityPolicy::Aes256Ctr),
+            _ => None
+        }
+    }
+
+    pub fn signing_key_length(&self) -> usize {
+        SHA256_SIZE
+    }
+
+    pub fn encrypting_key_length(&self) -> usize {
+        match self {
+            PubSubSecurityPolicy::Aes128Ctr => 16,
+            PubSubSecurityPolicy::Aes256Ctr => 32,
+        }
+    }
+
+    /// Length of the key data that an SKS returns for each token, i.e. the signing key, the
+    /// encrypting key and the key nonce one after another
+    pub fn key_data_length(&self) -> usize {
+        self.signing_key_length() + self.encrypting_key_length() + Self::KEY_NONCE_LENGTH
+    }
+
+    pub fn signature_size(&self) -> usize {
+        SHA256_SIZE
+    }
+}
+
+/// The keys for one security token of a security group
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecurityKeys {
+    pub token_id: u32,
+    pub signing_key: Vec<u8>,
+    pub encrypting_key: Vec<u8>,
+    pub key_nonce: Vec<u8>,
+}
+
+impl SecurityKeys {
+    /// Splits the key data for a token, as returned by `GetSecurityKeys`, into its keys
+    pub fn from_key_data(security_policy: PubSubSecurityPolicy, token_id: u32, key_data: &[u8]) -> Result<SecurityKeys, StatusCode> {
+        if key_data.len() != security_policy.key_data_length() {
+            error!("Key data for token {} is {} bytes but {} bytes were expected", token_id, key_data.len(), security_policy.key_data_length());
+            return Err(StatusCode::BadSecurityChecksFailed);
+        }
+        let (signing_key, rest) = key_data.split_at(security_policy.signing_key_length());
+        let (encrypting_key, key_nonce) = rest.split_at(security_policy.encrypting_key_length());
+        Ok(SecurityKeys {
+            token_id,
+            signing_key: signing_key.to_vec(),
+            encrypting_key: encrypting_key.to_vec(),
+            key_nonce: key_nonce.to_vec(),
+        })
+    }
+
+    /// Creates random keys for the token, e.g. for a security key service to hand out
+    pub fn generate(security_policy: PubSubSecurityPolicy, token_id: u32) -> SecurityKeys {
+        let mut key_data = vec![0u8; security_policy.key_data_length()];
+        random::bytes(&mut key_data);
+        Self::from_key_data(security_policy, token_id, &key_data).unwrap()
+    }
+
+    /// Returns the keys as the key data that `GetSecurityKeys` returns
+    pub fn key_data(&self) -> ByteString {
+        let mut key_data = Vec::with_capacity(self.signing_key.len() + self.encrypting_key.len() + self.key_nonce.len());
+        key_data.extend_from_slice(&self.signing_key);
+        key_data.extend_from_slice(&self.encrypting_key);
+        key_data.extend_from_slice(&self.key_nonce);
+        ByteString::from(key_data)
+    }
+
+    pub fn sign(&self, data: &[u8]) -> Result<Vec<u8>, StatusCode> {
+        let mut signature = vec![0u8; SHA256_SIZE];
+        hmac_sha256(&self.signing_key, data, &mut signature)?;
+        Ok(signature)
+    }
+
+    pub fn verify(&self, data: &[u8], signature: &[u8]) -> bool {
+        verify_hmac_sha256(&self.signing_key, data, signature)
+    }
+
+    /// Encrypts or decrypts the data. The counter block is the key nonce, the message nonce and
+    /// a block counter that starts at 1.
+    pub fn crypt(&self, message_nonce: &[u8], data: &[u8]) -> Result<Vec<u8>, StatusCode> {
+        if message_nonce.len() != PubSubSecurityPolicy::MESSAGE_NONCE_LENGTH {
+            error!("Message nonce is {} bytes", message_nonce.len());
+            return Err(StatusCode::BadSecurityChecksFailed);
+        }
+        let mut counter_block = Vec::with_capacity(16);
+        counter_block.extend_from_slice(&self.key_nonce);
+        counter_block.extend_from_slice(message_nonce);
+        counter_block.extend_from_slice(&1u32.to_be_bytes());
+        aes_ctr(&self.encrypting_key, &counter_block, data)
+    }
+}
+
+/// Supplies the keys of a security group. Publishers secure messages with the current keys and
+/// subscribers look up the keys of the token named in each message.
+pub trait KeyProvider: Send + Sync {
+    fn security_policy(&self) -> PubSubSecurityPolicy;
+
+    /// The keys that messages are secured with now
+    fn current_keys(&self) -> Option<SecurityKeys>;
+
+    /// The keys of the token, if they are still known
+    fn keys(&self, token_id: u32) -> Option<SecurityKeys>;
+}
+
+/// Holds the keys of a security group as they are obtained from a Security Key Service. The keys
+/// returned by a `GetSecurityKeys` call are set with [`set_keys`] and the first of them is current.
+/// The current token moves on to the next key with [`next_token`], which the caller does when the
+/// SKS says the key lifetime has passed.
+///
+/// [`set_keys`]: #method.set_keys
+/// [`next_token`]: #method.next_token
+pub struct SecurityGroupKeys {
+    security_policy: PubSubSecurityPolicy,
+    /// The known keys and the token id of the current one
+    keys: RwLock<(Vec<SecurityKeys>, u32)>,
+}
+
+impl SecurityGroupKeys {
+    pub fn new(security_policy: PubSubSecurityPolicy) -> SecurityGroupKeys {
+        SecurityGroupKeys {
+            security_policy,
+            keys: RwLock::new((Vec::new(), 0)),
+        }
+    }
+
+    /// Creates a key provider holding the keys and with the first of them current
+    pub fn from_keys(security_policy: PubSubSecurityPolicy, keys: Vec<SecurityKeys>) -> SecurityGroupKeys {
+        let current_token_id = keys.first().map(|k| k.token_id).unwrap_or(0);
+        SecurityGroupKeys {
+            security_policy,
+            keys: RwLock::new((keys, current_token_id)),
+        }
+    }
+
+    /// Sets the keys from the output arguments of `GetSecurityKeys`. The first key has the
+    /// token id and the ones that follow have the ids after it.
+    pub fn set_keys(&self, first_token_id: u32, keys: &[ByteString]) -> Result<(), StatusCode> {
+        let keys = keys.iter().enumerate().map(|(i, key_data)| {
+            let key_data = key_data.value.as_ref().map(|v| &v[..]).unwrap_or(&[]);
+            SecurityKeys::from_key_data(self.security_policy, first_token_id.wrapping_add(i as u32), key_data)
+        }).collect::<Result<Vec<_>, _>>()?;
+        let mut state = trace_write_lock_unwrap!(self.keys);
+        *state = (keys, first_token_id);
+        Ok(())
+    }
+
+    /// Makes the key after the current one current. Returns false if there is no next key.
+    pub fn next_token(&self) -> bool {
+        let mut state = trace_write_lock_unwrap!(self.keys);
+        let next_token_id = state.1.wrapping_add(1);
+        if state.0.iter().any(|k| k.token_id == next_token_id) {
+            state.1 = next_token_id;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl KeyProvider for SecurityGroupKeys {
+    fn security_policy(&self) -> PubSubSecurityPolicy {
+        self.security_policy
+    }
+
+    fn current_keys(&self) -> Option<SecurityKeys> {
+        let state = trace_read_lock_unwrap!(self.keys);
+        state.0.iter().find(|k| k.token_id == state.1).cloned()
+    }
+
+    fn keys(&self, token_id: u32) -> Option<SecurityKeys> {
+        let state = trace_read_lock_unwrap!(self.keys);
+        state.0.iter().find(|k| k.token_id == token_id).cloned()
+    }
+}
@@ -0,0 +1,411 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! The subscriber receives NetworkMessages, picks out the DataSetMessages that its DataSetReaders
+//! are interested in and writes their fields to the target variables.
+//!
+//! UADP and JSON messages are both understood, whichever transport they arrive on. The fields
+//! of a JSON message are named, so a reader numbers them using the metadata of the DataSet,
+//! which it is either configured with or receives from the publisher.
+
+use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use opcua_types::{*, service_types::{DataSetMetaDataType, MessageSecurityMode}, status_code::StatusCode};
+
+use crate::{
+    data_set::DataSetTarget,
+    json::{JsonMessage, JsonMetaDataMessage, JsonNetworkMessage},
+    PubSubHandle,
+    security::KeyProvider,
+    transport::{self, NetworkTransport},
+    uadp::{DataSetMessage, DataSetMessagePayload, NetworkMessage, PublisherId},
+};
+
+/// Returns true if the sequence number is newer than the last one. Sequence numbers wrap, so a
+/// number is newer if it is less than half the range ahead of the last.
+pub fn is_newer_sequence_number(sequence_number: u16, last_sequence_number: u16) -> bool {
+    (sequence_number.wrapping_sub(last_sequence_number) as i16) > 0
+}
+
+/// Reads the DataSet from one DataSetWriter of a publisher into target variables
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataSetReader {
+    pub name: String,
+    pub publisher_id: PublisherId,
+    pub writer_group_id: u16,
+    pub data_set_writer_id: u16,
+    /// Time in milliseconds after which the writer is thought to have gone if nothing has been
+    /// received from it. Once it has gone, the next message is accepted whatever its sequence
+    /// number so a publisher that restarts is picked up again.
+    pub message_receive_timeout: f64,
+    /// The variables that the fields of the DataSet are written to, in the order of the fields
+    pub target_variables: Vec<NodeId>,
+    /// The metadata of the DataSet, if it is known in advance. Otherwise it is taken from the
+    /// metadata messages of the writer.
+    pub data_set_meta_data: Option<DataSetMetaDataType>,
+    /// Queue that the writer's messages are received from over a broker transport. If not set,
+    /// it is `opcua/+/data/<publisher id>/#`.
+    pub queue_name: Option<String>,
+    /// Queue that the writer's metadata is received from over a broker transport. If not set, it
+    /// is `opcua/json/metadata/<publisher id>/#`.
+    pub meta_data_queue_name: Option<String>,
+}
+
+impl DataSetReader {
+    pub fn new<T>(name: T, publisher_id: PublisherId, writer_group_id: u16, data_set_writer_id: u16) -> DataSetReader where T: Into<String> {
+        DataSetReader {
+            name: name.into(),
+            publisher_id,
+            writer_group_id,
+            data_set_writer_id,
+            message_receive_timeout: 10000.0,
+            target_variables: Vec::new(),
+            data_set_meta_data: None,
+            queue_name: None,
+            meta_data_queue_name: None,
+        }
+    }
+
+    pub fn message_receive_timeout(mut self, message_receive_timeout: f64) -> Self {
+        self.message_receive_timeout = message_receive_timeout;
+        self
+    }
+
+    /// Adds the variable that the next field is written to
+    pub fn target_variable<N>(mut self, node_id: N) -> Self where N: Into<NodeId> {
+        self.target_variables.push(node_id.into());
+        self
+    }
+
+    pub fn meta_data(mut self, data_set_meta_data: DataSetMetaDataType) -> Self {
+        self.data_set_meta_data = Some(data_set_meta_data);
+        self
+    }
+
+    pub fn queue_name<T>(mut self, queue_name: T) -> Self where T: Into<String> {
+        self.queue_name = Some(queue_name.into());
+        self
+    }
+
+    pub fn meta_data_queue_name<T>(mut self, meta_data_queue_name: T) -> Self where T: Into<String> {
+        self.meta_data_queue_name = Some(meta_data_queue_name.into());
+        self
+    }
+
+    /// Returns the queues to subscribe to over a broker transport
+    fn queue_names(&self) -> Vec<String> {
+        vec![
+            self.queue_name.clone().unwrap_or_else(|| format!("opcua/+/data/{}/#", self.publisher_id)),
+            self.meta_data_queue_name.clone().unwrap_or_else(|| format!("opcua/json/metadata/{}/#", self.publisher_id)),
+        ]
+    }
+
+    fn matches(&self, publisher_id: Option<&PublisherId>, writer_group_id: Option<u16>) -> bool {
+        publisher_id == Some(&self.publisher_id) && writer_group_id == Some(self.writer_group_id)
+    }
+
+    /// Tests if the reader is for the writer of a JSON message, which names the publisher but
+    /// not the writer group
+    fn matches_json(&self, publisher_id: Option<&String>, data_set_writer_id: u16) -> bool {
+        publisher_id.map(|p| *p == self.publisher_id.to_string()).unwrap_or(false) && data_set_writer_id == self.data_set_writer_id
+    }
+}
+
+/// A group of DataSetReaders sharing the same security settings
+#[derive(Clone)]
+pub struct ReaderGroup {
+    pub name: String,
+    /// The least security that messages must have to be accepted
+    pub security_mode: MessageSecurityMode,
+    /// Supplies the keys to verify and decrypt secured messages
+    pub security_key_provider: Option<Arc<dyn KeyProvider>>,
+    pub readers: Vec<DataSetReader>,
+}
+
+impl ReaderGroup {
+    pub fn new<T>(name: T) -> ReaderGroup where T: Into<String> {
+        ReaderGroup {
+            name: name.into(),
+            security_mode: MessageSecurityMode::None,
+            security_key_provider: None,
+            readers: Vec::new(),
+        }
+    }
+
+    /// Requires messages to be secured with keys from the provider
+    pub fn security(mut self, security_mode: MessageSecurityMode, security_key_provider: Arc<dyn KeyProvider>) -> Self {
+        self.security_mode = security_mode;
+        self.security_key_provider = Some(security_key_provider);
+        self
+    }
+
+    pub fn reader(mut self, reader: DataSetReader) -> Self {
+        self.readers.push(reader);
+        self
+    }
+
+    fn accepts_security_mode(&self, security_mode: MessageSecurityMode) -> bool {
+        match self.security_mode {
+            MessageSecurityMode::SignAndEncrypt => security_mode == MessageSecurityMode::SignAndEncrypt,
+            MessageSecurityMode::Sign => security_mode == MessageSecurityMode::Sign || security_mode == MessageSecurityMode::SignAndEncrypt,
+            _ => true
+        }
+    }
+}
+
+/// Receives DataSets from the address of a PubSubConnection and writes them to a target, e.g. a
+/// server's address space
+pub struct Subscriber {
+    address: String,
+    target: Arc<dyn DataSetTarget>,
+    reader_groups: Vec<ReaderGroup>,
+    decoding_limits: DecodingLimits,
+}
+
+impl Subscriber {
+    /// Creates a subscriber which receives from the address url, e.g. `opc.udp://239.0.0.1:4840`
+    /// or `mqtt://broker:1883`
+    pub fn new<T>(address: T, target: Arc<dyn DataSetTarget>) -> Subscriber where T: Into<String> {
+        Subscriber {
+            address: address.into(),
+            target,
+            reader_groups: Vec::new(),
+            decoding_limits: DecodingLimits::default(),
+        }
+    }
+
+    pub fn reader_group(mut self, reader_group: ReaderGroup) -> Self {
+        self.reader_groups.push(reader_group);
+        self
+    }
+
+    /// Starts receiving on a thread of its own until the returned handle is stopped
+    pub fn start(self) -> Result<PubSubHandle, StatusCode> {
+        for reader_group in &self.reader_groups {
+            if reader_group.security_mode != MessageSecurityMode::None && reader_group.security_key_provider.is_none() {
+                error!("Reader group {} requires security but has no security key provider", reader_group.name);
+                return Err(StatusCode::BadConfigurationError);
+            }
+        }
+        let mut queue_names = Vec::new();
+        self.reader_groups.iter().flat_map(|g| g.readers.iter()).flat_map(|r| r.queue_names()).for_each(|queue_name| {
+            if !queue_names.contains(&queue_name) {
+                queue_names.push(queue_name);
+            }
+        });
+        let transport = transport::subscriber_transport(&self.address, queue_names)?;
+        let abort = Arc::new(AtomicBool::new(false));
+        let mut task = SubscriberTask::new(self, transport);
+        let thread = {
+            let abort = abort.clone();
+            thread::spawn(move || task.run(abort))
+        };
+        Ok(PubSubHandle::new(abort, vec![thread]))
+    }
+}
+
+/// What a reader last received
+#[derive(Default)]
+struct ReaderState {
+    last_sequence_number: Option<u16>,
+    last_received: Option<Instant>,
+    /// Names of the fields of the DataSet from its metadata
+    field_names: Option<Vec<String>>,
+}
+
+impl ReaderState {
+    fn new(reader: &DataSetReader) -> ReaderState {
+        ReaderState {
+            field_names: reader.data_set_meta_data.as_ref().map(Self::field_names),
+            ..Default::default()
+        }
+    }
+
+    fn field_names(meta_data: &DataSetMetaDataType) -> Vec<String> {
+        meta_data.fields.as_ref()
+            .map(|fields| fields.iter().map(|f| f.name.as_ref().to_string()).collect())
+            .unwrap_or_default()
+    }
+}
+
+struct SubscriberTask {
+    subscriber: Subscriber,
+    transport: Box<dyn NetworkTransport>,
+    /// State of each reader of each group
+    reader_states: Vec<Vec<ReaderState>>,
+}
+
+impl SubscriberTask {
+    fn new(subscriber: Subscriber, transport: Box<dyn NetworkTransport>) -> SubscriberTask {
+        let reader_states = subscriber.reader_groups.iter()
+            .map(|g| g.readers.iter().map(ReaderState::new).collect())
+            .collect();
+        SubscriberTask {
+            subscriber,
+            transport,
+            reader_states,
+        }
+    }
+
+    fn run(&mut self, abort: Arc<AtomicBool>) {
+        info!("Subscriber is receiving from {}", self.subscriber.address);
+        while !abort.load(Ordering::Relaxed) {
+            match self.transport.receive() {
+                Ok(Some(data)) => self.receive(&data),
+                Ok(None) => {}
+                Err(_) => {
+                    // Errors have been logged, try again after a pause
+                    thread::sleep(Duration::from_millis(100));
+                }
+            }
+        }
+        info!("Subscriber has stopped receiving from {}", self.subscriber.address);
+    }
+
+    fn receive(&mut self, data: &[u8]) {
+        if JsonMessage::is_json(data) {
+            match JsonMessage::decode(data, &self.subscriber.decoding_limits) {
+                Ok(JsonMessage::Data(message)) => self.process_json_message(message),
+                Ok(JsonMessage::MetaData(message)) => self.process_meta_data_message(message),
+                Err(status_code) => {
+                    debug!("Received JSON message was rejected, {}", status_code);
+                }
+            }
+            return;
+        }
+        let reader_groups = &self.subscriber.reader_groups;
+        let message = NetworkMessage::decode(data, &self.subscriber.decoding_limits, |message| {
+            reader_groups.iter()
+                .find(|g| g.readers.iter().any(|r| r.matches(message.publisher_id.as_ref(), message.writer_group_id())))
+                .and_then(|g| g.security_key_provider.clone())
+        });
+        match message {
+            Ok(message) => self.process_message(message),
+            Err(status_code) => {
+                debug!("Received NetworkMessage was rejected, {}", status_code);
+            }
+        }
+    }
+
+    /// Writes the DataSetMessages of the NetworkMessage that readers are interested in to the
+    /// target
+    fn process_message(&mut self, message: NetworkMessage) {
+        let now = Instant::now();
+        let publisher_id = message.publisher_id.as_ref();
+        let writer_group_id = message.writer_group_id();
+        let security_mode = message.security_mode();
+        let mut values = Vec::new();
+        for (reader_group, reader_states) in self.subscriber.reader_groups.iter().zip(self.reader_states.iter_mut()) {
+            for (reader, reader_state) in reader_group.readers.iter().zip(reader_states.iter_mut()) {
+                if !reader.matches(publisher_id, writer_group_id) {
+                    continue;
+                }
+                if !reader_group.accepts_security_mode(security_mode) {
+                    error!("Reader {} requires security mode {:?} but the message has {:?}", reader.name, reader_group.security_mode, security_mode);
+                    continue;
+                }
+                if let Some(data_set_message) = message.messages.iter().find(|m| m.data_set_writer_id == reader.data_set_writer_id) {
+                    if Self::accept_sequence_number(reader, reader_state, data_set_message, now) {
+                        Self::read_fields(reader, data_set_message, &mut values);
+                    }
+                }
+            }
+        }
+        if !values.is_empty() {
+            self.subscriber.target.write(values);
+        }
+    }
+
+    /// Writes the DataSetMessages of the JSON NetworkMessage that readers are interested in to
+    /// the target
+    fn process_json_message(&mut self, message: JsonNetworkMessage) {
+        let now = Instant::now();
+        let publisher_id = message.publisher_id.as_ref();
+        let mut values = Vec::new();
+        for (reader_group, reader_states) in self.subscriber.reader_groups.iter().zip(self.reader_states.iter_mut()) {
+            for (reader, reader_state) in reader_group.readers.iter().zip(reader_states.iter_mut()) {
+                let json_message = match message.messages.iter().find(|m| reader.matches_json(publisher_id, m.data_set_writer_id)) {
+                    Some(json_message) => json_message,
+                    None => continue,
+                };
+                if reader_group.security_mode != MessageSecurityMode::None {
+                    error!("Reader {} requires security mode {:?} but JSON messages are not secured", reader.name, reader_group.security_mode);
+                    continue;
+                }
+                match json_message.to_data_set_message(reader_state.field_names.as_ref().map(|f| &f[..])) {
+                    Ok(data_set_message) => {
+                        if Self::accept_sequence_number(reader, reader_state, &data_set_message, now) {
+                            Self::read_fields(reader, &data_set_message, &mut values);
+                        }
+                    }
+                    Err(_) => {
+                        debug!("Reader {} cannot read a message without the metadata of the DataSet", reader.name);
+                    }
+                }
+            }
+        }
+        if !values.is_empty() {
+            self.subscriber.target.write(values);
+        }
+    }
+
+    /// Takes the field names from the metadata for the readers of the writer
+    fn process_meta_data_message(&mut self, message: JsonMetaDataMessage) {
+        let publisher_id = message.publisher_id.as_ref();
+        for (reader_group, reader_states) in self.subscriber.reader_groups.iter().zip(self.reader_states.iter_mut()) {
+            for (reader, reader_state) in reader_group.readers.iter().zip(reader_states.iter_mut()) {
+                if reader.matches_json(publisher_id, message.data_set_writer_id) {
+                    debug!("Reader {} received the metadata of DataSet {}", reader.name, message.meta_data.name);
+                    reader_state.field_names = Some(ReaderState::field_names(&message.meta_data));
+                }
+            }
+        }
+    }
+
+    /// Tests that the message is newer than the last one from the writer, so duplicates and
+    /// messages that arrive out of order are dropped
+    fn accept_sequence_number(reader: &DataSetReader, reader_state: &mut ReaderState, data_set_message: &DataSetMessage, now: Instant) -> bool {
+        let timed_out = reader_state.last_received
+            .map(|last_received| reader.message_receive_timeout > 0.0 && now.duration_since(last_received).as_secs_f64() * 1000.0 > reader.message_receive_timeout)
+            .unwrap_or(true);
+        reader_state.last_received = Some(now);
+        if let DataSetMessagePayload::KeepAlive = data_set_message.payload {
+            // A keep alive holds the next sequence number, which the next message will reuse
+            return true;
+        }
+        match (data_set_message.sequence_number, reader_state.last_sequence_number) {
+            (Some(sequence_number), Some(last_sequence_number)) if !timed_out && !is_newer_sequence_number(sequence_number, last_sequence_number) => {
+                debug!("Reader {} dropped message with sequence number {} after {}", reader.name, sequence_number, last_sequence_number);
+                false
+            }
+            (sequence_number, _) => {
+                reader_state.last_sequence_number = sequence_number;
+                true
+            }
+        }
+    }
+
+    fn read_fields(reader: &DataSetReader, data_set_message: &DataSetMessage, values: &mut Vec<(NodeId, DataValue)>) {
+        let target_variable = |index: usize| {
+            let node_id = reader.target_variables.get(index);
+            if node_id.is_none() {
+                error!("Reader {} has no target variable for field {}", reader.name, index);
+            }
+            node_id.cloned()
+        };
+        match data_set_message.payload {
+            DataSetMessagePayload::KeyFrame(ref fields) => {
+                values.extend(fields.iter().enumerate()
+                    .filter_map(|(i, field)| target_variable(i).map(|node_id| (node_id, field.clone()))));
+            }
+            DataSetMessagePayload::DeltaFrame(ref fields) => {
+                values.extend(fields.iter()
+                    .filter_map(|(i, field)| target_variable(*i as usize).map(|node_id| (node_id, field.clone()))));
+            }
+            DataSetMessagePayload::KeepAlive => {}
+        }
+    }
+}
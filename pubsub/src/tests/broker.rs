@@ -0,0 +1,162 @@
+//! A small MQTT 3.1.1 broker which stands in for Mosquitto in tests. It accepts connections,
+//! forwards published messages to the clients subscribed to matching topics and retains messages
+//! that are published with the retain flag. Only QoS 0 is supported.
+
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}};
+use std::thread;
+use std::time::Duration;
+
+use crate::transport::mqtt::Packet;
+
+/// Test if a topic matches a filter, which may contain `+` for one level and `#` for the rest
+pub fn topic_matches(filter: &str, topic: &str) -> bool {
+    let mut topic_levels = topic.split('/');
+    for filter_level in filter.split('/') {
+        match filter_level {
+            "#" => return true,
+            "+" => if topic_levels.next().is_none() { return false; },
+            level => if topic_levels.next() != Some(level) { return false; },
+        }
+    }
+    topic_levels.next().is_none()
+}
+
+#[derive(Default)]
+struct BrokerState {
+    /// Each client's stream for writing to and its topic filters
+    subscriptions: Vec<(Arc<Mutex<TcpStream>>, Vec<String>)>,
+    retained: BTreeMap<String, Vec<u8>>,
+    /// User name and password that clients must connect with, if any
+    credentials: Option<(String, String)>,
+}
+
+pub struct Broker {
+    address: SocketAddr,
+    abort: Arc<AtomicBool>,
+    state: Arc<Mutex<BrokerState>>,
+}
+
+impl Broker {
+    /// Starts a broker on a free port of the loopback interface
+    pub fn start() -> Broker {
+        Self::start_with_state(BrokerState::default())
+    }
+
+    /// Starts a broker which only accepts clients that connect with the user name and password
+    pub fn with_credentials(username: &str, password: &str) -> Broker {
+        Self::start_with_state(BrokerState {
+            credentials: Some((username.to_string(), password.to_string())),
+            ..Default::default()
+        })
+    }
+
+    fn start_with_state(state: BrokerState) -> Broker {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.set_nonblocking(true).unwrap();
+        let address = listener.local_addr().unwrap();
+        let abort = Arc::new(AtomicBool::new(false));
+        let state = Arc::new(Mutex::new(state));
+        {
+            let abort = abort.clone();
+            let state = state.clone();
+            thread::spawn(move || {
+                while !abort.load(Ordering::Relaxed) {
+                    match listener.accept() {
+                        Ok((stream, _)) => {
+                            let state = state.clone();
+                            thread::spawn(move || Self::serve(stream, state));
+                        }
+                        Err(_) => thread::sleep(Duration::from_millis(10)),
+                    }
+                }
+            });
+        }
+        Broker { address, abort, state }
+    }
+
+    pub fn url(&self) -> String {
+        format!("mqtt://{}", self.address)
+    }
+
+    /// Returns the retained message of the topic
+    pub fn retained(&self, topic: &str) -> Option<Vec<u8>> {
+        self.state.lock().unwrap().retained.get(topic).cloned()
+    }
+
+    fn serve(mut stream: TcpStream, state: Arc<Mutex<BrokerState>>) {
+        stream.set_nonblocking(false).unwrap();
+        let writer = Arc::new(Mutex::new(stream.try_clone().unwrap()));
+        let write = |packet: &Packet| {
+            let _ = writer.lock().unwrap().write_all(&packet.encode());
+        };
+        let mut buffer = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            while let Ok(Some((packet, size))) = Packet::decode(&buffer) {
+                buffer.drain(..size);
+                match packet {
+                    Packet::Connect { username, password, .. } => {
+                        let accepted = match state.lock().unwrap().credentials {
+                            Some((ref u, ref p)) => username.as_ref() == Some(u) && password.as_ref() == Some(p),
+                            None => true,
+                        };
+                        // Return code 4 is a bad user name or password
+                        write(&Packet::ConnAck { session_present: false, return_code: if accepted { 0 } else { 4 } });
+                        if !accepted {
+                            Self::disconnect(&stream, &writer, &state);
+                            return;
+                        }
+                    }
+                    Packet::Subscribe { packet_id, topic_filters } => {
+                        write(&Packet::SubAck { packet_id, return_codes: vec![0; topic_filters.len()] });
+                        let mut state = state.lock().unwrap();
+                        state.retained.iter()
+                            .filter(|(topic, _)| topic_filters.iter().any(|f| topic_matches(f, topic)))
+                            .for_each(|(topic, payload)| write(&Packet::Publish { topic: topic.clone(), payload: payload.clone(), retain: true }));
+                        state.subscriptions.push((writer.clone(), topic_filters));
+                    }
+                    Packet::Publish { topic, payload, retain } => {
+                        let mut state = state.lock().unwrap();
+                        if retain {
+                            if payload.is_empty() {
+                                state.retained.remove(&topic);
+                            } else {
+                                state.retained.insert(topic.clone(), payload.clone());
+                            }
+                        }
+                        let packet = Packet::Publish { topic: topic.clone(), payload, retain: false }.encode();
+                        state.subscriptions.iter()
+                            .filter(|(_, filters)| filters.iter().any(|f| topic_matches(f, &topic)))
+                            .for_each(|(subscriber, _)| { let _ = subscriber.lock().unwrap().write_all(&packet); });
+                    }
+                    Packet::PingReq => write(&Packet::PingResp),
+                    _ => {
+                        Self::disconnect(&stream, &writer, &state);
+                        return;
+                    }
+                }
+            }
+            match stream.read(&mut buf) {
+                Ok(0) | Err(_) => {
+                    Self::disconnect(&stream, &writer, &state);
+                    return;
+                }
+                Ok(size) => buffer.extend_from_slice(&buf[..size]),
+            }
+        }
+    }
+
+    fn disconnect(stream: &TcpStream, writer: &Arc<Mutex<TcpStream>>, state: &Arc<Mutex<BrokerState>>) {
+        state.lock().unwrap().subscriptions.retain(|(w, _)| !Arc::ptr_eq(w, writer));
+        let _ = stream.shutdown(Shutdown::Both);
+    }
+}
+
+impl Drop for Broker {
+    fn drop(&mut self) {
+        self.abort.store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,307 @@
+use std::sync::{Arc, RwLock};
+
+use opcua_server::{prelude::*, session::Session};
+
+use crate::{
+    configuration::{self, load_configuration, TRANSPORT_PROFILE_UDP_UADP},
+    data_set::PublishedDataSet,
+    information_model::PubSubInformationModel,
+};
+
+use super::*;
+
+fn connection(name: &str, port: u16) -> PubSubConnectionDataType {
+    PubSubConnectionDataType {
+        name: UAString::from(name),
+        enabled: true,
+        publisher_id: Variant::from(1u16),
+        transport_profile_uri: UAString::from(TRANSPORT_PROFILE_UDP_UADP),
+        address: ExtensionObject::from_encodable(ObjectId::NetworkAddressUrlDataType_Encoding_DefaultBinary, &NetworkAddressUrlDataType {
+            network_interface: UAString::null(),
+            url: UAString::from(format!("opc.udp://127.0.0.1:{}", port)),
+        }),
+        connection_properties: None,
+        transport_settings: ExtensionObject::null(),
+        writer_groups: None,
+        reader_groups: None,
+    }
+}
+
+fn writer_group(name: &str, writer_group_id: u16, data_set_name: &str) -> WriterGroupDataType {
+    WriterGroupDataType {
+        name: UAString::from(name),
+        enabled: true,
+        security_mode: MessageSecurityMode::None,
+        security_group_id: UAString::null(),
+        security_key_services: None,
+        max_network_message_size: 0,
+        group_properties: None,
+        writer_group_id,
+        publishing_interval: 10.0,
+        keep_alive_time: 100.0,
+        priority: 0,
+        locale_ids: None,
+        header_layout_uri: UAString::null(),
+        transport_settings: ExtensionObject::null(),
+        message_settings: ExtensionObject::null(),
+        data_set_writers: Some(vec![DataSetWriterDataType {
+            name: UAString::from("Writer"),
+            enabled: true,
+            data_set_writer_id: 1,
+            data_set_field_content_mask: DataSetFieldContentMask::None,
+            key_frame_count: 1,
+            data_set_name: UAString::from(data_set_name),
+            data_set_writer_properties: None,
+            transport_settings: ExtensionObject::null(),
+            message_settings: ExtensionObject::null(),
+        }]),
+    }
+}
+
+fn reader_group(name: &str, writer_group_id: u16, target_variable: NodeId) -> ReaderGroupDataType {
+    let target_variables = TargetVariablesDataType {
+        target_variables: Some(vec![FieldTargetDataType {
+            data_set_field_id: Guid::null(),
+            receiver_index_range: UAString::null(),
+            target_node_id: target_variable,
+            attribute_id: AttributeId::Value as u32,
+            write_index_range: UAString::null(),
+            override_value_handling: OverrideValueHandling::Disabled,
+            override_value: Variant::Empty,
+        }]),
+    };
+    ReaderGroupDataType {
+        name: UAString::from(name),
+        enabled: true,
+        security_mode: MessageSecurityMode::None,
+        security_group_id: UAString::null(),
+        security_key_services: None,
+        max_network_message_size: 0,
+        group_properties: None,
+        transport_settings: ExtensionObject::null(),
+        message_settings: ExtensionObject::null(),
+        data_set_readers: Some(vec![DataSetReaderDataType {
+            name: UAString::from("Reader"),
+            enabled: true,
+            publisher_id: Variant::from(1u16),
+            writer_group_id,
+            data_set_writer_id: 1,
+            data_set_meta_data: PublishedDataSet::new("").meta_data(&make_address_space()),
+            data_set_field_content_mask: DataSetFieldContentMask::None,
+            message_receive_timeout: 0.0,
+            key_frame_count: 0,
+            header_layout_uri: UAString::null(),
+            security_mode: MessageSecurityMode::None,
+            security_group_id: UAString::null(),
+            security_key_services: None,
+            data_set_reader_properties: None,
+            transport_settings: ExtensionObject::null(),
+            message_settings: ExtensionObject::null(),
+            subscribed_data_set: ExtensionObject::from_encodable(ObjectId::TargetVariablesDataType_Encoding_DefaultBinary, &target_variables),
+        }]),
+    }
+}
+
+fn published_variable(node_id: NodeId) -> Variant {
+    Variant::from(ExtensionObject::from_encodable(ObjectId::PublishedVariableDataType_Encoding_DefaultBinary, &PublishedVariableDataType {
+        published_variable: node_id,
+        attribute_id: AttributeId::Value as u32,
+        sampling_interval_hint: 0.0,
+        deadband_type: 0,
+        deadband_value: 0.0,
+        index_range: UAString::null(),
+        substitute_value: Variant::Empty,
+        meta_data_properties: None,
+    }))
+}
+
+/// Calls a method of the address space as the method service does
+fn call<S, T>(address_space: &Arc<RwLock<AddressSpace>>, object_id: S, method_id: T, input_arguments: Vec<Variant>) -> Result<Vec<Variant>, StatusCode>
+    where S: Into<NodeId>, T: Into<NodeId> {
+    let server = ServerBuilder::new_sample().server().unwrap();
+    let mut session = Session::new(&server);
+    let server_state = server.server_state();
+    let server_state = server_state.read().unwrap();
+    let request = CallMethodRequest {
+        object_id: object_id.into(),
+        method_id: method_id.into(),
+        input_arguments: Some(input_arguments),
+    };
+    let mut address_space = address_space.write().unwrap();
+    address_space.call_method(&server_state, &mut session, &request)
+        .map(|result| result.output_arguments.unwrap_or_default())
+}
+
+fn state(address_space: &Arc<RwLock<AddressSpace>>, state_id: NodeId) -> Variant {
+    get_value(address_space, state_id)
+}
+
+fn pubsub_id_of(address_space: &AddressSpace, id: &str) -> NodeId {
+    NodeId::new(address_space.default_namespace(), format!("PubSub.{}", id))
+}
+
+fn pubsub_id(address_space: &Arc<RwLock<AddressSpace>>, id: &str) -> NodeId {
+    pubsub_id_of(&address_space.read().unwrap(), id)
+}
+
+fn add_data_set(address_space: &Arc<RwLock<AddressSpace>>, name: &str) -> Vec<Variant> {
+    let published_data_sets_id: NodeId = ObjectId::PublishSubscribe_PublishedDataSets.into();
+    call(address_space, published_data_sets_id, pubsub_id(address_space, "PublishedDataSets.AddPublishedDataItems"), vec![
+        Variant::from(name),
+        Variant::from(vec![Variant::from("v1"), Variant::from("missing")]),
+        Variant::Empty,
+        Variant::from(vec![published_variable(NodeId::new(1, 1)), published_variable(NodeId::new(1, 100))]),
+    ]).unwrap()
+}
+
+#[test]
+fn configuration_methods() {
+    let address_space = make_address_space();
+    let model = PubSubInformationModel::new(address_space.clone())
+        .start(PubSubConfigurationDataType {
+            published_data_sets: None,
+            connections: None,
+            enabled: true,
+        })
+        .unwrap();
+    wait_for(|| state(&address_space, VariableId::PublishSubscribe_Status_State.into()) == Variant::from(PubSubState::Operational as i32));
+
+    // The data set only has the variable that exists
+    let output = add_data_set(&address_space, "DataSet");
+    assert_eq!(output[0], Variant::from(pubsub_id(&address_space, "DataSet.DataSet")));
+    assert_eq!(output[2], Variant::from(vec![Variant::from(StatusCode::Good), Variant::from(StatusCode::BadNodeIdUnknown)]));
+    assert!(address_space.read().unwrap().node_exists(&pubsub_id(&address_space, "DataSet.DataSet.PublishedData")));
+    let configuration = model.configuration();
+    let data_set = configuration::published_data_set(&configuration.published_data_sets.as_ref().unwrap()[0]).unwrap();
+    assert_eq!(data_set.fields.len(), 1);
+    assert_eq!(data_set.fields[0].name, "v1");
+
+    // Connections are checked before they are added
+    let mut bad_connection = connection("Connection", 14842);
+    bad_connection.writer_groups = Some(vec![writer_group("Group", 1, "NoSuchDataSet")]);
+    let add_connection = |connection: &PubSubConnectionDataType| {
+        call(&address_space, ObjectId::PublishSubscribe, MethodId::PublishSubscribe_AddConnection, vec![
+            Variant::from(ExtensionObject::from_encodable(ObjectId::PubSubConnectionDataType_Encoding_DefaultBinary, connection))
+        ])
+    };
+    assert_eq!(add_connection(&bad_connection).unwrap_err(), StatusCode::BadInvalidArgument);
+    assert_eq!(add_connection(&connection("", 14842)).unwrap_err(), StatusCode::BadInvalidArgument);
+
+    let connection_id = pubsub_id(&address_space, "Connection.Connection");
+    let output = add_connection(&connection("Connection", 14842)).unwrap();
+    assert_eq!(output, vec![Variant::from(connection_id.clone())]);
+    assert_eq!(add_connection(&connection("Connection", 14842)).unwrap_err(), StatusCode::BadBrowseNameDuplicated);
+
+    // Groups are added to the connection
+    let output = call(&address_space, connection_id.clone(), pubsub_id(&address_space, "Connection.Connection.AddWriterGroup"), vec![
+        Variant::from(ExtensionObject::from_encodable(ObjectId::WriterGroupDataType_Encoding_DefaultBinary, &writer_group("Group", 1, "DataSet")))
+    ]).unwrap();
+    assert_eq!(output, vec![Variant::from(pubsub_id(&address_space, "Connection.Connection.Group"))]);
+    {
+        let address_space = address_space.read().unwrap();
+        let writer_id = pubsub_id_of(&address_space, "Connection.Connection.Group.Writer");
+        assert!(address_space.node_exists(&writer_id));
+        assert!(address_space.find_references(&pubsub_id_of(&address_space, "DataSet.DataSet"), Some((ReferenceTypeId::DataSetToWriter, false)))
+            .map(|r| r.iter().any(|r| r.target_node == writer_id))
+            .unwrap_or(false));
+    }
+    assert_eq!(model.configuration().connections.unwrap()[0].writer_groups.as_ref().unwrap().len(), 1);
+
+    // Removing the connection removes its nodes
+    call(&address_space, ObjectId::PublishSubscribe, MethodId::PublishSubscribe_RemoveConnection, vec![Variant::from(connection_id.clone())]).unwrap();
+    assert!(!address_space.read().unwrap().node_exists(&connection_id));
+    assert!(!address_space.read().unwrap().node_exists(&pubsub_id(&address_space, "Connection.Connection.Group")));
+    assert_eq!(call(&address_space, ObjectId::PublishSubscribe, MethodId::PublishSubscribe_RemoveConnection, vec![Variant::from(connection_id)]).unwrap_err(), StatusCode::BadNodeIdUnknown);
+
+    model.stop();
+}
+
+#[test]
+fn configuration_states() {
+    let address_space = make_address_space();
+    let model = PubSubInformationModel::new(address_space.clone())
+        .start(PubSubConfigurationDataType {
+            published_data_sets: None,
+            connections: None,
+            enabled: true,
+        })
+        .unwrap();
+    add_data_set(&address_space, "DataSet");
+    let mut connection = connection("Connection", 14843);
+    connection.writer_groups = Some(vec![writer_group("WriterGroup", 1, "DataSet")]);
+    connection.reader_groups = Some(vec![reader_group("ReaderGroup", 1, NodeId::new(2, 1))]);
+    call(&address_space, ObjectId::PublishSubscribe, MethodId::PublishSubscribe_AddConnection, vec![
+        Variant::from(ExtensionObject::from_encodable(ObjectId::PubSubConnectionDataType_Encoding_DefaultBinary, &connection))
+    ]).unwrap();
+
+    let operational = Variant::from(PubSubState::Operational as i32);
+    let paused = Variant::from(PubSubState::Paused as i32);
+    let disabled = Variant::from(PubSubState::Disabled as i32);
+    let connection_state = pubsub_id(&address_space, "Connection.Connection.Status.State");
+    let writer_state = pubsub_id(&address_space, "Connection.Connection.WriterGroup.Writer.Status.State");
+    assert_eq!(state(&address_space, connection_state.clone()), operational);
+    assert_eq!(state(&address_space, writer_state.clone()), operational);
+
+    // The connection publishes and subscribes
+    set_value(&address_space, NodeId::new(1, 1), 42);
+    wait_for(|| get_value(&address_space, NodeId::new(2, 1)) == Variant::from(42i32));
+
+    // Disabling the connection pauses its children and stops it
+    let status_id = pubsub_id(&address_space, "Connection.Connection.Status");
+    call(&address_space, status_id.clone(), pubsub_id(&address_space, "Connection.Connection.Status.Disable"), vec![]).unwrap();
+    assert_eq!(state(&address_space, connection_state.clone()), disabled);
+    assert_eq!(state(&address_space, writer_state.clone()), paused);
+    assert_eq!(call(&address_space, status_id.clone(), pubsub_id(&address_space, "Connection.Connection.Status.Disable"), vec![]).unwrap_err(), StatusCode::BadInvalidState);
+    assert!(!model.configuration().connections.unwrap()[0].enabled);
+
+    // Disabling PublishSubscribe pauses an enabled connection
+    call(&address_space, status_id.clone(), pubsub_id(&address_space, "Connection.Connection.Status.Enable"), vec![]).unwrap();
+    let root_status_id: NodeId = ObjectId::PublishSubscribe_Status.into();
+    call(&address_space, root_status_id.clone(), pubsub_id(&address_space, "Status.Disable"), vec![]).unwrap();
+    assert_eq!(state(&address_space, VariableId::PublishSubscribe_Status_State.into()), disabled);
+    assert_eq!(state(&address_space, connection_state.clone()), paused);
+
+    // Enabling it again resumes publishing
+    call(&address_space, root_status_id, pubsub_id(&address_space, "Status.Enable"), vec![]).unwrap();
+    assert_eq!(state(&address_space, writer_state), operational);
+    set_value(&address_space, NodeId::new(1, 1), 43);
+    wait_for(|| get_value(&address_space, NodeId::new(2, 1)) == Variant::from(43i32));
+
+    model.stop();
+}
+
+#[test]
+fn configuration_file() {
+    let address_space = make_address_space();
+    let model = PubSubInformationModel::new(address_space.clone())
+        .start(PubSubConfigurationDataType {
+            published_data_sets: None,
+            connections: None,
+            enabled: false,
+        })
+        .unwrap();
+    add_data_set(&address_space, "DataSet");
+    let mut connection = connection("Connection", 14844);
+    connection.writer_groups = Some(vec![writer_group("WriterGroup", 1, "DataSet")]);
+    call(&address_space, ObjectId::PublishSubscribe, MethodId::PublishSubscribe_AddConnection, vec![
+        Variant::from(ExtensionObject::from_encodable(ObjectId::PubSubConnectionDataType_Encoding_DefaultBinary, &connection))
+    ]).unwrap();
+
+    let mut path = std::env::temp_dir();
+    path.push("opcua-pubsub-configuration.bin");
+    model.save_configuration(&path).unwrap();
+    let configuration = load_configuration(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(configuration, model.configuration());
+    model.stop();
+
+    // The saved configuration makes the same nodes in another address space
+    let address_space = make_address_space();
+    let model = PubSubInformationModel::new(address_space.clone()).start(configuration).unwrap();
+    assert!(address_space.read().unwrap().node_exists(&pubsub_id(&address_space, "Connection.Connection.WriterGroup.Writer")));
+    assert!(address_space.read().unwrap().node_exists(&pubsub_id(&address_space, "DataSet.DataSet")));
+    assert_eq!(state(&address_space, pubsub_id(&address_space, "Connection.Connection.Status.State")), Variant::from(PubSubState::Paused as i32));
+    model.stop();
+
+    assert_eq!(load_configuration("no/such/file").unwrap_err(), StatusCode::BadNotFound);
+}
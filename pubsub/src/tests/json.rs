@@ -0,0 +1,151 @@
+use opcua_types::{*, service_types::ConfigurationVersionDataType, status_code::StatusCode};
+
+use crate::{
+    data_set::{DataSetSource, FieldType, PublishedDataSet},
+    json::*,
+    uadp::{DataSetMessage, DataSetMessagePayload, FieldEncoding},
+};
+
+fn json_data_set_message(message_type: JsonDataSetMessageType, field_encoding: FieldEncoding, payload: Vec<(String, DataValue)>) -> JsonDataSetMessage {
+    JsonDataSetMessage {
+        data_set_writer_id: 12,
+        sequence_number: Some(100),
+        meta_data_version: Some(ConfigurationVersionDataType { major_version: 1, minor_version: 2 }),
+        timestamp: Some(DateTime::now()),
+        status: None,
+        message_type,
+        field_encoding,
+        payload,
+    }
+}
+
+fn roundtrip(message: &JsonMessage) -> JsonMessage {
+    let data = message.encode();
+    assert!(JsonMessage::is_json(data.as_bytes()));
+    JsonMessage::decode(data.as_bytes(), &DecodingLimits::default()).unwrap()
+}
+
+#[test]
+fn network_message() {
+    let message = JsonMessage::Data(JsonNetworkMessage {
+        message_id: "abc".to_string(),
+        publisher_id: Some("Publisher1".to_string()),
+        data_set_class_id: None,
+        messages: vec![
+            json_data_set_message(JsonDataSetMessageType::KeyFrame, FieldEncoding::Variant, vec![
+                ("Temperature".to_string(), DataValue::value_only(21.5f64)),
+                ("Name".to_string(), DataValue::value_only("Boiler")),
+                ("Empty".to_string(), DataValue::value_only(Variant::Empty)),
+            ]),
+            json_data_set_message(JsonDataSetMessageType::DeltaFrame, FieldEncoding::DataValue, vec![
+                ("Pressure".to_string(), DataValue {
+                    value: Some(Variant::from(3u32)),
+                    status: Some(StatusCode::BadSensorFailure),
+                    ..Default::default()
+                }),
+            ]),
+            json_data_set_message(JsonDataSetMessageType::KeepAlive, FieldEncoding::Variant, vec![]),
+        ],
+    });
+    assert_eq!(roundtrip(&message), message);
+
+    // The members are named as Part 14 says
+    let value = message.encode().parse::<JsonValue>().unwrap();
+    assert_eq!(value["MessageType"], "ua-data");
+    assert_eq!(value["PublisherId"], "Publisher1");
+    assert_eq!(value["Messages"][0]["DataSetWriterId"], 12);
+    assert_eq!(value["Messages"][0]["MessageType"], "ua-keyframe");
+    assert_eq!(value["Messages"][0]["Payload"]["Temperature"]["Body"], 21.5);
+    assert_eq!(value["Messages"][1]["MessageType"], "ua-deltaframe");
+    assert!(value["Messages"][2].get("Payload").is_none());
+}
+
+struct TestSource;
+
+impl DataSetSource for TestSource {
+    fn read(&self, fields: &[crate::data_set::DataSetField]) -> Vec<DataValue> {
+        fields.iter().map(|_| DataValue::default()).collect()
+    }
+
+    fn field_type(&self, _field: &crate::data_set::DataSetField) -> FieldType {
+        FieldType {
+            data_type: DataTypeId::Double.into(),
+            value_rank: -1,
+            array_dimensions: None,
+        }
+    }
+}
+
+#[test]
+fn meta_data_message() {
+    let published_data_set = PublishedDataSet::new("DataSet1")
+        .field("Temperature", NodeId::new(2, 1))
+        .field("Pressure", NodeId::new(2, 2));
+    let meta_data = published_data_set.meta_data(&TestSource);
+    let fields = meta_data.fields.as_ref().unwrap();
+    assert_eq!(fields.len(), 2);
+    assert_eq!(fields[1].name.as_ref(), "Pressure");
+    assert_eq!(fields[1].built_in_type, 11);
+    assert_eq!(meta_data.configuration_version, published_data_set.configuration_version);
+
+    let message = JsonMessage::MetaData(JsonMetaDataMessage {
+        message_id: "abc".to_string(),
+        publisher_id: Some("1".to_string()),
+        data_set_writer_id: 3,
+        meta_data,
+    });
+    assert_eq!(roundtrip(&message), message);
+    let value = message.encode().parse::<JsonValue>().unwrap();
+    assert_eq!(value["MessageType"], "ua-metadata");
+    assert_eq!(value["MetaData"]["Name"], "DataSet1");
+}
+
+#[test]
+fn field_names() {
+    let field_names = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+    let message = DataSetMessage {
+        data_set_writer_id: 1,
+        sequence_number: Some(5),
+        timestamp: None,
+        status: None,
+        configuration_version: None,
+        field_encoding: FieldEncoding::Variant,
+        payload: DataSetMessagePayload::DeltaFrame(vec![(2, DataValue::value_only(3i32)), (0, DataValue::value_only(1i32))]),
+    };
+    let json_message = JsonDataSetMessage::from_data_set_message(&message, &field_names);
+    assert_eq!(json_message.payload[0].0, "c");
+    assert_eq!(json_message.payload[1].0, "a");
+    assert_eq!(json_message.to_data_set_message(Some(&field_names)).unwrap(), message);
+
+    // A delta frame needs the field names but a key frame is numbered in order without them
+    assert!(json_message.to_data_set_message(None).is_err());
+    let json_message = json_data_set_message(JsonDataSetMessageType::KeyFrame, FieldEncoding::Variant, vec![
+        ("c".to_string(), DataValue::value_only(3i32)),
+        ("a".to_string(), DataValue::value_only(1i32)),
+    ]);
+    let key_frame = |message: DataSetMessage| match message.payload {
+        DataSetMessagePayload::KeyFrame(fields) => fields.into_iter().map(|f| f.value.unwrap_or(Variant::Empty)).collect::<Vec<_>>(),
+        _ => panic!(),
+    };
+    assert_eq!(key_frame(json_message.to_data_set_message(None).unwrap()), vec![Variant::from(3i32), Variant::from(1i32)]);
+    assert_eq!(key_frame(json_message.to_data_set_message(Some(&field_names)).unwrap()), vec![Variant::from(1i32), Variant::Empty, Variant::from(3i32)]);
+}
+
+#[test]
+fn invalid_json_messages() {
+    let decoding_limits = DecodingLimits::default();
+    assert!(!JsonMessage::is_json(&[0xf1, 0x7b]));
+    assert!(JsonMessage::is_json(b"  {}"));
+    assert!(JsonMessage::decode(b"{", &decoding_limits).is_err());
+    assert!(JsonMessage::decode(b"{\"MessageType\":\"ua-discovery\"}", &decoding_limits).is_err());
+    assert!(JsonMessage::decode(b"{\"MessageType\":\"ua-data\",\"Messages\":[{\"DataSetWriterId\":1,\"MessageType\":\"ua-event\"}]}", &decoding_limits).is_err());
+    // A lone DataSetMessage without the array around it
+    match JsonMessage::decode(b"{\"MessageType\":\"ua-data\",\"Messages\":{\"DataSetWriterId\":1,\"Payload\":{\"x\":{\"Type\":6,\"Body\":5}}}}", &decoding_limits).unwrap() {
+        JsonMessage::Data(message) => {
+            assert_eq!(message.messages.len(), 1);
+            assert_eq!(message.messages[0].message_type, JsonDataSetMessageType::KeyFrame);
+            assert_eq!(message.messages[0].payload[0].1.value, Some(Variant::from(5i32)));
+        }
+        _ => panic!(),
+    }
+}
@@ -0,0 +1,52 @@
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use opcua_server::prelude::*;
+
+mod broker;
+mod information_model;
+mod json;
+mod mqtt;
+mod pubsub;
+mod uadp;
+
+/// Makes an address space with a publisher's variables in namespace 1 and a subscriber's target
+/// variables in namespace 2
+fn make_address_space() -> Arc<RwLock<AddressSpace>> {
+    let mut address_space = AddressSpace::new();
+    let _ = address_space.register_namespace("urn:pubsub:target2");
+    let _ = address_space.register_namespace("urn:pubsub:target3");
+    (1..=3).for_each(|ns| {
+        (1..=3).for_each(|i| {
+            let id = format!("v{}", i);
+            VariableBuilder::new(&NodeId::new(ns, i), &id, &id)
+                .data_type(DataTypeId::Int32)
+                .value(0i32)
+                .organized_by(ObjectId::ObjectsFolder)
+                .insert(&mut address_space);
+        });
+    });
+    Arc::new(RwLock::new(address_space))
+}
+
+fn set_value(address_space: &Arc<RwLock<AddressSpace>>, node_id: NodeId, value: i32) {
+    let now = DateTime::now();
+    let mut address_space = address_space.write().unwrap();
+    let variable = address_space.find_variable_mut(node_id).unwrap();
+    let _ = variable.set_value_direct(value, StatusCode::Good, &now, &now);
+}
+
+fn get_value(address_space: &Arc<RwLock<AddressSpace>>, node_id: NodeId) -> Variant {
+    let address_space = address_space.read().unwrap();
+    address_space.get_variable_value(node_id).unwrap().value.unwrap()
+}
+
+/// Waits for the condition to be true, failing after a couple of seconds
+fn wait_for<F>(mut condition: F) where F: FnMut() -> bool {
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while !condition() {
+        assert!(Instant::now() < deadline, "Condition was not met in time");
+        thread::sleep(Duration::from_millis(10));
+    }
+}
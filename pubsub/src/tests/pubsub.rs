@@ -0,0 +1,114 @@
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use opcua_server::prelude::*;
+
+use crate::prelude::*;
+
+use super::*;
+
+fn publish_and_subscribe(port: u16, security: Option<(MessageSecurityMode, Arc<dyn KeyProvider>)>) {
+    let address_space = make_address_space();
+    let address = format!("opc.udp://127.0.0.1:{}", port);
+
+    let mut reader_group = ReaderGroup::new("ReaderGroup")
+        .reader(DataSetReader::new("Reader1", PublisherId::UInt16(1), 1, 1)
+            .target_variable(NodeId::new(2, 1))
+            .target_variable(NodeId::new(2, 2)))
+        .reader(DataSetReader::new("Reader2", PublisherId::UInt16(1), 1, 2)
+            .target_variable(NodeId::new(3, 1)));
+    let mut writer_group = WriterGroup::new("WriterGroup", 1)
+        .publishing_interval(10.0)
+        .keep_alive_time(100.0)
+        .writer(DataSetWriter::new("Writer1", 1, "DataSet1").key_frame_count(5))
+        .writer(DataSetWriter::new("Writer2", 2, "DataSet2").field_encoding(FieldEncoding::DataValue));
+    if let Some((security_mode, key_provider)) = security {
+        reader_group = reader_group.security(security_mode, key_provider.clone());
+        writer_group = writer_group.security(security_mode, key_provider);
+    }
+
+    let subscriber = Subscriber::new(address.as_str(), Arc::new(address_space.clone()))
+        .reader_group(reader_group)
+        .start()
+        .unwrap();
+    let publisher = Publisher::new(PublisherId::UInt16(1), address.as_str(), Arc::new(address_space.clone()))
+        .published_data_set(PublishedDataSet::new("DataSet1")
+            .field("v1", NodeId::new(1, 1))
+            .field("v2", NodeId::new(1, 2)))
+        .published_data_set(PublishedDataSet::new("DataSet2")
+            .field("v3", NodeId::new(1, 3)))
+        .writer_group(writer_group)
+        .start()
+        .unwrap();
+
+    // Values are copied from the published variables to the targets as they change
+    for i in 1..=5 {
+        set_value(&address_space, NodeId::new(1, 1), i * 10);
+        set_value(&address_space, NodeId::new(1, 3), i * 100);
+        wait_for(|| get_value(&address_space, NodeId::new(2, 1)) == Variant::from(i * 10) && get_value(&address_space, NodeId::new(3, 1)) == Variant::from(i * 100));
+        // Delta frames leave the unchanged field alone
+        assert_eq!(get_value(&address_space, NodeId::new(2, 2)), Variant::from(0i32));
+    }
+    set_value(&address_space, NodeId::new(1, 2), -1);
+    wait_for(|| get_value(&address_space, NodeId::new(2, 2)) == Variant::from(-1i32));
+
+    publisher.stop();
+    subscriber.stop();
+}
+
+#[test]
+fn publish_subscribe_udp() {
+    publish_and_subscribe(14840, None);
+}
+
+#[test]
+fn publish_subscribe_udp_sign_and_encrypt() {
+    let security_policy = PubSubSecurityPolicy::Aes256Ctr;
+    let key_provider = Arc::new(SecurityGroupKeys::from_keys(security_policy, vec![SecurityKeys::generate(security_policy, 1)]));
+    publish_and_subscribe(14841, Some((MessageSecurityMode::SignAndEncrypt, key_provider)));
+}
+
+#[test]
+fn subscriber_rejects_insecure_messages() {
+    let address_space = make_address_space();
+    let address = "opc.udp://127.0.0.1:14842";
+    let security_policy = PubSubSecurityPolicy::Aes128Ctr;
+    let key_provider = Arc::new(SecurityGroupKeys::from_keys(security_policy, vec![SecurityKeys::generate(security_policy, 1)]));
+
+    let subscriber = Subscriber::new(address, Arc::new(address_space.clone()))
+        .reader_group(ReaderGroup::new("ReaderGroup")
+            .security(MessageSecurityMode::Sign, key_provider)
+            .reader(DataSetReader::new("Reader1", PublisherId::UInt16(1), 1, 1)
+                .target_variable(NodeId::new(2, 1))))
+        .start()
+        .unwrap();
+    let publisher = Publisher::new(PublisherId::UInt16(1), address, Arc::new(address_space.clone()))
+        .published_data_set(PublishedDataSet::new("DataSet1").field("v1", NodeId::new(1, 1)))
+        .writer_group(WriterGroup::new("WriterGroup", 1)
+            .publishing_interval(10.0)
+            .writer(DataSetWriter::new("Writer1", 1, "DataSet1")))
+        .start()
+        .unwrap();
+
+    set_value(&address_space, NodeId::new(1, 1), 5);
+    thread::sleep(Duration::from_millis(200));
+    assert_eq!(get_value(&address_space, NodeId::new(2, 1)), Variant::from(0i32));
+
+    publisher.stop();
+    subscriber.stop();
+}
+
+#[test]
+fn invalid_configuration() {
+    let address_space = make_address_space();
+    // Unknown data set
+    let publisher = Publisher::new(PublisherId::UInt16(1), "opc.udp://127.0.0.1:14843", Arc::new(address_space.clone()))
+        .writer_group(WriterGroup::new("WriterGroup", 1).writer(DataSetWriter::new("Writer1", 1, "DataSet1")))
+        .start();
+    assert_eq!(publisher.err().unwrap(), StatusCode::BadConfigurationError);
+    // Not a udp url
+    let subscriber = Subscriber::new("opc.tcp://127.0.0.1:14843", Arc::new(address_space))
+        .start();
+    assert_eq!(subscriber.err().unwrap(), StatusCode::BadTcpEndpointUrlInvalid);
+}
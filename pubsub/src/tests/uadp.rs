@@ -0,0 +1,215 @@
+use std::sync::Arc;
+
+use opcua_types::{*, encoding::DecodingLimits, service_types::{ConfigurationVersionDataType, MessageSecurityMode}, status_code::StatusCode};
+
+use crate::{
+    security::*,
+    subscriber::is_newer_sequence_number,
+    uadp::*,
+};
+
+fn network_message(messages: Vec<DataSetMessage>) -> NetworkMessage {
+    NetworkMessage {
+        publisher_id: Some(PublisherId::UInt16(100)),
+        data_set_class_id: None,
+        group_header: Some(GroupHeader {
+            writer_group_id: Some(10),
+            group_version: Some(12345),
+            network_message_number: Some(1),
+            sequence_number: Some(65535),
+        }),
+        timestamp: Some(DateTime::now()),
+        security_header: None,
+        messages,
+    }
+}
+
+fn data_set_message(data_set_writer_id: u16, field_encoding: FieldEncoding, payload: DataSetMessagePayload) -> DataSetMessage {
+    DataSetMessage {
+        data_set_writer_id,
+        sequence_number: Some(7),
+        timestamp: Some(DateTime::now()),
+        status: None,
+        configuration_version: None,
+        field_encoding,
+        payload,
+    }
+}
+
+fn no_keys(_: &NetworkMessage) -> Option<Arc<dyn KeyProvider>> {
+    None
+}
+
+fn roundtrip(message: &NetworkMessage) -> NetworkMessage {
+    let data = message.encode(None).unwrap();
+    NetworkMessage::decode(&data, &DecodingLimits::default(), no_keys).unwrap()
+}
+
+#[test]
+fn key_frame() {
+    let message = network_message(vec![
+        data_set_message(1, FieldEncoding::Variant, DataSetMessagePayload::KeyFrame(vec![
+            DataValue::value_only(1i32), DataValue::value_only("hello"), DataValue::value_only(3.5f64)
+        ]))
+    ]);
+    assert_eq!(roundtrip(&message), message);
+
+    // Publisher id and writer ids in the expected places
+    let data = message.encode(None).unwrap();
+    assert_eq!(data[0], 0xf1);
+    assert_eq!(data[1], 0x21);
+    assert_eq!(&data[2..4], &[100, 0]);
+}
+
+#[test]
+fn data_value_fields() {
+    let now = DateTime::now();
+    let message = network_message(vec![
+        data_set_message(1, FieldEncoding::DataValue, DataSetMessagePayload::KeyFrame(vec![
+            DataValue {
+                value: Some(Variant::from(1i32)),
+                status: Some(StatusCode::Good),
+                source_timestamp: Some(now.clone()),
+                ..Default::default()
+            },
+            DataValue {
+                value: None,
+                status: Some(StatusCode::BadNodeIdUnknown),
+                ..Default::default()
+            },
+        ]))
+    ]);
+    assert_eq!(roundtrip(&message), message);
+}
+
+#[test]
+fn delta_frame_and_keep_alive() {
+    let mut keep_alive = data_set_message(3, FieldEncoding::Variant, DataSetMessagePayload::KeepAlive);
+    keep_alive.status = Some(StatusCode::UncertainSubNormal);
+    keep_alive.configuration_version = Some(ConfigurationVersionDataType { major_version: 1, minor_version: 2 });
+    let message = network_message(vec![
+        data_set_message(1, FieldEncoding::Variant, DataSetMessagePayload::DeltaFrame(vec![
+            (2, DataValue::value_only(5u16)), (7, DataValue::value_only(true))
+        ])),
+        data_set_message(2, FieldEncoding::Variant, DataSetMessagePayload::KeyFrame(vec![])),
+        keep_alive,
+    ]);
+    assert_eq!(roundtrip(&message), message);
+}
+
+#[test]
+fn minimal_headers() {
+    let mut message = network_message(vec![]);
+    message.publisher_id = Some(PublisherId::String(UAString::from("publisher")));
+    message.group_header = None;
+    message.timestamp = None;
+    assert_eq!(roundtrip(&message), message);
+
+    message.publisher_id = Some(PublisherId::UInt64(1 << 40));
+    message.data_set_class_id = Some(Guid::new());
+    assert_eq!(roundtrip(&message), message);
+}
+
+#[test]
+fn invalid_messages() {
+    let message = network_message(vec![
+        data_set_message(1, FieldEncoding::Variant, DataSetMessagePayload::KeyFrame(vec![DataValue::value_only(1i32)]))
+    ]);
+    let mut data = message.encode(None).unwrap();
+    let decoding_limits = DecodingLimits::default();
+
+    // Truncated
+    assert!(NetworkMessage::decode(&data[..data.len() - 2], &decoding_limits, no_keys).is_err());
+
+    // Chunked messages are not supported
+    data[1] |= 0x80;
+    data.insert(2, 0x01);
+    assert!(NetworkMessage::decode(&data, &decoding_limits, no_keys).is_err());
+
+    // Wrong version
+    let mut data = message.encode(None).unwrap();
+    data[0] = 0xf2;
+    assert!(NetworkMessage::decode(&data, &decoding_limits, no_keys).is_err());
+}
+
+fn secured_message(security_mode: MessageSecurityMode, keys: &SecurityKeys) -> NetworkMessage {
+    let mut message = network_message(vec![
+        data_set_message(1, FieldEncoding::Variant, DataSetMessagePayload::KeyFrame(vec![
+            DataValue::value_only("secret value"), DataValue::value_only(42i32)
+        ]))
+    ]);
+    message.security_header = Some(SecurityHeader::new(security_mode, keys, 1));
+    message
+}
+
+#[test]
+fn sign_and_encrypt() {
+    [PubSubSecurityPolicy::Aes128Ctr, PubSubSecurityPolicy::Aes256Ctr].iter().for_each(|security_policy| {
+        let keys = SecurityKeys::generate(*security_policy, 5);
+        let key_provider: Arc<dyn KeyProvider> = Arc::new(SecurityGroupKeys::from_keys(*security_policy, vec![keys.clone()]));
+        let decoding_limits = DecodingLimits::default();
+
+        [MessageSecurityMode::Sign, MessageSecurityMode::SignAndEncrypt].iter().for_each(|security_mode| {
+            let message = secured_message(*security_mode, &keys);
+            let data = message.encode(Some(&keys)).unwrap();
+            let decoded = NetworkMessage::decode(&data, &decoding_limits, |_| Some(key_provider.clone())).unwrap();
+            assert_eq!(decoded, message);
+            assert_eq!(decoded.security_mode(), *security_mode);
+
+            // The secret is only visible when the message is not encrypted
+            let plain_text = b"secret value";
+            let visible = data.windows(plain_text.len()).any(|w| w == plain_text);
+            assert_eq!(visible, *security_mode == MessageSecurityMode::Sign);
+
+            // Tampering anywhere breaks the signature
+            [3, data.len() / 2, data.len() - 1].iter().for_each(|i| {
+                let mut data = data.clone();
+                data[*i] ^= 0x01;
+                assert_eq!(NetworkMessage::decode(&data, &decoding_limits, |_| Some(key_provider.clone())).unwrap_err(), StatusCode::BadSecurityChecksFailed);
+            });
+
+            // Without keys or with the wrong keys the message is rejected
+            assert!(NetworkMessage::decode(&data, &decoding_limits, no_keys).is_err());
+            let wrong_keys: Arc<dyn KeyProvider> = Arc::new(SecurityGroupKeys::from_keys(*security_policy, vec![SecurityKeys::generate(*security_policy, 5)]));
+            assert!(NetworkMessage::decode(&data, &decoding_limits, |_| Some(wrong_keys.clone())).is_err());
+
+            // The keys must match the token in the header
+            assert!(message.encode(Some(&SecurityKeys::generate(*security_policy, 6))).is_err());
+            assert!(message.encode(None).is_err());
+        });
+    });
+}
+
+#[test]
+fn security_group_keys() {
+    let security_policy = PubSubSecurityPolicy::Aes256Ctr;
+    assert_eq!(PubSubSecurityPolicy::from_uri(security_policy.to_uri()), Some(security_policy));
+    assert_eq!(security_policy.key_data_length(), 68);
+
+    let key1 = SecurityKeys::generate(security_policy, 0);
+    let key2 = SecurityKeys::generate(security_policy, 0);
+    let key_provider = SecurityGroupKeys::new(security_policy);
+    assert!(key_provider.current_keys().is_none());
+    key_provider.set_keys(20, &[key1.key_data(), key2.key_data()]).unwrap();
+    assert_eq!(key_provider.current_keys().unwrap().token_id, 20);
+    assert_eq!(key_provider.current_keys().unwrap().signing_key, key1.signing_key);
+    assert_eq!(key_provider.keys(21).unwrap().encrypting_key, key2.encrypting_key);
+    assert!(key_provider.next_token());
+    assert_eq!(key_provider.current_keys().unwrap().token_id, 21);
+    assert!(!key_provider.next_token());
+
+    // Key data of the wrong length
+    assert!(key_provider.set_keys(1, &[ByteString::from(vec![0u8; 10])]).is_err());
+}
+
+#[test]
+fn sequence_numbers() {
+    assert!(is_newer_sequence_number(2, 1));
+    assert!(!is_newer_sequence_number(1, 1));
+    assert!(!is_newer_sequence_number(1, 2));
+    assert!(is_newer_sequence_number(0, 65535));
+    assert!(is_newer_sequence_number(10, 65530));
+    assert!(!is_newer_sequence_number(65530, 10));
+    assert!(is_newer_sequence_number(32767, 0));
+    assert!(!is_newer_sequence_number(32768, 0));
+}
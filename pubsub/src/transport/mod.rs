@@ -0,0 +1,47 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! The transports that NetworkMessages are sent and received over. The scheme of the address url
+//! of a connection chooses the transport, `opc.udp://` for UDP datagrams and `mqtt://` or
+//! `mqtts://` for an MQTT broker.
+
+use opcua_types::status_code::StatusCode;
+
+pub mod mqtt;
+pub mod udp;
+
+/// Sends and receives encoded NetworkMessages
+pub trait NetworkTransport: Send {
+    /// Sends a message. A broker transport publishes it to the queue and, if `retain` is true,
+    /// keeps it for subscribers that arrive later. Datagram transports ignore both.
+    fn send(&mut self, data: &[u8], queue_name: Option<&str>, retain: bool) -> Result<(), StatusCode>;
+
+    /// Receives a message. Returns `None` if no message arrived in a short time, which lets the
+    /// caller check if it has been told to stop.
+    fn receive(&mut self) -> Result<Option<Vec<u8>>, StatusCode>;
+}
+
+/// Test if the url is for a broker transport, where messages are sent to queues
+pub fn is_broker_url(address: &str) -> bool {
+    mqtt::is_mqtt_url(address)
+}
+
+/// Creates the transport that a publisher sends to the address url with
+pub(crate) fn publisher_transport(address: &str) -> Result<Box<dyn NetworkTransport>, StatusCode> {
+    if mqtt::is_mqtt_url(address) {
+        Ok(Box::new(mqtt::MqttTransport::publisher(address)?))
+    } else {
+        Ok(Box::new(udp::UdpTransport::publisher(address)?))
+    }
+}
+
+/// Creates the transport that a subscriber receives from the address url with. A broker
+/// transport subscribes to the queues.
+pub(crate) fn subscriber_transport(address: &str, queue_names: Vec<String>) -> Result<Box<dyn NetworkTransport>, StatusCode> {
+    if mqtt::is_mqtt_url(address) {
+        Ok(Box::new(mqtt::MqttTransport::subscriber(address, queue_names)?))
+    } else {
+        Ok(Box::new(udp::UdpTransport::subscriber(address)?))
+    }
+}
@@ -0,0 +1,128 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! The UDP transport, OPC UA Part 14 7.3.2. Each NetworkMessage is sent in one datagram to a
+//! unicast or multicast address given by an `opc.udp://` url.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs, UdpSocket};
+use std::time::Duration;
+
+use opcua_core::comms::url::{hostname_port_from_url, is_opc_ua_udp_url};
+use opcua_types::{constants::DEFAULT_OPC_UA_SERVER_PORT, status_code::StatusCode};
+
+use super::NetworkTransport;
+
+/// Largest message that fits in a datagram. Chunking of larger messages is not supported.
+const MAX_DATAGRAM_SIZE: usize = 65535;
+/// How long a receive waits before returning so the caller can check if it should stop
+const RECEIVE_TIMEOUT_MS: u64 = 100;
+
+pub struct UdpTransport {
+    socket: UdpSocket,
+    address: SocketAddr,
+    buffer: Vec<u8>,
+}
+
+impl UdpTransport {
+    /// Creates a transport that sends to the address of the url, e.g. `opc.udp://239.0.0.1:4840`
+    pub fn publisher(url: &str) -> Result<UdpTransport, StatusCode> {
+        let address = Self::address_from_url(url)?;
+        let bind_address: SocketAddr = if address.is_ipv4() {
+            (Ipv4Addr::UNSPECIFIED, 0).into()
+        } else {
+            (Ipv6Addr::UNSPECIFIED, 0).into()
+        };
+        let socket = Self::bind(bind_address)?;
+        if address.ip().is_multicast() && address.is_ipv4() {
+            socket.set_multicast_ttl_v4(1).map_err(|_| StatusCode::BadCommunicationError)?;
+        }
+        Ok(UdpTransport {
+            socket,
+            address,
+            buffer: Vec::new(),
+        })
+    }
+
+    /// Creates a transport that receives messages sent to the address of the url. A multicast
+    /// address is joined on the default interface.
+    pub fn subscriber(url: &str) -> Result<UdpTransport, StatusCode> {
+        let address = Self::address_from_url(url)?;
+        let socket = match address.ip() {
+            IpAddr::V4(ip) if ip.is_multicast() => {
+                let socket = Self::bind((Ipv4Addr::UNSPECIFIED, address.port()).into())?;
+                socket.join_multicast_v4(&ip, &Ipv4Addr::UNSPECIFIED).map_err(|err| {
+                    error!("Cannot join multicast group {}, {}", ip, err);
+                    StatusCode::BadCommunicationError
+                })?;
+                socket
+            }
+            IpAddr::V6(ip) if ip.is_multicast() => {
+                let socket = Self::bind((Ipv6Addr::UNSPECIFIED, address.port()).into())?;
+                socket.join_multicast_v6(&ip, 0).map_err(|err| {
+                    error!("Cannot join multicast group {}, {}", ip, err);
+                    StatusCode::BadCommunicationError
+                })?;
+                socket
+            }
+            _ => Self::bind(address)?
+        };
+        socket.set_read_timeout(Some(Duration::from_millis(RECEIVE_TIMEOUT_MS))).map_err(|_| StatusCode::BadCommunicationError)?;
+        Ok(UdpTransport {
+            socket,
+            address,
+            buffer: vec![0u8; MAX_DATAGRAM_SIZE],
+        })
+    }
+
+    /// Returns the address that the socket is bound to
+    pub fn local_addr(&self) -> Result<SocketAddr, StatusCode> {
+        self.socket.local_addr().map_err(|_| StatusCode::BadUnexpectedError)
+    }
+
+    fn bind(address: SocketAddr) -> Result<UdpSocket, StatusCode> {
+        UdpSocket::bind(address).map_err(|err| {
+            error!("Cannot bind UDP socket to {}, {}", address, err);
+            StatusCode::BadResourceUnavailable
+        })
+    }
+
+    fn address_from_url(url: &str) -> Result<SocketAddr, StatusCode> {
+        if !is_opc_ua_udp_url(url) {
+            error!("Url {} is not an opc.udp url", url);
+            return Err(StatusCode::BadTcpEndpointUrlInvalid);
+        }
+        let (host, port) = hostname_port_from_url(url, DEFAULT_OPC_UA_SERVER_PORT)?;
+        (host.as_str(), port).to_socket_addrs()
+            .ok()
+            .and_then(|mut addrs| addrs.next())
+            .ok_or_else(|| {
+                error!("Cannot resolve the address of url {}", url);
+                StatusCode::BadTcpEndpointUrlInvalid
+            })
+    }
+}
+
+impl NetworkTransport for UdpTransport {
+    fn send(&mut self, data: &[u8], _queue_name: Option<&str>, _retain: bool) -> Result<(), StatusCode> {
+        if data.len() > MAX_DATAGRAM_SIZE {
+            error!("NetworkMessage of {} bytes is too large for a datagram", data.len());
+            return Err(StatusCode::BadEncodingLimitsExceeded);
+        }
+        self.socket.send_to(data, self.address).map(|_| ()).map_err(|err| {
+            error!("Cannot send NetworkMessage to {}, {}", self.address, err);
+            StatusCode::BadCommunicationError
+        })
+    }
+
+    fn receive(&mut self) -> Result<Option<Vec<u8>>, StatusCode> {
+        match self.socket.recv_from(&mut self.buffer) {
+            Ok((size, _)) => Ok(Some(self.buffer[..size].to_vec())),
+            Err(ref err) if err.kind() == std::io::ErrorKind::WouldBlock || err.kind() == std::io::ErrorKind::TimedOut => Ok(None),
+            Err(err) => {
+                error!("Cannot receive NetworkMessage, {}", err);
+                Err(StatusCode::BadCommunicationError)
+            }
+        }
+    }
+}
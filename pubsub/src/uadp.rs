@@ -0,0 +1,732 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! Encoding and decoding of UADP NetworkMessages, OPC UA Part 14 7.2.2. A NetworkMessage carries
+//! the DataSetMessages of one WriterGroup along with the headers that identify the publisher and
+//! group, and the security header when the message is signed or encrypted.
+//!
+//! Chunked messages, promoted fields, discovery messages, event DataSetMessages and the RawData
+//! field encoding are not supported.
+
+use std::fmt;
+use std::io::{Cursor, Read, Write};
+use std::sync::Arc;
+
+use opcua_types::{
+    *,
+    service_types::{ConfigurationVersionDataType, MessageSecurityMode},
+    status_code::StatusCode,
+};
+
+use crate::security::{KeyProvider, PubSubSecurityPolicy, SecurityKeys};
+
+/// Version of UADP in the first byte of the NetworkMessage
+pub const UADP_VERSION: u8 = 1;
+
+// UADPFlags, upper 4 bits of the first byte
+const UADP_FLAG_PUBLISHER_ID: u8 = 0x10;
+const UADP_FLAG_GROUP_HEADER: u8 = 0x20;
+const UADP_FLAG_PAYLOAD_HEADER: u8 = 0x40;
+const UADP_FLAG_EXTENDED_FLAGS1: u8 = 0x80;
+
+// ExtendedFlags1
+const EXT1_PUBLISHER_ID_TYPE_MASK: u8 = 0x07;
+const EXT1_DATA_SET_CLASS_ID: u8 = 0x08;
+const EXT1_SECURITY: u8 = 0x10;
+const EXT1_TIMESTAMP: u8 = 0x20;
+const EXT1_PICO_SECONDS: u8 = 0x40;
+const EXT1_EXTENDED_FLAGS2: u8 = 0x80;
+
+// ExtendedFlags2
+const EXT2_CHUNK: u8 = 0x01;
+const EXT2_PROMOTED_FIELDS: u8 = 0x02;
+const EXT2_MESSAGE_TYPE_MASK: u8 = 0x1c;
+
+// GroupFlags
+const GROUP_FLAG_WRITER_GROUP_ID: u8 = 0x01;
+const GROUP_FLAG_GROUP_VERSION: u8 = 0x02;
+const GROUP_FLAG_NETWORK_MESSAGE_NUMBER: u8 = 0x04;
+const GROUP_FLAG_SEQUENCE_NUMBER: u8 = 0x08;
+
+// SecurityFlags
+const SECURITY_FLAG_SIGNED: u8 = 0x01;
+const SECURITY_FLAG_ENCRYPTED: u8 = 0x02;
+const SECURITY_FLAG_FOOTER: u8 = 0x04;
+
+// DataSetFlags1
+const DS_FLAG_VALID: u8 = 0x01;
+const DS_FLAG_FIELD_ENCODING_MASK: u8 = 0x06;
+const DS_FLAG_FIELD_ENCODING_VARIANT: u8 = 0x00;
+const DS_FLAG_FIELD_ENCODING_DATA_VALUE: u8 = 0x04;
+const DS_FLAG_SEQUENCE_NUMBER: u8 = 0x08;
+const DS_FLAG_STATUS: u8 = 0x10;
+const DS_FLAG_MAJOR_VERSION: u8 = 0x20;
+const DS_FLAG_MINOR_VERSION: u8 = 0x40;
+const DS_FLAG_FLAGS2: u8 = 0x80;
+
+// DataSetFlags2
+const DS2_MESSAGE_TYPE_MASK: u8 = 0x0f;
+const DS2_KEY_FRAME: u8 = 0x00;
+const DS2_DELTA_FRAME: u8 = 0x01;
+const DS2_KEEP_ALIVE: u8 = 0x03;
+const DS2_TIMESTAMP: u8 = 0x10;
+const DS2_PICO_SECONDS: u8 = 0x20;
+
+/// Identifies the publisher of a NetworkMessage. The variant decides how it is encoded.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PublisherId {
+    Byte(u8),
+    UInt16(u16),
+    UInt32(u32),
+    UInt64(u64),
+    String(UAString),
+}
+
+impl PublisherId {
+    fn type_bits(&self) -> u8 {
+        match self {
+            PublisherId::Byte(_) => 0,
+            PublisherId::UInt16(_) => 1,
+            PublisherId::UInt32(_) => 2,
+            PublisherId::UInt64(_) => 3,
+            PublisherId::String(_) => 4,
+        }
+    }
+
+    fn encode<S: Write>(&self, stream: &mut S) -> EncodingResult<usize> {
+        match self {
+            PublisherId::Byte(v) => v.encode(stream),
+            PublisherId::UInt16(v) => v.encode(stream),
+            PublisherId::UInt32(v) => v.encode(stream),
+            PublisherId::UInt64(v) => v.encode(stream),
+            PublisherId::String(v) => v.encode(stream),
+        }
+    }
+
+    fn decode<S: Read>(stream: &mut S, type_bits: u8, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        Ok(match type_bits {
+            0 => PublisherId::Byte(u8::decode(stream, decoding_limits)?),
+            1 => PublisherId::UInt16(u16::decode(stream, decoding_limits)?),
+            2 => PublisherId::UInt32(u32::decode(stream, decoding_limits)?),
+            3 => PublisherId::UInt64(u64::decode(stream, decoding_limits)?),
+            4 => PublisherId::String(UAString::decode(stream, decoding_limits)?),
+            _ => {
+                error!("Publisher id type {} is invalid", type_bits);
+                return Err(StatusCode::BadDecodingError);
+            }
+        })
+    }
+}
+
+/// The publisher id as it is written in JSON messages and topic names
+impl fmt::Display for PublisherId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PublisherId::Byte(v) => write!(f, "{}", v),
+            PublisherId::UInt16(v) => write!(f, "{}", v),
+            PublisherId::UInt32(v) => write!(f, "{}", v),
+            PublisherId::UInt64(v) => write!(f, "{}", v),
+            PublisherId::String(v) => write!(f, "{}", v.as_ref()),
+        }
+    }
+}
+
+impl From<u8> for PublisherId {
+    fn from(v: u8) -> Self { PublisherId::Byte(v) }
+}
+
+impl From<u16> for PublisherId {
+    fn from(v: u16) -> Self { PublisherId::UInt16(v) }
+}
+
+impl From<u32> for PublisherId {
+    fn from(v: u32) -> Self { PublisherId::UInt32(v) }
+}
+
+impl From<u64> for PublisherId {
+    fn from(v: u64) -> Self { PublisherId::UInt64(v) }
+}
+
+impl<'a> From<&'a str> for PublisherId {
+    fn from(v: &'a str) -> Self { PublisherId::String(UAString::from(v)) }
+}
+
+/// The publisher id as it is held in the PubSub configuration
+impl<'a> From<&'a PublisherId> for Variant {
+    fn from(v: &'a PublisherId) -> Self {
+        match v {
+            PublisherId::Byte(v) => Variant::from(*v),
+            PublisherId::UInt16(v) => Variant::from(*v),
+            PublisherId::UInt32(v) => Variant::from(*v),
+            PublisherId::UInt64(v) => Variant::from(*v),
+            PublisherId::String(v) => Variant::from(v.clone()),
+        }
+    }
+}
+
+impl PublisherId {
+    /// Gets the publisher id from the value it is held as in the PubSub configuration, which must
+    /// be an unsigned integer or a string
+    pub fn from_variant(value: &Variant) -> Option<PublisherId> {
+        match value {
+            Variant::Byte(v) => Some(PublisherId::Byte(*v)),
+            Variant::UInt16(v) => Some(PublisherId::UInt16(*v)),
+            Variant::UInt32(v) => Some(PublisherId::UInt32(*v)),
+            Variant::UInt64(v) => Some(PublisherId::UInt64(*v)),
+            Variant::String(v) if !v.is_null() => Some(PublisherId::String(v.clone())),
+            _ => None
+        }
+    }
+}
+
+/// How the fields of a DataSetMessage are encoded
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FieldEncoding {
+    /// Each field is a Variant
+    Variant,
+    /// Each field is a DataValue, carrying its status and timestamps
+    DataValue,
+}
+
+/// The fields that a DataSetMessage carries
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataSetMessagePayload {
+    /// Every field of the DataSet
+    KeyFrame(Vec<DataValue>),
+    /// The fields that have changed since the last message, with their index in the DataSet
+    DeltaFrame(Vec<(u16, DataValue)>),
+    /// No fields, it only says that the writer is still there
+    KeepAlive,
+}
+
+/// A DataSetMessage, the values of one DataSet from one DataSetWriter
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataSetMessage {
+    pub data_set_writer_id: u16,
+    pub sequence_number: Option<u16>,
+    pub timestamp: Option<DateTime>,
+    pub status: Option<StatusCode>,
+    pub configuration_version: Option<ConfigurationVersionDataType>,
+    pub field_encoding: FieldEncoding,
+    pub payload: DataSetMessagePayload,
+}
+
+impl DataSetMessage {
+    fn encode<S: Write>(&self, stream: &mut S) -> EncodingResult<usize> {
+        let mut flags1 = DS_FLAG_VALID | DS_FLAG_FLAGS2;
+        flags1 |= match self.field_encoding {
+            FieldEncoding::Variant => DS_FLAG_FIELD_ENCODING_VARIANT,
+            FieldEncoding::DataValue => DS_FLAG_FIELD_ENCODING_DATA_VALUE,
+        };
+        if self.sequence_number.is_some() {
+            flags1 |= DS_FLAG_SEQUENCE_NUMBER;
+        }
+        if self.status.is_some() {
+            flags1 |= DS_FLAG_STATUS;
+        }
+        if self.configuration_version.is_some() {
+            flags1 |= DS_FLAG_MAJOR_VERSION | DS_FLAG_MINOR_VERSION;
+        }
+        let mut flags2 = match self.payload {
+            DataSetMessagePayload::KeyFrame(_) => DS2_KEY_FRAME,
+            DataSetMessagePayload::DeltaFrame(_) => DS2_DELTA_FRAME,
+            DataSetMessagePayload::KeepAlive => DS2_KEEP_ALIVE,
+        };
+        if self.timestamp.is_some() {
+            flags2 |= DS2_TIMESTAMP;
+        }
+
+        let mut size = write_u8(stream, flags1)?;
+        size += write_u8(stream, flags2)?;
+        if let Some(sequence_number) = self.sequence_number {
+            size += sequence_number.encode(stream)?;
+        }
+        if let Some(ref timestamp) = self.timestamp {
+            size += timestamp.encode(stream)?;
+        }
+        if let Some(status) = self.status {
+            // Only the upper 16 bits of the status code are sent
+            size += write_u16(stream, (status.bits() >> 16) as u16)?;
+        }
+        if let Some(ref configuration_version) = self.configuration_version {
+            size += configuration_version.major_version.encode(stream)?;
+            size += configuration_version.minor_version.encode(stream)?;
+        }
+        match self.payload {
+            DataSetMessagePayload::KeyFrame(ref fields) => {
+                size += write_u16(stream, Self::field_count(fields.len())?)?;
+                for field in fields {
+                    size += self.encode_field(stream, field)?;
+                }
+            }
+            DataSetMessagePayload::DeltaFrame(ref fields) => {
+                size += write_u16(stream, Self::field_count(fields.len())?)?;
+                for (index, field) in fields {
+                    size += index.encode(stream)?;
+                    size += self.encode_field(stream, field)?;
+                }
+            }
+            DataSetMessagePayload::KeepAlive => {}
+        }
+        Ok(size)
+    }
+
+    fn field_count(len: usize) -> EncodingResult<u16> {
+        if len > u16::MAX as usize {
+            error!("DataSetMessage has too many fields {}", len);
+            Err(StatusCode::BadEncodingLimitsExceeded)
+        } else {
+            Ok(len as u16)
+        }
+    }
+
+    fn encode_field<S: Write>(&self, stream: &mut S, field: &DataValue) -> EncodingResult<usize> {
+        match self.field_encoding {
+            FieldEncoding::Variant => {
+                match field.value {
+                    Some(ref value) => value.encode(stream),
+                    None => Variant::Empty.encode(stream),
+                }
+            }
+            FieldEncoding::DataValue => field.encode(stream),
+        }
+    }
+
+    fn decode<S: Read>(stream: &mut S, data_set_writer_id: u16, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let flags1 = read_u8(stream)?;
+        let flags2 = if flags1 & DS_FLAG_FLAGS2 != 0 { read_u8(stream)? } else { DS2_KEY_FRAME };
+        if flags1 & DS_FLAG_VALID == 0 {
+            error!("DataSetMessage from writer {} is not valid", data_set_writer_id);
+            return Err(StatusCode::BadDecodingError);
+        }
+        let field_encoding = match flags1 & DS_FLAG_FIELD_ENCODING_MASK {
+            DS_FLAG_FIELD_ENCODING_VARIANT => FieldEncoding::Variant,
+            DS_FLAG_FIELD_ENCODING_DATA_VALUE => FieldEncoding::DataValue,
+            _ => {
+                error!("DataSetMessage from writer {} uses an unsupported field encoding", data_set_writer_id);
+                return Err(StatusCode::BadDecodingError);
+            }
+        };
+        let sequence_number = if flags1 & DS_FLAG_SEQUENCE_NUMBER != 0 {
+            Some(u16::decode(stream, decoding_limits)?)
+        } else {
+            None
+        };
+        let timestamp = if flags2 & DS2_TIMESTAMP != 0 {
+            Some(DateTime::decode(stream, decoding_limits)?)
+        } else {
+            None
+        };
+        if flags2 & DS2_PICO_SECONDS != 0 {
+            let _ = read_u16(stream)?;
+        }
+        let status = if flags1 & DS_FLAG_STATUS != 0 {
+            Some(StatusCode::from_bits_truncate((read_u16(stream)? as u32) << 16))
+        } else {
+            None
+        };
+        let major_version = if flags1 & DS_FLAG_MAJOR_VERSION != 0 { Some(read_u32(stream)?) } else { None };
+        let minor_version = if flags1 & DS_FLAG_MINOR_VERSION != 0 { Some(read_u32(stream)?) } else { None };
+        let configuration_version = if major_version.is_some() || minor_version.is_some() {
+            Some(ConfigurationVersionDataType {
+                major_version: major_version.unwrap_or(0),
+                minor_version: minor_version.unwrap_or(0),
+            })
+        } else {
+            None
+        };
+
+        let mut message = DataSetMessage {
+            data_set_writer_id,
+            sequence_number,
+            timestamp,
+            status,
+            configuration_version,
+            field_encoding,
+            payload: DataSetMessagePayload::KeepAlive,
+        };
+        message.payload = match flags2 & DS2_MESSAGE_TYPE_MASK {
+            DS2_KEY_FRAME => {
+                let field_count = read_u16(stream)? as usize;
+                Self::check_field_count(field_count, decoding_limits)?;
+                let fields = (0..field_count)
+                    .map(|_| message.decode_field(stream, decoding_limits))
+                    .collect::<EncodingResult<Vec<_>>>()?;
+                DataSetMessagePayload::KeyFrame(fields)
+            }
+            DS2_DELTA_FRAME => {
+                let field_count = read_u16(stream)? as usize;
+                Self::check_field_count(field_count, decoding_limits)?;
+                let fields = (0..field_count)
+                    .map(|_| {
+                        let index = read_u16(stream)?;
+                        Ok((index, message.decode_field(stream, decoding_limits)?))
+                    })
+                    .collect::<EncodingResult<Vec<_>>>()?;
+                DataSetMessagePayload::DeltaFrame(fields)
+            }
+            DS2_KEEP_ALIVE => DataSetMessagePayload::KeepAlive,
+            message_type => {
+                error!("DataSetMessage type {} is not supported", message_type);
+                return Err(StatusCode::BadDecodingError);
+            }
+        };
+        Ok(message)
+    }
+
+    fn check_field_count(field_count: usize, decoding_limits: &DecodingLimits) -> EncodingResult<()> {
+        if field_count > decoding_limits.max_array_length {
+            error!("DataSetMessage field count {} exceeds decoding limit {}", field_count, decoding_limits.max_array_length);
+            Err(StatusCode::BadDecodingError)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn decode_field<S: Read>(&self, stream: &mut S, decoding_limits: &DecodingLimits) -> EncodingResult<DataValue> {
+        match self.field_encoding {
+            FieldEncoding::Variant => Ok(DataValue::value_only(Variant::decode(stream, decoding_limits)?)),
+            FieldEncoding::DataValue => DataValue::decode(stream, decoding_limits),
+        }
+    }
+}
+
+/// The group header, which identifies the WriterGroup and numbers its NetworkMessages
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupHeader {
+    pub writer_group_id: Option<u16>,
+    pub group_version: Option<u32>,
+    pub network_message_number: Option<u16>,
+    pub sequence_number: Option<u16>,
+}
+
+/// The security header of a signed or encrypted NetworkMessage
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecurityHeader {
+    pub signed: bool,
+    pub encrypted: bool,
+    /// The token of the security group keys that secure the message
+    pub security_token_id: u32,
+    /// The nonce used to encrypt the message
+    pub message_nonce: Vec<u8>,
+}
+
+impl SecurityHeader {
+    /// Creates the header for a message secured with the keys, with a fresh nonce made from a
+    /// random part and the sequence number of the message
+    pub fn new(security_mode: MessageSecurityMode, keys: &SecurityKeys, sequence_number: u32) -> SecurityHeader {
+        let mut message_nonce = vec![0u8; PubSubSecurityPolicy::MESSAGE_NONCE_LENGTH];
+        opcua_crypto::random::bytes(&mut message_nonce[0..4]);
+        message_nonce[4..].copy_from_slice(&sequence_number.to_le_bytes());
+        SecurityHeader {
+            signed: security_mode != MessageSecurityMode::None,
+            encrypted: security_mode == MessageSecurityMode::SignAndEncrypt,
+            security_token_id: keys.token_id,
+            message_nonce,
+        }
+    }
+
+    pub fn security_mode(&self) -> MessageSecurityMode {
+        if self.encrypted {
+            MessageSecurityMode::SignAndEncrypt
+        } else if self.signed {
+            MessageSecurityMode::Sign
+        } else {
+            MessageSecurityMode::None
+        }
+    }
+}
+
+/// A UADP NetworkMessage
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkMessage {
+    pub publisher_id: Option<PublisherId>,
+    pub data_set_class_id: Option<Guid>,
+    pub group_header: Option<GroupHeader>,
+    pub timestamp: Option<DateTime>,
+    pub security_header: Option<SecurityHeader>,
+    pub messages: Vec<DataSetMessage>,
+}
+
+impl NetworkMessage {
+    /// Returns the security mode that the message was or will be sent with
+    pub fn security_mode(&self) -> MessageSecurityMode {
+        self.security_header.as_ref().map(|h| h.security_mode()).unwrap_or(MessageSecurityMode::None)
+    }
+
+    /// Returns the writer group id from the group header, if there is one
+    pub fn writer_group_id(&self) -> Option<u16> {
+        self.group_header.as_ref().and_then(|h| h.writer_group_id)
+    }
+
+    /// Encodes the message. A message with a security header is signed and encrypted with the
+    /// keys, which must be for the token named in the header.
+    pub fn encode(&self, keys: Option<&SecurityKeys>) -> EncodingResult<Vec<u8>> {
+        if self.messages.len() > u8::MAX as usize {
+            error!("NetworkMessage has too many DataSetMessages {}", self.messages.len());
+            return Err(StatusCode::BadEncodingLimitsExceeded);
+        }
+
+        let mut stream = Cursor::new(Vec::with_capacity(256));
+        self.encode_header(&mut stream)?;
+
+        // The payload holds the size of each DataSetMessage when there is more than one
+        let mut payload = Cursor::new(Vec::with_capacity(256));
+        let messages = self.messages.iter().map(|m| {
+            let mut stream = Cursor::new(Vec::with_capacity(128));
+            m.encode(&mut stream)?;
+            Ok(stream.into_inner())
+        }).collect::<EncodingResult<Vec<_>>>()?;
+        if messages.len() > 1 {
+            for message in &messages {
+                if message.len() > u16::MAX as usize {
+                    error!("DataSetMessage is too large to send with others, {} bytes", message.len());
+                    return Err(StatusCode::BadEncodingLimitsExceeded);
+                }
+                write_u16(&mut payload, message.len() as u16)?;
+            }
+        }
+        for message in &messages {
+            payload.write_all(message).map_err(|_| StatusCode::BadEncodingError)?;
+        }
+        let mut payload = payload.into_inner();
+
+        let mut data = stream.into_inner();
+        match self.security_header {
+            Some(ref security_header) if security_header.signed || security_header.encrypted => {
+                let keys = match keys {
+                    Some(keys) if keys.token_id == security_header.security_token_id => keys,
+                    _ => {
+                        error!("NetworkMessage cannot be secured without the keys for token {}", security_header.security_token_id);
+                        return Err(StatusCode::BadSecurityChecksFailed);
+                    }
+                };
+                if security_header.encrypted {
+                    payload = keys.crypt(&security_header.message_nonce, &payload)?;
+                }
+                data.extend_from_slice(&payload);
+                if security_header.signed {
+                    let signature = keys.sign(&data)?;
+                    data.extend_from_slice(&signature);
+                }
+            }
+            _ => data.extend_from_slice(&payload),
+        }
+        Ok(data)
+    }
+
+    fn encode_header<S: Write>(&self, stream: &mut S) -> EncodingResult<usize> {
+        // The payload header is always sent so readers can pick out messages by writer id
+        let mut flags = UADP_VERSION | UADP_FLAG_PAYLOAD_HEADER | UADP_FLAG_EXTENDED_FLAGS1;
+        let mut ext_flags1 = 0u8;
+        if let Some(ref publisher_id) = self.publisher_id {
+            flags |= UADP_FLAG_PUBLISHER_ID;
+            ext_flags1 |= publisher_id.type_bits();
+        }
+        if self.group_header.is_some() {
+            flags |= UADP_FLAG_GROUP_HEADER;
+        }
+        if self.data_set_class_id.is_some() {
+            ext_flags1 |= EXT1_DATA_SET_CLASS_ID;
+        }
+        if self.security_header.is_some() {
+            ext_flags1 |= EXT1_SECURITY;
+        }
+        if self.timestamp.is_some() {
+            ext_flags1 |= EXT1_TIMESTAMP;
+        }
+
+        let mut size = write_u8(stream, flags)?;
+        size += write_u8(stream, ext_flags1)?;
+        if let Some(ref publisher_id) = self.publisher_id {
+            size += publisher_id.encode(stream)?;
+        }
+        if let Some(ref data_set_class_id) = self.data_set_class_id {
+            size += data_set_class_id.encode(stream)?;
+        }
+        if let Some(ref group_header) = self.group_header {
+            let mut group_flags = 0u8;
+            if group_header.writer_group_id.is_some() {
+                group_flags |= GROUP_FLAG_WRITER_GROUP_ID;
+            }
+            if group_header.group_version.is_some() {
+                group_flags |= GROUP_FLAG_GROUP_VERSION;
+            }
+            if group_header.network_message_number.is_some() {
+                group_flags |= GROUP_FLAG_NETWORK_MESSAGE_NUMBER;
+            }
+            if group_header.sequence_number.is_some() {
+                group_flags |= GROUP_FLAG_SEQUENCE_NUMBER;
+            }
+            size += write_u8(stream, group_flags)?;
+            if let Some(writer_group_id) = group_header.writer_group_id {
+                size += writer_group_id.encode(stream)?;
+            }
+            if let Some(group_version) = group_header.group_version {
+                size += group_version.encode(stream)?;
+            }
+            if let Some(network_message_number) = group_header.network_message_number {
+                size += network_message_number.encode(stream)?;
+            }
+            if let Some(sequence_number) = group_header.sequence_number {
+                size += sequence_number.encode(stream)?;
+            }
+        }
+        size += write_u8(stream, self.messages.len() as u8)?;
+        for message in &self.messages {
+            size += message.data_set_writer_id.encode(stream)?;
+        }
+        if let Some(ref timestamp) = self.timestamp {
+            size += timestamp.encode(stream)?;
+        }
+        if let Some(ref security_header) = self.security_header {
+            let mut security_flags = 0u8;
+            if security_header.signed {
+                security_flags |= SECURITY_FLAG_SIGNED;
+            }
+            if security_header.encrypted {
+                security_flags |= SECURITY_FLAG_ENCRYPTED;
+            }
+            size += write_u8(stream, security_flags)?;
+            size += security_header.security_token_id.encode(stream)?;
+            size += write_u8(stream, security_header.message_nonce.len() as u8)?;
+            stream.write_all(&security_header.message_nonce).map_err(|_| StatusCode::BadEncodingError)?;
+            size += security_header.message_nonce.len();
+        }
+        Ok(size)
+    }
+
+    /// Decodes a message. When the message is secured, the key provider for it is obtained from
+    /// the supplied function, which is passed the message headers so it can tell which group the
+    /// message is from. The message is rejected if its signature does not verify.
+    pub fn decode<F>(data: &[u8], decoding_limits: &DecodingLimits, key_provider: F) -> EncodingResult<NetworkMessage>
+        where F: FnOnce(&NetworkMessage) -> Option<Arc<dyn KeyProvider>>
+    {
+        let mut stream = Cursor::new(data);
+        let (mut message, data_set_writer_ids) = Self::decode_header(&mut stream, decoding_limits)?;
+        let header_size = stream.position() as usize;
+
+        let payload = match message.security_header {
+            Some(ref security_header) if security_header.signed || security_header.encrypted => {
+                let keys = key_provider(&message)
+                    .and_then(|key_provider| key_provider.keys(security_header.security_token_id))
+                    .ok_or_else(|| {
+                        error!("NetworkMessage is secured with token {} which has no keys", security_header.security_token_id);
+                        StatusCode::BadSecurityChecksFailed
+                    })?;
+                let mut payload_end = data.len();
+                if security_header.signed {
+                    // The signature is at the end and covers the rest of the message
+                    let signature_size = PubSubSecurityPolicy::Aes128Ctr.signature_size();
+                    if data.len() < header_size + signature_size {
+                        error!("NetworkMessage is too short to be signed");
+                        return Err(StatusCode::BadSecurityChecksFailed);
+                    }
+                    payload_end -= signature_size;
+                    if !keys.verify(&data[..payload_end], &data[payload_end..]) {
+                        error!("NetworkMessage signature is invalid");
+                        return Err(StatusCode::BadSecurityChecksFailed);
+                    }
+                }
+                if security_header.encrypted {
+                    keys.crypt(&security_header.message_nonce, &data[header_size..payload_end])?
+                } else {
+                    data[header_size..payload_end].to_vec()
+                }
+            }
+            _ => data[header_size..].to_vec(),
+        };
+
+        let mut stream = Cursor::new(&payload);
+        if data_set_writer_ids.len() > 1 {
+            // The sizes are not needed since each message says how many fields it has
+            for _ in 0..data_set_writer_ids.len() {
+                let _ = read_u16(&mut stream)?;
+            }
+        }
+        message.messages = data_set_writer_ids.iter()
+            .map(|data_set_writer_id| DataSetMessage::decode(&mut stream, *data_set_writer_id, decoding_limits))
+            .collect::<EncodingResult<Vec<_>>>()?;
+        Ok(message)
+    }
+
+    fn decode_header<S: Read>(stream: &mut S, decoding_limits: &DecodingLimits) -> EncodingResult<(NetworkMessage, Vec<u16>)> {
+        let flags = read_u8(stream)?;
+        if flags & 0x0f != UADP_VERSION {
+            error!("UADP version {} is not supported", flags & 0x0f);
+            return Err(StatusCode::BadDecodingError);
+        }
+        let ext_flags1 = if flags & UADP_FLAG_EXTENDED_FLAGS1 != 0 { read_u8(stream)? } else { 0 };
+        let ext_flags2 = if ext_flags1 & EXT1_EXTENDED_FLAGS2 != 0 { read_u8(stream)? } else { 0 };
+        if ext_flags2 & (EXT2_CHUNK | EXT2_PROMOTED_FIELDS | EXT2_MESSAGE_TYPE_MASK) != 0 {
+            error!("NetworkMessage with extended flags {:x} is not supported", ext_flags2);
+            return Err(StatusCode::BadDecodingError);
+        }
+
+        let publisher_id = if flags & UADP_FLAG_PUBLISHER_ID != 0 {
+            Some(PublisherId::decode(stream, ext_flags1 & EXT1_PUBLISHER_ID_TYPE_MASK, decoding_limits)?)
+        } else {
+            None
+        };
+        let data_set_class_id = if ext_flags1 & EXT1_DATA_SET_CLASS_ID != 0 {
+            Some(Guid::decode(stream, decoding_limits)?)
+        } else {
+            None
+        };
+        let group_header = if flags & UADP_FLAG_GROUP_HEADER != 0 {
+            let group_flags = read_u8(stream)?;
+            let writer_group_id = if group_flags & GROUP_FLAG_WRITER_GROUP_ID != 0 { Some(read_u16(stream)?) } else { None };
+            let group_version = if group_flags & GROUP_FLAG_GROUP_VERSION != 0 { Some(read_u32(stream)?) } else { None };
+            let network_message_number = if group_flags & GROUP_FLAG_NETWORK_MESSAGE_NUMBER != 0 { Some(read_u16(stream)?) } else { None };
+            let sequence_number = if group_flags & GROUP_FLAG_SEQUENCE_NUMBER != 0 { Some(read_u16(stream)?) } else { None };
+            Some(GroupHeader { writer_group_id, group_version, network_message_number, sequence_number })
+        } else {
+            None
+        };
+        let data_set_writer_ids = if flags & UADP_FLAG_PAYLOAD_HEADER != 0 {
+            let count = read_u8(stream)?;
+            (0..count).map(|_| read_u16(stream)).collect::<EncodingResult<Vec<_>>>()?
+        } else {
+            // Without a payload header there is one DataSetMessage from an unknown writer
+            vec![0]
+        };
+        let timestamp = if ext_flags1 & EXT1_TIMESTAMP != 0 {
+            Some(DateTime::decode(stream, decoding_limits)?)
+        } else {
+            None
+        };
+        if ext_flags1 & EXT1_PICO_SECONDS != 0 {
+            let _ = read_u16(stream)?;
+        }
+        let security_header = if ext_flags1 & EXT1_SECURITY != 0 {
+            let security_flags = read_u8(stream)?;
+            let security_token_id = read_u32(stream)?;
+            let nonce_length = read_u8(stream)? as usize;
+            let mut message_nonce = vec![0u8; nonce_length];
+            read_bytes(stream, &mut message_nonce)?;
+            if security_flags & SECURITY_FLAG_FOOTER != 0 {
+                error!("NetworkMessage with a security footer is not supported");
+                return Err(StatusCode::BadDecodingError);
+            }
+            Some(SecurityHeader {
+                signed: security_flags & SECURITY_FLAG_SIGNED != 0,
+                encrypted: security_flags & SECURITY_FLAG_ENCRYPTED != 0,
+                security_token_id,
+                message_nonce,
+            })
+        } else {
+            None
+        };
+
+        let message = NetworkMessage {
+            publisher_id,
+            data_set_class_id,
+            group_header,
+            timestamp,
+            security_header,
+            messages: Vec::new(),
+        };
+        Ok((message, data_set_writer_ids))
+    }
+}
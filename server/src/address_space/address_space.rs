@@ -4,6 +4,7 @@
 
 //! Implementation of `AddressSpace`.
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::{Arc, Mutex, RwLock};
 
 use chrono::Utc;
@@ -11,14 +12,18 @@ use chrono::Utc;
 use opcua_types::{
     *,
     node_ids::VariableId::*,
-    service_types::{BrowseDirection, CallMethodRequest, CallMethodResult, NodeClass},
+    service_types::{BrowseDirection, CallMethodRequest, CallMethodResult, EnumDefinition, NodeClass, StructureDefinition},
     status_code::StatusCode,
 };
 
 use crate::{
     address_space::{
         AttrFnGetter,
+        custom_data_types,
         node::{HasNodeId, NodeType},
+        node_manager::NodeManager,
+        nodeset_export,
+        nodeset_import,
         object::{Object, ObjectBuilder},
         references::{Reference, ReferenceDirection, References},
         variable::Variable,
@@ -120,6 +125,10 @@ macro_rules! server_diagnostics_summary {
 
 pub(crate) type MethodCallback = Box<dyn callbacks::Method + Send + Sync>;
 
+pub(crate) type AddressSpaceMethodCallback = Box<dyn callbacks::AddressSpaceMethod + Send + Sync>;
+
+pub(crate) type NodeManagerBox = Box<dyn NodeManager + Send + Sync>;
+
 const OPCUA_INTERNAL_NAMESPACE_IDX: u16 = 1;
 
 #[derive(PartialEq, Eq, Clone, Debug, Hash)]
@@ -168,8 +177,10 @@ pub struct AddressSpace {
     audit_namespace: u16,
     /// The namespace to generate sequential internal node ids
     internal_namespace: u16,
-    /// The list of all registered namespaces.
-    namespaces: Vec<String>,
+    /// The table of all registered namespaces.
+    namespaces: NamespaceMap,
+    /// Node managers which are responsible for all the nodes in their namespace
+    node_managers: HashMap<u16, NodeManagerBox>,
 }
 
 impl Default for AddressSpace {
@@ -185,7 +196,8 @@ impl Default for AddressSpace {
             // By default, there will be two standard namespaces. The first is the default
             // OPC UA namespace for its standard nodes. The second is the internal namespace used
             // by this implementation.
-            namespaces: vec!["http://opcfoundation.org/UA/".to_string()],
+            namespaces: NamespaceMap::new(),
+            node_managers: HashMap::new(),
         }
     }
 }
@@ -211,34 +223,82 @@ impl AddressSpace {
     /// The last registered namespace becomes the default namespace unless you explcitly call
     /// `set_default_namespace()` after this.
     pub fn register_namespace(&mut self, namespace: &str) -> Result<u16, ()> {
-        use std::u16;
         let now = DateTime::now();
-        if namespace.is_empty() || self.namespaces.len() == u16::MAX as usize {
-            Err(())
+        // Check if namespace already exists or not
+        if let Some(i) = self.namespace_index(namespace) {
+            // Existing namespace index
+            Ok(i)
         } else {
-            // Check if namespace already exists or not
-            if let Some(i) = self.namespace_index(namespace) {
-                // Existing namespace index
-                Ok(i)
-            } else {
-                // Add and register new namespace
-                self.namespaces.push(namespace.into());
-                self.set_namespaces(&now);
-                // New namespace index
-                let ns = (self.namespaces.len() - 1) as u16;
-                // Make this the new default namespace
-                self.default_namespace = ns;
-                Ok(ns)
-            }
+            // Add and register new namespace
+            let ns = self.namespaces.add(namespace).map_err(|_| ())?;
+            self.set_namespaces(&now);
+            // Make this the new default namespace
+            self.default_namespace = ns;
+            Ok(ns)
         }
     }
 
+    /// Returns the uris of the registered namespaces in index order
+    pub(crate) fn namespaces(&self) -> &[String] {
+        self.namespaces.uris()
+    }
+
+    /// Returns the table of registered namespaces, e.g. to resolve node ids that name their
+    /// namespace by uri such as `nsu=urn:MyServer;s=Tag`.
+    pub fn namespace_map(&self) -> &NamespaceMap {
+        &self.namespaces
+    }
+
+    /// Returns an iterator over every node in the address space
+    pub(crate) fn nodes(&self) -> impl Iterator<Item=&NodeType> {
+        self.node_map.values()
+    }
+
     /// Finds the namespace index of a given namespace
     pub fn namespace_index(&self, namespace: &str) -> Option<u16> {
-        self.namespaces.iter().position(|ns| {
-            let ns: &str = ns.as_ref();
-            ns == namespace
-        }).map(|i| i as u16)
+        self.namespaces.index_of(namespace)
+    }
+
+    /// Registers a node manager that will supply all of the nodes in the specified namespace
+    /// on demand. The namespace must already be registered and cannot be namespace 0. Nodes
+    /// in the namespace are not expected to be inserted into the address space, and any that are
+    /// will be ignored by services in favour of the manager. Registering a manager for a namespace
+    /// that already has one replaces the previous manager.
+    pub fn register_node_manager<T>(&mut self, namespace: u16, node_manager: T) -> bool
+        where T: NodeManager + Send + Sync + 'static
+    {
+        if namespace == 0 || namespace as usize >= self.namespaces.len() {
+            error!("Cannot register a node manager for namespace {}", namespace);
+            false
+        } else {
+            self.node_managers.insert(namespace, Box::new(node_manager));
+            true
+        }
+    }
+
+    /// Removes the node manager for the specified namespace, returning true if there was one.
+    pub fn unregister_node_manager(&mut self, namespace: u16) -> bool {
+        self.node_managers.remove(&namespace).is_some()
+    }
+
+    /// Returns the node manager that is responsible for the node, if there is one.
+    pub fn node_manager(&self, node_id: &NodeId) -> Option<&(dyn NodeManager + Send + Sync)> {
+        self.node_managers.get(&node_id.namespace).map(|m| m.as_ref())
+    }
+
+    /// Tests if the node belongs to a node manager and the node manager says it exists.
+    pub fn is_managed_node(&self, node_id: &NodeId) -> bool {
+        self.node_manager(node_id).map(|m| m.node_exists(node_id)).unwrap_or(false)
+    }
+
+    /// Tests if the node exists, either in the address space or via the node manager that is
+    /// responsible for its namespace.
+    pub fn is_known_node(&self, node_id: &NodeId) -> bool {
+        if let Some(node_manager) = self.node_manager(node_id) {
+            node_manager.node_exists(node_id)
+        } else {
+            self.node_exists(node_id)
+        }
     }
 
     fn set_servers(&mut self, server_state: Arc<RwLock<ServerState>>, now: &DateTime) {
@@ -249,7 +309,7 @@ impl AddressSpace {
     }
 
     fn set_namespaces(&mut self, now: &DateTime) {
-        let value = Variant::from(&self.namespaces);
+        let value = Variant::from(self.namespaces.uris());
         if let Some(ref mut v) = self.find_variable_mut(Server_NamespaceArray) {
             let _ = v.set_value_direct(value, StatusCode::Good, now, now);
         }
@@ -260,6 +320,13 @@ impl AddressSpace {
         self.set_variable_value(Server_ServiceLevel, service_level, now, now);
     }
 
+    /// Sets the redundancy the server supports and the application uris of the servers in its
+    /// non-transparent redundant server set, which clients read to find the other servers
+    pub fn set_server_redundancy(&mut self, redundancy_support: service_types::RedundancySupport, server_uris: &[String], now: &DateTime) {
+        self.set_variable_value(Server_ServerRedundancy_RedundancySupport, redundancy_support as i32, now, now);
+        self.set_variable_value(Server_ServerRedundancy_ServerUriArray, server_uris, now, now);
+    }
+
     /// Sets values for nodes representing the server.
     pub fn set_server_state(&mut self, server_state: Arc<RwLock<ServerState>>) {
         // Server state requires the generated address space, otherwise nothing
@@ -420,6 +487,7 @@ impl AddressSpace {
             // ServerDiagnostics
             // VendorServiceInfo
             // ServerRedundancy
+            self.set_variable_value(Server_ServerRedundancy_RedundancySupport, service_types::RedundancySupport::None as i32, &now, &now);
 
             // Server_ServerStatus_StartTime
             self.set_variable_value(Server_ServerStatus_StartTime, now.clone(), &now, &now);
@@ -434,10 +502,16 @@ impl AddressSpace {
 
             // State OPC UA Part 5 12.6, Valid states are
             //     State (Server_ServerStatus_State)
+            // Services hold the server state while they wait for the address space, so the getter
+            // reads the state through its own lock rather than locking the server state.
+            let state = {
+                let server_state = trace_read_lock_unwrap!(server_state);
+                server_state.state.clone()
+            };
             self.set_variable_getter(Server_ServerStatus_State, move |_, timestamps_to_return, _, _, _, _| {
-                // let server_state =  trace_read_lock_unwrap!(server_state);
+                let state = *trace_read_lock_unwrap!(state) as i32;
                 let now = DateTime::now();
-                let mut value = DataValue::from(0i32);
+                let mut value = DataValue::from(state);
                 value.set_timestamps(timestamps_to_return, now.clone(), now);
                 Ok(Some(value))
             });
@@ -561,6 +635,61 @@ impl AddressSpace {
         }
     }
 
+    /// Imports the nodes of a UANodeSet2 XML file into the address space. The namespaces declared
+    /// by the nodeset are registered and the namespace indexes of its nodes, references and values
+    /// are remapped to match. The return value is the number of nodes that were inserted.
+    ///
+    /// The whole document is read before anything is inserted, so a document that fails to import
+    /// leaves the address space unchanged.
+    pub fn import_nodeset_file<P>(&mut self, path: P) -> Result<usize, ()> where P: AsRef<Path> {
+        nodeset_import::import_file(self, path.as_ref())
+    }
+
+    /// Imports every UANodeSet2 XML file in a directory into the address space. Files are imported
+    /// in an order that satisfies the `RequiredModel` dependencies they declare. Each file is
+    /// imported whole or not at all, but files imported before one that fails are kept.
+    pub fn import_nodeset_dir<P>(&mut self, path: P) -> Result<usize, ()> where P: AsRef<Path> {
+        nodeset_import::import_dir(self, path.as_ref())
+    }
+
+    /// Imports the nodes of a UANodeSet2 XML document held in a string into the address space.
+    pub fn import_nodeset_str(&mut self, xml: &str) -> Result<usize, ()> {
+        nodeset_import::import_str(self, xml)
+    }
+
+    /// Exports the nodes of a namespace, or of every namespace when `None`, as a UANodeSet2 XML
+    /// document. The document contains the namespaces, models and aliases it uses, and the
+    /// references and values of each node. Inverse references from nodes outside the exported
+    /// namespace are written so the nodes stay connected to the rest of the address space when
+    /// the document is imported.
+    pub fn export_nodeset(&self, namespace: Option<u16>) -> Result<String, ()> {
+        nodeset_export::export_str(self, namespace)
+    }
+
+    /// Exports the nodes of a namespace, or of every namespace when `None`, to a UANodeSet2 XML file.
+    pub fn export_nodeset_file<P>(&self, path: P, namespace: Option<u16>) -> Result<(), ()> where P: AsRef<Path> {
+        nodeset_export::export_file(self, path.as_ref(), namespace)
+    }
+
+    /// Adds a structured data type described by a `StructureDefinition`. The data type is a
+    /// subtype of the definition's base data type, or of `Structure` when that is null. A
+    /// "Default Binary" encoding node is created with the definition's `default_encoding_id` and
+    /// the data type dictionary of the namespace is updated to describe the structure.
+    pub fn add_structure_data_type<R, S>(&mut self, data_type_id: &NodeId, browse_name: R, display_name: S, definition: StructureDefinition) -> bool
+        where R: Into<QualifiedName>, S: Into<LocalizedText>
+    {
+        custom_data_types::add_structure_data_type(self, data_type_id, browse_name.into(), display_name.into(), definition)
+    }
+
+    /// Adds an enumerated data type described by an `EnumDefinition`. The data type is a subtype
+    /// of `Enumeration` with an `EnumValues` property and is described by the data type dictionary
+    /// of its namespace.
+    pub fn add_enum_data_type<R, S>(&mut self, data_type_id: &NodeId, browse_name: R, display_name: S, definition: EnumDefinition) -> bool
+        where R: Into<QualifiedName>, S: Into<LocalizedText>
+    {
+        custom_data_types::add_enum_data_type(self, data_type_id, browse_name.into(), display_name.into(), definition)
+    }
+
     // Inserts a bunch of references between two nodes into the address space
     pub fn insert_references<T>(&mut self, references: &[(&NodeId, &NodeId, &T)])
         where T: Into<NodeId> + Clone
@@ -731,6 +860,20 @@ impl AddressSpace {
         }
     }
 
+    /// Registers a method callback which is passed the address space, so that it may add, change
+    /// or remove nodes, on the specified method id
+    pub fn register_address_space_method_handler<N>(&mut self, method_id: N, handler: AddressSpaceMethodCallback) where N: Into<NodeId> {
+        let method_id = method_id.into();
+        if let Some(method) = self.find_mut(&method_id) {
+            match method {
+                NodeType::Method(method) => method.set_address_space_callback(handler),
+                _ => panic!("{} is not a method node", method_id)
+            }
+        } else {
+            panic!("{} method id does not exist", method_id);
+        }
+    }
+
     /// Test if the type definition is defined and valid for a class of the specified type.
     /// i.e. if we have a Variable or Object class that the type is a VariableType or ObjectType
     /// respectively.
@@ -798,7 +941,10 @@ impl AddressSpace {
     pub fn call_method(&mut self, _server_state: &ServerState, session: &mut Session, request: &CallMethodRequest) -> Result<CallMethodResult, StatusCode> {
         let (object_id, method_id) = (&request.object_id, &request.method_id);
         // Handle the call
-        if !is_object!(self, object_id) {
+        if let Some(node_manager) = self.node_manager(method_id).or_else(|| self.node_manager(object_id)) {
+            // Calls on or to managed nodes are the responsibility of the manager
+            node_manager.call(session, request)
+        } else if !is_object!(self, object_id) {
             error!("Method call to {:?} on {:?} but the node id is not recognized!", method_id, object_id);
             Err(StatusCode::BadNodeIdUnknown)
         } else if !is_method!(self, method_id) {
@@ -807,6 +953,16 @@ impl AddressSpace {
         } else if !self.method_exists_on_object(object_id, method_id) {
             error!("Method call to {:?} on {:?} but the method does not exist on the object!", method_id, object_id);
             Err(StatusCode::BadMethodInvalid)
+        } else if let Some(mut callback) = self.take_address_space_callback(method_id) {
+            // The callback is out of its node while it runs so it can be given the address space
+            let result = callback.call(self, session, request);
+            // Put it back unless the node has gone or has been given another callback
+            if let Some(NodeType::Method(method)) = self.find_mut(method_id) {
+                if !method.has_callback() {
+                    method.set_address_space_callback(callback);
+                }
+            }
+            result
         } else if let Some(method) = self.find_mut(method_id) {
             // TODO check security - session / user may not have permission to call methods
             match method {
@@ -818,6 +974,13 @@ impl AddressSpace {
         }
     }
 
+    fn take_address_space_callback(&mut self, method_id: &NodeId) -> Option<AddressSpaceMethodCallback> {
+        match self.find_mut(method_id) {
+            Some(NodeType::Method(method)) => method.take_address_space_callback(),
+            _ => None
+        }
+    }
+
     /// Recursive function tries to find if a type is a subtype of another type by looking at its
     /// references. Function will positively match a type against itself.
     pub fn is_subtype(&self, subtype_id: &NodeId, base_type_id: &NodeId) -> bool {
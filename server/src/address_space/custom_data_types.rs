@@ -0,0 +1,360 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! Contains the functions that add custom structured and enumerated data types to the
+//! `AddressSpace`, along with their encoding nodes and the legacy OPC Binary data type dictionary
+//! (Part 3 5.8 and Part 5 D) that older clients use to decode them.
+
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+};
+
+use opcua_types::{
+    *,
+    service_types::{EnumDefinition, EnumValueType, StructureDefinition, StructureType},
+};
+
+use crate::address_space::{
+    address_space::AddressSpace,
+    data_type::{DataType, DataTypeBuilder},
+    node::{NodeBase, NodeType},
+    object::ObjectBuilder,
+    references::ReferenceDirection,
+    variable::VariableBuilder,
+};
+
+/// The browse name of the node for the default binary encoding of a data type
+const DEFAULT_BINARY: &str = "Default Binary";
+
+/// The identifier of the data type dictionary variable of a namespace
+const DICTIONARY_ID: &str = "BinarySchema";
+
+pub(crate) fn add_structure_data_type(address_space: &mut AddressSpace, data_type_id: &NodeId, browse_name: QualifiedName, display_name: LocalizedText, definition: StructureDefinition) -> bool {
+    if address_space.node_exists(data_type_id) || !is_registered_namespace(address_space, data_type_id.namespace) {
+        error!("Cannot add structure data type {}, the node already exists or its namespace is not registered", data_type_id);
+        return false;
+    }
+    let encoding_id = definition.default_encoding_id.clone();
+    if encoding_id.is_null() || address_space.node_exists(&encoding_id) {
+        error!("Cannot add structure data type {}, the default encoding id {} is null or already exists", data_type_id, encoding_id);
+        return false;
+    }
+    let mut definition = definition;
+    if definition.base_data_type.is_null() {
+        definition.base_data_type = if definition.structure_type == StructureType::Union {
+            DataTypeId::Union.into()
+        } else {
+            DataTypeId::Structure.into()
+        };
+    }
+
+    let inserted = DataTypeBuilder::new(data_type_id, browse_name, display_name)
+        .subtype_of(definition.base_data_type.clone())
+        .data_type_definition(definition)
+        .reference(encoding_id.clone(), ReferenceTypeId::HasEncoding, ReferenceDirection::Forward)
+        .insert(address_space);
+    if inserted {
+        ObjectBuilder::new(&encoding_id, DEFAULT_BINARY, DEFAULT_BINARY)
+            .has_type_definition(ObjectTypeId::DataTypeEncodingType)
+            .insert(address_space);
+        update_data_type_dictionary(address_space, data_type_id.namespace);
+    }
+    inserted
+}
+
+pub(crate) fn add_enum_data_type(address_space: &mut AddressSpace, data_type_id: &NodeId, browse_name: QualifiedName, display_name: LocalizedText, definition: EnumDefinition) -> bool {
+    if address_space.node_exists(data_type_id) || !is_registered_namespace(address_space, data_type_id.namespace) {
+        error!("Cannot add enum data type {}, the node already exists or its namespace is not registered", data_type_id);
+        return false;
+    }
+
+    // Enumerations expose their values through the EnumValues property
+    let enum_values = definition.fields.as_ref().map_or(Vec::new(), |fields| {
+        fields.iter().map(|field| {
+            Variant::from(ExtensionObject::from_encodable(ObjectId::EnumValueType_Encoding_DefaultBinary, &EnumValueType {
+                value: field.value,
+                display_name: field.display_name.clone(),
+                description: field.description.clone(),
+            }))
+        }).collect::<Vec<Variant>>()
+    });
+
+    let inserted = DataTypeBuilder::new(data_type_id, browse_name, display_name)
+        .subtype_of(DataTypeId::Enumeration)
+        .data_type_definition(definition)
+        .insert(address_space);
+    if inserted {
+        let enum_values_id = NodeId::next_numeric(data_type_id.namespace);
+        VariableBuilder::new(&enum_values_id, "EnumValues", "EnumValues")
+            .data_type(DataTypeId::EnumValueType)
+            .value_rank(1)
+            .value(enum_values)
+            .has_type_definition(VariableTypeId::PropertyType)
+            .property_of(data_type_id.clone())
+            .insert(address_space);
+        update_data_type_dictionary(address_space, data_type_id.namespace);
+    }
+    inserted
+}
+
+fn is_registered_namespace(address_space: &AddressSpace, namespace: u16) -> bool {
+    namespace > 0 && (namespace as usize) < address_space.namespaces().len()
+}
+
+/// Returns the node id of a data type's default binary encoding
+pub(crate) fn default_binary_encoding(address_space: &AddressSpace, data_type_id: &NodeId) -> Option<NodeId> {
+    address_space.find_references(data_type_id, Some((ReferenceTypeId::HasEncoding, false)))
+        .and_then(|references| {
+            references.into_iter()
+                .map(|r| r.target_node)
+                .find(|encoding_id| {
+                    address_space.find_node(encoding_id)
+                        .map(|n| n.as_node().browse_name().name.as_ref() == DEFAULT_BINARY)
+                        .unwrap_or(false)
+                })
+        })
+}
+
+/// Returns the supertype of a data type
+fn super_type(address_space: &AddressSpace, data_type_id: &NodeId) -> Option<NodeId> {
+    address_space.find_inverse_references(data_type_id, Some((ReferenceTypeId::HasSubtype, false)))
+        .and_then(|references| references.into_iter().next().map(|r| r.target_node))
+}
+
+/// Creates or updates the data type dictionary of a namespace so it describes every structured
+/// and enumerated data type in the namespace that has a `DataTypeDefinition`.
+pub(crate) fn update_data_type_dictionary(address_space: &mut AddressSpace, namespace: u16) {
+    let namespace_uri = match address_space.namespaces().get(namespace as usize) {
+        Some(namespace_uri) if namespace > 0 => namespace_uri.clone(),
+        _ => return
+    };
+
+    // Gather the types of the namespace, ordered by name
+    let mut types = BTreeMap::new();
+    address_space.nodes()
+        .filter_map(|node| if let NodeType::DataType(data_type) = node { Some(data_type) } else { None })
+        .filter(|data_type| data_type.node_id().namespace == namespace)
+        .for_each(|data_type| {
+            if let Some(definition) = data_type.data_type_definition() {
+                types.insert(data_type.browse_name().name.to_string(), (data_type.node_id(), definition.clone()));
+            }
+        });
+
+    let dictionary_id = NodeId::new(namespace, DICTIONARY_ID);
+    let schema = DictionaryWriter::new(address_space, namespace, &namespace_uri).write(&types);
+    let now = DateTime::now();
+    if address_space.node_exists(&dictionary_id) {
+        address_space.set_variable_value(dictionary_id.clone(), ByteString::from(schema.into_bytes()), &now, &now);
+    } else {
+        VariableBuilder::new(&dictionary_id, namespace_uri.as_str(), namespace_uri.as_str())
+            .data_type(DataTypeId::ByteString)
+            .value(ByteString::from(schema.into_bytes()))
+            .has_type_definition(VariableTypeId::DataTypeDictionaryType)
+            .component_of(ObjectId::OPCBinarySchema_TypeSystem)
+            .insert(address_space);
+        VariableBuilder::new(&NodeId::new(namespace, format!("{}.NamespaceUri", DICTIONARY_ID)), "NamespaceUri", "NamespaceUri")
+            .data_type(DataTypeId::String)
+            .value(namespace_uri.as_str())
+            .has_type_definition(VariableTypeId::PropertyType)
+            .property_of(dictionary_id.clone())
+            .insert(address_space);
+    }
+
+    // Each structure's binary encoding points at a description of it in the dictionary
+    for (name, (data_type_id, definition)) in &types {
+        if definition.as_structure().is_none() {
+            continue;
+        }
+        let description_id = NodeId::new(namespace, format!("{}.{}", DICTIONARY_ID, name));
+        if !address_space.node_exists(&description_id) {
+            VariableBuilder::new(&description_id, name.as_str(), name.as_str())
+                .data_type(DataTypeId::String)
+                .value(name.as_str())
+                .has_type_definition(VariableTypeId::DataTypeDescriptionType)
+                .component_of(dictionary_id.clone())
+                .insert(address_space);
+            if let Some(encoding_id) = default_binary_encoding(address_space, data_type_id) {
+                address_space.insert_reference(&encoding_id, &description_id, ReferenceTypeId::HasDescription);
+            }
+        }
+    }
+}
+
+/// Writes an OPC Binary type dictionary (Part 3 Annex C)
+struct DictionaryWriter<'a> {
+    address_space: &'a AddressSpace,
+    namespace: u16,
+    namespace_uri: &'a str,
+    /// Other namespaces referenced by the dictionary, mapped to their prefix
+    imports: BTreeMap<u16, String>,
+}
+
+impl<'a> DictionaryWriter<'a> {
+    fn new(address_space: &'a AddressSpace, namespace: u16, namespace_uri: &'a str) -> Self {
+        DictionaryWriter {
+            address_space,
+            namespace,
+            namespace_uri,
+            imports: BTreeMap::new(),
+        }
+    }
+
+    fn write(mut self, types: &BTreeMap<String, (NodeId, DataTypeDefinition)>) -> String {
+        let mut body = String::new();
+        for (name, (_, definition)) in types {
+            match definition {
+                DataTypeDefinition::Structure(definition) => self.write_structure(&mut body, name, definition),
+                DataTypeDefinition::Enum(definition) => Self::write_enum(&mut body, name, definition),
+            }
+        }
+
+        let mut xml = String::with_capacity(body.len() + 1024);
+        let _ = write!(xml, r#"<opc:TypeDictionary xmlns:opc="http://opcfoundation.org/BinarySchema/" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns:ua="http://opcfoundation.org/UA/" xmlns:tns="{}""#, escape(self.namespace_uri));
+        for (ns, prefix) in &self.imports {
+            let _ = write!(xml, r#" xmlns:{}="{}""#, prefix, escape(&self.address_space.namespaces()[*ns as usize]));
+        }
+        let _ = writeln!(xml, r#" DefaultByteOrder="LittleEndian" TargetNamespace="{}">"#, escape(self.namespace_uri));
+        let _ = writeln!(xml, r#"  <opc:Import Namespace="http://opcfoundation.org/UA/" Location="Opc.Ua.BinarySchema.bsd" />"#);
+        for ns in self.imports.keys() {
+            let _ = writeln!(xml, r#"  <opc:Import Namespace="{}" />"#, escape(&self.address_space.namespaces()[*ns as usize]));
+        }
+        xml.push_str(&body);
+        xml.push_str("</opc:TypeDictionary>\n");
+        xml
+    }
+
+    fn write_structure(&mut self, xml: &mut String, name: &str, definition: &StructureDefinition) {
+        let _ = writeln!(xml, r#"  <opc:StructuredType Name="{}" BaseType="ua:ExtensionObject">"#, escape(name));
+        let fields = definition.fields.as_deref().unwrap_or(&[]);
+        match definition.structure_type {
+            StructureType::StructureWithOptionalFields => {
+                // The encoding mask has a bit for each optional field, padded to 32 bits
+                let optional = fields.iter().filter(|f| f.is_optional).collect::<Vec<_>>();
+                optional.iter().for_each(|f| {
+                    let _ = writeln!(xml, r#"    <opc:Field TypeName="opc:Bit" Name="{}Specified" />"#, escape(f.name.as_ref()));
+                });
+                if optional.len() < 32 {
+                    let _ = writeln!(xml, r#"    <opc:Field Name="Reserved1" TypeName="opc:Bit" Length="{}" />"#, 32 - optional.len());
+                }
+            }
+            StructureType::Union => {
+                let _ = writeln!(xml, r#"    <opc:Field Name="SwitchField" TypeName="opc:UInt32" />"#);
+            }
+            StructureType::Structure => {}
+        }
+        for (idx, field) in fields.iter().enumerate() {
+            let field_name = escape(field.name.as_ref());
+            let type_name = self.type_name(&field.data_type);
+            let switch = match definition.structure_type {
+                StructureType::StructureWithOptionalFields if field.is_optional => format!(r#" SwitchField="{}Specified""#, field_name),
+                StructureType::Union => format!(r#" SwitchField="SwitchField" SwitchValue="{}""#, idx + 1),
+                _ => String::new(),
+            };
+            if field.value_rank >= 1 {
+                let _ = writeln!(xml, r#"    <opc:Field Name="NoOf{}" TypeName="opc:Int32"{} />"#, field_name, switch);
+                let _ = writeln!(xml, r#"    <opc:Field Name="{}" TypeName="{}" LengthField="NoOf{}"{} />"#, field_name, type_name, field_name, switch);
+            } else {
+                let _ = writeln!(xml, r#"    <opc:Field Name="{}" TypeName="{}"{} />"#, field_name, type_name, switch);
+            }
+        }
+        let _ = writeln!(xml, "  </opc:StructuredType>");
+    }
+
+    fn write_enum(xml: &mut String, name: &str, definition: &EnumDefinition) {
+        let _ = writeln!(xml, r#"  <opc:EnumeratedType Name="{}" LengthInBits="32">"#, escape(name));
+        if let Some(ref fields) = definition.fields {
+            for field in fields {
+                let field_name = if field.name.is_empty() { &field.display_name.text } else { &field.name };
+                let _ = writeln!(xml, r#"    <opc:EnumeratedValue Name="{}" Value="{}" />"#, escape(field_name.as_ref()), field.value);
+            }
+        }
+        let _ = writeln!(xml, "  </opc:EnumeratedType>");
+    }
+
+    /// Returns the OPC Binary type name of a data type. Types derived from built-in types are
+    /// encoded as the built-in type and enumerations are encoded as Int32.
+    fn type_name(&mut self, data_type: &NodeId) -> String {
+        let mut data_type = data_type.clone();
+        loop {
+            if data_type.namespace == 0 {
+                if let Identifier::Numeric(id) = data_type.identifier {
+                    if let Some(name) = Self::built_in_type_name(id) {
+                        return name.to_string();
+                    }
+                    if id == DataTypeId::Enumeration as u32 {
+                        return "opc:Int32".to_string();
+                    }
+                }
+            }
+            let node = match self.address_space.find_node(&data_type) {
+                Some(NodeType::DataType(node)) => node,
+                _ => return "ua:Variant".to_string()
+            };
+            // Structures and enums are referred to by name, other types by the type they derive from
+            if let Some(definition) = node.data_type_definition() {
+                if definition.as_enum().is_some() && data_type.namespace != self.namespace {
+                    return "opc:Int32".to_string();
+                }
+                return self.qualified_type_name(node);
+            }
+            match super_type(self.address_space, &data_type) {
+                Some(super_type) if super_type == DataTypeId::Structure.into() || super_type == DataTypeId::Union.into() => {
+                    return self.qualified_type_name(node);
+                }
+                Some(super_type) => data_type = super_type,
+                None => return "ua:Variant".to_string()
+            }
+        }
+    }
+
+    fn qualified_type_name(&mut self, node: &DataType) -> String {
+        let ns = node.node_id().namespace;
+        let prefix = if ns == 0 {
+            "ua".to_string()
+        } else if ns == self.namespace {
+            "tns".to_string()
+        } else {
+            self.imports.entry(ns).or_insert_with(|| format!("ns{}", ns)).clone()
+        };
+        format!("{}:{}", prefix, escape(node.browse_name().name.as_ref()))
+    }
+
+    fn built_in_type_name(id: u32) -> Option<&'static str> {
+        let name = match id {
+            1 => "opc:Boolean",
+            2 => "opc:SByte",
+            3 => "opc:Byte",
+            4 => "opc:Int16",
+            5 => "opc:UInt16",
+            6 => "opc:Int32",
+            7 => "opc:UInt32",
+            8 => "opc:Int64",
+            9 => "opc:UInt64",
+            10 => "opc:Float",
+            11 => "opc:Double",
+            12 => "opc:String",
+            13 => "opc:DateTime",
+            14 => "opc:Guid",
+            15 => "opc:ByteString",
+            16 => "ua:XmlElement",
+            17 => "ua:NodeId",
+            18 => "ua:ExpandedNodeId",
+            19 => "ua:StatusCode",
+            20 => "ua:QualifiedName",
+            21 => "ua:LocalizedText",
+            22 => "ua:ExtensionObject",
+            23 => "ua:DataValue",
+            24 => "ua:Variant",
+            25 => "ua:DiagnosticInfo",
+            _ => return None
+        };
+        Some(name)
+    }
+}
+
+/// Escapes text for an XML attribute
+fn escape(value: &str) -> String {
+    value.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: MPL-2.0
 // Copyright (C) 2017-2020 Adam Lock
 
-//! Contains the implementation of `Method` and `MethodBuilder`.
+//! Contains the implementation of `DataType` and `DataTypeBuilder`.
 
 use opcua_types::service_types::DataTypeAttributes;
 
@@ -10,11 +10,26 @@ use crate::address_space::{base::Base, node::Node, node::NodeBase};
 
 node_builder_impl!(DataTypeBuilder, DataType);
 
+node_builder_impl_subtype!(DataTypeBuilder);
+
+impl DataTypeBuilder {
+    pub fn is_abstract(mut self, is_abstract: bool) -> Self {
+        self.node.set_is_abstract(is_abstract);
+        self
+    }
+
+    pub fn data_type_definition<T>(mut self, data_type_definition: T) -> Self where T: Into<DataTypeDefinition> {
+        self.node.set_data_type_definition(data_type_definition.into());
+        self
+    }
+}
+
 /// A `DataType` is a type of node within the `AddressSpace`.
 #[derive(Debug)]
 pub struct DataType {
     base: Base,
     is_abstract: bool,
+    data_type_definition: Option<DataTypeDefinition>,
 }
 
 impl Default for DataType {
@@ -22,6 +37,7 @@ impl Default for DataType {
         Self {
             base: Base::new(NodeClass::DataType, &NodeId::null(), "", ""),
             is_abstract: false,
+            data_type_definition: None,
         }
     }
 }
@@ -32,6 +48,7 @@ impl Node for DataType {
     fn get_attribute_max_age(&self, timestamps_to_return: TimestampsToReturn, attribute_id: AttributeId, index_range: NumericRange, data_encoding: &QualifiedName, max_age: f64) -> Option<DataValue> {
         match attribute_id {
             AttributeId::IsAbstract => Some(self.is_abstract().into()),
+            AttributeId::DataTypeDefinition => self.data_type_definition.as_ref().map(|v| Variant::from(v).into()),
             _ => self.base.get_attribute_max_age(timestamps_to_return, attribute_id, index_range, data_encoding, max_age)
         }
     }
@@ -46,6 +63,16 @@ impl Node for DataType {
                     Err(StatusCode::BadTypeMismatch)
                 }
             }
+            AttributeId::DataTypeDefinition => {
+                if let Variant::ExtensionObject(v) = value {
+                    let data_type_definition = DataTypeDefinition::from_extension_object(&v, &DecodingLimits::default())
+                        .map_err(|_| StatusCode::BadTypeMismatch)?;
+                    self.set_data_type_definition(data_type_definition);
+                    Ok(())
+                } else {
+                    Err(StatusCode::BadTypeMismatch)
+                }
+            }
             _ => self.base.set_attribute(attribute_id, value)
         }
     }
@@ -59,6 +86,7 @@ impl DataType {
         DataType {
             base: Base::new(NodeClass::DataType, node_id, browse_name, display_name),
             is_abstract,
+            data_type_definition: None,
         }
     }
 
@@ -95,4 +123,12 @@ impl DataType {
     pub fn set_is_abstract(&mut self, is_abstract: bool) {
         self.is_abstract = is_abstract;
     }
+
+    pub fn data_type_definition(&self) -> Option<&DataTypeDefinition> {
+        self.data_type_definition.as_ref()
+    }
+
+    pub fn set_data_type_definition(&mut self, data_type_definition: DataTypeDefinition) {
+        self.data_type_definition = Some(data_type_definition);
+    }
 }
@@ -8,7 +8,7 @@ use opcua_types::service_types::{Argument, MethodAttributes};
 
 use crate::{
     address_space::{
-        address_space::MethodCallback,
+        address_space::{AddressSpaceMethodCallback, MethodCallback},
         base::Base,
         node::{Node, NodeBase},
         variable::VariableBuilder,
@@ -40,6 +40,11 @@ impl MethodBuilder {
         self
     }
 
+    pub fn address_space_callback(mut self, callback: AddressSpaceMethodCallback) -> Self {
+        self.node.set_address_space_callback(callback);
+        self
+    }
+
     fn args_to_variant(arguments: &[Argument]) -> Vec<Variant> {
         arguments.iter().map(|arg| {
             Variant::from(ExtensionObject::from_encodable(ObjectId::Argument_Encoding_DefaultBinary, arg))
@@ -70,6 +75,8 @@ pub struct Method {
     user_executable: bool,
     #[derivative(Debug = "ignore")]
     callback: Option<MethodCallback>,
+    #[derivative(Debug = "ignore")]
+    address_space_callback: Option<AddressSpaceMethodCallback>,
 }
 
 impl Default for Method {
@@ -79,6 +86,7 @@ impl Default for Method {
             executable: false,
             user_executable: false,
             callback: None,
+            address_space_callback: None,
         }
     }
 }
@@ -127,6 +135,7 @@ impl Method {
             executable,
             user_executable,
             callback: None,
+            address_space_callback: None,
         }
     }
 
@@ -177,11 +186,24 @@ impl Method {
     }
 
     pub fn set_callback(&mut self, callback: MethodCallback) {
+        self.address_space_callback = None;
         self.callback = Some(callback);
     }
 
+    /// Sets a callback that is passed the address space when the method is called. It replaces
+    /// any callback set with `set_callback()`.
+    pub fn set_address_space_callback(&mut self, callback: AddressSpaceMethodCallback) {
+        self.callback = None;
+        self.address_space_callback = Some(callback);
+    }
+
     pub fn has_callback(&self) -> bool {
-        self.callback.is_some()
+        self.callback.is_some() || self.address_space_callback.is_some()
+    }
+
+    /// Takes the address space callback out of the node so it can be called with the address space
+    pub(crate) fn take_address_space_callback(&mut self) -> Option<AddressSpaceMethodCallback> {
+        self.address_space_callback.take()
     }
 
     pub fn call(&mut self, session: &mut Session, request: &CallMethodRequest) -> Result<CallMethodResult, StatusCode> {
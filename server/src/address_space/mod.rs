@@ -281,11 +281,15 @@ macro_rules! node_base_impl {
 
 pub mod address_space;
 pub mod base;
+pub mod custom_data_types;
 pub mod relative_path;
 pub mod object;
 pub mod variable;
 pub mod method;
 pub mod node;
+pub mod node_manager;
+pub mod nodeset_export;
+pub mod nodeset_import;
 pub mod reference_type;
 pub mod object_type;
 pub mod variable_type;
@@ -337,6 +341,7 @@ pub mod types {
     pub use super::data_type::{DataType, DataTypeBuilder};
     pub use super::method::{Method, MethodBuilder};
     pub use super::node::{NodeBase, NodeType};
+    pub use super::node_manager::NodeManager;
     pub use super::object::{Object, ObjectBuilder};
     pub use super::object_type::{ObjectType, ObjectTypeBuilder};
     pub use super::reference_type::{ReferenceType, ReferenceTypeBuilder};
@@ -0,0 +1,93 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! Contains the `NodeManager` trait which allows a server to supply the nodes of an entire
+//! namespace on demand instead of materialising them in the `AddressSpace`.
+
+use std::result::Result;
+
+use opcua_types::{
+    *,
+    service_types::{BrowseDescription, BrowseDirection, CallMethodRequest, CallMethodResult, ReferenceDescription, RelativePathElement},
+    status_code::StatusCode,
+};
+
+use crate::session::Session;
+
+/// A `NodeManager` is responsible for every node in a namespace that has been registered to it via
+/// `AddressSpace::register_node_manager()`. Nodes owned by a manager do not exist in the
+/// `AddressSpace` node map - the View, Attribute, Method and MonitoredItem services delegate
+/// to the manager whenever they encounter a node id in its namespace. This makes it
+/// possible to expose very large or externally held namespaces, e.g. tags held in a database,
+/// without creating a `NodeType` for each one.
+///
+/// Namespace 0 is always served by the `AddressSpace` and cannot have a manager.
+///
+/// The functions take `&self` because they are called while the address space is locked for
+/// reading. An implementation that holds mutable state must use interior mutability and should
+/// return quickly since the services are blocked while it runs.
+pub trait NodeManager {
+    /// Tests if the node exists within the manager's namespace.
+    fn node_exists(&self, node_id: &NodeId) -> bool;
+
+    /// Reads an attribute of a node. The implementation should return `BadNodeIdUnknown` if
+    /// the node does not exist, `BadAttributeIdInvalid` if the node does not have the attribute,
+    /// and `BadNotReadable` if the attribute cannot be read.
+    ///
+    /// The `NodeClass`, `BrowseName` and `DisplayName` attributes are also read by the server when it
+    /// must describe a managed node that is the target of a reference from a node in the address
+    /// space, so an implementation should always support them.
+    ///
+    /// The server only asks for the `Value` of a `Variable` once its `AccessLevel` and
+    /// `UserAccessLevel` say the session may read it, so the manager must supply at least one of
+    /// those attributes for the value to be readable.
+    fn read(&self, node_id: &NodeId, timestamps_to_return: TimestampsToReturn, attribute_id: AttributeId, index_range: NumericRange, data_encoding: &QualifiedName, max_age: f64) -> Result<DataValue, StatusCode>;
+
+    /// Writes an attribute of a node on behalf of the session. The default implementation rejects
+    /// all writes.
+    ///
+    /// The server only calls this once the node's attributes say the session may write to it, i.e. a
+    /// `Variable` whose `UserAccessLevel` contains `CURRENT_WRITE` for its `Value`, or a `WriteMask`
+    /// with the bit for any other attribute, so the manager must supply those attributes through
+    /// `read()` for its nodes to be writable.
+    fn write(&self, _session: &Session, _node_id: &NodeId, _attribute_id: AttributeId, _index_range: NumericRange, _value: DataValue) -> Result<(), StatusCode> {
+        Err(StatusCode::BadNotWritable)
+    }
+
+    /// Browses the references of a node. The implementation is expected to honour the browse
+    /// direction, reference type filter, node class mask and result mask of the description. The
+    /// server takes care of continuation points so the complete list should be returned.
+    ///
+    /// The server also browses the `HasTypeDefinition` reference of an `Object` or `Variable` when
+    /// it describes a managed node as the target of a reference from the address space.
+    fn browse(&self, node_to_browse: &BrowseDescription) -> Result<Vec<ReferenceDescription>, StatusCode>;
+
+    /// Follows a single element of a relative path from the node, returning the ids of the
+    /// target nodes. The default implementation browses the node and matches each target's browse
+    /// name against the element's target name.
+    fn follow_relative_path(&self, node_id: &NodeId, element: &RelativePathElement) -> Result<Vec<NodeId>, StatusCode> {
+        let browse_direction = if element.is_inverse { BrowseDirection::Inverse } else { BrowseDirection::Forward };
+        let node_to_browse = BrowseDescription {
+            node_id: node_id.clone(),
+            browse_direction,
+            reference_type_id: element.reference_type_id.clone(),
+            include_subtypes: element.include_subtypes,
+            node_class_mask: 0,
+            result_mask: BrowseDescriptionResultMask::RESULT_MASK_BROWSE_NAME.bits(),
+        };
+        let compare_target_name = !element.target_name.is_null();
+        let targets = self.browse(&node_to_browse)?
+            .into_iter()
+            .filter(|r| !compare_target_name || r.browse_name == element.target_name)
+            .map(|r| r.node_id.node_id)
+            .collect();
+        Ok(targets)
+    }
+
+    /// Calls a method on an object where either the object or the method belongs to the manager.
+    /// The default implementation reports that no such method exists.
+    fn call(&self, _session: &mut Session, _request: &CallMethodRequest) -> Result<CallMethodResult, StatusCode> {
+        Err(StatusCode::BadMethodInvalid)
+    }
+}
@@ -0,0 +1,568 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! Contains the exporter that writes the nodes of the `AddressSpace` as a UANodeSet2 XML
+//! document (Part 6 Annex F). The output can be read back with the nodeset importer.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Write,
+    fs,
+    path::Path,
+    result::Result,
+};
+
+use opcua_types::{
+    *,
+    argument::Argument,
+    service_types::{EnumValueType, EUInformation, Range, StructureType},
+};
+
+use crate::address_space::{
+    address_space::AddressSpace,
+    node::{HasNodeId, NodeType},
+    AccessLevel, UserAccessLevel,
+};
+
+/// The OPC UA namespace which is always present as namespace 0
+const OPC_UA_NAMESPACE_URI: &str = "http://opcfoundation.org/UA/";
+
+/// Writes the nodes of a namespace, or of every namespace, to a UANodeSet2 XML file.
+pub(crate) fn export_file(address_space: &AddressSpace, path: &Path, namespace: Option<u16>) -> Result<(), ()> {
+    let xml = export_str(address_space, namespace)?;
+    fs::write(path, xml).map_err(|err| {
+        error!("Cannot write nodeset file {}, error = {}", path.display(), err);
+    })
+}
+
+/// Writes the nodes of a namespace, or of every namespace, as a UANodeSet2 XML document.
+pub(crate) fn export_str(address_space: &AddressSpace, namespace: Option<u16>) -> Result<String, ()> {
+    if let Some(namespace) = namespace {
+        if namespace as usize >= address_space.namespaces().len() {
+            error!("Cannot export namespace {} because it is not registered", namespace);
+            return Err(());
+        }
+    }
+    Ok(NodeSetExporter::new(address_space, namespace).export())
+}
+
+/// Escapes text for an XML element or attribute
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    value.chars().for_each(|c| match c {
+        '&' => escaped.push_str("&amp;"),
+        '<' => escaped.push_str("&lt;"),
+        '>' => escaped.push_str("&gt;"),
+        '"' => escaped.push_str("&quot;"),
+        '\'' => escaped.push_str("&apos;"),
+        c => escaped.push(c),
+    });
+    escaped
+}
+
+/// Formats a float the way XML Schema writes them, i.e. with INF, -INF and NaN
+fn format_float(value: f64) -> String {
+    if value.is_infinite() {
+        if value > 0.0 { "INF".to_string() } else { "-INF".to_string() }
+    } else {
+        value.to_string()
+    }
+}
+
+/// Returns a key that sorts nodes into a stable order, types before instances, so that the
+/// output of an unchanged address space is always the same.
+fn sort_key(node: &NodeType) -> (u8, u16, u8, u32, String) {
+    let class_order = match node {
+        NodeType::ReferenceType(_) => 0,
+        NodeType::DataType(_) => 1,
+        NodeType::ObjectType(_) => 2,
+        NodeType::VariableType(_) => 3,
+        NodeType::Object(_) => 4,
+        NodeType::Variable(_) => 5,
+        NodeType::Method(_) => 6,
+        NodeType::View(_) => 7,
+    };
+    let node_id = node.node_id();
+    let (id_order, numeric, other) = match node_id.identifier {
+        Identifier::Numeric(v) => (0, v, String::new()),
+        Identifier::String(ref v) => (1, 0, v.to_string()),
+        Identifier::Guid(ref v) => (2, 0, format!("{:?}", v)),
+        Identifier::ByteString(ref v) => (3, 0, v.as_base64()),
+    };
+    (class_order, node_id.namespace, id_order, numeric, other)
+}
+
+struct NodeSetExporter<'a> {
+    address_space: &'a AddressSpace,
+    /// The namespace to export, or all of them
+    namespace: Option<u16>,
+    /// Namespaces of the address space in the order they appear in the document's NamespaceUris
+    namespaces: Vec<u16>,
+    /// Aliases for the standard reference and data types used by the document
+    aliases: BTreeMap<String, NodeId>,
+}
+
+impl<'a> NodeSetExporter<'a> {
+    fn new(address_space: &'a AddressSpace, namespace: Option<u16>) -> Self {
+        // The exported namespace comes first so it is namespace 1 in the document
+        let namespaces = match namespace {
+            Some(namespace) if namespace > 0 => vec![namespace],
+            Some(_) => Vec::new(),
+            None => (1..address_space.namespaces().len() as u16).collect(),
+        };
+        NodeSetExporter {
+            address_space,
+            namespace,
+            namespaces,
+            aliases: BTreeMap::new(),
+        }
+    }
+
+    fn export(mut self) -> String {
+        let mut nodes = self.address_space.nodes()
+            .filter(|node| self.namespace.map(|ns| node.node_id().namespace == ns).unwrap_or(true))
+            .collect::<Vec<_>>();
+        nodes.sort_by_key(|node| sort_key(node));
+        let exported = nodes.iter().map(|node| node.node_id()).collect::<HashSet<NodeId>>();
+
+        // Nodes are written first because they determine the namespaces and aliases in the header
+        let mut body = String::new();
+        nodes.iter().for_each(|node| self.write_node(&mut body, node, &exported));
+
+        let mut xml = String::with_capacity(body.len() + 4096);
+        let _ = writeln!(xml, r#"<?xml version="1.0" encoding="utf-8"?>"#);
+        let _ = writeln!(xml, r#"<UANodeSet xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" xmlns:uax="http://opcfoundation.org/UA/2008/02/Types.xsd" xmlns="http://opcfoundation.org/UA/2011/03/UANodeSet.xsd" LastModified="{}">"#,
+                             self.address_space.last_modified().to_rfc3339());
+        if !self.namespaces.is_empty() {
+            let _ = writeln!(xml, "  <NamespaceUris>");
+            for ns in &self.namespaces {
+                let _ = writeln!(xml, "    <Uri>{}</Uri>", escape(&self.address_space.namespaces()[*ns as usize]));
+            }
+            let _ = writeln!(xml, "  </NamespaceUris>");
+        }
+        self.write_models(&mut xml);
+        if !self.aliases.is_empty() {
+            let _ = writeln!(xml, "  <Aliases>");
+            for (alias, node_id) in &self.aliases {
+                let _ = writeln!(xml, r#"    <Alias Alias="{}">{}</Alias>"#, escape(alias), node_id);
+            }
+            let _ = writeln!(xml, "  </Aliases>");
+        }
+        xml.push_str(&body);
+        xml.push_str("</UANodeSet>\n");
+        xml
+    }
+
+    /// Writes a model for each exported namespace
+    fn write_models(&self, xml: &mut String) {
+        let models = match self.namespace {
+            Some(0) => vec![0],
+            Some(ns) => vec![ns],
+            None => (0..self.address_space.namespaces().len() as u16).collect(),
+        };
+        let _ = writeln!(xml, "  <Models>");
+        for ns in models {
+            let uri = &self.address_space.namespaces()[ns as usize];
+            if ns == 0 {
+                let _ = writeln!(xml, r#"    <Model ModelUri="{}" />"#, escape(uri));
+            } else {
+                let _ = writeln!(xml, r#"    <Model ModelUri="{}">"#, escape(uri));
+                let _ = writeln!(xml, r#"      <RequiredModel ModelUri="{}" />"#, OPC_UA_NAMESPACE_URI);
+                let _ = writeln!(xml, "    </Model>");
+            }
+        }
+        let _ = writeln!(xml, "  </Models>");
+    }
+
+    /// Maps a namespace index in the address space to its index in the document, adding it to
+    /// the document's namespaces if necessary
+    fn namespace(&mut self, ns: u16) -> u16 {
+        if ns == 0 {
+            0
+        } else if let Some(idx) = self.namespaces.iter().position(|n| *n == ns) {
+            (idx + 1) as u16
+        } else {
+            self.namespaces.push(ns);
+            self.namespaces.len() as u16
+        }
+    }
+
+    fn node_id(&mut self, node_id: &NodeId) -> String {
+        NodeId {
+            namespace: self.namespace(node_id.namespace),
+            identifier: node_id.identifier.clone(),
+        }.to_string()
+    }
+
+    /// Returns an alias for standard reference types and data types, otherwise the node id
+    fn alias(&mut self, node_id: &NodeId) -> String {
+        if node_id.namespace == 0 {
+            if let Some(node) = self.address_space.find_node(node_id) {
+                let alias = node.as_node().browse_name().name.to_string();
+                if !alias.is_empty() && self.aliases.get(&alias).map(|n| n == node_id).unwrap_or(true) {
+                    self.aliases.insert(alias.clone(), node_id.clone());
+                    return alias;
+                }
+            }
+        }
+        self.node_id(node_id)
+    }
+
+    fn qualified_name(&mut self, name: &QualifiedName) -> String {
+        let ns = self.namespace(name.namespace_index);
+        if ns == 0 {
+            name.name.to_string()
+        } else {
+            format!("{}:{}", ns, name.name)
+        }
+    }
+
+    fn localized_text_element(xml: &mut String, indent: &str, element: &str, text: &LocalizedText) {
+        if text.locale.is_empty() {
+            let _ = writeln!(xml, "{}<{}>{}</{}>", indent, element, escape(text.text.as_ref()), element);
+        } else {
+            let _ = writeln!(xml, r#"{}<{} Locale="{}">{}</{}>"#, indent, element, escape(text.locale.as_ref()), escape(text.text.as_ref()), element);
+        }
+    }
+
+    fn write_node(&mut self, xml: &mut String, node: &NodeType, exported: &HashSet<NodeId>) {
+        let element = match node {
+            NodeType::Object(_) => "UAObject",
+            NodeType::ObjectType(_) => "UAObjectType",
+            NodeType::ReferenceType(_) => "UAReferenceType",
+            NodeType::Variable(_) => "UAVariable",
+            NodeType::VariableType(_) => "UAVariableType",
+            NodeType::View(_) => "UAView",
+            NodeType::DataType(_) => "UADataType",
+            NodeType::Method(_) => "UAMethod",
+        };
+        let base = node.as_node();
+        let node_id = base.node_id();
+
+        let mut attributes = format!(r#" NodeId="{}" BrowseName="{}""#, escape(&self.node_id(&node_id)), escape(&self.qualified_name(&base.browse_name())));
+        if let Some(write_mask) = base.write_mask() {
+            let _ = write!(attributes, r#" WriteMask="{}""#, write_mask.bits());
+        }
+        if let Some(user_write_mask) = base.user_write_mask() {
+            let _ = write!(attributes, r#" UserWriteMask="{}""#, user_write_mask.bits());
+        }
+        let mut value = None;
+        let mut inverse_name = None;
+        let mut definition = None;
+        match node {
+            NodeType::Object(node) => {
+                if !node.event_notifier().is_empty() {
+                    let _ = write!(attributes, r#" EventNotifier="{}""#, node.event_notifier().bits());
+                }
+            }
+            NodeType::View(node) => {
+                if !node.event_notifier().is_empty() {
+                    let _ = write!(attributes, r#" EventNotifier="{}""#, node.event_notifier().bits());
+                }
+                if node.contains_no_loops() {
+                    attributes.push_str(r#" ContainsNoLoops="true""#);
+                }
+            }
+            NodeType::ObjectType(node) => {
+                if node.is_abstract() {
+                    attributes.push_str(r#" IsAbstract="true""#);
+                }
+            }
+            NodeType::DataType(node) => {
+                if node.is_abstract() {
+                    attributes.push_str(r#" IsAbstract="true""#);
+                }
+                definition = node.data_type_definition().cloned();
+            }
+            NodeType::ReferenceType(node) => {
+                if node.is_abstract() {
+                    attributes.push_str(r#" IsAbstract="true""#);
+                }
+                if node.symmetric() {
+                    attributes.push_str(r#" Symmetric="true""#);
+                }
+                inverse_name = node.inverse_name();
+            }
+            NodeType::Method(node) => {
+                if !node.executable() {
+                    attributes.push_str(r#" Executable="false""#);
+                }
+                if !node.user_executable() {
+                    attributes.push_str(r#" UserExecutable="false""#);
+                }
+            }
+            NodeType::Variable(node) => {
+                let _ = write!(attributes, r#" DataType="{}""#, escape(&self.alias(&node.data_type())));
+                self.write_value_rank(&mut attributes, node.value_rank(), node.array_dimensions());
+                if node.access_level() != AccessLevel::CURRENT_READ {
+                    let _ = write!(attributes, r#" AccessLevel="{}""#, node.access_level().bits());
+                }
+                if node.user_access_level() != UserAccessLevel::CURRENT_READ {
+                    let _ = write!(attributes, r#" UserAccessLevel="{}""#, node.user_access_level().bits());
+                }
+                if let Some(minimum_sampling_interval) = node.minimum_sampling_interval() {
+                    let _ = write!(attributes, r#" MinimumSamplingInterval="{}""#, format_float(minimum_sampling_interval));
+                }
+                if node.historizing() {
+                    attributes.push_str(r#" Historizing="true""#);
+                }
+                value = node.value(TimestampsToReturn::Neither, NumericRange::None, &QualifiedName::null(), 0.0).value;
+            }
+            NodeType::VariableType(node) => {
+                let _ = write!(attributes, r#" DataType="{}""#, escape(&self.alias(&node.data_type())));
+                self.write_value_rank(&mut attributes, node.value_rank(), node.array_dimensions());
+                if node.is_abstract() {
+                    attributes.push_str(r#" IsAbstract="true""#);
+                }
+                value = node.value().and_then(|v| v.value);
+            }
+        }
+
+        let _ = writeln!(xml, "  <{}{}>", element, attributes);
+        Self::localized_text_element(xml, "    ", "DisplayName", &base.display_name());
+        if let Some(description) = base.description() {
+            if !description.text.is_empty() {
+                Self::localized_text_element(xml, "    ", "Description", &description);
+            }
+        }
+        self.write_references(xml, &node_id, exported);
+        if let Some(inverse_name) = inverse_name {
+            Self::localized_text_element(xml, "    ", "InverseName", &inverse_name);
+        }
+        if let Some(definition) = definition {
+            self.write_definition(xml, &base.browse_name(), &definition);
+        }
+        if let Some(value) = value {
+            if value != Variant::Empty {
+                let mut value_xml = String::new();
+                if self.write_value(&mut value_xml, "      ", &value) {
+                    let _ = writeln!(xml, "    <Value>");
+                    xml.push_str(&value_xml);
+                    let _ = writeln!(xml, "    </Value>");
+                }
+            }
+        }
+        let _ = writeln!(xml, "  </{}>", element);
+    }
+
+    /// Writes the `<Definition>` of a structured or enumerated data type
+    fn write_definition(&mut self, xml: &mut String, browse_name: &QualifiedName, definition: &DataTypeDefinition) {
+        let name = escape(&self.qualified_name(browse_name));
+        match definition {
+            DataTypeDefinition::Structure(definition) => {
+                if definition.structure_type == StructureType::Union {
+                    let _ = writeln!(xml, r#"    <Definition Name="{}" IsUnion="true">"#, name);
+                } else {
+                    let _ = writeln!(xml, r#"    <Definition Name="{}">"#, name);
+                }
+                for field in definition.fields.iter().flatten() {
+                    let mut attributes = format!(r#" Name="{}" DataType="{}""#, escape(field.name.as_ref()), escape(&self.alias(&field.data_type)));
+                    self.write_value_rank(&mut attributes, field.value_rank, field.array_dimensions.clone());
+                    if field.max_string_length > 0 {
+                        let _ = write!(attributes, r#" MaxStringLength="{}""#, field.max_string_length);
+                    }
+                    if field.is_optional {
+                        attributes.push_str(r#" IsOptional="true""#);
+                    }
+                    if field.description.text.is_empty() {
+                        let _ = writeln!(xml, "      <Field{} />", attributes);
+                    } else {
+                        let _ = writeln!(xml, "      <Field{}>", attributes);
+                        Self::localized_text_element(xml, "        ", "Description", &field.description);
+                        let _ = writeln!(xml, "      </Field>");
+                    }
+                }
+            }
+            DataTypeDefinition::Enum(definition) => {
+                let _ = writeln!(xml, r#"    <Definition Name="{}">"#, name);
+                for field in definition.fields.iter().flatten() {
+                    let _ = writeln!(xml, r#"      <Field Name="{}" Value="{}">"#, escape(field.name.as_ref()), field.value);
+                    if !field.display_name.text.is_empty() {
+                        Self::localized_text_element(xml, "        ", "DisplayName", &field.display_name);
+                    }
+                    if !field.description.text.is_empty() {
+                        Self::localized_text_element(xml, "        ", "Description", &field.description);
+                    }
+                    let _ = writeln!(xml, "      </Field>");
+                }
+            }
+        }
+        let _ = writeln!(xml, "    </Definition>");
+    }
+
+    fn write_value_rank(&self, attributes: &mut String, value_rank: i32, array_dimensions: Option<Vec<u32>>) {
+        if value_rank != -1 {
+            let _ = write!(attributes, r#" ValueRank="{}""#, value_rank);
+        }
+        if let Some(array_dimensions) = array_dimensions {
+            if value_rank > 0 && !array_dimensions.is_empty() {
+                let array_dimensions = array_dimensions.iter().map(|d| d.to_string()).collect::<Vec<_>>().join(",");
+                let _ = write!(attributes, r#" ArrayDimensions="{}""#, array_dimensions);
+            }
+        }
+    }
+
+    /// Writes the forward references of the node, and the inverse references from nodes that
+    /// are not part of the document so they are not lost.
+    fn write_references(&mut self, xml: &mut String, node_id: &NodeId, exported: &HashSet<NodeId>) {
+        let mut references = Vec::new();
+        if let Some(forward) = self.address_space.find_references::<NodeId>(node_id, None) {
+            forward.into_iter().for_each(|r| references.push((r.reference_type, r.target_node, true)));
+        }
+        if let Some(inverse) = self.address_space.find_inverse_references::<NodeId>(node_id, None) {
+            inverse.into_iter()
+                .filter(|r| !exported.contains(&r.target_node))
+                .for_each(|r| references.push((r.reference_type, r.target_node, false)));
+        }
+        if !references.is_empty() {
+            let _ = writeln!(xml, "    <References>");
+            for (reference_type, target_node, is_forward) in references {
+                let reference_type = escape(&self.alias(&reference_type));
+                let target_node = escape(&self.node_id(&target_node));
+                if is_forward {
+                    let _ = writeln!(xml, r#"      <Reference ReferenceType="{}">{}</Reference>"#, reference_type, target_node);
+                } else {
+                    let _ = writeln!(xml, r#"      <Reference ReferenceType="{}" IsForward="false">{}</Reference>"#, reference_type, target_node);
+                }
+            }
+            let _ = writeln!(xml, "    </References>");
+        }
+    }
+
+    /// Writes a value in the XML encoding (Part 6 5.3), returning false if the value cannot be
+    /// represented.
+    fn write_value(&mut self, xml: &mut String, indent: &str, value: &Variant) -> bool {
+        let (element, content) = match value {
+            Variant::Empty => return false,
+            Variant::Boolean(v) => ("Boolean", v.to_string()),
+            Variant::SByte(v) => ("SByte", v.to_string()),
+            Variant::Byte(v) => ("Byte", v.to_string()),
+            Variant::Int16(v) => ("Int16", v.to_string()),
+            Variant::UInt16(v) => ("UInt16", v.to_string()),
+            Variant::Int32(v) => ("Int32", v.to_string()),
+            Variant::UInt32(v) => ("UInt32", v.to_string()),
+            Variant::Int64(v) => ("Int64", v.to_string()),
+            Variant::UInt64(v) => ("UInt64", v.to_string()),
+            Variant::Float(v) => ("Float", format_float(*v as f64)),
+            Variant::Double(v) => ("Double", format_float(*v)),
+            Variant::String(v) => ("String", escape(v.as_ref())),
+            Variant::DateTime(v) => ("DateTime", v.to_string()),
+            Variant::Guid(v) => ("Guid", format!("<uax:String>{:?}</uax:String>", v)),
+            Variant::StatusCode(v) => ("StatusCode", format!("<uax:Code>{}</uax:Code>", v.bits())),
+            Variant::ByteString(v) => ("ByteString", v.as_base64()),
+            Variant::XmlElement(v) => ("XmlElement", v.as_ref().to_string()),
+            Variant::QualifiedName(v) => {
+                let ns = self.namespace(v.namespace_index);
+                ("QualifiedName", format!("<uax:NamespaceIndex>{}</uax:NamespaceIndex><uax:Name>{}</uax:Name>", ns, escape(v.name.as_ref())))
+            }
+            Variant::LocalizedText(v) => ("LocalizedText", Self::localized_text_content(v)),
+            Variant::NodeId(v) => ("NodeId", format!("<uax:Identifier>{}</uax:Identifier>", escape(&self.node_id(v)))),
+            Variant::ExpandedNodeId(v) => {
+                let identifier = if v.server_index == 0 && v.namespace_uri.is_null() {
+                    self.node_id(&v.node_id)
+                } else {
+                    v.to_string()
+                };
+                ("ExpandedNodeId", format!("<uax:Identifier>{}</uax:Identifier>", escape(&identifier)))
+            }
+            Variant::ExtensionObject(v) => ("ExtensionObject", self.extension_object_content(v)),
+            Variant::Array(array) => {
+                if array.has_dimensions() {
+                    warn!("Multi-dimensional array values cannot be exported to a nodeset");
+                    return false;
+                }
+                let element = match array.values.first() {
+                    Some(value) => Self::value_element(value),
+                    None => return false
+                };
+                let _ = writeln!(xml, "{}<uax:ListOf{}>", indent, element);
+                let item_indent = format!("{}  ", indent);
+                for value in &array.values {
+                    self.write_value(xml, &item_indent, value);
+                }
+                let _ = writeln!(xml, "{}</uax:ListOf{}>", indent, element);
+                return true;
+            }
+        };
+        let _ = writeln!(xml, "{}<uax:{}>{}</uax:{}>", indent, element, content, element);
+        true
+    }
+
+    /// Returns the element name of a scalar value
+    fn value_element(value: &Variant) -> &'static str {
+        match value {
+            Variant::Boolean(_) => "Boolean",
+            Variant::SByte(_) => "SByte",
+            Variant::Byte(_) => "Byte",
+            Variant::Int16(_) => "Int16",
+            Variant::UInt16(_) => "UInt16",
+            Variant::Int32(_) => "Int32",
+            Variant::UInt32(_) => "UInt32",
+            Variant::Int64(_) => "Int64",
+            Variant::UInt64(_) => "UInt64",
+            Variant::Float(_) => "Float",
+            Variant::Double(_) => "Double",
+            Variant::String(_) => "String",
+            Variant::DateTime(_) => "DateTime",
+            Variant::Guid(_) => "Guid",
+            Variant::StatusCode(_) => "StatusCode",
+            Variant::ByteString(_) => "ByteString",
+            Variant::XmlElement(_) => "XmlElement",
+            Variant::QualifiedName(_) => "QualifiedName",
+            Variant::LocalizedText(_) => "LocalizedText",
+            Variant::NodeId(_) => "NodeId",
+            Variant::ExpandedNodeId(_) => "ExpandedNodeId",
+            Variant::ExtensionObject(_) => "ExtensionObject",
+            Variant::Empty | Variant::Array(_) => "Variant",
+        }
+    }
+
+    fn localized_text_content(value: &LocalizedText) -> String {
+        format!("<uax:Locale>{}</uax:Locale><uax:Text>{}</uax:Text>", escape(value.locale.as_ref()), escape(value.text.as_ref()))
+    }
+
+    /// Writes the type id and body of an extension object. Known types are written with their
+    /// XML encoding, other binary bodies are written as a ByteString.
+    fn extension_object_content(&mut self, value: &ExtensionObject) -> String {
+        let decoding_limits = DecodingLimits::default();
+        let known_body = match value.object_id() {
+            Ok(ObjectId::Argument_Encoding_DefaultBinary) => value.decode_inner::<Argument>(&decoding_limits).ok().map(|v| {
+                let array_dimensions = v.array_dimensions.as_ref().map_or(String::new(), |d| {
+                    d.iter().map(|d| format!("<uax:UInt32>{}</uax:UInt32>", d)).collect::<String>()
+                });
+                let body = format!("<uax:Argument><uax:Name>{}</uax:Name><uax:DataType><uax:Identifier>{}</uax:Identifier></uax:DataType><uax:ValueRank>{}</uax:ValueRank><uax:ArrayDimensions>{}</uax:ArrayDimensions><uax:Description>{}</uax:Description></uax:Argument>",
+                                   escape(v.name.as_ref()), escape(&self.node_id(&v.data_type)), v.value_rank, array_dimensions, Self::localized_text_content(&v.description));
+                (297, body)
+            }),
+            Ok(ObjectId::EnumValueType_Encoding_DefaultBinary) => value.decode_inner::<EnumValueType>(&decoding_limits).ok().map(|v| {
+                let body = format!("<uax:EnumValueType><uax:Value>{}</uax:Value><uax:DisplayName>{}</uax:DisplayName><uax:Description>{}</uax:Description></uax:EnumValueType>",
+                                   v.value, Self::localized_text_content(&v.display_name), Self::localized_text_content(&v.description));
+                (7616, body)
+            }),
+            Ok(ObjectId::EUInformation_Encoding_DefaultBinary) => value.decode_inner::<EUInformation>(&decoding_limits).ok().map(|v| {
+                let body = format!("<uax:EUInformation><uax:NamespaceUri>{}</uax:NamespaceUri><uax:UnitId>{}</uax:UnitId><uax:DisplayName>{}</uax:DisplayName><uax:Description>{}</uax:Description></uax:EUInformation>",
+                                   escape(v.namespace_uri.as_ref()), v.unit_id, Self::localized_text_content(&v.display_name), Self::localized_text_content(&v.description));
+                (888, body)
+            }),
+            Ok(ObjectId::Range_Encoding_DefaultBinary) => value.decode_inner::<Range>(&decoding_limits).ok().map(|v| {
+                (885, format!("<uax:Range><uax:Low>{}</uax:Low><uax:High>{}</uax:High></uax:Range>", format_float(v.low), format_float(v.high)))
+            }),
+            _ => None
+        };
+        if let Some((type_id, body)) = known_body {
+            format!("<uax:TypeId><uax:Identifier>i={}</uax:Identifier></uax:TypeId><uax:Body>{}</uax:Body>", type_id, body)
+        } else {
+            let type_id = escape(&self.node_id(&value.node_id));
+            match value.body {
+                ExtensionObjectEncoding::None => format!("<uax:TypeId><uax:Identifier>{}</uax:Identifier></uax:TypeId>", type_id),
+                ExtensionObjectEncoding::ByteString(ref body) => {
+                    format!("<uax:TypeId><uax:Identifier>{}</uax:Identifier></uax:TypeId><uax:Body><uax:ByteString>{}</uax:ByteString></uax:Body>", type_id, body.as_base64())
+                }
+                ExtensionObjectEncoding::XmlElement(ref body) => {
+                    format!("<uax:TypeId><uax:Identifier>{}</uax:Identifier></uax:TypeId><uax:Body>{}</uax:Body>", type_id, body.as_ref())
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,760 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! Contains the importer that loads a UANodeSet2 XML document (Part 6 Annex F) into the
+//! `AddressSpace` at runtime. This is the runtime equivalent of generating Rust from a nodeset with
+//! `tools/schema/gen_nodeset.js`.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    result::Result,
+    str::FromStr,
+};
+
+use roxmltree::{Document, Node};
+
+use opcua_types::{
+    *,
+    argument::Argument,
+    service_types::{EnumDefinition, EnumField, EnumValueType, EUInformation, Range, StructureDefinition, StructureField, StructureType},
+    status_code::StatusCode,
+};
+
+use crate::address_space::{
+    address_space::AddressSpace,
+    base::Base,
+    custom_data_types,
+    data_type::DataType,
+    method::Method,
+    node::{NodeBase, NodeType},
+    object::Object,
+    object_type::ObjectType,
+    reference_type::ReferenceType,
+    references::ReferenceDirection,
+    variable::Variable,
+    variable_type::VariableType,
+    view::View,
+    AccessLevel, EventNotifier, UserAccessLevel,
+};
+
+/// The root element of a UANodeSet2 document
+const UA_NODESET_ROOT: &str = "UANodeSet";
+
+/// The OPC UA namespace which is always present as namespace 0
+const OPC_UA_NAMESPACE_URI: &str = "http://opcfoundation.org/UA/";
+
+/// Imports all the nodes of a UANodeSet2 file into the address space, returning the number of
+/// nodes that were inserted.
+pub(crate) fn import_file(address_space: &mut AddressSpace, path: &Path) -> Result<usize, ()> {
+    let xml = read_file(path)?;
+    import_str(address_space, &xml).map_err(|_| {
+        error!("Cannot import nodeset file {}", path.display());
+    })
+}
+
+/// Imports every `.xml` file in a directory. Files are imported so that a model is loaded after
+/// the models it requires, regardless of the order of file names.
+pub(crate) fn import_dir(address_space: &mut AddressSpace, path: &Path) -> Result<usize, ()> {
+    let entries = fs::read_dir(path).map_err(|err| {
+        error!("Cannot read nodeset directory {}, error = {}", path.display(), err);
+    })?;
+    let mut paths = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.extension().map(|ext| ext.eq_ignore_ascii_case("xml")).unwrap_or(false))
+        .collect::<Vec<PathBuf>>();
+    paths.sort();
+
+    // Read the models each file provides and requires
+    let mut pending = Vec::with_capacity(paths.len());
+    for path in paths {
+        let xml = read_file(&path)?;
+        let (provides, requires) = {
+            let document = parse_document(&xml)?;
+            model_dependencies(&document)
+        };
+        pending.push((path, xml, provides, requires));
+    }
+
+    let mut total = 0;
+    while !pending.is_empty() {
+        // Pick the first file whose required models are all present, or the first file if there
+        // is a missing or circular dependency.
+        let idx = pending.iter()
+            .position(|(_, _, provides, requires)| {
+                requires.iter().all(|uri| provides.contains(uri) || address_space.namespace_index(uri).is_some())
+            })
+            .unwrap_or_else(|| {
+                warn!("Nodeset file {} requires models that have not been loaded", pending[0].0.display());
+                0
+            });
+        let (path, xml, _, _) = pending.remove(idx);
+        debug!("Importing nodeset file {}", path.display());
+        total += import_str(address_space, &xml).map_err(|_| {
+            error!("Cannot import nodeset file {}", path.display());
+        })?;
+    }
+    Ok(total)
+}
+
+/// Imports all the nodes of a UANodeSet2 document into the address space, returning the number
+/// of nodes that were inserted.
+pub(crate) fn import_str(address_space: &mut AddressSpace, xml: &str) -> Result<usize, ()> {
+    let document = parse_document(xml)?;
+    let nodeset = document.root_element();
+
+    let (_, requires) = model_dependencies(&document);
+    requires.iter()
+        .filter(|uri| address_space.namespace_index(uri).is_none())
+        .for_each(|uri| {
+            warn!("Nodeset requires model {} which is not in the address space", uri);
+        });
+
+    // Registering a namespace makes it the default namespace which is not what an import should do
+    let default_namespace = address_space.default_namespace();
+    let result = NodeSetImporter::new(xml, address_space, nodeset).and_then(|importer| importer.import(nodeset));
+    address_space.set_default_namespace(default_namespace);
+    result
+}
+
+fn read_file(path: &Path) -> Result<String, ()> {
+    fs::read_to_string(path).map_err(|err| {
+        error!("Cannot read nodeset file {}, error = {}", path.display(), err);
+    })
+}
+
+fn parse_document(xml: &str) -> Result<Document<'_>, ()> {
+    let document = Document::parse(xml).map_err(|err| {
+        error!("Cannot parse nodeset, error = {}", err);
+    })?;
+    if document.root_element().tag_name().name() != UA_NODESET_ROOT {
+        error!("Document is not a nodeset, root element is {}", document.root_element().tag_name().name());
+        Err(())
+    } else {
+        Ok(document)
+    }
+}
+
+/// Returns the model uris provided and required by the nodeset. A nodeset without a `<Models>`
+/// element is assumed to provide the namespaces it declares.
+fn model_dependencies(document: &Document) -> (HashSet<String>, HashSet<String>) {
+    let nodeset = document.root_element();
+    let mut provides = HashSet::new();
+    let mut requires = HashSet::new();
+    if let Some(models) = child(nodeset, "Models") {
+        children(models, "Model").for_each(|model| {
+            if let Some(uri) = model.attribute("ModelUri") {
+                provides.insert(uri.to_string());
+            }
+            children(model, "RequiredModel")
+                .filter_map(|required| required.attribute("ModelUri"))
+                .for_each(|uri| { requires.insert(uri.to_string()); });
+        });
+    } else if let Some(namespace_uris) = child(nodeset, "NamespaceUris") {
+        children(namespace_uris, "Uri").for_each(|uri| { provides.insert(text(uri).to_string()); });
+    }
+    requires.remove(OPC_UA_NAMESPACE_URI);
+    for uri in &provides {
+        requires.remove(uri);
+    }
+    (provides, requires)
+}
+
+/// Returns the first child element with the local name
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|n| n.is_element() && n.tag_name().name() == name)
+}
+
+/// Returns all the child elements with the local name
+fn children<'a, 'input: 'a>(node: Node<'a, 'input>, name: &'a str) -> impl Iterator<Item=Node<'a, 'input>> + 'a {
+    node.children().filter(move |n| n.is_element() && n.tag_name().name() == name)
+}
+
+/// Returns the trimmed text of an element
+fn text<'a>(node: Node<'a, '_>) -> &'a str {
+    node.text().map(|t| t.trim()).unwrap_or("")
+}
+
+/// Returns the trimmed text of a child element
+fn child_text<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    child(node, name).map(text)
+}
+
+fn parse_text<T>(value: &str) -> Result<T, ()> where T: FromStr {
+    value.parse::<T>().map_err(|_| {
+        error!("Cannot parse value \"{}\" in nodeset", value);
+    })
+}
+
+fn parse_attribute<T>(node: Node, name: &str, default: T) -> Result<T, ()> where T: FromStr {
+    node.attribute(name).map_or(Ok(default), parse_text)
+}
+
+fn parse_bool(value: &str) -> Result<bool, ()> {
+    match value {
+        "true" | "1" => Ok(true),
+        "false" | "0" => Ok(false),
+        _ => {
+            error!("Cannot parse boolean \"{}\" in nodeset", value);
+            Err(())
+        }
+    }
+}
+
+fn parse_bool_attribute(node: Node, name: &str, default: bool) -> Result<bool, ()> {
+    node.attribute(name).map_or(Ok(default), parse_bool)
+}
+
+fn parse_array_dimensions(value: &str) -> Result<Vec<u32>, ()> {
+    value.split(',').map(|d| parse_text::<u32>(d.trim())).collect()
+}
+
+/// Parses a float the way XML Schema writes them, i.e. with INF, -INF and NaN
+fn parse_float<T>(value: &str) -> Result<T, ()> where T: FromStr {
+    match value {
+        "INF" => parse_text("inf"),
+        "-INF" => parse_text("-inf"),
+        _ => parse_text(value),
+    }
+}
+
+struct NodeSetImporter<'a, 'input> {
+    /// The document text, used for preserving unknown extension object bodies
+    xml: &'input str,
+    address_space: &'a mut AddressSpace,
+    /// Maps the namespace indexes in the document to the indexes in the address space
+    namespace_map: Vec<u16>,
+    /// Namespaces in the document that must be registered in the address space
+    new_namespaces: Vec<String>,
+    /// Aliases from the document
+    aliases: HashMap<String, String>,
+}
+
+impl<'a, 'input> NodeSetImporter<'a, 'input> {
+    fn new(xml: &'input str, address_space: &'a mut AddressSpace, nodeset: Node<'_, 'input>) -> Result<Self, ()> {
+        // Index 0 is always the OPC UA namespace, the others are mapped in the order they appear.
+        // Namespaces that are not in the address space are given the indexes they will be
+        // registered with, but are only registered once the whole document has been read.
+        let mut namespace_map = vec![0u16];
+        let mut new_namespaces: Vec<String> = Vec::new();
+        if let Some(namespace_uris) = child(nodeset, "NamespaceUris") {
+            for uri in children(namespace_uris, "Uri").map(text) {
+                let ns = if let Some(ns) = address_space.namespace_index(uri) {
+                    ns
+                } else if let Some(idx) = new_namespaces.iter().position(|n| n == uri) {
+                    (address_space.namespaces().len() + idx) as u16
+                } else if address_space.namespaces().len() + new_namespaces.len() <= u16::MAX as usize {
+                    new_namespaces.push(uri.to_string());
+                    (address_space.namespaces().len() + new_namespaces.len() - 1) as u16
+                } else {
+                    error!("Cannot register namespace \"{}\" from nodeset", uri);
+                    return Err(());
+                };
+                namespace_map.push(ns);
+            }
+        }
+
+        let mut aliases = HashMap::new();
+        if let Some(alias_list) = child(nodeset, "Aliases") {
+            for alias in children(alias_list, "Alias") {
+                if let (Some(name), Some(node_id)) = (alias.attribute("Alias"), alias.text()) {
+                    aliases.insert(name.to_string(), node_id.trim().to_string());
+                }
+            }
+        }
+
+        Ok(NodeSetImporter {
+            xml,
+            address_space,
+            namespace_map,
+            new_namespaces,
+            aliases,
+        })
+    }
+
+    /// Imports the nodes. Every node in the document is read before the address space is
+    /// touched, so a document with a malformed node leaves the address space as it was.
+    fn import(self, nodeset: Node) -> Result<usize, ()> {
+        let mut nodes = Vec::new();
+        let mut structures = Vec::new();
+        for node in nodeset.children().filter(|n| n.is_element()) {
+            let node_type = match node.tag_name().name() {
+                "UAObject" => self.object(node)?,
+                "UAVariable" => self.variable(node)?,
+                "UAMethod" => self.method(node)?,
+                "UAView" => self.view(node)?,
+                "UAObjectType" => self.object_type(node)?,
+                "UAVariableType" => self.variable_type(node)?,
+                "UADataType" => self.data_type(node)?,
+                "UAReferenceType" => self.reference_type(node)?,
+                _ => continue
+            };
+            if let NodeType::DataType(ref data_type) = node_type {
+                if data_type.data_type_definition().and_then(|d| d.as_structure()).is_some() {
+                    structures.push(data_type.node_id());
+                }
+            }
+            let references = self.references(node)?;
+            nodes.push((node_type, references));
+        }
+
+        for uri in &self.new_namespaces {
+            self.address_space.register_namespace(uri)?;
+        }
+
+        let mut inserted = 0;
+        for (node_type, references) in nodes {
+            let references = references.iter()
+                .map(|(target, reference_type, direction)| (target, reference_type, *direction))
+                .collect::<Vec<_>>();
+            if self.address_space.insert(node_type, Some(references.as_slice())) {
+                inserted += 1;
+            }
+        }
+        // The default encoding of a structure is only known once its encoding nodes are imported
+        for data_type_id in structures {
+            if let Some(encoding_id) = custom_data_types::default_binary_encoding(self.address_space, &data_type_id) {
+                if let Some(NodeType::DataType(data_type)) = self.address_space.find_node_mut(&data_type_id) {
+                    if let Some(DataTypeDefinition::Structure(mut definition)) = data_type.data_type_definition().cloned() {
+                        definition.default_encoding_id = encoding_id;
+                        data_type.set_data_type_definition(definition.into());
+                    }
+                }
+            }
+        }
+        debug!("Imported {} nodes from nodeset", inserted);
+        Ok(inserted)
+    }
+
+    /// Maps a namespace index in the document to the index in the address space
+    fn namespace(&self, ns: u16) -> Result<u16, ()> {
+        self.namespace_map.get(ns as usize).copied().ok_or_else(|| {
+            error!("Namespace index {} is not declared in the nodeset NamespaceUris", ns);
+        })
+    }
+
+    /// Parses a node id, resolving aliases and remapping the namespace index
+    fn node_id(&self, value: &str) -> Result<NodeId, ()> {
+        let value = self.aliases.get(value).map(|v| v.as_str()).unwrap_or(value);
+        let mut node_id = NodeId::from_str(value).map_err(|_| {
+            error!("Cannot parse node id \"{}\" in nodeset", value);
+        })?;
+        node_id.namespace = self.namespace(node_id.namespace)?;
+        Ok(node_id)
+    }
+
+    fn expanded_node_id(&self, value: &str) -> Result<ExpandedNodeId, ()> {
+        if value.starts_with("svr=") {
+            let mut node_id = ExpandedNodeId::from_str(value).map_err(|_| {
+                error!("Cannot parse expanded node id \"{}\" in nodeset", value);
+            })?;
+            if node_id.namespace_uri.is_null() {
+                node_id.node_id.namespace = self.namespace(node_id.node_id.namespace)?;
+            }
+            Ok(node_id)
+        } else {
+            self.node_id(value).map(|node_id| node_id.into())
+        }
+    }
+
+    /// Parses a browse name of the form "ns:Name" where the namespace index is optional
+    fn qualified_name(&self, value: &str) -> Result<QualifiedName, ()> {
+        if let Some(idx) = value.find(':') {
+            if let Ok(ns) = value[..idx].parse::<u16>() {
+                return Ok(QualifiedName::new(self.namespace(ns)?, &value[idx + 1..]));
+            }
+        }
+        Ok(QualifiedName::new(0, value))
+    }
+
+    fn localized_text(node: Node) -> LocalizedText {
+        LocalizedText::new(node.attribute("Locale").unwrap_or(""), node.text().unwrap_or(""))
+    }
+
+    /// Creates the base of a node from the common UANode attributes, returning it along with the
+    /// display name and description
+    fn base(&self, node: Node, node_class: NodeClass) -> Result<Base, ()> {
+        let node_id = node.attribute("NodeId").ok_or_else(|| {
+            error!("Nodeset element {} has no NodeId", node.tag_name().name());
+        })?;
+        let node_id = self.node_id(node_id)?;
+        let browse_name = self.qualified_name(node.attribute("BrowseName").unwrap_or(""))?;
+        let display_name = child(node, "DisplayName")
+            .map(Self::localized_text)
+            .unwrap_or_else(|| LocalizedText::new("", browse_name.name.as_ref()));
+
+        let mut base = Base::new(node_class, &node_id, browse_name, display_name);
+        if let Some(description) = child(node, "Description") {
+            base.set_description(Self::localized_text(description));
+        }
+        if let Some(write_mask) = node.attribute("WriteMask") {
+            base.set_write_mask(WriteMask::from_bits_truncate(parse_text(write_mask)?));
+        }
+        if let Some(user_write_mask) = node.attribute("UserWriteMask") {
+            base.set_user_write_mask(WriteMask::from_bits_truncate(parse_text(user_write_mask)?));
+        }
+        Ok(base)
+    }
+
+    /// Copies the common attributes of the base onto the node
+    fn apply_base<T>(node: &mut T, base: &Base) where T: NodeBase {
+        if let Some(description) = base.description() {
+            node.set_description(description);
+        }
+        if let Some(write_mask) = base.write_mask() {
+            node.set_write_mask(write_mask);
+        }
+        if let Some(user_write_mask) = base.user_write_mask() {
+            node.set_user_write_mask(user_write_mask);
+        }
+    }
+
+    fn references(&self, node: Node) -> Result<Vec<(NodeId, NodeId, ReferenceDirection)>, ()> {
+        let mut references = Vec::new();
+        if let Some(reference_list) = child(node, "References") {
+            for reference in children(reference_list, "Reference") {
+                let reference_type = reference.attribute("ReferenceType").ok_or_else(|| {
+                    error!("Reference in nodeset has no ReferenceType");
+                })?;
+                let reference_type = self.node_id(reference_type)?;
+                let target = self.node_id(text(reference))?;
+                let direction = if parse_bool_attribute(reference, "IsForward", true)? {
+                    ReferenceDirection::Forward
+                } else {
+                    ReferenceDirection::Inverse
+                };
+                references.push((target, reference_type, direction));
+            }
+        }
+        Ok(references)
+    }
+
+    fn object(&self, node: Node) -> Result<NodeType, ()> {
+        let base = self.base(node, NodeClass::Object)?;
+        let event_notifier = EventNotifier::from_bits_truncate(parse_attribute(node, "EventNotifier", 0u8)?);
+        let mut object = Object::new(&base.node_id(), base.browse_name(), base.display_name(), event_notifier);
+        Self::apply_base(&mut object, &base);
+        Ok(object.into())
+    }
+
+    fn view(&self, node: Node) -> Result<NodeType, ()> {
+        let base = self.base(node, NodeClass::View)?;
+        let event_notifier = EventNotifier::from_bits_truncate(parse_attribute(node, "EventNotifier", 0u8)?);
+        let contains_no_loops = parse_bool_attribute(node, "ContainsNoLoops", false)?;
+        let mut view = View::new(&base.node_id(), base.browse_name(), base.display_name(), event_notifier, contains_no_loops);
+        Self::apply_base(&mut view, &base);
+        Ok(view.into())
+    }
+
+    fn variable(&self, node: Node) -> Result<NodeType, ()> {
+        let base = self.base(node, NodeClass::Variable)?;
+        let data_type = self.node_id(node.attribute("DataType").unwrap_or("i=24"))?;
+        let value = self.node_value(node)?;
+        let mut variable = Variable::new_data_value(&base.node_id(), base.browse_name(), base.display_name(), data_type, None, None, value);
+        Self::apply_base(&mut variable, &base);
+        variable.set_value_rank(parse_attribute(node, "ValueRank", -1i32)?);
+        if let Some(array_dimensions) = node.attribute("ArrayDimensions") {
+            variable.set_array_dimensions(&parse_array_dimensions(array_dimensions)?);
+        }
+        variable.set_access_level(AccessLevel::from_bits_truncate(parse_attribute(node, "AccessLevel", 1u8)?));
+        variable.set_user_access_level(UserAccessLevel::from_bits_truncate(parse_attribute(node, "UserAccessLevel", 1u8)?));
+        variable.set_historizing(parse_bool_attribute(node, "Historizing", false)?);
+        if let Some(minimum_sampling_interval) = node.attribute("MinimumSamplingInterval") {
+            variable.set_minimum_sampling_interval(parse_float(minimum_sampling_interval)?);
+        }
+        Ok(variable.into())
+    }
+
+    fn method(&self, node: Node) -> Result<NodeType, ()> {
+        let base = self.base(node, NodeClass::Method)?;
+        let executable = parse_bool_attribute(node, "Executable", true)?;
+        let user_executable = parse_bool_attribute(node, "UserExecutable", true)?;
+        let mut method = Method::new(&base.node_id(), base.browse_name(), base.display_name(), executable, user_executable);
+        Self::apply_base(&mut method, &base);
+        Ok(method.into())
+    }
+
+    fn object_type(&self, node: Node) -> Result<NodeType, ()> {
+        let base = self.base(node, NodeClass::ObjectType)?;
+        let is_abstract = parse_bool_attribute(node, "IsAbstract", false)?;
+        let mut object_type = ObjectType::new(&base.node_id(), base.browse_name(), base.display_name(), is_abstract);
+        Self::apply_base(&mut object_type, &base);
+        Ok(object_type.into())
+    }
+
+    fn variable_type(&self, node: Node) -> Result<NodeType, ()> {
+        let base = self.base(node, NodeClass::VariableType)?;
+        let data_type = self.node_id(node.attribute("DataType").unwrap_or("i=24"))?;
+        let is_abstract = parse_bool_attribute(node, "IsAbstract", false)?;
+        let value_rank = parse_attribute(node, "ValueRank", -1i32)?;
+        let mut variable_type = VariableType::new(&base.node_id(), base.browse_name(), base.display_name(), data_type, is_abstract, value_rank);
+        Self::apply_base(&mut variable_type, &base);
+        if let Some(array_dimensions) = node.attribute("ArrayDimensions") {
+            variable_type.set_array_dimensions(&parse_array_dimensions(array_dimensions)?);
+        }
+        if child(node, "Value").is_some() {
+            variable_type.set_value(self.node_value(node)?);
+        }
+        Ok(variable_type.into())
+    }
+
+    fn data_type(&self, node: Node) -> Result<NodeType, ()> {
+        let base = self.base(node, NodeClass::DataType)?;
+        let is_abstract = parse_bool_attribute(node, "IsAbstract", false)?;
+        let mut data_type = DataType::new(&base.node_id(), base.browse_name(), base.display_name(), is_abstract);
+        Self::apply_base(&mut data_type, &base);
+        if let Some(definition) = child(node, "Definition") {
+            data_type.set_data_type_definition(self.definition(node, definition)?);
+        }
+        Ok(data_type.into())
+    }
+
+    /// Parses the `<Definition>` of a data type. Enumerations are recognised by being a subtype of
+    /// `Enumeration` or by their fields having values.
+    fn definition(&self, node: Node, definition: Node) -> Result<DataTypeDefinition, ()> {
+        let super_type = self.references(node)?.into_iter()
+            .find(|(_, reference_type, direction)| *reference_type == ReferenceTypeId::HasSubtype.into() && matches!(direction, ReferenceDirection::Inverse))
+            .map(|(target, _, _)| target)
+            .unwrap_or_else(NodeId::null);
+        let is_enum = super_type == DataTypeId::Enumeration.into() || children(definition, "Field").any(|f| f.attribute("Value").is_some());
+        if is_enum {
+            let mut fields = Vec::new();
+            for field in children(definition, "Field") {
+                let name = field.attribute("Name").unwrap_or("");
+                let display_name = child(field, "DisplayName").map(Self::localized_text).unwrap_or_else(|| LocalizedText::new("", name));
+                fields.push(EnumField {
+                    value: parse_attribute(field, "Value", 0i64)?,
+                    display_name,
+                    description: child(field, "Description").map(Self::localized_text).unwrap_or_else(LocalizedText::null),
+                    name: UAString::from(name),
+                });
+            }
+            Ok(EnumDefinition { fields: Some(fields) }.into())
+        } else {
+            let mut fields = Vec::new();
+            let mut structure_type = if parse_bool_attribute(definition, "IsUnion", false)? {
+                StructureType::Union
+            } else {
+                StructureType::Structure
+            };
+            for field in children(definition, "Field") {
+                let is_optional = parse_bool_attribute(field, "IsOptional", false)?;
+                if is_optional && structure_type == StructureType::Structure {
+                    structure_type = StructureType::StructureWithOptionalFields;
+                }
+                let data_type = match field.attribute("DataType") {
+                    Some(data_type) => self.node_id(data_type)?,
+                    None => DataTypeId::BaseDataType.into()
+                };
+                let array_dimensions = match field.attribute("ArrayDimensions") {
+                    Some(array_dimensions) => Some(parse_array_dimensions(array_dimensions)?),
+                    None => None
+                };
+                fields.push(StructureField {
+                    name: UAString::from(field.attribute("Name").unwrap_or("")),
+                    description: child(field, "Description").map(Self::localized_text).unwrap_or_else(LocalizedText::null),
+                    data_type,
+                    value_rank: parse_attribute(field, "ValueRank", -1i32)?,
+                    array_dimensions,
+                    max_string_length: parse_attribute(field, "MaxStringLength", 0u32)?,
+                    is_optional,
+                });
+            }
+            Ok(StructureDefinition {
+                default_encoding_id: NodeId::null(),
+                base_data_type: super_type,
+                structure_type,
+                fields: Some(fields),
+            }.into())
+        }
+    }
+
+    fn reference_type(&self, node: Node) -> Result<NodeType, ()> {
+        let base = self.base(node, NodeClass::ReferenceType)?;
+        let is_abstract = parse_bool_attribute(node, "IsAbstract", false)?;
+        let symmetric = parse_bool_attribute(node, "Symmetric", false)?;
+        let inverse_name = child(node, "InverseName").map(Self::localized_text);
+        let mut reference_type = ReferenceType::new(&base.node_id(), base.browse_name(), base.display_name(), inverse_name, symmetric, is_abstract);
+        Self::apply_base(&mut reference_type, &base);
+        Ok(reference_type.into())
+    }
+
+    /// Decodes the `<Value>` of a variable or variable type. A missing or empty value is `Empty`.
+    fn node_value(&self, node: Node) -> Result<Variant, ()> {
+        match child(node, "Value").and_then(|value| value.children().find(|n| n.is_element())) {
+            Some(value) => self.value(value),
+            None => Ok(Variant::Empty)
+        }
+    }
+
+    /// Decodes a value element of the XML encoding (Part 6 5.3)
+    fn value(&self, node: Node) -> Result<Variant, ()> {
+        let name = node.tag_name().name();
+        if name.starts_with("ListOf") {
+            let values = node.children()
+                .filter(|n| n.is_element())
+                .map(|n| self.value(n))
+                .collect::<Result<Vec<Variant>, ()>>()?;
+            return Ok(Variant::from(values));
+        }
+        let value = match name {
+            "Boolean" => Variant::Boolean(parse_bool(text(node))?),
+            "SByte" => Variant::SByte(parse_text(text(node))?),
+            "Byte" => Variant::Byte(parse_text(text(node))?),
+            "Int16" => Variant::Int16(parse_text(text(node))?),
+            "UInt16" => Variant::UInt16(parse_text(text(node))?),
+            "Int32" => Variant::Int32(parse_text(text(node))?),
+            "UInt32" => Variant::UInt32(parse_text(text(node))?),
+            "Int64" => Variant::Int64(parse_text(text(node))?),
+            "UInt64" => Variant::UInt64(parse_text(text(node))?),
+            "Float" => Variant::Float(parse_float(text(node))?),
+            "Double" => Variant::Double(parse_float(text(node))?),
+            "String" => Variant::String(UAString::from(node.text().unwrap_or(""))),
+            "DateTime" => Variant::from(DateTime::from_str(text(node))?),
+            "Guid" => {
+                let guid = child_text(node, "String").unwrap_or_else(|| text(node));
+                Variant::from(Guid::from_str(guid)?)
+            }
+            "ByteString" => {
+                let data = text(node).split_whitespace().collect::<String>();
+                Variant::ByteString(ByteString::from_base64(&data).ok_or_else(|| {
+                    error!("Cannot decode ByteString in nodeset");
+                })?)
+            }
+            "XmlElement" => Variant::XmlElement(self.inner_xml(node)),
+            "NodeId" => Variant::from(self.node_id(child_text(node, "Identifier").unwrap_or(""))?),
+            "ExpandedNodeId" => Variant::from(self.expanded_node_id(child_text(node, "Identifier").unwrap_or(""))?),
+            "StatusCode" => {
+                let code = parse_text::<u32>(child_text(node, "Code").unwrap_or("0"))?;
+                Variant::StatusCode(StatusCode::from_u32(code).ok_or_else(|| {
+                    error!("Invalid status code {} in nodeset", code);
+                })?)
+            }
+            "QualifiedName" => Variant::from(self.qualified_name_value(node)?),
+            "LocalizedText" => Variant::from(Self::localized_text_value(node)),
+            "ExtensionObject" => Variant::from(self.extension_object(node)?),
+            "Variant" => match child(node, "Value").and_then(|value| value.children().find(|n| n.is_element())) {
+                Some(value) => self.value(value)?,
+                None => Variant::Empty
+            },
+            _ => {
+                error!("Value of type {} in nodeset is not supported", name);
+                return Err(());
+            }
+        };
+        Ok(value)
+    }
+
+    /// Returns the elements inside a node as an `XmlElement`
+    fn inner_xml(&self, node: Node) -> XmlElement {
+        let xml = node.children()
+            .filter(|n| n.is_element())
+            .map(|n| &self.xml[n.range()])
+            .collect::<String>();
+        XmlElement::from(xml)
+    }
+
+    fn qualified_name_value(&self, node: Node) -> Result<QualifiedName, ()> {
+        let ns = parse_text::<u16>(child_text(node, "NamespaceIndex").unwrap_or("0"))?;
+        Ok(QualifiedName::new(self.namespace(ns)?, child_text(node, "Name").unwrap_or("")))
+    }
+
+    fn localized_text_value(node: Node) -> LocalizedText {
+        LocalizedText::new(child_text(node, "Locale").unwrap_or(""), child(node, "Text").and_then(|n| n.text()).unwrap_or(""))
+    }
+
+    /// Decodes an extension object. Bodies of known types are converted to their binary encoding
+    /// so the server can serve them to any client. Other bodies are preserved as XML.
+    fn extension_object(&self, node: Node) -> Result<ExtensionObject, ()> {
+        let type_id = self.node_id(child(node, "TypeId").and_then(|n| child_text(n, "Identifier")).unwrap_or(""))?;
+        let body = match child(node, "Body").and_then(|n| n.children().find(|n| n.is_element())) {
+            Some(body) => body,
+            None => return Ok(ExtensionObject::null())
+        };
+
+        // A body in the binary encoding is held in a ByteString
+        if body.tag_name().name() == "ByteString" {
+            let data = text(body).split_whitespace().collect::<String>();
+            let body = ByteString::from_base64(&data).ok_or_else(|| {
+                error!("Cannot decode ExtensionObject ByteString body in nodeset");
+            })?;
+            return Ok(ExtensionObject {
+                node_id: type_id,
+                body: ExtensionObjectEncoding::ByteString(body),
+            });
+        }
+
+        // The type id should be the XML encoding id, but some nodesets use the data type id
+        let known_type = if type_id.namespace == 0 {
+            match type_id.identifier {
+                Identifier::Numeric(296) | Identifier::Numeric(297) | Identifier::Numeric(298) => Some(ObjectId::Argument_Encoding_DefaultBinary),
+                Identifier::Numeric(7594) | Identifier::Numeric(7616) | Identifier::Numeric(8251) => Some(ObjectId::EnumValueType_Encoding_DefaultBinary),
+                Identifier::Numeric(887) | Identifier::Numeric(888) | Identifier::Numeric(889) => Some(ObjectId::EUInformation_Encoding_DefaultBinary),
+                Identifier::Numeric(884) | Identifier::Numeric(885) | Identifier::Numeric(886) => Some(ObjectId::Range_Encoding_DefaultBinary),
+                _ => None
+            }
+        } else {
+            None
+        };
+
+        let extension_object = match known_type {
+            Some(ObjectId::Argument_Encoding_DefaultBinary) => ExtensionObject::from_encodable(ObjectId::Argument_Encoding_DefaultBinary, &self.argument(body)?),
+            Some(ObjectId::EnumValueType_Encoding_DefaultBinary) => {
+                let value = EnumValueType {
+                    value: parse_text(child_text(body, "Value").unwrap_or("0"))?,
+                    display_name: child(body, "DisplayName").map(Self::localized_text_value).unwrap_or_else(LocalizedText::null),
+                    description: child(body, "Description").map(Self::localized_text_value).unwrap_or_else(LocalizedText::null),
+                };
+                ExtensionObject::from_encodable(ObjectId::EnumValueType_Encoding_DefaultBinary, &value)
+            }
+            Some(ObjectId::EUInformation_Encoding_DefaultBinary) => {
+                let value = EUInformation {
+                    namespace_uri: UAString::from(child_text(body, "NamespaceUri").unwrap_or("")),
+                    unit_id: parse_text(child_text(body, "UnitId").unwrap_or("0"))?,
+                    display_name: child(body, "DisplayName").map(Self::localized_text_value).unwrap_or_else(LocalizedText::null),
+                    description: child(body, "Description").map(Self::localized_text_value).unwrap_or_else(LocalizedText::null),
+                };
+                ExtensionObject::from_encodable(ObjectId::EUInformation_Encoding_DefaultBinary, &value)
+            }
+            Some(ObjectId::Range_Encoding_DefaultBinary) => {
+                let value = Range {
+                    low: parse_float(child_text(body, "Low").unwrap_or("0"))?,
+                    high: parse_float(child_text(body, "High").unwrap_or("0"))?,
+                };
+                ExtensionObject::from_encodable(ObjectId::Range_Encoding_DefaultBinary, &value)
+            }
+            _ => {
+                debug!("Extension object of type {} in nodeset is preserved as XML", type_id);
+                ExtensionObject {
+                    node_id: type_id,
+                    body: ExtensionObjectEncoding::XmlElement(XmlElement::from(&self.xml[body.range()])),
+                }
+            }
+        };
+        Ok(extension_object)
+    }
+
+    fn argument(&self, body: Node) -> Result<Argument, ()> {
+        let data_type = child(body, "DataType").and_then(|n| child_text(n, "Identifier")).unwrap_or("i=24");
+        let array_dimensions = child(body, "ArrayDimensions")
+            .map(|n| children(n, "UInt32").map(|d| parse_text::<u32>(text(d))).collect::<Result<Vec<u32>, ()>>())
+            .transpose()?
+            .filter(|d| !d.is_empty());
+        let value_rank = parse_text(child_text(body, "ValueRank").unwrap_or("-1"))?;
+        // A one dimensional array with unspecified dimensions has a length of 0
+        let array_dimensions = if array_dimensions.is_none() && value_rank == 1 {
+            Some(vec![0])
+        } else {
+            array_dimensions
+        };
+        Ok(Argument {
+            name: UAString::from(child_text(body, "Name").unwrap_or("")),
+            data_type: self.node_id(data_type)?,
+            value_rank,
+            array_dimensions,
+            description: child(body, "Description").map(Self::localized_text_value).unwrap_or_else(LocalizedText::null),
+        })
+    }
+}
@@ -5,10 +5,13 @@
 use std::collections::HashSet;
 
 use opcua_types::{
+    AttributeId,
     node_id::NodeId,
+    NumericRange,
     QualifiedName,
-    service_types::{RelativePath, RelativePathElement},
+    service_types::{RelativePath, RelativePathElement, TimestampsToReturn},
     status_code::StatusCode,
+    Variant,
 };
 
 use crate::{
@@ -61,59 +64,77 @@ pub(crate) fn find_node_from_browse_path<'a>(address_space: &'a AddressSpace, pa
 
 /// Given a `RelativePath`, find all the nodes that match against it.
 pub(crate) fn find_nodes_relative_path(address_space: &AddressSpace, node_id: &NodeId, relative_path: &RelativePath) -> Result<Vec<NodeId>, StatusCode> {
-    match address_space.find_node(node_id) {
-        None => {
-            trace!("find_nodes_relative_path cannot find node {}", node_id);
-            Err(StatusCode::BadNodeIdUnknown)
-        }
-        Some(_) => {
-            let elements = relative_path.elements.as_ref().unwrap();
-            if elements.is_empty() {
-                warn!("find_nodes_relative_path elements are empty");
-                Err(StatusCode::BadNothingToDo)
-            } else {
-                let mut matching_nodes = vec![node_id.clone()];
-                let mut next_matching_nodes = Vec::with_capacity(100);
+    if !address_space.is_known_node(node_id) {
+        trace!("find_nodes_relative_path cannot find node {}", node_id);
+        Err(StatusCode::BadNodeIdUnknown)
+    } else {
+        let elements = relative_path.elements.as_ref().unwrap();
+        if elements.is_empty() {
+            warn!("find_nodes_relative_path elements are empty");
+            Err(StatusCode::BadNothingToDo)
+        } else {
+            let mut matching_nodes = vec![node_id.clone()];
+            let mut next_matching_nodes = Vec::with_capacity(100);
 
-                // Traverse the relative path elements. Each time around, we will find the matching
-                // elements at that level using the next element
-                for element in elements.iter() {
-                    if element.target_name.is_null() {
-                        warn!("find_nodes_relative_path browse name is invalid (null)");
-                        return Err(StatusCode::BadBrowseNameInvalid);
-                    }
+            // Traverse the relative path elements. Each time around, we will find the matching
+            // elements at that level using the next element
+            for element in elements.iter() {
+                if element.target_name.is_null() {
+                    warn!("find_nodes_relative_path browse name is invalid (null)");
+                    return Err(StatusCode::BadBrowseNameInvalid);
+                }
 
-                    next_matching_nodes.clear();
+                next_matching_nodes.clear();
 
-                    matching_nodes.drain(..).for_each(|node_id| {
-                        trace!("Following relative path on node {}", node_id);
-                        // Iterate current set of nodes and put the results into next
-                        if let Some(mut result) = follow_relative_path(address_space, &node_id, element) {
-                            trace!("  Found matching nodes {:#?}", result);
-                            next_matching_nodes.append(&mut result);
-                        } else {
-                            trace!("  Found no matching nodes");
-                        }
-                    });
-                    if next_matching_nodes.is_empty() {
-                        break;
+                matching_nodes.drain(..).for_each(|node_id| {
+                    trace!("Following relative path on node {}", node_id);
+                    // Iterate current set of nodes and put the results into next
+                    if let Some(mut result) = follow_relative_path(address_space, &node_id, element) {
+                        trace!("  Found matching nodes {:#?}", result);
+                        next_matching_nodes.append(&mut result);
                     } else {
-                        matching_nodes.append(&mut next_matching_nodes);
+                        trace!("  Found no matching nodes");
                     }
-                }
-
-                if matching_nodes.is_empty() {
-                    warn!("find_nodes_relative_path bad no match");
-                    Err(StatusCode::BadNoMatch)
+                });
+                if next_matching_nodes.is_empty() {
+                    break;
                 } else {
-                    Ok(matching_nodes)
+                    matching_nodes.append(&mut next_matching_nodes);
                 }
             }
+
+            if matching_nodes.is_empty() {
+                warn!("find_nodes_relative_path bad no match");
+                Err(StatusCode::BadNoMatch)
+            } else {
+                Ok(matching_nodes)
+            }
+        }
+    }
+}
+
+/// Reads the browse name of a node regardless of it being in the address space or belonging
+/// to a node manager.
+fn browse_name_of(address_space: &AddressSpace, node_id: &NodeId) -> Option<QualifiedName> {
+    if let Some(node_manager) = address_space.node_manager(node_id) {
+        match node_manager.read(node_id, TimestampsToReturn::Neither, AttributeId::BrowseName, NumericRange::None, &QualifiedName::null(), 0f64) {
+            Ok(data_value) => match data_value.value {
+                Some(Variant::QualifiedName(browse_name)) => Some(*browse_name),
+                _ => None
+            },
+            Err(_) => None
         }
+    } else {
+        address_space.find_node(node_id).map(|node| node.as_node().browse_name())
     }
 }
 
 fn follow_relative_path(address_space: &AddressSpace, node_id: &NodeId, relative_path: &RelativePathElement) -> Option<Vec<NodeId>> {
+    // A node manager follows the path from its own nodes
+    if let Some(node_manager) = address_space.node_manager(node_id) {
+        return node_manager.follow_relative_path(node_id, relative_path).ok();
+    }
+
     let reference_filter = {
         if let Ok(reference_type_id) = relative_path.reference_type_id.as_reference_type_id() {
             Some((reference_type_id, relative_path.include_subtypes))
@@ -130,9 +151,8 @@ fn follow_relative_path(address_space: &AddressSpace, node_id: &NodeId, relative
         let compare_target_name = !relative_path.target_name.is_null();
         let mut result = Vec::with_capacity(references.len());
         for reference in &references {
-            if let Some(node) = address_space.find_node(&reference.target_node) {
-                let node = node.as_node();
-                if !compare_target_name || node.browse_name() == relative_path.target_name {
+            if let Some(browse_name) = browse_name_of(address_space, &reference.target_node) {
+                if !compare_target_name || browse_name == relative_path.target_name {
                     result.push(reference.target_node.clone());
                 }
             }
@@ -8,7 +8,7 @@ use opcua_core::config::Config;
 
 use crate::{
     constants,
-    config::{ServerConfig, ServerEndpoint, ServerUserToken, ANONYMOUS_USER_TOKEN_ID},
+    config::{HttpsConfig, ReverseConnectConfig, ServerConfig, ServerEndpoint, ServerUserToken, WssConfig, ANONYMOUS_USER_TOKEN_ID},
     server::Server,
 };
 
@@ -207,6 +207,45 @@ impl ServerBuilder {
         self
     }
 
+    /// Offers every endpoint over the HTTPS transport as well as opc.tcp, listening on the
+    /// hostname and port. TLS uses the application instance certificate unless the configuration
+    /// supplies another.
+    pub fn https<T>(mut self, host: T, port: u16) -> Self where T: Into<String> {
+        self.config.https_config = Some(HttpsConfig {
+            host: host.into(),
+            port,
+            certificate_path: None,
+            private_key_path: None,
+            max_connections: HttpsConfig::DEFAULT_MAX_CONNECTIONS,
+        });
+        self
+    }
+
+    /// Offers every endpoint over the WebSocket secure transport as well as opc.tcp, listening on
+    /// the hostname and port. TLS uses the application instance certificate unless the
+    /// configuration supplies another.
+    pub fn wss<T>(mut self, host: T, port: u16) -> Self where T: Into<String> {
+        self.config.wss_config = Some(WssConfig {
+            host: host.into(),
+            port,
+            certificate_path: None,
+            private_key_path: None,
+        });
+        self
+    }
+
+    /// Connects out to a client at the url, e.g. opc.tcp://client:4841, using Reverse Connect. The
+    /// server retries at the default interval while the client is not connected. Call this once
+    /// for each client.
+    pub fn reverse_connect<T>(mut self, client_url: T) -> Self where T: Into<String> {
+        let reverse_connect_config = self.config.reverse_connect_config.get_or_insert_with(|| ReverseConnectConfig {
+            client_urls: Vec::new(),
+            retry_interval: ReverseConnectConfig::DEFAULT_RETRY_INTERVAL,
+        });
+        reverse_connect_config.client_urls.push(client_url.into());
+        self
+    }
+
     /// Discovery endpoint urls - the urls of this server used by clients to get endpoints.
     /// If the url is relative, e.g. "/" then the code will make a url for you using the port/host
     /// settings as they are at the time this function is executed.
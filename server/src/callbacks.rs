@@ -13,7 +13,7 @@ use opcua_types::{
     status_code::StatusCode,
 };
 
-use crate::session::Session;
+use crate::{address_space::AddressSpace, session::Session};
 
 /// An attribute getter trait is used to obtain the data value associated with the particular attribute id
 /// This allows server implementations to supply a value on demand, usually in response to a polling action
@@ -72,3 +72,12 @@ pub trait Method {
     /// be invoked to handle the call.
     fn call(&mut self, session: &mut Session, request: &CallMethodRequest) -> Result<CallMethodResult, StatusCode>;
 }
+
+/// Called by the Method service when it invokes a method that changes the address space, e.g. one
+/// that adds or removes the nodes of the objects it manages. The address space is locked for
+/// writing while the method runs, so it must be changed through the reference that is passed in.
+pub trait AddressSpaceMethod {
+    /// A method is registered via `AddressSpace::register_address_space_method_handler()` to a
+    /// method id. The object id of the call is in the request.
+    fn call(&mut self, address_space: &mut AddressSpace, session: &mut Session, request: &CallMethodRequest) -> Result<CallMethodResult, StatusCode>;
+}
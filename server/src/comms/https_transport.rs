@@ -0,0 +1,481 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! The HTTPS transport (https-uabinary), OPC UA Part 6 7.4. Each service request arrives as the
+//! binary encoded body of an HTTP POST over TLS and the response is returned in the body of the
+//! HTTP response. There is no secure channel or connection state, so sessions are kept in a table
+//! and requests are matched to them by the authentication token in their request header.
+
+use std::{
+    collections::HashMap,
+    net::{Shutdown, SocketAddr},
+    sync::{
+        Arc, atomic::{AtomicU32, AtomicUsize, Ordering}, Mutex, RwLock,
+    },
+    time::{Duration, Instant},
+};
+
+use chrono::Utc;
+use futures::{
+    Future, future::{self, Loop}, Sink, Stream,
+    stream::{SplitSink, SplitStream},
+    sync::{mpsc::UnboundedReceiver, oneshot},
+};
+use tokio::{self, net::TcpListener};
+use tokio_codec::Framed;
+use tokio_timer::{Interval, Timeout};
+
+use opcua_core::{
+    comms::{
+        https::{self, HttpCodec, HttpMessage, SECURITY_POLICY_HEADER},
+        transport_stream::TransportStream,
+        websocket,
+    },
+    completion_pact,
+    supported_message::SupportedMessage,
+};
+use opcua_crypto::{CertificateStore, SecurityPolicy, TlsAcceptor, TlsStream};
+use opcua_types::{
+    DecodingLimits, node_id::NodeId, request_header::RequestHeader, service_types::ServiceFault, status_code::StatusCode,
+};
+
+use crate::{
+    address_space::types::AddressSpace,
+    services::message_handler::MessageHandler,
+    session::Session,
+    state::ServerState,
+    subscriptions::subscription::TickReason,
+};
+
+/// The largest request body that the server will read
+const MAX_REQUEST_BODY_SIZE: usize = 4 * 1024 * 1024;
+/// Time that an idle keep-alive connection is held open
+const KEEP_ALIVE_TIMEOUT_MS: u64 = 60000;
+/// Time that a publish request can wait for a response, should the subscription not expire it first
+const PUBLISH_WAIT_TIMEOUT_MS: u64 = 120000;
+/// Rate at which an idle connection checks if the server is aborting
+const ABORT_POLL_MS: u64 = 100;
+
+type HttpWriter = SplitSink<Framed<TransportStream, HttpCodec>>;
+type HttpReader = Timeout<SplitStream<Framed<TransportStream, HttpCodec>>>;
+/// A round of the loop that serves a connection, breaking with the writer to close
+type ConnectionLoop = Box<dyn Future<Item=Loop<HttpWriter, (HttpWriter, HttpReader, Instant)>, Error=()> + Send>;
+
+/// A session created over HTTPS and the handler of its requests
+struct HttpsSession {
+    session: Arc<RwLock<Session>>,
+    message_handler: Arc<Mutex<MessageHandler>>,
+}
+
+/// The response to a request. A publish request may have to wait for its response until a
+/// subscription has a notification or a keep-alive for it.
+enum HttpsResponse {
+    Ready(SupportedMessage),
+    Publish {
+        request_id: u32,
+        request_header: RequestHeader,
+        receiver: oneshot::Receiver<SupportedMessage>,
+    },
+}
+
+/// The server side of the HTTPS transport. It listens on its own port and shares the server's
+/// state, address space and tokio runtime with the opc.tcp transport.
+pub struct HttpsTransport {
+    server_state: Arc<RwLock<ServerState>>,
+    certificate_store: Arc<RwLock<CertificateStore>>,
+    address_space: Arc<RwLock<AddressSpace>>,
+    acceptor: Arc<TlsAcceptor>,
+    decoding_limits: DecodingLimits,
+    /// Time that a client has to complete the TLS handshake
+    handshake_timeout: Duration,
+    /// Maximum number of connections open at once, 0 for no limit
+    max_connections: usize,
+    /// Number of connections that are open
+    connection_count: AtomicUsize,
+    /// Sessions by their authentication token
+    sessions: Mutex<HashMap<NodeId, HttpsSession>>,
+    /// Senders of publish responses by the request id of the publish request waiting for them
+    publish_waiters: Mutex<HashMap<u32, oneshot::Sender<SupportedMessage>>>,
+    /// Request ids stand in for those a secure channel would supply
+    next_request_id: AtomicU32,
+}
+
+impl HttpsTransport {
+    pub fn new(server_state: Arc<RwLock<ServerState>>, certificate_store: Arc<RwLock<CertificateStore>>, address_space: Arc<RwLock<AddressSpace>>) -> Result<HttpsTransport, StatusCode> {
+        let (acceptor, decoding_limits, handshake_timeout, max_connections) = {
+            let server_state = trace_read_lock_unwrap!(server_state);
+            let config = trace_read_lock_unwrap!(server_state.config);
+            let https_config = config.https_config.as_ref().ok_or_else(|| {
+                error!("Server has no https configuration");
+                StatusCode::BadConfigurationError
+            })?;
+            let acceptor = server_state.tls_acceptor(&https_config.certificate_path, &https_config.private_key_path)?;
+            (acceptor, config.decoding_limits(), Duration::from_secs(config.tcp_config.hello_timeout as u64), https_config.max_connections as usize)
+        };
+        Ok(HttpsTransport {
+            server_state,
+            certificate_store,
+            address_space,
+            acceptor: Arc::new(acceptor),
+            decoding_limits,
+            handshake_timeout,
+            max_connections,
+            connection_count: AtomicUsize::new(0),
+            sessions: Mutex::new(HashMap::new()),
+            publish_waiters: Mutex::new(HashMap::new()),
+            next_request_id: AtomicU32::new(1),
+        })
+    }
+
+    /// Spawns the listener and the subscription timer onto the server's runtime. The listener
+    /// stops when the rx_abort is signalled and the timer stops when the server aborts.
+    pub fn run(transport: Arc<HttpsTransport>, sock_addr: SocketAddr, looping_interval_ms: f64, rx_abort: UnboundedReceiver<()>) -> Result<(), StatusCode> {
+        let listener = TcpListener::bind(&sock_addr).map_err(|err| {
+            error!("Cannot bind https listener to {}, {}", sock_addr, err);
+            StatusCode::BadCommunicationError
+        })?;
+        info!("Waiting for https connections on {}", sock_addr);
+
+        {
+            let transport_for_abort = transport.clone();
+            let transport_for_tick = transport.clone();
+            let transport = transport.clone();
+            let task = Interval::new(Instant::now(), Duration::from_millis(looping_interval_ms as u64))
+                .take_while(move |_| future::ok(!transport_for_abort.is_server_abort()))
+                .for_each(move |_| {
+                    transport_for_tick.tick_sessions();
+                    Ok(())
+                })
+                .map_err(|err| {
+                    error!("Https subscription timer, error = {:?}", err);
+                });
+            tokio::spawn(task.then(move |_| {
+                transport.close_all_sessions();
+                info!("Https subscription timer is finished");
+                Ok(())
+            }));
+        }
+
+        let task = completion_pact::stream_completion_pact(listener.incoming(), rx_abort)
+            .for_each(move |socket| {
+                if transport.is_server_abort() {
+                    info!("Server is aborting so it will not accept new https connections");
+                    return Ok(());
+                }
+                let connection_count = transport.connection_count.load(Ordering::SeqCst);
+                if transport.max_connections > 0 && connection_count >= transport.max_connections {
+                    warn!("Https connection {:?} is rejected because there are already {} connections", socket, connection_count);
+                    let _ = socket.shutdown(Shutdown::Both);
+                    return Ok(());
+                }
+                info!("Handling new https connection {:?}", socket);
+                transport.connection_count.fetch_add(1, Ordering::SeqCst);
+                // The TLS handshake is held to the hello timeout so that a stalled client cannot
+                // keep the server from stopping
+                let transport_for_connection = transport.clone();
+                let transport_for_count = transport.clone();
+                let task = Timeout::new(websocket::tls_accept(transport.acceptor.clone(), socket), transport.handshake_timeout)
+                    .map_err(|err| {
+                        error!("Https connection failed or timed out in its TLS handshake, {:?}", err);
+                    })
+                    .and_then(move |stream| Self::handle_connection(transport_for_connection, stream))
+                    .then(move |_| {
+                        transport_for_count.connection_count.fetch_sub(1, Ordering::SeqCst);
+                        Ok(())
+                    });
+                tokio::spawn(task);
+                Ok(())
+            })
+            .map(|_| {
+                info!("Https listener has completed");
+            })
+            .map_err(|err| {
+                error!("Https listener, incoming error = {:?}", err);
+            });
+        tokio::spawn(task);
+        Ok(())
+    }
+
+    fn is_server_abort(&self) -> bool {
+        let server_state = trace_read_lock_unwrap!(self.server_state);
+        server_state.is_abort()
+    }
+
+    /// Reads requests from the connection and writes their responses until the client closes it,
+    /// the connection is idle for the keep-alive timeout or the server aborts
+    fn handle_connection(transport: Arc<HttpsTransport>, stream: TlsStream<tokio::net::TcpStream>) -> impl Future<Item=(), Error=()> {
+        let (writer, reader) = Framed::new(TransportStream::Tls(stream), HttpCodec::new(MAX_REQUEST_BODY_SIZE)).split();
+        // The reader times out regularly so that an idle connection notices the server aborting
+        let reader = Timeout::new(reader, Duration::from_millis(ABORT_POLL_MS));
+        future::loop_fn((writer, reader, Instant::now()), move |(writer, reader, idle_since)| {
+            let transport = transport.clone();
+            reader.into_future().then(move |result| -> ConnectionLoop {
+                match result {
+                    Ok((Some(Ok(request)), reader)) => {
+                        let keep_alive = request.keep_alive();
+                        let task = Self::handle_http_request(&transport, &request)
+                            .and_then(move |mut response| {
+                                if !keep_alive {
+                                    response.set_header("Connection", "close");
+                                }
+                                writer.send(response).map_err(|err| {
+                                    error!("Cannot write https response, {}", err);
+                                })
+                            })
+                            .map(move |writer| {
+                                if keep_alive && !transport.is_server_abort() {
+                                    Loop::Continue((writer, reader, Instant::now()))
+                                } else {
+                                    Loop::Break(writer)
+                                }
+                            });
+                        Box::new(task)
+                    }
+                    Ok((Some(Err(StatusCode::BadRequestTooLarge)), _)) => {
+                        let task = writer.send(HttpMessage::response(413, "Payload Too Large", Vec::new()))
+                            .map(Loop::Break)
+                            .map_err(|err| {
+                                error!("Cannot write https response, {}", err);
+                            });
+                        Box::new(task)
+                    }
+                    Ok((Some(Err(_)), _)) => {
+                        debug!("Https connection sent an unreadable request");
+                        Box::new(future::ok(Loop::Break(writer)))
+                    }
+                    Ok((None, _)) => Box::new(future::ok(Loop::Break(writer))),
+                    Err((err, reader)) => {
+                        if !err.is_elapsed() {
+                            debug!("Https connection is closed, {:?}", err);
+                            Box::new(future::ok(Loop::Break(writer)))
+                        } else if transport.is_server_abort() || idle_since.elapsed() >= Duration::from_millis(KEEP_ALIVE_TIMEOUT_MS) {
+                            Box::new(future::ok(Loop::Break(writer)))
+                        } else {
+                            Box::new(future::ok(Loop::Continue((writer, reader, idle_since))))
+                        }
+                    }
+                }
+            })
+        }).and_then(|mut writer| {
+            // Closing the writer shuts down the TLS session and the socket
+            future::poll_fn(move || writer.close()).map_err(|_| ())
+        })
+    }
+
+    /// Handles an HTTP request and returns a future of its response
+    fn handle_http_request(transport: &Arc<HttpsTransport>, request: &HttpMessage) -> Box<dyn Future<Item=HttpMessage, Error=()> + Send> {
+        if request.method() != Some("POST") {
+            let mut response = HttpMessage::response(405, "Method Not Allowed", Vec::new());
+            response.set_header("Allow", "POST");
+            return Box::new(future::ok(response));
+        }
+        let message = match https::decode_message(&request.body, &transport.decoding_limits) {
+            Ok(message) => message,
+            Err(status_code) => {
+                error!("Cannot decode https request, {}", status_code);
+                return Box::new(future::ok(HttpMessage::response(400, "Bad Request", Vec::new())));
+            }
+        };
+        // The client names the security policy of the session in a header, OPC UA Part 6 7.4.2
+        let security_policy = match request.header(SECURITY_POLICY_HEADER) {
+            Some(security_policy_uri) => SecurityPolicy::from_uri(security_policy_uri),
+            None => SecurityPolicy::None
+        };
+        match transport.handle_message(message, security_policy) {
+            HttpsResponse::Ready(response) => {
+                Box::new(future::ok(HttpMessage::response(200, "OK", https::encode_message(&response))))
+            }
+            HttpsResponse::Publish { request_id, request_header, receiver } => {
+                let transport = transport.clone();
+                let task = Timeout::new(receiver, Duration::from_millis(PUBLISH_WAIT_TIMEOUT_MS))
+                    .or_else(move |_| {
+                        let mut publish_waiters = trace_lock_unwrap!(transport.publish_waiters);
+                        publish_waiters.remove(&request_id);
+                        Ok(ServiceFault::new(&request_header, StatusCode::BadTimeout).into())
+                    })
+                    .map(|response: SupportedMessage| HttpMessage::response(200, "OK", https::encode_message(&response)));
+                Box::new(task)
+            }
+        }
+    }
+
+    /// Handles a request and returns its response
+    fn handle_message(&self, request: SupportedMessage, security_policy: SecurityPolicy) -> HttpsResponse {
+        let request_id = self.next_request_id.fetch_add(1, Ordering::Relaxed);
+        match request {
+            SupportedMessage::GetEndpointsRequest(_) | SupportedMessage::FindServersRequest(_) |
+            SupportedMessage::RegisterServerRequest(_) | SupportedMessage::RegisterServer2Request(_) => {
+                // Discovery services do not need a session so a throwaway one will do
+                let session = Arc::new(RwLock::new(Session::new_with_state(self.server_state.clone(), self.certificate_store.clone())));
+                let mut message_handler = self.new_message_handler(session);
+                HttpsResponse::Ready(Self::unwrap_response(&request, message_handler.handle_message(request_id, request.clone())))
+            }
+            SupportedMessage::CreateSessionRequest(ref create_session_request) => {
+                let security_mode = {
+                    let server_state = trace_read_lock_unwrap!(self.server_state);
+                    let config = trace_read_lock_unwrap!(server_state.config);
+                    config.endpoints.values()
+                        .find(|e| e.security_policy() == security_policy && config.endpoint_matches_url(e, create_session_request.endpoint_url.as_ref()))
+                        .map(|e| e.message_security_mode())
+                };
+                let security_mode = if let Some(security_mode) = security_mode {
+                    security_mode
+                } else {
+                    error!("No endpoint at {} has security policy {}", create_session_request.endpoint_url, security_policy);
+                    return HttpsResponse::Ready(ServiceFault::new(&create_session_request.request_header, StatusCode::BadSecurityPolicyRejected).into());
+                };
+                let session = Session::new_with_state(self.server_state.clone(), self.certificate_store.clone());
+                {
+                    let secure_channel = session.secure_channel();
+                    let mut secure_channel = trace_write_lock_unwrap!(secure_channel);
+                    secure_channel.set_security_policy(security_policy);
+                    secure_channel.set_security_mode(security_mode);
+                }
+                let session = Arc::new(RwLock::new(session));
+                let mut message_handler = self.new_message_handler(session.clone());
+                let response = Self::unwrap_response(&request, message_handler.handle_message(request_id, request.clone()));
+                if let SupportedMessage::CreateSessionResponse(_) = response {
+                    let authentication_token = {
+                        let session = trace_read_lock_unwrap!(session);
+                        session.authentication_token().clone()
+                    };
+                    let mut sessions = trace_lock_unwrap!(self.sessions);
+                    sessions.insert(authentication_token, HttpsSession {
+                        session,
+                        message_handler: Arc::new(Mutex::new(message_handler)),
+                    });
+                }
+                HttpsResponse::Ready(response)
+            }
+            request => {
+                let authentication_token = request.request_header().authentication_token.clone();
+                let (session, message_handler) = {
+                    let sessions = trace_lock_unwrap!(self.sessions);
+                    if let Some(https_session) = sessions.get(&authentication_token) {
+                        (https_session.session.clone(), https_session.message_handler.clone())
+                    } else {
+                        error!("Https request {:?} has an unknown authentication token", request.node_id());
+                        return HttpsResponse::Ready(ServiceFault::new(request.request_header(), StatusCode::BadSessionIdInvalid).into());
+                    }
+                };
+                let response = if let SupportedMessage::PublishRequest(_) = request {
+                    // Register to receive the response before the request is queued so it cannot be missed
+                    let (tx, rx) = oneshot::channel();
+                    {
+                        let mut publish_waiters = trace_lock_unwrap!(self.publish_waiters);
+                        publish_waiters.insert(request_id, tx);
+                    }
+                    let response = {
+                        let mut message_handler = trace_lock_unwrap!(message_handler);
+                        message_handler.handle_message(request_id, request.clone())
+                    };
+                    match response {
+                        Ok(Some(response)) => {
+                            let mut publish_waiters = trace_lock_unwrap!(self.publish_waiters);
+                            publish_waiters.remove(&request_id);
+                            HttpsResponse::Ready(response)
+                        }
+                        Ok(None) => HttpsResponse::Publish {
+                            request_id,
+                            request_header: request.request_header().clone(),
+                            receiver: rx,
+                        },
+                        Err(status_code) => {
+                            let mut publish_waiters = trace_lock_unwrap!(self.publish_waiters);
+                            publish_waiters.remove(&request_id);
+                            HttpsResponse::Ready(ServiceFault::new(request.request_header(), status_code).into())
+                        }
+                    }
+                } else {
+                    let mut message_handler = trace_lock_unwrap!(message_handler);
+                    HttpsResponse::Ready(Self::unwrap_response(&request, message_handler.handle_message(request_id, request.clone())))
+                };
+                let close_session = if let SupportedMessage::CloseSessionRequest(_) = request {
+                    true
+                } else {
+                    let session = trace_read_lock_unwrap!(session);
+                    session.is_session_terminated()
+                };
+                if close_session {
+                    self.remove_session(&authentication_token);
+                }
+                response
+            }
+        }
+    }
+
+    fn new_message_handler(&self, session: Arc<RwLock<Session>>) -> MessageHandler {
+        MessageHandler::new(self.certificate_store.clone(), self.server_state.clone(), session, self.address_space.clone())
+    }
+
+    /// Turns the result of the message handler into a response for requests that always have one
+    fn unwrap_response(request: &SupportedMessage, response: Result<Option<SupportedMessage>, StatusCode>) -> SupportedMessage {
+        match response {
+            Ok(Some(response)) => response,
+            Ok(None) => ServiceFault::new(request.request_header(), StatusCode::BadInternalError).into(),
+            Err(status_code) => ServiceFault::new(request.request_header(), status_code).into()
+        }
+    }
+
+    fn remove_session(&self, authentication_token: &NodeId) {
+        let https_session = {
+            let mut sessions = trace_lock_unwrap!(self.sessions);
+            sessions.remove(authentication_token)
+        };
+        if let Some(https_session) = https_session {
+            let mut session = trace_write_lock_unwrap!(https_session.session);
+            session.set_terminated();
+            session.deregister_session(self.address_space.clone());
+        }
+    }
+
+    fn close_all_sessions(&self) {
+        let authentication_tokens: Vec<NodeId> = {
+            let sessions = trace_lock_unwrap!(self.sessions);
+            sessions.keys().cloned().collect()
+        };
+        authentication_tokens.iter().for_each(|authentication_token| self.remove_session(authentication_token));
+    }
+
+    /// Ticks the subscriptions of every session, passes publish responses to the requests waiting
+    /// for them and removes sessions that have timed out.
+    fn tick_sessions(&self) {
+        let now = Utc::now();
+        let sessions: Vec<(NodeId, Arc<RwLock<Session>>)> = {
+            let sessions = trace_lock_unwrap!(self.sessions);
+            sessions.iter().map(|(k, v)| (k.clone(), v.session.clone())).collect()
+        };
+        for (authentication_token, session) in sessions {
+            let (publish_responses, timed_out) = {
+                let mut session = trace_write_lock_unwrap!(session);
+                // Request queue might contain stale publish requests
+                session.expire_stale_publish_requests(&now);
+                {
+                    let address_space = trace_read_lock_unwrap!(self.address_space);
+                    let _ = session.tick_subscriptions(&now, &address_space, TickReason::TickTimerFired);
+                }
+                // Without a connection, a session times out when no request has arrived for the
+                // duration of its timeout
+                let elapsed = now.signed_duration_since(session.last_service_request_timestamp());
+                let timed_out = session.session_timeout() > 0f64 &&
+                    elapsed.num_milliseconds() as f64 > session.session_timeout();
+                (session.subscriptions_mut().take_publish_responses(), timed_out)
+            };
+            if let Some(publish_responses) = publish_responses {
+                let mut publish_waiters = trace_lock_unwrap!(self.publish_waiters);
+                publish_responses.into_iter().for_each(|publish_response| {
+                    if let Some(tx) = publish_waiters.remove(&publish_response.request_id) {
+                        let _ = tx.send(publish_response.response);
+                    } else {
+                        debug!("Publish response for request {} has nobody waiting for it", publish_response.request_id);
+                    }
+                });
+            }
+            if timed_out {
+                info!("Https session has timed out and will be removed");
+                self.remove_session(&authentication_token);
+            }
+        }
+    }
+}
@@ -9,3 +9,4 @@ mod secure_channel_service;
 
 pub mod transport;
 pub mod tcp_transport;
+pub mod https_transport;
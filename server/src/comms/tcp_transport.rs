@@ -22,7 +22,7 @@ use futures::{
     Stream,
     sync::mpsc::{self, unbounded, UnboundedReceiver, UnboundedSender},
 };
-use tokio::{self, net::TcpStream};
+use tokio;
 use tokio_codec::FramedRead;
 use tokio_io::{AsyncRead, AsyncWrite, io::{self, ReadHalf, WriteHalf}};
 use tokio_timer::Interval;
@@ -33,7 +33,7 @@ use opcua_core::{
         secure_channel::SecureChannel,
         tcp_codec::{self, TcpCodec},
         tcp_types::*,
-        wrapped_tcp_stream::WrappedTcpStream,
+        transport_stream::TransportStream,
     },
     prelude::*,
     RUNTIME,
@@ -53,7 +53,7 @@ use crate::{
 };
 
 // TODO these need to go, and use session settings
-const RECEIVE_BUFFER_SIZE: usize = std::u16::MAX as usize;
+pub(crate) const RECEIVE_BUFFER_SIZE: usize = u16::MAX as usize;
 const SEND_BUFFER_SIZE: usize = std::u16::MAX as usize;
 const MAX_MESSAGE_SIZE: usize = std::u16::MAX as usize;
 
@@ -96,7 +96,7 @@ struct WriteState {
     /// Secure channel state
     pub secure_channel: Arc<RwLock<SecureChannel>>,
     /// Writing portion of socket
-    pub writer: Option<WriteHalf<WrappedTcpStream>>,
+    pub writer: Option<WriteHalf<TransportStream>>,
     /// Write buffer (protected since it might be accessed by publish response / event activity)
     pub send_buffer: Arc<Mutex<MessageWriter>>,
 }
@@ -190,7 +190,10 @@ impl TcpTransport {
 
     /// This is the entry point for the session. This function is asynchronous - it spawns tokio
     /// tasks to handle the session execution loop so this function will returns immediately.
-    pub fn run(connection: Arc<RwLock<TcpTransport>>, socket: TcpStream, looping_interval_ms: f64) {
+    ///
+    /// The stream is the socket for opc.tcp, or a WebSocket for opc.wss.
+    pub fn run(connection: Arc<RwLock<TcpTransport>>, stream: TransportStream, looping_interval_ms: f64) {
+        let socket = stream.socket();
         info!("Socket info:\n  Linger - {}\n  Keepalive - {},\n  TTL - {}",
               if let Ok(v) = socket.linger() {
                   match v {
@@ -218,11 +221,11 @@ impl TcpTransport {
         // Store the address of the client
         {
             let mut connection = trace_write_lock_unwrap!(connection);
-            connection.client_address = Some(socket.peer_addr().unwrap());
+            connection.client_address = Some(stream.peer_addr().unwrap());
             connection.transport_state = TransportState::WaitingHello;
         }
         // Spawn the tasks we need to run
-        Self::spawn_looping_task(connection, socket, looping_interval_ms);
+        Self::spawn_looping_task(connection, stream, looping_interval_ms);
     }
 
     fn write_bytes_task(connection: Arc<Mutex<WriteState>>) -> impl Future<Item=Arc<Mutex<WriteState>>, Error=Arc<Mutex<WriteState>>> {
@@ -238,12 +241,13 @@ impl TcpTransport {
         };
         let connection_for_err = connection.clone();
         io::write_all(writer.unwrap(), bytes_to_write)
+            .and_then(|(writer, _)| io::flush(writer))
             .map_err(move |err| {
                 error!("Write IO error {:?}", err);
                 let mut transport = trace_write_lock_unwrap!(transport);
                 transport.finish(StatusCode::BadCommunicationError);
             })
-            .map(move |(writer, _)| {
+            .map(move |writer| {
                 // Build a new connection state
                 {
                     let mut connection = trace_lock_unwrap!(connection);
@@ -255,7 +259,7 @@ impl TcpTransport {
         })
     }
 
-    fn spawn_looping_task(transport: Arc<RwLock<TcpTransport>>, socket: TcpStream, looping_interval_ms: f64) {
+    fn spawn_looping_task(transport: Arc<RwLock<TcpTransport>>, stream: TransportStream, looping_interval_ms: f64) {
         let session_start_time = Utc::now();
         info!("Session started {}", session_start_time);
 
@@ -266,7 +270,7 @@ impl TcpTransport {
         let (tx, rx) = unbounded::<Message>();
         let send_buffer = Arc::new(Mutex::new(MessageWriter::new(send_buffer_size)));
 
-        let (reader, writer) = WrappedTcpStream(socket).split();
+        let (reader, writer) = stream.split();
         let secure_channel = {
             let transport = trace_read_lock_unwrap!(transport);
             transport.secure_channel.clone()
@@ -327,7 +331,7 @@ impl TcpTransport {
 
     /// Spawns the writing loop task. The writing loop takes messages to send off of a queue
     /// and sends them to the stream.
-    fn spawn_writing_loop_task(writer: WriteHalf<WrappedTcpStream>, receiver: UnboundedReceiver<Message>, secure_channel: Arc<RwLock<SecureChannel>>, transport: Arc<RwLock<TcpTransport>>, send_buffer: Arc<Mutex<MessageWriter>>) {
+    fn spawn_writing_loop_task(writer: WriteHalf<TransportStream>, receiver: UnboundedReceiver<Message>, secure_channel: Arc<RwLock<SecureChannel>>, transport: Arc<RwLock<TcpTransport>>, send_buffer: Arc<Mutex<MessageWriter>>) {
         let id = Self::make_session_id("server_writing_loop_task", transport.clone());
         let id_for_map = id.clone();
         let id_for_map_err = id.clone();
@@ -438,7 +442,7 @@ impl TcpTransport {
 
     /// Creates the framed read task / future. This will read chunks from the
     /// reader and process them.
-    fn framed_read_task(reader: ReadHalf<WrappedTcpStream>, finished_flag: Arc<RwLock<bool>>, connection: Arc<RwLock<ReadState>>) -> impl Future<Item=(), Error=()>
+    fn framed_read_task(reader: ReadHalf<TransportStream>, finished_flag: Arc<RwLock<bool>>, connection: Arc<RwLock<ReadState>>) -> impl Future<Item=(), Error=()>
     {
         let (transport, mut sender) = {
             let connection = trace_read_lock_unwrap!(connection);
@@ -528,7 +532,7 @@ impl TcpTransport {
 
     /// Spawns the reading loop where a reader task continuously reads messages, chunks from the
     /// input and process them. The reading task will terminate upon error.
-    fn spawn_reading_loop_task(reader: ReadHalf<WrappedTcpStream>, finished_flag: Arc<RwLock<bool>>, transport: Arc<RwLock<TcpTransport>>, sender: UnboundedSender<Message>, receive_buffer_size: usize) {
+    fn spawn_reading_loop_task(reader: ReadHalf<TransportStream>, finished_flag: Arc<RwLock<bool>>, transport: Arc<RwLock<TcpTransport>>, sender: UnboundedSender<Message>, receive_buffer_size: usize) {
         // Connection state is maintained for looping through each task
         let connection = Arc::new(RwLock::new(ReadState {
             transport: transport.clone(),
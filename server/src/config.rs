@@ -8,7 +8,7 @@ use std::path::PathBuf;
 use std::str::FromStr;
 
 use opcua_core::{
-    comms::url::url_matches_except_host,
+    comms::url::{is_opc_ua_binary_url, url_matches_except_host},
     config::Config,
 };
 use opcua_crypto::{CertificateStore, SecurityPolicy, Thumbprint};
@@ -32,6 +32,61 @@ pub struct TcpConfig {
     pub port: u16,
 }
 
+/// Configuration of the HTTPS transport (https-uabinary). When it is present, every endpoint is
+/// also offered at the same path over HTTPS.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct HttpsConfig {
+    /// The hostname to supply in the endpoints
+    pub host: String,
+    /// The port number of the service, typically 443
+    pub port: u16,
+    /// Path to the certificate presented in the TLS handshake. The application instance
+    /// certificate is used if this is not set.
+    pub certificate_path: Option<PathBuf>,
+    /// Path to the private key of the TLS certificate
+    pub private_key_path: Option<PathBuf>,
+    /// Maximum number of connections open at once, 0 for no limit. Connections beyond the limit
+    /// are closed as soon as they are accepted.
+    pub max_connections: u32,
+}
+
+impl HttpsConfig {
+    /// Default maximum number of connections open at once
+    pub const DEFAULT_MAX_CONNECTIONS: u32 = 100;
+}
+
+/// Configuration of the WebSocket secure transport (wss-uasc-uabinary). When it is present, every
+/// endpoint is also offered at the same path over WebSockets, with the secure channel running
+/// inside the TLS connection.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct WssConfig {
+    /// The hostname to supply in the endpoints
+    pub host: String,
+    /// The port number of the service, typically 443
+    pub port: u16,
+    /// Path to the certificate presented in the TLS handshake. The application instance
+    /// certificate is used if this is not set.
+    pub certificate_path: Option<PathBuf>,
+    /// Path to the private key of the TLS certificate
+    pub private_key_path: Option<PathBuf>,
+}
+
+/// Configuration of Reverse Connect, OPC UA Part 6 7.1.3. The server opens a connection to each
+/// client url and announces itself with a ReverseHello, so clients can reach a server that cannot
+/// accept incoming connections, e.g. one behind a firewall or NAT.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct ReverseConnectConfig {
+    /// Urls of the clients to connect to, e.g. opc.tcp://client:4841
+    pub client_urls: Vec<String>,
+    /// Time in milliseconds between attempts to connect to a client that is not connected
+    pub retry_interval: u32,
+}
+
+impl ReverseConnectConfig {
+    /// Default time in milliseconds between attempts to connect to a client
+    pub const DEFAULT_RETRY_INTERVAL: u32 = 5000;
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
 pub struct ServerUserToken {
     /// User name
@@ -389,6 +444,12 @@ pub struct ServerConfig {
     pub discovery_server_url: Option<String>,
     /// tcp configuration information
     pub tcp_config: TcpConfig,
+    /// https configuration information, if the server offers the HTTPS transport
+    pub https_config: Option<HttpsConfig>,
+    /// wss configuration information, if the server offers the WebSocket secure transport
+    pub wss_config: Option<WssConfig>,
+    /// reverse connect configuration, if the server connects out to clients
+    pub reverse_connect_config: Option<ReverseConnectConfig>,
     /// Server limits
     pub limits: ServerLimits,
     /// Supported locale ids
@@ -434,6 +495,30 @@ impl Config for ServerConfig {
                 valid = false;
             }
         }
+        if let Some(ref https_config) = self.https_config {
+            if https_config.certificate_path.is_some() != https_config.private_key_path.is_some() {
+                error!("Server configuration is invalid. The HTTPS certificate and private key paths must both be set or neither");
+                valid = false;
+            }
+        }
+        if let Some(ref wss_config) = self.wss_config {
+            if wss_config.certificate_path.is_some() != wss_config.private_key_path.is_some() {
+                error!("Server configuration is invalid. The WSS certificate and private key paths must both be set or neither");
+                valid = false;
+            }
+        }
+        if let Some(ref reverse_connect_config) = self.reverse_connect_config {
+            for client_url in &reverse_connect_config.client_urls {
+                if !is_opc_ua_binary_url(client_url) {
+                    error!("Server configuration is invalid. Reverse connect client url {} is not an opc.tcp url", client_url);
+                    valid = false;
+                }
+            }
+            if reverse_connect_config.retry_interval == 0 {
+                error!("Server configuration is invalid. Reverse connect retry interval must be greater than zero");
+                valid = false;
+            }
+        }
         if self.limits.max_array_length == 0 {
             error!("Server configuration is invalid. Max array length is invalid");
             valid = false;
@@ -487,6 +572,9 @@ impl Default for ServerConfig {
                 port: constants::DEFAULT_RUST_OPC_UA_SERVER_PORT,
                 hello_timeout: constants::DEFAULT_HELLO_TIMEOUT_SECONDS,
             },
+            https_config: None,
+            wss_config: None,
+            reverse_connect_config: None,
             limits: ServerLimits::default(),
             user_tokens: BTreeMap::new(),
             locale_ids: vec!["en".to_string()],
@@ -530,6 +618,9 @@ impl ServerConfig {
                 port,
                 hello_timeout: constants::DEFAULT_HELLO_TIMEOUT_SECONDS,
             },
+            https_config: None,
+            wss_config: None,
+            reverse_connect_config: None,
             limits: ServerLimits::default(),
             locale_ids,
             user_tokens,
@@ -561,6 +652,32 @@ impl ServerConfig {
         format!("opc.tcp://{}:{}", self.tcp_config.host, self.tcp_config.port)
     }
 
+    /// Returns a https://server:port url that paths can be appended onto, if the server offers
+    /// the HTTPS transport
+    pub fn base_https_endpoint_url(&self) -> Option<String> {
+        self.https_config.as_ref().map(|https_config| format!("https://{}:{}", https_config.host, https_config.port))
+    }
+
+    /// Returns a opc.wss://server:port url that paths can be appended onto, if the server offers
+    /// the WebSocket secure transport
+    pub fn base_wss_endpoint_url(&self) -> Option<String> {
+        self.wss_config.as_ref().map(|wss_config| format!("opc.wss://{}:{}", wss_config.host, wss_config.port))
+    }
+
+    /// Returns the base url of each transport the server offers
+    pub fn base_endpoint_urls(&self) -> Vec<String> {
+        let mut base_endpoint_urls = vec![self.base_endpoint_url()];
+        base_endpoint_urls.extend(self.base_https_endpoint_url());
+        base_endpoint_urls.extend(self.base_wss_endpoint_url());
+        base_endpoint_urls
+    }
+
+    /// Tests if the endpoint is offered at the url over any of the transports
+    pub fn endpoint_matches_url(&self, endpoint: &ServerEndpoint, endpoint_url: &str) -> bool {
+        self.base_endpoint_urls().iter()
+            .any(|base_endpoint_url| url_matches_except_host(&endpoint.endpoint_url(base_endpoint_url), endpoint_url))
+    }
+
     /// Find the default endpoint
     pub fn default_endpoint(&self) -> Option<&ServerEndpoint> {
         if let Some(ref default_endpoint) = self.default_endpoint {
@@ -573,10 +690,9 @@ impl ServerConfig {
     /// Find the first endpoint that matches the specified url, security policy and message
     /// security mode.
     pub fn find_endpoint(&self, endpoint_url: &str, security_policy: SecurityPolicy, security_mode: MessageSecurityMode) -> Option<&ServerEndpoint> {
-        let base_endpoint_url = self.base_endpoint_url();
         let endpoint = self.endpoints.iter().find(|&(_, e)| {
             // Test end point's security_policy_uri and matching url
-            if url_matches_except_host(&e.endpoint_url(&base_endpoint_url), endpoint_url) {
+            if self.endpoint_matches_url(e, endpoint_url) {
                 if e.security_policy() == security_policy && e.message_security_mode() == security_mode {
                     trace!("Found matching endpoint for url {} - {:?}", endpoint_url, e);
                     true
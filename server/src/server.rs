@@ -11,11 +11,12 @@ use std::{
     time::{Duration, Instant},
 };
 
-use futures::{Future, future, Stream, sync::mpsc::{unbounded, UnboundedSender}};
+use futures::{Future, future, Stream, sync::mpsc::{unbounded, UnboundedReceiver, UnboundedSender}};
 use tokio::{self, net::{TcpListener, TcpStream}};
-use tokio_timer::Interval;
+use tokio_timer::{Interval, Timeout};
 
 use opcua_core::{
+    comms::{url::hostname_port_from_url, websocket},
     completion_pact,
     config::Config,
     prelude::*,
@@ -25,6 +26,7 @@ use opcua_types::service_types::ServerState as ServerStateType;
 
 use crate::{
     address_space::types::AddressSpace,
+    comms::https_transport::HttpsTransport,
     comms::tcp_transport::*,
     comms::transport::Transport,
     config::ServerConfig,
@@ -148,7 +150,7 @@ impl Server {
             },
             servers,
             base_endpoint,
-            state: ServerStateType::Shutdown,
+            state: Arc::new(RwLock::new(ServerStateType::Shutdown)),
             start_time,
             config,
             server_certificate,
@@ -250,6 +252,8 @@ impl Server {
             let server_for_listener = server.clone();
 
             let (tx_abort, rx_abort) = unbounded::<()>();
+            let (tx_wss_abort, rx_wss_abort) = unbounded::<()>();
+            let (tx_https_abort, rx_https_abort) = unbounded::<()>();
 
             // Put the server into a running state
             future::lazy(move || {
@@ -271,10 +275,19 @@ impl Server {
 
                     // Start any pending polling action timers
                     server.start_pending_polling_actions();
+
+                    // Start the https listener if the server offers that transport
+                    server.start_https_transport(rx_https_abort);
                 }
 
+                // Start the wss listener if the server offers that transport
+                Self::start_wss_transport(server.clone(), rx_wss_abort);
+
+                // Start connecting out to clients if the server is configured for reverse connect
+                Self::start_reverse_connect(server.clone());
+
                 // Start a server abort task loop
-                Self::start_abort_poll(server, tx_abort);
+                Self::start_abort_poll(server, vec![tx_abort, tx_wss_abort, tx_https_abort]);
 
                 future::ok(())
             }).and_then(move |_| {
@@ -292,7 +305,7 @@ impl Server {
                         } {
                             info!("Server is aborting so it will not accept new connections");
                         } else {
-                            server.handle_connection(socket);
+                            server.handle_connection(TransportStream::from(socket));
                         }
                         Ok(())
                     })
@@ -401,10 +414,242 @@ impl Server {
         }
     }
 
+    /// Returns the https socket address, if the server offers the HTTPS transport.
+    fn get_https_socket_address(&self) -> Option<SocketAddr> {
+        use std::net::ToSocketAddrs;
+        let server_state = trace_read_lock_unwrap!(self.server_state);
+        let config = trace_read_lock_unwrap!(server_state.config);
+        let https_config = config.https_config.as_ref()?;
+        let address = format!("{}:{}", https_config.host, https_config.port);
+        if let Ok(mut addrs_iter) = address.to_socket_addrs() {
+            addrs_iter.next()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the wss socket address, if the server offers the WebSocket secure transport.
+    fn get_wss_socket_address(&self) -> Option<SocketAddr> {
+        use std::net::ToSocketAddrs;
+        let server_state = trace_read_lock_unwrap!(self.server_state);
+        let config = trace_read_lock_unwrap!(server_state.config);
+        let wss_config = config.wss_config.as_ref()?;
+        let address = format!("{}:{}", wss_config.host, wss_config.port);
+        if let Ok(mut addrs_iter) = address.to_socket_addrs() {
+            addrs_iter.next()
+        } else {
+            None
+        }
+    }
+
+    /// Starts the HTTPS transport if it is configured. The listener stops when the rx_abort is
+    /// signalled.
+    fn start_https_transport(&self, rx_abort: UnboundedReceiver<()>) {
+        let has_https_config = {
+            let server_state = trace_read_lock_unwrap!(self.server_state);
+            let config = trace_read_lock_unwrap!(server_state.config);
+            config.https_config.is_some()
+        };
+        if !has_https_config {
+            return;
+        }
+        let sock_addr = if let Some(sock_addr) = self.get_https_socket_address() {
+            sock_addr
+        } else {
+            error!("Cannot resolve https server address, check configuration of server");
+            return;
+        };
+        let looping_interval_ms = {
+            let server_state = trace_read_lock_unwrap!(self.server_state);
+            f64::min(server_state.min_publishing_interval_ms, server_state.min_sampling_interval_ms)
+        };
+        match HttpsTransport::new(self.server_state.clone(), self.certificate_store.clone(), self.address_space.clone()) {
+            Ok(transport) => {
+                if HttpsTransport::run(Arc::new(transport), sock_addr, looping_interval_ms, rx_abort).is_err() {
+                    error!("Cannot start the https transport");
+                }
+            }
+            Err(_) => {
+                error!("Cannot create the https transport, check the certificate and key");
+            }
+        }
+    }
+
+    /// Starts listening for WebSocket secure connections if the transport is configured. The
+    /// listener stops when the rx_abort is signalled.
+    fn start_wss_transport(server: Arc<RwLock<Server>>, rx_abort: UnboundedReceiver<()>) {
+        let (sock_addr, acceptor, hello_timeout) = {
+            let server = trace_read_lock_unwrap!(server);
+            let server_state = trace_read_lock_unwrap!(server.server_state);
+            let config = trace_read_lock_unwrap!(server_state.config);
+            let wss_config = if let Some(ref wss_config) = config.wss_config {
+                wss_config
+            } else {
+                return;
+            };
+            let sock_addr = if let Some(sock_addr) = server.get_wss_socket_address() {
+                sock_addr
+            } else {
+                error!("Cannot resolve wss server address, check configuration of server");
+                return;
+            };
+            let acceptor = if let Ok(acceptor) = server_state.tls_acceptor(&wss_config.certificate_path, &wss_config.private_key_path) {
+                Arc::new(acceptor)
+            } else {
+                error!("Cannot create the wss transport, check the certificate and key");
+                return;
+            };
+            (sock_addr, acceptor, Duration::from_secs(config.tcp_config.hello_timeout as u64))
+        };
+        let listener = match TcpListener::bind(&sock_addr) {
+            Ok(listener) => listener,
+            Err(err) => {
+                error!("Cannot bind wss listener to {}, {}", sock_addr, err);
+                return;
+            }
+        };
+        info!("Waiting for wss connections on {}", sock_addr);
+        let task = completion_pact::stream_completion_pact(listener.incoming(), rx_abort)
+            .for_each(move |socket| {
+                info!("Handling new wss connection {:?}", socket);
+                let is_abort = {
+                    let server = trace_read_lock_unwrap!(server);
+                    let server_state = trace_read_lock_unwrap!(server.server_state);
+                    server_state.is_abort()
+                };
+                if is_abort {
+                    info!("Server is aborting so it will not accept new connections");
+                } else {
+                    // The TLS and WebSocket handshakes must complete before the transport runs
+                    // over the WebSocket. They are held to the hello timeout so that a stalled
+                    // client cannot keep the server from stopping.
+                    let server = server.clone();
+                    let handshake = websocket::tls_accept(acceptor.clone(), socket)
+                        .and_then(|stream| websocket::accept(stream, RECEIVE_BUFFER_SIZE));
+                    let task = Timeout::new(handshake, hello_timeout)
+                        .map(move |stream| {
+                            let mut server = trace_write_lock_unwrap!(server);
+                            server.handle_connection(TransportStream::WebSocket(stream));
+                        })
+                        .map_err(|err| {
+                            error!("WebSocket connection failed or timed out in its handshake, {:?}", err);
+                        });
+                    tokio::spawn(task);
+                }
+                Ok(())
+            })
+            .map(|_| {
+                info!("Wss listener has completed");
+            })
+            .map_err(|err| {
+                error!("Wss listener, incoming error = {:?}", err);
+            });
+        tokio::spawn(task);
+    }
+
+    /// Starts a timer that keeps a connection open to each client configured for reverse connect.
+    /// A connection is opened and a ReverseHello sent whenever the client has no connection from
+    /// the server waiting for its hello, i.e. at first, after the client has used the last one and
+    /// after a connection closes or fails.
+    fn start_reverse_connect(server: Arc<RwLock<Server>>) {
+        let (client_urls, retry_interval, server_uri, endpoint_url, server_state) = {
+            let server = trace_read_lock_unwrap!(server);
+            let server_state = trace_read_lock_unwrap!(server.server_state);
+            let config = trace_read_lock_unwrap!(server_state.config);
+            let reverse_connect_config = if let Some(ref reverse_connect_config) = config.reverse_connect_config {
+                reverse_connect_config
+            } else {
+                return;
+            };
+            // The reverse hello names the default endpoint, or failing that any endpoint
+            let base_endpoint_url = config.base_endpoint_url();
+            let endpoint_url = config.default_endpoint()
+                .or_else(|| config.endpoints.values().next())
+                .map(|endpoint| endpoint.endpoint_url(&base_endpoint_url))
+                .unwrap_or(base_endpoint_url);
+            (reverse_connect_config.client_urls.clone(), Duration::from_millis(reverse_connect_config.retry_interval as u64),
+             config.application_uri.clone(), endpoint_url, server.server_state.clone())
+        };
+
+        // The last connection made to each client. The flag is set while a connection is being made.
+        let client_connections: Vec<_> = client_urls.into_iter()
+            .map(|client_url| (client_url, Arc::new(RwLock::new((false, None::<Arc<RwLock<TcpTransport>>>)))))
+            .collect();
+
+        let task = Interval::new(Instant::now(), retry_interval)
+            .take_while(move |_| {
+                let server_state = trace_read_lock_unwrap!(server_state);
+                future::ok(!server_state.is_abort())
+            })
+            .for_each(move |_| {
+                for (client_url, client_connection) in &client_connections {
+                    {
+                        let mut client_connection = trace_write_lock_unwrap!(client_connection);
+                        let (ref mut connecting, ref connection) = *client_connection;
+                        let waiting_for_hello = connection.as_ref().map(|connection| {
+                            let connection = trace_read_lock_unwrap!(connection);
+                            !connection.has_received_hello() && !connection.is_finished()
+                        }).unwrap_or(false);
+                        if *connecting || waiting_for_hello {
+                            continue;
+                        }
+                        *connecting = true;
+                    }
+                    let addr = hostname_port_from_url(client_url, opcua_types::constants::DEFAULT_OPC_UA_SERVER_PORT).ok()
+                        .and_then(|(host, port)| {
+                            use std::net::ToSocketAddrs;
+                            format!("{}:{}", host, port).to_socket_addrs().ok()?.next()
+                        });
+                    let addr = if let Some(addr) = addr {
+                        addr
+                    } else {
+                        error!("Cannot resolve reverse connect client url {}", client_url);
+                        trace_write_lock_unwrap!(client_connection).0 = false;
+                        continue;
+                    };
+
+                    debug!("Reverse connecting to client {}", client_url);
+                    let reverse_hello = ReverseHelloMessage::new(&server_uri, &endpoint_url);
+                    let mut buf = Vec::with_capacity(reverse_hello.byte_len());
+                    let _ = reverse_hello.encode(&mut buf);
+
+                    let server = server.clone();
+                    let client_url = client_url.clone();
+                    let client_connection = client_connection.clone();
+                    let client_connection_for_err = client_connection.clone();
+                    let connect = TcpStream::connect(&addr)
+                        .and_then(move |socket| tokio::io::write_all(socket, buf))
+                        .and_then(|(socket, _)| tokio::io::flush(socket));
+                    let task = Timeout::new(connect, retry_interval)
+                        .map(move |socket| {
+                            info!("Reverse connected to client {}", client_url);
+                            let connection = {
+                                let mut server = trace_write_lock_unwrap!(server);
+                                server.handle_connection(TransportStream::from(socket))
+                            };
+                            *trace_write_lock_unwrap!(client_connection) = (false, Some(connection));
+                        })
+                        .map_err(move |err| {
+                            warn!("Reverse connect to client failed, {:?}", err);
+                            *trace_write_lock_unwrap!(client_connection_for_err) = (false, None);
+                        });
+                    tokio::spawn(task);
+                }
+                Ok(())
+            })
+            .map(|_| {
+                info!("Reverse connect task is finished");
+            })
+            .map_err(|err| {
+                error!("Reverse connect task is finished with an error {:?}", err);
+            });
+        tokio::spawn(task);
+    }
+
     /// This timer will poll the server to see if it has aborted. It also cleans up dead connections.
-    /// If it determines to abort it will signal the tx_abort so that the main listener loop can
-    /// be broken at its convenience.
-    fn start_abort_poll(server: Arc<RwLock<Server>>, tx_abort: UnboundedSender<()>) {
+    /// If it determines to abort it will signal each of the tx_aborts so that the listener loops
+    /// can be broken at their convenience.
+    fn start_abort_poll(server: Arc<RwLock<Server>>, tx_aborts: Vec<UnboundedSender<()>>) {
         let task = Interval::new(Instant::now(), Duration::from_millis(1000))
             .take_while(move |_| {
                 trace!("abort_poll_task.take_while");
@@ -425,7 +670,10 @@ impl Server {
                 };
                 if abort {
                     info!("Server has aborted so, sending a command to break the listen loop");
-                    tx_abort.unbounded_send(()).unwrap();
+                    // A listener that was never started has dropped its receiver
+                    tx_aborts.iter().for_each(|tx_abort| {
+                        let _ = tx_abort.unbounded_send(());
+                    });
                 }
                 future::ok(!abort)
             })
@@ -562,7 +810,7 @@ impl Server {
     }
 
     /// Handles the incoming request
-    fn handle_connection(&mut self, socket: TcpStream) {
+    fn handle_connection(&mut self, stream: TransportStream) -> Arc<RwLock<TcpTransport>> {
         trace!("Connection thread spawning");
 
         // Spawn a thread for the connection
@@ -580,6 +828,7 @@ impl Server {
         };
 
         // Run adds a session task to the tokio session
-        TcpTransport::run(connection, socket, looping_interval_ms);
+        TcpTransport::run(connection.clone(), stream, looping_interval_ms);
+        connection
     }
 }
@@ -12,7 +12,7 @@ use opcua_types::*;
 use opcua_types::status_code::StatusCode;
 
 use crate::{
-    address_space::{AddressSpace, node::{HasNodeId, NodeType}, UserAccessLevel, variable::Variable},
+    address_space::{AccessLevel, AddressSpace, node::{HasNodeId, NodeType}, node_manager::NodeManager, UserAccessLevel, variable::Variable},
     services::Service,
     session::Session,
     state::ServerState,
@@ -327,11 +327,40 @@ impl AttributeService {
         }
     }
 
+    /// Reads a value from a node which belongs to a node manager
+    fn read_managed_node_value(session: &Session, node_manager: &dyn NodeManager, node_to_read: &ReadValueId, max_age: f64, timestamps_to_return: TimestampsToReturn) -> DataValue {
+        let result = AttributeId::from_u32(node_to_read.attribute_id)
+            .map_err(|_| StatusCode::BadAttributeIdInvalid)
+            .and_then(|attribute_id| {
+                let index_range = node_to_read.index_range.as_ref().parse::<NumericRange>()
+                    .map_err(|_| StatusCode::BadIndexRangeInvalid)?;
+                if attribute_id != AttributeId::Value && index_range != NumericRange::None {
+                    Err(StatusCode::BadIndexRangeNoData)
+                } else if !Self::is_supported_data_encoding(&node_to_read.data_encoding) {
+                    Err(StatusCode::BadDataEncodingInvalid)
+                } else {
+                    Self::check_managed_node_readable(session, node_manager, &node_to_read.node_id, attribute_id)?;
+                    node_manager.read(&node_to_read.node_id, timestamps_to_return, attribute_id, index_range, &node_to_read.data_encoding, max_age)
+                }
+            });
+        match result {
+            Ok(data_value) => data_value,
+            Err(status_code) => {
+                debug!("read_managed_node_value result for read node id {}, attribute {} is {}", node_to_read.node_id, node_to_read.attribute_id, status_code);
+                let mut result_value = DataValue::null();
+                result_value.status = Some(status_code);
+                result_value
+            }
+        }
+    }
+
     fn read_node_value(session: &Session, address_space: &AddressSpace, node_to_read: &ReadValueId, max_age: f64, timestamps_to_return: TimestampsToReturn) -> DataValue {
         // Node node found
         // debug!("read_node_value asked to read node id {}, attribute {}", node_to_read.node_id, node_to_read.attribute_id);
         let mut result_value = DataValue::null();
-        if let Some(node) = address_space.find_node(&node_to_read.node_id) {
+        if let Some(node_manager) = address_space.node_manager(&node_to_read.node_id) {
+            result_value = Self::read_managed_node_value(session, node_manager, node_to_read, max_age, timestamps_to_return);
+        } else if let Some(node) = address_space.find_node(&node_to_read.node_id) {
             if let Ok(attribute_id) = AttributeId::from_u32(node_to_read.attribute_id) {
                 let index_range = match node_to_read.index_range.as_ref().parse::<NumericRange>()
                     .map_err(|_| StatusCode::BadIndexRangeInvalid) {
@@ -454,44 +483,46 @@ impl AttributeService {
         }
 
         if let Some(write_mask) = node.as_node().write_mask() {
-            match attribute_id {
-                AttributeId::Value => if let NodeType::VariableType(_) = node {
-                    write_mask.contains(WriteMask::VALUE_FOR_VARIABLE_TYPE)
-                } else {
-                    false
-                }
-                AttributeId::NodeId => write_mask.contains(WriteMask::NODE_ID),
-                AttributeId::NodeClass => write_mask.contains(WriteMask::NODE_CLASS),
-                AttributeId::BrowseName => write_mask.contains(WriteMask::BROWSE_NAME),
-                AttributeId::DisplayName => write_mask.contains(WriteMask::DISPLAY_NAME),
-                AttributeId::Description => write_mask.contains(WriteMask::DESCRIPTION),
-                AttributeId::WriteMask => write_mask.contains(WriteMask::WRITE_MASK),
-                AttributeId::UserWriteMask => write_mask.contains(WriteMask::USER_WRITE_MASK),
-                AttributeId::IsAbstract => write_mask.contains(WriteMask::IS_ABSTRACT),
-                AttributeId::Symmetric => write_mask.contains(WriteMask::SYMMETRIC),
-                AttributeId::InverseName => write_mask.contains(WriteMask::INVERSE_NAME),
-                AttributeId::ContainsNoLoops => write_mask.contains(WriteMask::CONTAINS_NO_LOOPS),
-                AttributeId::EventNotifier => write_mask.contains(WriteMask::EVENT_NOTIFIER),
-                AttributeId::DataType => write_mask.contains(WriteMask::DATA_TYPE),
-                AttributeId::ValueRank => write_mask.contains(WriteMask::VALUE_RANK),
-                AttributeId::ArrayDimensions => write_mask.contains(WriteMask::ARRAY_DIMENSIONS),
-                AttributeId::AccessLevel => write_mask.contains(WriteMask::ACCESS_LEVEL),
-                AttributeId::UserAccessLevel => write_mask.contains(WriteMask::USER_ACCESS_LEVEL),
-                AttributeId::MinimumSamplingInterval => write_mask.contains(WriteMask::MINIMUM_SAMPLING_INTERVAL),
-                AttributeId::Historizing => write_mask.contains(WriteMask::HISTORIZING),
-                AttributeId::Executable => write_mask.contains(WriteMask::EXECUTABLE),
-                AttributeId::UserExecutable => write_mask.contains(WriteMask::USER_EXECUTABLE),
-                AttributeId::DataTypeDefinition => write_mask.contains(WriteMask::DATA_TYPE_DEFINITION),
-                AttributeId::RolePermissions => write_mask.contains(WriteMask::ROLE_PERMISSIONS),
-                AttributeId::AccessRestrictions => write_mask.contains(WriteMask::ACCESS_RESTRICTIONS),
-                AttributeId::AccessLevelEx => write_mask.contains(WriteMask::ACCESS_LEVEL_EX),
-                AttributeId::UserRolePermissions => false // Reserved
-            }
+            let is_variable_type = matches!(node, NodeType::VariableType(_));
+            Self::write_mask_allows(write_mask, attribute_id, is_variable_type)
         } else {
             false
         }
     }
 
+    /// Tests if the write mask of a node allows the attribute to be written
+    fn write_mask_allows(write_mask: WriteMask, attribute_id: AttributeId, is_variable_type: bool) -> bool {
+        match attribute_id {
+            AttributeId::Value => is_variable_type && write_mask.contains(WriteMask::VALUE_FOR_VARIABLE_TYPE),
+            AttributeId::NodeId => write_mask.contains(WriteMask::NODE_ID),
+            AttributeId::NodeClass => write_mask.contains(WriteMask::NODE_CLASS),
+            AttributeId::BrowseName => write_mask.contains(WriteMask::BROWSE_NAME),
+            AttributeId::DisplayName => write_mask.contains(WriteMask::DISPLAY_NAME),
+            AttributeId::Description => write_mask.contains(WriteMask::DESCRIPTION),
+            AttributeId::WriteMask => write_mask.contains(WriteMask::WRITE_MASK),
+            AttributeId::UserWriteMask => write_mask.contains(WriteMask::USER_WRITE_MASK),
+            AttributeId::IsAbstract => write_mask.contains(WriteMask::IS_ABSTRACT),
+            AttributeId::Symmetric => write_mask.contains(WriteMask::SYMMETRIC),
+            AttributeId::InverseName => write_mask.contains(WriteMask::INVERSE_NAME),
+            AttributeId::ContainsNoLoops => write_mask.contains(WriteMask::CONTAINS_NO_LOOPS),
+            AttributeId::EventNotifier => write_mask.contains(WriteMask::EVENT_NOTIFIER),
+            AttributeId::DataType => write_mask.contains(WriteMask::DATA_TYPE),
+            AttributeId::ValueRank => write_mask.contains(WriteMask::VALUE_RANK),
+            AttributeId::ArrayDimensions => write_mask.contains(WriteMask::ARRAY_DIMENSIONS),
+            AttributeId::AccessLevel => write_mask.contains(WriteMask::ACCESS_LEVEL),
+            AttributeId::UserAccessLevel => write_mask.contains(WriteMask::USER_ACCESS_LEVEL),
+            AttributeId::MinimumSamplingInterval => write_mask.contains(WriteMask::MINIMUM_SAMPLING_INTERVAL),
+            AttributeId::Historizing => write_mask.contains(WriteMask::HISTORIZING),
+            AttributeId::Executable => write_mask.contains(WriteMask::EXECUTABLE),
+            AttributeId::UserExecutable => write_mask.contains(WriteMask::USER_EXECUTABLE),
+            AttributeId::DataTypeDefinition => write_mask.contains(WriteMask::DATA_TYPE_DEFINITION),
+            AttributeId::RolePermissions => write_mask.contains(WriteMask::ROLE_PERMISSIONS),
+            AttributeId::AccessRestrictions => write_mask.contains(WriteMask::ACCESS_RESTRICTIONS),
+            AttributeId::AccessLevelEx => write_mask.contains(WriteMask::ACCESS_LEVEL_EX),
+            AttributeId::UserRolePermissions => false // Reserved
+        }
+    }
+
     /*
     fn is_history_readable(session: &Session, node: &NodeType) -> bool {
         Self::user_access_level(session, node, AttributeId::Value).contains(UserAccessLevel::HISTORY_READ)
@@ -512,6 +543,13 @@ impl AttributeService {
         let valid = if let Variant::Empty = value {
             // Assigning an empty value is permissible
             true
+        } else if let Variant::ExtensionObject(ref value) = value {
+            // The data type of a structure is the one that has the value's encoding
+            let value_data_type = address_space.find_inverse_references(&value.node_id, Some((ReferenceTypeId::HasEncoding, false)))
+                .and_then(|references| references.into_iter().next())
+                .map(|reference| reference.target_node)
+                .unwrap_or_else(|| DataTypeId::Structure.into());
+            address_space.is_subtype(&value_data_type, &node_data_type)
         } else if let Some(value_data_type) = value.scalar_data_type() {
             // Value is scalar. Check if the data type matches
             let data_type_matches = address_space.is_subtype(&value_data_type, &node_data_type);
@@ -548,8 +586,114 @@ impl AttributeService {
         valid
     }
 
+    /// Reads an attribute of a managed node that the server needs for itself
+    fn read_managed_node_attribute(node_manager: &dyn NodeManager, node_id: &NodeId, attribute_id: AttributeId) -> Option<Variant> {
+        node_manager.read(node_id, TimestampsToReturn::Neither, attribute_id, NumericRange::None, &QualifiedName::null(), 0f64)
+            .ok()
+            .and_then(|data_value| data_value.value)
+    }
+
+    /// Tests if a managed node is writable by the session in the same way as `is_writable()`,
+    /// using the `NodeClass`, `UserAccessLevel` and `WriteMask` attributes that the manager reports
+    fn is_managed_node_writable(session: &Session, node_manager: &dyn NodeManager, node_id: &NodeId, attribute_id: AttributeId) -> bool {
+        let node_class = match Self::read_managed_node_attribute(node_manager, node_id, AttributeId::NodeClass) {
+            Some(Variant::Int32(node_class)) => node_class,
+            _ => return false
+        };
+        if node_class == NodeClass::Variable as i32 && attribute_id == AttributeId::Value {
+            match Self::read_managed_node_attribute(node_manager, node_id, AttributeId::UserAccessLevel) {
+                Some(Variant::Byte(user_access_level)) => {
+                    let user_access_level = UserAccessLevel::from_bits_truncate(user_access_level);
+                    session.effective_user_access_level(user_access_level, node_id, attribute_id).contains(UserAccessLevel::CURRENT_WRITE)
+                }
+                _ => false
+            }
+        } else {
+            match Self::read_managed_node_attribute(node_manager, node_id, AttributeId::WriteMask) {
+                Some(Variant::UInt32(write_mask)) => {
+                    let is_variable_type = node_class == NodeClass::VariableType as i32;
+                    Self::write_mask_allows(WriteMask::from_bits_truncate(write_mask), attribute_id, is_variable_type)
+                }
+                _ => false
+            }
+        }
+    }
+
+    /// Tests if the value of a managed node is readable by the session, using the `AccessLevel` and
+    /// `UserAccessLevel` attributes that the manager reports for a `Variable`. Returns
+    /// `BadNotReadable` if nobody may read the value, or `BadUserAccessDenied` if this session may
+    /// not. A variable that reports neither attribute is not readable.
+    fn check_managed_node_readable(session: &Session, node_manager: &dyn NodeManager, node_id: &NodeId, attribute_id: AttributeId) -> Result<(), StatusCode> {
+        if attribute_id != AttributeId::Value {
+            return Ok(());
+        }
+        match Self::read_managed_node_attribute(node_manager, node_id, AttributeId::NodeClass) {
+            Some(Variant::Int32(node_class)) if node_class == NodeClass::Variable as i32 => {}
+            _ => return Ok(())
+        }
+        let access_level = match Self::read_managed_node_attribute(node_manager, node_id, AttributeId::AccessLevel) {
+            Some(Variant::Byte(access_level)) => Some(AccessLevel::from_bits_truncate(access_level)),
+            _ => None
+        };
+        let user_access_level = match Self::read_managed_node_attribute(node_manager, node_id, AttributeId::UserAccessLevel) {
+            Some(Variant::Byte(user_access_level)) => Some(UserAccessLevel::from_bits_truncate(user_access_level)),
+            _ => None
+        };
+        if access_level.map(|access_level| !access_level.contains(AccessLevel::CURRENT_READ)).unwrap_or(false) {
+            debug!("Value of managed node {} is not readable", node_id);
+            return Err(StatusCode::BadNotReadable);
+        }
+        // The user access level can only narrow the access level, so that stands in for it if absent
+        let user_access_level = match user_access_level.or_else(|| access_level.map(|access_level| UserAccessLevel::from_bits_truncate(access_level.bits()))) {
+            Some(user_access_level) => user_access_level,
+            None => {
+                debug!("Managed node {} has no access level so its value is not readable", node_id);
+                return Err(StatusCode::BadNotReadable);
+            }
+        };
+        if session.effective_user_access_level(user_access_level, node_id, attribute_id).contains(UserAccessLevel::CURRENT_READ) {
+            Ok(())
+        } else {
+            debug!("Value of managed node {} is not readable by the user of the session", node_id);
+            Err(StatusCode::BadUserAccessDenied)
+        }
+    }
+
+    /// Writes a value to a node which belongs to a node manager
+    fn write_managed_node_value(session: &Session, node_manager: &dyn NodeManager, node_to_write: &WriteValue) -> StatusCode {
+        if let Ok(attribute_id) = AttributeId::from_u32(node_to_write.attribute_id) {
+            if let Ok(index_range) = node_to_write.index_range.as_ref().parse::<NumericRange>() {
+                if !Self::is_managed_node_writable(session, node_manager, &node_to_write.node_id, attribute_id) {
+                    StatusCode::BadNotWritable
+                } else if attribute_id != AttributeId::Value && !node_to_write.index_range.is_null() {
+                    error!("Server does not support indexes for attributes other than Value");
+                    StatusCode::BadWriteNotSupported
+                } else if node_to_write.value.value.is_none() {
+                    error!("Server does not support missing value in write");
+                    StatusCode::BadTypeMismatch
+                } else {
+                    match node_manager.write(session, &node_to_write.node_id, attribute_id, index_range, node_to_write.value.clone()) {
+                        Ok(_) => StatusCode::Good,
+                        Err(err) => {
+                            error!("Value could not be set to managed node {} attribute {:?}, error = {}", node_to_write.node_id, attribute_id, err);
+                            err
+                        }
+                    }
+                }
+            } else {
+                error!("Index range is invalid");
+                StatusCode::BadIndexRangeInvalid
+            }
+        } else {
+            warn!("Attribute id {} is invalid", node_to_write.attribute_id);
+            StatusCode::BadAttributeIdInvalid
+        }
+    }
+
     fn write_node_value(session: &Session, address_space: &mut AddressSpace, node_to_write: &WriteValue) -> StatusCode {
-        if let Some(node) = address_space.find_node(&node_to_write.node_id) {
+        if let Some(node_manager) = address_space.node_manager(&node_to_write.node_id) {
+            Self::write_managed_node_value(session, node_manager, node_to_write)
+        } else if let Some(node) = address_space.find_node(&node_to_write.node_id) {
             if let Ok(attribute_id) = AttributeId::from_u32(node_to_write.attribute_id) {
                 let index_range = node_to_write.index_range.as_ref().parse::<NumericRange>();
 
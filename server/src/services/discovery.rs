@@ -58,9 +58,13 @@ impl DiscoveryService {
 
         // TODO endpoint URL
         // TODO localeids
-        // TODO serverUris
 
-        let servers = Some(vec![application_description]);
+        // The server only knows itself, so it is left out if the client asks for other servers
+        let servers = match request.server_uris {
+            Some(ref server_uris) if !server_uris.is_empty() && !server_uris.contains(&application_description.application_uri) => Vec::new(),
+            _ => vec![application_description]
+        };
+        let servers = Some(servers);
 
         FindServersResponse {
             response_header: ResponseHeader::new_good(&request.request_header),
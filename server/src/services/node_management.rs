@@ -156,13 +156,12 @@ impl NodeManagementService {
         }
     }
 
-    fn create_node(node_id: &NodeId, node_class: NodeClass, browse_name: QualifiedName, node_attributes: &ExtensionObject, decoding_limits: &DecodingLimits) -> Result<NodeType, StatusCode> {
-        let object_id = node_attributes.node_id.as_object_id().map_err(|_| StatusCode::BadNodeAttributesInvalid)?;
+    fn create_node(node_id: &NodeId, node_class: NodeClass, browse_name: QualifiedName, node_attributes: &ExtensionObject, namespaces: &NamespaceMap, decoding_limits: &DecodingLimits) -> Result<NodeType, StatusCode> {
         // Note we are expecting the node_class and the object id for the attributes to be for the same
         // thing. If they are different, it is an error.
 
-        match object_id {
-            ObjectId::ObjectAttributes_Encoding_DefaultBinary => {
+        match node_attributes.node_id.as_object_id() {
+            Ok(ObjectId::ObjectAttributes_Encoding_DefaultBinary) => {
                 if node_class == NodeClass::Object {
                     let attributes = node_attributes.decode_inner::<ObjectAttributes>(decoding_limits)?;
                     Object::from_attributes(node_id, browse_name, attributes).map(|n| n.into())
@@ -171,7 +170,7 @@ impl NodeManagementService {
                     Err(())
                 }
             }
-            ObjectId::VariableAttributes_Encoding_DefaultBinary => {
+            Ok(ObjectId::VariableAttributes_Encoding_DefaultBinary) => {
                 if node_class == NodeClass::Variable {
                     let attributes = node_attributes.decode_inner::<VariableAttributes>(decoding_limits)?;
                     Variable::from_attributes(node_id, browse_name, attributes).map(|n| n.into())
@@ -180,7 +179,7 @@ impl NodeManagementService {
                     Err(())
                 }
             }
-            ObjectId::MethodAttributes_Encoding_DefaultBinary => {
+            Ok(ObjectId::MethodAttributes_Encoding_DefaultBinary) => {
                 if node_class == NodeClass::Method {
                     let attributes = node_attributes.decode_inner::<MethodAttributes>(decoding_limits)?;
                     Method::from_attributes(node_id, browse_name, attributes).map(|n| n.into())
@@ -189,7 +188,7 @@ impl NodeManagementService {
                     Err(())
                 }
             }
-            ObjectId::ObjectTypeAttributes_Encoding_DefaultBinary => {
+            Ok(ObjectId::ObjectTypeAttributes_Encoding_DefaultBinary) => {
                 if node_class == NodeClass::ObjectType {
                     let attributes = node_attributes.decode_inner::<ObjectTypeAttributes>(decoding_limits)?;
                     ObjectType::from_attributes(node_id, browse_name, attributes).map(|n| n.into())
@@ -198,7 +197,7 @@ impl NodeManagementService {
                     Err(())
                 }
             }
-            ObjectId::VariableTypeAttributes_Encoding_DefaultBinary => {
+            Ok(ObjectId::VariableTypeAttributes_Encoding_DefaultBinary) => {
                 if node_class == NodeClass::VariableType {
                     let attributes = node_attributes.decode_inner::<VariableTypeAttributes>(decoding_limits)?;
                     VariableType::from_attributes(node_id, browse_name, attributes).map(|n| n.into())
@@ -207,7 +206,7 @@ impl NodeManagementService {
                     Err(())
                 }
             }
-            ObjectId::ReferenceTypeAttributes_Encoding_DefaultBinary => {
+            Ok(ObjectId::ReferenceTypeAttributes_Encoding_DefaultBinary) => {
                 if node_class == NodeClass::ReferenceType {
                     let attributes = node_attributes.decode_inner::<ReferenceTypeAttributes>(decoding_limits)?;
                     ReferenceType::from_attributes(node_id, browse_name, attributes).map(|n| n.into())
@@ -216,7 +215,7 @@ impl NodeManagementService {
                     Err(())
                 }
             }
-            ObjectId::DataTypeAttributes_Encoding_DefaultBinary => {
+            Ok(ObjectId::DataTypeAttributes_Encoding_DefaultBinary) => {
                 if node_class == NodeClass::DataType {
                     let attributes = node_attributes.decode_inner::<DataTypeAttributes>(decoding_limits)?;
                     DataType::from_attributes(node_id, browse_name, attributes).map(|n| n.into())
@@ -225,7 +224,7 @@ impl NodeManagementService {
                     Err(())
                 }
             }
-            ObjectId::ViewAttributes_Encoding_DefaultBinary => {
+            Ok(ObjectId::ViewAttributes_Encoding_DefaultBinary) => {
                 if node_class == NodeClass::View {
                     let attributes = node_attributes.decode_inner::<ViewAttributes>(decoding_limits)?;
                     View::from_attributes(node_id, browse_name, attributes).map(|n| n.into())
@@ -235,12 +234,46 @@ impl NodeManagementService {
                 }
             }
             _ => {
-                error!("create_node was called with an object id which does not match a supported type");
-                Err(())
+                // The attributes may be a type registered by the application, e.g. one of its own
+                // encoding ids for one of the attribute structures
+                match node_attributes.decode_registered(namespaces, decoding_limits) {
+                    Some(Ok(attributes)) => Self::create_node_from_registered(node_id, node_class, browse_name, attributes),
+                    Some(Err(_)) => Err(()),
+                    None => {
+                        error!("create_node was called with an object id which does not match a supported type");
+                        Err(())
+                    }
+                }
             }
         }.map_err(|_| StatusCode::BadNodeAttributesInvalid)
     }
 
+    fn create_node_from_registered(node_id: &NodeId, node_class: NodeClass, browse_name: QualifiedName, attributes: RegisteredValue) -> Result<NodeType, ()> {
+        let node = match node_class {
+            NodeClass::Object => attributes.downcast_ref::<ObjectAttributes>()
+                .map(|a| Object::from_attributes(node_id, browse_name, a.clone()).map(|n| n.into())),
+            NodeClass::Variable => attributes.downcast_ref::<VariableAttributes>()
+                .map(|a| Variable::from_attributes(node_id, browse_name, a.clone()).map(|n| n.into())),
+            NodeClass::Method => attributes.downcast_ref::<MethodAttributes>()
+                .map(|a| Method::from_attributes(node_id, browse_name, a.clone()).map(|n| n.into())),
+            NodeClass::ObjectType => attributes.downcast_ref::<ObjectTypeAttributes>()
+                .map(|a| ObjectType::from_attributes(node_id, browse_name, a.clone()).map(|n| n.into())),
+            NodeClass::VariableType => attributes.downcast_ref::<VariableTypeAttributes>()
+                .map(|a| VariableType::from_attributes(node_id, browse_name, a.clone()).map(|n| n.into())),
+            NodeClass::ReferenceType => attributes.downcast_ref::<ReferenceTypeAttributes>()
+                .map(|a| ReferenceType::from_attributes(node_id, browse_name, a.clone()).map(|n| n.into())),
+            NodeClass::DataType => attributes.downcast_ref::<DataTypeAttributes>()
+                .map(|a| DataType::from_attributes(node_id, browse_name, a.clone()).map(|n| n.into())),
+            NodeClass::View => attributes.downcast_ref::<ViewAttributes>()
+                .map(|a| View::from_attributes(node_id, browse_name, a.clone()).map(|n| n.into())),
+            NodeClass::Unspecified => None,
+        };
+        node.unwrap_or_else(|| {
+            error!("node class and registered node attributes are not compatible");
+            Err(())
+        })
+    }
+
     fn add_node(session: &Session, address_space: &mut AddressSpace, item: &AddNodesItem, decoding_limits: &DecodingLimits) -> (StatusCode, NodeId) {
         if !session.can_modify_address_space() {
             // No permission to modify address space
@@ -273,13 +306,15 @@ impl NodeManagementService {
             return (StatusCode::BadBrowseNameInvalid, NodeId::null());
         }
 
-        // Test duplicate browse name to same parent
-        let browse_name = if item.browse_name.namespace_index != 0 {
-            format!("{}:{}", item.browse_name.namespace_index, item.browse_name.name.as_ref())
-        } else {
-            format!("/{}", item.browse_name.name.as_ref())
+        // Test duplicate browse name to same parent, following hierarchical references to it
+        let relative_path = RelativePath {
+            elements: Some(vec![RelativePathElement {
+                reference_type_id: ReferenceTypeId::HierarchicalReferences.into(),
+                is_inverse: false,
+                include_subtypes: true,
+                target_name: item.browse_name.clone(),
+            }])
         };
-        let relative_path = RelativePath::from_str(&browse_name, &RelativePathElement::default_node_resolver).unwrap();
         if let Ok(nodes) = relative_path::find_nodes_relative_path(address_space, &item.parent_node_id.node_id, &relative_path) {
             if !nodes.is_empty() {
                 error!("node cannot be created because the browse name is a duplicate");
@@ -312,10 +347,10 @@ impl NodeManagementService {
             }
 
             // Create a node
-            if let Ok(node) = Self::create_node(&new_node_id, item.node_class, item.browse_name.clone(), &item.node_attributes, decoding_limits) {
-                // Add the node to the address space
+            if let Ok(node) = Self::create_node(&new_node_id, item.node_class, item.browse_name.clone(), &item.node_attributes, address_space.namespace_map(), decoding_limits) {
+                // Add the node to the address space, referenced from its parent
                 address_space.insert(node, Some(&[
-                    (&item.parent_node_id.node_id, &reference_type_id, ReferenceDirection::Forward),
+                    (&item.parent_node_id.node_id, &reference_type_id, ReferenceDirection::Inverse),
                 ]));
                 // Object / Variable types must add a reference to the type
                 if item.node_class == NodeClass::Object || item.node_class == NodeClass::Variable {
@@ -14,7 +14,7 @@ use opcua_types::{
 };
 
 use crate::{
-    address_space::{AddressSpace, relative_path},
+    address_space::{AddressSpace, node_manager::NodeManager, relative_path, references::Reference},
     continuation_point::BrowseContinuationPoint,
     services::Service,
     session::Session,
@@ -247,6 +247,12 @@ impl ViewService {
     }
 
     fn browse_node(session: &mut Session, address_space: &AddressSpace, starting_index: usize, node_to_browse: &BrowseDescription, max_references_per_node: usize) -> Result<BrowseResult, StatusCode> {
+        // Managed nodes are browsed by their node manager
+        if let Some(node_manager) = address_space.node_manager(&node_to_browse.node_id) {
+            let reference_descriptions = node_manager.browse(node_to_browse)?;
+            return Ok(Self::reference_description_to_browse_result(session, address_space, &reference_descriptions, 0, max_references_per_node));
+        }
+
         // Node must exist or there will be no references
         if node_to_browse.node_id.is_null() || !address_space.node_exists(&node_to_browse.node_id) {
             return Err(StatusCode::BadNodeIdUnknown);
@@ -282,6 +288,13 @@ impl ViewService {
             if target_node_id.is_null() {
                 continue;
             }
+            if let Some(node_manager) = address_space.node_manager(&target_node_id) {
+                // The target is a managed node so the manager has to describe it
+                if let Some(reference_description) = Self::managed_reference_description(node_manager, reference, idx < inverse_ref_idx, result_mask, node_class_mask) {
+                    reference_descriptions.push(reference_description);
+                }
+                continue;
+            }
             let target_node = address_space.find_node(&target_node_id);
             if target_node.is_none() {
                 continue;
@@ -361,6 +374,93 @@ impl ViewService {
         Ok(Self::reference_description_to_browse_result(session, address_space, &reference_descriptions, 0, max_references_per_node))
     }
 
+    /// Creates a reference description for a reference from a node in the address space to a node
+    /// that belongs to a node manager. The manager is asked for the attributes of the target node,
+    /// and for its type definition if the result mask includes it.
+    /// No description is returned if the target does not exist or is excluded by the node class mask.
+    fn managed_reference_description(node_manager: &dyn NodeManager, reference: &Reference, is_forward: bool, result_mask: BrowseDescriptionResultMask, node_class_mask: NodeClassMask) -> Option<ReferenceDescription> {
+        let target_node_id = &reference.target_node;
+        if !node_manager.node_exists(target_node_id) {
+            return None;
+        }
+        let read_attribute = |attribute_id: AttributeId| -> Option<Variant> {
+            node_manager.read(target_node_id, TimestampsToReturn::Neither, attribute_id, NumericRange::None, &QualifiedName::null(), 0f64)
+                .ok()
+                .and_then(|v| v.value)
+        };
+
+        let target_node_class = match read_attribute(AttributeId::NodeClass) {
+            Some(Variant::Int32(1)) => NodeClass::Object,
+            Some(Variant::Int32(2)) => NodeClass::Variable,
+            Some(Variant::Int32(4)) => NodeClass::Method,
+            Some(Variant::Int32(8)) => NodeClass::ObjectType,
+            Some(Variant::Int32(16)) => NodeClass::VariableType,
+            Some(Variant::Int32(32)) => NodeClass::ReferenceType,
+            Some(Variant::Int32(64)) => NodeClass::DataType,
+            Some(Variant::Int32(128)) => NodeClass::View,
+            _ => NodeClass::Unspecified
+        };
+
+        // Skip target nodes not required by the mask
+        if target_node_class != NodeClass::Unspecified && !node_class_mask.is_empty()
+            && !node_class_mask.contains(NodeClassMask::from_bits_truncate(target_node_class as u32)) {
+            return None;
+        }
+
+        let reference_type_id = if result_mask.contains(BrowseDescriptionResultMask::RESULT_MASK_REFERENCE_TYPE) {
+            reference.reference_type.clone()
+        } else {
+            NodeId::null()
+        };
+        let is_forward = !result_mask.contains(BrowseDescriptionResultMask::RESULT_MASK_IS_FORWARD) || is_forward;
+        let node_class = if result_mask.contains(BrowseDescriptionResultMask::RESULT_MASK_NODE_CLASS) {
+            target_node_class
+        } else {
+            NodeClass::Unspecified
+        };
+        let browse_name = match read_attribute(AttributeId::BrowseName) {
+            Some(Variant::QualifiedName(browse_name)) if result_mask.contains(BrowseDescriptionResultMask::RESULT_MASK_BROWSE_NAME) => *browse_name,
+            _ => QualifiedName::null()
+        };
+        let display_name = match read_attribute(AttributeId::DisplayName) {
+            Some(Variant::LocalizedText(display_name)) if result_mask.contains(BrowseDescriptionResultMask::RESULT_MASK_DISPLAY_NAME) => *display_name,
+            _ => LocalizedText::null()
+        };
+        let type_definition = if result_mask.contains(BrowseDescriptionResultMask::RESULT_MASK_TYPE_DEFINITION) {
+            // Only Object and Variable nodes have a type definition, which the manager reports as
+            // the target of a HasTypeDefinition reference
+            match target_node_class {
+                NodeClass::Object | NodeClass::Variable => {
+                    let node_to_browse = BrowseDescription {
+                        node_id: target_node_id.clone(),
+                        browse_direction: BrowseDirection::Forward,
+                        reference_type_id: ReferenceTypeId::HasTypeDefinition.into(),
+                        include_subtypes: false,
+                        node_class_mask: 0,
+                        result_mask: BrowseDescriptionResultMask::RESULT_MASK_REFERENCE_TYPE.bits(),
+                    };
+                    let has_type_definition: NodeId = ReferenceTypeId::HasTypeDefinition.into();
+                    node_manager.browse(&node_to_browse).ok()
+                        .and_then(|references| references.into_iter().find(|r| r.reference_type_id == has_type_definition))
+                        .map(|reference| reference.node_id)
+                        .unwrap_or_else(ExpandedNodeId::null)
+                }
+                _ => ExpandedNodeId::null()
+            }
+        } else {
+            ExpandedNodeId::null()
+        };
+        Some(ReferenceDescription {
+            node_id: ExpandedNodeId::new(target_node_id.clone()),
+            reference_type_id,
+            is_forward,
+            node_class,
+            browse_name,
+            display_name,
+            type_definition,
+        })
+    }
+
     fn browse_from_continuation_point(session: &mut Session, address_space: &AddressSpace, continuation_point: &ByteString) -> BrowseResult {
         // Find the continuation point in the session
         if let Some(continuation_point) = session.find_browse_continuation_point(continuation_point) {
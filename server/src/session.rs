@@ -12,7 +12,7 @@ use std::{
 use chrono::{self, Utc};
 
 use opcua_core::comms::secure_channel::{Role, SecureChannel};
-use opcua_crypto::X509;
+use opcua_crypto::{CertificateStore, X509};
 use opcua_types::{
     *, service_types::PublishRequest, status_code::StatusCode,
 };
@@ -23,6 +23,7 @@ use crate::{
     diagnostics::ServerDiagnostics,
     identity_token::IdentityToken,
     server::Server,
+    state::ServerState,
     session_diagnostics::SessionDiagnostics,
     subscriptions::subscription::TickReason,
     subscriptions::subscriptions::Subscriptions,
@@ -154,9 +155,14 @@ impl Session {
 
     /// Create a `Session` from a `Server`
     pub fn new(server: &Server) -> Session {
+        Self::new_with_state(server.server_state(), server.certificate_store())
+    }
+
+    /// Creates a session from the server's state and certificate store, used by transports that
+    /// do not hold onto the server
+    pub(crate) fn new_with_state(server_state: Arc<RwLock<ServerState>>, certificate_store: Arc<RwLock<CertificateStore>>) -> Session {
         let max_browse_continuation_points = super::constants::MAX_BROWSE_CONTINUATION_POINTS;
 
-        let server_state = server.server_state();
         let server_state = trace_read_lock_unwrap!(server_state);
         let max_subscriptions = server_state.max_subscriptions;
         let diagnostics = server_state.diagnostics.clone();
@@ -175,7 +181,7 @@ impl Session {
             client_certificate: None,
             security_policy_uri: String::new(),
             authentication_token: NodeId::null(),
-            secure_channel: Arc::new(RwLock::new(SecureChannel::new(server.certificate_store(), Role::Server, decoding_limits))),
+            secure_channel: Arc::new(RwLock::new(SecureChannel::new(certificate_store, Role::Server, decoding_limits))),
             session_nonce: ByteString::null(),
             session_name: UAString::null(),
             session_timeout: 0f64,
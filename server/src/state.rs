@@ -4,10 +4,11 @@
 
 //! Provides server state information, such as status, configuration, running servers and so on.
 
+use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
 
 use opcua_core::prelude::*;
-use opcua_crypto::{PrivateKey, SecurityPolicy, user_identity, X509};
+use opcua_crypto::{CertificateStore, PrivateKey, SecurityPolicy, TlsAcceptor, user_identity, X509};
 use opcua_types::{
     profiles,
     service_types::{
@@ -105,8 +106,8 @@ pub struct ServerState {
     pub max_lifetime_count: u32,
     /// Operational limits
     pub(crate) operational_limits: OperationalLimits,
-    //// Current state
-    pub state: ServerStateType,
+    /// Current state, which is shared with the getter of `Server_ServerStatus_State`
+    pub(crate) state: Arc<RwLock<ServerStateType>>,
     /// Sets the abort flag that terminates the associated server
     pub abort: bool,
     /// Audit log
@@ -127,35 +128,41 @@ impl ServerState {
     pub fn endpoints(&self, endpoint_url: &UAString, transport_profile_uris: &Option<Vec<UAString>>) -> Option<Vec<EndpointDescription>> {
         // Filter endpoints based on profile_uris
         debug!("Endpoints requested, transport profile uris {:?}", transport_profile_uris);
+        let config = trace_read_lock_unwrap!(self.config);
+        let mut transports = Self::transports(&config);
         if let Some(ref transport_profile_uris) = *transport_profile_uris {
             // Note - some clients pass an empty array
             if !transport_profile_uris.is_empty() {
-                // As we only support binary transport, the result is None if the supplied profile_uris does not contain that profile
-                let found_binary_transport = transport_profile_uris.iter().any(|profile_uri| {
-                    profile_uri.as_ref() == profiles::TRANSPORT_PROFILE_URI_BINARY
+                // The result is None if the supplied profile_uris contains none of the supported transports
+                transports.retain(|(_, transport_profile_uri)| {
+                    transport_profile_uris.iter().any(|profile_uri| profile_uri.as_ref() == *transport_profile_uri)
                 });
-                if !found_binary_transport {
-                    error!("Client wants to connect with a non binary transport {:#?}", transport_profile_uris);
+                if transports.is_empty() {
+                    error!("Client wants to connect with an unsupported transport {:#?}", transport_profile_uris);
                     return None;
                 }
             }
         }
 
-        let config = trace_read_lock_unwrap!(self.config);
         if let Ok(hostname) = hostname_from_url(endpoint_url.as_ref()) {
             if !hostname.eq_ignore_ascii_case(&config.tcp_config.host) {
                 debug!("Endpoint url \"{}\" hostname supplied by caller does not match server's hostname \"{}\"", endpoint_url, &config.tcp_config.host);
             }
-            let endpoints = config.endpoints.iter()
-                .map(|(_, e)| {
-                    self.new_endpoint_description(&config, e, true)
+            let endpoints = transports.iter()
+                .flat_map(|(base_endpoint_url, transport_profile_uri)| {
+                    config.endpoints.values().map(move |e| (base_endpoint_url, *transport_profile_uri, e))
+                })
+                .map(|(base_endpoint_url, transport_profile_uri, e)| {
+                    self.new_endpoint_description(&config, e, base_endpoint_url, transport_profile_uri, true)
                 })
                 .collect();
             Some(endpoints)
         } else {
             warn!("Endpoint url \"{}\" is unrecognized, using default", endpoint_url);
             if let Some(e) = config.default_endpoint() {
-                Some(vec![self.new_endpoint_description(&config, e, true)])
+                Some(transports.iter().map(|(base_endpoint_url, transport_profile_uri)| {
+                    self.new_endpoint_description(&config, e, base_endpoint_url, transport_profile_uri, true)
+                }).collect())
             }
             else {
                 Some(vec![])
@@ -163,6 +170,18 @@ impl ServerState {
         }
     }
 
+    /// Returns the base url and transport profile uri of each transport the server offers
+    fn transports(config: &ServerConfig) -> Vec<(String, &'static str)> {
+        let mut transports = vec![(config.base_endpoint_url(), profiles::TRANSPORT_PROFILE_URI_BINARY)];
+        if let Some(base_https_endpoint_url) = config.base_https_endpoint_url() {
+            transports.push((base_https_endpoint_url, profiles::TRANSPORT_PROFILE_URI_HTTPS_BINARY));
+        }
+        if let Some(base_wss_endpoint_url) = config.base_wss_endpoint_url() {
+            transports.push((base_wss_endpoint_url, profiles::TRANSPORT_PROFILE_URI_WSS_BINARY));
+        }
+        transports
+    }
+
     pub fn endpoint_exists(&self, endpoint_url: &str, security_policy: SecurityPolicy, security_mode: MessageSecurityMode) -> bool {
         let config = trace_read_lock_unwrap!(self.config);
         config.find_endpoint(endpoint_url, security_policy, security_mode).is_some()
@@ -174,11 +193,24 @@ impl ServerState {
     pub fn new_endpoint_descriptions(&self, endpoint_url: &str) -> Option<Vec<EndpointDescription>> {
         debug!("find_endpoint, url = {}", endpoint_url);
         let config = trace_read_lock_unwrap!(self.config);
-        let base_endpoint_url = config.base_endpoint_url();
+        // The transport of the url decides which endpoint urls the descriptions hold
+        let (base_endpoint_url, transport_profile_uri) = if is_opc_ua_https_url(endpoint_url) {
+            match config.base_https_endpoint_url() {
+                Some(base_https_endpoint_url) => (base_https_endpoint_url, profiles::TRANSPORT_PROFILE_URI_HTTPS_BINARY),
+                None => return None
+            }
+        } else if is_opc_ua_wss_url(endpoint_url) {
+            match config.base_wss_endpoint_url() {
+                Some(base_wss_endpoint_url) => (base_wss_endpoint_url, profiles::TRANSPORT_PROFILE_URI_WSS_BINARY),
+                None => return None
+            }
+        } else {
+            (config.base_endpoint_url(), profiles::TRANSPORT_PROFILE_URI_BINARY)
+        };
         let endpoints: Vec<EndpointDescription> = config.endpoints.iter().filter(|&(_, e)| {
             // Test end point's security_policy_uri and matching url
             url_matches_except_host(&e.endpoint_url(&base_endpoint_url), endpoint_url)
-        }).map(|(_, e)| self.new_endpoint_description(&config, e, false)).collect();
+        }).map(|(_, e)| self.new_endpoint_description(&config, e, &base_endpoint_url, transport_profile_uri, false)).collect();
         if endpoints.is_empty() { None } else { Some(endpoints) }
     }
 
@@ -243,9 +275,7 @@ impl ServerState {
     }
 
     /// Constructs a new endpoint description using the server's info and that in an Endpoint
-    fn new_endpoint_description(&self, config: &ServerConfig, endpoint: &ServerEndpoint, all_fields: bool) -> EndpointDescription {
-        let base_endpoint_url = config.base_endpoint_url();
-
+    fn new_endpoint_description(&self, config: &ServerConfig, endpoint: &ServerEndpoint, base_endpoint_url: &str, transport_profile_uri: &str, all_fields: bool) -> EndpointDescription {
         let user_identity_tokens = self.user_identity_tokens(config, endpoint);
 
         // CreateSession doesn't need all the endpoint description
@@ -274,13 +304,13 @@ impl ServerState {
         };
 
         EndpointDescription {
-            endpoint_url: endpoint.endpoint_url(&base_endpoint_url).into(),
+            endpoint_url: endpoint.endpoint_url(base_endpoint_url).into(),
             server,
             server_certificate,
             security_mode: endpoint.message_security_mode(),
             security_policy_uri: UAString::from(endpoint.security_policy().to_uri()),
             user_identity_tokens: Some(user_identity_tokens),
-            transport_profile_uri: UAString::from(profiles::TRANSPORT_PROFILE_URI_BINARY),
+            transport_profile_uri: UAString::from(transport_profile_uri),
             security_level: endpoint.security_level,
         }
     }
@@ -301,18 +331,39 @@ impl ServerState {
     pub fn abort(&mut self) {
         info!("Server has been told to abort");
         self.abort = true;
-        self.state = ServerStateType::Shutdown;
+        self.set_state(ServerStateType::Shutdown);
     }
 
-    pub fn state(&self) -> ServerStateType { self.state }
+    pub fn state(&self) -> ServerStateType { *trace_read_lock_unwrap!(self.state) }
 
     pub fn set_state(&mut self, state: ServerStateType) {
-        self.state = state;
+        *trace_write_lock_unwrap!(self.state) = state;
     }
 
     pub fn is_abort(&self) -> bool { self.abort }
 
-    pub fn is_running(&self) -> bool { self.state == ServerStateType::Running }
+    pub fn is_running(&self) -> bool { self.state() == ServerStateType::Running }
+
+    /// Creates the acceptor for a transport that runs over TLS. It presents the certificate at the
+    /// path if one is configured, otherwise the application instance certificate.
+    pub(crate) fn tls_acceptor(&self, certificate_path: &Option<PathBuf>, private_key_path: &Option<PathBuf>) -> Result<TlsAcceptor, StatusCode> {
+        if let (Some(ref certificate_path), Some(ref private_key_path)) = (certificate_path, private_key_path) {
+            let cert = CertificateStore::read_cert(certificate_path).map_err(|err| {
+                error!("Cannot read the TLS certificate, {}", err);
+                StatusCode::BadCertificateInvalid
+            })?;
+            let pkey = CertificateStore::read_pkey(private_key_path).map_err(|err| {
+                error!("Cannot read the TLS private key, {}", err);
+                StatusCode::BadCertificateInvalid
+            })?;
+            TlsAcceptor::new(&cert, &pkey)
+        } else if let (Some(ref cert), Some(ref pkey)) = (&self.server_certificate, &self.server_pkey) {
+            TlsAcceptor::new(cert, pkey)
+        } else {
+            error!("Server has no application instance certificate to use for TLS");
+            Err(StatusCode::BadCertificateInvalid)
+        }
+    }
 
     pub fn server_certificate_as_byte_string(&self) -> ByteString {
         if let Some(ref server_certificate) = self.server_certificate {
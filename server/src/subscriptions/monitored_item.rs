@@ -51,30 +51,47 @@ pub(crate) enum FilterType {
 }
 
 impl FilterType {
-    pub fn from_filter(filter: &ExtensionObject) -> Result<FilterType, StatusCode> {
+    pub fn from_filter(filter: &ExtensionObject, namespaces: &NamespaceMap) -> Result<FilterType, StatusCode> {
         // Check if the filter is a supported filter type
         let filter_type_id = &filter.node_id;
         if filter_type_id.is_null() {
             // No data filter was passed, so just a dumb value comparison
             Ok(FilterType::None)
-        } else if let Ok(filter_type_id) = filter_type_id.as_object_id() {
-            match filter_type_id {
-                ObjectId::DataChangeFilter_Encoding_DefaultBinary => {
+        } else {
+            match filter_type_id.as_object_id() {
+                Ok(ObjectId::DataChangeFilter_Encoding_DefaultBinary) => {
                     let decoding_limits = DecodingLimits::minimal();
                     Ok(FilterType::DataChangeFilter(filter.decode_inner::<DataChangeFilter>(&decoding_limits)?))
                 }
-                ObjectId::EventFilter_Encoding_DefaultBinary => {
+                Ok(ObjectId::EventFilter_Encoding_DefaultBinary) => {
                     let decoding_limits = DecodingLimits::default();
                     Ok(FilterType::EventFilter(filter.decode_inner::<EventFilter>(&decoding_limits)?))
                 }
-                _ => {
-                    error!("Requested data filter type is not supported, {:?}", filter_type_id);
+                _ => Self::from_registered_filter(filter, namespaces)
+            }
+        }
+    }
+
+    /// Decodes a filter whose encoding id is in the type registry, resolving its namespace through
+    /// the server's namespace table. The registered type must be one of the supported filters.
+    fn from_registered_filter(filter: &ExtensionObject, namespaces: &NamespaceMap) -> Result<FilterType, StatusCode> {
+        let decoding_limits = DecodingLimits::default();
+        match filter.decode_registered(namespaces, &decoding_limits) {
+            Some(Ok(value)) => {
+                if let Some(data_change_filter) = value.downcast_ref::<DataChangeFilter>() {
+                    Ok(FilterType::DataChangeFilter(data_change_filter.clone()))
+                } else if let Some(event_filter) = value.downcast_ref::<EventFilter>() {
+                    Ok(FilterType::EventFilter(event_filter.clone()))
+                } else {
+                    error!("Requested data filter type is registered but not supported, {:?}", filter.node_id);
                     Err(StatusCode::BadFilterNotAllowed)
                 }
             }
-        } else {
-            error!("Requested data filter type is not an object id, {:?}", filter_type_id);
-            Err(StatusCode::BadFilterNotAllowed)
+            Some(Err(err)) => Err(err),
+            None => {
+                error!("Requested data filter type is not supported, {:?}", filter.node_id);
+                Err(StatusCode::BadFilterNotAllowed)
+            }
         }
     }
 }
@@ -112,8 +129,8 @@ pub(crate) enum TickResult {
 }
 
 impl MonitoredItem {
-    pub fn new(now: &DateTimeUtc, monitored_item_id: u32, timestamps_to_return: TimestampsToReturn, request: &MonitoredItemCreateRequest) -> Result<MonitoredItem, StatusCode> {
-        let filter = FilterType::from_filter(&request.requested_parameters.filter)?;
+    pub fn new(now: &DateTimeUtc, monitored_item_id: u32, timestamps_to_return: TimestampsToReturn, namespaces: &NamespaceMap, request: &MonitoredItemCreateRequest) -> Result<MonitoredItem, StatusCode> {
+        let filter = FilterType::from_filter(&request.requested_parameters.filter, namespaces)?;
         let sampling_interval = Self::sanitize_sampling_interval(request.requested_parameters.sampling_interval);
         let queue_size = Self::sanitize_queue_size(request.requested_parameters.queue_size as usize);
         Ok(MonitoredItem {
@@ -138,7 +155,7 @@ impl MonitoredItem {
     /// holds the filter result.
     pub fn modify(&mut self, address_space: &AddressSpace, timestamps_to_return: TimestampsToReturn, request: &MonitoredItemModifyRequest) -> Result<ExtensionObject, StatusCode> {
         self.timestamps_to_return = timestamps_to_return;
-        self.filter = FilterType::from_filter(&request.requested_parameters.filter)?;
+        self.filter = FilterType::from_filter(&request.requested_parameters.filter, address_space.namespace_map())?;
         self.sampling_interval = Self::sanitize_sampling_interval(request.requested_parameters.sampling_interval);
         self.queue_size = Self::sanitize_queue_size(request.requested_parameters.queue_size as usize);
         self.client_handle = request.requested_parameters.client_handle;
@@ -266,8 +283,7 @@ impl MonitoredItem {
         }
     }
 
-    fn check_for_data_change(&mut self, _address_space: &AddressSpace, resend_data: bool, attribute_id: AttributeId, node: &dyn Node) -> bool {
-        let data_value = node.get_attribute(TimestampsToReturn::Neither, attribute_id, NumericRange::None, &QualifiedName::null());
+    fn check_for_data_change(&mut self, _address_space: &AddressSpace, resend_data: bool, data_value: Option<DataValue>) -> bool {
         if let Some(mut data_value) = data_value {
             // Test for data change
             let data_change = if resend_data {
@@ -353,7 +369,21 @@ impl MonitoredItem {
         if self.monitoring_mode == MonitoringMode::Disabled {
             panic!("Should not check value while monitoring mode is disabled");
         }
-        let changed = if let Some(node) = address_space.find_node(&self.item_to_monitor.node_id) {
+        let changed = if let Some(node_manager) = address_space.node_manager(&self.item_to_monitor.node_id) {
+            // Managed nodes are sampled by reading from their node manager. Events are not
+            // supported on managed nodes.
+            match AttributeId::from_u32(self.item_to_monitor.attribute_id) {
+                Ok(attribute_id) if !self.is_event_filter() => {
+                    let node_id = &self.item_to_monitor.node_id;
+                    let data_value = self.item_to_monitor.index_range.as_ref().parse::<NumericRange>()
+                        .map_err(|_| StatusCode::BadIndexRangeInvalid)
+                        .and_then(|index_range| node_manager.read(node_id, TimestampsToReturn::Neither, attribute_id, index_range, &QualifiedName::null(), 0f64))
+                        .ok();
+                    self.check_for_data_change(address_space, resend_data, data_value)
+                }
+                _ => false
+            }
+        } else if let Some(node) = address_space.find_node(&self.item_to_monitor.node_id) {
             match AttributeId::from_u32(self.item_to_monitor.attribute_id) {
                 Ok(attribute_id) => {
                     let node = node.as_node();
@@ -368,7 +398,8 @@ impl MonitoredItem {
                             }
                         }
                         _ => {
-                            self.check_for_data_change(address_space, resend_data, attribute_id, node)
+                            let data_value = node.get_attribute(TimestampsToReturn::Neither, attribute_id, NumericRange::None, &QualifiedName::null());
+                            self.check_for_data_change(address_space, resend_data, data_value)
                         }
                     }
                 }
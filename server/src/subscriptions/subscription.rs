@@ -223,7 +223,7 @@ impl Subscription {
 
         // Add items to the subscription if they're not already in its
         items_to_create.iter().map(|item_to_create| {
-            if !address_space.node_exists(&item_to_create.item_to_monitor.node_id) {
+            if !address_space.is_known_node(&item_to_create.item_to_monitor.node_id) {
                 Self::monitored_item_create_error(StatusCode::BadNodeIdUnknown)
             } else {
 
@@ -232,7 +232,7 @@ impl Subscription {
 
                 // Create a monitored item, if possible
                 let monitored_item_id = self.next_monitored_item_id;
-                match MonitoredItem::new(now, monitored_item_id, timestamps_to_return, item_to_create) {
+                match MonitoredItem::new(now, monitored_item_id, timestamps_to_return, address_space.namespace_map(), item_to_create) {
                     Ok(monitored_item) => {
                         if max_monitored_items_per_sub == 0 || self.monitored_items.len() <= max_monitored_items_per_sub {
                             let revised_sampling_interval = monitored_item.sampling_interval();
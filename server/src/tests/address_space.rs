@@ -670,4 +670,132 @@ fn hierarchical_references() {
     assert!(refs.contains(&ObjectId::Server_ServerCapabilities_ModellingRules.into()));
     assert!(refs.contains(&ObjectId::Server_ServerCapabilities_AggregateFunctions.into()));
     assert!(refs.contains(&ObjectId::HistoryServerCapabilities.into()));
-}
\ No newline at end of file
+}
+fn add_sample_data_types(address_space: &mut AddressSpace, ns: u16) {
+    let color = EnumDefinition {
+        fields: Some(vec![
+            EnumField { value: 0, display_name: LocalizedText::new("", "Red"), description: LocalizedText::null(), name: UAString::from("Red") },
+            EnumField { value: 1, display_name: LocalizedText::new("", "Green"), description: LocalizedText::null(), name: UAString::from("Green") },
+        ])
+    };
+    assert!(address_space.add_enum_data_type(&NodeId::new(ns, 3001), "Color", "Color", color));
+
+    let field = |name: &str, data_type: NodeId, value_rank: i32, is_optional: bool| StructureField {
+        name: UAString::from(name),
+        description: LocalizedText::null(),
+        data_type,
+        value_rank,
+        array_dimensions: None,
+        max_string_length: 0,
+        is_optional,
+    };
+    let point = StructureDefinition {
+        default_encoding_id: NodeId::new(ns, 5001),
+        base_data_type: NodeId::null(),
+        structure_type: StructureType::StructureWithOptionalFields,
+        fields: Some(vec![
+            field("X", DataTypeId::Double.into(), -1, false),
+            field("Y", DataTypeId::Double.into(), -1, false),
+            field("Tags", DataTypeId::String.into(), 1, false),
+            field("Color", NodeId::new(ns, 3001), -1, true),
+            field("Unit", DataTypeId::EUInformation.into(), -1, true),
+        ]),
+    };
+    assert!(address_space.add_structure_data_type(&NodeId::new(ns, 3002), "Point", "Point", point));
+}
+
+#[test]
+fn add_structure_data_type() {
+    let mut address_space = AddressSpace::new();
+    let ns = address_space.register_namespace("urn:test").unwrap();
+    add_sample_data_types(&mut address_space, ns);
+
+    let point_id = NodeId::new(ns, 3002);
+    let encoding_id = NodeId::new(ns, 5001);
+    assert!(address_space.is_subtype(&point_id, &DataTypeId::Structure.into()));
+    assert!(address_space.has_reference(&point_id, &encoding_id, ReferenceTypeId::HasEncoding));
+    assert!(address_space.has_reference(&encoding_id, &ObjectTypeId::DataTypeEncodingType.into(), ReferenceTypeId::HasTypeDefinition));
+    assert_eq!(address_space.find_node(&encoding_id).unwrap().as_node().browse_name(), QualifiedName::from("Default Binary"));
+
+    // The definition is the value of the DataTypeDefinition attribute
+    let value = address_space.find_node(&point_id).unwrap().as_node()
+        .get_attribute(TimestampsToReturn::Neither, AttributeId::DataTypeDefinition, NumericRange::None, &QualifiedName::null())
+        .unwrap().value.unwrap();
+    let definition = if let Variant::ExtensionObject(value) = value {
+        DataTypeDefinition::from_extension_object(&value, &DecodingLimits::default()).unwrap()
+    } else {
+        panic!("Expected an extension object");
+    };
+    let definition = definition.as_structure().unwrap();
+    assert_eq!(definition.base_data_type, DataTypeId::Structure.into());
+    assert_eq!(definition.default_encoding_id, encoding_id);
+    assert_eq!(definition.fields.as_ref().unwrap().len(), 5);
+
+    // The data type dictionary describes the structure and the encoding points at its description
+    let dictionary_id = NodeId::new(ns, "BinarySchema");
+    assert!(address_space.has_reference(&ObjectId::OPCBinarySchema_TypeSystem.into(), &dictionary_id, ReferenceTypeId::HasComponent));
+    let description_id = NodeId::new(ns, "BinarySchema.Point");
+    assert!(address_space.has_reference(&dictionary_id, &description_id, ReferenceTypeId::HasComponent));
+    assert!(address_space.has_reference(&encoding_id, &description_id, ReferenceTypeId::HasDescription));
+    let dictionary = if let Some(Variant::ByteString(value)) = address_space.get_variable_value(dictionary_id).unwrap().value {
+        String::from_utf8(value.value.unwrap()).unwrap()
+    } else {
+        panic!("Expected a byte string");
+    };
+    assert!(dictionary.contains(r#"TargetNamespace="urn:test""#));
+    assert!(dictionary.contains(r#"<opc:StructuredType Name="Point" BaseType="ua:ExtensionObject">"#));
+    assert!(dictionary.contains(r#"<opc:Field TypeName="opc:Bit" Name="ColorSpecified" />"#));
+    assert!(dictionary.contains(r#"<opc:Field Name="Reserved1" TypeName="opc:Bit" Length="30" />"#));
+    assert!(dictionary.contains(r#"<opc:Field Name="X" TypeName="opc:Double" />"#));
+    assert!(dictionary.contains(r#"<opc:Field Name="NoOfTags" TypeName="opc:Int32" />"#));
+    assert!(dictionary.contains(r#"<opc:Field Name="Tags" TypeName="opc:String" LengthField="NoOfTags" />"#));
+    assert!(dictionary.contains(r#"<opc:Field Name="Color" TypeName="tns:Color" SwitchField="ColorSpecified" />"#));
+    assert!(dictionary.contains(r#"<opc:Field Name="Unit" TypeName="ua:EUInformation" SwitchField="UnitSpecified" />"#));
+
+    // Existing ids are rejected
+    let definition = definition.clone();
+    assert!(!address_space.add_structure_data_type(&point_id, "Point", "Point", definition.clone()));
+    assert!(!address_space.add_structure_data_type(&NodeId::new(ns, 3003), "Point2", "Point2", definition));
+}
+
+#[test]
+fn add_enum_data_type() {
+    let mut address_space = AddressSpace::new();
+    let ns = address_space.register_namespace("urn:test").unwrap();
+    add_sample_data_types(&mut address_space, ns);
+
+    let color_id = NodeId::new(ns, 3001);
+    assert!(address_space.is_subtype(&color_id, &DataTypeId::Enumeration.into()));
+    if let NodeType::DataType(data_type) = address_space.find_node(&color_id).unwrap() {
+        assert_eq!(data_type.data_type_definition().unwrap().as_enum().unwrap().fields.as_ref().unwrap().len(), 2);
+    } else {
+        panic!("Expected a data type");
+    }
+
+    // Enum values are a property of the data type
+    let enum_values = address_space.find_aggregates_of(&color_id).unwrap();
+    assert_eq!(enum_values.len(), 1);
+    let enum_values = address_space.find_variable_by_ref(&enum_values[0]).unwrap();
+    assert_eq!(enum_values.browse_name(), QualifiedName::from("EnumValues"));
+    if let Some(Variant::Array(values)) = enum_values.value(TimestampsToReturn::Neither, NumericRange::None, &QualifiedName::null(), 0.0).value {
+        assert_eq!(values.values.len(), 2);
+        if let Variant::ExtensionObject(ref value) = values.values[1] {
+            let value = value.decode_inner::<EnumValueType>(&DecodingLimits::default()).unwrap();
+            assert_eq!(value.value, 1);
+            assert_eq!(value.display_name.text, UAString::from("Green"));
+        } else {
+            panic!("Expected an extension object");
+        }
+    } else {
+        panic!("Expected an array");
+    }
+
+    let dictionary = address_space.get_variable_value(NodeId::new(ns, "BinarySchema")).unwrap().value.unwrap();
+    if let Variant::ByteString(value) = dictionary {
+        let dictionary = String::from_utf8(value.value.unwrap()).unwrap();
+        assert!(dictionary.contains(r#"<opc:EnumeratedType Name="Color" LengthInBits="32">"#));
+        assert!(dictionary.contains(r#"<opc:EnumeratedValue Name="Green" Value="1" />"#));
+    } else {
+        panic!("Expected a byte string");
+    }
+}
@@ -23,11 +23,12 @@ use crate::{
     },
     session::*,
     subscriptions::*,
-    config::ServerConfig,
+    config::{ReverseConnectConfig, ServerConfig},
     builder::ServerBuilder,
 };
 
 mod address_space;
+mod nodeset;
 mod services;
 mod subscriptions;
 mod events;
@@ -86,6 +87,66 @@ pub fn server_config_save() {
     }
 }
 
+#[test]
+pub fn server_config_https() {
+    let path = make_test_file("server_config_https.yaml");
+    let mut config = ServerBuilder::new_anonymous("foo").https("localhost", 8443).config();
+    assert!(config.is_valid());
+    assert_eq!(config.base_https_endpoint_url().unwrap(), "https://localhost:8443");
+    let endpoint = config.endpoints.get("none").unwrap().clone();
+    assert!(config.endpoint_matches_url(&endpoint, "https://localhost:8443/"));
+    assert!(config.endpoint_matches_url(&endpoint, &format!("opc.tcp://localhost:{}/", config.tcp_config.port)));
+    assert!(!config.endpoint_matches_url(&endpoint, "https://localhost:9443/"));
+
+    assert!(config.save(&path).is_ok());
+    let config2: ServerConfig = ServerConfig::load(&path).unwrap();
+    assert_eq!(config2, config);
+
+    // A certificate needs its private key
+    config.https_config.as_mut().unwrap().certificate_path = Some(PathBuf::from("tls/cert.der"));
+    assert_eq!(config.is_valid(), false);
+}
+
+#[test]
+pub fn server_config_wss() {
+    let path = make_test_file("server_config_wss.yaml");
+    let mut config = ServerBuilder::new_anonymous("foo").wss("localhost", 8443).config();
+    assert!(config.is_valid());
+    assert_eq!(config.base_wss_endpoint_url().unwrap(), "opc.wss://localhost:8443");
+    let endpoint = config.endpoints.get("none").unwrap().clone();
+    assert!(config.endpoint_matches_url(&endpoint, "opc.wss://localhost:8443/"));
+    assert!(!config.endpoint_matches_url(&endpoint, "https://localhost:8443/"));
+
+    assert!(config.save(&path).is_ok());
+    let config2: ServerConfig = ServerConfig::load(&path).unwrap();
+    assert_eq!(config2, config);
+
+    // A private key needs its certificate
+    config.wss_config.as_mut().unwrap().private_key_path = Some(PathBuf::from("tls/key.pem"));
+    assert_eq!(config.is_valid(), false);
+}
+
+#[test]
+pub fn server_config_reverse_connect() {
+    let path = make_test_file("server_config_reverse_connect.yaml");
+    let mut config = ServerBuilder::new_anonymous("foo")
+        .reverse_connect("opc.tcp://client1:4841")
+        .reverse_connect("opc.tcp://client2:4841")
+        .config();
+    assert!(config.is_valid());
+    let reverse_connect_config = config.reverse_connect_config.as_ref().unwrap();
+    assert_eq!(reverse_connect_config.client_urls, vec!["opc.tcp://client1:4841", "opc.tcp://client2:4841"]);
+    assert_eq!(reverse_connect_config.retry_interval, ReverseConnectConfig::DEFAULT_RETRY_INTERVAL);
+
+    assert!(config.save(&path).is_ok());
+    let config2: ServerConfig = ServerConfig::load(&path).unwrap();
+    assert_eq!(config2, config);
+
+    // Clients are reached over opc.tcp
+    config.reverse_connect_config.as_mut().unwrap().client_urls.push("https://client3:443".to_string());
+    assert_eq!(config.is_valid(), false);
+}
+
 #[test]
 pub fn server_config_invalid() {
     // Remove the endpoint
@@ -0,0 +1,488 @@
+use std::fs;
+
+use crate::{
+    address_space::{
+        AccessLevel,
+        references::Reference,
+    },
+    prelude::*,
+    tests::*,
+};
+
+const VENDOR_NODESET: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<UANodeSet xmlns="http://opcfoundation.org/UA/2011/03/UANodeSet.xsd" xmlns:uax="http://opcfoundation.org/UA/2008/02/Types.xsd">
+  <NamespaceUris>
+    <Uri>urn:vendor:model</Uri>
+  </NamespaceUris>
+  <Models>
+    <Model ModelUri="urn:vendor:model" Version="1.0.0">
+      <RequiredModel ModelUri="http://opcfoundation.org/UA/" Version="1.04" />
+    </Model>
+  </Models>
+  <Aliases>
+    <Alias Alias="Int32">i=6</Alias>
+    <Alias Alias="String">i=12</Alias>
+    <Alias Alias="Range">i=884</Alias>
+    <Alias Alias="Argument">i=296</Alias>
+    <Alias Alias="HasComponent">i=47</Alias>
+    <Alias Alias="HasProperty">i=46</Alias>
+    <Alias Alias="HasSubtype">i=45</Alias>
+    <Alias Alias="HasTypeDefinition">i=40</Alias>
+    <Alias Alias="Organizes">i=35</Alias>
+  </Aliases>
+  <UAReferenceType NodeId="ns=1;i=4001" BrowseName="1:FeedsInto">
+    <DisplayName>FeedsInto</DisplayName>
+    <InverseName>FedFrom</InverseName>
+    <References>
+      <Reference ReferenceType="HasSubtype" IsForward="false">i=32</Reference>
+    </References>
+  </UAReferenceType>
+  <UADataType NodeId="ns=1;i=3001" BrowseName="1:PumpMode">
+    <DisplayName>PumpMode</DisplayName>
+    <References>
+      <Reference ReferenceType="HasSubtype" IsForward="false">i=29</Reference>
+    </References>
+  </UADataType>
+  <UAObjectType NodeId="ns=1;i=1001" BrowseName="1:PumpType">
+    <DisplayName>PumpType</DisplayName>
+    <Description>A pump</Description>
+    <References>
+      <Reference ReferenceType="HasSubtype" IsForward="false">i=58</Reference>
+    </References>
+  </UAObjectType>
+  <UAObject NodeId="ns=1;i=5001" BrowseName="1:Pump1" EventNotifier="1">
+    <DisplayName>Pump 1</DisplayName>
+    <References>
+      <Reference ReferenceType="Organizes" IsForward="false">i=85</Reference>
+      <Reference ReferenceType="HasTypeDefinition">ns=1;i=1001</Reference>
+      <Reference ReferenceType="HasComponent">ns=1;s=Pump1.Speed</Reference>
+      <Reference ReferenceType="ns=1;i=4001">ns=1;i=5002</Reference>
+    </References>
+  </UAObject>
+  <UAObject NodeId="ns=1;i=5002" BrowseName="1:Tank1">
+    <DisplayName>Tank 1</DisplayName>
+  </UAObject>
+  <UAVariable NodeId="ns=1;s=Pump1.Speed" BrowseName="1:Speed" DataType="Int32" AccessLevel="3">
+    <DisplayName>Speed</DisplayName>
+    <References>
+      <Reference ReferenceType="HasTypeDefinition">i=63</Reference>
+    </References>
+    <Value>
+      <uax:Int32>1500</uax:Int32>
+    </Value>
+  </UAVariable>
+  <UAVariable NodeId="ns=1;i=6001" BrowseName="1:Modes" DataType="String" ValueRank="1" ArrayDimensions="2">
+    <DisplayName>Modes</DisplayName>
+    <Value>
+      <uax:ListOfString>
+        <uax:String>Auto</uax:String>
+        <uax:String>Manual</uax:String>
+      </uax:ListOfString>
+    </Value>
+  </UAVariable>
+  <UAVariable NodeId="ns=1;i=6002" BrowseName="EURange" DataType="Range">
+    <DisplayName>EURange</DisplayName>
+    <Value>
+      <uax:ExtensionObject>
+        <uax:TypeId><uax:Identifier>i=885</uax:Identifier></uax:TypeId>
+        <uax:Body>
+          <uax:Range><uax:Low>0</uax:Low><uax:High>3000</uax:High></uax:Range>
+        </uax:Body>
+      </uax:ExtensionObject>
+    </Value>
+  </UAVariable>
+  <UAVariable NodeId="ns=1;i=6003" BrowseName="1:Settings" DataType="i=22">
+    <DisplayName>Settings</DisplayName>
+    <Value>
+      <uax:ExtensionObject>
+        <uax:TypeId><uax:Identifier>ns=1;i=5005</uax:Identifier></uax:TypeId>
+        <uax:Body><PumpSettings xmlns="urn:vendor:model"><Gain>2</Gain></PumpSettings></uax:Body>
+      </uax:ExtensionObject>
+    </Value>
+  </UAVariable>
+  <UAVariable NodeId="ns=1;i=6004" BrowseName="1:Owner" DataType="i=20">
+    <DisplayName>Owner</DisplayName>
+    <Value>
+      <uax:QualifiedName><uax:NamespaceIndex>1</uax:NamespaceIndex><uax:Name>Plant</uax:Name></uax:QualifiedName>
+    </Value>
+  </UAVariable>
+  <UAMethod NodeId="ns=1;i=7001" BrowseName="1:Start" ParentNodeId="ns=1;i=5001">
+    <DisplayName>Start</DisplayName>
+    <References>
+      <Reference ReferenceType="HasComponent" IsForward="false">ns=1;i=5001</Reference>
+      <Reference ReferenceType="HasProperty">ns=1;i=7002</Reference>
+    </References>
+  </UAMethod>
+  <UAVariable NodeId="ns=1;i=7002" BrowseName="InputArguments" DataType="Argument" ValueRank="1" ArrayDimensions="1">
+    <DisplayName>InputArguments</DisplayName>
+    <Value>
+      <uax:ListOfExtensionObject>
+        <uax:ExtensionObject>
+          <uax:TypeId><uax:Identifier>i=297</uax:Identifier></uax:TypeId>
+          <uax:Body>
+            <uax:Argument>
+              <uax:Name>Speed</uax:Name>
+              <uax:DataType><uax:Identifier>i=6</uax:Identifier></uax:DataType>
+              <uax:ValueRank>-1</uax:ValueRank>
+              <uax:ArrayDimensions />
+              <uax:Description p5:nil="true" xmlns:p5="http://www.w3.org/2001/XMLSchema-instance" />
+            </uax:Argument>
+          </uax:Body>
+        </uax:ExtensionObject>
+      </uax:ListOfExtensionObject>
+    </Value>
+  </UAVariable>
+</UANodeSet>"#;
+
+const DEPENDENT_NODESET: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<UANodeSet xmlns="http://opcfoundation.org/UA/2011/03/UANodeSet.xsd">
+  <NamespaceUris>
+    <Uri>urn:vendor:machine</Uri>
+    <Uri>urn:vendor:model</Uri>
+  </NamespaceUris>
+  <Models>
+    <Model ModelUri="urn:vendor:machine">
+      <RequiredModel ModelUri="urn:vendor:model" />
+    </Model>
+  </Models>
+  <UAObject NodeId="ns=1;i=1" BrowseName="1:Machine">
+    <DisplayName>Machine</DisplayName>
+    <References>
+      <Reference ReferenceType="i=40">ns=2;i=1001</Reference>
+    </References>
+  </UAObject>
+</UANodeSet>"#;
+
+fn value_of(address_space: &AddressSpace, node_id: &NodeId) -> Variant {
+    address_space.get_variable_value(node_id.clone()).unwrap().value.unwrap()
+}
+
+#[test]
+fn import_nodeset() {
+    let mut address_space = AddressSpace::new();
+    let default_ns = address_space.register_namespace("urn:test").unwrap();
+
+    let inserted = address_space.import_nodeset_str(VENDOR_NODESET).unwrap();
+    assert_eq!(inserted, 12);
+
+    // Namespace index 1 in the nodeset is remapped to the next index in the address space
+    let ns = address_space.namespace_index("urn:vendor:model").unwrap();
+    assert_eq!(ns, default_ns + 1);
+    assert_eq!(address_space.default_namespace(), default_ns);
+
+    // Object with its type definition and the reference from the objects folder
+    let pump_id = NodeId::new(ns, 5001);
+    let pump = address_space.find_node(&pump_id).unwrap().as_node();
+    assert_eq!(pump.node_class(), NodeClass::Object);
+    assert_eq!(pump.browse_name(), QualifiedName::new(ns, "Pump1"));
+    assert_eq!(pump.display_name(), LocalizedText::new("", "Pump 1"));
+    assert!(address_space.has_reference(&pump_id, &NodeId::new(ns, 1001), ReferenceTypeId::HasTypeDefinition));
+    assert!(address_space.has_reference(&ObjectId::ObjectsFolder.into(), &pump_id, ReferenceTypeId::Organizes));
+    assert!(address_space.has_reference(&pump_id, &NodeId::new(ns, "Pump1.Speed"), ReferenceTypeId::HasComponent));
+    assert!(address_space.has_reference(&pump_id, &NodeId::new(ns, 5002), NodeId::new(ns, 4001)));
+
+    // Types
+    let pump_type_id = NodeId::new(ns, 1001);
+    if let NodeType::ObjectType(pump_type) = address_space.find_node(&pump_type_id).unwrap() {
+        assert_eq!(pump_type.description(), Some(LocalizedText::new("", "A pump")));
+    } else {
+        panic!("Expected an object type");
+    }
+    assert!(address_space.is_subtype(&pump_type_id, &ObjectTypeId::BaseObjectType.into()));
+    assert_eq!(address_space.find_node(&NodeId::new(ns, 3001)).unwrap().node_class(), NodeClass::DataType);
+    if let NodeType::ReferenceType(reference_type) = address_space.find_node(&NodeId::new(ns, 4001)).unwrap() {
+        assert_eq!(reference_type.inverse_name(), Some(LocalizedText::new("", "FedFrom")));
+    } else {
+        panic!("Expected a reference type");
+    }
+
+    // Variables and their values
+    let speed = address_space.find_variable(NodeId::new(ns, "Pump1.Speed")).unwrap();
+    assert_eq!(speed.data_type(), DataTypeId::Int32.into());
+    assert_eq!(speed.access_level(), AccessLevel::CURRENT_READ | AccessLevel::CURRENT_WRITE);
+    assert_eq!(value_of(&address_space, &NodeId::new(ns, "Pump1.Speed")), Variant::Int32(1500));
+
+    let modes = address_space.find_variable(NodeId::new(ns, 6001)).unwrap();
+    assert_eq!(modes.value_rank(), 1);
+    assert_eq!(modes.array_dimensions(), Some(vec![2]));
+    assert_eq!(value_of(&address_space, &NodeId::new(ns, 6001)), Variant::from(vec![Variant::from("Auto"), Variant::from("Manual")]));
+
+    assert_eq!(value_of(&address_space, &NodeId::new(ns, 6004)), Variant::from(QualifiedName::new(ns, "Plant")));
+
+    // Known extension objects are converted to their binary encoding
+    if let Variant::ExtensionObject(range) = value_of(&address_space, &NodeId::new(ns, 6002)) {
+        assert_eq!(range.node_id, ObjectId::Range_Encoding_DefaultBinary.into());
+        let range = range.decode_inner::<Range>(&DecodingLimits::default()).unwrap();
+        assert_eq!(range, Range { low: 0.0, high: 3000.0 });
+    } else {
+        panic!("Expected an extension object");
+    }
+
+    // Unknown extension objects are preserved as XML
+    if let Variant::ExtensionObject(settings) = value_of(&address_space, &NodeId::new(ns, 6003)) {
+        assert_eq!(settings.node_id, NodeId::new(ns, 5005));
+        assert_eq!(settings.body, ExtensionObjectEncoding::XmlElement(XmlElement::from(r#"<PumpSettings xmlns="urn:vendor:model"><Gain>2</Gain></PumpSettings>"#)));
+    } else {
+        panic!("Expected an extension object");
+    }
+
+    // Method and its arguments
+    let method_id = NodeId::new(ns, 7001);
+    assert_eq!(address_space.find_node(&method_id).unwrap().node_class(), NodeClass::Method);
+    assert!(address_space.has_reference(&pump_id, &method_id, ReferenceTypeId::HasComponent));
+    if let Variant::Array(array) = value_of(&address_space, &NodeId::new(ns, 7002)) {
+        assert_eq!(array.values.len(), 1);
+        if let Variant::ExtensionObject(ref argument) = array.values[0] {
+            let argument = argument.decode_inner::<opcua_types::service_types::Argument>(&DecodingLimits::default()).unwrap();
+            assert_eq!(argument.name, UAString::from("Speed"));
+            assert_eq!(argument.data_type, DataTypeId::Int32.into());
+            assert_eq!(argument.value_rank, -1);
+        } else {
+            panic!("Expected an extension object");
+        }
+    } else {
+        panic!("Expected an array");
+    }
+}
+
+#[test]
+fn import_nodeset_twice() {
+    let mut address_space = AddressSpace::new();
+    assert_eq!(address_space.import_nodeset_str(VENDOR_NODESET).unwrap(), 12);
+    // Nodes that already exist are not inserted again
+    assert_eq!(address_space.import_nodeset_str(VENDOR_NODESET).unwrap(), 0);
+}
+
+#[test]
+fn import_nodeset_invalid() {
+    let mut address_space = AddressSpace::new();
+    // Not XML
+    assert!(address_space.import_nodeset_str("UANodeSet").is_err());
+    // Not a nodeset
+    assert!(address_space.import_nodeset_str("<Foo/>").is_err());
+    // Namespace index that is not declared
+    let xml = r#"<UANodeSet><UAObject NodeId="ns=3;i=1" BrowseName="3:X"><DisplayName>X</DisplayName></UAObject></UANodeSet>"#;
+    assert!(address_space.import_nodeset_str(xml).is_err());
+    // Unsupported value
+    let xml = r#"<UANodeSet><UAVariable NodeId="i=50000" BrowseName="X"><DisplayName>X</DisplayName><Value><Matrix/></Value></UAVariable></UANodeSet>"#;
+    assert!(address_space.import_nodeset_str(xml).is_err());
+}
+
+#[test]
+fn import_nodeset_malformed_late() {
+    let mut address_space = AddressSpace::new();
+    let namespace_count = address_space.namespaces().len();
+    let vendor_ns = namespace_count as u16;
+
+    // The last node has a value that cannot be read, so nothing in the document is imported
+    let bad_node = r#"<UAVariable NodeId="ns=1;i=6099" BrowseName="1:Bad"><DisplayName>Bad</DisplayName><Value><Matrix/></Value></UAVariable>
+</UANodeSet>"#;
+    let xml = VENDOR_NODESET.replace("</UANodeSet>", bad_node);
+    assert!(address_space.import_nodeset_str(&xml).is_err());
+    assert_eq!(address_space.namespaces().len(), namespace_count);
+    assert!(address_space.namespace_index("urn:vendor:model").is_none());
+    assert!(address_space.find_node(&NodeId::new(vendor_ns, 5001)).is_none());
+    assert!(address_space.find_node(&NodeId::new(vendor_ns, 1001)).is_none());
+
+    // The valid document imports into the same indexes afterwards
+    assert_eq!(address_space.import_nodeset_str(VENDOR_NODESET).unwrap(), 12);
+    assert_eq!(address_space.namespace_index("urn:vendor:model"), Some(vendor_ns));
+    assert!(address_space.find_node(&NodeId::new(vendor_ns, 5001)).is_some());
+}
+
+#[test]
+fn import_nodeset_dir() {
+    let dir = make_test_file("opcua-server-nodesets");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    // The dependent nodeset sorts first by name but must be imported second
+    fs::write(dir.join("a_machine.xml"), DEPENDENT_NODESET).unwrap();
+    fs::write(dir.join("b_model.xml"), VENDOR_NODESET).unwrap();
+    fs::write(dir.join("readme.txt"), "Not a nodeset").unwrap();
+
+    let mut address_space = AddressSpace::new();
+    let inserted = address_space.import_nodeset_dir(&dir).unwrap();
+    assert_eq!(inserted, 13);
+
+    let model_ns = address_space.namespace_index("urn:vendor:model").unwrap();
+    let machine_ns = address_space.namespace_index("urn:vendor:machine").unwrap();
+    assert!(model_ns < machine_ns);
+
+    let machine_id = NodeId::new(machine_ns, 1);
+    let references = address_space.find_references(&machine_id, Some((ReferenceTypeId::HasTypeDefinition, false))).unwrap();
+    assert_eq!(references, vec![Reference::new(ReferenceTypeId::HasTypeDefinition, NodeId::new(model_ns, 1001))]);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn export_nodeset() {
+    let mut address_space = AddressSpace::new();
+    address_space.register_namespace("urn:test").unwrap();
+    address_space.import_nodeset_str(VENDOR_NODESET).unwrap();
+    let ns = address_space.namespace_index("urn:vendor:model").unwrap();
+
+    // Values that need escaping
+    let escaped_id = NodeId::new(ns, "Escaped");
+    let _ = address_space.add_variables(vec![Variable::new(&escaped_id, "Escaped", "Escaped", "<Tom & \"Jerry\">")], &NodeId::new(ns, 5002));
+
+    let xml = address_space.export_nodeset(Some(ns)).unwrap();
+    // The exported namespace becomes namespace 1 and standard types are aliased by browse name
+    assert!(xml.contains("<Uri>urn:vendor:model</Uri>"));
+    assert!(!xml.contains("<Uri>urn:test</Uri>"));
+    assert!(xml.contains(r#"<Model ModelUri="urn:vendor:model">"#));
+    assert!(xml.contains(r#"<Alias Alias="HasComponent">i=47</Alias>"#));
+    assert!(xml.contains(r#"<UAObject NodeId="ns=1;i=5001" BrowseName="1:Pump1" EventNotifier="1">"#));
+    assert!(xml.contains(r#"<Reference ReferenceType="Organizes" IsForward="false">i=85</Reference>"#));
+    assert!(xml.contains("&lt;Tom &amp; &quot;Jerry&quot;&gt;"));
+
+    // Importing the export into another address space recreates the nodes
+    let mut imported = AddressSpace::new();
+    imported.register_namespace("urn:other").unwrap();
+    imported.register_namespace("urn:another").unwrap();
+    assert_eq!(imported.import_nodeset_str(&xml).unwrap(), 13);
+    let imported_ns = imported.namespace_index("urn:vendor:model").unwrap();
+    assert_ne!(imported_ns, ns);
+
+    let pump_id = NodeId::new(imported_ns, 5001);
+    let pump = imported.find_node(&pump_id).unwrap().as_node();
+    assert_eq!(pump.browse_name(), QualifiedName::new(imported_ns, "Pump1"));
+    assert_eq!(pump.display_name(), LocalizedText::new("", "Pump 1"));
+    assert!(imported.has_reference(&ObjectId::ObjectsFolder.into(), &pump_id, ReferenceTypeId::Organizes));
+    assert!(imported.has_reference(&pump_id, &NodeId::new(imported_ns, 5002), NodeId::new(imported_ns, 4001)));
+    assert!(imported.is_subtype(&NodeId::new(imported_ns, 1001), &ObjectTypeId::BaseObjectType.into()));
+
+    // Values are the same, after remapping namespaces
+    for id in &[NodeId::new(ns, "Pump1.Speed"), NodeId::new(ns, 6001), NodeId::new(ns, 6002), NodeId::new(ns, 7002), escaped_id.clone()] {
+        let imported_id = NodeId::new(imported_ns, id.identifier.clone());
+        assert_eq!(value_of(&imported, &imported_id), value_of(&address_space, id));
+    }
+    assert_eq!(value_of(&imported, &NodeId::new(imported_ns, 6004)), Variant::from(QualifiedName::new(imported_ns, "Plant")));
+    if let Variant::ExtensionObject(settings) = value_of(&imported, &NodeId::new(imported_ns, 6003)) {
+        assert_eq!(settings.node_id, NodeId::new(imported_ns, 5005));
+    } else {
+        panic!("Expected an extension object");
+    }
+
+    // Exporting again gives the same nodes
+    let xml2 = imported.export_nodeset(Some(imported_ns)).unwrap();
+    let strip_header = |xml: &str| xml.lines().skip(2).collect::<Vec<_>>().join("\n");
+    assert_eq!(strip_header(&xml), strip_header(&xml2));
+}
+
+#[test]
+fn export_nodeset_all() {
+    let mut address_space = AddressSpace::new();
+    address_space.import_nodeset_str(VENDOR_NODESET).unwrap();
+
+    let xml = address_space.export_nodeset(None).unwrap();
+    assert!(xml.contains(r#"<Model ModelUri="http://opcfoundation.org/UA/" />"#));
+    assert!(xml.contains("<Uri>urn:vendor:model</Uri>"));
+    assert!(xml.contains(r#"<UAObject NodeId="i=85" BrowseName="Objects">"#));
+
+    // Namespace that is not registered
+    assert!(address_space.export_nodeset(Some(100)).is_err());
+}
+
+const DATA_TYPE_NODESET: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<UANodeSet xmlns="http://opcfoundation.org/UA/2011/03/UANodeSet.xsd">
+  <NamespaceUris>
+    <Uri>urn:vendor:types</Uri>
+  </NamespaceUris>
+  <Aliases>
+    <Alias Alias="Double">i=11</Alias>
+    <Alias Alias="HasSubtype">i=45</Alias>
+    <Alias Alias="HasEncoding">i=38</Alias>
+    <Alias Alias="HasTypeDefinition">i=40</Alias>
+  </Aliases>
+  <UADataType NodeId="ns=1;i=3001" BrowseName="1:Mode">
+    <DisplayName>Mode</DisplayName>
+    <References>
+      <Reference ReferenceType="HasSubtype" IsForward="false">i=29</Reference>
+    </References>
+    <Definition Name="1:Mode">
+      <Field Name="Off" Value="0" />
+      <Field Name="Auto" Value="2">
+        <Description>Automatic</Description>
+      </Field>
+    </Definition>
+  </UADataType>
+  <UADataType NodeId="ns=1;i=3002" BrowseName="1:Setpoint">
+    <DisplayName>Setpoint</DisplayName>
+    <References>
+      <Reference ReferenceType="HasSubtype" IsForward="false">i=22</Reference>
+      <Reference ReferenceType="HasEncoding">ns=1;i=5002</Reference>
+      <Reference ReferenceType="HasEncoding">ns=1;i=5001</Reference>
+    </References>
+    <Definition Name="1:Setpoint">
+      <Field Name="Value" DataType="Double" />
+      <Field Name="Mode" DataType="ns=1;i=3001" IsOptional="true" />
+      <Field Name="History" DataType="Double" ValueRank="1" ArrayDimensions="10" />
+    </Definition>
+  </UADataType>
+  <UAObject NodeId="ns=1;i=5001" BrowseName="Default Binary" SymbolicName="DefaultBinary">
+    <DisplayName>Default Binary</DisplayName>
+    <References>
+      <Reference ReferenceType="HasTypeDefinition">i=76</Reference>
+    </References>
+  </UAObject>
+  <UAObject NodeId="ns=1;i=5002" BrowseName="Default XML" SymbolicName="DefaultXml">
+    <DisplayName>Default XML</DisplayName>
+    <References>
+      <Reference ReferenceType="HasTypeDefinition">i=76</Reference>
+    </References>
+  </UAObject>
+</UANodeSet>"#;
+
+fn definition_of(address_space: &AddressSpace, node_id: &NodeId) -> DataTypeDefinition {
+    if let Some(NodeType::DataType(data_type)) = address_space.find_node(node_id) {
+        data_type.data_type_definition().unwrap().clone()
+    } else {
+        panic!("Expected a data type");
+    }
+}
+
+#[test]
+fn import_export_data_type_definition() {
+    let mut address_space = AddressSpace::new();
+    address_space.register_namespace("urn:other").unwrap();
+    assert_eq!(address_space.import_nodeset_str(DATA_TYPE_NODESET).unwrap(), 4);
+    let ns = address_space.namespace_index("urn:vendor:types").unwrap();
+
+    let mode = definition_of(&address_space, &NodeId::new(ns, 3001));
+    let fields = mode.as_enum().unwrap().fields.as_ref().unwrap();
+    assert_eq!(fields.len(), 2);
+    assert_eq!(fields[1].value, 2);
+    assert_eq!(fields[1].name, UAString::from("Auto"));
+    assert_eq!(fields[1].display_name, LocalizedText::new("", "Auto"));
+    assert_eq!(fields[1].description, LocalizedText::new("", "Automatic"));
+
+    // The default encoding is resolved from the encoding nodes
+    let setpoint = definition_of(&address_space, &NodeId::new(ns, 3002));
+    let setpoint = setpoint.as_structure().unwrap();
+    assert_eq!(setpoint.default_encoding_id, NodeId::new(ns, 5001));
+    assert_eq!(setpoint.base_data_type, DataTypeId::Structure.into());
+    assert_eq!(setpoint.structure_type, StructureType::StructureWithOptionalFields);
+    let fields = setpoint.fields.as_ref().unwrap();
+    assert_eq!(fields.len(), 3);
+    assert_eq!(fields[0].data_type, DataTypeId::Double.into());
+    assert_eq!(fields[0].value_rank, -1);
+    assert_eq!(fields[1].data_type, NodeId::new(ns, 3001));
+    assert!(fields[1].is_optional);
+    assert_eq!(fields[2].value_rank, 1);
+    assert_eq!(fields[2].array_dimensions, Some(vec![10]));
+
+    // Definitions survive an export and import
+    let xml = address_space.export_nodeset(Some(ns)).unwrap();
+    assert!(xml.contains(r#"<Definition Name="1:Setpoint">"#));
+    assert!(xml.contains(r#"<Field Name="Mode" DataType="ns=1;i=3001" IsOptional="true" />"#));
+    assert!(xml.contains(r#"<Field Name="Auto" Value="2">"#));
+
+    let mut imported = AddressSpace::new();
+    imported.import_nodeset_str(&xml).unwrap();
+    let imported_ns = imported.namespace_index("urn:vendor:types").unwrap();
+    assert_eq!(definition_of(&imported, &NodeId::new(imported_ns, 3001)), mode);
+    let imported_setpoint = definition_of(&imported, &NodeId::new(imported_ns, 3002));
+    assert_eq!(imported_setpoint.as_structure().unwrap().default_encoding_id, NodeId::new(imported_ns, 5001));
+    assert_eq!(imported_setpoint.as_structure().unwrap().fields.as_ref().unwrap()[1].data_type, NodeId::new(imported_ns, 3001));
+}
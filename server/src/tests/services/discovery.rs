@@ -1,4 +1,6 @@
-use opcua_types::UAString;
+use opcua_types::{profiles, UAString};
+
+use crate::config::{HttpsConfig, WssConfig};
 
 use crate::services::discovery::DiscoveryService;
 
@@ -36,6 +38,97 @@ fn get_endpoints() {
     });
 }
 
+#[test]
+fn get_endpoints_https() {
+    do_discovery_service_test(|server_state, _session, ds| {
+        {
+            let server_state = trace_read_lock_unwrap!(server_state);
+            let mut config = trace_write_lock_unwrap!(server_state.config);
+            config.https_config = Some(HttpsConfig {
+                host: "localhost".to_string(),
+                port: 443,
+                certificate_path: None,
+                private_key_path: None,
+                max_connections: HttpsConfig::DEFAULT_MAX_CONNECTIONS,
+            });
+        }
+
+        let get_endpoints = |profile_uris: Option<Vec<UAString>>| {
+            let request = GetEndpointsRequest {
+                request_header: make_request_header(),
+                endpoint_url: UAString::from("opc.tcp://localhost:4855/"),
+                locale_ids: None,
+                profile_uris,
+            };
+            let result = ds.get_endpoints(server_state.clone(), &request);
+            supported_message_as!(result, GetEndpointsResponse).endpoints
+        };
+
+        // Every endpoint is offered over both transports
+        let endpoints = get_endpoints(None).unwrap();
+        let https_endpoints: Vec<_> = endpoints.iter()
+            .filter(|e| e.transport_profile_uri.as_ref() == profiles::TRANSPORT_PROFILE_URI_HTTPS_BINARY)
+            .collect();
+        assert_eq!(https_endpoints.len() * 2, endpoints.len());
+        https_endpoints.iter().for_each(|e| {
+            assert!(e.endpoint_url.as_ref().starts_with("https://localhost:443/"));
+        });
+
+        // Filtered by transport
+        let endpoints = get_endpoints(Some(vec![UAString::from(profiles::TRANSPORT_PROFILE_URI_HTTPS_BINARY)])).unwrap();
+        assert_eq!(endpoints.len(), https_endpoints.len());
+        assert!(endpoints.iter().all(|e| e.transport_profile_uri.as_ref() == profiles::TRANSPORT_PROFILE_URI_HTTPS_BINARY));
+
+        let endpoints = get_endpoints(Some(vec![UAString::from(profiles::TRANSPORT_PROFILE_URI_BINARY)])).unwrap();
+        assert!(endpoints.iter().all(|e| e.transport_profile_uri.as_ref() == profiles::TRANSPORT_PROFILE_URI_BINARY));
+
+        assert!(get_endpoints(Some(vec![UAString::from("http://opcfoundation.org/UA-Profile/Transport/wss-uajson")])).is_none());
+
+        // The https url finds the endpoint that a session is created on
+        let server_state = trace_read_lock_unwrap!(server_state);
+        let endpoints = server_state.new_endpoint_descriptions("https://localhost:443/").unwrap();
+        assert!(endpoints.iter().all(|e| e.transport_profile_uri.as_ref() == profiles::TRANSPORT_PROFILE_URI_HTTPS_BINARY));
+        assert!(server_state.endpoint_exists("https://localhost:443/", SecurityPolicy::None, MessageSecurityMode::None));
+    });
+}
+
+#[test]
+fn get_endpoints_wss() {
+    do_discovery_service_test(|server_state, _session, ds| {
+        {
+            let server_state = trace_read_lock_unwrap!(server_state);
+            let mut config = trace_write_lock_unwrap!(server_state.config);
+            config.wss_config = Some(WssConfig {
+                host: "localhost".to_string(),
+                port: 8443,
+                certificate_path: None,
+                private_key_path: None,
+            });
+        }
+
+        let request = GetEndpointsRequest {
+            request_header: make_request_header(),
+            endpoint_url: UAString::from("opc.wss://localhost:8443/"),
+            locale_ids: None,
+            profile_uris: Some(vec![UAString::from(profiles::TRANSPORT_PROFILE_URI_WSS_BINARY)]),
+        };
+        let result = ds.get_endpoints(server_state.clone(), &request);
+        let endpoints = supported_message_as!(result, GetEndpointsResponse).endpoints.unwrap();
+        assert!(!endpoints.is_empty());
+        endpoints.iter().for_each(|e| {
+            assert_eq!(e.transport_profile_uri.as_ref(), profiles::TRANSPORT_PROFILE_URI_WSS_BINARY);
+            assert!(e.endpoint_url.as_ref().starts_with("opc.wss://localhost:8443/"));
+        });
+
+        // The secure channel still applies over wss, so every security policy is offered
+        let server_state = trace_read_lock_unwrap!(server_state);
+        let endpoints = server_state.new_endpoint_descriptions("opc.wss://localhost:8443/").unwrap();
+        assert!(endpoints.iter().all(|e| e.transport_profile_uri.as_ref() == profiles::TRANSPORT_PROFILE_URI_WSS_BINARY));
+        assert!(server_state.endpoint_exists("opc.wss://localhost:8443/", SecurityPolicy::Basic256Sha256, MessageSecurityMode::SignAndEncrypt));
+        assert!(!server_state.endpoint_exists("opc.wss://localhost:9443/", SecurityPolicy::None, MessageSecurityMode::None));
+    });
+}
+
 #[test]
 fn find_servers() {
     do_discovery_service_test(|server_state, _session, ds| {
@@ -46,7 +139,7 @@ fn find_servers() {
             locale_ids: None,
             server_uris: None,
         };
-        let result = ds.find_servers(server_state, &request);
+        let result = ds.find_servers(server_state.clone(), &request);
 
         let response = supported_message_as!(result, FindServersResponse);
         let servers = response.servers.unwrap();
@@ -62,7 +155,20 @@ fn find_servers() {
             assert_eq!(s.product_uri.as_ref(), "urn:OPC UA Sample Server Testkit");
         });
 
-        // TODO other requests should exercise those filters
+        // The server is only returned when the server uris name it
+        let find_servers = |server_uris: Vec<&str>| {
+            let request = FindServersRequest {
+                request_header: make_request_header(),
+                endpoint_url: Default::default(),
+                locale_ids: None,
+                server_uris: Some(server_uris.into_iter().map(UAString::from).collect()),
+            };
+            let result = ds.find_servers(server_state.clone(), &request);
+            supported_message_as!(result, FindServersResponse).servers.unwrap()
+        };
+        assert_eq!(find_servers(vec![]).len(), 1);
+        assert_eq!(find_servers(vec!["urn:backup", "urn:OPC UA Sample Server"]).len(), 1);
+        assert!(find_servers(vec!["urn:backup"]).is_empty());
     });
 }
 
@@ -233,3 +233,44 @@ fn call_resend_data() {
         }
     });
 }
+
+/// Adds a folder under the objects folder each time it is called
+struct AddFolderMethod;
+
+impl AddressSpaceMethod for AddFolderMethod {
+    fn call(&mut self, address_space: &mut AddressSpace, _session: &mut Session, _request: &CallMethodRequest) -> Result<CallMethodResult, StatusCode> {
+        let folder_id = address_space.add_folder("Added", "Added", &NodeId::objects_folder_id()).map_err(|_| StatusCode::BadInternalError)?;
+        Ok(CallMethodResult {
+            status_code: StatusCode::Good,
+            input_argument_results: None,
+            input_argument_diagnostic_infos: None,
+            output_arguments: Some(vec![folder_id.into()]),
+        })
+    }
+}
+
+#[test]
+fn call_address_space_method() {
+    do_method_service_test(|server_state, session, address_space, s| {
+        let method_id = NodeId::new(1, "AddFolder");
+        {
+            let mut address_space = address_space.write().unwrap();
+            MethodBuilder::new(&method_id, "AddFolder", "AddFolder")
+                .component_of(ObjectId::ObjectsFolder)
+                .address_space_callback(Box::new(AddFolderMethod))
+                .insert(&mut address_space);
+        }
+
+        // The callback stays registered after each call
+        for _ in 0..2 {
+            let request = new_call_method_request(ObjectId::ObjectsFolder, method_id.clone(), None);
+            let response = call_single(s, server_state.clone(), session.clone(), address_space.clone(), request).unwrap();
+            assert_eq!(response.status_code, StatusCode::Good);
+            let folder_id = match response.output_arguments.unwrap().remove(0) {
+                Variant::NodeId(folder_id) => *folder_id,
+                _ => panic!("Expected a node id"),
+            };
+            assert!(address_space.read().unwrap().find_node(&folder_id).is_some());
+        }
+    });
+}
@@ -168,7 +168,7 @@ fn publish_tick_response<T>(session: Arc<RwLock<Session>>, ss: &SubscriptionServ
 
 fn populate_monitored_item(discard_oldest: bool) -> MonitoredItem {
     let client_handle = 999;
-    let mut monitored_item = MonitoredItem::new(&chrono::Utc::now(), 1, TimestampsToReturn::Both, &make_create_request_data_change_filter(-1f64, 5)).unwrap();
+    let mut monitored_item = MonitoredItem::new(&chrono::Utc::now(), 1, TimestampsToReturn::Both, &NamespaceMap::new(), &make_create_request_data_change_filter(-1f64, 5)).unwrap();
     monitored_item.set_discard_oldest(discard_oldest);
     for i in 0..5 {
         monitored_item.enqueue_notification_message(MonitoredItemNotification {
@@ -325,7 +325,7 @@ fn monitored_item_data_change_filter() {
 
     // Create request should monitor attribute of variable, e.g. value
     // Sample interval is negative so it will always test on repeated calls
-    let mut monitored_item = MonitoredItem::new(&chrono::Utc::now(), 1, TimestampsToReturn::Both, &make_create_request_data_change_filter(-1f64, 5)).unwrap();
+    let mut monitored_item = MonitoredItem::new(&chrono::Utc::now(), 1, TimestampsToReturn::Both, &NamespaceMap::new(), &make_create_request_data_change_filter(-1f64, 5)).unwrap();
 
     let now = Utc::now();
 
@@ -358,6 +358,42 @@ fn monitored_item_data_change_filter() {
     assert_eq!(monitored_item.notification_queue().len(), 2);
 }
 
+#[test]
+fn monitored_item_registered_filter() {
+    // A filter with an encoding id registered for DataChangeFilter is accepted. The process wide
+    // registry is shared with other tests so this one uses its own namespace.
+    let encoding_id = NodeId::new(903, "DataChangeFilter");
+    let data_change_filter = DataChangeFilter {
+        trigger: DataChangeTrigger::StatusValue,
+        deadband_type: DeadbandType::None as u32,
+        deadband_value: 0f64,
+    };
+    let filter = ExtensionObject::from_encodable(encoding_id.clone(), &data_change_filter);
+    assert_eq!(FilterType::from_filter(&filter, &NamespaceMap::new()).unwrap_err(), StatusCode::BadFilterNotAllowed);
+
+    type_registry().write().unwrap().register::<DataChangeFilter, _>(encoding_id.clone());
+    assert_eq!(FilterType::from_filter(&filter, &NamespaceMap::new()).unwrap(), FilterType::DataChangeFilter(data_change_filter.clone()));
+
+    // Registered types which are not filters are still rejected
+    let other_id = NodeId::new(903, "Range");
+    type_registry().write().unwrap().register::<Range, _>(other_id.clone());
+    let other = ExtensionObject::from_encodable(other_id.clone(), &Range { low: 0.0, high: 1.0 });
+    assert_eq!(FilterType::from_filter(&other, &NamespaceMap::new()).unwrap_err(), StatusCode::BadFilterNotAllowed);
+
+    // A filter registered by namespace uri is resolved through the server's namespace table
+    let mut address_space = AddressSpace::new();
+    let ns = address_space.register_namespace("urn:registered-filter").unwrap();
+    type_registry().write().unwrap().register_with_namespace_uri::<DataChangeFilter, _>("urn:registered-filter", "DataChangeFilter");
+    let filter = ExtensionObject::from_encodable(NodeId::new(ns, "DataChangeFilter"), &data_change_filter);
+    assert_eq!(FilterType::from_filter(&filter, address_space.namespace_map()).unwrap(), FilterType::DataChangeFilter(data_change_filter));
+    assert_eq!(FilterType::from_filter(&filter, &NamespaceMap::new()).unwrap_err(), StatusCode::BadFilterNotAllowed);
+
+    let mut type_registry = type_registry().write().unwrap();
+    type_registry.unregister(&encoding_id);
+    type_registry.unregister(&other_id);
+    type_registry.unregister_with_namespace_uri("urn:registered-filter", "DataChangeFilter");
+}
+
 #[test]
 fn monitored_item_event_filter() {
     // create an address space
@@ -366,7 +402,7 @@ fn monitored_item_event_filter() {
 
     // Create request should monitor attribute of variable, e.g. value
     // Sample interval is negative so it will always test on repeated calls
-    let mut monitored_item = MonitoredItem::new(&chrono::Utc::now(), 1, TimestampsToReturn::Both, &make_create_request_event_filter(-1f64, 5)).unwrap();
+    let mut monitored_item = MonitoredItem::new(&chrono::Utc::now(), 1, TimestampsToReturn::Both, &NamespaceMap::new(), &make_create_request_event_filter(-1f64, 5)).unwrap();
 
     let mut now = Utc::now();
 
@@ -1,4 +1,4 @@
-use opcua_types::node_ids::{ObjectId, ObjectTypeId, ReferenceTypeId, DataTypeId, MethodId};
+use opcua_types::node_ids::{ObjectId, ObjectTypeId, ReferenceTypeId, DataTypeId, MethodId, VariableTypeId};
 
 use crate::{
     services::node_management::NodeManagementService,
@@ -293,6 +293,36 @@ fn add_nodes_browse_name_duplicated() {
     }, StatusCode::BadBrowseNameDuplicated);
 }
 
+#[test]
+fn add_nodes_browse_name_reserved_chars() {
+    // Names with characters that are reserved in the relative path syntax are valid browse names
+    // and are tested for duplicates like any other
+    do_node_management_service_test(true, |server_state, session, address_space, nms| {
+        let item = |browse_name: QualifiedName| AddNodesItem {
+            parent_node_id: ObjectId::ObjectsFolder.into(),
+            reference_type_id: ReferenceTypeId::Organizes.into(),
+            requested_new_node_id: ExpandedNodeId::null(),
+            browse_name,
+            node_class: NodeClass::Object,
+            node_attributes: object_attributes("foo"),
+            type_definition: ObjectTypeId::BaseObjectType.into(),
+        };
+        let response = nms.add_nodes(server_state, session, address_space, &AddNodesRequest {
+            request_header: RequestHeader::dummy(),
+            nodes_to_add: Some(vec![
+                item(QualifiedName::from("a/b.c<d>:#!&")),
+                item(QualifiedName::new(2, "<2:x>/y")),
+                item(QualifiedName::from("a/b.c<d>:#!&")),
+                item(QualifiedName::new(2, "<2:x>/y")),
+            ]),
+        });
+        let response: AddNodesResponse = supported_message_as!(response, AddNodesResponse);
+        let results = response.results.unwrap();
+        let status_codes = results.iter().map(|r| r.status_code).collect::<Vec<_>>();
+        assert_eq!(status_codes, vec![StatusCode::Good, StatusCode::Good, StatusCode::BadBrowseNameDuplicated, StatusCode::BadBrowseNameDuplicated]);
+    });
+}
+
 #[test]
 fn add_nodes_valid() {
     // Add a node which is valid
@@ -307,6 +337,39 @@ fn add_nodes_valid() {
     }, StatusCode::Good);
 }
 
+#[test]
+fn add_nodes_registered_attributes() {
+    // Attributes may use any encoding id registered for one of the attribute types. The process
+    // wide registry is shared with other tests so this one uses its own namespace.
+    let encoding_id = NodeId::new(902, "ObjectAttributes");
+    type_registry().write().unwrap().register::<ObjectAttributes, _>(encoding_id.clone());
+    let attributes = object_attributes("foo");
+    let node_attributes = ExtensionObject { node_id: encoding_id.clone(), body: attributes.body };
+
+    do_add_node_test_with_expected_error(true, AddNodesItem {
+        parent_node_id: ObjectId::ObjectsFolder.into(),
+        reference_type_id: ReferenceTypeId::Organizes.into(),
+        requested_new_node_id: ExpandedNodeId::null(),
+        browse_name: QualifiedName::from("boo"),
+        node_class: NodeClass::Object,
+        node_attributes: node_attributes.clone(),
+        type_definition: ObjectTypeId::BaseObjectType.into(),
+    }, StatusCode::Good);
+
+    // The registered type must still match the node class
+    do_add_node_test_with_expected_error(true, AddNodesItem {
+        parent_node_id: ObjectId::ObjectsFolder.into(),
+        reference_type_id: ReferenceTypeId::Organizes.into(),
+        requested_new_node_id: ExpandedNodeId::null(),
+        browse_name: QualifiedName::from("boo"),
+        node_class: NodeClass::Variable,
+        node_attributes,
+        type_definition: VariableTypeId::BaseDataVariableType.into(),
+    }, StatusCode::BadNodeAttributesInvalid);
+
+    type_registry().write().unwrap().unregister(&encoding_id);
+}
+
 #[test]
 fn add_nodes_user_access_denied() {
     // Add a node without permission
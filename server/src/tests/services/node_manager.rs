@@ -0,0 +1,372 @@
+use std::sync::Mutex;
+
+use crate::{
+    address_space::node_manager::NodeManager,
+    services::{
+        attribute::AttributeService,
+        monitored_item::MonitoredItemService,
+        subscription::SubscriptionService,
+        view::ViewService,
+    },
+    subscriptions::monitored_item::{MonitoredItem, Notification},
+};
+
+use super::*;
+
+const TAG_COUNT: u32 = 1000;
+
+/// Tags from this index onwards are read-only
+const READ_ONLY_TAG: u32 = 900;
+
+/// Tags from this index onwards are only readable by the server, not the user
+const NO_USER_READ_TAG: u32 = 950;
+
+/// Tags from this index onwards are not readable at all
+const NOT_READABLE_TAG: u32 = 990;
+
+/// A node manager that serves a folder of tags held in a vec instead of the address space
+struct TagManager {
+    namespace: u16,
+    values: Mutex<Vec<f64>>,
+}
+
+impl TagManager {
+    fn new(namespace: u16) -> TagManager {
+        TagManager {
+            namespace,
+            values: Mutex::new((0..TAG_COUNT).map(|i| i as f64).collect()),
+        }
+    }
+
+    fn tag_index(&self, node_id: &NodeId) -> Option<usize> {
+        match node_id.identifier {
+            Identifier::Numeric(i) if node_id.namespace == self.namespace && i < TAG_COUNT => Some(i as usize),
+            _ => None
+        }
+    }
+
+    fn is_folder(&self, node_id: &NodeId) -> bool {
+        *node_id == tags_folder_id(self.namespace)
+    }
+}
+
+impl NodeManager for TagManager {
+    fn node_exists(&self, node_id: &NodeId) -> bool {
+        self.is_folder(node_id) || self.tag_index(node_id).is_some()
+    }
+
+    fn read(&self, node_id: &NodeId, _timestamps_to_return: TimestampsToReturn, attribute_id: AttributeId, index_range: NumericRange, _data_encoding: &QualifiedName, _max_age: f64) -> Result<DataValue, StatusCode> {
+        let (node_class, name) = if self.is_folder(node_id) {
+            (NodeClass::Object, "Tags".to_string())
+        } else if let Some(idx) = self.tag_index(node_id) {
+            (NodeClass::Variable, format!("Tag{}", idx))
+        } else {
+            return Err(StatusCode::BadNodeIdUnknown);
+        };
+        let value: Variant = match attribute_id {
+            AttributeId::NodeId => node_id.clone().into(),
+            AttributeId::NodeClass => (node_class as i32).into(),
+            AttributeId::BrowseName => QualifiedName::new(self.namespace, name).into(),
+            AttributeId::DisplayName => LocalizedText::from(name.as_str()).into(),
+            AttributeId::AccessLevel if node_class == NodeClass::Variable => {
+                let idx = self.tag_index(node_id).unwrap() as u32;
+                let access_level = if idx < NOT_READABLE_TAG { AccessLevel::CURRENT_READ | AccessLevel::CURRENT_WRITE } else { AccessLevel::CURRENT_WRITE };
+                access_level.bits().into()
+            }
+            AttributeId::UserAccessLevel if node_class == NodeClass::Variable => {
+                let idx = self.tag_index(node_id).unwrap() as u32;
+                let user_access_level = if idx < READ_ONLY_TAG {
+                    UserAccessLevel::CURRENT_READ | UserAccessLevel::CURRENT_WRITE
+                } else if idx < NO_USER_READ_TAG {
+                    UserAccessLevel::CURRENT_READ
+                } else {
+                    UserAccessLevel::empty()
+                };
+                user_access_level.bits().into()
+            }
+            AttributeId::Value if node_class == NodeClass::Variable => {
+                // Tags are scalars so they have no elements to index
+                if index_range != NumericRange::None {
+                    return Err(StatusCode::BadIndexRangeNoData);
+                }
+                let values = self.values.lock().unwrap();
+                values[self.tag_index(node_id).unwrap()].into()
+            }
+            _ => return Err(StatusCode::BadAttributeIdInvalid)
+        };
+        Ok(DataValue::new_now(value))
+    }
+
+    fn write(&self, _session: &Session, node_id: &NodeId, attribute_id: AttributeId, _index_range: NumericRange, value: DataValue) -> Result<(), StatusCode> {
+        let idx = self.tag_index(node_id).ok_or(StatusCode::BadNodeIdUnknown)?;
+        if attribute_id != AttributeId::Value {
+            Err(StatusCode::BadNotWritable)
+        } else if let Some(Variant::Double(value)) = value.value {
+            let mut values = self.values.lock().unwrap();
+            values[idx] = value;
+            Ok(())
+        } else {
+            Err(StatusCode::BadTypeMismatch)
+        }
+    }
+
+    fn browse(&self, node_to_browse: &BrowseDescription) -> Result<Vec<ReferenceDescription>, StatusCode> {
+        if !self.node_exists(&node_to_browse.node_id) {
+            Err(StatusCode::BadNodeIdUnknown)
+        } else if node_to_browse.reference_type_id == ReferenceTypeId::HasTypeDefinition.into() {
+            let type_definition = if self.is_folder(&node_to_browse.node_id) {
+                ExpandedNodeId::new(ObjectTypeId::FolderType)
+            } else {
+                ExpandedNodeId::new(VariableTypeId::BaseDataVariableType)
+            };
+            Ok(vec![ReferenceDescription {
+                node_id: type_definition,
+                reference_type_id: ReferenceTypeId::HasTypeDefinition.into(),
+                is_forward: true,
+                node_class: NodeClass::Unspecified,
+                browse_name: QualifiedName::null(),
+                display_name: LocalizedText::null(),
+                type_definition: ExpandedNodeId::null(),
+            }])
+        } else if self.is_folder(&node_to_browse.node_id) && node_to_browse.browse_direction != BrowseDirection::Inverse {
+            let references = (0..TAG_COUNT).map(|i| {
+                ReferenceDescription {
+                    node_id: ExpandedNodeId::new(NodeId::new(self.namespace, i)),
+                    reference_type_id: ReferenceTypeId::Organizes.into(),
+                    is_forward: true,
+                    node_class: NodeClass::Variable,
+                    browse_name: QualifiedName::new(self.namespace, format!("Tag{}", i)),
+                    display_name: LocalizedText::from(format!("Tag{}", i).as_str()),
+                    type_definition: ExpandedNodeId::new(VariableTypeId::BaseDataVariableType),
+                }
+            }).collect();
+            Ok(references)
+        } else {
+            Ok(Vec::new())
+        }
+    }
+}
+
+fn tags_folder_id(namespace: u16) -> NodeId {
+    NodeId::new(namespace, "Tags")
+}
+
+/// Registers the tag manager and links its folder into the objects folder. Returns the namespace.
+fn add_tag_manager(address_space: Arc<RwLock<AddressSpace>>) -> u16 {
+    let mut address_space = trace_write_lock_unwrap!(address_space);
+    let ns = address_space.register_namespace("urn:tags").unwrap();
+    assert!(address_space.register_node_manager(ns, TagManager::new(ns)));
+    address_space.insert_reference(&NodeId::objects_folder_id(), &tags_folder_id(ns), ReferenceTypeId::Organizes);
+    ns
+}
+
+fn read_values(server_state: Arc<RwLock<ServerState>>, session: Arc<RwLock<Session>>, address_space: Arc<RwLock<AddressSpace>>, node_ids: &[NodeId]) -> Vec<DataValue> {
+    let request = ReadRequest {
+        request_header: make_request_header(),
+        max_age: 0f64,
+        timestamps_to_return: TimestampsToReturn::Both,
+        nodes_to_read: Some(node_ids.iter().map(|n| n.clone().into()).collect()),
+    };
+    let response = AttributeService::new().read(server_state, session, address_space, &request);
+    let response: ReadResponse = supported_message_as!(response, ReadResponse);
+    response.results.unwrap()
+}
+
+#[test]
+fn register_node_manager_namespace() {
+    let st = ServiceTest::new();
+    let mut address_space = trace_write_lock_unwrap!(st.address_space);
+    // Namespace 0 and unregistered namespaces are rejected
+    assert!(!address_space.register_node_manager(0, TagManager::new(0)));
+    assert!(!address_space.register_node_manager(100, TagManager::new(100)));
+    let ns = address_space.register_namespace("urn:tags").unwrap();
+    assert!(address_space.register_node_manager(ns, TagManager::new(ns)));
+    assert!(address_space.is_known_node(&NodeId::new(ns, 5)));
+    assert!(!address_space.is_known_node(&NodeId::new(ns, TAG_COUNT)));
+    assert!(address_space.unregister_node_manager(ns));
+    assert!(!address_space.is_known_node(&NodeId::new(ns, 5)));
+}
+
+#[test]
+fn read_write_managed_node() {
+    let st = ServiceTest::new();
+    let ns = add_tag_manager(st.address_space.clone());
+
+    let results = read_values(st.server_state.clone(), st.session.clone(), st.address_space.clone(), &[NodeId::new(ns, 5), NodeId::new(ns, TAG_COUNT + 1)]);
+    assert_eq!(results[0].value, Some(Variant::Double(5f64)));
+    assert_eq!(results[1].status, Some(StatusCode::BadNodeIdUnknown));
+
+    let request = WriteRequest {
+        request_header: make_request_header(),
+        nodes_to_write: Some(vec![
+            WriteValue {
+                node_id: NodeId::new(ns, 5),
+                attribute_id: AttributeId::Value as u32,
+                index_range: UAString::null(),
+                value: DataValue::value_only(99f64),
+            },
+            WriteValue {
+                node_id: NodeId::new(ns, 6),
+                attribute_id: AttributeId::DisplayName as u32,
+                index_range: UAString::null(),
+                value: DataValue::value_only(LocalizedText::from("x")),
+            },
+            WriteValue {
+                node_id: NodeId::new(ns, READ_ONLY_TAG),
+                attribute_id: AttributeId::Value as u32,
+                index_range: UAString::null(),
+                value: DataValue::value_only(99f64),
+            }
+        ]),
+    };
+    let response = AttributeService::new().write(st.server_state.clone(), st.session.clone(), st.address_space.clone(), &request);
+    let response: WriteResponse = supported_message_as!(response, WriteResponse);
+    let results = response.results.unwrap();
+    assert_eq!(results, vec![StatusCode::Good, StatusCode::BadNotWritable, StatusCode::BadNotWritable]);
+
+    // The read-only tag is refused by the server before the manager is asked to write it
+    let results = read_values(st.server_state.clone(), st.session.clone(), st.address_space.clone(), &[NodeId::new(ns, 5), NodeId::new(ns, READ_ONLY_TAG)]);
+    assert_eq!(results[0].value, Some(Variant::Double(99f64)));
+    assert_eq!(results[1].value, Some(Variant::Double(READ_ONLY_TAG as f64)));
+
+    // Values are refused by the server when the access levels say they cannot be read, but
+    // the other attributes of the same nodes can be
+    let results = read_values(st.server_state.clone(), st.session.clone(), st.address_space.clone(), &[NodeId::new(ns, NO_USER_READ_TAG), NodeId::new(ns, NOT_READABLE_TAG)]);
+    assert_eq!(results[0].value, None);
+    assert_eq!(results[0].status, Some(StatusCode::BadUserAccessDenied));
+    assert_eq!(results[1].value, None);
+    assert_eq!(results[1].status, Some(StatusCode::BadNotReadable));
+    let request = ReadRequest {
+        request_header: make_request_header(),
+        max_age: 0f64,
+        timestamps_to_return: TimestampsToReturn::Both,
+        nodes_to_read: Some(vec![ReadValueId {
+            node_id: NodeId::new(ns, NOT_READABLE_TAG),
+            attribute_id: AttributeId::BrowseName as u32,
+            index_range: UAString::null(),
+            data_encoding: QualifiedName::null(),
+        }]),
+    };
+    let response = AttributeService::new().read(st.server_state.clone(), st.session.clone(), st.address_space.clone(), &request);
+    let response: ReadResponse = supported_message_as!(response, ReadResponse);
+    let results = response.results.unwrap();
+    assert_eq!(results[0].value, Some(Variant::from(QualifiedName::new(ns, format!("Tag{}", NOT_READABLE_TAG)))));
+}
+
+#[test]
+fn browse_managed_node() {
+    let st = ServiceTest::new();
+    let ns = add_tag_manager(st.address_space.clone());
+    let vs = ViewService::new();
+
+    // Browse the objects folder and expect to see the managed folder described by the manager
+    let request = BrowseRequest {
+        request_header: make_request_header(),
+        view: ViewDescription {
+            view_id: NodeId::null(),
+            timestamp: DateTime::null(),
+            view_version: 0,
+        },
+        requested_max_references_per_node: 100,
+        nodes_to_browse: Some(vec![
+            BrowseDescription {
+                node_id: NodeId::objects_folder_id(),
+                browse_direction: BrowseDirection::Forward,
+                reference_type_id: ReferenceTypeId::Organizes.into(),
+                include_subtypes: true,
+                node_class_mask: 0,
+                result_mask: BrowseDescriptionResultMask::all().bits(),
+            },
+            BrowseDescription {
+                node_id: tags_folder_id(ns),
+                browse_direction: BrowseDirection::Forward,
+                reference_type_id: ReferenceTypeId::Organizes.into(),
+                include_subtypes: true,
+                node_class_mask: 0,
+                result_mask: BrowseDescriptionResultMask::all().bits(),
+            }
+        ]),
+    };
+    let response = vs.browse(st.server_state.clone(), st.session.clone(), st.address_space.clone(), &request);
+    let response: BrowseResponse = supported_message_as!(response, BrowseResponse);
+    let results = response.results.unwrap();
+
+    let references = results[0].references.as_ref().unwrap();
+    let tags_folder = references.iter().find(|r| r.node_id.node_id == tags_folder_id(ns)).unwrap();
+    assert_eq!(tags_folder.browse_name, QualifiedName::new(ns, "Tags"));
+    assert_eq!(tags_folder.node_class, NodeClass::Object);
+    assert_eq!(tags_folder.type_definition, ExpandedNodeId::new(ObjectTypeId::FolderType));
+
+    // The managed folder has more references than requested so there is a continuation point
+    let references = results[1].references.as_ref().unwrap();
+    assert_eq!(references.len(), 100);
+    assert_eq!(references[0].browse_name, QualifiedName::new(ns, "Tag0"));
+    assert!(!results[1].continuation_point.is_null());
+}
+
+#[test]
+fn translate_browse_path_to_managed_node() {
+    let st = ServiceTest::new();
+    let ns = add_tag_manager(st.address_space.clone());
+    let vs = ViewService::new();
+
+    let element = |target_name: QualifiedName| RelativePathElement {
+        reference_type_id: ReferenceTypeId::Organizes.into(),
+        is_inverse: false,
+        include_subtypes: true,
+        target_name,
+    };
+    let request = TranslateBrowsePathsToNodeIdsRequest {
+        request_header: make_request_header(),
+        browse_paths: Some(vec![BrowsePath {
+            starting_node: NodeId::objects_folder_id(),
+            relative_path: RelativePath {
+                elements: Some(vec![
+                    element(QualifiedName::new(ns, "Tags")),
+                    element(QualifiedName::new(ns, "Tag42")),
+                ]),
+            },
+        }]),
+    };
+    let response = vs.translate_browse_paths_to_node_ids(st.server_state.clone(), st.address_space.clone(), &request);
+    let response: TranslateBrowsePathsToNodeIdsResponse = supported_message_as!(response, TranslateBrowsePathsToNodeIdsResponse);
+    let results = response.results.unwrap();
+    assert_eq!(results[0].status_code, StatusCode::Good);
+    let targets = results[0].targets.as_ref().unwrap();
+    assert_eq!(targets.len(), 1);
+    assert_eq!(targets[0].target_id.node_id, NodeId::new(ns, 42));
+}
+
+#[test]
+fn monitor_managed_node() {
+    let st = ServiceTest::new();
+    let ns = add_tag_manager(st.address_space.clone());
+    let ss = SubscriptionService::new();
+    let mis = MonitoredItemService::new();
+
+    let request = create_subscription_request(0, 0);
+    let response: CreateSubscriptionResponse = supported_message_as!(ss.create_subscription(st.server_state.clone(), st.session.clone(), &request), CreateSubscriptionResponse);
+
+    let request = create_monitored_items_request(response.subscription_id, vec![NodeId::new(ns, 1), NodeId::new(ns, TAG_COUNT)]);
+    let response: CreateMonitoredItemsResponse = supported_message_as!(mis.create_monitored_items(st.server_state.clone(), st.session.clone(), st.address_space.clone(), &request), CreateMonitoredItemsResponse);
+    let results = response.results.unwrap();
+    assert_eq!(results[0].status_code, StatusCode::Good);
+    assert_eq!(results[1].status_code, StatusCode::BadNodeIdUnknown);
+
+    // The monitored item samples its value from the manager
+    let address_space = trace_read_lock_unwrap!(st.address_space);
+    let now = chrono::Utc::now();
+    let mut monitored_item = MonitoredItem::new(&now, 1, TimestampsToReturn::Both, address_space.namespace_map(), &request.items_to_create.as_ref().unwrap()[0]).unwrap();
+    assert!(monitored_item.check_value(&address_space, &now, false));
+    match monitored_item.oldest_notification_message().unwrap() {
+        Notification::MonitoredItemNotification(n) => assert_eq!(n.value.value, Some(Variant::Double(1f64))),
+        _ => panic!()
+    }
+    assert!(!monitored_item.check_value(&address_space, &now, false));
+
+    // The index range of the item is passed to the manager, which has no elements to sample
+    let mut item_to_create = request.items_to_create.as_ref().unwrap()[0].clone();
+    item_to_create.item_to_monitor.index_range = UAString::from("1");
+    let mut monitored_item = MonitoredItem::new(&now, 2, TimestampsToReturn::Both, address_space.namespace_map(), &item_to_create).unwrap();
+    assert!(!monitored_item.check_value(&address_space, &now, false));
+}
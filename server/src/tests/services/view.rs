@@ -510,7 +510,7 @@ fn translate_browse_paths_to_node_ids2() {
         ].iter().map(|path|
             BrowsePath {
                 starting_node: starting_node.clone(),
-                relative_path: RelativePath::from_str(path, &RelativePathElement::default_node_resolver).unwrap(),
+                relative_path: RelativePath::parse_with_resolver(path, &RelativePathElement::default_node_resolver).unwrap(),
             }
         ).collect::<Vec<_>>();
 
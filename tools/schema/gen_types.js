@@ -118,6 +118,7 @@ fs.readFile(types_xml, (err, data) => {
                     if (_.has(field["$"], "LengthField")) {
                         fields_to_add.push({
                             name: field_name,
+                            json_name: field["$"]["Name"],
                             type: `Option<Vec<${type}>>`,
                             contained_type: type,
                             inner_type: type,
@@ -127,6 +128,7 @@ fs.readFile(types_xml, (err, data) => {
                     } else {
                         fields_to_add.push({
                             name: field_name,
+                            json_name: field["$"]["Name"],
                             type: type,
                             contained_type: type
                         })
@@ -221,6 +223,17 @@ pub use self::impls::*;
 `
     });
 
+    contents += `
+// The types above that can be held in an extension object with a JSON body
+json_extension_object_types! {
+`;
+    _.each(structured_types, structured_type => {
+        contents += `    ${structured_type.name}, ${structured_type.name}_Encoding_DefaultBinary, ${structured_type.name}_Encoding_DefaultJson;
+`
+    });
+    contents += `}
+`;
+
     util.write_to_file(file_path, contents);
 }
 
@@ -234,6 +247,7 @@ function generate_enum_types(enums) {
 use std::io::{Read, Write};
 
 use crate::encoding::*;
+use crate::json::*;
 use crate::status_codes::StatusCode;
 
 // All enums assumed to be i32 length in bits when encoded.
@@ -287,6 +301,33 @@ impl BinaryEncoder<${enum_type.name}> for ${enum_type.name} {
         }
     }
 }
+
+impl JsonEncoder<${enum_type.name}> for ${enum_type.name} {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {`;
+
+        _.each(enum_type.values, (value) => {
+            contents += `
+            ${value.value} => Ok(Self::${value.name}),`;
+        });
+
+        contents += `
+            v => {
+                error!("Invalid value {} for enum ${enum_type.name}", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
 `
     });
 
@@ -299,6 +340,7 @@ function generate_type_imports(structured_types, fields_to_add, fields_to_hide,
     let imports = `#[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
 `;
 
@@ -483,6 +525,42 @@ pub struct ${structured_type.name} {
     contents += `        })
     }
 }
+
+impl JsonEncoder<${structured_type.name}> for ${structured_type.name} {
+`;
+
+    let json_fields = _.filter(structured_type.fields_to_add, field => !_.includes(structured_type.fields_to_hide, field.name));
+    if (json_fields.length > 0) {
+        contents += `    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+`;
+        _.each(json_fields, field => {
+            contents += `        write_json_field(&mut object, "${field.json_name}", &self.${field.name}, ctx);\n`;
+        });
+        contents += `        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(${structured_type.name} {
+`;
+        _.each(json_fields, field => {
+            contents += `            ${field.name}: read_json_field(object, "${field.json_name}", decoding_limits)?,\n`;
+        });
+    } else {
+        contents += `    fn encode_json(&self, _ctx: &JsonContext) -> JsonValue {
+        JsonValue::Object(JsonObject::new())
+    }
+
+    fn decode_json(value: &JsonValue, _decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        json_object(value)?;
+        Ok(${structured_type.name} {
+`;
+    }
+
+    contents += `        })
+    }
+}
 `;
 
     util.write_to_file(file_path, contents);
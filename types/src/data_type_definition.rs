@@ -0,0 +1,80 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! Contains the `DataTypeDefinition` which is the value of the DataTypeDefinition attribute of a
+//! DataType node.
+
+use crate::{
+    encoding::DecodingLimits,
+    extension_object::ExtensionObject,
+    node_ids::ObjectId,
+    service_types::{EnumDefinition, StructureDefinition},
+    status_codes::StatusCode,
+    variant::Variant,
+};
+
+/// The DataTypeDefinition attribute (Part 3 5.8.3) describes the fields of a structured DataType
+/// or the values of an enumerated DataType. On the wire it is an `ExtensionObject` holding a
+/// `StructureDefinition` or an `EnumDefinition`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataTypeDefinition {
+    Structure(StructureDefinition),
+    Enum(EnumDefinition),
+}
+
+impl From<StructureDefinition> for DataTypeDefinition {
+    fn from(value: StructureDefinition) -> Self {
+        DataTypeDefinition::Structure(value)
+    }
+}
+
+impl From<EnumDefinition> for DataTypeDefinition {
+    fn from(value: EnumDefinition) -> Self {
+        DataTypeDefinition::Enum(value)
+    }
+}
+
+impl From<&DataTypeDefinition> for ExtensionObject {
+    fn from(value: &DataTypeDefinition) -> Self {
+        match value {
+            DataTypeDefinition::Structure(value) => ExtensionObject::from_encodable(ObjectId::StructureDefinition_Encoding_DefaultBinary, value),
+            DataTypeDefinition::Enum(value) => ExtensionObject::from_encodable(ObjectId::EnumDefinition_Encoding_DefaultBinary, value),
+        }
+    }
+}
+
+impl From<&DataTypeDefinition> for Variant {
+    fn from(value: &DataTypeDefinition) -> Self {
+        Variant::from(ExtensionObject::from(value))
+    }
+}
+
+impl DataTypeDefinition {
+    /// Decodes a definition from an extension object holding a `StructureDefinition` or an
+    /// `EnumDefinition`.
+    pub fn from_extension_object(value: &ExtensionObject, decoding_limits: &DecodingLimits) -> Result<DataTypeDefinition, StatusCode> {
+        match value.object_id() {
+            Ok(ObjectId::StructureDefinition_Encoding_DefaultBinary) => {
+                value.decode_inner::<StructureDefinition>(decoding_limits).map(DataTypeDefinition::Structure)
+            }
+            Ok(ObjectId::EnumDefinition_Encoding_DefaultBinary) => {
+                value.decode_inner::<EnumDefinition>(decoding_limits).map(DataTypeDefinition::Enum)
+            }
+            _ => {
+                error!("Extension object {} is not a data type definition", value.node_id);
+                Err(StatusCode::BadDataTypeIdUnknown)
+            }
+        }
+    }
+
+    /// Returns the structure definition if this is one
+    pub fn as_structure(&self) -> Option<&StructureDefinition> {
+        if let DataTypeDefinition::Structure(ref value) = self { Some(value) } else { None }
+    }
+
+    /// Returns the enum definition if this is one
+    pub fn as_enum(&self) -> Option<&EnumDefinition> {
+        if let DataTypeDefinition::Enum(ref value) = self { Some(value) } else { None }
+    }
+}
@@ -10,6 +10,7 @@ use crate::{
     byte_string::ByteString,
     date_time::*,
     encoding::*,
+    json::*,
     guid::Guid,
     localized_text::LocalizedText,
     node_id::NodeId,
@@ -308,6 +309,43 @@ impl Default for DataValue {
     }
 }
 
+/// Data values are JSON objects holding the members which are present. A Good status and zero
+/// picoseconds are omitted. An empty data value is null.
+impl JsonEncoder<DataValue> for DataValue {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        if let Some(ref v) = self.value { write_json_field(&mut object, "Value", v, ctx); }
+        if let Some(ref v) = self.status {
+            if *v != StatusCode::Good { write_json_field(&mut object, "Status", v, ctx); }
+        }
+        if let Some(ref v) = self.source_timestamp { write_json_field(&mut object, "SourceTimestamp", v, ctx); }
+        if let Some(ref v) = self.source_picoseconds {
+            if *v != 0 { write_json_field(&mut object, "SourcePicoseconds", v, ctx); }
+        }
+        if let Some(ref v) = self.server_timestamp { write_json_field(&mut object, "ServerTimestamp", v, ctx); }
+        if let Some(ref v) = self.server_picoseconds {
+            if *v != 0 { write_json_field(&mut object, "ServerPicoseconds", v, ctx); }
+        }
+        if object.is_empty() {
+            JsonValue::Null
+        } else {
+            JsonValue::Object(object)
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(DataValue {
+            value: read_json_optional_field(object, "Value", decoding_limits)?,
+            status: read_json_optional_field(object, "Status", decoding_limits)?,
+            source_timestamp: read_json_optional_field(object, "SourceTimestamp", decoding_limits)?,
+            source_picoseconds: read_json_optional_field(object, "SourcePicoseconds", decoding_limits)?,
+            server_timestamp: read_json_optional_field(object, "ServerTimestamp", decoding_limits)?,
+            server_picoseconds: read_json_optional_field(object, "ServerPicoseconds", decoding_limits)?,
+        })
+    }
+}
+
 impl DataValue {
     /// Creates a `DataValue` from the supplied value with nothing else.
     pub fn value_only<V>(value: V) -> DataValue where V: Into<Variant> {
@@ -13,7 +13,7 @@ use std::{
 use chrono::{self, Datelike, Timelike, TimeZone, Utc};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::encoding::*;
+use crate::{encoding::*, json::*, status_codes::StatusCode};
 
 const NANOS_PER_SECOND: i64 = 1_000_000_000;
 const NANOS_PER_TICK: i64 = 100;
@@ -269,3 +269,22 @@ impl DateTime {
         seconds * TICKS_PER_SECOND + nanos / NANOS_PER_TICK
     }
 }
+
+/// Date times are ISO 8601 JSON strings in UTC, a null date time is null
+impl JsonEncoder<DateTime> for DateTime {
+    fn encode_json(&self, _: &JsonContext) -> JsonValue {
+        if self.is_null() {
+            JsonValue::Null
+        } else {
+            let date_time = if self.checked_ticks() == i64::MAX { Self::endtimes_chrono() } else { self.date_time };
+            JsonValue::from(date_time.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        match json_str(value, decoding_limits)? {
+            Some(value) => DateTime::from_str(value).map_err(|_| StatusCode::BadDecodingError),
+            None => Ok(DateTime::null()),
+        }
+    }
+}
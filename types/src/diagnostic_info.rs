@@ -8,6 +8,7 @@ use std::io::{Read, Write};
 
 use crate::{
     encoding::*,
+    json::*,
     status_codes::StatusCode,
     string::UAString,
 };
@@ -218,3 +219,36 @@ impl DiagnosticInfo {
         encoding_mask
     }
 }
+
+/// Diagnostic info is a JSON object holding the members which are present. An empty diagnostic
+/// info is null.
+impl JsonEncoder<DiagnosticInfo> for DiagnosticInfo {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        if let Some(ref v) = self.symbolic_id { write_json_field(&mut object, "SymbolicId", v, ctx); }
+        if let Some(ref v) = self.namespace_uri { write_json_field(&mut object, "NamespaceUri", v, ctx); }
+        if let Some(ref v) = self.locale { write_json_field(&mut object, "Locale", v, ctx); }
+        if let Some(ref v) = self.localized_text { write_json_field(&mut object, "LocalizedText", v, ctx); }
+        if let Some(ref v) = self.additional_info { write_json_field(&mut object, "AdditionalInfo", v, ctx); }
+        if let Some(ref v) = self.inner_status_code { write_json_field(&mut object, "InnerStatusCode", v, ctx); }
+        if let Some(ref v) = self.inner_diagnostic_info { write_json_field(&mut object, "InnerDiagnosticInfo", v.as_ref(), ctx); }
+        if object.is_empty() {
+            JsonValue::Null
+        } else {
+            JsonValue::Object(object)
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(DiagnosticInfo {
+            symbolic_id: read_json_optional_field(object, "SymbolicId", decoding_limits)?,
+            namespace_uri: read_json_optional_field(object, "NamespaceUri", decoding_limits)?,
+            locale: read_json_optional_field(object, "Locale", decoding_limits)?,
+            localized_text: read_json_optional_field(object, "LocalizedText", decoding_limits)?,
+            additional_info: read_json_optional_field(object, "AdditionalInfo", decoding_limits)?,
+            inner_status_code: read_json_optional_field(object, "InnerStatusCode", decoding_limits)?,
+            inner_diagnostic_info: read_json_optional_field::<DiagnosticInfo>(object, "InnerDiagnosticInfo", decoding_limits)?.map(Box::new),
+        })
+    }
+}
@@ -0,0 +1,542 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! Contains `DynamicStructure`, a generic value for structures that are not compiled into this
+//! crate, and `DynamicTypes` which decodes and encodes them with the `DataTypeDefinition` of
+//! their data type.
+
+use std::{
+    collections::HashMap,
+    io::{Cursor, Read, Write},
+};
+
+use crate::{
+    byte_string::ByteString,
+    data_type_definition::DataTypeDefinition,
+    encoding::*,
+    extension_object::{ExtensionObject, ExtensionObjectEncoding},
+    node_id::{Identifier, NodeId},
+    node_ids::DataTypeId,
+    service_types::{StructureDefinition, StructureType},
+    status_codes::StatusCode,
+    variant::{Variant, VariantTypeId},
+};
+
+/// The value of a field of a `DynamicStructure`
+#[derive(Debug, Clone, PartialEq)]
+pub enum DynamicValue {
+    /// A value of a built-in type. Enumerations are `Int32` values.
+    Scalar(Variant),
+    /// A nested structure
+    Structure(DynamicStructure),
+    /// The elements of an array field
+    Array(Vec<DynamicValue>),
+}
+
+impl<T> From<T> for DynamicValue where T: Into<Variant> {
+    fn from(value: T) -> Self {
+        DynamicValue::Scalar(value.into())
+    }
+}
+
+impl From<DynamicStructure> for DynamicValue {
+    fn from(value: DynamicStructure) -> Self {
+        DynamicValue::Structure(value)
+    }
+}
+
+impl From<Vec<DynamicValue>> for DynamicValue {
+    fn from(value: Vec<DynamicValue>) -> Self {
+        DynamicValue::Array(value)
+    }
+}
+
+/// A structure held as its data type id and a list of named fields. Optional fields that are not
+/// specified are left out, and a union holds only the field that is set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DynamicStructure {
+    /// The id of the structure's data type
+    pub data_type_id: NodeId,
+    /// The fields in the order of the data type definition
+    pub fields: Vec<(String, DynamicValue)>,
+}
+
+impl DynamicStructure {
+    pub fn new<T>(data_type_id: T) -> DynamicStructure where T: Into<NodeId> {
+        DynamicStructure {
+            data_type_id: data_type_id.into(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// Returns the value of a field
+    pub fn field(&self, name: &str) -> Option<&DynamicValue> {
+        self.fields.iter().find(|(n, _)| n == name).map(|(_, v)| v)
+    }
+
+    /// Sets the value of a field, replacing any previous value
+    pub fn set_field<V>(&mut self, name: &str, value: V) where V: Into<DynamicValue> {
+        let value = value.into();
+        if let Some(field) = self.fields.iter_mut().find(|(n, _)| n == name) {
+            field.1 = value;
+        } else {
+            self.fields.push((name.to_string(), value));
+        }
+    }
+
+    /// Sets the value of a field and returns the structure
+    pub fn with_field<V>(mut self, name: &str, value: V) -> Self where V: Into<DynamicValue> {
+        self.set_field(name, value);
+        self
+    }
+}
+
+/// How the value of a data type is encoded
+enum FieldType<'a> {
+    /// A built-in type, identified by its id
+    BuiltIn(u32),
+    /// An enumeration, encoded as an Int32
+    Enum,
+    /// A structure encoded inline
+    Structure(&'a StructureDefinition),
+}
+
+/// The data types that `DynamicStructure` values can be decoded from and encoded to. A type is
+/// known if it is a built-in type, has a `DataTypeDefinition` or is a subtype of a built-in
+/// type. Clients can learn the definitions from the DataTypeDefinition attribute of the server's
+/// data type nodes.
+#[derive(Debug, Clone)]
+pub struct DynamicTypes {
+    /// Structure and enum definitions by data type id
+    definitions: HashMap<NodeId, DataTypeDefinition>,
+    /// Subtypes of built-in types by data type id, mapped to the built-in type
+    simple_types: HashMap<NodeId, NodeId>,
+    /// Data type ids by encoding id
+    encodings: HashMap<NodeId, NodeId>,
+}
+
+impl Default for DynamicTypes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DynamicTypes {
+    pub fn new() -> DynamicTypes {
+        let mut types = DynamicTypes {
+            definitions: HashMap::new(),
+            simple_types: HashMap::new(),
+            encodings: HashMap::new(),
+        };
+        // Subtypes of built-in types that are commonly used in structures
+        let simple_types = [
+            (DataTypeId::IntegerId, DataTypeId::UInt32),
+            (DataTypeId::Counter, DataTypeId::UInt32),
+            (DataTypeId::Duration, DataTypeId::Double),
+            (DataTypeId::NumericRange, DataTypeId::String),
+            (DataTypeId::Time, DataTypeId::String),
+            (DataTypeId::Date, DataTypeId::DateTime),
+            (DataTypeId::UtcTime, DataTypeId::DateTime),
+            (DataTypeId::LocaleId, DataTypeId::String),
+        ];
+        simple_types.iter().for_each(|(data_type_id, built_in_type)| {
+            types.add_simple_type(*data_type_id, *built_in_type);
+        });
+        types
+    }
+
+    /// Adds the definition of a structure or enum. The default encoding id of a structure is
+    /// registered as an encoding of the data type.
+    pub fn add_definition<T>(&mut self, data_type_id: T, definition: DataTypeDefinition) where T: Into<NodeId> {
+        let data_type_id = data_type_id.into();
+        if let DataTypeDefinition::Structure(ref definition) = definition {
+            if !definition.default_encoding_id.is_null() {
+                self.encodings.insert(definition.default_encoding_id.clone(), data_type_id.clone());
+            }
+        }
+        self.definitions.insert(data_type_id, definition);
+    }
+
+    /// Adds a data type that is encoded as the built-in type it derives from
+    pub fn add_simple_type<T, S>(&mut self, data_type_id: T, built_in_type: S) where T: Into<NodeId>, S: Into<NodeId> {
+        self.simple_types.insert(data_type_id.into(), built_in_type.into());
+    }
+
+    /// Adds the binary encoding of a data type
+    pub fn add_encoding<T, S>(&mut self, encoding_id: T, data_type_id: S) where T: Into<NodeId>, S: Into<NodeId> {
+        self.encodings.insert(encoding_id.into(), data_type_id.into());
+    }
+
+    /// Returns the data type of a binary encoding
+    pub fn data_type_of_encoding(&self, encoding_id: &NodeId) -> Option<&NodeId> {
+        self.encodings.get(encoding_id)
+    }
+
+    /// Returns the definition of a data type
+    pub fn definition(&self, data_type_id: &NodeId) -> Option<&DataTypeDefinition> {
+        self.definitions.get(data_type_id)
+    }
+
+    /// Tests if values of the data type can be decoded and encoded, not counting the types of
+    /// its fields
+    pub fn is_known(&self, data_type_id: &NodeId) -> bool {
+        Self::built_in_type(data_type_id).is_some() || self.definitions.contains_key(data_type_id) || self.simple_types.contains_key(data_type_id)
+    }
+
+    /// Returns the data types used by the data type, or by the fields of its structures, that are
+    /// not known. These have to be added before a value of the data type can be decoded.
+    pub fn unknown_types(&self, data_type_id: &NodeId) -> Vec<NodeId> {
+        let mut unknown = Vec::new();
+        let mut visited = Vec::new();
+        self.find_unknown_types(data_type_id, &mut visited, &mut unknown);
+        unknown
+    }
+
+    fn find_unknown_types(&self, data_type_id: &NodeId, visited: &mut Vec<NodeId>, unknown: &mut Vec<NodeId>) {
+        if visited.contains(data_type_id) {
+            return;
+        }
+        visited.push(data_type_id.clone());
+        if !self.is_known(data_type_id) {
+            unknown.push(data_type_id.clone());
+        } else if let Some(DataTypeDefinition::Structure(definition)) = self.definitions.get(data_type_id) {
+            definition.fields.iter().flatten().for_each(|field| {
+                self.find_unknown_types(&field.data_type, visited, unknown);
+            });
+        }
+    }
+
+    /// Decodes an extension object with a binary body into a structure
+    pub fn decode(&self, value: &ExtensionObject, decoding_limits: &DecodingLimits) -> EncodingResult<DynamicStructure> {
+        let data_type_id = self.encodings.get(&value.node_id).ok_or_else(|| {
+            error!("Extension object encoding {} is not known", value.node_id);
+            StatusCode::BadDataTypeIdUnknown
+        })?;
+        let definition = self.structure_definition(data_type_id)?;
+        match value.body {
+            ExtensionObjectEncoding::ByteString(ByteString { value: Some(ref body) }) => {
+                let mut stream = Cursor::new(body);
+                self.decode_structure(&mut stream, data_type_id, definition, decoding_limits)
+            }
+            ExtensionObjectEncoding::XmlElement(_) => {
+                error!("Extension object {} has an XML body which cannot be decoded", value.node_id);
+                Err(StatusCode::BadDataEncodingUnsupported)
+            }
+            _ => {
+                error!("Extension object {} has no body", value.node_id);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+
+    /// Encodes a structure as an extension object with a binary body and the structure's default
+    /// encoding as its type id
+    pub fn encode(&self, value: &DynamicStructure) -> EncodingResult<ExtensionObject> {
+        let definition = self.structure_definition(&value.data_type_id)?;
+        let encoding_id = if definition.default_encoding_id.is_null() {
+            self.encodings.iter().find(|(_, data_type_id)| **data_type_id == value.data_type_id).map(|(encoding_id, _)| encoding_id.clone())
+        } else {
+            Some(definition.default_encoding_id.clone())
+        }.ok_or_else(|| {
+            error!("Data type {} has no binary encoding", value.data_type_id);
+            StatusCode::BadDataEncodingUnsupported
+        })?;
+        let mut stream = Cursor::new(Vec::new());
+        self.encode_structure(&mut stream, value, definition)?;
+        Ok(ExtensionObject {
+            node_id: encoding_id,
+            body: ExtensionObjectEncoding::ByteString(ByteString::from(stream.into_inner())),
+        })
+    }
+
+    fn structure_definition(&self, data_type_id: &NodeId) -> EncodingResult<&StructureDefinition> {
+        match self.definitions.get(data_type_id) {
+            Some(DataTypeDefinition::Structure(definition)) => Ok(definition),
+            _ => {
+                error!("Data type {} is not a known structure", data_type_id);
+                Err(StatusCode::BadDataTypeIdUnknown)
+            }
+        }
+    }
+
+    /// Returns the id of a built-in type if the data type is one. Abstract numeric types and
+    /// `BaseDataType` are encoded as a `Variant`, `Structure` as an `ExtensionObject`.
+    fn built_in_type(data_type_id: &NodeId) -> Option<u32> {
+        match data_type_id.identifier {
+            Identifier::Numeric(id) if data_type_id.namespace == 0 => {
+                match id {
+                    1..=25 => Some(id),
+                    id if id == DataTypeId::Number as u32 || id == DataTypeId::Integer as u32 || id == DataTypeId::UInteger as u32 => {
+                        Some(DataTypeId::BaseDataType as u32)
+                    }
+                    id if id == DataTypeId::Enumeration as u32 => Some(DataTypeId::Int32 as u32),
+                    _ => None
+                }
+            }
+            _ => None
+        }
+    }
+
+    fn field_type(&self, data_type_id: &NodeId) -> EncodingResult<FieldType<'_>> {
+        if let Some(id) = Self::built_in_type(data_type_id) {
+            Ok(FieldType::BuiltIn(id))
+        } else if let Some(id) = self.simple_types.get(data_type_id).and_then(Self::built_in_type) {
+            Ok(FieldType::BuiltIn(id))
+        } else {
+            match self.definitions.get(data_type_id) {
+                Some(DataTypeDefinition::Structure(definition)) => Ok(FieldType::Structure(definition)),
+                Some(DataTypeDefinition::Enum(_)) => Ok(FieldType::Enum),
+                None => {
+                    error!("Data type {} is not known", data_type_id);
+                    Err(StatusCode::BadDataTypeIdUnknown)
+                }
+            }
+        }
+    }
+
+    fn decode_structure<S: Read>(&self, stream: &mut S, data_type_id: &NodeId, definition: &StructureDefinition, decoding_limits: &DecodingLimits) -> EncodingResult<DynamicStructure> {
+        let mut value = DynamicStructure::new(data_type_id.clone());
+        let fields = definition.fields.as_deref().unwrap_or(&[]);
+        match definition.structure_type {
+            StructureType::Structure => {
+                for field in fields {
+                    let field_value = self.decode_field(stream, &field.data_type, field.value_rank, decoding_limits)?;
+                    value.fields.push((field.name.to_string(), field_value));
+                }
+            }
+            StructureType::StructureWithOptionalFields => {
+                // Each optional field has a bit in the encoding mask, in order
+                let encoding_mask = u32::decode(stream, decoding_limits)?;
+                let mut optional_idx = 0;
+                for field in fields {
+                    if field.is_optional {
+                        let is_set = encoding_mask & (1 << optional_idx) != 0;
+                        optional_idx += 1;
+                        if !is_set {
+                            continue;
+                        }
+                    }
+                    let field_value = self.decode_field(stream, &field.data_type, field.value_rank, decoding_limits)?;
+                    value.fields.push((field.name.to_string(), field_value));
+                }
+            }
+            StructureType::Union => {
+                // The switch field is the 1-based index of the field that follows, or 0 for none
+                let switch_field = u32::decode(stream, decoding_limits)? as usize;
+                if switch_field > fields.len() {
+                    error!("Union {} has an invalid switch field {}", data_type_id, switch_field);
+                    return Err(StatusCode::BadDecodingError);
+                } else if switch_field > 0 {
+                    let field = &fields[switch_field - 1];
+                    let field_value = self.decode_field(stream, &field.data_type, field.value_rank, decoding_limits)?;
+                    value.fields.push((field.name.to_string(), field_value));
+                }
+            }
+        }
+        Ok(value)
+    }
+
+    fn decode_field<S: Read>(&self, stream: &mut S, data_type_id: &NodeId, value_rank: i32, decoding_limits: &DecodingLimits) -> EncodingResult<DynamicValue> {
+        if value_rank > 1 {
+            error!("Fields with more than one array dimension are not supported");
+            Err(StatusCode::BadDataEncodingUnsupported)
+        } else if value_rank >= 0 {
+            let len = i32::decode(stream, decoding_limits)?;
+            if len > decoding_limits.max_array_length as i32 {
+                error!("Array length {} exceeds decoding limit {}", len, decoding_limits.max_array_length);
+                return Err(StatusCode::BadEncodingLimitsExceeded);
+            }
+            let mut values = Vec::with_capacity(len.max(0) as usize);
+            for _ in 0..len {
+                values.push(self.decode_value(stream, data_type_id, decoding_limits)?);
+            }
+            Ok(DynamicValue::Array(values))
+        } else {
+            self.decode_value(stream, data_type_id, decoding_limits)
+        }
+    }
+
+    fn decode_value<S: Read>(&self, stream: &mut S, data_type_id: &NodeId, decoding_limits: &DecodingLimits) -> EncodingResult<DynamicValue> {
+        match self.field_type(data_type_id)? {
+            FieldType::BuiltIn(id) if id == DataTypeId::Structure as u32 => {
+                // Decode the nested extension object if its type is known
+                let value = ExtensionObject::decode(stream, decoding_limits)?;
+                if !value.is_empty() && self.encodings.contains_key(&value.node_id) {
+                    Ok(DynamicValue::Structure(self.decode(&value, decoding_limits)?))
+                } else {
+                    Ok(DynamicValue::Scalar(value.into()))
+                }
+            }
+            FieldType::BuiltIn(id) if id == DataTypeId::BaseDataType as u32 => {
+                Ok(DynamicValue::Scalar(Variant::decode(stream, decoding_limits)?))
+            }
+            FieldType::BuiltIn(id) if id == DataTypeId::DataValue as u32 || id == DataTypeId::DiagnosticInfo as u32 => {
+                error!("Fields of type {} are not supported", data_type_id);
+                Err(StatusCode::BadDataEncodingUnsupported)
+            }
+            FieldType::BuiltIn(id) => {
+                Ok(DynamicValue::Scalar(Variant::decode_variant_value(stream, id as u8, decoding_limits)?))
+            }
+            FieldType::Enum => {
+                Ok(DynamicValue::Scalar(i32::decode(stream, decoding_limits)?.into()))
+            }
+            FieldType::Structure(definition) => {
+                Ok(DynamicValue::Structure(self.decode_structure(stream, data_type_id, definition, decoding_limits)?))
+            }
+        }
+    }
+
+    fn encode_structure<S: Write>(&self, stream: &mut S, value: &DynamicStructure, definition: &StructureDefinition) -> EncodingResult<usize> {
+        let fields = definition.fields.as_deref().unwrap_or(&[]);
+        let mut size = 0;
+        match definition.structure_type {
+            StructureType::Structure => {
+                for field in fields {
+                    let field_value = value.field(field.name.as_ref()).ok_or_else(|| {
+                        error!("Structure {} has no value for field {}", value.data_type_id, field.name);
+                        StatusCode::BadEncodingError
+                    })?;
+                    size += self.encode_field(stream, &field.data_type, field.value_rank, field_value)?;
+                }
+            }
+            StructureType::StructureWithOptionalFields => {
+                let encoding_mask = fields.iter()
+                    .filter(|field| field.is_optional)
+                    .enumerate()
+                    .filter(|(_, field)| value.field(field.name.as_ref()).is_some())
+                    .fold(0u32, |mask, (idx, _)| mask | (1 << idx));
+                size += encoding_mask.encode(stream)?;
+                for field in fields {
+                    match value.field(field.name.as_ref()) {
+                        Some(field_value) => {
+                            size += self.encode_field(stream, &field.data_type, field.value_rank, field_value)?;
+                        }
+                        None if field.is_optional => {}
+                        None => {
+                            error!("Structure {} has no value for field {}", value.data_type_id, field.name);
+                            return Err(StatusCode::BadEncodingError);
+                        }
+                    }
+                }
+            }
+            StructureType::Union => {
+                let field = fields.iter().enumerate()
+                    .find_map(|(idx, field)| value.field(field.name.as_ref()).map(|v| (idx, field, v)));
+                if let Some((idx, field, field_value)) = field {
+                    size += ((idx + 1) as u32).encode(stream)?;
+                    size += self.encode_field(stream, &field.data_type, field.value_rank, field_value)?;
+                } else {
+                    size += 0u32.encode(stream)?;
+                }
+            }
+        }
+        Ok(size)
+    }
+
+    fn encode_field<S: Write>(&self, stream: &mut S, data_type_id: &NodeId, value_rank: i32, value: &DynamicValue) -> EncodingResult<usize> {
+        if value_rank > 1 {
+            error!("Fields with more than one array dimension are not supported");
+            Err(StatusCode::BadDataEncodingUnsupported)
+        } else if value_rank >= 0 {
+            match value {
+                DynamicValue::Array(values) => {
+                    let mut size = (values.len() as i32).encode(stream)?;
+                    for value in values {
+                        size += self.encode_value(stream, data_type_id, value)?;
+                    }
+                    Ok(size)
+                }
+                DynamicValue::Scalar(Variant::Array(array)) => {
+                    let mut size = (array.values.len() as i32).encode(stream)?;
+                    for value in &array.values {
+                        size += self.encode_value(stream, data_type_id, &DynamicValue::Scalar(value.clone()))?;
+                    }
+                    Ok(size)
+                }
+                DynamicValue::Scalar(Variant::Empty) => (-1i32).encode(stream),
+                _ => {
+                    error!("Array field of type {} has a scalar value", data_type_id);
+                    Err(StatusCode::BadTypeMismatch)
+                }
+            }
+        } else {
+            self.encode_value(stream, data_type_id, value)
+        }
+    }
+
+    fn encode_value<S: Write>(&self, stream: &mut S, data_type_id: &NodeId, value: &DynamicValue) -> EncodingResult<usize> {
+        match (self.field_type(data_type_id)?, value) {
+            (FieldType::BuiltIn(id), DynamicValue::Structure(value)) if id == DataTypeId::Structure as u32 => {
+                self.encode(value)?.encode(stream)
+            }
+            (FieldType::BuiltIn(id), DynamicValue::Scalar(value)) if id == DataTypeId::Structure as u32 => {
+                match value {
+                    Variant::ExtensionObject(value) => value.encode(stream),
+                    Variant::Empty => ExtensionObject::null().encode(stream),
+                    _ => Err(StatusCode::BadTypeMismatch)
+                }
+            }
+            (FieldType::BuiltIn(id), DynamicValue::Scalar(value)) if id == DataTypeId::BaseDataType as u32 => {
+                value.encode(stream)
+            }
+            (FieldType::BuiltIn(id), DynamicValue::Scalar(value)) => {
+                let value = Self::coerce(value, id).ok_or_else(|| {
+                    error!("Value {:?} cannot be encoded as data type {}", value, data_type_id);
+                    StatusCode::BadTypeMismatch
+                })?;
+                Variant::encode_variant_value(stream, &value)
+            }
+            (FieldType::Enum, DynamicValue::Scalar(value)) => {
+                match value.convert(VariantTypeId::Int32) {
+                    Variant::Int32(value) => value.encode(stream),
+                    _ => Err(StatusCode::BadTypeMismatch)
+                }
+            }
+            (FieldType::Structure(definition), DynamicValue::Structure(value)) => {
+                self.encode_structure(stream, value, definition)
+            }
+            _ => {
+                error!("Value {:?} does not match data type {}", value, data_type_id);
+                Err(StatusCode::BadTypeMismatch)
+            }
+        }
+    }
+
+    /// Converts a value to the built-in type of a field
+    fn coerce(value: &Variant, built_in_type: u32) -> Option<Variant> {
+        let type_id = match built_in_type {
+            1 => VariantTypeId::Boolean,
+            2 => VariantTypeId::SByte,
+            3 => VariantTypeId::Byte,
+            4 => VariantTypeId::Int16,
+            5 => VariantTypeId::UInt16,
+            6 => VariantTypeId::Int32,
+            7 => VariantTypeId::UInt32,
+            8 => VariantTypeId::Int64,
+            9 => VariantTypeId::UInt64,
+            10 => VariantTypeId::Float,
+            11 => VariantTypeId::Double,
+            12 => VariantTypeId::String,
+            13 => VariantTypeId::DateTime,
+            14 => VariantTypeId::Guid,
+            15 => VariantTypeId::ByteString,
+            16 => VariantTypeId::XmlElement,
+            17 => VariantTypeId::NodeId,
+            18 => VariantTypeId::ExpandedNodeId,
+            19 => VariantTypeId::StatusCode,
+            20 => VariantTypeId::QualifiedName,
+            21 => VariantTypeId::LocalizedText,
+            _ => return None
+        };
+        match (value, type_id) {
+            // Strings and XML elements have the same encoding
+            (Variant::String(value), VariantTypeId::XmlElement) => Some(Variant::XmlElement(value.clone())),
+            (value, type_id) if value.type_id() == type_id => Some(value.clone()),
+            (value, type_id) => {
+                let value = value.convert(type_id);
+                if value.type_id() == type_id { Some(value) } else { None }
+            }
+        }
+    }
+}
@@ -9,8 +9,10 @@ use std::io::{Cursor, Read, Write};
 use crate::{
     byte_string::ByteString,
     encoding::*,
+    json::*,
     node_id::NodeId,
     node_ids::ObjectId,
+    service_types,
     status_codes::StatusCode,
     string::XmlElement,
 };
@@ -97,6 +99,73 @@ impl BinaryEncoder<ExtensionObject> for ExtensionObject {
     }
 }
 
+/// Extension objects holding a known type are JSON objects with the TypeId of the type's JSON
+/// encoding and the type encoded as JSON in the Body. Other bodies are written as they are with an
+/// Encoding member of 1 for a byte string, which is written in base64, or 2 for XML. The
+/// non-reversible form is just the body. A null extension object is null.
+impl JsonEncoder<ExtensionObject> for ExtensionObject {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let (type_id, encoding, body) = match self.body {
+            ExtensionObjectEncoding::None => {
+                if self.is_null() {
+                    return JsonValue::Null;
+                }
+                (self.node_id.clone(), None, JsonValue::Null)
+            }
+            ExtensionObjectEncoding::ByteString(ref value) => {
+                match service_types::encode_json_body(self, ctx) {
+                    Some(Ok((type_id, body))) => (type_id.into(), None, body),
+                    Some(Err(_)) => {
+                        error!("Extension object {} does not decode as its type so its body is written in base64", self.node_id);
+                        (self.node_id.clone(), Some(1), value.encode_json(ctx))
+                    }
+                    None => (self.node_id.clone(), Some(1), value.encode_json(ctx))
+                }
+            }
+            ExtensionObjectEncoding::XmlElement(ref value) => (self.node_id.clone(), Some(2), value.encode_json(ctx)),
+        };
+        if ctx.reversible {
+            let mut object = JsonObject::new();
+            write_json_field(&mut object, "TypeId", &type_id, ctx);
+            if let Some(encoding) = encoding {
+                object.insert("Encoding".to_string(), JsonValue::from(encoding));
+            }
+            if !body.is_null() {
+                object.insert("Body".to_string(), body);
+            }
+            JsonValue::Object(object)
+        } else {
+            body
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        let node_id: NodeId = read_json_field(object, "TypeId", decoding_limits)?;
+        let encoding: u8 = read_json_field(object, "Encoding", decoding_limits)?;
+        let body = object.get("Body").unwrap_or(&JsonValue::Null);
+        let extension_object = match encoding {
+            0 if body.is_null() => ExtensionObject { node_id, body: ExtensionObjectEncoding::None },
+            0 => {
+                match service_types::decode_json_body(&node_id, body, decoding_limits) {
+                    Some(result) => result?,
+                    None => {
+                        error!("Extension object {} has a JSON body of an unknown type", node_id);
+                        return Err(StatusCode::BadDecodingError);
+                    }
+                }
+            }
+            1 => ExtensionObject { node_id, body: ExtensionObjectEncoding::ByteString(ByteString::decode_json(body, decoding_limits)?) },
+            2 => ExtensionObject { node_id, body: ExtensionObjectEncoding::XmlElement(XmlElement::decode_json(body, decoding_limits)?) },
+            encoding => {
+                error!("Invalid encoding {} in JSON extension object", encoding);
+                return Err(StatusCode::BadDecodingError);
+            }
+        };
+        Ok(extension_object)
+    }
+}
+
 impl ExtensionObject {
     /// Creates a null extension object, i.e. one with no value or payload
     pub fn null() -> ExtensionObject {
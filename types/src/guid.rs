@@ -12,7 +12,7 @@ use std::{
 };
 use uuid::Uuid;
 
-use crate::encoding::*;
+use crate::{encoding::*, json::*, status_codes::StatusCode};
 
 /// A Guid is a 16 byte Globally Unique Identifier.
 #[derive(Eq, PartialEq, Clone, Hash)]
@@ -114,3 +114,17 @@ impl Guid {
         Guid { uuid: Uuid::from_bytes(bytes) }
     }
 }
+
+/// Guids are JSON strings
+impl JsonEncoder<Guid> for Guid {
+    fn encode_json(&self, _: &JsonContext) -> JsonValue {
+        JsonValue::from(format!("{:?}", self))
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        match json_str(value, decoding_limits)? {
+            Some(value) => Guid::from_str(value).map_err(|_| StatusCode::BadDecodingError),
+            None => Ok(Guid::null()),
+        }
+    }
+}
@@ -0,0 +1,405 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! Contains the `JsonEncoder` trait and helpers for the OPC UA JSON encoding described in
+//! Part 6 5.4.
+//!
+//! There are two forms of the encoding. The reversible form holds enough information to decode
+//! the value back to what it was, e.g. the type of each variant and the namespace index of each
+//! node id. The non-reversible form is meant for consumers that do not understand OPC UA, so
+//! variants are written as their bare values, enums as their names and namespaces as uris. Only
+//! the reversible form can be decoded.
+
+use std::{convert::TryFrom, str::FromStr};
+
+pub use serde_json::{Map as JsonMap, Value as JsonValue};
+
+use crate::{
+    byte_string::ByteString,
+    encoding::{DecodingLimits, EncodingResult},
+    status_codes::StatusCode,
+    string::UAString,
+};
+
+/// A JSON object. Members keep the order they were inserted in, which is the order of the fields
+/// of a structure.
+pub type JsonObject = JsonMap<String, JsonValue>;
+
+/// Controls how values are written as JSON.
+#[derive(Debug, Clone)]
+pub struct JsonContext {
+    /// Writes the reversible form if true, otherwise the non-reversible form
+    pub reversible: bool,
+    /// The namespace table. The non-reversible form writes namespace uris from this table in place
+    /// of namespace indices greater than 1.
+    pub namespaces: Vec<String>,
+    /// The server table. The non-reversible form writes server uris from this table in place of
+    /// server indices.
+    pub server_uris: Vec<String>,
+}
+
+impl Default for JsonContext {
+    fn default() -> Self {
+        JsonContext::reversible()
+    }
+}
+
+impl JsonContext {
+    /// A context for the reversible form
+    pub fn reversible() -> JsonContext {
+        JsonContext {
+            reversible: true,
+            namespaces: Vec::new(),
+            server_uris: Vec::new(),
+        }
+    }
+
+    /// A context for the non-reversible form, resolving namespace indices against the supplied
+    /// namespace table
+    pub fn non_reversible<S>(namespaces: &[S]) -> JsonContext where S: AsRef<str> {
+        JsonContext {
+            reversible: false,
+            namespaces: namespaces.iter().map(|ns| ns.as_ref().to_string()).collect(),
+            server_uris: Vec::new(),
+        }
+    }
+
+    /// Returns the value to write for a namespace index. Indices 0 and 1 and those that are not in
+    /// the namespace table are written as numbers.
+    pub(crate) fn namespace(&self, namespace: u16) -> JsonValue {
+        if !self.reversible && namespace > 1 {
+            if let Some(uri) = self.namespaces.get(namespace as usize) {
+                return JsonValue::from(uri.as_str());
+            }
+        }
+        JsonValue::from(namespace)
+    }
+
+    /// Returns the value to write for a server index
+    pub(crate) fn server_uri(&self, server_index: u32) -> JsonValue {
+        if !self.reversible {
+            if let Some(uri) = self.server_uris.get(server_index as usize) {
+                return JsonValue::from(uri.as_str());
+            }
+        }
+        JsonValue::from(server_index)
+    }
+}
+
+/// OPC UA JSON encoding interface. Anything that encodes to JSON must implement this. `T` is the
+/// type decoded from the JSON, i.e. it is the type implementing the trait.
+///
+/// A value which is null, e.g. a null string or node id, encodes as `JsonValue::Null` and is
+/// omitted from the object it is a field of. Decoding a null value returns the default, so
+/// omitted fields decode to their defaults.
+pub trait JsonEncoder<T> {
+    /// Encodes the value as JSON in the form set by the context
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue;
+
+    /// Decodes a value from its reversible JSON form
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<T>;
+
+    /// Encodes the value and returns it as a JSON string
+    fn to_json_string(&self, ctx: &JsonContext) -> String {
+        self.encode_json(ctx).to_string()
+    }
+
+    /// Decodes a value from a string holding its reversible JSON form
+    fn from_json_str(value: &str, decoding_limits: &DecodingLimits) -> EncodingResult<T> {
+        let value = JsonValue::from_str(value).map_err(|err| {
+            error!("Cannot parse JSON, error = {}", err);
+            StatusCode::BadDecodingError
+        })?;
+        Self::decode_json(&value, decoding_limits)
+    }
+}
+
+/// Writes a field to an object unless its value is null
+pub fn write_json_field<T>(object: &mut JsonObject, name: &str, value: &T, ctx: &JsonContext) where T: JsonEncoder<T> {
+    let value = value.encode_json(ctx);
+    if !value.is_null() {
+        object.insert(name.to_string(), value);
+    }
+}
+
+/// Reads a field from an object. A field that is missing decodes as null.
+pub fn read_json_field<T>(object: &JsonObject, name: &str, decoding_limits: &DecodingLimits) -> EncodingResult<T> where T: JsonEncoder<T> {
+    T::decode_json(object.get(name).unwrap_or(&JsonValue::Null), decoding_limits)
+}
+
+/// Reads a field which may be missing from an object
+pub(crate) fn read_json_optional_field<T>(object: &JsonObject, name: &str, decoding_limits: &DecodingLimits) -> EncodingResult<Option<T>> where T: JsonEncoder<T> {
+    match object.get(name) {
+        None | Some(JsonValue::Null) => Ok(None),
+        Some(value) => T::decode_json(value, decoding_limits).map(Some),
+    }
+}
+
+lazy_static! {
+    static ref EMPTY_OBJECT: JsonObject = JsonObject::new();
+}
+
+/// Returns the value as an object. Null is treated as an object without fields.
+pub fn json_object(value: &JsonValue) -> EncodingResult<&JsonObject> {
+    match value {
+        JsonValue::Object(object) => Ok(object),
+        JsonValue::Null => Ok(&EMPTY_OBJECT),
+        value => {
+            error!("Expected a JSON object, got {}", value);
+            Err(StatusCode::BadDecodingError)
+        }
+    }
+}
+
+/// Returns the value as a string, or `None` for null
+pub(crate) fn json_str<'a>(value: &'a JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Option<&'a str>> {
+    match value {
+        JsonValue::String(value) => {
+            if value.len() > decoding_limits.max_string_length {
+                error!("String length {} exceeds decoding limit {}", value.len(), decoding_limits.max_string_length);
+                Err(StatusCode::BadDecodingError)
+            } else {
+                Ok(Some(value))
+            }
+        }
+        JsonValue::Null => Ok(None),
+        value => {
+            error!("Expected a JSON string, got {}", value);
+            Err(StatusCode::BadDecodingError)
+        }
+    }
+}
+
+/// Returns the value as an array, or `None` for null
+pub(crate) fn json_array<'a>(value: &'a JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Option<&'a Vec<JsonValue>>> {
+    match value {
+        JsonValue::Array(values) => {
+            if values.len() > decoding_limits.max_array_length {
+                error!("Array length {} exceeds decoding limit {}", values.len(), decoding_limits.max_array_length);
+                Err(StatusCode::BadDecodingError)
+            } else {
+                Ok(Some(values))
+            }
+        }
+        JsonValue::Null => Ok(None),
+        value => {
+            error!("Expected a JSON array, got {}", value);
+            Err(StatusCode::BadDecodingError)
+        }
+    }
+}
+
+/// Arrays are JSON arrays, a null array is null
+impl<T> JsonEncoder<Option<Vec<T>>> for Option<Vec<T>> where T: JsonEncoder<T> {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        match self {
+            Some(values) => JsonValue::Array(values.iter().map(|v| v.encode_json(ctx)).collect()),
+            None => JsonValue::Null,
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        if let Some(values) = json_array(value, decoding_limits)? {
+            let values = values.iter()
+                .map(|v| T::decode_json(v, decoding_limits))
+                .collect::<EncodingResult<Vec<T>>>()?;
+            Ok(Some(values))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl JsonEncoder<bool> for bool {
+    fn encode_json(&self, _: &JsonContext) -> JsonValue {
+        JsonValue::Bool(*self)
+    }
+
+    fn decode_json(value: &JsonValue, _: &DecodingLimits) -> EncodingResult<Self> {
+        match value {
+            JsonValue::Bool(value) => Ok(*value),
+            JsonValue::Null => Ok(false),
+            value => {
+                error!("Expected a JSON boolean, got {}", value);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
+/// Integers up to 32-bits are JSON numbers
+macro_rules! json_number_impl {
+    ($t: ty) => {
+        impl JsonEncoder<$t> for $t {
+            fn encode_json(&self, _: &JsonContext) -> JsonValue {
+                JsonValue::from(*self)
+            }
+
+            fn decode_json(value: &JsonValue, _: &DecodingLimits) -> EncodingResult<Self> {
+                match value {
+                    JsonValue::Number(number) => {
+                        number.as_i64()
+                            .and_then(|v| <$t>::try_from(v).ok())
+                            .ok_or_else(|| {
+                                error!("JSON number {} is not a valid {}", number, stringify!($t));
+                                StatusCode::BadDecodingError
+                            })
+                    }
+                    JsonValue::Null => Ok(0),
+                    value => {
+                        error!("Expected a JSON number, got {}", value);
+                        Err(StatusCode::BadDecodingError)
+                    }
+                }
+            }
+        }
+    }
+}
+
+json_number_impl!(i8);
+json_number_impl!(u8);
+json_number_impl!(i16);
+json_number_impl!(u16);
+json_number_impl!(i32);
+json_number_impl!(u32);
+
+/// 64-bit integers are JSON strings since JSON numbers are doubles to most parsers
+macro_rules! json_int64_impl {
+    ($t: ty) => {
+        impl JsonEncoder<$t> for $t {
+            fn encode_json(&self, _: &JsonContext) -> JsonValue {
+                JsonValue::from(self.to_string())
+            }
+
+            fn decode_json(value: &JsonValue, _: &DecodingLimits) -> EncodingResult<Self> {
+                let result = match value {
+                    JsonValue::String(value) => <$t>::from_str(value).ok(),
+                    JsonValue::Number(number) => number.as_i64().and_then(|v| <$t>::try_from(v).ok())
+                        .or_else(|| number.as_u64().and_then(|v| <$t>::try_from(v).ok())),
+                    JsonValue::Null => Some(0),
+                    _ => None
+                };
+                result.ok_or_else(|| {
+                    error!("JSON value {} is not a valid {}", value, stringify!($t));
+                    StatusCode::BadDecodingError
+                })
+            }
+        }
+    }
+}
+
+json_int64_impl!(i64);
+json_int64_impl!(u64);
+
+/// Floats are JSON numbers, except for NaN and the infinities which are strings
+macro_rules! json_float_impl {
+    ($t: ty) => {
+        impl JsonEncoder<$t> for $t {
+            fn encode_json(&self, _: &JsonContext) -> JsonValue {
+                if self.is_nan() {
+                    JsonValue::from("NaN")
+                } else if self.is_infinite() {
+                    JsonValue::from(if self.is_sign_positive() { "Infinity" } else { "-Infinity" })
+                } else {
+                    JsonValue::from(*self)
+                }
+            }
+
+            fn decode_json(value: &JsonValue, _: &DecodingLimits) -> EncodingResult<Self> {
+                match value {
+                    JsonValue::Number(number) => Ok(number.as_f64().unwrap_or(0.0) as $t),
+                    JsonValue::String(value) if value == "NaN" => Ok(<$t>::NAN),
+                    JsonValue::String(value) if value == "Infinity" => Ok(<$t>::INFINITY),
+                    JsonValue::String(value) if value == "-Infinity" => Ok(<$t>::NEG_INFINITY),
+                    JsonValue::Null => Ok(0.0),
+                    value => {
+                        error!("Expected a JSON number, got {}", value);
+                        Err(StatusCode::BadDecodingError)
+                    }
+                }
+            }
+        }
+    }
+}
+
+json_float_impl!(f32);
+json_float_impl!(f64);
+
+/// Strings are JSON strings, a null string is null
+impl JsonEncoder<UAString> for UAString {
+    fn encode_json(&self, _: &JsonContext) -> JsonValue {
+        match self.value() {
+            Some(value) => JsonValue::from(value.as_str()),
+            None => JsonValue::Null,
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        Ok(json_str(value, decoding_limits)?.map(UAString::from).unwrap_or_else(UAString::null))
+    }
+}
+
+/// Byte strings are base64 encoded JSON strings, a null byte string is null
+impl JsonEncoder<ByteString> for ByteString {
+    fn encode_json(&self, _: &JsonContext) -> JsonValue {
+        if self.is_null() {
+            JsonValue::Null
+        } else {
+            JsonValue::from(self.as_base64())
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        match value {
+            JsonValue::String(value) => {
+                let byte_string = ByteString::from_base64(value).ok_or_else(|| {
+                    error!("JSON string is not valid base64");
+                    StatusCode::BadDecodingError
+                })?;
+                if byte_string.as_ref().len() > decoding_limits.max_byte_string_length {
+                    error!("Byte string length exceeds decoding limit {}", decoding_limits.max_byte_string_length);
+                    Err(StatusCode::BadDecodingError)
+                } else {
+                    Ok(byte_string)
+                }
+            }
+            JsonValue::Null => Ok(ByteString::null()),
+            value => {
+                error!("Expected a JSON string, got {}", value);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
+/// Implements the encoding of the types above inside an extension object. The generated
+/// service types module invokes this with each type and its binary and JSON encoding ids.
+macro_rules! json_extension_object_types {
+    ($($type_name: ident, $binary_id: ident, $json_id: ident;)*) => {
+        /// Decodes the binary body of an extension object holding a known type and returns the id
+        /// of the type's JSON encoding and the body encoded as JSON. Returns `None` if the type is
+        /// not known.
+        pub(crate) fn encode_json_body(extension_object: &crate::extension_object::ExtensionObject, ctx: &crate::json::JsonContext) -> Option<crate::encoding::EncodingResult<(crate::node_ids::ObjectId, crate::json::JsonValue)>> {
+            use crate::{encoding::DecodingLimits, json::JsonEncoder, node_ids::ObjectId};
+            let decoding_limits = DecodingLimits::default();
+            match extension_object.object_id().ok()? {
+                $(ObjectId::$binary_id => Some(extension_object.decode_inner::<$type_name>(&decoding_limits)
+                    .map(|value| (ObjectId::$json_id, value.encode_json(ctx)))),)*
+                _ => None
+            }
+        }
+
+        /// Decodes the JSON body of an extension object holding a known type and returns the
+        /// extension object with the body in its binary encoding. Returns `None` if the type is
+        /// not known.
+        pub(crate) fn decode_json_body(type_id: &crate::node_id::NodeId, body: &crate::json::JsonValue, decoding_limits: &crate::encoding::DecodingLimits) -> Option<crate::encoding::EncodingResult<crate::extension_object::ExtensionObject>> {
+            use crate::{extension_object::ExtensionObject, json::JsonEncoder, node_ids::ObjectId};
+            match type_id.as_object_id().ok()? {
+                $(ObjectId::$json_id | ObjectId::$binary_id => Some($type_name::decode_json(body, decoding_limits)
+                    .map(|value| ExtensionObject::from_encodable(ObjectId::$binary_id, &value))),)*
+                _ => None
+            }
+        }
+    }
+}
@@ -20,13 +20,14 @@ extern crate lazy_static;
 extern crate bitflags;
 #[macro_use]
 extern crate serde_derive;
-#[cfg(test)]
 extern crate serde_json;
 
 ///Contains constants recognized by OPC UA clients and servers to describe various protocols and
 /// profiles used during communication and encryption.
 pub mod profiles {
     pub const TRANSPORT_PROFILE_URI_BINARY: &str = "http://opcfoundation.org/UA-Profile/Transport/uatcp-uasc-uabinary";
+    pub const TRANSPORT_PROFILE_URI_HTTPS_BINARY: &str = "http://opcfoundation.org/UA-Profile/Transport/https-uabinary";
+    pub const TRANSPORT_PROFILE_URI_WSS_BINARY: &str = "http://opcfoundation.org/UA-Profile/Transport/wss-uasc-uabinary";
 
     pub const SECURITY_USER_TOKEN_POLICY_ANONYMOUS: &str = "http://opcfoundation.org/UA-Profile/Security/UserToken/Anonymous";
     pub const SECURITY_USER_TOKEN_POLICY_USERPASS: &str = "http://opcfoundation.org/UA-Profile/ Security/UserToken-Server/UserNamePassword";
@@ -37,6 +38,8 @@ pub mod constants {
     /// on a different port. So OPC UA for Rust does not use this nr by default but it is used
     /// implicitly in opc.tcp:// urls and elsewhere.
     pub const DEFAULT_OPC_UA_SERVER_PORT: u16 = 4840;
+    /// Default port number of https:// and opc.https:// urls
+    pub const DEFAULT_OPC_UA_HTTPS_PORT: u16 = 443;
     /// Maximum number of elements in an array
     pub const MAX_ARRAY_LENGTH: usize = 1000;
     /// Maximum size of a string in chars
@@ -189,6 +192,8 @@ bitflags! {
 mod status_codes;
 
 pub mod encoding;
+#[macro_use]
+pub mod json;
 pub mod basic_types;
 pub mod string;
 pub mod qualified_name;
@@ -208,6 +213,10 @@ pub mod notification_message;
 pub mod attribute;
 pub mod numeric_range;
 pub mod argument;
+pub mod data_type_definition;
+pub mod dynamic_structure;
+pub mod type_registry;
+pub mod namespace_map;
 pub mod service_types;
 pub mod status_code;
 pub mod relative_path;
@@ -217,6 +226,7 @@ pub mod response_header;
 
 pub use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     localized_text::*,
     qualified_name::*,
@@ -235,7 +245,12 @@ pub use crate::{
     attribute::*,
     service_types::*,
     numeric_range::*,
+    relative_path::*,
     argument::*,
+    data_type_definition::*,
+    dynamic_structure::*,
+    type_registry::*,
+    namespace_map::*,
     operand::*,
     request_header::*,
     response_header::*,
@@ -8,7 +8,7 @@ use std::{
     io::{Read, Write},
 };
 
-use crate::{encoding::*, string::*};
+use crate::{encoding::*, json::*, string::*};
 
 /// A human readable text with an optional locale identifier.
 #[derive(PartialEq, Default, Debug, Clone, Serialize, Deserialize)]
@@ -121,3 +121,28 @@ impl LocalizedText {
         }
     }
 }
+
+/// Localized text is a JSON object with Locale and Text members in the reversible form and just
+/// the text in the non-reversible form. A null localized text is null.
+impl JsonEncoder<LocalizedText> for LocalizedText {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if !ctx.reversible {
+            self.text.encode_json(ctx)
+        } else if self.locale.is_null() && self.text.is_null() {
+            JsonValue::Null
+        } else {
+            let mut object = JsonObject::new();
+            write_json_field(&mut object, "Locale", &self.locale, ctx);
+            write_json_field(&mut object, "Text", &self.text, ctx);
+            JsonValue::Object(object)
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(LocalizedText {
+            locale: read_json_field(object, "Locale", decoding_limits)?,
+            text: read_json_field(object, "Text", decoding_limits)?,
+        })
+    }
+}
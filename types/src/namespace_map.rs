@@ -0,0 +1,159 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! Contains the `NamespaceMap`, a table of namespace uris for resolving node ids that name their
+//! namespace by uri, e.g. `nsu=urn:MyServer;s=Tag`, to the namespace indexes of a server.
+
+use std::str::FromStr;
+
+use crate::{
+    node_id::{ExpandedNodeId, NodeId},
+    status_code::StatusCode,
+    string::UAString,
+};
+
+/// The uri of namespace 0, which holds the nodes defined by OPC UA
+pub const OPC_UA_NAMESPACE_URI: &str = "http://opcfoundation.org/UA/";
+
+/// A table of namespace uris in index order, such as a server's `NamespaceArray`. Namespace
+/// indexes can differ between servers and between builds of a server, but uris do not, so node
+/// ids are resolved through the table to find the index a server uses for a uri, and remapped
+/// between the tables of different servers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamespaceMap {
+    namespaces: Vec<String>,
+}
+
+impl Default for NamespaceMap {
+    fn default() -> Self {
+        NamespaceMap {
+            namespaces: vec![OPC_UA_NAMESPACE_URI.to_string()],
+        }
+    }
+}
+
+impl NamespaceMap {
+    /// Creates a table holding only the OPC UA namespace at index 0
+    pub fn new() -> NamespaceMap {
+        NamespaceMap::default()
+    }
+
+    /// Creates a table from uris in index order, e.g. the value of a server's `NamespaceArray`
+    pub fn from_uris<S>(uris: &[S]) -> NamespaceMap where S: AsRef<str> {
+        NamespaceMap {
+            namespaces: uris.iter().map(|uri| uri.as_ref().to_string()).collect(),
+        }
+    }
+
+    /// Adds a namespace uri to the end of the table and returns its index. A uri that is already
+    /// in the table keeps its index.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u16)` - The index of the namespace
+    /// * `Err(StatusCode)` - `BadInvalidArgument` if the uri is empty, or `BadTooManyOperations`
+    ///   if the table is full
+    ///
+    pub fn add(&mut self, uri: &str) -> Result<u16, StatusCode> {
+        if uri.is_empty() {
+            Err(StatusCode::BadInvalidArgument)
+        } else if let Some(index) = self.index_of(uri) {
+            Ok(index)
+        } else if self.namespaces.len() > u16::MAX as usize {
+            Err(StatusCode::BadTooManyOperations)
+        } else {
+            self.namespaces.push(uri.to_string());
+            Ok((self.namespaces.len() - 1) as u16)
+        }
+    }
+
+    /// Returns the index of the namespace uri
+    pub fn index_of(&self, uri: &str) -> Option<u16> {
+        self.namespaces.iter().position(|ns| ns == uri).map(|i| i as u16)
+    }
+
+    /// Returns the uri of the namespace index
+    pub fn uri(&self, index: u16) -> Option<&str> {
+        self.namespaces.get(index as usize).map(|uri| uri.as_str())
+    }
+
+    /// Returns the namespace uris in index order
+    pub fn uris(&self) -> &[String] {
+        &self.namespaces
+    }
+
+    /// Returns the number of namespaces in the table
+    pub fn len(&self) -> usize {
+        self.namespaces.len()
+    }
+
+    /// Tests if the table has no namespaces
+    pub fn is_empty(&self) -> bool {
+        self.namespaces.is_empty()
+    }
+
+    /// Resolves an expanded node id to a node id of this table. A namespace uri is replaced by
+    /// its index, and a node id without one is returned as it is. Returns `None` if the uri is
+    /// not in the table or the node is on another server.
+    pub fn resolve(&self, node_id: &ExpandedNodeId) -> Option<NodeId> {
+        if node_id.server_index != 0 {
+            None
+        } else if node_id.namespace_uri.is_empty() {
+            Some(node_id.node_id.clone())
+        } else {
+            self.index_of(node_id.namespace_uri.as_ref()).map(|namespace| NodeId {
+                namespace,
+                identifier: node_id.node_id.identifier.clone(),
+            })
+        }
+    }
+
+    /// Makes an expanded node id that names the namespace of a node id of this table by its uri,
+    /// so it stays valid for another table. A node id in namespace 0, or in a namespace that is
+    /// not in the table, keeps its index.
+    pub fn to_expanded_node_id(&self, node_id: &NodeId) -> ExpandedNodeId {
+        match self.uri(node_id.namespace) {
+            Some(uri) if node_id.namespace != 0 => ExpandedNodeId {
+                node_id: NodeId {
+                    namespace: 0,
+                    identifier: node_id.identifier.clone(),
+                },
+                namespace_uri: UAString::from(uri),
+                server_index: 0,
+            },
+            _ => ExpandedNodeId::new(node_id.clone())
+        }
+    }
+
+    /// Remaps a node id of this table to the index its namespace has in another table. Returns
+    /// `None` if the namespace is in either table but not both.
+    pub fn remap(&self, node_id: &NodeId, other: &NamespaceMap) -> Option<NodeId> {
+        let uri = self.uri(node_id.namespace)?;
+        other.index_of(uri).map(|namespace| NodeId {
+            namespace,
+            identifier: node_id.identifier.clone(),
+        })
+    }
+
+    /// Parses a node id in any of the text forms of OPC UA Part 6 5.3.1.10 and 5.3.1.11, e.g.
+    /// `i=2253`, `ns=2;s=Tag`, `nsu=urn:MyServer;s=Tag` or `svr=0;nsu=urn:MyServer;s=Tag`, and
+    /// resolves it to a node id of this table.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(NodeId)` - The node id
+    /// * `Err(StatusCode)` - `BadNodeIdInvalid` if the text is not a node id, or `BadNodeIdUnknown`
+    ///   if its namespace uri is not in the table or it is on another server
+    ///
+    pub fn parse_node_id(&self, node_id: &str) -> Result<NodeId, StatusCode> {
+        let node_id = if node_id.starts_with("svr=") {
+            ExpandedNodeId::from_str(node_id)?
+        } else if node_id.starts_with("nsu=") {
+            ExpandedNodeId::from_str(&format!("svr=0;{}", node_id))?
+        } else {
+            ExpandedNodeId::new(NodeId::from_str(node_id)?)
+        };
+        self.resolve(&node_id).ok_or(StatusCode::BadNodeIdUnknown)
+    }
+}
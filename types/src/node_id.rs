@@ -16,6 +16,7 @@ use crate::{
     byte_string::ByteString,
     encoding::*,
     guid::Guid,
+    json::*,
     node_ids::{ObjectId, ReferenceTypeId},
     status_codes::StatusCode,
     string::*,
@@ -657,4 +658,140 @@ impl ExpandedNodeId {
     pub fn is_null(&self) -> bool {
         self.node_id.is_null()
     }
-}
\ No newline at end of file
+}
+
+impl NodeId {
+    /// Reads the IdType and Id members of the object form of a JSON node id
+    fn read_json_identifier(object: &JsonObject, decoding_limits: &DecodingLimits) -> EncodingResult<Identifier> {
+        let id_type: u8 = read_json_field(object, "IdType", decoding_limits)?;
+        match id_type {
+            0 => read_json_field::<u32>(object, "Id", decoding_limits).map(Identifier::Numeric),
+            1 => read_json_field::<UAString>(object, "Id", decoding_limits).map(Identifier::String),
+            2 => read_json_field::<Guid>(object, "Id", decoding_limits).map(Identifier::Guid),
+            3 => read_json_field::<ByteString>(object, "Id", decoding_limits).map(Identifier::ByteString),
+            id_type => {
+                error!("Invalid IdType {} in JSON node id", id_type);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
+/// Escapes the % and ; chars of a namespace or server uri in the string form of a node id
+fn escape_uri(uri: &str) -> String {
+    uri.replace("%", "%25").replace(";", "%3b")
+}
+
+/// Writes the namespace prefix of the string form of a node id, i.e. `ns=<index>;`, or
+/// `nsu=<uri>;` where the non-reversible form has a uri for the index. Nothing is written for
+/// namespace 0.
+fn json_namespace_prefix(namespace: u16, ctx: &JsonContext) -> String {
+    match ctx.namespace(namespace) {
+        JsonValue::String(uri) => format!("nsu={};", escape_uri(&uri)),
+        _ if namespace != 0 => format!("ns={};", namespace),
+        _ => String::new(),
+    }
+}
+
+/// Node ids are strings in the form of Part 6 5.3.1.10, e.g. `"i=2253"` or `"ns=2;s=Pump"`. The
+/// non-reversible form names namespaces other than 0 and 1 by uri, e.g. `"nsu=urn:model;s=Pump"`.
+/// A null node id is null. The object form with IdType, Id and Namespace members that older
+/// encoders write is also decoded.
+impl JsonEncoder<NodeId> for NodeId {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if self.is_null() {
+            JsonValue::Null
+        } else {
+            JsonValue::from(format!("{}{}", json_namespace_prefix(self.namespace, ctx), self.identifier))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        match value {
+            JsonValue::Null => Ok(NodeId::null()),
+            JsonValue::String(value) => {
+                if value.starts_with("nsu=") {
+                    // A node id has nowhere to hold the uri and there is no table to look it up in
+                    error!("JSON node id {} names its namespace by uri which cannot be decoded", value);
+                    Err(StatusCode::BadDecodingError)
+                } else {
+                    NodeId::from_str(value).map_err(|_| {
+                        error!("Invalid JSON node id {}", value);
+                        StatusCode::BadDecodingError
+                    })
+                }
+            }
+            value => {
+                let object = json_object(value)?;
+                let identifier = Self::read_json_identifier(object, decoding_limits)?;
+                let namespace = read_json_field(object, "Namespace", decoding_limits)?;
+                Ok(NodeId { namespace, identifier })
+            }
+        }
+    }
+}
+
+/// Expanded node ids are strings in the form of Part 6 5.3.1.11 except that the `svr=<index>;`
+/// prefix is omitted for the local server, e.g. `"nsu=urn:elsewhere;i=5"` or `"svr=2;ns=4;i=5"`.
+/// The non-reversible form names servers by uri with a `svu=<uri>;` prefix. The object form is
+/// also decoded.
+impl JsonEncoder<ExpandedNodeId> for ExpandedNodeId {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if self.is_null() && self.namespace_uri.is_null() && self.server_index == 0 {
+            JsonValue::Null
+        } else {
+            let server = if self.server_index == 0 {
+                String::new()
+            } else {
+                match ctx.server_uri(self.server_index) {
+                    JsonValue::String(uri) => format!("svu={};", escape_uri(&uri)),
+                    _ => format!("svr={};", self.server_index),
+                }
+            };
+            let namespace = if !self.namespace_uri.is_null() {
+                format!("nsu={};", escape_uri(self.namespace_uri.as_ref()))
+            } else {
+                json_namespace_prefix(self.node_id.namespace, ctx)
+            };
+            JsonValue::from(format!("{}{}{}", server, namespace, self.node_id.identifier))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        match value {
+            JsonValue::Null => Ok(ExpandedNodeId::null()),
+            JsonValue::String(value) => {
+                let invalid = || {
+                    error!("Invalid JSON expanded node id {}", value);
+                    StatusCode::BadDecodingError
+                };
+                // The server index is optional in the JSON form but not in the text form
+                let value = if value.starts_with("svr=") { value.clone() } else { format!("svr=0;{}", value) };
+                if value.contains(";nsu=") {
+                    ExpandedNodeId::from_str(&value).map_err(|_| invalid())
+                } else {
+                    // The text form requires a namespace, which is omitted for namespace 0
+                    let (server, node_id) = value.split_at(value.find(';').ok_or_else(invalid)?);
+                    let server_index = server[4..].parse::<u32>().map_err(|_| invalid())?;
+                    let node_id = NodeId::from_str(&node_id[1..]).map_err(|_| invalid())?;
+                    Ok(ExpandedNodeId { node_id, namespace_uri: UAString::null(), server_index })
+                }
+            }
+            value => {
+                let object = json_object(value)?;
+                let identifier = NodeId::read_json_identifier(object, decoding_limits)?;
+                // The namespace is an index, or the uri of a namespace that is not in the namespace table
+                let (namespace, namespace_uri) = match object.get("Namespace") {
+                    Some(JsonValue::String(_)) => (0, read_json_field(object, "Namespace", decoding_limits)?),
+                    _ => (read_json_field(object, "Namespace", decoding_limits)?, UAString::null()),
+                };
+                let server_index = read_json_field(object, "ServerUri", decoding_limits)?;
+                Ok(ExpandedNodeId {
+                    node_id: NodeId { namespace, identifier },
+                    namespace_uri,
+                    server_index,
+                })
+            }
+        }
+    }
+}
@@ -74,6 +74,12 @@ impl NotificationMessage {
         }
     }
 
+    /// Tests if the message is a keep-alive, i.e. it contains no notifications. The sequence
+    /// number of a keep-alive is the one the next notification message will have.
+    pub fn is_keep_alive(&self) -> bool {
+        self.notification_data.as_ref().map(|n| n.is_empty()).unwrap_or(true)
+    }
+
     /// Extract notifications from the message. Unrecognized / unparseable notifications will be
     /// ignored. If there are no notifications, the function will return `None`.
     pub fn notifications(&self, decoding_limits: &DecodingLimits) -> Option<(Vec<DataChangeNotification>, Vec<EventNotificationList>)> {
@@ -5,7 +5,7 @@
 //! Contains the definition of `QualifiedName`.
 use std::io::{Read, Write};
 
-use crate::{encoding::*, string::*};
+use crate::{encoding::*, json::*, string::*};
 
 /// An identifier for a error or condition that is associated with a value or an operation.
 ///
@@ -90,3 +90,28 @@ impl QualifiedName {
         self.namespace_index == 0 && self.name.is_null()
     }
 }
+
+/// Qualified names are JSON objects with a Name member and a Uri member holding the namespace,
+/// which is omitted for namespace 0. A null qualified name is null.
+impl JsonEncoder<QualifiedName> for QualifiedName {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if self.is_null() {
+            JsonValue::Null
+        } else {
+            let mut object = JsonObject::new();
+            write_json_field(&mut object, "Name", &self.name, ctx);
+            if self.namespace_index != 0 {
+                object.insert("Uri".to_string(), ctx.namespace(self.namespace_index));
+            }
+            JsonValue::Object(object)
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(QualifiedName {
+            namespace_index: read_json_field(object, "Uri", decoding_limits)?,
+            name: read_json_field(object, "Name", decoding_limits)?,
+        })
+    }
+}
@@ -5,18 +5,160 @@
 //! Contains functions used for making relative paths from / to strings, as per OPC UA Part 4, Appendix A
 //!
 //! Functions are implemented on the `RelativePath` and `RelativePathElement` structs where
-//! there are most useful.
+//! there are most useful. `RelativePath` implements `FromStr` and `Display` for the text format,
+//! naming reference types with a [`ReferenceTypeNames`] table.
 //!
-use regex::Regex;
+//! [`ReferenceTypeNames`]: ./struct.ReferenceTypeNames.html
+//!
+use std::{
+    collections::HashMap,
+    fmt,
+    iter::Peekable,
+    str::{Chars, FromStr},
+};
 
 use crate::{
     node_id::{Identifier, NodeId},
     node_ids::*,
     qualified_name::QualifiedName,
     service_types::{RelativePath, RelativePathElement},
+    status_code::StatusCode,
     string::UAString,
 };
 
+/// The standard reference types of namespace 0, which are named in paths by their browse names
+const STANDARD_REFERENCE_TYPES: [ReferenceTypeId; 43] = [
+    ReferenceTypeId::References,
+    ReferenceTypeId::NonHierarchicalReferences,
+    ReferenceTypeId::HierarchicalReferences,
+    ReferenceTypeId::HasChild,
+    ReferenceTypeId::Organizes,
+    ReferenceTypeId::HasEventSource,
+    ReferenceTypeId::HasModellingRule,
+    ReferenceTypeId::HasEncoding,
+    ReferenceTypeId::HasDescription,
+    ReferenceTypeId::HasTypeDefinition,
+    ReferenceTypeId::GeneratesEvent,
+    ReferenceTypeId::Aggregates,
+    ReferenceTypeId::HasSubtype,
+    ReferenceTypeId::HasProperty,
+    ReferenceTypeId::HasComponent,
+    ReferenceTypeId::HasNotifier,
+    ReferenceTypeId::HasOrderedComponent,
+    ReferenceTypeId::FromState,
+    ReferenceTypeId::ToState,
+    ReferenceTypeId::HasCause,
+    ReferenceTypeId::HasEffect,
+    ReferenceTypeId::HasHistoricalConfiguration,
+    ReferenceTypeId::HasSubStateMachine,
+    ReferenceTypeId::HasArgumentDescription,
+    ReferenceTypeId::HasOptionalInputArgumentDescription,
+    ReferenceTypeId::AlwaysGeneratesEvent,
+    ReferenceTypeId::HasTrueSubState,
+    ReferenceTypeId::HasFalseSubState,
+    ReferenceTypeId::HasCondition,
+    ReferenceTypeId::HasPubSubConnection,
+    ReferenceTypeId::DataSetToWriter,
+    ReferenceTypeId::HasGuard,
+    ReferenceTypeId::HasDataSetWriter,
+    ReferenceTypeId::HasDataSetReader,
+    ReferenceTypeId::HasAlarmSuppressionGroup,
+    ReferenceTypeId::AlarmGroupMember,
+    ReferenceTypeId::HasEffectDisable,
+    ReferenceTypeId::HasDictionaryEntry,
+    ReferenceTypeId::HasInterface,
+    ReferenceTypeId::HasAddIn,
+    ReferenceTypeId::HasEffectEnable,
+    ReferenceTypeId::HasEffectSuppressed,
+    ReferenceTypeId::HasEffectUnsuppressed,
+];
+
+/// Returns the standard reference type with the browse name, e.g. `HasChild`
+fn standard_reference_type(browse_name: &str) -> Option<ReferenceTypeId> {
+    lazy_static! {
+        static ref BY_NAME: HashMap<String, ReferenceTypeId> = STANDARD_REFERENCE_TYPES.iter()
+            .map(|r| (format!("{:?}", r), *r))
+            .collect();
+    }
+    BY_NAME.get(browse_name).copied()
+}
+
+/// Returns the browse name of a standard reference type
+fn standard_reference_type_name(node_id: &NodeId) -> Option<String> {
+    match node_id.identifier {
+        Identifier::Numeric(id) if node_id.namespace == 0 => STANDARD_REFERENCE_TYPES.iter()
+            .find(|r| **r as u32 == id)
+            .map(|r| format!("{:?}", r)),
+        _ => None
+    }
+}
+
+/// The names of the reference types that relative paths refer to. The standard reference types
+/// of namespace 0 are always known by their browse names, e.g. `<HasChild>`, and reference types
+/// of other namespaces, such as those of a server's information model, can be added to the
+/// table under their browse names, e.g. `<2:ConnectedTo>`.
+#[derive(Debug, Clone, Default)]
+pub struct ReferenceTypeNames {
+    node_ids: HashMap<(u16, String), NodeId>,
+    browse_names: HashMap<NodeId, QualifiedName>,
+}
+
+impl ReferenceTypeNames {
+    /// Creates a table that knows the standard reference types
+    pub fn new() -> ReferenceTypeNames {
+        ReferenceTypeNames::default()
+    }
+
+    /// Adds a reference type to the table under its browse name
+    pub fn add<T>(&mut self, browse_name: QualifiedName, node_id: T) where T: Into<NodeId> {
+        let node_id = node_id.into();
+        self.node_ids.insert((browse_name.namespace_index, browse_name.name.as_ref().to_string()), node_id.clone());
+        self.browse_names.insert(node_id, browse_name);
+    }
+
+    /// Returns the reference type with the browse name in the namespace
+    pub fn node_id(&self, namespace: u16, browse_name: &str) -> Option<NodeId> {
+        if let Some(node_id) = self.node_ids.get(&(namespace, browse_name.to_string())) {
+            Some(node_id.clone())
+        } else if namespace == 0 {
+            standard_reference_type(browse_name).map(|r| r.into())
+        } else {
+            None
+        }
+    }
+
+    /// Returns the browse name of the reference type
+    pub fn browse_name(&self, node_id: &NodeId) -> Option<QualifiedName> {
+        if let Some(browse_name) = self.browse_names.get(node_id) {
+            Some(browse_name.clone())
+        } else {
+            standard_reference_type_name(node_id).map(|name| QualifiedName::new(0, name))
+        }
+    }
+}
+
+impl FromStr for RelativePath {
+    type Err = StatusCode;
+
+    /// Parses a relative path, where reference types may be the standard reference types. See
+    /// `RelativePath::parse_with()` to name other reference types.
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        RelativePath::parse_with(path, &ReferenceTypeNames::new())
+    }
+}
+
+impl fmt::Display for RelativePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string_with(&ReferenceTypeNames::new()))
+    }
+}
+
+impl fmt::Display for RelativePathElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string_with(&ReferenceTypeNames::new()))
+    }
+}
+
 impl RelativePath {
     /// The maximum size in chars of any path element.
     const MAX_TOKEN_LEN: usize = 256;
@@ -26,73 +168,38 @@ impl RelativePath {
     /// Converts a string into a relative path. Caller must supply a `node_resolver` which will
     /// be used to look up nodes from their browse name. The function will reject strings
     /// that look unusually long or contain too many elements.
-    pub fn from_str<CB>(path: &str, node_resolver: &CB) -> Result<RelativePath, ()>
+    pub fn parse_with_resolver<CB>(path: &str, node_resolver: &CB) -> Result<RelativePath, StatusCode>
         where CB: Fn(u16, &str) -> Option<NodeId> {
-        let mut elements: Vec<RelativePathElement> = Vec::new();
-
-        // This loop will break the string up into path segments. For each segment it will
-        // then parse it into a relative path element. When the string is successfully parsed,
-        // the elements will be returned.
-        let mut escaped_char = false;
-        let mut token = String::with_capacity(path.len());
-        for c in path.chars() {
-            if escaped_char {
-                token.push(c);
-                escaped_char = false;
-            } else {
-                // Parse the
-                match c {
-                    '&' => {
-                        // The next character is escaped and part of the token
-                        escaped_char = true;
-                    }
-                    '/' | '.' | '<' => {
-                        // We have reached the start of a token and need to process the previous one
-                        if !token.is_empty() {
-                            if elements.len() == Self::MAX_ELEMENTS {
-                                break;
-                            }
-                            elements.push(RelativePathElement::from_str(&token, node_resolver)?);
-                            token.clear();
-                        }
-                    }
-                    _ => {}
-                }
-                token.push(c);
-            }
-            if token.len() > Self::MAX_TOKEN_LEN {
-                error!("Path segment seems unusually long and has been rejected");
-                return Err(());
-            }
-        }
+        parse_relative_path(path, node_resolver)
+    }
 
-        if !token.is_empty() {
-            if elements.len() == Self::MAX_ELEMENTS {
-                error!("Number of elements in relative path is too long, rejecting it");
-                return Err(());
-            }
-            elements.push(RelativePathElement::from_str(&token, node_resolver)?);
-        }
+    /// Parses a relative path, looking up the reference types it names in the table.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(RelativePath)` - The relative path
+    /// * `Err(StatusCode)` - `BadBrowseNameInvalid` if the path does not follow the syntax, or
+    ///   `BadReferenceTypeIdInvalid` if it names a reference type that is not in the table
+    ///
+    pub fn parse_with(path: &str, reference_types: &ReferenceTypeNames) -> Result<RelativePath, StatusCode> {
+        parse_relative_path(path, &|namespace, browse_name| reference_types.node_id(namespace, browse_name))
+    }
 
-        Ok(RelativePath {
-            elements: Some(elements)
-        })
+    /// Converts the relative path to a string, naming reference types from the table. A reference
+    /// type that is not in the table is named by its string identifier if it has one, otherwise
+    /// by its node id, which cannot be parsed back.
+    pub fn to_string_with(&self, reference_types: &ReferenceTypeNames) -> String {
+        if let Some(ref elements) = self.elements {
+            elements.iter().map(|e| e.to_string_with(reference_types)).collect()
+        } else {
+            String::new()
+        }
     }
 }
 
 impl<'a> From<&'a RelativePathElement> for String {
     fn from(element: &'a RelativePathElement) -> String {
-        let mut result = element.relative_path_reference_type(&RelativePathElement::default_browse_name_resolver);
-        if !element.target_name.name.is_null() {
-            let always_use_namespace = true;
-            let target_browse_name = escape_browse_name(element.target_name.name.as_ref());
-            if always_use_namespace || element.target_name.namespace_index > 0 {
-                result.push_str(&format!("{}:{}", element.target_name.namespace_index, target_browse_name));
-            } else {
-                result.push_str(&target_browse_name);
-            }
-        }
-        result
+        element.to_string()
     }
 }
 
@@ -105,37 +212,10 @@ impl RelativePathElement {
     /// types and if fails to match it will produce a node id from the namespace and browse name.
     pub fn default_node_resolver(namespace: u16, browse_name: &str) -> Option<NodeId> {
         let node_id = if namespace == 0 {
-            match browse_name {
-                "References" => ReferenceTypeId::References.into(),
-                "NonHierarchicalReferences" => ReferenceTypeId::NonHierarchicalReferences.into(),
-                "HierarchicalReferences" => ReferenceTypeId::HierarchicalReferences.into(),
-                "HasChild" => ReferenceTypeId::HasChild.into(),
-                "Organizes" => ReferenceTypeId::Organizes.into(),
-                "HasEventSource" => ReferenceTypeId::HasEventSource.into(),
-                "HasModellingRule" => ReferenceTypeId::HasModellingRule.into(),
-                "HasEncoding" => ReferenceTypeId::HasEncoding.into(),
-                "HasDescription" => ReferenceTypeId::HasDescription.into(),
-                "HasTypeDefinition" => ReferenceTypeId::HasTypeDefinition.into(),
-                "GeneratesEvent" => ReferenceTypeId::GeneratesEvent.into(),
-                "Aggregates" => ReferenceTypeId::Aggregates.into(),
-                "HasSubtype" => ReferenceTypeId::HasSubtype.into(),
-                "HasProperty" => ReferenceTypeId::HasProperty.into(),
-                "HasComponent" => ReferenceTypeId::HasComponent.into(),
-                "HasNotifier" => ReferenceTypeId::HasNotifier.into(),
-                "HasOrderedComponent" => ReferenceTypeId::HasOrderedComponent.into(),
-                "FromState" => ReferenceTypeId::FromState.into(),
-                "ToState" => ReferenceTypeId::ToState.into(),
-                "HasCause" => ReferenceTypeId::HasCause.into(),
-                "HasEffect" => ReferenceTypeId::HasEffect.into(),
-                "HasHistoricalConfiguration" => ReferenceTypeId::HasHistoricalConfiguration.into(),
-                "HasSubStateMachine" => ReferenceTypeId::HasSubStateMachine.into(),
-                "AlwaysGeneratesEvent" => ReferenceTypeId::AlwaysGeneratesEvent.into(),
-                "HasTrueSubState" => ReferenceTypeId::HasTrueSubState.into(),
-                "HasFalseSubState" => ReferenceTypeId::HasFalseSubState.into(),
-                "HasCondition" => ReferenceTypeId::HasCondition.into(),
-                _ => {
-                    NodeId::new(0, UAString::from(browse_name))
-                }
+            if let Some(reference_type_id) = standard_reference_type(browse_name) {
+                reference_type_id.into()
+            } else {
+                NodeId::new(0, UAString::from(browse_name))
             }
         } else {
             NodeId::new(namespace, UAString::from(browse_name))
@@ -143,50 +223,10 @@ impl RelativePathElement {
         Some(node_id)
     }
 
-    fn id_from_reference_type(id: u32) -> Option<String> {
-        // This syntax is horrible - it casts the u32 into an enum if it can
-        Some(match id {
-            id if id == ReferenceTypeId::References as u32 => "References",
-            id if id == ReferenceTypeId::NonHierarchicalReferences as u32 => "NonHierarchicalReferences",
-            id if id == ReferenceTypeId::HierarchicalReferences as u32 => "HierarchicalReferences",
-            id if id == ReferenceTypeId::HasChild as u32 => "HasChild",
-            id if id == ReferenceTypeId::Organizes as u32 => "Organizes",
-            id if id == ReferenceTypeId::HasEventSource as u32 => "HasEventSource",
-            id if id == ReferenceTypeId::HasModellingRule as u32 => "HasModellingRule",
-            id if id == ReferenceTypeId::HasEncoding as u32 => "HasEncoding",
-            id if id == ReferenceTypeId::HasDescription as u32 => "HasDescription",
-            id if id == ReferenceTypeId::HasTypeDefinition as u32 => "HasTypeDefinition",
-            id if id == ReferenceTypeId::GeneratesEvent as u32 => "GeneratesEvent",
-            id if id == ReferenceTypeId::Aggregates as u32 => "Aggregates",
-            id if id == ReferenceTypeId::HasSubtype as u32 => "HasSubtype",
-            id if id == ReferenceTypeId::HasProperty as u32 => "HasProperty",
-            id if id == ReferenceTypeId::HasComponent as u32 => "HasComponent",
-            id if id == ReferenceTypeId::HasNotifier as u32 => "HasNotifier",
-            id if id == ReferenceTypeId::HasOrderedComponent as u32 => "HasOrderedComponent",
-            id if id == ReferenceTypeId::FromState as u32 => "FromState",
-            id if id == ReferenceTypeId::ToState as u32 => "ToState",
-            id if id == ReferenceTypeId::HasCause as u32 => "HasCause",
-            id if id == ReferenceTypeId::HasEffect as u32 => "HasEffect",
-            id if id == ReferenceTypeId::HasHistoricalConfiguration as u32 => "HasHistoricalConfiguration",
-            id if id == ReferenceTypeId::HasSubStateMachine as u32 => "HasSubStateMachine",
-            id if id == ReferenceTypeId::AlwaysGeneratesEvent as u32 => "AlwaysGeneratesEvent",
-            id if id == ReferenceTypeId::HasTrueSubState as u32 => "HasTrueSubState",
-            id if id == ReferenceTypeId::HasFalseSubState as u32 => "HasFalseSubState",
-            id if id == ReferenceTypeId::HasCondition as u32 => "HasCondition",
-            _ => return None
-        }.to_string())
-    }
-
     pub fn default_browse_name_resolver(node_id: &NodeId) -> Option<String> {
         match &node_id.identifier {
             Identifier::String(browse_name) => Some(browse_name.as_ref().to_string()),
-            Identifier::Numeric(id) => {
-                if node_id.namespace == 0 {
-                    Self::id_from_reference_type(*id)
-                } else {
-                    None
-                }
-            }
+            Identifier::Numeric(_) => standard_reference_type_name(node_id),
             _ => None
         }
     }
@@ -211,74 +251,36 @@ impl RelativePathElement {
     /// * `<!NonHierarchicalReferences>foo`
     /// * `<#!2:MyReftype>2:blah`
     ///
-    pub fn from_str<CB>(path: &str, node_resolver: &CB) -> Result<RelativePathElement, ()>
+    pub fn parse_with_resolver<CB>(path: &str, node_resolver: &CB) -> Result<RelativePathElement, StatusCode>
         where CB: Fn(u16, &str) -> Option<NodeId> {
-        lazy_static! {
-            static ref RE: Regex = Regex::new(r"(?P<reftype>/|\.|(<(?P<flags>#|!|#!)?((?P<nsidx>[0-9]+):)?(?P<name>[^#!].*)>))(?P<target>.*)").unwrap();
+        let relative_path = parse_relative_path(path, node_resolver)?;
+        match relative_path.elements {
+            Some(mut elements) if elements.len() == 1 => Ok(elements.remove(0)),
+            _ => {
+                error!("Path {} is not a single relative path element", path);
+                Err(StatusCode::BadBrowseNameInvalid)
+            }
         }
+    }
 
-        // NOTE: This could be more safely done with a parser library, e.g. nom.
-
-        if let Some(captures) = RE.captures(&path) {
-            let target_name = target_name(captures.name("target").unwrap().as_str())?;
-
-            let reference_type = captures.name("reftype").unwrap();
-            let (reference_type_id, include_subtypes, is_inverse) = match reference_type.as_str() {
-                "/" => (ReferenceTypeId::HierarchicalReferences.into(), true, false),
-                "." => (ReferenceTypeId::Aggregates.into(), true, false),
-                _ => {
-                    let (include_subtypes, is_inverse) = if let Some(flags) = captures.name("flags") {
-                        match flags.as_str() {
-                            "#" => (false, false),
-                            "!" => (true, true),
-                            "#!" => (false, true),
-                            _ => panic!("Error in regular expression for flags")
-                        }
-                    } else {
-                        (true, false)
-                    };
-
-                    let browse_name = captures.name("name").unwrap().as_str();
-
-                    // Process the token as a reference type
-                    let reference_type_id = if let Some(namespace) = captures.name("nsidx") {
-                        let namespace = namespace.as_str();
-                        if namespace == "0" || namespace == "" {
-                            node_resolver(0, browse_name)
-                        } else if let Ok(namespace) = namespace.parse::<u16>() {
-                            node_resolver(namespace, browse_name)
-                        } else {
-                            error!("Namespace {} is out of range", namespace);
-                            return Err(());
-                        }
-                    } else {
-                        node_resolver(0, browse_name)
-                    };
-                    if reference_type_id.is_none() {
-                        error!("Supplied node resolver was unable to resolve a reference type from {}", path);
-                        return Err(());
-                    }
-                    (reference_type_id.unwrap(), include_subtypes, is_inverse)
-                }
-            };
-            Ok(RelativePathElement {
-                reference_type_id,
-                is_inverse,
-                include_subtypes,
-                target_name,
-            })
-        } else {
-            error!("Path {} does not match a relative path", path);
-            Err(())
+    /// Converts the element to a string, naming its reference type from the table, see
+    /// `RelativePath::to_string_with()`.
+    pub fn to_string_with(&self, reference_types: &ReferenceTypeNames) -> String {
+        let mut result = self.relative_path_reference_type(reference_types);
+        if !self.target_name.name.is_null() {
+            let always_use_namespace = true;
+            let target_browse_name = escape_browse_name(self.target_name.name.as_ref());
+            if always_use_namespace || self.target_name.namespace_index > 0 {
+                result.push_str(&format!("{}:{}", self.target_name.namespace_index, target_browse_name));
+            } else {
+                result.push_str(&target_browse_name);
+            }
         }
+        result
     }
 
     /// Constructs a string representation of the reference type in the relative path.
-    /// This code assumes that the reference type's node id has a string identifier and that
-    /// the string identifier is the same as the browse name.
-    pub(crate) fn relative_path_reference_type<CB>(&self, browse_name_resolver: &CB) -> String
-        where CB: Fn(&NodeId) -> Option<String> {
-        let browse_name = browse_name_resolver(&self.reference_type_id).unwrap();
+    fn relative_path_reference_type(&self, reference_types: &ReferenceTypeNames) -> String {
         let mut result = String::with_capacity(1024);
         // Common references will come out as '/' or '.'
         if self.include_subtypes && !self.is_inverse {
@@ -297,32 +299,135 @@ impl RelativePathElement {
             if self.is_inverse {
                 result.push('!');
             }
-
-            let browse_name = escape_browse_name(browse_name.as_ref());
-            if self.reference_type_id.namespace != 0 {
-                result.push_str(&format!("{}:{}", self.reference_type_id.namespace, browse_name));
+            let browse_name = if let Some(browse_name) = reference_types.browse_name(&self.reference_type_id) {
+                browse_name
+            } else if let Identifier::String(ref browse_name) = self.reference_type_id.identifier {
+                QualifiedName::new(self.reference_type_id.namespace, browse_name.clone())
             } else {
-                result.push_str(&browse_name);
+                QualifiedName::new(0, self.reference_type_id.to_string())
+            };
+            let name = escape_browse_name(browse_name.name.as_ref());
+            if browse_name.namespace_index != 0 {
+                result.push_str(&format!("{}:{}", browse_name.namespace_index, name));
+            } else {
+                result.push_str(&name);
             }
             result.push('>');
         }
-
         result
     }
 }
 
 impl<'a> From<&'a RelativePath> for String {
     fn from(path: &'a RelativePath) -> String {
-        if let Some(ref elements) = path.elements {
-            let mut result = String::with_capacity(1024);
-            for e in elements.iter() {
-                result.push_str(String::from(e).as_ref());
-            };
-            result
+        path.to_string()
+    }
+}
+
+/// Parses a relative path, resolving the browse names of reference types with the resolver
+fn parse_relative_path<CB>(path: &str, node_resolver: &CB) -> Result<RelativePath, StatusCode>
+    where CB: Fn(u16, &str) -> Option<NodeId> {
+    let mut chars = path.chars().peekable();
+    let mut elements = Vec::new();
+    while let Some(c) = chars.next() {
+        if elements.len() == RelativePath::MAX_ELEMENTS {
+            error!("Number of elements in relative path is too long, rejecting it");
+            return Err(StatusCode::BadBrowseNameInvalid);
+        }
+        let (reference_type_id, include_subtypes, is_inverse) = match c {
+            '/' => (ReferenceTypeId::HierarchicalReferences.into(), true, false),
+            '.' => (ReferenceTypeId::Aggregates.into(), true, false),
+            '<' => {
+                let include_subtypes = !next_if_eq(&mut chars, '#');
+                let is_inverse = next_if_eq(&mut chars, '!');
+                let (namespace, browse_name) = parse_browse_name(&mut chars, &['>'])?;
+                if chars.next() != Some('>') || browse_name.is_empty() {
+                    error!("Reference type in path {} is not a browse name enclosed in <>", path);
+                    return Err(StatusCode::BadBrowseNameInvalid);
+                }
+                let reference_type_id = node_resolver(namespace, &browse_name).ok_or_else(|| {
+                    error!("Reference type {}:{} in path {} cannot be resolved", namespace, browse_name, path);
+                    StatusCode::BadReferenceTypeIdInvalid
+                })?;
+                (reference_type_id, include_subtypes, is_inverse)
+            }
+            c => {
+                error!("Path {} has an element that starts with {} instead of a reference type", path, c);
+                return Err(StatusCode::BadBrowseNameInvalid);
+            }
+        };
+        let (namespace, browse_name) = parse_browse_name(&mut chars, &['/', '.', '<'])?;
+        let target_name = if browse_name.is_empty() {
+            QualifiedName::new(namespace, UAString::null())
         } else {
-            String::new()
+            QualifiedName::new(namespace, browse_name)
+        };
+        elements.push(RelativePathElement {
+            reference_type_id,
+            is_inverse,
+            include_subtypes,
+            target_name,
+        });
+    }
+    Ok(RelativePath {
+        elements: Some(elements)
+    })
+}
+
+/// Consumes the next character if it is the one expected
+fn next_if_eq(chars: &mut Peekable<Chars<'_>>, expected: char) -> bool {
+    if chars.peek() == Some(&expected) {
+        chars.next();
+        true
+    } else {
+        false
+    }
+}
+
+/// Parses a browse name up to one of the terminating characters, or the end of the path, into
+/// its namespace index and unescaped name. The namespace index is 0 if the name has none.
+fn parse_browse_name(chars: &mut Peekable<Chars<'_>>, terminators: &[char]) -> Result<(u16, String), StatusCode> {
+    let mut namespace = None;
+    let mut name = String::new();
+    // Whether the name so far could be a namespace index
+    let mut digits_only = true;
+    while let Some(c) = chars.peek().cloned() {
+        if terminators.contains(&c) {
+            break;
+        }
+        chars.next();
+        match c {
+            '&' => {
+                if let Some(c) = chars.next() {
+                    name.push(c);
+                    digits_only = false;
+                } else {
+                    error!("Browse name ends with an escape character");
+                    return Err(StatusCode::BadBrowseNameInvalid);
+                }
+            }
+            ':' if namespace.is_none() && digits_only && !name.is_empty() => {
+                namespace = Some(name.parse::<u16>().map_err(|_| {
+                    error!("Namespace {} is out of range", name);
+                    StatusCode::BadBrowseNameInvalid
+                })?);
+                name.clear();
+            }
+            c if BROWSE_NAME_RESERVED_CHARS.contains(c) => {
+                error!("Browse name contains the reserved character {} without escaping it", c);
+                return Err(StatusCode::BadBrowseNameInvalid);
+            }
+            c => {
+                digits_only = digits_only && c.is_ascii_digit();
+                name.push(c);
+            }
+        }
+        if name.len() > RelativePath::MAX_TOKEN_LEN {
+            error!("Path segment seems unusually long and has been rejected");
+            return Err(StatusCode::BadBrowseNameInvalid);
         }
     }
+    Ok((namespace.unwrap_or(0), name))
 }
 
 /// Reserved characters in the browse name which must be escaped with a &
@@ -330,59 +435,31 @@ const BROWSE_NAME_RESERVED_CHARS: &str = "&/.<>:#!";
 
 /// Escapes reserved characters in the browse name
 fn escape_browse_name(name: &str) -> String {
-    let mut result = String::from(name);
-    BROWSE_NAME_RESERVED_CHARS.chars().for_each(|c| {
-        result = result.replace(c, &format!("&{}", c));
+    let mut result = String::with_capacity(name.len());
+    name.chars().for_each(|c| {
+        if BROWSE_NAME_RESERVED_CHARS.contains(c) {
+            result.push('&');
+        }
+        result.push(c);
     });
     result
 }
 
 /// Unescapes reserved characters in the browse name
+#[cfg(test)]
 fn unescape_browse_name(name: &str) -> String {
-    let mut result = String::from(name);
-    BROWSE_NAME_RESERVED_CHARS.chars().for_each(|c| {
-        result = result.replace(&format!("&{}", c), &c.to_string());
-    });
-    result
-}
-
-/// Parse a target name into a qualified name. The name is either `nsidx:name` or just
-/// `name`, where `nsidx` is a numeric index and `name` may contain escaped reserved chars.
-///
-/// # Examples
-///
-/// * 0:foo
-/// * bar
-///
-fn target_name(target_name: &str) -> Result<QualifiedName, ()> {
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"((?P<nsidx>[0-9+]):)?(?P<name>.*)").unwrap();
-    }
-    if let Some(captures) = RE.captures(&target_name) {
-        let namespace = if let Some(namespace) = captures.name("nsidx") {
-            if let Ok(namespace) = namespace.as_str().parse::<u16>() {
-                namespace
-            } else {
-                error!("Namespace {} for target name is out of range", namespace.as_str());
-                return Err(());
-            }
-        } else {
-            0
-        };
-        let name = if let Some(name) = captures.name("name") {
-            let name = name.as_str();
-            if name.is_empty() {
-                UAString::null()
-            } else {
-                UAString::from(unescape_browse_name(name))
+    let mut result = String::with_capacity(name.len());
+    let mut chars = name.chars();
+    while let Some(c) = chars.next() {
+        if c == '&' {
+            if let Some(c) = chars.next() {
+                result.push(c);
             }
         } else {
-            UAString::null()
-        };
-        Ok(QualifiedName::new(namespace, name))
-    } else {
-        Ok(QualifiedName::null())
+            result.push(c);
+        }
     }
+    result
 }
 
 /// Test that escaping of browse names works as expected in each direction
@@ -458,7 +535,7 @@ fn test_relative_path_element() {
         assert_eq!(expected, actual);
 
         // Turn string back to element, compare to original element
-        let actual = RelativePathElement::from_str(&actual, &RelativePathElement::default_node_resolver).unwrap();
+        let actual = RelativePathElement::parse_with_resolver(&actual, &RelativePathElement::default_node_resolver).unwrap();
         assert_eq!(*element, actual);
     });
 }
@@ -558,7 +635,81 @@ fn test_relative_path() {
         assert_eq!(expected, actual);
 
         // Turn string back to element, compare to original path
-        let actual = RelativePath::from_str(&actual, &RelativePathElement::default_node_resolver).unwrap();
+        let actual = RelativePath::parse_with_resolver(&actual, &RelativePathElement::default_node_resolver).unwrap();
         assert_eq!(relative_path, actual);
     });
 }
+
+/// Test the text format through `FromStr` and `Display`, including the samples from OPC UA Part 4
+/// Appendix A
+#[test]
+fn test_relative_path_from_str() {
+    [
+        "/2:Block&.Output",
+        "/3:Truck.0:NodeVersion",
+        "<HasChild>2:Wheel",
+        "<!HasChild>0:Truck",
+        "<#HasComponent>1:Motor",
+        "<#!Organizes>0:Objects",
+        "<HasChild>",
+        "/12:Deep/300:Deeper",
+        "/0:a&&b&:c&#d&!e&<f&>g",
+        "<HasAddIn>0:Plugin",
+    ].iter().for_each(|path| {
+        let relative_path = path.parse::<RelativePath>().unwrap();
+        assert_eq!(relative_path.to_string(), *path);
+    });
+
+    // Namespace 0 may be left out and long namespace indices are allowed
+    let relative_path = "/Objects/Server.ServerStatus".parse::<RelativePath>().unwrap();
+    let elements = relative_path.elements.unwrap();
+    assert_eq!(elements.len(), 3);
+    assert_eq!(elements[2].reference_type_id, ReferenceTypeId::Aggregates.into());
+    assert_eq!(elements[2].target_name, QualifiedName::new(0, "ServerStatus"));
+    let relative_path = "<#!HasComponent>123:Name".parse::<RelativePath>().unwrap();
+    let element = &relative_path.elements.unwrap()[0];
+    assert!(!element.include_subtypes);
+    assert!(element.is_inverse);
+    assert_eq!(element.reference_type_id, ReferenceTypeId::HasComponent.into());
+    assert_eq!(element.target_name, QualifiedName::new(123, "Name"));
+    // A colon after anything other than a namespace index must be escaped
+    assert_eq!("/ab:cd".parse::<RelativePath>(), Err(StatusCode::BadBrowseNameInvalid));
+    assert_eq!("/1:ab&:cd".parse::<RelativePath>().unwrap().elements.unwrap()[0].target_name, QualifiedName::new(1, "ab:cd"));
+
+    // Bad syntax
+    [
+        "Objects",
+        "/Objects>",
+        "/Obj#ects",
+        "/Objects&",
+        "<HasChild",
+        "<>Objects",
+        "/99999:Objects",
+    ].iter().for_each(|path| {
+        assert_eq!(path.parse::<RelativePath>(), Err(StatusCode::BadBrowseNameInvalid), "path {}", path);
+    });
+
+    // Reference types must be known
+    assert_eq!("<ConnectedTo>1:Boiler".parse::<RelativePath>(), Err(StatusCode::BadReferenceTypeIdInvalid));
+    assert_eq!("<2:HasChild>1:Boiler".parse::<RelativePath>(), Err(StatusCode::BadReferenceTypeIdInvalid));
+}
+
+/// Test that reference types of other namespaces are named from a table
+#[test]
+fn test_reference_type_names() {
+    let mut reference_types = ReferenceTypeNames::new();
+    reference_types.add(QualifiedName::new(2, "ConnectedTo"), NodeId::new(2, 5000));
+    assert_eq!(reference_types.node_id(2, "ConnectedTo"), Some(NodeId::new(2, 5000)));
+    assert_eq!(reference_types.node_id(0, "HasChild"), Some(ReferenceTypeId::HasChild.into()));
+    assert_eq!(reference_types.node_id(0, "ConnectedTo"), None);
+    assert_eq!(reference_types.browse_name(&ReferenceTypeId::Organizes.into()), Some(QualifiedName::new(0, "Organizes")));
+
+    let path = "<2:ConnectedTo>1:Boiler/1:HeatSensor";
+    let relative_path = RelativePath::parse_with(path, &reference_types).unwrap();
+    let elements = relative_path.elements.as_ref().unwrap();
+    assert_eq!(elements[0].reference_type_id, NodeId::new(2, 5000));
+    assert_eq!(relative_path.to_string_with(&reference_types), path);
+
+    // Without the table the reference type can only be named by its node id
+    assert_eq!(relative_path.to_string(), "<ns=2;i=5000>1:Boiler/1:HeatSensor");
+}
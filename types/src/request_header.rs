@@ -9,6 +9,7 @@ use crate::{
     date_time::DateTime,
     diagnostic_info::DiagnosticBits,
     encoding::*,
+    json::*,
     extension_object::ExtensionObject,
     node_id::NodeId,
     string::UAString,
@@ -133,6 +134,33 @@ impl BinaryEncoder<RequestHeader> for RequestHeader {
     }
 }
 
+impl JsonEncoder<RequestHeader> for RequestHeader {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "AuthenticationToken", &self.authentication_token, ctx);
+        write_json_field(&mut object, "Timestamp", &self.timestamp, ctx);
+        write_json_field(&mut object, "RequestHandle", &self.request_handle, ctx);
+        write_json_field(&mut object, "ReturnDiagnostics", &self.return_diagnostics.bits(), ctx);
+        write_json_field(&mut object, "AuditEntryId", &self.audit_entry_id, ctx);
+        write_json_field(&mut object, "TimeoutHint", &self.timeout_hint, ctx);
+        write_json_field(&mut object, "AdditionalHeader", &self.additional_header, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(RequestHeader {
+            authentication_token: read_json_field(object, "AuthenticationToken", decoding_limits)?,
+            timestamp: read_json_field(object, "Timestamp", decoding_limits)?,
+            request_handle: read_json_field(object, "RequestHandle", decoding_limits)?,
+            return_diagnostics: DiagnosticBits::from_bits_truncate(read_json_field(object, "ReturnDiagnostics", decoding_limits)?),
+            audit_entry_id: read_json_field(object, "AuditEntryId", decoding_limits)?,
+            timeout_hint: read_json_field(object, "TimeoutHint", decoding_limits)?,
+            additional_header: read_json_field(object, "AdditionalHeader", decoding_limits)?,
+        })
+    }
+}
+
 impl RequestHeader {
     pub fn new(authentication_token: &NodeId, timestamp: &DateTime, request_handle: IntegerId) -> RequestHeader {
         RequestHeader {
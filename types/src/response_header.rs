@@ -9,6 +9,7 @@ use crate::{
     date_time::DateTime,
     diagnostic_info::DiagnosticInfo,
     encoding::*,
+    json::*,
     extension_object::ExtensionObject,
     request_header::RequestHeader,
     status_codes::StatusCode,
@@ -68,6 +69,31 @@ impl BinaryEncoder<ResponseHeader> for ResponseHeader {
     }
 }
 
+impl JsonEncoder<ResponseHeader> for ResponseHeader {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Timestamp", &self.timestamp, ctx);
+        write_json_field(&mut object, "RequestHandle", &self.request_handle, ctx);
+        write_json_field(&mut object, "ServiceResult", &self.service_result, ctx);
+        write_json_field(&mut object, "ServiceDiagnostics", &self.service_diagnostics, ctx);
+        write_json_field(&mut object, "StringTable", &self.string_table, ctx);
+        write_json_field(&mut object, "AdditionalHeader", &self.additional_header, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ResponseHeader {
+            timestamp: read_json_field(object, "Timestamp", decoding_limits)?,
+            request_handle: read_json_field(object, "RequestHandle", decoding_limits)?,
+            service_result: read_json_field(object, "ServiceResult", decoding_limits)?,
+            service_diagnostics: read_json_field(object, "ServiceDiagnostics", decoding_limits)?,
+            string_table: read_json_field(object, "StringTable", decoding_limits)?,
+            additional_header: read_json_field(object, "AdditionalHeader", decoding_limits)?,
+        })
+    }
+}
+
 impl ResponseHeader {
     pub fn new_good(request_header: &RequestHeader) -> ResponseHeader {
         ResponseHeader::new_service_result(request_header, StatusCode::Good)
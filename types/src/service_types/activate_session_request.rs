@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -78,3 +79,28 @@ impl BinaryEncoder<ActivateSessionRequest> for ActivateSessionRequest {
         })
     }
 }
+
+impl JsonEncoder<ActivateSessionRequest> for ActivateSessionRequest {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, ctx);
+        write_json_field(&mut object, "ClientSignature", &self.client_signature, ctx);
+        write_json_field(&mut object, "ClientSoftwareCertificates", &self.client_software_certificates, ctx);
+        write_json_field(&mut object, "LocaleIds", &self.locale_ids, ctx);
+        write_json_field(&mut object, "UserIdentityToken", &self.user_identity_token, ctx);
+        write_json_field(&mut object, "UserTokenSignature", &self.user_token_signature, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ActivateSessionRequest {
+            request_header: read_json_field(object, "RequestHeader", decoding_limits)?,
+            client_signature: read_json_field(object, "ClientSignature", decoding_limits)?,
+            client_software_certificates: read_json_field(object, "ClientSoftwareCertificates", decoding_limits)?,
+            locale_ids: read_json_field(object, "LocaleIds", decoding_limits)?,
+            user_identity_token: read_json_field(object, "UserIdentityToken", decoding_limits)?,
+            user_token_signature: read_json_field(object, "UserTokenSignature", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -67,3 +68,24 @@ impl BinaryEncoder<ActivateSessionResponse> for ActivateSessionResponse {
         })
     }
 }
+
+impl JsonEncoder<ActivateSessionResponse> for ActivateSessionResponse {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ResponseHeader", &self.response_header, ctx);
+        write_json_field(&mut object, "ServerNonce", &self.server_nonce, ctx);
+        write_json_field(&mut object, "Results", &self.results, ctx);
+        write_json_field(&mut object, "DiagnosticInfos", &self.diagnostic_infos, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ActivateSessionResponse {
+            response_header: read_json_field(object, "ResponseHeader", decoding_limits)?,
+            server_nonce: read_json_field(object, "ServerNonce", decoding_limits)?,
+            results: read_json_field(object, "Results", decoding_limits)?,
+            diagnostic_infos: read_json_field(object, "DiagnosticInfos", decoding_limits)?,
+        })
+    }
+}
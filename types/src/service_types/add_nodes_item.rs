@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -83,3 +84,30 @@ impl BinaryEncoder<AddNodesItem> for AddNodesItem {
         })
     }
 }
+
+impl JsonEncoder<AddNodesItem> for AddNodesItem {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ParentNodeId", &self.parent_node_id, ctx);
+        write_json_field(&mut object, "ReferenceTypeId", &self.reference_type_id, ctx);
+        write_json_field(&mut object, "RequestedNewNodeId", &self.requested_new_node_id, ctx);
+        write_json_field(&mut object, "BrowseName", &self.browse_name, ctx);
+        write_json_field(&mut object, "NodeClass", &self.node_class, ctx);
+        write_json_field(&mut object, "NodeAttributes", &self.node_attributes, ctx);
+        write_json_field(&mut object, "TypeDefinition", &self.type_definition, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(AddNodesItem {
+            parent_node_id: read_json_field(object, "ParentNodeId", decoding_limits)?,
+            reference_type_id: read_json_field(object, "ReferenceTypeId", decoding_limits)?,
+            requested_new_node_id: read_json_field(object, "RequestedNewNodeId", decoding_limits)?,
+            browse_name: read_json_field(object, "BrowseName", decoding_limits)?,
+            node_class: read_json_field(object, "NodeClass", decoding_limits)?,
+            node_attributes: read_json_field(object, "NodeAttributes", decoding_limits)?,
+            type_definition: read_json_field(object, "TypeDefinition", decoding_limits)?,
+        })
+    }
+}
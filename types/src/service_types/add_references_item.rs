@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -77,3 +78,28 @@ impl BinaryEncoder<AddReferencesItem> for AddReferencesItem {
         })
     }
 }
+
+impl JsonEncoder<AddReferencesItem> for AddReferencesItem {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "SourceNodeId", &self.source_node_id, ctx);
+        write_json_field(&mut object, "ReferenceTypeId", &self.reference_type_id, ctx);
+        write_json_field(&mut object, "IsForward", &self.is_forward, ctx);
+        write_json_field(&mut object, "TargetServerUri", &self.target_server_uri, ctx);
+        write_json_field(&mut object, "TargetNodeId", &self.target_node_id, ctx);
+        write_json_field(&mut object, "TargetNodeClass", &self.target_node_class, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(AddReferencesItem {
+            source_node_id: read_json_field(object, "SourceNodeId", decoding_limits)?,
+            reference_type_id: read_json_field(object, "ReferenceTypeId", decoding_limits)?,
+            is_forward: read_json_field(object, "IsForward", decoding_limits)?,
+            target_server_uri: read_json_field(object, "TargetServerUri", decoding_limits)?,
+            target_node_id: read_json_field(object, "TargetNodeId", decoding_limits)?,
+            target_node_class: read_json_field(object, "TargetNodeClass", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -55,3 +56,20 @@ impl BinaryEncoder<AddReferencesRequest> for AddReferencesRequest {
         })
     }
 }
+
+impl JsonEncoder<AddReferencesRequest> for AddReferencesRequest {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, ctx);
+        write_json_field(&mut object, "ReferencesToAdd", &self.references_to_add, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(AddReferencesRequest {
+            request_header: read_json_field(object, "RequestHeader", decoding_limits)?,
+            references_to_add: read_json_field(object, "ReferencesToAdd", decoding_limits)?,
+        })
+    }
+}
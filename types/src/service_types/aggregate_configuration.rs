@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -68,3 +69,26 @@ impl BinaryEncoder<AggregateConfiguration> for AggregateConfiguration {
         })
     }
 }
+
+impl JsonEncoder<AggregateConfiguration> for AggregateConfiguration {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "UseServerCapabilitiesDefaults", &self.use_server_capabilities_defaults, ctx);
+        write_json_field(&mut object, "TreatUncertainAsBad", &self.treat_uncertain_as_bad, ctx);
+        write_json_field(&mut object, "PercentDataBad", &self.percent_data_bad, ctx);
+        write_json_field(&mut object, "PercentDataGood", &self.percent_data_good, ctx);
+        write_json_field(&mut object, "UseSlopedExtrapolation", &self.use_sloped_extrapolation, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(AggregateConfiguration {
+            use_server_capabilities_defaults: read_json_field(object, "UseServerCapabilitiesDefaults", decoding_limits)?,
+            treat_uncertain_as_bad: read_json_field(object, "TreatUncertainAsBad", decoding_limits)?,
+            percent_data_bad: read_json_field(object, "PercentDataBad", decoding_limits)?,
+            percent_data_good: read_json_field(object, "PercentDataGood", decoding_limits)?,
+            use_sloped_extrapolation: read_json_field(object, "UseSlopedExtrapolation", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     date_time::DateTime,
     node_id::NodeId,
@@ -58,3 +59,24 @@ impl BinaryEncoder<AggregateFilter> for AggregateFilter {
         })
     }
 }
+
+impl JsonEncoder<AggregateFilter> for AggregateFilter {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "StartTime", &self.start_time, ctx);
+        write_json_field(&mut object, "AggregateType", &self.aggregate_type, ctx);
+        write_json_field(&mut object, "ProcessingInterval", &self.processing_interval, ctx);
+        write_json_field(&mut object, "AggregateConfiguration", &self.aggregate_configuration, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(AggregateFilter {
+            start_time: read_json_field(object, "StartTime", decoding_limits)?,
+            aggregate_type: read_json_field(object, "AggregateType", decoding_limits)?,
+            processing_interval: read_json_field(object, "ProcessingInterval", decoding_limits)?,
+            aggregate_configuration: read_json_field(object, "AggregateConfiguration", decoding_limits)?,
+        })
+    }
+}
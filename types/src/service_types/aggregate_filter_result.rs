@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     date_time::DateTime,
     service_types::AggregateConfiguration,
@@ -52,3 +53,22 @@ impl BinaryEncoder<AggregateFilterResult> for AggregateFilterResult {
         })
     }
 }
+
+impl JsonEncoder<AggregateFilterResult> for AggregateFilterResult {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RevisedStartTime", &self.revised_start_time, ctx);
+        write_json_field(&mut object, "RevisedProcessingInterval", &self.revised_processing_interval, ctx);
+        write_json_field(&mut object, "RevisedAggregateConfiguration", &self.revised_aggregate_configuration, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(AggregateFilterResult {
+            revised_start_time: read_json_field(object, "RevisedStartTime", decoding_limits)?,
+            revised_processing_interval: read_json_field(object, "RevisedProcessingInterval", decoding_limits)?,
+            revised_aggregate_configuration: read_json_field(object, "RevisedAggregateConfiguration", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -60,3 +61,22 @@ impl BinaryEncoder<Annotation> for Annotation {
         })
     }
 }
+
+impl JsonEncoder<Annotation> for Annotation {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Message", &self.message, ctx);
+        write_json_field(&mut object, "UserName", &self.user_name, ctx);
+        write_json_field(&mut object, "AnnotationTime", &self.annotation_time, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(Annotation {
+            message: read_json_field(object, "Message", decoding_limits)?,
+            user_name: read_json_field(object, "UserName", decoding_limits)?,
+            annotation_time: read_json_field(object, "AnnotationTime", decoding_limits)?,
+        })
+    }
+}
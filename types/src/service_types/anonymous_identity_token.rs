@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     string::UAString,
 };
@@ -41,3 +42,18 @@ impl BinaryEncoder<AnonymousIdentityToken> for AnonymousIdentityToken {
         })
     }
 }
+
+impl JsonEncoder<AnonymousIdentityToken> for AnonymousIdentityToken {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "PolicyId", &self.policy_id, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(AnonymousIdentityToken {
+            policy_id: read_json_field(object, "PolicyId", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -81,3 +82,30 @@ impl BinaryEncoder<ApplicationDescription> for ApplicationDescription {
         })
     }
 }
+
+impl JsonEncoder<ApplicationDescription> for ApplicationDescription {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ApplicationUri", &self.application_uri, ctx);
+        write_json_field(&mut object, "ProductUri", &self.product_uri, ctx);
+        write_json_field(&mut object, "ApplicationName", &self.application_name, ctx);
+        write_json_field(&mut object, "ApplicationType", &self.application_type, ctx);
+        write_json_field(&mut object, "GatewayServerUri", &self.gateway_server_uri, ctx);
+        write_json_field(&mut object, "DiscoveryProfileUri", &self.discovery_profile_uri, ctx);
+        write_json_field(&mut object, "DiscoveryUrls", &self.discovery_urls, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ApplicationDescription {
+            application_uri: read_json_field(object, "ApplicationUri", decoding_limits)?,
+            product_uri: read_json_field(object, "ProductUri", decoding_limits)?,
+            application_name: read_json_field(object, "ApplicationName", decoding_limits)?,
+            application_type: read_json_field(object, "ApplicationType", decoding_limits)?,
+            gateway_server_uri: read_json_field(object, "GatewayServerUri", decoding_limits)?,
+            discovery_profile_uri: read_json_field(object, "DiscoveryProfileUri", decoding_limits)?,
+            discovery_urls: read_json_field(object, "DiscoveryUrls", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -71,3 +72,26 @@ impl BinaryEncoder<Argument> for Argument {
         })
     }
 }
+
+impl JsonEncoder<Argument> for Argument {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Name", &self.name, ctx);
+        write_json_field(&mut object, "DataType", &self.data_type, ctx);
+        write_json_field(&mut object, "ValueRank", &self.value_rank, ctx);
+        write_json_field(&mut object, "ArrayDimensions", &self.array_dimensions, ctx);
+        write_json_field(&mut object, "Description", &self.description, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(Argument {
+            name: read_json_field(object, "Name", decoding_limits)?,
+            data_type: read_json_field(object, "DataType", decoding_limits)?,
+            value_rank: read_json_field(object, "ValueRank", decoding_limits)?,
+            array_dimensions: read_json_field(object, "ArrayDimensions", decoding_limits)?,
+            description: read_json_field(object, "Description", decoding_limits)?,
+        })
+    }
+}
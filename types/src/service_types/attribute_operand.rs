@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     node_id::NodeId,
     string::UAString,
@@ -63,3 +64,26 @@ impl BinaryEncoder<AttributeOperand> for AttributeOperand {
         })
     }
 }
+
+impl JsonEncoder<AttributeOperand> for AttributeOperand {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NodeId", &self.node_id, ctx);
+        write_json_field(&mut object, "Alias", &self.alias, ctx);
+        write_json_field(&mut object, "BrowsePath", &self.browse_path, ctx);
+        write_json_field(&mut object, "AttributeId", &self.attribute_id, ctx);
+        write_json_field(&mut object, "IndexRange", &self.index_range, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(AttributeOperand {
+            node_id: read_json_field(object, "NodeId", decoding_limits)?,
+            alias: read_json_field(object, "Alias", decoding_limits)?,
+            browse_path: read_json_field(object, "BrowsePath", decoding_limits)?,
+            attribute_id: read_json_field(object, "AttributeId", decoding_limits)?,
+            index_range: read_json_field(object, "IndexRange", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -72,3 +73,26 @@ impl BinaryEncoder<AxisInformation> for AxisInformation {
         })
     }
 }
+
+impl JsonEncoder<AxisInformation> for AxisInformation {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "EngineeringUnits", &self.engineering_units, ctx);
+        write_json_field(&mut object, "EURange", &self.eu_range, ctx);
+        write_json_field(&mut object, "Title", &self.title, ctx);
+        write_json_field(&mut object, "AxisScaleType", &self.axis_scale_type, ctx);
+        write_json_field(&mut object, "AxisSteps", &self.axis_steps, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(AxisInformation {
+            engineering_units: read_json_field(object, "EngineeringUnits", decoding_limits)?,
+            eu_range: read_json_field(object, "EURange", decoding_limits)?,
+            title: read_json_field(object, "Title", decoding_limits)?,
+            axis_scale_type: read_json_field(object, "AxisScaleType", decoding_limits)?,
+            axis_steps: read_json_field(object, "AxisSteps", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     string::UAString,
 };
@@ -46,3 +47,20 @@ impl BinaryEncoder<BrokerConnectionTransportDataType> for BrokerConnectionTransp
         })
     }
 }
+
+impl JsonEncoder<BrokerConnectionTransportDataType> for BrokerConnectionTransportDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ResourceUri", &self.resource_uri, ctx);
+        write_json_field(&mut object, "AuthenticationProfileUri", &self.authentication_profile_uri, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(BrokerConnectionTransportDataType {
+            resource_uri: read_json_field(object, "ResourceUri", decoding_limits)?,
+            authentication_profile_uri: read_json_field(object, "AuthenticationProfileUri", decoding_limits)?,
+        })
+    }
+}
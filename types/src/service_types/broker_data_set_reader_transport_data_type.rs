@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     string::UAString,
     service_types::enums::BrokerTransportQualityOfService,
@@ -62,3 +63,26 @@ impl BinaryEncoder<BrokerDataSetReaderTransportDataType> for BrokerDataSetReader
         })
     }
 }
+
+impl JsonEncoder<BrokerDataSetReaderTransportDataType> for BrokerDataSetReaderTransportDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "QueueName", &self.queue_name, ctx);
+        write_json_field(&mut object, "ResourceUri", &self.resource_uri, ctx);
+        write_json_field(&mut object, "AuthenticationProfileUri", &self.authentication_profile_uri, ctx);
+        write_json_field(&mut object, "RequestedDeliveryGuarantee", &self.requested_delivery_guarantee, ctx);
+        write_json_field(&mut object, "MetaDataQueueName", &self.meta_data_queue_name, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(BrokerDataSetReaderTransportDataType {
+            queue_name: read_json_field(object, "QueueName", decoding_limits)?,
+            resource_uri: read_json_field(object, "ResourceUri", decoding_limits)?,
+            authentication_profile_uri: read_json_field(object, "AuthenticationProfileUri", decoding_limits)?,
+            requested_delivery_guarantee: read_json_field(object, "RequestedDeliveryGuarantee", decoding_limits)?,
+            meta_data_queue_name: read_json_field(object, "MetaDataQueueName", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     string::UAString,
     service_types::enums::BrokerTransportQualityOfService,
@@ -57,3 +58,24 @@ impl BinaryEncoder<BrokerWriterGroupTransportDataType> for BrokerWriterGroupTran
         })
     }
 }
+
+impl JsonEncoder<BrokerWriterGroupTransportDataType> for BrokerWriterGroupTransportDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "QueueName", &self.queue_name, ctx);
+        write_json_field(&mut object, "ResourceUri", &self.resource_uri, ctx);
+        write_json_field(&mut object, "AuthenticationProfileUri", &self.authentication_profile_uri, ctx);
+        write_json_field(&mut object, "RequestedDeliveryGuarantee", &self.requested_delivery_guarantee, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(BrokerWriterGroupTransportDataType {
+            queue_name: read_json_field(object, "QueueName", decoding_limits)?,
+            resource_uri: read_json_field(object, "ResourceUri", decoding_limits)?,
+            authentication_profile_uri: read_json_field(object, "AuthenticationProfileUri", decoding_limits)?,
+            requested_delivery_guarantee: read_json_field(object, "RequestedDeliveryGuarantee", decoding_limits)?,
+        })
+    }
+}
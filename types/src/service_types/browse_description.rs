@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -75,3 +76,28 @@ impl BinaryEncoder<BrowseDescription> for BrowseDescription {
         })
     }
 }
+
+impl JsonEncoder<BrowseDescription> for BrowseDescription {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NodeId", &self.node_id, ctx);
+        write_json_field(&mut object, "BrowseDirection", &self.browse_direction, ctx);
+        write_json_field(&mut object, "ReferenceTypeId", &self.reference_type_id, ctx);
+        write_json_field(&mut object, "IncludeSubtypes", &self.include_subtypes, ctx);
+        write_json_field(&mut object, "NodeClassMask", &self.node_class_mask, ctx);
+        write_json_field(&mut object, "ResultMask", &self.result_mask, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(BrowseDescription {
+            node_id: read_json_field(object, "NodeId", decoding_limits)?,
+            browse_direction: read_json_field(object, "BrowseDirection", decoding_limits)?,
+            reference_type_id: read_json_field(object, "ReferenceTypeId", decoding_limits)?,
+            include_subtypes: read_json_field(object, "IncludeSubtypes", decoding_limits)?,
+            node_class_mask: read_json_field(object, "NodeClassMask", decoding_limits)?,
+            result_mask: read_json_field(object, "ResultMask", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -60,3 +61,22 @@ impl BinaryEncoder<BrowseNextRequest> for BrowseNextRequest {
         })
     }
 }
+
+impl JsonEncoder<BrowseNextRequest> for BrowseNextRequest {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, ctx);
+        write_json_field(&mut object, "ReleaseContinuationPoints", &self.release_continuation_points, ctx);
+        write_json_field(&mut object, "ContinuationPoints", &self.continuation_points, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(BrowseNextRequest {
+            request_header: read_json_field(object, "RequestHeader", decoding_limits)?,
+            release_continuation_points: read_json_field(object, "ReleaseContinuationPoints", decoding_limits)?,
+            continuation_points: read_json_field(object, "ContinuationPoints", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -55,3 +56,20 @@ impl BinaryEncoder<BrowsePath> for BrowsePath {
         })
     }
 }
+
+impl JsonEncoder<BrowsePath> for BrowsePath {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "StartingNode", &self.starting_node, ctx);
+        write_json_field(&mut object, "RelativePath", &self.relative_path, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(BrowsePath {
+            starting_node: read_json_field(object, "StartingNode", decoding_limits)?,
+            relative_path: read_json_field(object, "RelativePath", decoding_limits)?,
+        })
+    }
+}
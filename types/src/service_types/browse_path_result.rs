@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -55,3 +56,20 @@ impl BinaryEncoder<BrowsePathResult> for BrowsePathResult {
         })
     }
 }
+
+impl JsonEncoder<BrowsePathResult> for BrowsePathResult {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "StatusCode", &self.status_code, ctx);
+        write_json_field(&mut object, "Targets", &self.targets, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(BrowsePathResult {
+            status_code: read_json_field(object, "StatusCode", decoding_limits)?,
+            targets: read_json_field(object, "Targets", decoding_limits)?,
+        })
+    }
+}
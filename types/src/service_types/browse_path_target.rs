@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -54,3 +55,20 @@ impl BinaryEncoder<BrowsePathTarget> for BrowsePathTarget {
         })
     }
 }
+
+impl JsonEncoder<BrowsePathTarget> for BrowsePathTarget {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "TargetId", &self.target_id, ctx);
+        write_json_field(&mut object, "RemainingPathIndex", &self.remaining_path_index, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(BrowsePathTarget {
+            target_id: read_json_field(object, "TargetId", decoding_limits)?,
+            remaining_path_index: read_json_field(object, "RemainingPathIndex", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -66,3 +67,24 @@ impl BinaryEncoder<BrowseRequest> for BrowseRequest {
         })
     }
 }
+
+impl JsonEncoder<BrowseRequest> for BrowseRequest {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, ctx);
+        write_json_field(&mut object, "View", &self.view, ctx);
+        write_json_field(&mut object, "RequestedMaxReferencesPerNode", &self.requested_max_references_per_node, ctx);
+        write_json_field(&mut object, "NodesToBrowse", &self.nodes_to_browse, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(BrowseRequest {
+            request_header: read_json_field(object, "RequestHeader", decoding_limits)?,
+            view: read_json_field(object, "View", decoding_limits)?,
+            requested_max_references_per_node: read_json_field(object, "RequestedMaxReferencesPerNode", decoding_limits)?,
+            nodes_to_browse: read_json_field(object, "NodesToBrowse", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -75,3 +76,28 @@ impl BinaryEncoder<BuildInfo> for BuildInfo {
         })
     }
 }
+
+impl JsonEncoder<BuildInfo> for BuildInfo {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ProductUri", &self.product_uri, ctx);
+        write_json_field(&mut object, "ManufacturerName", &self.manufacturer_name, ctx);
+        write_json_field(&mut object, "ProductName", &self.product_name, ctx);
+        write_json_field(&mut object, "SoftwareVersion", &self.software_version, ctx);
+        write_json_field(&mut object, "BuildNumber", &self.build_number, ctx);
+        write_json_field(&mut object, "BuildDate", &self.build_date, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(BuildInfo {
+            product_uri: read_json_field(object, "ProductUri", decoding_limits)?,
+            manufacturer_name: read_json_field(object, "ManufacturerName", decoding_limits)?,
+            product_name: read_json_field(object, "ProductName", decoding_limits)?,
+            software_version: read_json_field(object, "SoftwareVersion", decoding_limits)?,
+            build_number: read_json_field(object, "BuildNumber", decoding_limits)?,
+            build_date: read_json_field(object, "BuildDate", decoding_limits)?,
+        })
+    }
+}
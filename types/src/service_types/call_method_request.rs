@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -60,3 +61,22 @@ impl BinaryEncoder<CallMethodRequest> for CallMethodRequest {
         })
     }
 }
+
+impl JsonEncoder<CallMethodRequest> for CallMethodRequest {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ObjectId", &self.object_id, ctx);
+        write_json_field(&mut object, "MethodId", &self.method_id, ctx);
+        write_json_field(&mut object, "InputArguments", &self.input_arguments, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(CallMethodRequest {
+            object_id: read_json_field(object, "ObjectId", decoding_limits)?,
+            method_id: read_json_field(object, "MethodId", decoding_limits)?,
+            input_arguments: read_json_field(object, "InputArguments", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -66,3 +67,24 @@ impl BinaryEncoder<CallMethodResult> for CallMethodResult {
         })
     }
 }
+
+impl JsonEncoder<CallMethodResult> for CallMethodResult {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "StatusCode", &self.status_code, ctx);
+        write_json_field(&mut object, "InputArgumentResults", &self.input_argument_results, ctx);
+        write_json_field(&mut object, "InputArgumentDiagnosticInfos", &self.input_argument_diagnostic_infos, ctx);
+        write_json_field(&mut object, "OutputArguments", &self.output_arguments, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(CallMethodResult {
+            status_code: read_json_field(object, "StatusCode", decoding_limits)?,
+            input_argument_results: read_json_field(object, "InputArgumentResults", decoding_limits)?,
+            input_argument_diagnostic_infos: read_json_field(object, "InputArgumentDiagnosticInfos", decoding_limits)?,
+            output_arguments: read_json_field(object, "OutputArguments", decoding_limits)?,
+        })
+    }
+}
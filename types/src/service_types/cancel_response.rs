@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -54,3 +55,20 @@ impl BinaryEncoder<CancelResponse> for CancelResponse {
         })
     }
 }
+
+impl JsonEncoder<CancelResponse> for CancelResponse {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ResponseHeader", &self.response_header, ctx);
+        write_json_field(&mut object, "CancelCount", &self.cancel_count, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(CancelResponse {
+            response_header: read_json_field(object, "ResponseHeader", decoding_limits)?,
+            cancel_count: read_json_field(object, "CancelCount", decoding_limits)?,
+        })
+    }
+}
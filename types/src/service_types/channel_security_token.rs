@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -64,3 +65,24 @@ impl BinaryEncoder<ChannelSecurityToken> for ChannelSecurityToken {
         })
     }
 }
+
+impl JsonEncoder<ChannelSecurityToken> for ChannelSecurityToken {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ChannelId", &self.channel_id, ctx);
+        write_json_field(&mut object, "TokenId", &self.token_id, ctx);
+        write_json_field(&mut object, "CreatedAt", &self.created_at, ctx);
+        write_json_field(&mut object, "RevisedLifetime", &self.revised_lifetime, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ChannelSecurityToken {
+            channel_id: read_json_field(object, "ChannelId", decoding_limits)?,
+            token_id: read_json_field(object, "TokenId", decoding_limits)?,
+            created_at: read_json_field(object, "CreatedAt", decoding_limits)?,
+            revised_lifetime: read_json_field(object, "RevisedLifetime", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -49,3 +50,18 @@ impl BinaryEncoder<CloseSecureChannelRequest> for CloseSecureChannelRequest {
         })
     }
 }
+
+impl JsonEncoder<CloseSecureChannelRequest> for CloseSecureChannelRequest {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(CloseSecureChannelRequest {
+            request_header: read_json_field(object, "RequestHeader", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -54,3 +55,20 @@ impl BinaryEncoder<CloseSessionRequest> for CloseSessionRequest {
         })
     }
 }
+
+impl JsonEncoder<CloseSessionRequest> for CloseSessionRequest {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, ctx);
+        write_json_field(&mut object, "DeleteSubscriptions", &self.delete_subscriptions, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(CloseSessionRequest {
+            request_header: read_json_field(object, "RequestHeader", decoding_limits)?,
+            delete_subscriptions: read_json_field(object, "DeleteSubscriptions", decoding_limits)?,
+        })
+    }
+}
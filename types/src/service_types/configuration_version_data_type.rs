@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -53,3 +54,20 @@ impl BinaryEncoder<ConfigurationVersionDataType> for ConfigurationVersionDataTyp
         })
     }
 }
+
+impl JsonEncoder<ConfigurationVersionDataType> for ConfigurationVersionDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "MajorVersion", &self.major_version, ctx);
+        write_json_field(&mut object, "MinorVersion", &self.minor_version, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ConfigurationVersionDataType {
+            major_version: read_json_field(object, "MajorVersion", decoding_limits)?,
+            minor_version: read_json_field(object, "MinorVersion", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -55,3 +56,20 @@ impl BinaryEncoder<ContentFilterElement> for ContentFilterElement {
         })
     }
 }
+
+impl JsonEncoder<ContentFilterElement> for ContentFilterElement {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "FilterOperator", &self.filter_operator, ctx);
+        write_json_field(&mut object, "FilterOperands", &self.filter_operands, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ContentFilterElement {
+            filter_operator: read_json_field(object, "FilterOperator", decoding_limits)?,
+            filter_operands: read_json_field(object, "FilterOperands", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -55,3 +56,20 @@ impl BinaryEncoder<ContentFilterResult> for ContentFilterResult {
         })
     }
 }
+
+impl JsonEncoder<ContentFilterResult> for ContentFilterResult {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ElementResults", &self.element_results, ctx);
+        write_json_field(&mut object, "ElementDiagnosticInfos", &self.element_diagnostic_infos, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ContentFilterResult {
+            element_results: read_json_field(object, "ElementResults", decoding_limits)?,
+            element_diagnostic_infos: read_json_field(object, "ElementDiagnosticInfos", decoding_limits)?,
+        })
+    }
+}
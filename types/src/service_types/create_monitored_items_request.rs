@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -66,3 +67,24 @@ impl BinaryEncoder<CreateMonitoredItemsRequest> for CreateMonitoredItemsRequest
         })
     }
 }
+
+impl JsonEncoder<CreateMonitoredItemsRequest> for CreateMonitoredItemsRequest {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, ctx);
+        write_json_field(&mut object, "SubscriptionId", &self.subscription_id, ctx);
+        write_json_field(&mut object, "TimestampsToReturn", &self.timestamps_to_return, ctx);
+        write_json_field(&mut object, "ItemsToCreate", &self.items_to_create, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(CreateMonitoredItemsRequest {
+            request_header: read_json_field(object, "RequestHeader", decoding_limits)?,
+            subscription_id: read_json_field(object, "SubscriptionId", decoding_limits)?,
+            timestamps_to_return: read_json_field(object, "TimestampsToReturn", decoding_limits)?,
+            items_to_create: read_json_field(object, "ItemsToCreate", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -92,3 +93,34 @@ impl BinaryEncoder<CreateSessionRequest> for CreateSessionRequest {
         })
     }
 }
+
+impl JsonEncoder<CreateSessionRequest> for CreateSessionRequest {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, ctx);
+        write_json_field(&mut object, "ClientDescription", &self.client_description, ctx);
+        write_json_field(&mut object, "ServerUri", &self.server_uri, ctx);
+        write_json_field(&mut object, "EndpointUrl", &self.endpoint_url, ctx);
+        write_json_field(&mut object, "SessionName", &self.session_name, ctx);
+        write_json_field(&mut object, "ClientNonce", &self.client_nonce, ctx);
+        write_json_field(&mut object, "ClientCertificate", &self.client_certificate, ctx);
+        write_json_field(&mut object, "RequestedSessionTimeout", &self.requested_session_timeout, ctx);
+        write_json_field(&mut object, "MaxResponseMessageSize", &self.max_response_message_size, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(CreateSessionRequest {
+            request_header: read_json_field(object, "RequestHeader", decoding_limits)?,
+            client_description: read_json_field(object, "ClientDescription", decoding_limits)?,
+            server_uri: read_json_field(object, "ServerUri", decoding_limits)?,
+            endpoint_url: read_json_field(object, "EndpointUrl", decoding_limits)?,
+            session_name: read_json_field(object, "SessionName", decoding_limits)?,
+            client_nonce: read_json_field(object, "ClientNonce", decoding_limits)?,
+            client_certificate: read_json_field(object, "ClientCertificate", decoding_limits)?,
+            requested_session_timeout: read_json_field(object, "RequestedSessionTimeout", decoding_limits)?,
+            max_response_message_size: read_json_field(object, "MaxResponseMessageSize", decoding_limits)?,
+        })
+    }
+}
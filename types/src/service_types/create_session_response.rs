@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -99,3 +100,36 @@ impl BinaryEncoder<CreateSessionResponse> for CreateSessionResponse {
         })
     }
 }
+
+impl JsonEncoder<CreateSessionResponse> for CreateSessionResponse {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ResponseHeader", &self.response_header, ctx);
+        write_json_field(&mut object, "SessionId", &self.session_id, ctx);
+        write_json_field(&mut object, "AuthenticationToken", &self.authentication_token, ctx);
+        write_json_field(&mut object, "RevisedSessionTimeout", &self.revised_session_timeout, ctx);
+        write_json_field(&mut object, "ServerNonce", &self.server_nonce, ctx);
+        write_json_field(&mut object, "ServerCertificate", &self.server_certificate, ctx);
+        write_json_field(&mut object, "ServerEndpoints", &self.server_endpoints, ctx);
+        write_json_field(&mut object, "ServerSoftwareCertificates", &self.server_software_certificates, ctx);
+        write_json_field(&mut object, "ServerSignature", &self.server_signature, ctx);
+        write_json_field(&mut object, "MaxRequestMessageSize", &self.max_request_message_size, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(CreateSessionResponse {
+            response_header: read_json_field(object, "ResponseHeader", decoding_limits)?,
+            session_id: read_json_field(object, "SessionId", decoding_limits)?,
+            authentication_token: read_json_field(object, "AuthenticationToken", decoding_limits)?,
+            revised_session_timeout: read_json_field(object, "RevisedSessionTimeout", decoding_limits)?,
+            server_nonce: read_json_field(object, "ServerNonce", decoding_limits)?,
+            server_certificate: read_json_field(object, "ServerCertificate", decoding_limits)?,
+            server_endpoints: read_json_field(object, "ServerEndpoints", decoding_limits)?,
+            server_software_certificates: read_json_field(object, "ServerSoftwareCertificates", decoding_limits)?,
+            server_signature: read_json_field(object, "ServerSignature", decoding_limits)?,
+            max_request_message_size: read_json_field(object, "MaxRequestMessageSize", decoding_limits)?,
+        })
+    }
+}
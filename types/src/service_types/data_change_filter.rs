@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::enums::DataChangeTrigger,
 };
@@ -51,3 +52,22 @@ impl BinaryEncoder<DataChangeFilter> for DataChangeFilter {
         })
     }
 }
+
+impl JsonEncoder<DataChangeFilter> for DataChangeFilter {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Trigger", &self.trigger, ctx);
+        write_json_field(&mut object, "DeadbandType", &self.deadband_type, ctx);
+        write_json_field(&mut object, "DeadbandValue", &self.deadband_value, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(DataChangeFilter {
+            trigger: read_json_field(object, "Trigger", decoding_limits)?,
+            deadband_type: read_json_field(object, "DeadbandType", decoding_limits)?,
+            deadband_value: read_json_field(object, "DeadbandValue", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     diagnostic_info::DiagnosticInfo,
     service_types::MonitoredItemNotification,
@@ -47,3 +48,20 @@ impl BinaryEncoder<DataChangeNotification> for DataChangeNotification {
         })
     }
 }
+
+impl JsonEncoder<DataChangeNotification> for DataChangeNotification {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "MonitoredItems", &self.monitored_items, ctx);
+        write_json_field(&mut object, "DiagnosticInfos", &self.diagnostic_infos, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(DataChangeNotification {
+            monitored_items: read_json_field(object, "MonitoredItems", decoding_limits)?,
+            diagnostic_infos: read_json_field(object, "DiagnosticInfos", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     string::UAString,
     localized_text::LocalizedText,
@@ -88,3 +89,34 @@ impl BinaryEncoder<DataSetMetaDataType> for DataSetMetaDataType {
         })
     }
 }
+
+impl JsonEncoder<DataSetMetaDataType> for DataSetMetaDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Namespaces", &self.namespaces, ctx);
+        write_json_field(&mut object, "StructureDataTypes", &self.structure_data_types, ctx);
+        write_json_field(&mut object, "EnumDataTypes", &self.enum_data_types, ctx);
+        write_json_field(&mut object, "SimpleDataTypes", &self.simple_data_types, ctx);
+        write_json_field(&mut object, "Name", &self.name, ctx);
+        write_json_field(&mut object, "Description", &self.description, ctx);
+        write_json_field(&mut object, "Fields", &self.fields, ctx);
+        write_json_field(&mut object, "DataSetClassId", &self.data_set_class_id, ctx);
+        write_json_field(&mut object, "ConfigurationVersion", &self.configuration_version, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(DataSetMetaDataType {
+            namespaces: read_json_field(object, "Namespaces", decoding_limits)?,
+            structure_data_types: read_json_field(object, "StructureDataTypes", decoding_limits)?,
+            enum_data_types: read_json_field(object, "EnumDataTypes", decoding_limits)?,
+            simple_data_types: read_json_field(object, "SimpleDataTypes", decoding_limits)?,
+            name: read_json_field(object, "Name", decoding_limits)?,
+            description: read_json_field(object, "Description", decoding_limits)?,
+            fields: read_json_field(object, "Fields", decoding_limits)?,
+            data_set_class_id: read_json_field(object, "DataSetClassId", decoding_limits)?,
+            configuration_version: read_json_field(object, "ConfigurationVersion", decoding_limits)?,
+        })
+    }
+}
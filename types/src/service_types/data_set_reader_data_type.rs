@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -136,3 +137,50 @@ impl BinaryEncoder<DataSetReaderDataType> for DataSetReaderDataType {
         })
     }
 }
+
+impl JsonEncoder<DataSetReaderDataType> for DataSetReaderDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Name", &self.name, ctx);
+        write_json_field(&mut object, "Enabled", &self.enabled, ctx);
+        write_json_field(&mut object, "PublisherId", &self.publisher_id, ctx);
+        write_json_field(&mut object, "WriterGroupId", &self.writer_group_id, ctx);
+        write_json_field(&mut object, "DataSetWriterId", &self.data_set_writer_id, ctx);
+        write_json_field(&mut object, "DataSetMetaData", &self.data_set_meta_data, ctx);
+        write_json_field(&mut object, "DataSetFieldContentMask", &self.data_set_field_content_mask, ctx);
+        write_json_field(&mut object, "MessageReceiveTimeout", &self.message_receive_timeout, ctx);
+        write_json_field(&mut object, "KeyFrameCount", &self.key_frame_count, ctx);
+        write_json_field(&mut object, "HeaderLayoutUri", &self.header_layout_uri, ctx);
+        write_json_field(&mut object, "SecurityMode", &self.security_mode, ctx);
+        write_json_field(&mut object, "SecurityGroupId", &self.security_group_id, ctx);
+        write_json_field(&mut object, "SecurityKeyServices", &self.security_key_services, ctx);
+        write_json_field(&mut object, "DataSetReaderProperties", &self.data_set_reader_properties, ctx);
+        write_json_field(&mut object, "TransportSettings", &self.transport_settings, ctx);
+        write_json_field(&mut object, "MessageSettings", &self.message_settings, ctx);
+        write_json_field(&mut object, "SubscribedDataSet", &self.subscribed_data_set, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(DataSetReaderDataType {
+            name: read_json_field(object, "Name", decoding_limits)?,
+            enabled: read_json_field(object, "Enabled", decoding_limits)?,
+            publisher_id: read_json_field(object, "PublisherId", decoding_limits)?,
+            writer_group_id: read_json_field(object, "WriterGroupId", decoding_limits)?,
+            data_set_writer_id: read_json_field(object, "DataSetWriterId", decoding_limits)?,
+            data_set_meta_data: read_json_field(object, "DataSetMetaData", decoding_limits)?,
+            data_set_field_content_mask: read_json_field(object, "DataSetFieldContentMask", decoding_limits)?,
+            message_receive_timeout: read_json_field(object, "MessageReceiveTimeout", decoding_limits)?,
+            key_frame_count: read_json_field(object, "KeyFrameCount", decoding_limits)?,
+            header_layout_uri: read_json_field(object, "HeaderLayoutUri", decoding_limits)?,
+            security_mode: read_json_field(object, "SecurityMode", decoding_limits)?,
+            security_group_id: read_json_field(object, "SecurityGroupId", decoding_limits)?,
+            security_key_services: read_json_field(object, "SecurityKeyServices", decoding_limits)?,
+            data_set_reader_properties: read_json_field(object, "DataSetReaderProperties", decoding_limits)?,
+            transport_settings: read_json_field(object, "TransportSettings", decoding_limits)?,
+            message_settings: read_json_field(object, "MessageSettings", decoding_limits)?,
+            subscribed_data_set: read_json_field(object, "SubscribedDataSet", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -41,3 +42,15 @@ impl BinaryEncoder<DataSetReaderMessageDataType> for DataSetReaderMessageDataTyp
         })
     }
 }
+
+impl JsonEncoder<DataSetReaderMessageDataType> for DataSetReaderMessageDataType {
+    fn encode_json(&self, _ctx: &JsonContext) -> JsonValue {
+        JsonValue::Object(JsonObject::new())
+    }
+
+    fn decode_json(value: &JsonValue, _decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        json_object(value)?;
+        Ok(DataSetReaderMessageDataType {
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -92,3 +93,34 @@ impl BinaryEncoder<DataSetWriterDataType> for DataSetWriterDataType {
         })
     }
 }
+
+impl JsonEncoder<DataSetWriterDataType> for DataSetWriterDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Name", &self.name, ctx);
+        write_json_field(&mut object, "Enabled", &self.enabled, ctx);
+        write_json_field(&mut object, "DataSetWriterId", &self.data_set_writer_id, ctx);
+        write_json_field(&mut object, "DataSetFieldContentMask", &self.data_set_field_content_mask, ctx);
+        write_json_field(&mut object, "KeyFrameCount", &self.key_frame_count, ctx);
+        write_json_field(&mut object, "DataSetName", &self.data_set_name, ctx);
+        write_json_field(&mut object, "DataSetWriterProperties", &self.data_set_writer_properties, ctx);
+        write_json_field(&mut object, "TransportSettings", &self.transport_settings, ctx);
+        write_json_field(&mut object, "MessageSettings", &self.message_settings, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(DataSetWriterDataType {
+            name: read_json_field(object, "Name", decoding_limits)?,
+            enabled: read_json_field(object, "Enabled", decoding_limits)?,
+            data_set_writer_id: read_json_field(object, "DataSetWriterId", decoding_limits)?,
+            data_set_field_content_mask: read_json_field(object, "DataSetFieldContentMask", decoding_limits)?,
+            key_frame_count: read_json_field(object, "KeyFrameCount", decoding_limits)?,
+            data_set_name: read_json_field(object, "DataSetName", decoding_limits)?,
+            data_set_writer_properties: read_json_field(object, "DataSetWriterProperties", decoding_limits)?,
+            transport_settings: read_json_field(object, "TransportSettings", decoding_limits)?,
+            message_settings: read_json_field(object, "MessageSettings", decoding_limits)?,
+        })
+    }
+}
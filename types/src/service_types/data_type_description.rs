@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -55,3 +56,20 @@ impl BinaryEncoder<DataTypeDescription> for DataTypeDescription {
         })
     }
 }
+
+impl JsonEncoder<DataTypeDescription> for DataTypeDescription {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "DataTypeId", &self.data_type_id, ctx);
+        write_json_field(&mut object, "Name", &self.name, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(DataTypeDescription {
+            data_type_id: read_json_field(object, "DataTypeId", decoding_limits)?,
+            name: read_json_field(object, "Name", decoding_limits)?,
+        })
+    }
+}
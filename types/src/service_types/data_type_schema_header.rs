@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -67,3 +68,24 @@ impl BinaryEncoder<DataTypeSchemaHeader> for DataTypeSchemaHeader {
         })
     }
 }
+
+impl JsonEncoder<DataTypeSchemaHeader> for DataTypeSchemaHeader {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Namespaces", &self.namespaces, ctx);
+        write_json_field(&mut object, "StructureDataTypes", &self.structure_data_types, ctx);
+        write_json_field(&mut object, "EnumDataTypes", &self.enum_data_types, ctx);
+        write_json_field(&mut object, "SimpleDataTypes", &self.simple_data_types, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(DataTypeSchemaHeader {
+            namespaces: read_json_field(object, "Namespaces", decoding_limits)?,
+            structure_data_types: read_json_field(object, "StructureDataTypes", decoding_limits)?,
+            enum_data_types: read_json_field(object, "EnumDataTypes", decoding_limits)?,
+            simple_data_types: read_json_field(object, "SimpleDataTypes", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     extension_object::ExtensionObject,
 };
@@ -41,3 +42,18 @@ impl BinaryEncoder<DatagramConnectionTransportDataType> for DatagramConnectionTr
         })
     }
 }
+
+impl JsonEncoder<DatagramConnectionTransportDataType> for DatagramConnectionTransportDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "DiscoveryAddress", &self.discovery_address, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(DatagramConnectionTransportDataType {
+            discovery_address: read_json_field(object, "DiscoveryAddress", decoding_limits)?,
+        })
+    }
+}
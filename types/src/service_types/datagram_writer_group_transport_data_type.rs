@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
 };
 
@@ -45,3 +46,20 @@ impl BinaryEncoder<DatagramWriterGroupTransportDataType> for DatagramWriterGroup
         })
     }
 }
+
+impl JsonEncoder<DatagramWriterGroupTransportDataType> for DatagramWriterGroupTransportDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "MessageRepeatCount", &self.message_repeat_count, ctx);
+        write_json_field(&mut object, "MessageRepeatDelay", &self.message_repeat_delay, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(DatagramWriterGroupTransportDataType {
+            message_repeat_count: read_json_field(object, "MessageRepeatCount", decoding_limits)?,
+            message_repeat_delay: read_json_field(object, "MessageRepeatDelay", decoding_limits)?,
+        })
+    }
+}
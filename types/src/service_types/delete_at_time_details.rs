@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     node_id::NodeId,
     date_time::DateTime,
@@ -47,3 +48,20 @@ impl BinaryEncoder<DeleteAtTimeDetails> for DeleteAtTimeDetails {
         })
     }
 }
+
+impl JsonEncoder<DeleteAtTimeDetails> for DeleteAtTimeDetails {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NodeId", &self.node_id, ctx);
+        write_json_field(&mut object, "ReqTimes", &self.req_times, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(DeleteAtTimeDetails {
+            node_id: read_json_field(object, "NodeId", decoding_limits)?,
+            req_times: read_json_field(object, "ReqTimes", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     node_id::NodeId,
     byte_string::ByteString,
@@ -47,3 +48,20 @@ impl BinaryEncoder<DeleteEventDetails> for DeleteEventDetails {
         })
     }
 }
+
+impl JsonEncoder<DeleteEventDetails> for DeleteEventDetails {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NodeId", &self.node_id, ctx);
+        write_json_field(&mut object, "EventIds", &self.event_ids, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(DeleteEventDetails {
+            node_id: read_json_field(object, "NodeId", decoding_limits)?,
+            event_ids: read_json_field(object, "EventIds", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -54,3 +55,20 @@ impl BinaryEncoder<DeleteNodesItem> for DeleteNodesItem {
         })
     }
 }
+
+impl JsonEncoder<DeleteNodesItem> for DeleteNodesItem {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NodeId", &self.node_id, ctx);
+        write_json_field(&mut object, "DeleteTargetReferences", &self.delete_target_references, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(DeleteNodesItem {
+            node_id: read_json_field(object, "NodeId", decoding_limits)?,
+            delete_target_references: read_json_field(object, "DeleteTargetReferences", decoding_limits)?,
+        })
+    }
+}
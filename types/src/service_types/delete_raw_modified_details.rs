@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     node_id::NodeId,
     date_time::DateTime,
@@ -57,3 +58,24 @@ impl BinaryEncoder<DeleteRawModifiedDetails> for DeleteRawModifiedDetails {
         })
     }
 }
+
+impl JsonEncoder<DeleteRawModifiedDetails> for DeleteRawModifiedDetails {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NodeId", &self.node_id, ctx);
+        write_json_field(&mut object, "IsDeleteModified", &self.is_delete_modified, ctx);
+        write_json_field(&mut object, "StartTime", &self.start_time, ctx);
+        write_json_field(&mut object, "EndTime", &self.end_time, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(DeleteRawModifiedDetails {
+            node_id: read_json_field(object, "NodeId", decoding_limits)?,
+            is_delete_modified: read_json_field(object, "IsDeleteModified", decoding_limits)?,
+            start_time: read_json_field(object, "StartTime", decoding_limits)?,
+            end_time: read_json_field(object, "EndTime", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -53,3 +54,20 @@ impl BinaryEncoder<DoubleComplexNumberType> for DoubleComplexNumberType {
         })
     }
 }
+
+impl JsonEncoder<DoubleComplexNumberType> for DoubleComplexNumberType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Real", &self.real, ctx);
+        write_json_field(&mut object, "Imaginary", &self.imaginary, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(DoubleComplexNumberType {
+            real: read_json_field(object, "Real", decoding_limits)?,
+            imaginary: read_json_field(object, "Imaginary", decoding_limits)?,
+        })
+    }
+}
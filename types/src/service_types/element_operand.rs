@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
 };
 
@@ -40,3 +41,18 @@ impl BinaryEncoder<ElementOperand> for ElementOperand {
         })
     }
 }
+
+impl JsonEncoder<ElementOperand> for ElementOperand {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Index", &self.index, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ElementOperand {
+            index: read_json_field(object, "Index", decoding_limits)?,
+        })
+    }
+}
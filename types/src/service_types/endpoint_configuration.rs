@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -88,3 +89,34 @@ impl BinaryEncoder<EndpointConfiguration> for EndpointConfiguration {
         })
     }
 }
+
+impl JsonEncoder<EndpointConfiguration> for EndpointConfiguration {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "OperationTimeout", &self.operation_timeout, ctx);
+        write_json_field(&mut object, "UseBinaryEncoding", &self.use_binary_encoding, ctx);
+        write_json_field(&mut object, "MaxStringLength", &self.max_string_length, ctx);
+        write_json_field(&mut object, "MaxByteStringLength", &self.max_byte_string_length, ctx);
+        write_json_field(&mut object, "MaxArrayLength", &self.max_array_length, ctx);
+        write_json_field(&mut object, "MaxMessageSize", &self.max_message_size, ctx);
+        write_json_field(&mut object, "MaxBufferSize", &self.max_buffer_size, ctx);
+        write_json_field(&mut object, "ChannelLifetime", &self.channel_lifetime, ctx);
+        write_json_field(&mut object, "SecurityTokenLifetime", &self.security_token_lifetime, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(EndpointConfiguration {
+            operation_timeout: read_json_field(object, "OperationTimeout", decoding_limits)?,
+            use_binary_encoding: read_json_field(object, "UseBinaryEncoding", decoding_limits)?,
+            max_string_length: read_json_field(object, "MaxStringLength", decoding_limits)?,
+            max_byte_string_length: read_json_field(object, "MaxByteStringLength", decoding_limits)?,
+            max_array_length: read_json_field(object, "MaxArrayLength", decoding_limits)?,
+            max_message_size: read_json_field(object, "MaxMessageSize", decoding_limits)?,
+            max_buffer_size: read_json_field(object, "MaxBufferSize", decoding_limits)?,
+            channel_lifetime: read_json_field(object, "ChannelLifetime", decoding_limits)?,
+            security_token_lifetime: read_json_field(object, "SecurityTokenLifetime", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -88,3 +89,32 @@ impl BinaryEncoder<EndpointDescription> for EndpointDescription {
         })
     }
 }
+
+impl JsonEncoder<EndpointDescription> for EndpointDescription {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "EndpointUrl", &self.endpoint_url, ctx);
+        write_json_field(&mut object, "Server", &self.server, ctx);
+        write_json_field(&mut object, "ServerCertificate", &self.server_certificate, ctx);
+        write_json_field(&mut object, "SecurityMode", &self.security_mode, ctx);
+        write_json_field(&mut object, "SecurityPolicyUri", &self.security_policy_uri, ctx);
+        write_json_field(&mut object, "UserIdentityTokens", &self.user_identity_tokens, ctx);
+        write_json_field(&mut object, "TransportProfileUri", &self.transport_profile_uri, ctx);
+        write_json_field(&mut object, "SecurityLevel", &self.security_level, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(EndpointDescription {
+            endpoint_url: read_json_field(object, "EndpointUrl", decoding_limits)?,
+            server: read_json_field(object, "Server", decoding_limits)?,
+            server_certificate: read_json_field(object, "ServerCertificate", decoding_limits)?,
+            security_mode: read_json_field(object, "SecurityMode", decoding_limits)?,
+            security_policy_uri: read_json_field(object, "SecurityPolicyUri", decoding_limits)?,
+            user_identity_tokens: read_json_field(object, "UserIdentityTokens", decoding_limits)?,
+            transport_profile_uri: read_json_field(object, "TransportProfileUri", decoding_limits)?,
+            security_level: read_json_field(object, "SecurityLevel", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -65,3 +66,24 @@ impl BinaryEncoder<EndpointType> for EndpointType {
         })
     }
 }
+
+impl JsonEncoder<EndpointType> for EndpointType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "EndpointUrl", &self.endpoint_url, ctx);
+        write_json_field(&mut object, "SecurityMode", &self.security_mode, ctx);
+        write_json_field(&mut object, "SecurityPolicyUri", &self.security_policy_uri, ctx);
+        write_json_field(&mut object, "TransportProfileUri", &self.transport_profile_uri, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(EndpointType {
+            endpoint_url: read_json_field(object, "EndpointUrl", decoding_limits)?,
+            security_mode: read_json_field(object, "SecurityMode", decoding_limits)?,
+            security_policy_uri: read_json_field(object, "SecurityPolicyUri", decoding_limits)?,
+            transport_profile_uri: read_json_field(object, "TransportProfileUri", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::EnumField,
 };
@@ -41,3 +42,18 @@ impl BinaryEncoder<EnumDefinition> for EnumDefinition {
         })
     }
 }
+
+impl JsonEncoder<EnumDefinition> for EnumDefinition {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Fields", &self.fields, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(EnumDefinition {
+            fields: read_json_field(object, "Fields", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     node_id::NodeId,
     qualified_name::QualifiedName,
@@ -58,3 +59,24 @@ impl BinaryEncoder<EnumDescription> for EnumDescription {
         })
     }
 }
+
+impl JsonEncoder<EnumDescription> for EnumDescription {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "DataTypeId", &self.data_type_id, ctx);
+        write_json_field(&mut object, "Name", &self.name, ctx);
+        write_json_field(&mut object, "EnumDefinition", &self.enum_definition, ctx);
+        write_json_field(&mut object, "BuiltInType", &self.built_in_type, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(EnumDescription {
+            data_type_id: read_json_field(object, "DataTypeId", decoding_limits)?,
+            name: read_json_field(object, "Name", decoding_limits)?,
+            enum_definition: read_json_field(object, "EnumDefinition", decoding_limits)?,
+            built_in_type: read_json_field(object, "BuiltInType", decoding_limits)?,
+        })
+    }
+}
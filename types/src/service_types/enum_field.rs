@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     localized_text::LocalizedText,
     string::UAString,
@@ -57,3 +58,24 @@ impl BinaryEncoder<EnumField> for EnumField {
         })
     }
 }
+
+impl JsonEncoder<EnumField> for EnumField {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Value", &self.value, ctx);
+        write_json_field(&mut object, "DisplayName", &self.display_name, ctx);
+        write_json_field(&mut object, "Description", &self.description, ctx);
+        write_json_field(&mut object, "Name", &self.name, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(EnumField {
+            value: read_json_field(object, "Value", decoding_limits)?,
+            display_name: read_json_field(object, "DisplayName", decoding_limits)?,
+            description: read_json_field(object, "Description", decoding_limits)?,
+            name: read_json_field(object, "Name", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -59,3 +60,22 @@ impl BinaryEncoder<EnumValueType> for EnumValueType {
         })
     }
 }
+
+impl JsonEncoder<EnumValueType> for EnumValueType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Value", &self.value, ctx);
+        write_json_field(&mut object, "DisplayName", &self.display_name, ctx);
+        write_json_field(&mut object, "Description", &self.description, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(EnumValueType {
+            value: read_json_field(object, "Value", decoding_limits)?,
+            display_name: read_json_field(object, "DisplayName", decoding_limits)?,
+            description: read_json_field(object, "Description", decoding_limits)?,
+        })
+    }
+}
@@ -7,6 +7,7 @@
 use std::io::{Read, Write};
 
 use crate::encoding::*;
+use crate::json::*;
 use crate::status_codes::StatusCode;
 
 // All enums assumed to be i32 length in bits when encoded.
@@ -48,6 +49,32 @@ impl BinaryEncoder<NodeIdType> for NodeIdType {
     }
 }
 
+impl JsonEncoder<NodeIdType> for NodeIdType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::TwoByte),
+            1 => Ok(Self::FourByte),
+            2 => Ok(Self::Numeric),
+            3 => Ok(Self::String),
+            4 => Ok(Self::Guid),
+            5 => Ok(Self::ByteString),
+            v => {
+                error!("Invalid value {} for enum NodeIdType", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum NamingRuleType {
@@ -79,6 +106,29 @@ impl BinaryEncoder<NamingRuleType> for NamingRuleType {
     }
 }
 
+impl JsonEncoder<NamingRuleType> for NamingRuleType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            1 => Ok(Self::Mandatory),
+            2 => Ok(Self::Optional),
+            3 => Ok(Self::Constraint),
+            v => {
+                error!("Invalid value {} for enum NamingRuleType", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum OpenFileMode {
@@ -112,6 +162,30 @@ impl BinaryEncoder<OpenFileMode> for OpenFileMode {
     }
 }
 
+impl JsonEncoder<OpenFileMode> for OpenFileMode {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            1 => Ok(Self::Read),
+            2 => Ok(Self::Write),
+            4 => Ok(Self::EraseExisting),
+            8 => Ok(Self::Append),
+            v => {
+                error!("Invalid value {} for enum OpenFileMode", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum IdentityCriteriaType {
@@ -149,6 +223,32 @@ impl BinaryEncoder<IdentityCriteriaType> for IdentityCriteriaType {
     }
 }
 
+impl JsonEncoder<IdentityCriteriaType> for IdentityCriteriaType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            1 => Ok(Self::UserName),
+            2 => Ok(Self::Thumbprint),
+            3 => Ok(Self::Role),
+            4 => Ok(Self::GroupId),
+            5 => Ok(Self::Anonymous),
+            6 => Ok(Self::AuthenticatedUser),
+            v => {
+                error!("Invalid value {} for enum IdentityCriteriaType", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum TrustListMasks {
@@ -186,6 +286,32 @@ impl BinaryEncoder<TrustListMasks> for TrustListMasks {
     }
 }
 
+impl JsonEncoder<TrustListMasks> for TrustListMasks {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::TrustedCertificates),
+            2 => Ok(Self::TrustedCrls),
+            4 => Ok(Self::IssuerCertificates),
+            8 => Ok(Self::IssuerCrls),
+            15 => Ok(Self::All),
+            v => {
+                error!("Invalid value {} for enum TrustListMasks", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum PubSubState {
@@ -219,6 +345,30 @@ impl BinaryEncoder<PubSubState> for PubSubState {
     }
 }
 
+impl JsonEncoder<PubSubState> for PubSubState {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::Disabled),
+            1 => Ok(Self::Paused),
+            2 => Ok(Self::Operational),
+            3 => Ok(Self::Error),
+            v => {
+                error!("Invalid value {} for enum PubSubState", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum DataSetFieldFlags {
@@ -248,6 +398,28 @@ impl BinaryEncoder<DataSetFieldFlags> for DataSetFieldFlags {
     }
 }
 
+impl JsonEncoder<DataSetFieldFlags> for DataSetFieldFlags {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::PromotedField),
+            v => {
+                error!("Invalid value {} for enum DataSetFieldFlags", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum DataSetFieldContentMask {
@@ -287,6 +459,33 @@ impl BinaryEncoder<DataSetFieldContentMask> for DataSetFieldContentMask {
     }
 }
 
+impl JsonEncoder<DataSetFieldContentMask> for DataSetFieldContentMask {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::StatusCode),
+            2 => Ok(Self::SourceTimestamp),
+            4 => Ok(Self::ServerTimestamp),
+            8 => Ok(Self::SourcePicoSeconds),
+            16 => Ok(Self::ServerPicoSeconds),
+            32 => Ok(Self::RawData),
+            v => {
+                error!("Invalid value {} for enum DataSetFieldContentMask", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum OverrideValueHandling {
@@ -318,6 +517,29 @@ impl BinaryEncoder<OverrideValueHandling> for OverrideValueHandling {
     }
 }
 
+impl JsonEncoder<OverrideValueHandling> for OverrideValueHandling {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::Disabled),
+            1 => Ok(Self::LastUsableValue),
+            2 => Ok(Self::OverrideValue),
+            v => {
+                error!("Invalid value {} for enum OverrideValueHandling", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum DataSetOrderingType {
@@ -349,6 +571,29 @@ impl BinaryEncoder<DataSetOrderingType> for DataSetOrderingType {
     }
 }
 
+impl JsonEncoder<DataSetOrderingType> for DataSetOrderingType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::Undefined),
+            1 => Ok(Self::AscendingWriterId),
+            2 => Ok(Self::AscendingWriterIdSingle),
+            v => {
+                error!("Invalid value {} for enum DataSetOrderingType", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum UadpNetworkMessageContentMask {
@@ -398,6 +643,38 @@ impl BinaryEncoder<UadpNetworkMessageContentMask> for UadpNetworkMessageContentM
     }
 }
 
+impl JsonEncoder<UadpNetworkMessageContentMask> for UadpNetworkMessageContentMask {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::PublisherId),
+            2 => Ok(Self::GroupHeader),
+            4 => Ok(Self::WriterGroupId),
+            8 => Ok(Self::GroupVersion),
+            16 => Ok(Self::NetworkMessageNumber),
+            32 => Ok(Self::SequenceNumber),
+            64 => Ok(Self::PayloadHeader),
+            128 => Ok(Self::Timestamp),
+            256 => Ok(Self::PicoSeconds),
+            512 => Ok(Self::DataSetClassId),
+            1024 => Ok(Self::PromotedFields),
+            v => {
+                error!("Invalid value {} for enum UadpNetworkMessageContentMask", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum UadpDataSetMessageContentMask {
@@ -437,6 +714,33 @@ impl BinaryEncoder<UadpDataSetMessageContentMask> for UadpDataSetMessageContentM
     }
 }
 
+impl JsonEncoder<UadpDataSetMessageContentMask> for UadpDataSetMessageContentMask {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::Timestamp),
+            2 => Ok(Self::PicoSeconds),
+            4 => Ok(Self::Status),
+            8 => Ok(Self::MajorVersion),
+            16 => Ok(Self::MinorVersion),
+            32 => Ok(Self::SequenceNumber),
+            v => {
+                error!("Invalid value {} for enum UadpDataSetMessageContentMask", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum JsonNetworkMessageContentMask {
@@ -476,6 +780,33 @@ impl BinaryEncoder<JsonNetworkMessageContentMask> for JsonNetworkMessageContentM
     }
 }
 
+impl JsonEncoder<JsonNetworkMessageContentMask> for JsonNetworkMessageContentMask {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::NetworkMessageHeader),
+            2 => Ok(Self::DataSetMessageHeader),
+            4 => Ok(Self::SingleDataSetMessage),
+            8 => Ok(Self::PublisherId),
+            16 => Ok(Self::DataSetClassId),
+            32 => Ok(Self::ReplyTo),
+            v => {
+                error!("Invalid value {} for enum JsonNetworkMessageContentMask", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum JsonDataSetMessageContentMask {
@@ -513,6 +844,32 @@ impl BinaryEncoder<JsonDataSetMessageContentMask> for JsonDataSetMessageContentM
     }
 }
 
+impl JsonEncoder<JsonDataSetMessageContentMask> for JsonDataSetMessageContentMask {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::DataSetWriterId),
+            2 => Ok(Self::MetaDataVersion),
+            4 => Ok(Self::SequenceNumber),
+            8 => Ok(Self::Timestamp),
+            16 => Ok(Self::Status),
+            v => {
+                error!("Invalid value {} for enum JsonDataSetMessageContentMask", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum BrokerTransportQualityOfService {
@@ -548,6 +905,31 @@ impl BinaryEncoder<BrokerTransportQualityOfService> for BrokerTransportQualityOf
     }
 }
 
+impl JsonEncoder<BrokerTransportQualityOfService> for BrokerTransportQualityOfService {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::NotSpecified),
+            1 => Ok(Self::BestEffort),
+            2 => Ok(Self::AtLeastOnce),
+            3 => Ok(Self::AtMostOnce),
+            4 => Ok(Self::ExactlyOnce),
+            v => {
+                error!("Invalid value {} for enum BrokerTransportQualityOfService", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum DiagnosticsLevel {
@@ -583,15 +965,40 @@ impl BinaryEncoder<DiagnosticsLevel> for DiagnosticsLevel {
     }
 }
 
+impl JsonEncoder<DiagnosticsLevel> for DiagnosticsLevel {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
-pub enum PubSubDiagnosticsCounterClassification {
-    Information = 0,
-    Error = 1,
-}
-
-impl BinaryEncoder<PubSubDiagnosticsCounterClassification> for PubSubDiagnosticsCounterClassification {
-    fn byte_len(&self) -> usize {
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::Basic),
+            1 => Ok(Self::Advanced),
+            2 => Ok(Self::Info),
+            3 => Ok(Self::Log),
+            4 => Ok(Self::Debug),
+            v => {
+                error!("Invalid value {} for enum DiagnosticsLevel", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PubSubDiagnosticsCounterClassification {
+    Information = 0,
+    Error = 1,
+}
+
+impl BinaryEncoder<PubSubDiagnosticsCounterClassification> for PubSubDiagnosticsCounterClassification {
+    fn byte_len(&self) -> usize {
         4
     }
 
@@ -612,6 +1019,28 @@ impl BinaryEncoder<PubSubDiagnosticsCounterClassification> for PubSubDiagnostics
     }
 }
 
+impl JsonEncoder<PubSubDiagnosticsCounterClassification> for PubSubDiagnosticsCounterClassification {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::Information),
+            1 => Ok(Self::Error),
+            v => {
+                error!("Invalid value {} for enum PubSubDiagnosticsCounterClassification", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum IdType {
@@ -645,6 +1074,30 @@ impl BinaryEncoder<IdType> for IdType {
     }
 }
 
+impl JsonEncoder<IdType> for IdType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::Numeric),
+            1 => Ok(Self::String),
+            2 => Ok(Self::Guid),
+            3 => Ok(Self::Opaque),
+            v => {
+                error!("Invalid value {} for enum IdType", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum NodeClass {
@@ -688,6 +1141,35 @@ impl BinaryEncoder<NodeClass> for NodeClass {
     }
 }
 
+impl JsonEncoder<NodeClass> for NodeClass {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::Unspecified),
+            1 => Ok(Self::Object),
+            2 => Ok(Self::Variable),
+            4 => Ok(Self::Method),
+            8 => Ok(Self::ObjectType),
+            16 => Ok(Self::VariableType),
+            32 => Ok(Self::ReferenceType),
+            64 => Ok(Self::DataType),
+            128 => Ok(Self::View),
+            v => {
+                error!("Invalid value {} for enum NodeClass", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum PermissionType {
@@ -749,6 +1231,44 @@ impl BinaryEncoder<PermissionType> for PermissionType {
     }
 }
 
+impl JsonEncoder<PermissionType> for PermissionType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::Browse),
+            2 => Ok(Self::ReadRolePermissions),
+            4 => Ok(Self::WriteAttribute),
+            8 => Ok(Self::WriteRolePermissions),
+            16 => Ok(Self::WriteHistorizing),
+            32 => Ok(Self::Read),
+            64 => Ok(Self::Write),
+            128 => Ok(Self::ReadHistory),
+            256 => Ok(Self::InsertHistory),
+            512 => Ok(Self::ModifyHistory),
+            1024 => Ok(Self::DeleteHistory),
+            2048 => Ok(Self::ReceiveEvents),
+            4096 => Ok(Self::Call),
+            8192 => Ok(Self::AddReference),
+            16384 => Ok(Self::RemoveReference),
+            32768 => Ok(Self::DeleteNode),
+            65536 => Ok(Self::AddNode),
+            v => {
+                error!("Invalid value {} for enum PermissionType", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum AccessLevelType {
@@ -790,6 +1310,34 @@ impl BinaryEncoder<AccessLevelType> for AccessLevelType {
     }
 }
 
+impl JsonEncoder<AccessLevelType> for AccessLevelType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::CurrentRead),
+            2 => Ok(Self::CurrentWrite),
+            4 => Ok(Self::HistoryRead),
+            8 => Ok(Self::HistoryWrite),
+            16 => Ok(Self::SemanticChange),
+            32 => Ok(Self::StatusWrite),
+            64 => Ok(Self::TimestampWrite),
+            v => {
+                error!("Invalid value {} for enum AccessLevelType", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum AccessLevelExType {
@@ -837,6 +1385,37 @@ impl BinaryEncoder<AccessLevelExType> for AccessLevelExType {
     }
 }
 
+impl JsonEncoder<AccessLevelExType> for AccessLevelExType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::CurrentRead),
+            2 => Ok(Self::CurrentWrite),
+            4 => Ok(Self::HistoryRead),
+            8 => Ok(Self::HistoryWrite),
+            16 => Ok(Self::SemanticChange),
+            32 => Ok(Self::StatusWrite),
+            64 => Ok(Self::TimestampWrite),
+            256 => Ok(Self::NonatomicRead),
+            512 => Ok(Self::NonatomicWrite),
+            1024 => Ok(Self::WriteFullArrayOnly),
+            v => {
+                error!("Invalid value {} for enum AccessLevelExType", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum EventNotifierType {
@@ -870,6 +1449,30 @@ impl BinaryEncoder<EventNotifierType> for EventNotifierType {
     }
 }
 
+impl JsonEncoder<EventNotifierType> for EventNotifierType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::SubscribeToEvents),
+            4 => Ok(Self::HistoryRead),
+            8 => Ok(Self::HistoryWrite),
+            v => {
+                error!("Invalid value {} for enum EventNotifierType", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum AccessRestrictionType {
@@ -903,6 +1506,30 @@ impl BinaryEncoder<AccessRestrictionType> for AccessRestrictionType {
     }
 }
 
+impl JsonEncoder<AccessRestrictionType> for AccessRestrictionType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::SigningRequired),
+            2 => Ok(Self::EncryptionRequired),
+            4 => Ok(Self::SessionRequired),
+            v => {
+                error!("Invalid value {} for enum AccessRestrictionType", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum StructureType {
@@ -934,6 +1561,29 @@ impl BinaryEncoder<StructureType> for StructureType {
     }
 }
 
+impl JsonEncoder<StructureType> for StructureType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::Structure),
+            1 => Ok(Self::StructureWithOptionalFields),
+            2 => Ok(Self::Union),
+            v => {
+                error!("Invalid value {} for enum StructureType", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum ApplicationType {
@@ -967,6 +1617,30 @@ impl BinaryEncoder<ApplicationType> for ApplicationType {
     }
 }
 
+impl JsonEncoder<ApplicationType> for ApplicationType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::Server),
+            1 => Ok(Self::Client),
+            2 => Ok(Self::ClientAndServer),
+            3 => Ok(Self::DiscoveryServer),
+            v => {
+                error!("Invalid value {} for enum ApplicationType", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum MessageSecurityMode {
@@ -1000,6 +1674,30 @@ impl BinaryEncoder<MessageSecurityMode> for MessageSecurityMode {
     }
 }
 
+impl JsonEncoder<MessageSecurityMode> for MessageSecurityMode {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::Invalid),
+            1 => Ok(Self::None),
+            2 => Ok(Self::Sign),
+            3 => Ok(Self::SignAndEncrypt),
+            v => {
+                error!("Invalid value {} for enum MessageSecurityMode", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum UserTokenType {
@@ -1033,6 +1731,30 @@ impl BinaryEncoder<UserTokenType> for UserTokenType {
     }
 }
 
+impl JsonEncoder<UserTokenType> for UserTokenType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::Anonymous),
+            1 => Ok(Self::UserName),
+            2 => Ok(Self::Certificate),
+            3 => Ok(Self::IssuedToken),
+            v => {
+                error!("Invalid value {} for enum UserTokenType", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum SecurityTokenRequestType {
@@ -1062,6 +1784,28 @@ impl BinaryEncoder<SecurityTokenRequestType> for SecurityTokenRequestType {
     }
 }
 
+impl JsonEncoder<SecurityTokenRequestType> for SecurityTokenRequestType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::Issue),
+            1 => Ok(Self::Renew),
+            v => {
+                error!("Invalid value {} for enum SecurityTokenRequestType", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum NodeAttributesMask {
@@ -1157,6 +1901,61 @@ impl BinaryEncoder<NodeAttributesMask> for NodeAttributesMask {
     }
 }
 
+impl JsonEncoder<NodeAttributesMask> for NodeAttributesMask {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::AccessLevel),
+            2 => Ok(Self::ArrayDimensions),
+            4 => Ok(Self::BrowseName),
+            8 => Ok(Self::ContainsNoLoops),
+            16 => Ok(Self::DataType),
+            32 => Ok(Self::Description),
+            64 => Ok(Self::DisplayName),
+            128 => Ok(Self::EventNotifier),
+            256 => Ok(Self::Executable),
+            512 => Ok(Self::Historizing),
+            1024 => Ok(Self::InverseName),
+            2048 => Ok(Self::IsAbstract),
+            4096 => Ok(Self::MinimumSamplingInterval),
+            8192 => Ok(Self::NodeClass),
+            16384 => Ok(Self::NodeId),
+            32768 => Ok(Self::Symmetric),
+            65536 => Ok(Self::UserAccessLevel),
+            131072 => Ok(Self::UserExecutable),
+            262144 => Ok(Self::UserWriteMask),
+            524288 => Ok(Self::ValueRank),
+            1048576 => Ok(Self::WriteMask),
+            2097152 => Ok(Self::Value),
+            4194304 => Ok(Self::DataTypeDefinition),
+            8388608 => Ok(Self::RolePermissions),
+            16777216 => Ok(Self::AccessRestrictions),
+            33554431 => Ok(Self::All),
+            26501220 => Ok(Self::BaseNode),
+            26501348 => Ok(Self::Object),
+            26503268 => Ok(Self::ObjectType),
+            26571383 => Ok(Self::Variable),
+            28600438 => Ok(Self::VariableType),
+            26632548 => Ok(Self::Method),
+            26537060 => Ok(Self::ReferenceType),
+            26501356 => Ok(Self::View),
+            v => {
+                error!("Invalid value {} for enum NodeAttributesMask", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum AttributeWriteMask {
@@ -1194,12 +1993,59 @@ impl BinaryEncoder<AttributeWriteMask> for AttributeWriteMask {
         4
     }
 
-    fn encode<S: Write>(&self, stream: &mut S) -> EncodingResult<usize> {
-        write_i32(stream, *self as i32)
+    fn encode<S: Write>(&self, stream: &mut S) -> EncodingResult<usize> {
+        write_i32(stream, *self as i32)
+    }
+
+    fn decode<S: Read>(stream: &mut S, _: &DecodingLimits) -> EncodingResult<Self> {
+        let value = read_i32(stream)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::AccessLevel),
+            2 => Ok(Self::ArrayDimensions),
+            4 => Ok(Self::BrowseName),
+            8 => Ok(Self::ContainsNoLoops),
+            16 => Ok(Self::DataType),
+            32 => Ok(Self::Description),
+            64 => Ok(Self::DisplayName),
+            128 => Ok(Self::EventNotifier),
+            256 => Ok(Self::Executable),
+            512 => Ok(Self::Historizing),
+            1024 => Ok(Self::InverseName),
+            2048 => Ok(Self::IsAbstract),
+            4096 => Ok(Self::MinimumSamplingInterval),
+            8192 => Ok(Self::NodeClass),
+            16384 => Ok(Self::NodeId),
+            32768 => Ok(Self::Symmetric),
+            65536 => Ok(Self::UserAccessLevel),
+            131072 => Ok(Self::UserExecutable),
+            262144 => Ok(Self::UserWriteMask),
+            524288 => Ok(Self::ValueRank),
+            1048576 => Ok(Self::WriteMask),
+            2097152 => Ok(Self::ValueForVariableType),
+            4194304 => Ok(Self::DataTypeDefinition),
+            8388608 => Ok(Self::RolePermissions),
+            16777216 => Ok(Self::AccessRestrictions),
+            33554432 => Ok(Self::AccessLevelEx),
+            v => {
+                error!("Invalid value {} for enum AttributeWriteMask", v);
+                Err(StatusCode::BadUnexpectedError)
+            }
+        }
+    }
+}
+
+impl JsonEncoder<AttributeWriteMask> for AttributeWriteMask {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
     }
 
-    fn decode<S: Read>(stream: &mut S, _: &DecodingLimits) -> EncodingResult<Self> {
-        let value = read_i32(stream)?;
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
         match value {
             0 => Ok(Self::None),
             1 => Ok(Self::AccessLevel),
@@ -1230,7 +2076,7 @@ impl BinaryEncoder<AttributeWriteMask> for AttributeWriteMask {
             33554432 => Ok(Self::AccessLevelEx),
             v => {
                 error!("Invalid value {} for enum AttributeWriteMask", v);
-                Err(StatusCode::BadUnexpectedError)
+                Err(StatusCode::BadDecodingError)
             }
         }
     }
@@ -1269,6 +2115,30 @@ impl BinaryEncoder<BrowseDirection> for BrowseDirection {
     }
 }
 
+impl JsonEncoder<BrowseDirection> for BrowseDirection {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::Forward),
+            1 => Ok(Self::Inverse),
+            2 => Ok(Self::Both),
+            3 => Ok(Self::Invalid),
+            v => {
+                error!("Invalid value {} for enum BrowseDirection", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum BrowseResultMask {
@@ -1314,6 +2184,36 @@ impl BinaryEncoder<BrowseResultMask> for BrowseResultMask {
     }
 }
 
+impl JsonEncoder<BrowseResultMask> for BrowseResultMask {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::ReferenceTypeId),
+            2 => Ok(Self::IsForward),
+            4 => Ok(Self::NodeClass),
+            8 => Ok(Self::BrowseName),
+            16 => Ok(Self::DisplayName),
+            32 => Ok(Self::TypeDefinition),
+            63 => Ok(Self::All),
+            3 => Ok(Self::ReferenceTypeInfo),
+            60 => Ok(Self::TargetInfo),
+            v => {
+                error!("Invalid value {} for enum BrowseResultMask", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq, Serialize)]
 pub enum FilterOperator {
@@ -1375,6 +2275,44 @@ impl BinaryEncoder<FilterOperator> for FilterOperator {
     }
 }
 
+impl JsonEncoder<FilterOperator> for FilterOperator {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::Equals),
+            1 => Ok(Self::IsNull),
+            2 => Ok(Self::GreaterThan),
+            3 => Ok(Self::LessThan),
+            4 => Ok(Self::GreaterThanOrEqual),
+            5 => Ok(Self::LessThanOrEqual),
+            6 => Ok(Self::Like),
+            7 => Ok(Self::Not),
+            8 => Ok(Self::Between),
+            9 => Ok(Self::InList),
+            10 => Ok(Self::And),
+            11 => Ok(Self::Or),
+            12 => Ok(Self::Cast),
+            13 => Ok(Self::InView),
+            14 => Ok(Self::OfType),
+            15 => Ok(Self::RelatedTo),
+            16 => Ok(Self::BitwiseAnd),
+            17 => Ok(Self::BitwiseOr),
+            v => {
+                error!("Invalid value {} for enum FilterOperator", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq, Serialize)]
 pub enum TimestampsToReturn {
@@ -1409,6 +2347,31 @@ impl BinaryEncoder<TimestampsToReturn> for TimestampsToReturn {
     }
 }
 
+impl JsonEncoder<TimestampsToReturn> for TimestampsToReturn {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::Source),
+            1 => Ok(Self::Server),
+            2 => Ok(Self::Both),
+            3 => Ok(Self::Neither),
+            4 => Ok(Self::Invalid),
+            v => {
+                error!("Invalid value {} for enum TimestampsToReturn", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum HistoryUpdateType {
@@ -1442,6 +2405,30 @@ impl BinaryEncoder<HistoryUpdateType> for HistoryUpdateType {
     }
 }
 
+impl JsonEncoder<HistoryUpdateType> for HistoryUpdateType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            1 => Ok(Self::Insert),
+            2 => Ok(Self::Replace),
+            3 => Ok(Self::Update),
+            4 => Ok(Self::Delete),
+            v => {
+                error!("Invalid value {} for enum HistoryUpdateType", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum PerformUpdateType {
@@ -1475,6 +2462,30 @@ impl BinaryEncoder<PerformUpdateType> for PerformUpdateType {
     }
 }
 
+impl JsonEncoder<PerformUpdateType> for PerformUpdateType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            1 => Ok(Self::Insert),
+            2 => Ok(Self::Replace),
+            3 => Ok(Self::Update),
+            4 => Ok(Self::Remove),
+            v => {
+                error!("Invalid value {} for enum PerformUpdateType", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq, Serialize)]
 pub enum MonitoringMode {
@@ -1506,6 +2517,29 @@ impl BinaryEncoder<MonitoringMode> for MonitoringMode {
     }
 }
 
+impl JsonEncoder<MonitoringMode> for MonitoringMode {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::Disabled),
+            1 => Ok(Self::Sampling),
+            2 => Ok(Self::Reporting),
+            v => {
+                error!("Invalid value {} for enum MonitoringMode", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq, Serialize)]
 pub enum DataChangeTrigger {
@@ -1537,6 +2571,29 @@ impl BinaryEncoder<DataChangeTrigger> for DataChangeTrigger {
     }
 }
 
+impl JsonEncoder<DataChangeTrigger> for DataChangeTrigger {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::Status),
+            1 => Ok(Self::StatusValue),
+            2 => Ok(Self::StatusValueTimestamp),
+            v => {
+                error!("Invalid value {} for enum DataChangeTrigger", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum DeadbandType {
@@ -1568,6 +2625,29 @@ impl BinaryEncoder<DeadbandType> for DeadbandType {
     }
 }
 
+impl JsonEncoder<DeadbandType> for DeadbandType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::Absolute),
+            2 => Ok(Self::Percent),
+            v => {
+                error!("Invalid value {} for enum DeadbandType", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum RedundancySupport {
@@ -1605,6 +2685,32 @@ impl BinaryEncoder<RedundancySupport> for RedundancySupport {
     }
 }
 
+impl JsonEncoder<RedundancySupport> for RedundancySupport {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::None),
+            1 => Ok(Self::Cold),
+            2 => Ok(Self::Warm),
+            3 => Ok(Self::Hot),
+            4 => Ok(Self::Transparent),
+            5 => Ok(Self::HotAndMirrored),
+            v => {
+                error!("Invalid value {} for enum RedundancySupport", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum ServerState {
@@ -1646,6 +2752,34 @@ impl BinaryEncoder<ServerState> for ServerState {
     }
 }
 
+impl JsonEncoder<ServerState> for ServerState {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::Running),
+            1 => Ok(Self::Failed),
+            2 => Ok(Self::NoConfiguration),
+            3 => Ok(Self::Suspended),
+            4 => Ok(Self::Shutdown),
+            5 => Ok(Self::Test),
+            6 => Ok(Self::CommunicationFault),
+            7 => Ok(Self::Unknown),
+            v => {
+                error!("Invalid value {} for enum ServerState", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum ModelChangeStructureVerbMask {
@@ -1681,6 +2815,31 @@ impl BinaryEncoder<ModelChangeStructureVerbMask> for ModelChangeStructureVerbMas
     }
 }
 
+impl JsonEncoder<ModelChangeStructureVerbMask> for ModelChangeStructureVerbMask {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            1 => Ok(Self::NodeAdded),
+            2 => Ok(Self::NodeDeleted),
+            4 => Ok(Self::ReferenceAdded),
+            8 => Ok(Self::ReferenceDeleted),
+            16 => Ok(Self::DataTypeChanged),
+            v => {
+                error!("Invalid value {} for enum ModelChangeStructureVerbMask", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum AxisScaleEnumeration {
@@ -1712,6 +2871,29 @@ impl BinaryEncoder<AxisScaleEnumeration> for AxisScaleEnumeration {
     }
 }
 
+impl JsonEncoder<AxisScaleEnumeration> for AxisScaleEnumeration {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::Linear),
+            1 => Ok(Self::Log),
+            2 => Ok(Self::Ln),
+            v => {
+                error!("Invalid value {} for enum AxisScaleEnumeration", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
+
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum ExceptionDeviationFormat {
@@ -1746,3 +2928,28 @@ impl BinaryEncoder<ExceptionDeviationFormat> for ExceptionDeviationFormat {
         }
     }
 }
+
+impl JsonEncoder<ExceptionDeviationFormat> for ExceptionDeviationFormat {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(*self as i32)
+        } else {
+            JsonValue::from(format!("{:?}_{}", self, *self as i32))
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let value = i32::decode_json(value, decoding_limits)?;
+        match value {
+            0 => Ok(Self::AbsoluteValue),
+            1 => Ok(Self::PercentOfValue),
+            2 => Ok(Self::PercentOfRange),
+            3 => Ok(Self::PercentOfEURange),
+            4 => Ok(Self::Unknown),
+            v => {
+                error!("Invalid value {} for enum ExceptionDeviationFormat", v);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+}
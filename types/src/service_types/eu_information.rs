@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -65,3 +66,24 @@ impl BinaryEncoder<EUInformation> for EUInformation {
         })
     }
 }
+
+impl JsonEncoder<EUInformation> for EUInformation {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NamespaceUri", &self.namespace_uri, ctx);
+        write_json_field(&mut object, "UnitId", &self.unit_id, ctx);
+        write_json_field(&mut object, "DisplayName", &self.display_name, ctx);
+        write_json_field(&mut object, "Description", &self.description, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(EUInformation {
+            namespace_uri: read_json_field(object, "NamespaceUri", decoding_limits)?,
+            unit_id: read_json_field(object, "UnitId", decoding_limits)?,
+            display_name: read_json_field(object, "DisplayName", decoding_limits)?,
+            description: read_json_field(object, "Description", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -54,3 +55,20 @@ impl BinaryEncoder<EventFieldList> for EventFieldList {
         })
     }
 }
+
+impl JsonEncoder<EventFieldList> for EventFieldList {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ClientHandle", &self.client_handle, ctx);
+        write_json_field(&mut object, "EventFields", &self.event_fields, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(EventFieldList {
+            client_handle: read_json_field(object, "ClientHandle", decoding_limits)?,
+            event_fields: read_json_field(object, "EventFields", decoding_limits)?,
+        })
+    }
+}
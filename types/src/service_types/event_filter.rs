@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::SimpleAttributeOperand,
     service_types::ContentFilter,
@@ -47,3 +48,20 @@ impl BinaryEncoder<EventFilter> for EventFilter {
         })
     }
 }
+
+impl JsonEncoder<EventFilter> for EventFilter {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "SelectClauses", &self.select_clauses, ctx);
+        write_json_field(&mut object, "WhereClause", &self.where_clause, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(EventFilter {
+            select_clauses: read_json_field(object, "SelectClauses", decoding_limits)?,
+            where_clause: read_json_field(object, "WhereClause", decoding_limits)?,
+        })
+    }
+}
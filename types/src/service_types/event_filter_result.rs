@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     status_codes::StatusCode,
     diagnostic_info::DiagnosticInfo,
@@ -53,3 +54,22 @@ impl BinaryEncoder<EventFilterResult> for EventFilterResult {
         })
     }
 }
+
+impl JsonEncoder<EventFilterResult> for EventFilterResult {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "SelectClauseResults", &self.select_clause_results, ctx);
+        write_json_field(&mut object, "SelectClauseDiagnosticInfos", &self.select_clause_diagnostic_infos, ctx);
+        write_json_field(&mut object, "WhereClauseResult", &self.where_clause_result, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(EventFilterResult {
+            select_clause_results: read_json_field(object, "SelectClauseResults", decoding_limits)?,
+            select_clause_diagnostic_infos: read_json_field(object, "SelectClauseDiagnosticInfos", decoding_limits)?,
+            where_clause_result: read_json_field(object, "WhereClauseResult", decoding_limits)?,
+        })
+    }
+}
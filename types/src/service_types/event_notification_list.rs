@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::EventFieldList,
 };
@@ -41,3 +42,18 @@ impl BinaryEncoder<EventNotificationList> for EventNotificationList {
         })
     }
 }
+
+impl JsonEncoder<EventNotificationList> for EventNotificationList {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Events", &self.events, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(EventNotificationList {
+            events: read_json_field(object, "Events", decoding_limits)?,
+        })
+    }
+}
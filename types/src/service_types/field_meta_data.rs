@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -99,3 +100,36 @@ impl BinaryEncoder<FieldMetaData> for FieldMetaData {
         })
     }
 }
+
+impl JsonEncoder<FieldMetaData> for FieldMetaData {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Name", &self.name, ctx);
+        write_json_field(&mut object, "Description", &self.description, ctx);
+        write_json_field(&mut object, "FieldFlags", &self.field_flags, ctx);
+        write_json_field(&mut object, "BuiltInType", &self.built_in_type, ctx);
+        write_json_field(&mut object, "DataType", &self.data_type, ctx);
+        write_json_field(&mut object, "ValueRank", &self.value_rank, ctx);
+        write_json_field(&mut object, "ArrayDimensions", &self.array_dimensions, ctx);
+        write_json_field(&mut object, "MaxStringLength", &self.max_string_length, ctx);
+        write_json_field(&mut object, "DataSetFieldId", &self.data_set_field_id, ctx);
+        write_json_field(&mut object, "Properties", &self.properties, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(FieldMetaData {
+            name: read_json_field(object, "Name", decoding_limits)?,
+            description: read_json_field(object, "Description", decoding_limits)?,
+            field_flags: read_json_field(object, "FieldFlags", decoding_limits)?,
+            built_in_type: read_json_field(object, "BuiltInType", decoding_limits)?,
+            data_type: read_json_field(object, "DataType", decoding_limits)?,
+            value_rank: read_json_field(object, "ValueRank", decoding_limits)?,
+            array_dimensions: read_json_field(object, "ArrayDimensions", decoding_limits)?,
+            max_string_length: read_json_field(object, "MaxStringLength", decoding_limits)?,
+            data_set_field_id: read_json_field(object, "DataSetFieldId", decoding_limits)?,
+            properties: read_json_field(object, "Properties", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -83,3 +84,30 @@ impl BinaryEncoder<FieldTargetDataType> for FieldTargetDataType {
         })
     }
 }
+
+impl JsonEncoder<FieldTargetDataType> for FieldTargetDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "DataSetFieldId", &self.data_set_field_id, ctx);
+        write_json_field(&mut object, "ReceiverIndexRange", &self.receiver_index_range, ctx);
+        write_json_field(&mut object, "TargetNodeId", &self.target_node_id, ctx);
+        write_json_field(&mut object, "AttributeId", &self.attribute_id, ctx);
+        write_json_field(&mut object, "WriteIndexRange", &self.write_index_range, ctx);
+        write_json_field(&mut object, "OverrideValueHandling", &self.override_value_handling, ctx);
+        write_json_field(&mut object, "OverrideValue", &self.override_value, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(FieldTargetDataType {
+            data_set_field_id: read_json_field(object, "DataSetFieldId", decoding_limits)?,
+            receiver_index_range: read_json_field(object, "ReceiverIndexRange", decoding_limits)?,
+            target_node_id: read_json_field(object, "TargetNodeId", decoding_limits)?,
+            attribute_id: read_json_field(object, "AttributeId", decoding_limits)?,
+            write_index_range: read_json_field(object, "WriteIndexRange", decoding_limits)?,
+            override_value_handling: read_json_field(object, "OverrideValueHandling", decoding_limits)?,
+            override_value: read_json_field(object, "OverrideValue", decoding_limits)?,
+        })
+    }
+}
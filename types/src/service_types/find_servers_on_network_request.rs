@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -65,3 +66,24 @@ impl BinaryEncoder<FindServersOnNetworkRequest> for FindServersOnNetworkRequest
         })
     }
 }
+
+impl JsonEncoder<FindServersOnNetworkRequest> for FindServersOnNetworkRequest {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, ctx);
+        write_json_field(&mut object, "StartingRecordId", &self.starting_record_id, ctx);
+        write_json_field(&mut object, "MaxRecordsToReturn", &self.max_records_to_return, ctx);
+        write_json_field(&mut object, "ServerCapabilityFilter", &self.server_capability_filter, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(FindServersOnNetworkRequest {
+            request_header: read_json_field(object, "RequestHeader", decoding_limits)?,
+            starting_record_id: read_json_field(object, "StartingRecordId", decoding_limits)?,
+            max_records_to_return: read_json_field(object, "MaxRecordsToReturn", decoding_limits)?,
+            server_capability_filter: read_json_field(object, "ServerCapabilityFilter", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -61,3 +62,22 @@ impl BinaryEncoder<FindServersOnNetworkResponse> for FindServersOnNetworkRespons
         })
     }
 }
+
+impl JsonEncoder<FindServersOnNetworkResponse> for FindServersOnNetworkResponse {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ResponseHeader", &self.response_header, ctx);
+        write_json_field(&mut object, "LastCounterResetTime", &self.last_counter_reset_time, ctx);
+        write_json_field(&mut object, "Servers", &self.servers, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(FindServersOnNetworkResponse {
+            response_header: read_json_field(object, "ResponseHeader", decoding_limits)?,
+            last_counter_reset_time: read_json_field(object, "LastCounterResetTime", decoding_limits)?,
+            servers: read_json_field(object, "Servers", decoding_limits)?,
+        })
+    }
+}
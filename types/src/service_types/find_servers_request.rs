@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -65,3 +66,24 @@ impl BinaryEncoder<FindServersRequest> for FindServersRequest {
         })
     }
 }
+
+impl JsonEncoder<FindServersRequest> for FindServersRequest {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, ctx);
+        write_json_field(&mut object, "EndpointUrl", &self.endpoint_url, ctx);
+        write_json_field(&mut object, "LocaleIds", &self.locale_ids, ctx);
+        write_json_field(&mut object, "ServerUris", &self.server_uris, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(FindServersRequest {
+            request_header: read_json_field(object, "RequestHeader", decoding_limits)?,
+            endpoint_url: read_json_field(object, "EndpointUrl", decoding_limits)?,
+            locale_ids: read_json_field(object, "LocaleIds", decoding_limits)?,
+            server_uris: read_json_field(object, "ServerUris", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     localized_text::LocalizedText,
     service_types::GenericAttributeValue,
@@ -67,3 +68,28 @@ impl BinaryEncoder<GenericAttributes> for GenericAttributes {
         })
     }
 }
+
+impl JsonEncoder<GenericAttributes> for GenericAttributes {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "SpecifiedAttributes", &self.specified_attributes, ctx);
+        write_json_field(&mut object, "DisplayName", &self.display_name, ctx);
+        write_json_field(&mut object, "Description", &self.description, ctx);
+        write_json_field(&mut object, "WriteMask", &self.write_mask, ctx);
+        write_json_field(&mut object, "UserWriteMask", &self.user_write_mask, ctx);
+        write_json_field(&mut object, "AttributeValues", &self.attribute_values, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(GenericAttributes {
+            specified_attributes: read_json_field(object, "SpecifiedAttributes", decoding_limits)?,
+            display_name: read_json_field(object, "DisplayName", decoding_limits)?,
+            description: read_json_field(object, "Description", decoding_limits)?,
+            write_mask: read_json_field(object, "WriteMask", decoding_limits)?,
+            user_write_mask: read_json_field(object, "UserWriteMask", decoding_limits)?,
+            attribute_values: read_json_field(object, "AttributeValues", decoding_limits)?,
+        })
+    }
+}
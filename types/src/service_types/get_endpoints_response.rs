@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -55,3 +56,20 @@ impl BinaryEncoder<GetEndpointsResponse> for GetEndpointsResponse {
         })
     }
 }
+
+impl JsonEncoder<GetEndpointsResponse> for GetEndpointsResponse {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ResponseHeader", &self.response_header, ctx);
+        write_json_field(&mut object, "Endpoints", &self.endpoints, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(GetEndpointsResponse {
+            response_header: read_json_field(object, "ResponseHeader", decoding_limits)?,
+            endpoints: read_json_field(object, "Endpoints", decoding_limits)?,
+        })
+    }
+}
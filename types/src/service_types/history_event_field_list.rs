@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -49,3 +50,18 @@ impl BinaryEncoder<HistoryEventFieldList> for HistoryEventFieldList {
         })
     }
 }
+
+impl JsonEncoder<HistoryEventFieldList> for HistoryEventFieldList {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "EventFields", &self.event_fields, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(HistoryEventFieldList {
+            event_fields: read_json_field(object, "EventFields", decoding_limits)?,
+        })
+    }
+}
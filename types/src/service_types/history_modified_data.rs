@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     data_value::DataValue,
     service_types::ModificationInfo,
@@ -47,3 +48,20 @@ impl BinaryEncoder<HistoryModifiedData> for HistoryModifiedData {
         })
     }
 }
+
+impl JsonEncoder<HistoryModifiedData> for HistoryModifiedData {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "DataValues", &self.data_values, ctx);
+        write_json_field(&mut object, "ModificationInfos", &self.modification_infos, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(HistoryModifiedData {
+            data_values: read_json_field(object, "DataValues", decoding_limits)?,
+            modification_infos: read_json_field(object, "ModificationInfos", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -61,3 +62,22 @@ impl BinaryEncoder<HistoryReadResult> for HistoryReadResult {
         })
     }
 }
+
+impl JsonEncoder<HistoryReadResult> for HistoryReadResult {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "StatusCode", &self.status_code, ctx);
+        write_json_field(&mut object, "ContinuationPoint", &self.continuation_point, ctx);
+        write_json_field(&mut object, "HistoryData", &self.history_data, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(HistoryReadResult {
+            status_code: read_json_field(object, "StatusCode", decoding_limits)?,
+            continuation_point: read_json_field(object, "ContinuationPoint", decoding_limits)?,
+            history_data: read_json_field(object, "HistoryData", decoding_limits)?,
+        })
+    }
+}
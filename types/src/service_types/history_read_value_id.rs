@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -67,3 +68,24 @@ impl BinaryEncoder<HistoryReadValueId> for HistoryReadValueId {
         })
     }
 }
+
+impl JsonEncoder<HistoryReadValueId> for HistoryReadValueId {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NodeId", &self.node_id, ctx);
+        write_json_field(&mut object, "IndexRange", &self.index_range, ctx);
+        write_json_field(&mut object, "DataEncoding", &self.data_encoding, ctx);
+        write_json_field(&mut object, "ContinuationPoint", &self.continuation_point, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(HistoryReadValueId {
+            node_id: read_json_field(object, "NodeId", decoding_limits)?,
+            index_range: read_json_field(object, "IndexRange", decoding_limits)?,
+            data_encoding: read_json_field(object, "DataEncoding", decoding_limits)?,
+            continuation_point: read_json_field(object, "ContinuationPoint", decoding_limits)?,
+        })
+    }
+}
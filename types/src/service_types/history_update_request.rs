@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -55,3 +56,20 @@ impl BinaryEncoder<HistoryUpdateRequest> for HistoryUpdateRequest {
         })
     }
 }
+
+impl JsonEncoder<HistoryUpdateRequest> for HistoryUpdateRequest {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, ctx);
+        write_json_field(&mut object, "HistoryUpdateDetails", &self.history_update_details, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(HistoryUpdateRequest {
+            request_header: read_json_field(object, "RequestHeader", decoding_limits)?,
+            history_update_details: read_json_field(object, "HistoryUpdateDetails", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -55,3 +56,20 @@ impl BinaryEncoder<IdentityMappingRuleType> for IdentityMappingRuleType {
         })
     }
 }
+
+impl JsonEncoder<IdentityMappingRuleType> for IdentityMappingRuleType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "CriteriaType", &self.criteria_type, ctx);
+        write_json_field(&mut object, "Criteria", &self.criteria, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(IdentityMappingRuleType {
+            criteria_type: read_json_field(object, "CriteriaType", decoding_limits)?,
+            criteria: read_json_field(object, "Criteria", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     string::UAString,
     byte_string::ByteString,
@@ -52,3 +53,22 @@ impl BinaryEncoder<IssuedIdentityToken> for IssuedIdentityToken {
         })
     }
 }
+
+impl JsonEncoder<IssuedIdentityToken> for IssuedIdentityToken {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "PolicyId", &self.policy_id, ctx);
+        write_json_field(&mut object, "TokenData", &self.token_data, ctx);
+        write_json_field(&mut object, "EncryptionAlgorithm", &self.encryption_algorithm, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(IssuedIdentityToken {
+            policy_id: read_json_field(object, "PolicyId", decoding_limits)?,
+            token_data: read_json_field(object, "TokenData", decoding_limits)?,
+            encryption_algorithm: read_json_field(object, "EncryptionAlgorithm", decoding_limits)?,
+        })
+    }
+}
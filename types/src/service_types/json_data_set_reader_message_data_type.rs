@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::enums::JsonNetworkMessageContentMask,
     service_types::enums::JsonDataSetMessageContentMask,
@@ -47,3 +48,20 @@ impl BinaryEncoder<JsonDataSetReaderMessageDataType> for JsonDataSetReaderMessag
         })
     }
 }
+
+impl JsonEncoder<JsonDataSetReaderMessageDataType> for JsonDataSetReaderMessageDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NetworkMessageContentMask", &self.network_message_content_mask, ctx);
+        write_json_field(&mut object, "DataSetMessageContentMask", &self.data_set_message_content_mask, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(JsonDataSetReaderMessageDataType {
+            network_message_content_mask: read_json_field(object, "NetworkMessageContentMask", decoding_limits)?,
+            data_set_message_content_mask: read_json_field(object, "DataSetMessageContentMask", decoding_limits)?,
+        })
+    }
+}
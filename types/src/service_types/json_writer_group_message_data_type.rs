@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::enums::JsonNetworkMessageContentMask,
 };
@@ -41,3 +42,18 @@ impl BinaryEncoder<JsonWriterGroupMessageDataType> for JsonWriterGroupMessageDat
         })
     }
 }
+
+impl JsonEncoder<JsonWriterGroupMessageDataType> for JsonWriterGroupMessageDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NetworkMessageContentMask", &self.network_message_content_mask, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(JsonWriterGroupMessageDataType {
+            network_message_content_mask: read_json_field(object, "NetworkMessageContentMask", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     variant::Variant,
 };
@@ -41,3 +42,18 @@ impl BinaryEncoder<LiteralOperand> for LiteralOperand {
         })
     }
 }
+
+impl JsonEncoder<LiteralOperand> for LiteralOperand {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Value", &self.value, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(LiteralOperand {
+            value: read_json_field(object, "Value", decoding_limits)?,
+        })
+    }
+}
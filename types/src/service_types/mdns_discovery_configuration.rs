@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     string::UAString,
 };
@@ -46,3 +47,20 @@ impl BinaryEncoder<MdnsDiscoveryConfiguration> for MdnsDiscoveryConfiguration {
         })
     }
 }
+
+impl JsonEncoder<MdnsDiscoveryConfiguration> for MdnsDiscoveryConfiguration {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "MdnsServerName", &self.mdns_server_name, ctx);
+        write_json_field(&mut object, "ServerCapabilities", &self.server_capabilities, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(MdnsDiscoveryConfiguration {
+            mdns_server_name: read_json_field(object, "MdnsServerName", decoding_limits)?,
+            server_capabilities: read_json_field(object, "ServerCapabilities", decoding_limits)?,
+        })
+    }
+}
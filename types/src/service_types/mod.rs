@@ -567,3 +567,283 @@ pub use self::xv_type::*;
 pub use self::program_diagnostic_data_type::*;
 pub use self::program_diagnostic_2_data_type::*;
 pub use self::annotation::*;
+
+// The types above that can be held in an extension object with a JSON body
+json_extension_object_types! {
+    KeyValuePair, KeyValuePair_Encoding_DefaultBinary, KeyValuePair_Encoding_DefaultJson;
+    EndpointType, EndpointType_Encoding_DefaultBinary, EndpointType_Encoding_DefaultJson;
+    RationalNumber, RationalNumber_Encoding_DefaultBinary, RationalNumber_Encoding_DefaultJson;
+    Vector, Vector_Encoding_DefaultBinary, Vector_Encoding_DefaultJson;
+    ThreeDVector, ThreeDVector_Encoding_DefaultBinary, ThreeDVector_Encoding_DefaultJson;
+    CartesianCoordinates, CartesianCoordinates_Encoding_DefaultBinary, CartesianCoordinates_Encoding_DefaultJson;
+    ThreeDCartesianCoordinates, ThreeDCartesianCoordinates_Encoding_DefaultBinary, ThreeDCartesianCoordinates_Encoding_DefaultJson;
+    Orientation, Orientation_Encoding_DefaultBinary, Orientation_Encoding_DefaultJson;
+    ThreeDOrientation, ThreeDOrientation_Encoding_DefaultBinary, ThreeDOrientation_Encoding_DefaultJson;
+    Frame, Frame_Encoding_DefaultBinary, Frame_Encoding_DefaultJson;
+    ThreeDFrame, ThreeDFrame_Encoding_DefaultBinary, ThreeDFrame_Encoding_DefaultJson;
+    IdentityMappingRuleType, IdentityMappingRuleType_Encoding_DefaultBinary, IdentityMappingRuleType_Encoding_DefaultJson;
+    TrustListDataType, TrustListDataType_Encoding_DefaultBinary, TrustListDataType_Encoding_DefaultJson;
+    DecimalDataType, DecimalDataType_Encoding_DefaultBinary, DecimalDataType_Encoding_DefaultJson;
+    DataTypeSchemaHeader, DataTypeSchemaHeader_Encoding_DefaultBinary, DataTypeSchemaHeader_Encoding_DefaultJson;
+    DataTypeDescription, DataTypeDescription_Encoding_DefaultBinary, DataTypeDescription_Encoding_DefaultJson;
+    StructureDescription, StructureDescription_Encoding_DefaultBinary, StructureDescription_Encoding_DefaultJson;
+    EnumDescription, EnumDescription_Encoding_DefaultBinary, EnumDescription_Encoding_DefaultJson;
+    SimpleTypeDescription, SimpleTypeDescription_Encoding_DefaultBinary, SimpleTypeDescription_Encoding_DefaultJson;
+    UABinaryFileDataType, UABinaryFileDataType_Encoding_DefaultBinary, UABinaryFileDataType_Encoding_DefaultJson;
+    DataSetMetaDataType, DataSetMetaDataType_Encoding_DefaultBinary, DataSetMetaDataType_Encoding_DefaultJson;
+    FieldMetaData, FieldMetaData_Encoding_DefaultBinary, FieldMetaData_Encoding_DefaultJson;
+    ConfigurationVersionDataType, ConfigurationVersionDataType_Encoding_DefaultBinary, ConfigurationVersionDataType_Encoding_DefaultJson;
+    PublishedDataSetDataType, PublishedDataSetDataType_Encoding_DefaultBinary, PublishedDataSetDataType_Encoding_DefaultJson;
+    PublishedDataSetSourceDataType, PublishedDataSetSourceDataType_Encoding_DefaultBinary, PublishedDataSetSourceDataType_Encoding_DefaultJson;
+    PublishedVariableDataType, PublishedVariableDataType_Encoding_DefaultBinary, PublishedVariableDataType_Encoding_DefaultJson;
+    PublishedDataItemsDataType, PublishedDataItemsDataType_Encoding_DefaultBinary, PublishedDataItemsDataType_Encoding_DefaultJson;
+    PublishedEventsDataType, PublishedEventsDataType_Encoding_DefaultBinary, PublishedEventsDataType_Encoding_DefaultJson;
+    DataSetWriterDataType, DataSetWriterDataType_Encoding_DefaultBinary, DataSetWriterDataType_Encoding_DefaultJson;
+    DataSetWriterTransportDataType, DataSetWriterTransportDataType_Encoding_DefaultBinary, DataSetWriterTransportDataType_Encoding_DefaultJson;
+    DataSetWriterMessageDataType, DataSetWriterMessageDataType_Encoding_DefaultBinary, DataSetWriterMessageDataType_Encoding_DefaultJson;
+    PubSubGroupDataType, PubSubGroupDataType_Encoding_DefaultBinary, PubSubGroupDataType_Encoding_DefaultJson;
+    WriterGroupDataType, WriterGroupDataType_Encoding_DefaultBinary, WriterGroupDataType_Encoding_DefaultJson;
+    WriterGroupTransportDataType, WriterGroupTransportDataType_Encoding_DefaultBinary, WriterGroupTransportDataType_Encoding_DefaultJson;
+    WriterGroupMessageDataType, WriterGroupMessageDataType_Encoding_DefaultBinary, WriterGroupMessageDataType_Encoding_DefaultJson;
+    PubSubConnectionDataType, PubSubConnectionDataType_Encoding_DefaultBinary, PubSubConnectionDataType_Encoding_DefaultJson;
+    ConnectionTransportDataType, ConnectionTransportDataType_Encoding_DefaultBinary, ConnectionTransportDataType_Encoding_DefaultJson;
+    NetworkAddressDataType, NetworkAddressDataType_Encoding_DefaultBinary, NetworkAddressDataType_Encoding_DefaultJson;
+    NetworkAddressUrlDataType, NetworkAddressUrlDataType_Encoding_DefaultBinary, NetworkAddressUrlDataType_Encoding_DefaultJson;
+    ReaderGroupDataType, ReaderGroupDataType_Encoding_DefaultBinary, ReaderGroupDataType_Encoding_DefaultJson;
+    ReaderGroupTransportDataType, ReaderGroupTransportDataType_Encoding_DefaultBinary, ReaderGroupTransportDataType_Encoding_DefaultJson;
+    ReaderGroupMessageDataType, ReaderGroupMessageDataType_Encoding_DefaultBinary, ReaderGroupMessageDataType_Encoding_DefaultJson;
+    DataSetReaderDataType, DataSetReaderDataType_Encoding_DefaultBinary, DataSetReaderDataType_Encoding_DefaultJson;
+    DataSetReaderTransportDataType, DataSetReaderTransportDataType_Encoding_DefaultBinary, DataSetReaderTransportDataType_Encoding_DefaultJson;
+    DataSetReaderMessageDataType, DataSetReaderMessageDataType_Encoding_DefaultBinary, DataSetReaderMessageDataType_Encoding_DefaultJson;
+    SubscribedDataSetDataType, SubscribedDataSetDataType_Encoding_DefaultBinary, SubscribedDataSetDataType_Encoding_DefaultJson;
+    TargetVariablesDataType, TargetVariablesDataType_Encoding_DefaultBinary, TargetVariablesDataType_Encoding_DefaultJson;
+    FieldTargetDataType, FieldTargetDataType_Encoding_DefaultBinary, FieldTargetDataType_Encoding_DefaultJson;
+    SubscribedDataSetMirrorDataType, SubscribedDataSetMirrorDataType_Encoding_DefaultBinary, SubscribedDataSetMirrorDataType_Encoding_DefaultJson;
+    PubSubConfigurationDataType, PubSubConfigurationDataType_Encoding_DefaultBinary, PubSubConfigurationDataType_Encoding_DefaultJson;
+    UadpWriterGroupMessageDataType, UadpWriterGroupMessageDataType_Encoding_DefaultBinary, UadpWriterGroupMessageDataType_Encoding_DefaultJson;
+    UadpDataSetWriterMessageDataType, UadpDataSetWriterMessageDataType_Encoding_DefaultBinary, UadpDataSetWriterMessageDataType_Encoding_DefaultJson;
+    UadpDataSetReaderMessageDataType, UadpDataSetReaderMessageDataType_Encoding_DefaultBinary, UadpDataSetReaderMessageDataType_Encoding_DefaultJson;
+    JsonWriterGroupMessageDataType, JsonWriterGroupMessageDataType_Encoding_DefaultBinary, JsonWriterGroupMessageDataType_Encoding_DefaultJson;
+    JsonDataSetWriterMessageDataType, JsonDataSetWriterMessageDataType_Encoding_DefaultBinary, JsonDataSetWriterMessageDataType_Encoding_DefaultJson;
+    JsonDataSetReaderMessageDataType, JsonDataSetReaderMessageDataType_Encoding_DefaultBinary, JsonDataSetReaderMessageDataType_Encoding_DefaultJson;
+    DatagramConnectionTransportDataType, DatagramConnectionTransportDataType_Encoding_DefaultBinary, DatagramConnectionTransportDataType_Encoding_DefaultJson;
+    DatagramWriterGroupTransportDataType, DatagramWriterGroupTransportDataType_Encoding_DefaultBinary, DatagramWriterGroupTransportDataType_Encoding_DefaultJson;
+    BrokerConnectionTransportDataType, BrokerConnectionTransportDataType_Encoding_DefaultBinary, BrokerConnectionTransportDataType_Encoding_DefaultJson;
+    BrokerWriterGroupTransportDataType, BrokerWriterGroupTransportDataType_Encoding_DefaultBinary, BrokerWriterGroupTransportDataType_Encoding_DefaultJson;
+    BrokerDataSetWriterTransportDataType, BrokerDataSetWriterTransportDataType_Encoding_DefaultBinary, BrokerDataSetWriterTransportDataType_Encoding_DefaultJson;
+    BrokerDataSetReaderTransportDataType, BrokerDataSetReaderTransportDataType_Encoding_DefaultBinary, BrokerDataSetReaderTransportDataType_Encoding_DefaultJson;
+    RolePermissionType, RolePermissionType_Encoding_DefaultBinary, RolePermissionType_Encoding_DefaultJson;
+    StructureField, StructureField_Encoding_DefaultBinary, StructureField_Encoding_DefaultJson;
+    StructureDefinition, StructureDefinition_Encoding_DefaultBinary, StructureDefinition_Encoding_DefaultJson;
+    EnumDefinition, EnumDefinition_Encoding_DefaultBinary, EnumDefinition_Encoding_DefaultJson;
+    Argument, Argument_Encoding_DefaultBinary, Argument_Encoding_DefaultJson;
+    EnumValueType, EnumValueType_Encoding_DefaultBinary, EnumValueType_Encoding_DefaultJson;
+    EnumField, EnumField_Encoding_DefaultBinary, EnumField_Encoding_DefaultJson;
+    OptionSet, OptionSet_Encoding_DefaultBinary, OptionSet_Encoding_DefaultJson;
+    TimeZoneDataType, TimeZoneDataType_Encoding_DefaultBinary, TimeZoneDataType_Encoding_DefaultJson;
+    ApplicationDescription, ApplicationDescription_Encoding_DefaultBinary, ApplicationDescription_Encoding_DefaultJson;
+    ServiceFault, ServiceFault_Encoding_DefaultBinary, ServiceFault_Encoding_DefaultJson;
+    SessionlessInvokeRequestType, SessionlessInvokeRequestType_Encoding_DefaultBinary, SessionlessInvokeRequestType_Encoding_DefaultJson;
+    SessionlessInvokeResponseType, SessionlessInvokeResponseType_Encoding_DefaultBinary, SessionlessInvokeResponseType_Encoding_DefaultJson;
+    FindServersRequest, FindServersRequest_Encoding_DefaultBinary, FindServersRequest_Encoding_DefaultJson;
+    FindServersResponse, FindServersResponse_Encoding_DefaultBinary, FindServersResponse_Encoding_DefaultJson;
+    ServerOnNetwork, ServerOnNetwork_Encoding_DefaultBinary, ServerOnNetwork_Encoding_DefaultJson;
+    FindServersOnNetworkRequest, FindServersOnNetworkRequest_Encoding_DefaultBinary, FindServersOnNetworkRequest_Encoding_DefaultJson;
+    FindServersOnNetworkResponse, FindServersOnNetworkResponse_Encoding_DefaultBinary, FindServersOnNetworkResponse_Encoding_DefaultJson;
+    UserTokenPolicy, UserTokenPolicy_Encoding_DefaultBinary, UserTokenPolicy_Encoding_DefaultJson;
+    EndpointDescription, EndpointDescription_Encoding_DefaultBinary, EndpointDescription_Encoding_DefaultJson;
+    GetEndpointsRequest, GetEndpointsRequest_Encoding_DefaultBinary, GetEndpointsRequest_Encoding_DefaultJson;
+    GetEndpointsResponse, GetEndpointsResponse_Encoding_DefaultBinary, GetEndpointsResponse_Encoding_DefaultJson;
+    RegisteredServer, RegisteredServer_Encoding_DefaultBinary, RegisteredServer_Encoding_DefaultJson;
+    RegisterServerRequest, RegisterServerRequest_Encoding_DefaultBinary, RegisterServerRequest_Encoding_DefaultJson;
+    RegisterServerResponse, RegisterServerResponse_Encoding_DefaultBinary, RegisterServerResponse_Encoding_DefaultJson;
+    DiscoveryConfiguration, DiscoveryConfiguration_Encoding_DefaultBinary, DiscoveryConfiguration_Encoding_DefaultJson;
+    MdnsDiscoveryConfiguration, MdnsDiscoveryConfiguration_Encoding_DefaultBinary, MdnsDiscoveryConfiguration_Encoding_DefaultJson;
+    RegisterServer2Request, RegisterServer2Request_Encoding_DefaultBinary, RegisterServer2Request_Encoding_DefaultJson;
+    RegisterServer2Response, RegisterServer2Response_Encoding_DefaultBinary, RegisterServer2Response_Encoding_DefaultJson;
+    ChannelSecurityToken, ChannelSecurityToken_Encoding_DefaultBinary, ChannelSecurityToken_Encoding_DefaultJson;
+    OpenSecureChannelRequest, OpenSecureChannelRequest_Encoding_DefaultBinary, OpenSecureChannelRequest_Encoding_DefaultJson;
+    OpenSecureChannelResponse, OpenSecureChannelResponse_Encoding_DefaultBinary, OpenSecureChannelResponse_Encoding_DefaultJson;
+    CloseSecureChannelRequest, CloseSecureChannelRequest_Encoding_DefaultBinary, CloseSecureChannelRequest_Encoding_DefaultJson;
+    CloseSecureChannelResponse, CloseSecureChannelResponse_Encoding_DefaultBinary, CloseSecureChannelResponse_Encoding_DefaultJson;
+    SignedSoftwareCertificate, SignedSoftwareCertificate_Encoding_DefaultBinary, SignedSoftwareCertificate_Encoding_DefaultJson;
+    SignatureData, SignatureData_Encoding_DefaultBinary, SignatureData_Encoding_DefaultJson;
+    CreateSessionRequest, CreateSessionRequest_Encoding_DefaultBinary, CreateSessionRequest_Encoding_DefaultJson;
+    CreateSessionResponse, CreateSessionResponse_Encoding_DefaultBinary, CreateSessionResponse_Encoding_DefaultJson;
+    UserIdentityToken, UserIdentityToken_Encoding_DefaultBinary, UserIdentityToken_Encoding_DefaultJson;
+    AnonymousIdentityToken, AnonymousIdentityToken_Encoding_DefaultBinary, AnonymousIdentityToken_Encoding_DefaultJson;
+    UserNameIdentityToken, UserNameIdentityToken_Encoding_DefaultBinary, UserNameIdentityToken_Encoding_DefaultJson;
+    X509IdentityToken, X509IdentityToken_Encoding_DefaultBinary, X509IdentityToken_Encoding_DefaultJson;
+    IssuedIdentityToken, IssuedIdentityToken_Encoding_DefaultBinary, IssuedIdentityToken_Encoding_DefaultJson;
+    ActivateSessionRequest, ActivateSessionRequest_Encoding_DefaultBinary, ActivateSessionRequest_Encoding_DefaultJson;
+    ActivateSessionResponse, ActivateSessionResponse_Encoding_DefaultBinary, ActivateSessionResponse_Encoding_DefaultJson;
+    CloseSessionRequest, CloseSessionRequest_Encoding_DefaultBinary, CloseSessionRequest_Encoding_DefaultJson;
+    CloseSessionResponse, CloseSessionResponse_Encoding_DefaultBinary, CloseSessionResponse_Encoding_DefaultJson;
+    CancelRequest, CancelRequest_Encoding_DefaultBinary, CancelRequest_Encoding_DefaultJson;
+    CancelResponse, CancelResponse_Encoding_DefaultBinary, CancelResponse_Encoding_DefaultJson;
+    NodeAttributes, NodeAttributes_Encoding_DefaultBinary, NodeAttributes_Encoding_DefaultJson;
+    ObjectAttributes, ObjectAttributes_Encoding_DefaultBinary, ObjectAttributes_Encoding_DefaultJson;
+    VariableAttributes, VariableAttributes_Encoding_DefaultBinary, VariableAttributes_Encoding_DefaultJson;
+    MethodAttributes, MethodAttributes_Encoding_DefaultBinary, MethodAttributes_Encoding_DefaultJson;
+    ObjectTypeAttributes, ObjectTypeAttributes_Encoding_DefaultBinary, ObjectTypeAttributes_Encoding_DefaultJson;
+    VariableTypeAttributes, VariableTypeAttributes_Encoding_DefaultBinary, VariableTypeAttributes_Encoding_DefaultJson;
+    ReferenceTypeAttributes, ReferenceTypeAttributes_Encoding_DefaultBinary, ReferenceTypeAttributes_Encoding_DefaultJson;
+    DataTypeAttributes, DataTypeAttributes_Encoding_DefaultBinary, DataTypeAttributes_Encoding_DefaultJson;
+    ViewAttributes, ViewAttributes_Encoding_DefaultBinary, ViewAttributes_Encoding_DefaultJson;
+    GenericAttributeValue, GenericAttributeValue_Encoding_DefaultBinary, GenericAttributeValue_Encoding_DefaultJson;
+    GenericAttributes, GenericAttributes_Encoding_DefaultBinary, GenericAttributes_Encoding_DefaultJson;
+    AddNodesItem, AddNodesItem_Encoding_DefaultBinary, AddNodesItem_Encoding_DefaultJson;
+    AddNodesResult, AddNodesResult_Encoding_DefaultBinary, AddNodesResult_Encoding_DefaultJson;
+    AddNodesRequest, AddNodesRequest_Encoding_DefaultBinary, AddNodesRequest_Encoding_DefaultJson;
+    AddNodesResponse, AddNodesResponse_Encoding_DefaultBinary, AddNodesResponse_Encoding_DefaultJson;
+    AddReferencesItem, AddReferencesItem_Encoding_DefaultBinary, AddReferencesItem_Encoding_DefaultJson;
+    AddReferencesRequest, AddReferencesRequest_Encoding_DefaultBinary, AddReferencesRequest_Encoding_DefaultJson;
+    AddReferencesResponse, AddReferencesResponse_Encoding_DefaultBinary, AddReferencesResponse_Encoding_DefaultJson;
+    DeleteNodesItem, DeleteNodesItem_Encoding_DefaultBinary, DeleteNodesItem_Encoding_DefaultJson;
+    DeleteNodesRequest, DeleteNodesRequest_Encoding_DefaultBinary, DeleteNodesRequest_Encoding_DefaultJson;
+    DeleteNodesResponse, DeleteNodesResponse_Encoding_DefaultBinary, DeleteNodesResponse_Encoding_DefaultJson;
+    DeleteReferencesItem, DeleteReferencesItem_Encoding_DefaultBinary, DeleteReferencesItem_Encoding_DefaultJson;
+    DeleteReferencesRequest, DeleteReferencesRequest_Encoding_DefaultBinary, DeleteReferencesRequest_Encoding_DefaultJson;
+    DeleteReferencesResponse, DeleteReferencesResponse_Encoding_DefaultBinary, DeleteReferencesResponse_Encoding_DefaultJson;
+    ViewDescription, ViewDescription_Encoding_DefaultBinary, ViewDescription_Encoding_DefaultJson;
+    BrowseDescription, BrowseDescription_Encoding_DefaultBinary, BrowseDescription_Encoding_DefaultJson;
+    ReferenceDescription, ReferenceDescription_Encoding_DefaultBinary, ReferenceDescription_Encoding_DefaultJson;
+    BrowseResult, BrowseResult_Encoding_DefaultBinary, BrowseResult_Encoding_DefaultJson;
+    BrowseRequest, BrowseRequest_Encoding_DefaultBinary, BrowseRequest_Encoding_DefaultJson;
+    BrowseResponse, BrowseResponse_Encoding_DefaultBinary, BrowseResponse_Encoding_DefaultJson;
+    BrowseNextRequest, BrowseNextRequest_Encoding_DefaultBinary, BrowseNextRequest_Encoding_DefaultJson;
+    BrowseNextResponse, BrowseNextResponse_Encoding_DefaultBinary, BrowseNextResponse_Encoding_DefaultJson;
+    RelativePathElement, RelativePathElement_Encoding_DefaultBinary, RelativePathElement_Encoding_DefaultJson;
+    RelativePath, RelativePath_Encoding_DefaultBinary, RelativePath_Encoding_DefaultJson;
+    BrowsePath, BrowsePath_Encoding_DefaultBinary, BrowsePath_Encoding_DefaultJson;
+    BrowsePathTarget, BrowsePathTarget_Encoding_DefaultBinary, BrowsePathTarget_Encoding_DefaultJson;
+    BrowsePathResult, BrowsePathResult_Encoding_DefaultBinary, BrowsePathResult_Encoding_DefaultJson;
+    TranslateBrowsePathsToNodeIdsRequest, TranslateBrowsePathsToNodeIdsRequest_Encoding_DefaultBinary, TranslateBrowsePathsToNodeIdsRequest_Encoding_DefaultJson;
+    TranslateBrowsePathsToNodeIdsResponse, TranslateBrowsePathsToNodeIdsResponse_Encoding_DefaultBinary, TranslateBrowsePathsToNodeIdsResponse_Encoding_DefaultJson;
+    RegisterNodesRequest, RegisterNodesRequest_Encoding_DefaultBinary, RegisterNodesRequest_Encoding_DefaultJson;
+    RegisterNodesResponse, RegisterNodesResponse_Encoding_DefaultBinary, RegisterNodesResponse_Encoding_DefaultJson;
+    UnregisterNodesRequest, UnregisterNodesRequest_Encoding_DefaultBinary, UnregisterNodesRequest_Encoding_DefaultJson;
+    UnregisterNodesResponse, UnregisterNodesResponse_Encoding_DefaultBinary, UnregisterNodesResponse_Encoding_DefaultJson;
+    EndpointConfiguration, EndpointConfiguration_Encoding_DefaultBinary, EndpointConfiguration_Encoding_DefaultJson;
+    QueryDataDescription, QueryDataDescription_Encoding_DefaultBinary, QueryDataDescription_Encoding_DefaultJson;
+    NodeTypeDescription, NodeTypeDescription_Encoding_DefaultBinary, NodeTypeDescription_Encoding_DefaultJson;
+    QueryDataSet, QueryDataSet_Encoding_DefaultBinary, QueryDataSet_Encoding_DefaultJson;
+    NodeReference, NodeReference_Encoding_DefaultBinary, NodeReference_Encoding_DefaultJson;
+    ContentFilterElement, ContentFilterElement_Encoding_DefaultBinary, ContentFilterElement_Encoding_DefaultJson;
+    ContentFilter, ContentFilter_Encoding_DefaultBinary, ContentFilter_Encoding_DefaultJson;
+    FilterOperand, FilterOperand_Encoding_DefaultBinary, FilterOperand_Encoding_DefaultJson;
+    ElementOperand, ElementOperand_Encoding_DefaultBinary, ElementOperand_Encoding_DefaultJson;
+    LiteralOperand, LiteralOperand_Encoding_DefaultBinary, LiteralOperand_Encoding_DefaultJson;
+    AttributeOperand, AttributeOperand_Encoding_DefaultBinary, AttributeOperand_Encoding_DefaultJson;
+    SimpleAttributeOperand, SimpleAttributeOperand_Encoding_DefaultBinary, SimpleAttributeOperand_Encoding_DefaultJson;
+    ContentFilterElementResult, ContentFilterElementResult_Encoding_DefaultBinary, ContentFilterElementResult_Encoding_DefaultJson;
+    ContentFilterResult, ContentFilterResult_Encoding_DefaultBinary, ContentFilterResult_Encoding_DefaultJson;
+    ParsingResult, ParsingResult_Encoding_DefaultBinary, ParsingResult_Encoding_DefaultJson;
+    QueryFirstRequest, QueryFirstRequest_Encoding_DefaultBinary, QueryFirstRequest_Encoding_DefaultJson;
+    QueryFirstResponse, QueryFirstResponse_Encoding_DefaultBinary, QueryFirstResponse_Encoding_DefaultJson;
+    QueryNextRequest, QueryNextRequest_Encoding_DefaultBinary, QueryNextRequest_Encoding_DefaultJson;
+    QueryNextResponse, QueryNextResponse_Encoding_DefaultBinary, QueryNextResponse_Encoding_DefaultJson;
+    ReadValueId, ReadValueId_Encoding_DefaultBinary, ReadValueId_Encoding_DefaultJson;
+    ReadRequest, ReadRequest_Encoding_DefaultBinary, ReadRequest_Encoding_DefaultJson;
+    ReadResponse, ReadResponse_Encoding_DefaultBinary, ReadResponse_Encoding_DefaultJson;
+    HistoryReadValueId, HistoryReadValueId_Encoding_DefaultBinary, HistoryReadValueId_Encoding_DefaultJson;
+    HistoryReadResult, HistoryReadResult_Encoding_DefaultBinary, HistoryReadResult_Encoding_DefaultJson;
+    HistoryReadDetails, HistoryReadDetails_Encoding_DefaultBinary, HistoryReadDetails_Encoding_DefaultJson;
+    ReadEventDetails, ReadEventDetails_Encoding_DefaultBinary, ReadEventDetails_Encoding_DefaultJson;
+    ReadRawModifiedDetails, ReadRawModifiedDetails_Encoding_DefaultBinary, ReadRawModifiedDetails_Encoding_DefaultJson;
+    ReadProcessedDetails, ReadProcessedDetails_Encoding_DefaultBinary, ReadProcessedDetails_Encoding_DefaultJson;
+    ReadAtTimeDetails, ReadAtTimeDetails_Encoding_DefaultBinary, ReadAtTimeDetails_Encoding_DefaultJson;
+    HistoryData, HistoryData_Encoding_DefaultBinary, HistoryData_Encoding_DefaultJson;
+    ModificationInfo, ModificationInfo_Encoding_DefaultBinary, ModificationInfo_Encoding_DefaultJson;
+    HistoryModifiedData, HistoryModifiedData_Encoding_DefaultBinary, HistoryModifiedData_Encoding_DefaultJson;
+    HistoryEvent, HistoryEvent_Encoding_DefaultBinary, HistoryEvent_Encoding_DefaultJson;
+    HistoryReadRequest, HistoryReadRequest_Encoding_DefaultBinary, HistoryReadRequest_Encoding_DefaultJson;
+    HistoryReadResponse, HistoryReadResponse_Encoding_DefaultBinary, HistoryReadResponse_Encoding_DefaultJson;
+    WriteValue, WriteValue_Encoding_DefaultBinary, WriteValue_Encoding_DefaultJson;
+    WriteRequest, WriteRequest_Encoding_DefaultBinary, WriteRequest_Encoding_DefaultJson;
+    WriteResponse, WriteResponse_Encoding_DefaultBinary, WriteResponse_Encoding_DefaultJson;
+    HistoryUpdateDetails, HistoryUpdateDetails_Encoding_DefaultBinary, HistoryUpdateDetails_Encoding_DefaultJson;
+    UpdateDataDetails, UpdateDataDetails_Encoding_DefaultBinary, UpdateDataDetails_Encoding_DefaultJson;
+    UpdateStructureDataDetails, UpdateStructureDataDetails_Encoding_DefaultBinary, UpdateStructureDataDetails_Encoding_DefaultJson;
+    UpdateEventDetails, UpdateEventDetails_Encoding_DefaultBinary, UpdateEventDetails_Encoding_DefaultJson;
+    DeleteRawModifiedDetails, DeleteRawModifiedDetails_Encoding_DefaultBinary, DeleteRawModifiedDetails_Encoding_DefaultJson;
+    DeleteAtTimeDetails, DeleteAtTimeDetails_Encoding_DefaultBinary, DeleteAtTimeDetails_Encoding_DefaultJson;
+    DeleteEventDetails, DeleteEventDetails_Encoding_DefaultBinary, DeleteEventDetails_Encoding_DefaultJson;
+    HistoryUpdateResult, HistoryUpdateResult_Encoding_DefaultBinary, HistoryUpdateResult_Encoding_DefaultJson;
+    HistoryUpdateRequest, HistoryUpdateRequest_Encoding_DefaultBinary, HistoryUpdateRequest_Encoding_DefaultJson;
+    HistoryUpdateResponse, HistoryUpdateResponse_Encoding_DefaultBinary, HistoryUpdateResponse_Encoding_DefaultJson;
+    CallMethodRequest, CallMethodRequest_Encoding_DefaultBinary, CallMethodRequest_Encoding_DefaultJson;
+    CallMethodResult, CallMethodResult_Encoding_DefaultBinary, CallMethodResult_Encoding_DefaultJson;
+    CallRequest, CallRequest_Encoding_DefaultBinary, CallRequest_Encoding_DefaultJson;
+    CallResponse, CallResponse_Encoding_DefaultBinary, CallResponse_Encoding_DefaultJson;
+    MonitoringFilter, MonitoringFilter_Encoding_DefaultBinary, MonitoringFilter_Encoding_DefaultJson;
+    DataChangeFilter, DataChangeFilter_Encoding_DefaultBinary, DataChangeFilter_Encoding_DefaultJson;
+    EventFilter, EventFilter_Encoding_DefaultBinary, EventFilter_Encoding_DefaultJson;
+    AggregateConfiguration, AggregateConfiguration_Encoding_DefaultBinary, AggregateConfiguration_Encoding_DefaultJson;
+    AggregateFilter, AggregateFilter_Encoding_DefaultBinary, AggregateFilter_Encoding_DefaultJson;
+    MonitoringFilterResult, MonitoringFilterResult_Encoding_DefaultBinary, MonitoringFilterResult_Encoding_DefaultJson;
+    EventFilterResult, EventFilterResult_Encoding_DefaultBinary, EventFilterResult_Encoding_DefaultJson;
+    AggregateFilterResult, AggregateFilterResult_Encoding_DefaultBinary, AggregateFilterResult_Encoding_DefaultJson;
+    MonitoringParameters, MonitoringParameters_Encoding_DefaultBinary, MonitoringParameters_Encoding_DefaultJson;
+    MonitoredItemCreateRequest, MonitoredItemCreateRequest_Encoding_DefaultBinary, MonitoredItemCreateRequest_Encoding_DefaultJson;
+    MonitoredItemCreateResult, MonitoredItemCreateResult_Encoding_DefaultBinary, MonitoredItemCreateResult_Encoding_DefaultJson;
+    CreateMonitoredItemsRequest, CreateMonitoredItemsRequest_Encoding_DefaultBinary, CreateMonitoredItemsRequest_Encoding_DefaultJson;
+    CreateMonitoredItemsResponse, CreateMonitoredItemsResponse_Encoding_DefaultBinary, CreateMonitoredItemsResponse_Encoding_DefaultJson;
+    MonitoredItemModifyRequest, MonitoredItemModifyRequest_Encoding_DefaultBinary, MonitoredItemModifyRequest_Encoding_DefaultJson;
+    MonitoredItemModifyResult, MonitoredItemModifyResult_Encoding_DefaultBinary, MonitoredItemModifyResult_Encoding_DefaultJson;
+    ModifyMonitoredItemsRequest, ModifyMonitoredItemsRequest_Encoding_DefaultBinary, ModifyMonitoredItemsRequest_Encoding_DefaultJson;
+    ModifyMonitoredItemsResponse, ModifyMonitoredItemsResponse_Encoding_DefaultBinary, ModifyMonitoredItemsResponse_Encoding_DefaultJson;
+    SetMonitoringModeRequest, SetMonitoringModeRequest_Encoding_DefaultBinary, SetMonitoringModeRequest_Encoding_DefaultJson;
+    SetMonitoringModeResponse, SetMonitoringModeResponse_Encoding_DefaultBinary, SetMonitoringModeResponse_Encoding_DefaultJson;
+    SetTriggeringRequest, SetTriggeringRequest_Encoding_DefaultBinary, SetTriggeringRequest_Encoding_DefaultJson;
+    SetTriggeringResponse, SetTriggeringResponse_Encoding_DefaultBinary, SetTriggeringResponse_Encoding_DefaultJson;
+    DeleteMonitoredItemsRequest, DeleteMonitoredItemsRequest_Encoding_DefaultBinary, DeleteMonitoredItemsRequest_Encoding_DefaultJson;
+    DeleteMonitoredItemsResponse, DeleteMonitoredItemsResponse_Encoding_DefaultBinary, DeleteMonitoredItemsResponse_Encoding_DefaultJson;
+    CreateSubscriptionRequest, CreateSubscriptionRequest_Encoding_DefaultBinary, CreateSubscriptionRequest_Encoding_DefaultJson;
+    CreateSubscriptionResponse, CreateSubscriptionResponse_Encoding_DefaultBinary, CreateSubscriptionResponse_Encoding_DefaultJson;
+    ModifySubscriptionRequest, ModifySubscriptionRequest_Encoding_DefaultBinary, ModifySubscriptionRequest_Encoding_DefaultJson;
+    ModifySubscriptionResponse, ModifySubscriptionResponse_Encoding_DefaultBinary, ModifySubscriptionResponse_Encoding_DefaultJson;
+    SetPublishingModeRequest, SetPublishingModeRequest_Encoding_DefaultBinary, SetPublishingModeRequest_Encoding_DefaultJson;
+    SetPublishingModeResponse, SetPublishingModeResponse_Encoding_DefaultBinary, SetPublishingModeResponse_Encoding_DefaultJson;
+    NotificationMessage, NotificationMessage_Encoding_DefaultBinary, NotificationMessage_Encoding_DefaultJson;
+    NotificationData, NotificationData_Encoding_DefaultBinary, NotificationData_Encoding_DefaultJson;
+    DataChangeNotification, DataChangeNotification_Encoding_DefaultBinary, DataChangeNotification_Encoding_DefaultJson;
+    MonitoredItemNotification, MonitoredItemNotification_Encoding_DefaultBinary, MonitoredItemNotification_Encoding_DefaultJson;
+    EventNotificationList, EventNotificationList_Encoding_DefaultBinary, EventNotificationList_Encoding_DefaultJson;
+    EventFieldList, EventFieldList_Encoding_DefaultBinary, EventFieldList_Encoding_DefaultJson;
+    HistoryEventFieldList, HistoryEventFieldList_Encoding_DefaultBinary, HistoryEventFieldList_Encoding_DefaultJson;
+    StatusChangeNotification, StatusChangeNotification_Encoding_DefaultBinary, StatusChangeNotification_Encoding_DefaultJson;
+    SubscriptionAcknowledgement, SubscriptionAcknowledgement_Encoding_DefaultBinary, SubscriptionAcknowledgement_Encoding_DefaultJson;
+    PublishRequest, PublishRequest_Encoding_DefaultBinary, PublishRequest_Encoding_DefaultJson;
+    PublishResponse, PublishResponse_Encoding_DefaultBinary, PublishResponse_Encoding_DefaultJson;
+    RepublishRequest, RepublishRequest_Encoding_DefaultBinary, RepublishRequest_Encoding_DefaultJson;
+    RepublishResponse, RepublishResponse_Encoding_DefaultBinary, RepublishResponse_Encoding_DefaultJson;
+    TransferResult, TransferResult_Encoding_DefaultBinary, TransferResult_Encoding_DefaultJson;
+    TransferSubscriptionsRequest, TransferSubscriptionsRequest_Encoding_DefaultBinary, TransferSubscriptionsRequest_Encoding_DefaultJson;
+    TransferSubscriptionsResponse, TransferSubscriptionsResponse_Encoding_DefaultBinary, TransferSubscriptionsResponse_Encoding_DefaultJson;
+    DeleteSubscriptionsRequest, DeleteSubscriptionsRequest_Encoding_DefaultBinary, DeleteSubscriptionsRequest_Encoding_DefaultJson;
+    DeleteSubscriptionsResponse, DeleteSubscriptionsResponse_Encoding_DefaultBinary, DeleteSubscriptionsResponse_Encoding_DefaultJson;
+    BuildInfo, BuildInfo_Encoding_DefaultBinary, BuildInfo_Encoding_DefaultJson;
+    RedundantServerDataType, RedundantServerDataType_Encoding_DefaultBinary, RedundantServerDataType_Encoding_DefaultJson;
+    EndpointUrlListDataType, EndpointUrlListDataType_Encoding_DefaultBinary, EndpointUrlListDataType_Encoding_DefaultJson;
+    NetworkGroupDataType, NetworkGroupDataType_Encoding_DefaultBinary, NetworkGroupDataType_Encoding_DefaultJson;
+    SamplingIntervalDiagnosticsDataType, SamplingIntervalDiagnosticsDataType_Encoding_DefaultBinary, SamplingIntervalDiagnosticsDataType_Encoding_DefaultJson;
+    ServerDiagnosticsSummaryDataType, ServerDiagnosticsSummaryDataType_Encoding_DefaultBinary, ServerDiagnosticsSummaryDataType_Encoding_DefaultJson;
+    ServerStatusDataType, ServerStatusDataType_Encoding_DefaultBinary, ServerStatusDataType_Encoding_DefaultJson;
+    SessionDiagnosticsDataType, SessionDiagnosticsDataType_Encoding_DefaultBinary, SessionDiagnosticsDataType_Encoding_DefaultJson;
+    SessionSecurityDiagnosticsDataType, SessionSecurityDiagnosticsDataType_Encoding_DefaultBinary, SessionSecurityDiagnosticsDataType_Encoding_DefaultJson;
+    ServiceCounterDataType, ServiceCounterDataType_Encoding_DefaultBinary, ServiceCounterDataType_Encoding_DefaultJson;
+    StatusResult, StatusResult_Encoding_DefaultBinary, StatusResult_Encoding_DefaultJson;
+    SubscriptionDiagnosticsDataType, SubscriptionDiagnosticsDataType_Encoding_DefaultBinary, SubscriptionDiagnosticsDataType_Encoding_DefaultJson;
+    ModelChangeStructureDataType, ModelChangeStructureDataType_Encoding_DefaultBinary, ModelChangeStructureDataType_Encoding_DefaultJson;
+    SemanticChangeStructureDataType, SemanticChangeStructureDataType_Encoding_DefaultBinary, SemanticChangeStructureDataType_Encoding_DefaultJson;
+    Range, Range_Encoding_DefaultBinary, Range_Encoding_DefaultJson;
+    EUInformation, EUInformation_Encoding_DefaultBinary, EUInformation_Encoding_DefaultJson;
+    ComplexNumberType, ComplexNumberType_Encoding_DefaultBinary, ComplexNumberType_Encoding_DefaultJson;
+    DoubleComplexNumberType, DoubleComplexNumberType_Encoding_DefaultBinary, DoubleComplexNumberType_Encoding_DefaultJson;
+    AxisInformation, AxisInformation_Encoding_DefaultBinary, AxisInformation_Encoding_DefaultJson;
+    XVType, XVType_Encoding_DefaultBinary, XVType_Encoding_DefaultJson;
+    ProgramDiagnosticDataType, ProgramDiagnosticDataType_Encoding_DefaultBinary, ProgramDiagnosticDataType_Encoding_DefaultJson;
+    ProgramDiagnostic2DataType, ProgramDiagnostic2DataType_Encoding_DefaultBinary, ProgramDiagnostic2DataType_Encoding_DefaultJson;
+    Annotation, Annotation_Encoding_DefaultBinary, Annotation_Encoding_DefaultJson;
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -59,3 +60,22 @@ impl BinaryEncoder<ModelChangeStructureDataType> for ModelChangeStructureDataTyp
         })
     }
 }
+
+impl JsonEncoder<ModelChangeStructureDataType> for ModelChangeStructureDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Affected", &self.affected, ctx);
+        write_json_field(&mut object, "AffectedType", &self.affected_type, ctx);
+        write_json_field(&mut object, "Verb", &self.verb, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ModelChangeStructureDataType {
+            affected: read_json_field(object, "Affected", decoding_limits)?,
+            affected_type: read_json_field(object, "AffectedType", decoding_limits)?,
+            verb: read_json_field(object, "Verb", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -61,3 +62,22 @@ impl BinaryEncoder<ModificationInfo> for ModificationInfo {
         })
     }
 }
+
+impl JsonEncoder<ModificationInfo> for ModificationInfo {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ModificationTime", &self.modification_time, ctx);
+        write_json_field(&mut object, "UpdateType", &self.update_type, ctx);
+        write_json_field(&mut object, "UserName", &self.user_name, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ModificationInfo {
+            modification_time: read_json_field(object, "ModificationTime", decoding_limits)?,
+            update_type: read_json_field(object, "UpdateType", decoding_limits)?,
+            user_name: read_json_field(object, "UserName", decoding_limits)?,
+        })
+    }
+}
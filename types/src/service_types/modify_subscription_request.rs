@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -79,3 +80,30 @@ impl BinaryEncoder<ModifySubscriptionRequest> for ModifySubscriptionRequest {
         })
     }
 }
+
+impl JsonEncoder<ModifySubscriptionRequest> for ModifySubscriptionRequest {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, ctx);
+        write_json_field(&mut object, "SubscriptionId", &self.subscription_id, ctx);
+        write_json_field(&mut object, "RequestedPublishingInterval", &self.requested_publishing_interval, ctx);
+        write_json_field(&mut object, "RequestedLifetimeCount", &self.requested_lifetime_count, ctx);
+        write_json_field(&mut object, "RequestedMaxKeepAliveCount", &self.requested_max_keep_alive_count, ctx);
+        write_json_field(&mut object, "MaxNotificationsPerPublish", &self.max_notifications_per_publish, ctx);
+        write_json_field(&mut object, "Priority", &self.priority, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ModifySubscriptionRequest {
+            request_header: read_json_field(object, "RequestHeader", decoding_limits)?,
+            subscription_id: read_json_field(object, "SubscriptionId", decoding_limits)?,
+            requested_publishing_interval: read_json_field(object, "RequestedPublishingInterval", decoding_limits)?,
+            requested_lifetime_count: read_json_field(object, "RequestedLifetimeCount", decoding_limits)?,
+            requested_max_keep_alive_count: read_json_field(object, "RequestedMaxKeepAliveCount", decoding_limits)?,
+            max_notifications_per_publish: read_json_field(object, "MaxNotificationsPerPublish", decoding_limits)?,
+            priority: read_json_field(object, "Priority", decoding_limits)?,
+        })
+    }
+}
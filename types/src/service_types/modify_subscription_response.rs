@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -64,3 +65,24 @@ impl BinaryEncoder<ModifySubscriptionResponse> for ModifySubscriptionResponse {
         })
     }
 }
+
+impl JsonEncoder<ModifySubscriptionResponse> for ModifySubscriptionResponse {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ResponseHeader", &self.response_header, ctx);
+        write_json_field(&mut object, "RevisedPublishingInterval", &self.revised_publishing_interval, ctx);
+        write_json_field(&mut object, "RevisedLifetimeCount", &self.revised_lifetime_count, ctx);
+        write_json_field(&mut object, "RevisedMaxKeepAliveCount", &self.revised_max_keep_alive_count, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ModifySubscriptionResponse {
+            response_header: read_json_field(object, "ResponseHeader", decoding_limits)?,
+            revised_publishing_interval: read_json_field(object, "RevisedPublishingInterval", decoding_limits)?,
+            revised_lifetime_count: read_json_field(object, "RevisedLifetimeCount", decoding_limits)?,
+            revised_max_keep_alive_count: read_json_field(object, "RevisedMaxKeepAliveCount", decoding_limits)?,
+        })
+    }
+}
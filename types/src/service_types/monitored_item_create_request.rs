@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -61,3 +62,22 @@ impl BinaryEncoder<MonitoredItemCreateRequest> for MonitoredItemCreateRequest {
         })
     }
 }
+
+impl JsonEncoder<MonitoredItemCreateRequest> for MonitoredItemCreateRequest {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ItemToMonitor", &self.item_to_monitor, ctx);
+        write_json_field(&mut object, "MonitoringMode", &self.monitoring_mode, ctx);
+        write_json_field(&mut object, "RequestedParameters", &self.requested_parameters, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(MonitoredItemCreateRequest {
+            item_to_monitor: read_json_field(object, "ItemToMonitor", decoding_limits)?,
+            monitoring_mode: read_json_field(object, "MonitoringMode", decoding_limits)?,
+            requested_parameters: read_json_field(object, "RequestedParameters", decoding_limits)?,
+        })
+    }
+}
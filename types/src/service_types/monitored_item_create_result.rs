@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -70,3 +71,26 @@ impl BinaryEncoder<MonitoredItemCreateResult> for MonitoredItemCreateResult {
         })
     }
 }
+
+impl JsonEncoder<MonitoredItemCreateResult> for MonitoredItemCreateResult {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "StatusCode", &self.status_code, ctx);
+        write_json_field(&mut object, "MonitoredItemId", &self.monitored_item_id, ctx);
+        write_json_field(&mut object, "RevisedSamplingInterval", &self.revised_sampling_interval, ctx);
+        write_json_field(&mut object, "RevisedQueueSize", &self.revised_queue_size, ctx);
+        write_json_field(&mut object, "FilterResult", &self.filter_result, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(MonitoredItemCreateResult {
+            status_code: read_json_field(object, "StatusCode", decoding_limits)?,
+            monitored_item_id: read_json_field(object, "MonitoredItemId", decoding_limits)?,
+            revised_sampling_interval: read_json_field(object, "RevisedSamplingInterval", decoding_limits)?,
+            revised_queue_size: read_json_field(object, "RevisedQueueSize", decoding_limits)?,
+            filter_result: read_json_field(object, "FilterResult", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -54,3 +55,20 @@ impl BinaryEncoder<MonitoredItemModifyRequest> for MonitoredItemModifyRequest {
         })
     }
 }
+
+impl JsonEncoder<MonitoredItemModifyRequest> for MonitoredItemModifyRequest {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "MonitoredItemId", &self.monitored_item_id, ctx);
+        write_json_field(&mut object, "RequestedParameters", &self.requested_parameters, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(MonitoredItemModifyRequest {
+            monitored_item_id: read_json_field(object, "MonitoredItemId", decoding_limits)?,
+            requested_parameters: read_json_field(object, "RequestedParameters", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -69,3 +70,26 @@ impl BinaryEncoder<MonitoringParameters> for MonitoringParameters {
         })
     }
 }
+
+impl JsonEncoder<MonitoringParameters> for MonitoringParameters {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ClientHandle", &self.client_handle, ctx);
+        write_json_field(&mut object, "SamplingInterval", &self.sampling_interval, ctx);
+        write_json_field(&mut object, "Filter", &self.filter, ctx);
+        write_json_field(&mut object, "QueueSize", &self.queue_size, ctx);
+        write_json_field(&mut object, "DiscardOldest", &self.discard_oldest, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(MonitoringParameters {
+            client_handle: read_json_field(object, "ClientHandle", decoding_limits)?,
+            sampling_interval: read_json_field(object, "SamplingInterval", decoding_limits)?,
+            filter: read_json_field(object, "Filter", decoding_limits)?,
+            queue_size: read_json_field(object, "QueueSize", decoding_limits)?,
+            discard_oldest: read_json_field(object, "DiscardOldest", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     string::UAString,
 };
@@ -46,3 +47,20 @@ impl BinaryEncoder<NetworkAddressUrlDataType> for NetworkAddressUrlDataType {
         })
     }
 }
+
+impl JsonEncoder<NetworkAddressUrlDataType> for NetworkAddressUrlDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NetworkInterface", &self.network_interface, ctx);
+        write_json_field(&mut object, "Url", &self.url, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(NetworkAddressUrlDataType {
+            network_interface: read_json_field(object, "NetworkInterface", decoding_limits)?,
+            url: read_json_field(object, "Url", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -55,3 +56,20 @@ impl BinaryEncoder<NetworkGroupDataType> for NetworkGroupDataType {
         })
     }
 }
+
+impl JsonEncoder<NetworkGroupDataType> for NetworkGroupDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ServerUri", &self.server_uri, ctx);
+        write_json_field(&mut object, "NetworkPaths", &self.network_paths, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(NetworkGroupDataType {
+            server_uri: read_json_field(object, "ServerUri", decoding_limits)?,
+            network_paths: read_json_field(object, "NetworkPaths", decoding_limits)?,
+        })
+    }
+}
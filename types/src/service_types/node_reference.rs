@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -64,3 +65,24 @@ impl BinaryEncoder<NodeReference> for NodeReference {
         })
     }
 }
+
+impl JsonEncoder<NodeReference> for NodeReference {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NodeId", &self.node_id, ctx);
+        write_json_field(&mut object, "ReferenceTypeId", &self.reference_type_id, ctx);
+        write_json_field(&mut object, "IsForward", &self.is_forward, ctx);
+        write_json_field(&mut object, "ReferencedNodeIds", &self.referenced_node_ids, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(NodeReference {
+            node_id: read_json_field(object, "NodeId", decoding_limits)?,
+            reference_type_id: read_json_field(object, "ReferenceTypeId", decoding_limits)?,
+            is_forward: read_json_field(object, "IsForward", decoding_limits)?,
+            referenced_node_ids: read_json_field(object, "ReferencedNodeIds", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -60,3 +61,22 @@ impl BinaryEncoder<NodeTypeDescription> for NodeTypeDescription {
         })
     }
 }
+
+impl JsonEncoder<NodeTypeDescription> for NodeTypeDescription {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "TypeDefinitionNode", &self.type_definition_node, ctx);
+        write_json_field(&mut object, "IncludeSubTypes", &self.include_sub_types, ctx);
+        write_json_field(&mut object, "DataToReturn", &self.data_to_return, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(NodeTypeDescription {
+            type_definition_node: read_json_field(object, "TypeDefinitionNode", decoding_limits)?,
+            include_sub_types: read_json_field(object, "IncludeSubTypes", decoding_limits)?,
+            data_to_return: read_json_field(object, "DataToReturn", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -60,3 +61,22 @@ impl BinaryEncoder<NotificationMessage> for NotificationMessage {
         })
     }
 }
+
+impl JsonEncoder<NotificationMessage> for NotificationMessage {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "SequenceNumber", &self.sequence_number, ctx);
+        write_json_field(&mut object, "PublishTime", &self.publish_time, ctx);
+        write_json_field(&mut object, "NotificationData", &self.notification_data, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(NotificationMessage {
+            sequence_number: read_json_field(object, "SequenceNumber", decoding_limits)?,
+            publish_time: read_json_field(object, "PublishTime", decoding_limits)?,
+            notification_data: read_json_field(object, "NotificationData", decoding_limits)?,
+        })
+    }
+}
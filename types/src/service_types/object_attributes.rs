@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     localized_text::LocalizedText,
 };
@@ -66,3 +67,28 @@ impl BinaryEncoder<ObjectAttributes> for ObjectAttributes {
         })
     }
 }
+
+impl JsonEncoder<ObjectAttributes> for ObjectAttributes {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "SpecifiedAttributes", &self.specified_attributes, ctx);
+        write_json_field(&mut object, "DisplayName", &self.display_name, ctx);
+        write_json_field(&mut object, "Description", &self.description, ctx);
+        write_json_field(&mut object, "WriteMask", &self.write_mask, ctx);
+        write_json_field(&mut object, "UserWriteMask", &self.user_write_mask, ctx);
+        write_json_field(&mut object, "EventNotifier", &self.event_notifier, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ObjectAttributes {
+            specified_attributes: read_json_field(object, "SpecifiedAttributes", decoding_limits)?,
+            display_name: read_json_field(object, "DisplayName", decoding_limits)?,
+            description: read_json_field(object, "Description", decoding_limits)?,
+            write_mask: read_json_field(object, "WriteMask", decoding_limits)?,
+            user_write_mask: read_json_field(object, "UserWriteMask", decoding_limits)?,
+            event_notifier: read_json_field(object, "EventNotifier", decoding_limits)?,
+        })
+    }
+}
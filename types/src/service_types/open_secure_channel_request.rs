@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -77,3 +78,28 @@ impl BinaryEncoder<OpenSecureChannelRequest> for OpenSecureChannelRequest {
         })
     }
 }
+
+impl JsonEncoder<OpenSecureChannelRequest> for OpenSecureChannelRequest {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, ctx);
+        write_json_field(&mut object, "ClientProtocolVersion", &self.client_protocol_version, ctx);
+        write_json_field(&mut object, "RequestType", &self.request_type, ctx);
+        write_json_field(&mut object, "SecurityMode", &self.security_mode, ctx);
+        write_json_field(&mut object, "ClientNonce", &self.client_nonce, ctx);
+        write_json_field(&mut object, "RequestedLifetime", &self.requested_lifetime, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(OpenSecureChannelRequest {
+            request_header: read_json_field(object, "RequestHeader", decoding_limits)?,
+            client_protocol_version: read_json_field(object, "ClientProtocolVersion", decoding_limits)?,
+            request_type: read_json_field(object, "RequestType", decoding_limits)?,
+            security_mode: read_json_field(object, "SecurityMode", decoding_limits)?,
+            client_nonce: read_json_field(object, "ClientNonce", decoding_limits)?,
+            requested_lifetime: read_json_field(object, "RequestedLifetime", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -66,3 +67,24 @@ impl BinaryEncoder<OpenSecureChannelResponse> for OpenSecureChannelResponse {
         })
     }
 }
+
+impl JsonEncoder<OpenSecureChannelResponse> for OpenSecureChannelResponse {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ResponseHeader", &self.response_header, ctx);
+        write_json_field(&mut object, "ServerProtocolVersion", &self.server_protocol_version, ctx);
+        write_json_field(&mut object, "SecurityToken", &self.security_token, ctx);
+        write_json_field(&mut object, "ServerNonce", &self.server_nonce, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(OpenSecureChannelResponse {
+            response_header: read_json_field(object, "ResponseHeader", decoding_limits)?,
+            server_protocol_version: read_json_field(object, "ServerProtocolVersion", decoding_limits)?,
+            security_token: read_json_field(object, "SecurityToken", decoding_limits)?,
+            server_nonce: read_json_field(object, "ServerNonce", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -54,3 +55,20 @@ impl BinaryEncoder<OptionSet> for OptionSet {
         })
     }
 }
+
+impl JsonEncoder<OptionSet> for OptionSet {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Value", &self.value, ctx);
+        write_json_field(&mut object, "ValidBits", &self.valid_bits, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(OptionSet {
+            value: read_json_field(object, "Value", decoding_limits)?,
+            valid_bits: read_json_field(object, "ValidBits", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -60,3 +61,22 @@ impl BinaryEncoder<ParsingResult> for ParsingResult {
         })
     }
 }
+
+impl JsonEncoder<ParsingResult> for ParsingResult {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "StatusCode", &self.status_code, ctx);
+        write_json_field(&mut object, "DataStatusCodes", &self.data_status_codes, ctx);
+        write_json_field(&mut object, "DataDiagnosticInfos", &self.data_diagnostic_infos, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ParsingResult {
+            status_code: read_json_field(object, "StatusCode", decoding_limits)?,
+            data_status_codes: read_json_field(object, "DataStatusCodes", decoding_limits)?,
+            data_diagnostic_infos: read_json_field(object, "DataDiagnosticInfos", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -109,3 +110,40 @@ impl BinaryEncoder<ProgramDiagnostic2DataType> for ProgramDiagnostic2DataType {
         })
     }
 }
+
+impl JsonEncoder<ProgramDiagnostic2DataType> for ProgramDiagnostic2DataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "CreateSessionId", &self.create_session_id, ctx);
+        write_json_field(&mut object, "CreateClientName", &self.create_client_name, ctx);
+        write_json_field(&mut object, "InvocationCreationTime", &self.invocation_creation_time, ctx);
+        write_json_field(&mut object, "LastTransitionTime", &self.last_transition_time, ctx);
+        write_json_field(&mut object, "LastMethodCall", &self.last_method_call, ctx);
+        write_json_field(&mut object, "LastMethodSessionId", &self.last_method_session_id, ctx);
+        write_json_field(&mut object, "LastMethodInputArguments", &self.last_method_input_arguments, ctx);
+        write_json_field(&mut object, "LastMethodOutputArguments", &self.last_method_output_arguments, ctx);
+        write_json_field(&mut object, "LastMethodInputValues", &self.last_method_input_values, ctx);
+        write_json_field(&mut object, "LastMethodOutputValues", &self.last_method_output_values, ctx);
+        write_json_field(&mut object, "LastMethodCallTime", &self.last_method_call_time, ctx);
+        write_json_field(&mut object, "LastMethodReturnStatus", &self.last_method_return_status, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ProgramDiagnostic2DataType {
+            create_session_id: read_json_field(object, "CreateSessionId", decoding_limits)?,
+            create_client_name: read_json_field(object, "CreateClientName", decoding_limits)?,
+            invocation_creation_time: read_json_field(object, "InvocationCreationTime", decoding_limits)?,
+            last_transition_time: read_json_field(object, "LastTransitionTime", decoding_limits)?,
+            last_method_call: read_json_field(object, "LastMethodCall", decoding_limits)?,
+            last_method_session_id: read_json_field(object, "LastMethodSessionId", decoding_limits)?,
+            last_method_input_arguments: read_json_field(object, "LastMethodInputArguments", decoding_limits)?,
+            last_method_output_arguments: read_json_field(object, "LastMethodOutputArguments", decoding_limits)?,
+            last_method_input_values: read_json_field(object, "LastMethodInputValues", decoding_limits)?,
+            last_method_output_values: read_json_field(object, "LastMethodOutputValues", decoding_limits)?,
+            last_method_call_time: read_json_field(object, "LastMethodCallTime", decoding_limits)?,
+            last_method_return_status: read_json_field(object, "LastMethodReturnStatus", decoding_limits)?,
+        })
+    }
+}
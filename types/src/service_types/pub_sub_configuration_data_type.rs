@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -60,3 +61,22 @@ impl BinaryEncoder<PubSubConfigurationDataType> for PubSubConfigurationDataType
         })
     }
 }
+
+impl JsonEncoder<PubSubConfigurationDataType> for PubSubConfigurationDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "PublishedDataSets", &self.published_data_sets, ctx);
+        write_json_field(&mut object, "Connections", &self.connections, ctx);
+        write_json_field(&mut object, "Enabled", &self.enabled, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(PubSubConfigurationDataType {
+            published_data_sets: read_json_field(object, "PublishedDataSets", decoding_limits)?,
+            connections: read_json_field(object, "Connections", decoding_limits)?,
+            enabled: read_json_field(object, "Enabled", decoding_limits)?,
+        })
+    }
+}
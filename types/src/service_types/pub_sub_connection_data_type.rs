@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -94,3 +95,34 @@ impl BinaryEncoder<PubSubConnectionDataType> for PubSubConnectionDataType {
         })
     }
 }
+
+impl JsonEncoder<PubSubConnectionDataType> for PubSubConnectionDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Name", &self.name, ctx);
+        write_json_field(&mut object, "Enabled", &self.enabled, ctx);
+        write_json_field(&mut object, "PublisherId", &self.publisher_id, ctx);
+        write_json_field(&mut object, "TransportProfileUri", &self.transport_profile_uri, ctx);
+        write_json_field(&mut object, "Address", &self.address, ctx);
+        write_json_field(&mut object, "ConnectionProperties", &self.connection_properties, ctx);
+        write_json_field(&mut object, "TransportSettings", &self.transport_settings, ctx);
+        write_json_field(&mut object, "WriterGroups", &self.writer_groups, ctx);
+        write_json_field(&mut object, "ReaderGroups", &self.reader_groups, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(PubSubConnectionDataType {
+            name: read_json_field(object, "Name", decoding_limits)?,
+            enabled: read_json_field(object, "Enabled", decoding_limits)?,
+            publisher_id: read_json_field(object, "PublisherId", decoding_limits)?,
+            transport_profile_uri: read_json_field(object, "TransportProfileUri", decoding_limits)?,
+            address: read_json_field(object, "Address", decoding_limits)?,
+            connection_properties: read_json_field(object, "ConnectionProperties", decoding_limits)?,
+            transport_settings: read_json_field(object, "TransportSettings", decoding_limits)?,
+            writer_groups: read_json_field(object, "WriterGroups", decoding_limits)?,
+            reader_groups: read_json_field(object, "ReaderGroups", decoding_limits)?,
+        })
+    }
+}
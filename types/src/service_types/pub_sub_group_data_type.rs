@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -82,3 +83,30 @@ impl BinaryEncoder<PubSubGroupDataType> for PubSubGroupDataType {
         })
     }
 }
+
+impl JsonEncoder<PubSubGroupDataType> for PubSubGroupDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Name", &self.name, ctx);
+        write_json_field(&mut object, "Enabled", &self.enabled, ctx);
+        write_json_field(&mut object, "SecurityMode", &self.security_mode, ctx);
+        write_json_field(&mut object, "SecurityGroupId", &self.security_group_id, ctx);
+        write_json_field(&mut object, "SecurityKeyServices", &self.security_key_services, ctx);
+        write_json_field(&mut object, "MaxNetworkMessageSize", &self.max_network_message_size, ctx);
+        write_json_field(&mut object, "GroupProperties", &self.group_properties, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(PubSubGroupDataType {
+            name: read_json_field(object, "Name", decoding_limits)?,
+            enabled: read_json_field(object, "Enabled", decoding_limits)?,
+            security_mode: read_json_field(object, "SecurityMode", decoding_limits)?,
+            security_group_id: read_json_field(object, "SecurityGroupId", decoding_limits)?,
+            security_key_services: read_json_field(object, "SecurityKeyServices", decoding_limits)?,
+            max_network_message_size: read_json_field(object, "MaxNetworkMessageSize", decoding_limits)?,
+            group_properties: read_json_field(object, "GroupProperties", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -55,3 +56,20 @@ impl BinaryEncoder<PublishRequest> for PublishRequest {
         })
     }
 }
+
+impl JsonEncoder<PublishRequest> for PublishRequest {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, ctx);
+        write_json_field(&mut object, "SubscriptionAcknowledgements", &self.subscription_acknowledgements, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(PublishRequest {
+            request_header: read_json_field(object, "RequestHeader", decoding_limits)?,
+            subscription_acknowledgements: read_json_field(object, "SubscriptionAcknowledgements", decoding_limits)?,
+        })
+    }
+}
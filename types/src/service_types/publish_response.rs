@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -82,3 +83,30 @@ impl BinaryEncoder<PublishResponse> for PublishResponse {
         })
     }
 }
+
+impl JsonEncoder<PublishResponse> for PublishResponse {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ResponseHeader", &self.response_header, ctx);
+        write_json_field(&mut object, "SubscriptionId", &self.subscription_id, ctx);
+        write_json_field(&mut object, "AvailableSequenceNumbers", &self.available_sequence_numbers, ctx);
+        write_json_field(&mut object, "MoreNotifications", &self.more_notifications, ctx);
+        write_json_field(&mut object, "NotificationMessage", &self.notification_message, ctx);
+        write_json_field(&mut object, "Results", &self.results, ctx);
+        write_json_field(&mut object, "DiagnosticInfos", &self.diagnostic_infos, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(PublishResponse {
+            response_header: read_json_field(object, "ResponseHeader", decoding_limits)?,
+            subscription_id: read_json_field(object, "SubscriptionId", decoding_limits)?,
+            available_sequence_numbers: read_json_field(object, "AvailableSequenceNumbers", decoding_limits)?,
+            more_notifications: read_json_field(object, "MoreNotifications", decoding_limits)?,
+            notification_message: read_json_field(object, "NotificationMessage", decoding_limits)?,
+            results: read_json_field(object, "Results", decoding_limits)?,
+            diagnostic_infos: read_json_field(object, "DiagnosticInfos", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::PublishedVariableDataType,
 };
@@ -41,3 +42,18 @@ impl BinaryEncoder<PublishedDataItemsDataType> for PublishedDataItemsDataType {
         })
     }
 }
+
+impl JsonEncoder<PublishedDataItemsDataType> for PublishedDataItemsDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "PublishedData", &self.published_data, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(PublishedDataItemsDataType {
+            published_data: read_json_field(object, "PublishedData", decoding_limits)?,
+        })
+    }
+}
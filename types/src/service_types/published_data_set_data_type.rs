@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -72,3 +73,26 @@ impl BinaryEncoder<PublishedDataSetDataType> for PublishedDataSetDataType {
         })
     }
 }
+
+impl JsonEncoder<PublishedDataSetDataType> for PublishedDataSetDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Name", &self.name, ctx);
+        write_json_field(&mut object, "DataSetFolder", &self.data_set_folder, ctx);
+        write_json_field(&mut object, "DataSetMetaData", &self.data_set_meta_data, ctx);
+        write_json_field(&mut object, "ExtensionFields", &self.extension_fields, ctx);
+        write_json_field(&mut object, "DataSetSource", &self.data_set_source, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(PublishedDataSetDataType {
+            name: read_json_field(object, "Name", decoding_limits)?,
+            data_set_folder: read_json_field(object, "DataSetFolder", decoding_limits)?,
+            data_set_meta_data: read_json_field(object, "DataSetMetaData", decoding_limits)?,
+            extension_fields: read_json_field(object, "ExtensionFields", decoding_limits)?,
+            data_set_source: read_json_field(object, "DataSetSource", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     node_id::NodeId,
     service_types::SimpleAttributeOperand,
@@ -53,3 +54,22 @@ impl BinaryEncoder<PublishedEventsDataType> for PublishedEventsDataType {
         })
     }
 }
+
+impl JsonEncoder<PublishedEventsDataType> for PublishedEventsDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "EventNotifier", &self.event_notifier, ctx);
+        write_json_field(&mut object, "SelectedFields", &self.selected_fields, ctx);
+        write_json_field(&mut object, "Filter", &self.filter, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(PublishedEventsDataType {
+            event_notifier: read_json_field(object, "EventNotifier", decoding_limits)?,
+            selected_fields: read_json_field(object, "SelectedFields", decoding_limits)?,
+            filter: read_json_field(object, "Filter", decoding_limits)?,
+        })
+    }
+}
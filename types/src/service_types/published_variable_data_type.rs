@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -87,3 +88,32 @@ impl BinaryEncoder<PublishedVariableDataType> for PublishedVariableDataType {
         })
     }
 }
+
+impl JsonEncoder<PublishedVariableDataType> for PublishedVariableDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "PublishedVariable", &self.published_variable, ctx);
+        write_json_field(&mut object, "AttributeId", &self.attribute_id, ctx);
+        write_json_field(&mut object, "SamplingIntervalHint", &self.sampling_interval_hint, ctx);
+        write_json_field(&mut object, "DeadbandType", &self.deadband_type, ctx);
+        write_json_field(&mut object, "DeadbandValue", &self.deadband_value, ctx);
+        write_json_field(&mut object, "IndexRange", &self.index_range, ctx);
+        write_json_field(&mut object, "SubstituteValue", &self.substitute_value, ctx);
+        write_json_field(&mut object, "MetaDataProperties", &self.meta_data_properties, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(PublishedVariableDataType {
+            published_variable: read_json_field(object, "PublishedVariable", decoding_limits)?,
+            attribute_id: read_json_field(object, "AttributeId", decoding_limits)?,
+            sampling_interval_hint: read_json_field(object, "SamplingIntervalHint", decoding_limits)?,
+            deadband_type: read_json_field(object, "DeadbandType", decoding_limits)?,
+            deadband_value: read_json_field(object, "DeadbandValue", decoding_limits)?,
+            index_range: read_json_field(object, "IndexRange", decoding_limits)?,
+            substitute_value: read_json_field(object, "SubstituteValue", decoding_limits)?,
+            meta_data_properties: read_json_field(object, "MetaDataProperties", decoding_limits)?,
+        })
+    }
+}
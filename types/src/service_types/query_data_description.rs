@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -60,3 +61,22 @@ impl BinaryEncoder<QueryDataDescription> for QueryDataDescription {
         })
     }
 }
+
+impl JsonEncoder<QueryDataDescription> for QueryDataDescription {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RelativePath", &self.relative_path, ctx);
+        write_json_field(&mut object, "AttributeId", &self.attribute_id, ctx);
+        write_json_field(&mut object, "IndexRange", &self.index_range, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(QueryDataDescription {
+            relative_path: read_json_field(object, "RelativePath", decoding_limits)?,
+            attribute_id: read_json_field(object, "AttributeId", decoding_limits)?,
+            index_range: read_json_field(object, "IndexRange", decoding_limits)?,
+        })
+    }
+}
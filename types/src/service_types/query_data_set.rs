@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -60,3 +61,22 @@ impl BinaryEncoder<QueryDataSet> for QueryDataSet {
         })
     }
 }
+
+impl JsonEncoder<QueryDataSet> for QueryDataSet {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NodeId", &self.node_id, ctx);
+        write_json_field(&mut object, "TypeDefinitionNode", &self.type_definition_node, ctx);
+        write_json_field(&mut object, "Values", &self.values, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(QueryDataSet {
+            node_id: read_json_field(object, "NodeId", decoding_limits)?,
+            type_definition_node: read_json_field(object, "TypeDefinitionNode", decoding_limits)?,
+            values: read_json_field(object, "Values", decoding_limits)?,
+        })
+    }
+}
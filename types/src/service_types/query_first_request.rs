@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -77,3 +78,28 @@ impl BinaryEncoder<QueryFirstRequest> for QueryFirstRequest {
         })
     }
 }
+
+impl JsonEncoder<QueryFirstRequest> for QueryFirstRequest {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, ctx);
+        write_json_field(&mut object, "View", &self.view, ctx);
+        write_json_field(&mut object, "NodeTypes", &self.node_types, ctx);
+        write_json_field(&mut object, "Filter", &self.filter, ctx);
+        write_json_field(&mut object, "MaxDataSetsToReturn", &self.max_data_sets_to_return, ctx);
+        write_json_field(&mut object, "MaxReferencesToReturn", &self.max_references_to_return, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(QueryFirstRequest {
+            request_header: read_json_field(object, "RequestHeader", decoding_limits)?,
+            view: read_json_field(object, "View", decoding_limits)?,
+            node_types: read_json_field(object, "NodeTypes", decoding_limits)?,
+            filter: read_json_field(object, "Filter", decoding_limits)?,
+            max_data_sets_to_return: read_json_field(object, "MaxDataSetsToReturn", decoding_limits)?,
+            max_references_to_return: read_json_field(object, "MaxReferencesToReturn", decoding_limits)?,
+        })
+    }
+}
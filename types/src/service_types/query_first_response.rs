@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -79,3 +80,28 @@ impl BinaryEncoder<QueryFirstResponse> for QueryFirstResponse {
         })
     }
 }
+
+impl JsonEncoder<QueryFirstResponse> for QueryFirstResponse {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ResponseHeader", &self.response_header, ctx);
+        write_json_field(&mut object, "QueryDataSets", &self.query_data_sets, ctx);
+        write_json_field(&mut object, "ContinuationPoint", &self.continuation_point, ctx);
+        write_json_field(&mut object, "ParsingResults", &self.parsing_results, ctx);
+        write_json_field(&mut object, "DiagnosticInfos", &self.diagnostic_infos, ctx);
+        write_json_field(&mut object, "FilterResult", &self.filter_result, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(QueryFirstResponse {
+            response_header: read_json_field(object, "ResponseHeader", decoding_limits)?,
+            query_data_sets: read_json_field(object, "QueryDataSets", decoding_limits)?,
+            continuation_point: read_json_field(object, "ContinuationPoint", decoding_limits)?,
+            parsing_results: read_json_field(object, "ParsingResults", decoding_limits)?,
+            diagnostic_infos: read_json_field(object, "DiagnosticInfos", decoding_limits)?,
+            filter_result: read_json_field(object, "FilterResult", decoding_limits)?,
+        })
+    }
+}
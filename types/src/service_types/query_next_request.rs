@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -60,3 +61,22 @@ impl BinaryEncoder<QueryNextRequest> for QueryNextRequest {
         })
     }
 }
+
+impl JsonEncoder<QueryNextRequest> for QueryNextRequest {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, ctx);
+        write_json_field(&mut object, "ReleaseContinuationPoint", &self.release_continuation_point, ctx);
+        write_json_field(&mut object, "ContinuationPoint", &self.continuation_point, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(QueryNextRequest {
+            request_header: read_json_field(object, "RequestHeader", decoding_limits)?,
+            release_continuation_point: read_json_field(object, "ReleaseContinuationPoint", decoding_limits)?,
+            continuation_point: read_json_field(object, "ContinuationPoint", decoding_limits)?,
+        })
+    }
+}
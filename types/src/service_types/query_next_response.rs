@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -61,3 +62,22 @@ impl BinaryEncoder<QueryNextResponse> for QueryNextResponse {
         })
     }
 }
+
+impl JsonEncoder<QueryNextResponse> for QueryNextResponse {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ResponseHeader", &self.response_header, ctx);
+        write_json_field(&mut object, "QueryDataSets", &self.query_data_sets, ctx);
+        write_json_field(&mut object, "RevisedContinuationPoint", &self.revised_continuation_point, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(QueryNextResponse {
+            response_header: read_json_field(object, "ResponseHeader", decoding_limits)?,
+            query_data_sets: read_json_field(object, "QueryDataSets", decoding_limits)?,
+            revised_continuation_point: read_json_field(object, "RevisedContinuationPoint", decoding_limits)?,
+        })
+    }
+}
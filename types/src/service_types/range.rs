@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -53,3 +54,20 @@ impl BinaryEncoder<Range> for Range {
         })
     }
 }
+
+impl JsonEncoder<Range> for Range {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Low", &self.low, ctx);
+        write_json_field(&mut object, "High", &self.high, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(Range {
+            low: read_json_field(object, "Low", decoding_limits)?,
+            high: read_json_field(object, "High", decoding_limits)?,
+        })
+    }
+}
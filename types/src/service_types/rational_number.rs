@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -53,3 +54,20 @@ impl BinaryEncoder<RationalNumber> for RationalNumber {
         })
     }
 }
+
+impl JsonEncoder<RationalNumber> for RationalNumber {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Numerator", &self.numerator, ctx);
+        write_json_field(&mut object, "Denominator", &self.denominator, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(RationalNumber {
+            numerator: read_json_field(object, "Numerator", decoding_limits)?,
+            denominator: read_json_field(object, "Denominator", decoding_limits)?,
+        })
+    }
+}
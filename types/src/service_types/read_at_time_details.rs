@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     date_time::DateTime,
 };
@@ -46,3 +47,20 @@ impl BinaryEncoder<ReadAtTimeDetails> for ReadAtTimeDetails {
         })
     }
 }
+
+impl JsonEncoder<ReadAtTimeDetails> for ReadAtTimeDetails {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ReqTimes", &self.req_times, ctx);
+        write_json_field(&mut object, "UseSimpleBounds", &self.use_simple_bounds, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ReadAtTimeDetails {
+            req_times: read_json_field(object, "ReqTimes", decoding_limits)?,
+            use_simple_bounds: read_json_field(object, "UseSimpleBounds", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     date_time::DateTime,
     service_types::EventFilter,
@@ -57,3 +58,24 @@ impl BinaryEncoder<ReadEventDetails> for ReadEventDetails {
         })
     }
 }
+
+impl JsonEncoder<ReadEventDetails> for ReadEventDetails {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NumValuesPerNode", &self.num_values_per_node, ctx);
+        write_json_field(&mut object, "StartTime", &self.start_time, ctx);
+        write_json_field(&mut object, "EndTime", &self.end_time, ctx);
+        write_json_field(&mut object, "Filter", &self.filter, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ReadEventDetails {
+            num_values_per_node: read_json_field(object, "NumValuesPerNode", decoding_limits)?,
+            start_time: read_json_field(object, "StartTime", decoding_limits)?,
+            end_time: read_json_field(object, "EndTime", decoding_limits)?,
+            filter: read_json_field(object, "Filter", decoding_limits)?,
+        })
+    }
+}
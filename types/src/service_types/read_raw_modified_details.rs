@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     date_time::DateTime,
 };
@@ -61,3 +62,26 @@ impl BinaryEncoder<ReadRawModifiedDetails> for ReadRawModifiedDetails {
         })
     }
 }
+
+impl JsonEncoder<ReadRawModifiedDetails> for ReadRawModifiedDetails {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "IsReadModified", &self.is_read_modified, ctx);
+        write_json_field(&mut object, "StartTime", &self.start_time, ctx);
+        write_json_field(&mut object, "EndTime", &self.end_time, ctx);
+        write_json_field(&mut object, "NumValuesPerNode", &self.num_values_per_node, ctx);
+        write_json_field(&mut object, "ReturnBounds", &self.return_bounds, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ReadRawModifiedDetails {
+            is_read_modified: read_json_field(object, "IsReadModified", decoding_limits)?,
+            start_time: read_json_field(object, "StartTime", decoding_limits)?,
+            end_time: read_json_field(object, "EndTime", decoding_limits)?,
+            num_values_per_node: read_json_field(object, "NumValuesPerNode", decoding_limits)?,
+            return_bounds: read_json_field(object, "ReturnBounds", decoding_limits)?,
+        })
+    }
+}
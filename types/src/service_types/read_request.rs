@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -66,3 +67,24 @@ impl BinaryEncoder<ReadRequest> for ReadRequest {
         })
     }
 }
+
+impl JsonEncoder<ReadRequest> for ReadRequest {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, ctx);
+        write_json_field(&mut object, "MaxAge", &self.max_age, ctx);
+        write_json_field(&mut object, "TimestampsToReturn", &self.timestamps_to_return, ctx);
+        write_json_field(&mut object, "NodesToRead", &self.nodes_to_read, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ReadRequest {
+            request_header: read_json_field(object, "RequestHeader", decoding_limits)?,
+            max_age: read_json_field(object, "MaxAge", decoding_limits)?,
+            timestamps_to_return: read_json_field(object, "TimestampsToReturn", decoding_limits)?,
+            nodes_to_read: read_json_field(object, "NodesToRead", decoding_limits)?,
+        })
+    }
+}
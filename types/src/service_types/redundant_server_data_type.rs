@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -60,3 +61,22 @@ impl BinaryEncoder<RedundantServerDataType> for RedundantServerDataType {
         })
     }
 }
+
+impl JsonEncoder<RedundantServerDataType> for RedundantServerDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ServerId", &self.server_id, ctx);
+        write_json_field(&mut object, "ServiceLevel", &self.service_level, ctx);
+        write_json_field(&mut object, "ServerState", &self.server_state, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(RedundantServerDataType {
+            server_id: read_json_field(object, "ServerId", decoding_limits)?,
+            service_level: read_json_field(object, "ServiceLevel", decoding_limits)?,
+            server_state: read_json_field(object, "ServerState", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -83,3 +84,30 @@ impl BinaryEncoder<ReferenceDescription> for ReferenceDescription {
         })
     }
 }
+
+impl JsonEncoder<ReferenceDescription> for ReferenceDescription {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ReferenceTypeId", &self.reference_type_id, ctx);
+        write_json_field(&mut object, "IsForward", &self.is_forward, ctx);
+        write_json_field(&mut object, "NodeId", &self.node_id, ctx);
+        write_json_field(&mut object, "BrowseName", &self.browse_name, ctx);
+        write_json_field(&mut object, "DisplayName", &self.display_name, ctx);
+        write_json_field(&mut object, "NodeClass", &self.node_class, ctx);
+        write_json_field(&mut object, "TypeDefinition", &self.type_definition, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ReferenceDescription {
+            reference_type_id: read_json_field(object, "ReferenceTypeId", decoding_limits)?,
+            is_forward: read_json_field(object, "IsForward", decoding_limits)?,
+            node_id: read_json_field(object, "NodeId", decoding_limits)?,
+            browse_name: read_json_field(object, "BrowseName", decoding_limits)?,
+            display_name: read_json_field(object, "DisplayName", decoding_limits)?,
+            node_class: read_json_field(object, "NodeClass", decoding_limits)?,
+            type_definition: read_json_field(object, "TypeDefinition", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -61,3 +62,22 @@ impl BinaryEncoder<RegisterServer2Request> for RegisterServer2Request {
         })
     }
 }
+
+impl JsonEncoder<RegisterServer2Request> for RegisterServer2Request {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, ctx);
+        write_json_field(&mut object, "Server", &self.server, ctx);
+        write_json_field(&mut object, "DiscoveryConfiguration", &self.discovery_configuration, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(RegisterServer2Request {
+            request_header: read_json_field(object, "RequestHeader", decoding_limits)?,
+            server: read_json_field(object, "Server", decoding_limits)?,
+            discovery_configuration: read_json_field(object, "DiscoveryConfiguration", decoding_limits)?,
+        })
+    }
+}
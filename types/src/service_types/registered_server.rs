@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -86,3 +87,32 @@ impl BinaryEncoder<RegisteredServer> for RegisteredServer {
         })
     }
 }
+
+impl JsonEncoder<RegisteredServer> for RegisteredServer {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ServerUri", &self.server_uri, ctx);
+        write_json_field(&mut object, "ProductUri", &self.product_uri, ctx);
+        write_json_field(&mut object, "ServerNames", &self.server_names, ctx);
+        write_json_field(&mut object, "ServerType", &self.server_type, ctx);
+        write_json_field(&mut object, "GatewayServerUri", &self.gateway_server_uri, ctx);
+        write_json_field(&mut object, "DiscoveryUrls", &self.discovery_urls, ctx);
+        write_json_field(&mut object, "SemaphoreFilePath", &self.semaphore_file_path, ctx);
+        write_json_field(&mut object, "IsOnline", &self.is_online, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(RegisteredServer {
+            server_uri: read_json_field(object, "ServerUri", decoding_limits)?,
+            product_uri: read_json_field(object, "ProductUri", decoding_limits)?,
+            server_names: read_json_field(object, "ServerNames", decoding_limits)?,
+            server_type: read_json_field(object, "ServerType", decoding_limits)?,
+            gateway_server_uri: read_json_field(object, "GatewayServerUri", decoding_limits)?,
+            discovery_urls: read_json_field(object, "DiscoveryUrls", decoding_limits)?,
+            semaphore_file_path: read_json_field(object, "SemaphoreFilePath", decoding_limits)?,
+            is_online: read_json_field(object, "IsOnline", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -65,3 +66,24 @@ impl BinaryEncoder<RelativePathElement> for RelativePathElement {
         })
     }
 }
+
+impl JsonEncoder<RelativePathElement> for RelativePathElement {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ReferenceTypeId", &self.reference_type_id, ctx);
+        write_json_field(&mut object, "IsInverse", &self.is_inverse, ctx);
+        write_json_field(&mut object, "IncludeSubtypes", &self.include_subtypes, ctx);
+        write_json_field(&mut object, "TargetName", &self.target_name, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(RelativePathElement {
+            reference_type_id: read_json_field(object, "ReferenceTypeId", decoding_limits)?,
+            is_inverse: read_json_field(object, "IsInverse", decoding_limits)?,
+            include_subtypes: read_json_field(object, "IncludeSubtypes", decoding_limits)?,
+            target_name: read_json_field(object, "TargetName", decoding_limits)?,
+        })
+    }
+}
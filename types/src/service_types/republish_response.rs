@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -55,3 +56,20 @@ impl BinaryEncoder<RepublishResponse> for RepublishResponse {
         })
     }
 }
+
+impl JsonEncoder<RepublishResponse> for RepublishResponse {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ResponseHeader", &self.response_header, ctx);
+        write_json_field(&mut object, "NotificationMessage", &self.notification_message, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(RepublishResponse {
+            response_header: read_json_field(object, "ResponseHeader", decoding_limits)?,
+            notification_message: read_json_field(object, "NotificationMessage", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -55,3 +56,20 @@ impl BinaryEncoder<RolePermissionType> for RolePermissionType {
         })
     }
 }
+
+impl JsonEncoder<RolePermissionType> for RolePermissionType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RoleId", &self.role_id, ctx);
+        write_json_field(&mut object, "Permissions", &self.permissions, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(RolePermissionType {
+            role_id: read_json_field(object, "RoleId", decoding_limits)?,
+            permissions: read_json_field(object, "Permissions", decoding_limits)?,
+        })
+    }
+}
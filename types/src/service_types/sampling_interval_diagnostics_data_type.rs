@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -63,3 +64,24 @@ impl BinaryEncoder<SamplingIntervalDiagnosticsDataType> for SamplingIntervalDiag
         })
     }
 }
+
+impl JsonEncoder<SamplingIntervalDiagnosticsDataType> for SamplingIntervalDiagnosticsDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "SamplingInterval", &self.sampling_interval, ctx);
+        write_json_field(&mut object, "MonitoredItemCount", &self.monitored_item_count, ctx);
+        write_json_field(&mut object, "MaxMonitoredItemCount", &self.max_monitored_item_count, ctx);
+        write_json_field(&mut object, "DisabledMonitoredItemCount", &self.disabled_monitored_item_count, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(SamplingIntervalDiagnosticsDataType {
+            sampling_interval: read_json_field(object, "SamplingInterval", decoding_limits)?,
+            monitored_item_count: read_json_field(object, "MonitoredItemCount", decoding_limits)?,
+            max_monitored_item_count: read_json_field(object, "MaxMonitoredItemCount", decoding_limits)?,
+            disabled_monitored_item_count: read_json_field(object, "DisabledMonitoredItemCount", decoding_limits)?,
+        })
+    }
+}
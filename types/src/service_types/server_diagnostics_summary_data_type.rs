@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -103,3 +104,40 @@ impl BinaryEncoder<ServerDiagnosticsSummaryDataType> for ServerDiagnosticsSummar
         })
     }
 }
+
+impl JsonEncoder<ServerDiagnosticsSummaryDataType> for ServerDiagnosticsSummaryDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ServerViewCount", &self.server_view_count, ctx);
+        write_json_field(&mut object, "CurrentSessionCount", &self.current_session_count, ctx);
+        write_json_field(&mut object, "CumulatedSessionCount", &self.cumulated_session_count, ctx);
+        write_json_field(&mut object, "SecurityRejectedSessionCount", &self.security_rejected_session_count, ctx);
+        write_json_field(&mut object, "RejectedSessionCount", &self.rejected_session_count, ctx);
+        write_json_field(&mut object, "SessionTimeoutCount", &self.session_timeout_count, ctx);
+        write_json_field(&mut object, "SessionAbortCount", &self.session_abort_count, ctx);
+        write_json_field(&mut object, "CurrentSubscriptionCount", &self.current_subscription_count, ctx);
+        write_json_field(&mut object, "CumulatedSubscriptionCount", &self.cumulated_subscription_count, ctx);
+        write_json_field(&mut object, "PublishingIntervalCount", &self.publishing_interval_count, ctx);
+        write_json_field(&mut object, "SecurityRejectedRequestsCount", &self.security_rejected_requests_count, ctx);
+        write_json_field(&mut object, "RejectedRequestsCount", &self.rejected_requests_count, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ServerDiagnosticsSummaryDataType {
+            server_view_count: read_json_field(object, "ServerViewCount", decoding_limits)?,
+            current_session_count: read_json_field(object, "CurrentSessionCount", decoding_limits)?,
+            cumulated_session_count: read_json_field(object, "CumulatedSessionCount", decoding_limits)?,
+            security_rejected_session_count: read_json_field(object, "SecurityRejectedSessionCount", decoding_limits)?,
+            rejected_session_count: read_json_field(object, "RejectedSessionCount", decoding_limits)?,
+            session_timeout_count: read_json_field(object, "SessionTimeoutCount", decoding_limits)?,
+            session_abort_count: read_json_field(object, "SessionAbortCount", decoding_limits)?,
+            current_subscription_count: read_json_field(object, "CurrentSubscriptionCount", decoding_limits)?,
+            cumulated_subscription_count: read_json_field(object, "CumulatedSubscriptionCount", decoding_limits)?,
+            publishing_interval_count: read_json_field(object, "PublishingIntervalCount", decoding_limits)?,
+            security_rejected_requests_count: read_json_field(object, "SecurityRejectedRequestsCount", decoding_limits)?,
+            rejected_requests_count: read_json_field(object, "RejectedRequestsCount", decoding_limits)?,
+        })
+    }
+}
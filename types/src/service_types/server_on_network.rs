@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -64,3 +65,24 @@ impl BinaryEncoder<ServerOnNetwork> for ServerOnNetwork {
         })
     }
 }
+
+impl JsonEncoder<ServerOnNetwork> for ServerOnNetwork {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RecordId", &self.record_id, ctx);
+        write_json_field(&mut object, "ServerName", &self.server_name, ctx);
+        write_json_field(&mut object, "DiscoveryUrl", &self.discovery_url, ctx);
+        write_json_field(&mut object, "ServerCapabilities", &self.server_capabilities, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ServerOnNetwork {
+            record_id: read_json_field(object, "RecordId", decoding_limits)?,
+            server_name: read_json_field(object, "ServerName", decoding_limits)?,
+            discovery_url: read_json_field(object, "DiscoveryUrl", decoding_limits)?,
+            server_capabilities: read_json_field(object, "ServerCapabilities", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -77,3 +78,28 @@ impl BinaryEncoder<ServerStatusDataType> for ServerStatusDataType {
         })
     }
 }
+
+impl JsonEncoder<ServerStatusDataType> for ServerStatusDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "StartTime", &self.start_time, ctx);
+        write_json_field(&mut object, "CurrentTime", &self.current_time, ctx);
+        write_json_field(&mut object, "State", &self.state, ctx);
+        write_json_field(&mut object, "BuildInfo", &self.build_info, ctx);
+        write_json_field(&mut object, "SecondsTillShutdown", &self.seconds_till_shutdown, ctx);
+        write_json_field(&mut object, "ShutdownReason", &self.shutdown_reason, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ServerStatusDataType {
+            start_time: read_json_field(object, "StartTime", decoding_limits)?,
+            current_time: read_json_field(object, "CurrentTime", decoding_limits)?,
+            state: read_json_field(object, "State", decoding_limits)?,
+            build_info: read_json_field(object, "BuildInfo", decoding_limits)?,
+            seconds_till_shutdown: read_json_field(object, "SecondsTillShutdown", decoding_limits)?,
+            shutdown_reason: read_json_field(object, "ShutdownReason", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -53,3 +54,20 @@ impl BinaryEncoder<ServiceCounterDataType> for ServiceCounterDataType {
         })
     }
 }
+
+impl JsonEncoder<ServiceCounterDataType> for ServiceCounterDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "TotalCount", &self.total_count, ctx);
+        write_json_field(&mut object, "ErrorCount", &self.error_count, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ServiceCounterDataType {
+            total_count: read_json_field(object, "TotalCount", decoding_limits)?,
+            error_count: read_json_field(object, "ErrorCount", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -92,3 +93,34 @@ impl BinaryEncoder<SessionSecurityDiagnosticsDataType> for SessionSecurityDiagno
         })
     }
 }
+
+impl JsonEncoder<SessionSecurityDiagnosticsDataType> for SessionSecurityDiagnosticsDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "SessionId", &self.session_id, ctx);
+        write_json_field(&mut object, "ClientUserIdOfSession", &self.client_user_id_of_session, ctx);
+        write_json_field(&mut object, "ClientUserIdHistory", &self.client_user_id_history, ctx);
+        write_json_field(&mut object, "AuthenticationMechanism", &self.authentication_mechanism, ctx);
+        write_json_field(&mut object, "Encoding", &self.encoding, ctx);
+        write_json_field(&mut object, "TransportProtocol", &self.transport_protocol, ctx);
+        write_json_field(&mut object, "SecurityMode", &self.security_mode, ctx);
+        write_json_field(&mut object, "SecurityPolicyUri", &self.security_policy_uri, ctx);
+        write_json_field(&mut object, "ClientCertificate", &self.client_certificate, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(SessionSecurityDiagnosticsDataType {
+            session_id: read_json_field(object, "SessionId", decoding_limits)?,
+            client_user_id_of_session: read_json_field(object, "ClientUserIdOfSession", decoding_limits)?,
+            client_user_id_history: read_json_field(object, "ClientUserIdHistory", decoding_limits)?,
+            authentication_mechanism: read_json_field(object, "AuthenticationMechanism", decoding_limits)?,
+            encoding: read_json_field(object, "Encoding", decoding_limits)?,
+            transport_protocol: read_json_field(object, "TransportProtocol", decoding_limits)?,
+            security_mode: read_json_field(object, "SecurityMode", decoding_limits)?,
+            security_policy_uri: read_json_field(object, "SecurityPolicyUri", decoding_limits)?,
+            client_certificate: read_json_field(object, "ClientCertificate", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -69,3 +70,26 @@ impl BinaryEncoder<SessionlessInvokeRequestType> for SessionlessInvokeRequestTyp
         })
     }
 }
+
+impl JsonEncoder<SessionlessInvokeRequestType> for SessionlessInvokeRequestType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "UrisVersion", &self.uris_version, ctx);
+        write_json_field(&mut object, "NamespaceUris", &self.namespace_uris, ctx);
+        write_json_field(&mut object, "ServerUris", &self.server_uris, ctx);
+        write_json_field(&mut object, "LocaleIds", &self.locale_ids, ctx);
+        write_json_field(&mut object, "ServiceId", &self.service_id, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(SessionlessInvokeRequestType {
+            uris_version: read_json_field(object, "UrisVersion", decoding_limits)?,
+            namespace_uris: read_json_field(object, "NamespaceUris", decoding_limits)?,
+            server_uris: read_json_field(object, "ServerUris", decoding_limits)?,
+            locale_ids: read_json_field(object, "LocaleIds", decoding_limits)?,
+            service_id: read_json_field(object, "ServiceId", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -59,3 +60,22 @@ impl BinaryEncoder<SessionlessInvokeResponseType> for SessionlessInvokeResponseT
         })
     }
 }
+
+impl JsonEncoder<SessionlessInvokeResponseType> for SessionlessInvokeResponseType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NamespaceUris", &self.namespace_uris, ctx);
+        write_json_field(&mut object, "ServerUris", &self.server_uris, ctx);
+        write_json_field(&mut object, "ServiceId", &self.service_id, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(SessionlessInvokeResponseType {
+            namespace_uris: read_json_field(object, "NamespaceUris", decoding_limits)?,
+            server_uris: read_json_field(object, "ServerUris", decoding_limits)?,
+            service_id: read_json_field(object, "ServiceId", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -65,3 +66,24 @@ impl BinaryEncoder<SetMonitoringModeRequest> for SetMonitoringModeRequest {
         })
     }
 }
+
+impl JsonEncoder<SetMonitoringModeRequest> for SetMonitoringModeRequest {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, ctx);
+        write_json_field(&mut object, "SubscriptionId", &self.subscription_id, ctx);
+        write_json_field(&mut object, "MonitoringMode", &self.monitoring_mode, ctx);
+        write_json_field(&mut object, "MonitoredItemIds", &self.monitored_item_ids, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(SetMonitoringModeRequest {
+            request_header: read_json_field(object, "RequestHeader", decoding_limits)?,
+            subscription_id: read_json_field(object, "SubscriptionId", decoding_limits)?,
+            monitoring_mode: read_json_field(object, "MonitoringMode", decoding_limits)?,
+            monitored_item_ids: read_json_field(object, "MonitoredItemIds", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -59,3 +60,22 @@ impl BinaryEncoder<SetPublishingModeRequest> for SetPublishingModeRequest {
         })
     }
 }
+
+impl JsonEncoder<SetPublishingModeRequest> for SetPublishingModeRequest {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, ctx);
+        write_json_field(&mut object, "PublishingEnabled", &self.publishing_enabled, ctx);
+        write_json_field(&mut object, "SubscriptionIds", &self.subscription_ids, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(SetPublishingModeRequest {
+            request_header: read_json_field(object, "RequestHeader", decoding_limits)?,
+            publishing_enabled: read_json_field(object, "PublishingEnabled", decoding_limits)?,
+            subscription_ids: read_json_field(object, "SubscriptionIds", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -69,3 +70,26 @@ impl BinaryEncoder<SetTriggeringRequest> for SetTriggeringRequest {
         })
     }
 }
+
+impl JsonEncoder<SetTriggeringRequest> for SetTriggeringRequest {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "RequestHeader", &self.request_header, ctx);
+        write_json_field(&mut object, "SubscriptionId", &self.subscription_id, ctx);
+        write_json_field(&mut object, "TriggeringItemId", &self.triggering_item_id, ctx);
+        write_json_field(&mut object, "LinksToAdd", &self.links_to_add, ctx);
+        write_json_field(&mut object, "LinksToRemove", &self.links_to_remove, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(SetTriggeringRequest {
+            request_header: read_json_field(object, "RequestHeader", decoding_limits)?,
+            subscription_id: read_json_field(object, "SubscriptionId", decoding_limits)?,
+            triggering_item_id: read_json_field(object, "TriggeringItemId", decoding_limits)?,
+            links_to_add: read_json_field(object, "LinksToAdd", decoding_limits)?,
+            links_to_remove: read_json_field(object, "LinksToRemove", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -71,3 +72,26 @@ impl BinaryEncoder<SetTriggeringResponse> for SetTriggeringResponse {
         })
     }
 }
+
+impl JsonEncoder<SetTriggeringResponse> for SetTriggeringResponse {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ResponseHeader", &self.response_header, ctx);
+        write_json_field(&mut object, "AddResults", &self.add_results, ctx);
+        write_json_field(&mut object, "AddDiagnosticInfos", &self.add_diagnostic_infos, ctx);
+        write_json_field(&mut object, "RemoveResults", &self.remove_results, ctx);
+        write_json_field(&mut object, "RemoveDiagnosticInfos", &self.remove_diagnostic_infos, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(SetTriggeringResponse {
+            response_header: read_json_field(object, "ResponseHeader", decoding_limits)?,
+            add_results: read_json_field(object, "AddResults", decoding_limits)?,
+            add_diagnostic_infos: read_json_field(object, "AddDiagnosticInfos", decoding_limits)?,
+            remove_results: read_json_field(object, "RemoveResults", decoding_limits)?,
+            remove_diagnostic_infos: read_json_field(object, "RemoveDiagnosticInfos", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -55,3 +56,20 @@ impl BinaryEncoder<SignatureData> for SignatureData {
         })
     }
 }
+
+impl JsonEncoder<SignatureData> for SignatureData {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Algorithm", &self.algorithm, ctx);
+        write_json_field(&mut object, "Signature", &self.signature, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(SignatureData {
+            algorithm: read_json_field(object, "Algorithm", decoding_limits)?,
+            signature: read_json_field(object, "Signature", decoding_limits)?,
+        })
+    }
+}
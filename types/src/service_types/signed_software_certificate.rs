@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -54,3 +55,20 @@ impl BinaryEncoder<SignedSoftwareCertificate> for SignedSoftwareCertificate {
         })
     }
 }
+
+impl JsonEncoder<SignedSoftwareCertificate> for SignedSoftwareCertificate {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "CertificateData", &self.certificate_data, ctx);
+        write_json_field(&mut object, "Signature", &self.signature, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(SignedSoftwareCertificate {
+            certificate_data: read_json_field(object, "CertificateData", decoding_limits)?,
+            signature: read_json_field(object, "Signature", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     node_id::NodeId,
     qualified_name::QualifiedName,
@@ -58,3 +59,24 @@ impl BinaryEncoder<SimpleAttributeOperand> for SimpleAttributeOperand {
         })
     }
 }
+
+impl JsonEncoder<SimpleAttributeOperand> for SimpleAttributeOperand {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "TypeDefinitionId", &self.type_definition_id, ctx);
+        write_json_field(&mut object, "BrowsePath", &self.browse_path, ctx);
+        write_json_field(&mut object, "AttributeId", &self.attribute_id, ctx);
+        write_json_field(&mut object, "IndexRange", &self.index_range, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(SimpleAttributeOperand {
+            type_definition_id: read_json_field(object, "TypeDefinitionId", decoding_limits)?,
+            browse_path: read_json_field(object, "BrowsePath", decoding_limits)?,
+            attribute_id: read_json_field(object, "AttributeId", decoding_limits)?,
+            index_range: read_json_field(object, "IndexRange", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     status_codes::StatusCode,
     diagnostic_info::DiagnosticInfo,
@@ -47,3 +48,20 @@ impl BinaryEncoder<StatusChangeNotification> for StatusChangeNotification {
         })
     }
 }
+
+impl JsonEncoder<StatusChangeNotification> for StatusChangeNotification {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Status", &self.status, ctx);
+        write_json_field(&mut object, "DiagnosticInfo", &self.diagnostic_info, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(StatusChangeNotification {
+            status: read_json_field(object, "Status", decoding_limits)?,
+            diagnostic_info: read_json_field(object, "DiagnosticInfo", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -55,3 +56,20 @@ impl BinaryEncoder<StatusResult> for StatusResult {
         })
     }
 }
+
+impl JsonEncoder<StatusResult> for StatusResult {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "StatusCode", &self.status_code, ctx);
+        write_json_field(&mut object, "DiagnosticInfo", &self.diagnostic_info, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(StatusResult {
+            status_code: read_json_field(object, "StatusCode", decoding_limits)?,
+            diagnostic_info: read_json_field(object, "DiagnosticInfo", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     node_id::NodeId,
     service_types::enums::StructureType,
@@ -58,3 +59,24 @@ impl BinaryEncoder<StructureDefinition> for StructureDefinition {
         })
     }
 }
+
+impl JsonEncoder<StructureDefinition> for StructureDefinition {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "DefaultEncodingId", &self.default_encoding_id, ctx);
+        write_json_field(&mut object, "BaseDataType", &self.base_data_type, ctx);
+        write_json_field(&mut object, "StructureType", &self.structure_type, ctx);
+        write_json_field(&mut object, "Fields", &self.fields, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(StructureDefinition {
+            default_encoding_id: read_json_field(object, "DefaultEncodingId", decoding_limits)?,
+            base_data_type: read_json_field(object, "BaseDataType", decoding_limits)?,
+            structure_type: read_json_field(object, "StructureType", decoding_limits)?,
+            fields: read_json_field(object, "Fields", decoding_limits)?,
+        })
+    }
+}
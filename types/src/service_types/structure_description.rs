@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     node_id::NodeId,
     qualified_name::QualifiedName,
@@ -53,3 +54,22 @@ impl BinaryEncoder<StructureDescription> for StructureDescription {
         })
     }
 }
+
+impl JsonEncoder<StructureDescription> for StructureDescription {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "DataTypeId", &self.data_type_id, ctx);
+        write_json_field(&mut object, "Name", &self.name, ctx);
+        write_json_field(&mut object, "StructureDefinition", &self.structure_definition, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(StructureDescription {
+            data_type_id: read_json_field(object, "DataTypeId", decoding_limits)?,
+            name: read_json_field(object, "Name", decoding_limits)?,
+            structure_definition: read_json_field(object, "StructureDefinition", decoding_limits)?,
+        })
+    }
+}
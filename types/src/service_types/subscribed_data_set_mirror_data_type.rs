@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     string::UAString,
     service_types::RolePermissionType,
@@ -47,3 +48,20 @@ impl BinaryEncoder<SubscribedDataSetMirrorDataType> for SubscribedDataSetMirrorD
         })
     }
 }
+
+impl JsonEncoder<SubscribedDataSetMirrorDataType> for SubscribedDataSetMirrorDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ParentNodeName", &self.parent_node_name, ctx);
+        write_json_field(&mut object, "RolePermissions", &self.role_permissions, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(SubscribedDataSetMirrorDataType {
+            parent_node_name: read_json_field(object, "ParentNodeName", decoding_limits)?,
+            role_permissions: read_json_field(object, "RolePermissions", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -53,3 +54,20 @@ impl BinaryEncoder<SubscriptionAcknowledgement> for SubscriptionAcknowledgement
         })
     }
 }
+
+impl JsonEncoder<SubscriptionAcknowledgement> for SubscriptionAcknowledgement {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "SubscriptionId", &self.subscription_id, ctx);
+        write_json_field(&mut object, "SequenceNumber", &self.sequence_number, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(SubscriptionAcknowledgement {
+            subscription_id: read_json_field(object, "SubscriptionId", decoding_limits)?,
+            sequence_number: read_json_field(object, "SequenceNumber", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -199,3 +200,78 @@ impl BinaryEncoder<SubscriptionDiagnosticsDataType> for SubscriptionDiagnosticsD
         })
     }
 }
+
+impl JsonEncoder<SubscriptionDiagnosticsDataType> for SubscriptionDiagnosticsDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "SessionId", &self.session_id, ctx);
+        write_json_field(&mut object, "SubscriptionId", &self.subscription_id, ctx);
+        write_json_field(&mut object, "Priority", &self.priority, ctx);
+        write_json_field(&mut object, "PublishingInterval", &self.publishing_interval, ctx);
+        write_json_field(&mut object, "MaxKeepAliveCount", &self.max_keep_alive_count, ctx);
+        write_json_field(&mut object, "MaxLifetimeCount", &self.max_lifetime_count, ctx);
+        write_json_field(&mut object, "MaxNotificationsPerPublish", &self.max_notifications_per_publish, ctx);
+        write_json_field(&mut object, "PublishingEnabled", &self.publishing_enabled, ctx);
+        write_json_field(&mut object, "ModifyCount", &self.modify_count, ctx);
+        write_json_field(&mut object, "EnableCount", &self.enable_count, ctx);
+        write_json_field(&mut object, "DisableCount", &self.disable_count, ctx);
+        write_json_field(&mut object, "RepublishRequestCount", &self.republish_request_count, ctx);
+        write_json_field(&mut object, "RepublishMessageRequestCount", &self.republish_message_request_count, ctx);
+        write_json_field(&mut object, "RepublishMessageCount", &self.republish_message_count, ctx);
+        write_json_field(&mut object, "TransferRequestCount", &self.transfer_request_count, ctx);
+        write_json_field(&mut object, "TransferredToAltClientCount", &self.transferred_to_alt_client_count, ctx);
+        write_json_field(&mut object, "TransferredToSameClientCount", &self.transferred_to_same_client_count, ctx);
+        write_json_field(&mut object, "PublishRequestCount", &self.publish_request_count, ctx);
+        write_json_field(&mut object, "DataChangeNotificationsCount", &self.data_change_notifications_count, ctx);
+        write_json_field(&mut object, "EventNotificationsCount", &self.event_notifications_count, ctx);
+        write_json_field(&mut object, "NotificationsCount", &self.notifications_count, ctx);
+        write_json_field(&mut object, "LatePublishRequestCount", &self.late_publish_request_count, ctx);
+        write_json_field(&mut object, "CurrentKeepAliveCount", &self.current_keep_alive_count, ctx);
+        write_json_field(&mut object, "CurrentLifetimeCount", &self.current_lifetime_count, ctx);
+        write_json_field(&mut object, "UnacknowledgedMessageCount", &self.unacknowledged_message_count, ctx);
+        write_json_field(&mut object, "DiscardedMessageCount", &self.discarded_message_count, ctx);
+        write_json_field(&mut object, "MonitoredItemCount", &self.monitored_item_count, ctx);
+        write_json_field(&mut object, "DisabledMonitoredItemCount", &self.disabled_monitored_item_count, ctx);
+        write_json_field(&mut object, "MonitoringQueueOverflowCount", &self.monitoring_queue_overflow_count, ctx);
+        write_json_field(&mut object, "NextSequenceNumber", &self.next_sequence_number, ctx);
+        write_json_field(&mut object, "EventQueueOverFlowCount", &self.event_queue_over_flow_count, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(SubscriptionDiagnosticsDataType {
+            session_id: read_json_field(object, "SessionId", decoding_limits)?,
+            subscription_id: read_json_field(object, "SubscriptionId", decoding_limits)?,
+            priority: read_json_field(object, "Priority", decoding_limits)?,
+            publishing_interval: read_json_field(object, "PublishingInterval", decoding_limits)?,
+            max_keep_alive_count: read_json_field(object, "MaxKeepAliveCount", decoding_limits)?,
+            max_lifetime_count: read_json_field(object, "MaxLifetimeCount", decoding_limits)?,
+            max_notifications_per_publish: read_json_field(object, "MaxNotificationsPerPublish", decoding_limits)?,
+            publishing_enabled: read_json_field(object, "PublishingEnabled", decoding_limits)?,
+            modify_count: read_json_field(object, "ModifyCount", decoding_limits)?,
+            enable_count: read_json_field(object, "EnableCount", decoding_limits)?,
+            disable_count: read_json_field(object, "DisableCount", decoding_limits)?,
+            republish_request_count: read_json_field(object, "RepublishRequestCount", decoding_limits)?,
+            republish_message_request_count: read_json_field(object, "RepublishMessageRequestCount", decoding_limits)?,
+            republish_message_count: read_json_field(object, "RepublishMessageCount", decoding_limits)?,
+            transfer_request_count: read_json_field(object, "TransferRequestCount", decoding_limits)?,
+            transferred_to_alt_client_count: read_json_field(object, "TransferredToAltClientCount", decoding_limits)?,
+            transferred_to_same_client_count: read_json_field(object, "TransferredToSameClientCount", decoding_limits)?,
+            publish_request_count: read_json_field(object, "PublishRequestCount", decoding_limits)?,
+            data_change_notifications_count: read_json_field(object, "DataChangeNotificationsCount", decoding_limits)?,
+            event_notifications_count: read_json_field(object, "EventNotificationsCount", decoding_limits)?,
+            notifications_count: read_json_field(object, "NotificationsCount", decoding_limits)?,
+            late_publish_request_count: read_json_field(object, "LatePublishRequestCount", decoding_limits)?,
+            current_keep_alive_count: read_json_field(object, "CurrentKeepAliveCount", decoding_limits)?,
+            current_lifetime_count: read_json_field(object, "CurrentLifetimeCount", decoding_limits)?,
+            unacknowledged_message_count: read_json_field(object, "UnacknowledgedMessageCount", decoding_limits)?,
+            discarded_message_count: read_json_field(object, "DiscardedMessageCount", decoding_limits)?,
+            monitored_item_count: read_json_field(object, "MonitoredItemCount", decoding_limits)?,
+            disabled_monitored_item_count: read_json_field(object, "DisabledMonitoredItemCount", decoding_limits)?,
+            monitoring_queue_overflow_count: read_json_field(object, "MonitoringQueueOverflowCount", decoding_limits)?,
+            next_sequence_number: read_json_field(object, "NextSequenceNumber", decoding_limits)?,
+            event_queue_over_flow_count: read_json_field(object, "EventQueueOverFlowCount", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::FieldTargetDataType,
 };
@@ -41,3 +42,18 @@ impl BinaryEncoder<TargetVariablesDataType> for TargetVariablesDataType {
         })
     }
 }
+
+impl JsonEncoder<TargetVariablesDataType> for TargetVariablesDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "TargetVariables", &self.target_variables, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(TargetVariablesDataType {
+            target_variables: read_json_field(object, "TargetVariables", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
 };
 
@@ -50,3 +51,22 @@ impl BinaryEncoder<ThreeDCartesianCoordinates> for ThreeDCartesianCoordinates {
         })
     }
 }
+
+impl JsonEncoder<ThreeDCartesianCoordinates> for ThreeDCartesianCoordinates {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "X", &self.x, ctx);
+        write_json_field(&mut object, "Y", &self.y, ctx);
+        write_json_field(&mut object, "Z", &self.z, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ThreeDCartesianCoordinates {
+            x: read_json_field(object, "X", decoding_limits)?,
+            y: read_json_field(object, "Y", decoding_limits)?,
+            z: read_json_field(object, "Z", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::ThreeDCartesianCoordinates,
     service_types::ThreeDOrientation,
@@ -47,3 +48,20 @@ impl BinaryEncoder<ThreeDFrame> for ThreeDFrame {
         })
     }
 }
+
+impl JsonEncoder<ThreeDFrame> for ThreeDFrame {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "CartesianCoordinates", &self.cartesian_coordinates, ctx);
+        write_json_field(&mut object, "Orientation", &self.orientation, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ThreeDFrame {
+            cartesian_coordinates: read_json_field(object, "CartesianCoordinates", decoding_limits)?,
+            orientation: read_json_field(object, "Orientation", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
 };
 
@@ -50,3 +51,22 @@ impl BinaryEncoder<ThreeDOrientation> for ThreeDOrientation {
         })
     }
 }
+
+impl JsonEncoder<ThreeDOrientation> for ThreeDOrientation {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "A", &self.a, ctx);
+        write_json_field(&mut object, "B", &self.b, ctx);
+        write_json_field(&mut object, "C", &self.c, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ThreeDOrientation {
+            a: read_json_field(object, "A", decoding_limits)?,
+            b: read_json_field(object, "B", decoding_limits)?,
+            c: read_json_field(object, "C", decoding_limits)?,
+        })
+    }
+}
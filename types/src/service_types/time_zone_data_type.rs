@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -53,3 +54,20 @@ impl BinaryEncoder<TimeZoneDataType> for TimeZoneDataType {
         })
     }
 }
+
+impl JsonEncoder<TimeZoneDataType> for TimeZoneDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Offset", &self.offset, ctx);
+        write_json_field(&mut object, "DaylightSavingInOffset", &self.daylight_saving_in_offset, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(TimeZoneDataType {
+            offset: read_json_field(object, "Offset", decoding_limits)?,
+            daylight_saving_in_offset: read_json_field(object, "DaylightSavingInOffset", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -54,3 +55,20 @@ impl BinaryEncoder<TransferResult> for TransferResult {
         })
     }
 }
+
+impl JsonEncoder<TransferResult> for TransferResult {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "StatusCode", &self.status_code, ctx);
+        write_json_field(&mut object, "AvailableSequenceNumbers", &self.available_sequence_numbers, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(TransferResult {
+            status_code: read_json_field(object, "StatusCode", decoding_limits)?,
+            available_sequence_numbers: read_json_field(object, "AvailableSequenceNumbers", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -69,3 +70,26 @@ impl BinaryEncoder<TrustListDataType> for TrustListDataType {
         })
     }
 }
+
+impl JsonEncoder<TrustListDataType> for TrustListDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "SpecifiedLists", &self.specified_lists, ctx);
+        write_json_field(&mut object, "TrustedCertificates", &self.trusted_certificates, ctx);
+        write_json_field(&mut object, "TrustedCrls", &self.trusted_crls, ctx);
+        write_json_field(&mut object, "IssuerCertificates", &self.issuer_certificates, ctx);
+        write_json_field(&mut object, "IssuerCrls", &self.issuer_crls, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(TrustListDataType {
+            specified_lists: read_json_field(object, "SpecifiedLists", decoding_limits)?,
+            trusted_certificates: read_json_field(object, "TrustedCertificates", decoding_limits)?,
+            trusted_crls: read_json_field(object, "TrustedCrls", decoding_limits)?,
+            issuer_certificates: read_json_field(object, "IssuerCertificates", decoding_limits)?,
+            issuer_crls: read_json_field(object, "IssuerCrls", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     string::UAString,
     variant::Variant,
@@ -76,3 +77,30 @@ impl BinaryEncoder<UABinaryFileDataType> for UABinaryFileDataType {
         })
     }
 }
+
+impl JsonEncoder<UABinaryFileDataType> for UABinaryFileDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Namespaces", &self.namespaces, ctx);
+        write_json_field(&mut object, "StructureDataTypes", &self.structure_data_types, ctx);
+        write_json_field(&mut object, "EnumDataTypes", &self.enum_data_types, ctx);
+        write_json_field(&mut object, "SimpleDataTypes", &self.simple_data_types, ctx);
+        write_json_field(&mut object, "SchemaLocation", &self.schema_location, ctx);
+        write_json_field(&mut object, "FileHeader", &self.file_header, ctx);
+        write_json_field(&mut object, "Body", &self.body, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(UABinaryFileDataType {
+            namespaces: read_json_field(object, "Namespaces", decoding_limits)?,
+            structure_data_types: read_json_field(object, "StructureDataTypes", decoding_limits)?,
+            enum_data_types: read_json_field(object, "EnumDataTypes", decoding_limits)?,
+            simple_data_types: read_json_field(object, "SimpleDataTypes", decoding_limits)?,
+            schema_location: read_json_field(object, "SchemaLocation", decoding_limits)?,
+            file_header: read_json_field(object, "FileHeader", decoding_limits)?,
+            body: read_json_field(object, "Body", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     guid::Guid,
     service_types::enums::UadpNetworkMessageContentMask,
@@ -83,3 +84,34 @@ impl BinaryEncoder<UadpDataSetReaderMessageDataType> for UadpDataSetReaderMessag
         })
     }
 }
+
+impl JsonEncoder<UadpDataSetReaderMessageDataType> for UadpDataSetReaderMessageDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "GroupVersion", &self.group_version, ctx);
+        write_json_field(&mut object, "NetworkMessageNumber", &self.network_message_number, ctx);
+        write_json_field(&mut object, "DataSetOffset", &self.data_set_offset, ctx);
+        write_json_field(&mut object, "DataSetClassId", &self.data_set_class_id, ctx);
+        write_json_field(&mut object, "NetworkMessageContentMask", &self.network_message_content_mask, ctx);
+        write_json_field(&mut object, "DataSetMessageContentMask", &self.data_set_message_content_mask, ctx);
+        write_json_field(&mut object, "PublishingInterval", &self.publishing_interval, ctx);
+        write_json_field(&mut object, "ReceiveOffset", &self.receive_offset, ctx);
+        write_json_field(&mut object, "ProcessingOffset", &self.processing_offset, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(UadpDataSetReaderMessageDataType {
+            group_version: read_json_field(object, "GroupVersion", decoding_limits)?,
+            network_message_number: read_json_field(object, "NetworkMessageNumber", decoding_limits)?,
+            data_set_offset: read_json_field(object, "DataSetOffset", decoding_limits)?,
+            data_set_class_id: read_json_field(object, "DataSetClassId", decoding_limits)?,
+            network_message_content_mask: read_json_field(object, "NetworkMessageContentMask", decoding_limits)?,
+            data_set_message_content_mask: read_json_field(object, "DataSetMessageContentMask", decoding_limits)?,
+            publishing_interval: read_json_field(object, "PublishingInterval", decoding_limits)?,
+            receive_offset: read_json_field(object, "ReceiveOffset", decoding_limits)?,
+            processing_offset: read_json_field(object, "ProcessingOffset", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::enums::UadpDataSetMessageContentMask,
 };
@@ -56,3 +57,24 @@ impl BinaryEncoder<UadpDataSetWriterMessageDataType> for UadpDataSetWriterMessag
         })
     }
 }
+
+impl JsonEncoder<UadpDataSetWriterMessageDataType> for UadpDataSetWriterMessageDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "DataSetMessageContentMask", &self.data_set_message_content_mask, ctx);
+        write_json_field(&mut object, "ConfiguredSize", &self.configured_size, ctx);
+        write_json_field(&mut object, "NetworkMessageNumber", &self.network_message_number, ctx);
+        write_json_field(&mut object, "DataSetOffset", &self.data_set_offset, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(UadpDataSetWriterMessageDataType {
+            data_set_message_content_mask: read_json_field(object, "DataSetMessageContentMask", decoding_limits)?,
+            configured_size: read_json_field(object, "ConfiguredSize", decoding_limits)?,
+            network_message_number: read_json_field(object, "NetworkMessageNumber", decoding_limits)?,
+            data_set_offset: read_json_field(object, "DataSetOffset", decoding_limits)?,
+        })
+    }
+}
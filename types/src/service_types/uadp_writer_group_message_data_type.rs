@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::enums::DataSetOrderingType,
     service_types::enums::UadpNetworkMessageContentMask,
@@ -62,3 +63,26 @@ impl BinaryEncoder<UadpWriterGroupMessageDataType> for UadpWriterGroupMessageDat
         })
     }
 }
+
+impl JsonEncoder<UadpWriterGroupMessageDataType> for UadpWriterGroupMessageDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "GroupVersion", &self.group_version, ctx);
+        write_json_field(&mut object, "DataSetOrdering", &self.data_set_ordering, ctx);
+        write_json_field(&mut object, "NetworkMessageContentMask", &self.network_message_content_mask, ctx);
+        write_json_field(&mut object, "SamplingOffset", &self.sampling_offset, ctx);
+        write_json_field(&mut object, "PublishingOffset", &self.publishing_offset, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(UadpWriterGroupMessageDataType {
+            group_version: read_json_field(object, "GroupVersion", decoding_limits)?,
+            data_set_ordering: read_json_field(object, "DataSetOrdering", decoding_limits)?,
+            network_message_content_mask: read_json_field(object, "NetworkMessageContentMask", decoding_limits)?,
+            sampling_offset: read_json_field(object, "SamplingOffset", decoding_limits)?,
+            publishing_offset: read_json_field(object, "PublishingOffset", decoding_limits)?,
+        })
+    }
+}
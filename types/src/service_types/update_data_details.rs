@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     node_id::NodeId,
     service_types::enums::PerformUpdateType,
@@ -53,3 +54,22 @@ impl BinaryEncoder<UpdateDataDetails> for UpdateDataDetails {
         })
     }
 }
+
+impl JsonEncoder<UpdateDataDetails> for UpdateDataDetails {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NodeId", &self.node_id, ctx);
+        write_json_field(&mut object, "PerformInsertReplace", &self.perform_insert_replace, ctx);
+        write_json_field(&mut object, "UpdateValues", &self.update_values, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(UpdateDataDetails {
+            node_id: read_json_field(object, "NodeId", decoding_limits)?,
+            perform_insert_replace: read_json_field(object, "PerformInsertReplace", decoding_limits)?,
+            update_values: read_json_field(object, "UpdateValues", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     node_id::NodeId,
     service_types::enums::PerformUpdateType,
@@ -59,3 +60,24 @@ impl BinaryEncoder<UpdateEventDetails> for UpdateEventDetails {
         })
     }
 }
+
+impl JsonEncoder<UpdateEventDetails> for UpdateEventDetails {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "NodeId", &self.node_id, ctx);
+        write_json_field(&mut object, "PerformInsertReplace", &self.perform_insert_replace, ctx);
+        write_json_field(&mut object, "Filter", &self.filter, ctx);
+        write_json_field(&mut object, "EventData", &self.event_data, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(UpdateEventDetails {
+            node_id: read_json_field(object, "NodeId", decoding_limits)?,
+            perform_insert_replace: read_json_field(object, "PerformInsertReplace", decoding_limits)?,
+            filter: read_json_field(object, "Filter", decoding_limits)?,
+            event_data: read_json_field(object, "EventData", decoding_limits)?,
+        })
+    }
+}
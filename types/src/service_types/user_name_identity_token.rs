@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     string::UAString,
     byte_string::ByteString,
@@ -57,3 +58,24 @@ impl BinaryEncoder<UserNameIdentityToken> for UserNameIdentityToken {
         })
     }
 }
+
+impl JsonEncoder<UserNameIdentityToken> for UserNameIdentityToken {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "PolicyId", &self.policy_id, ctx);
+        write_json_field(&mut object, "UserName", &self.user_name, ctx);
+        write_json_field(&mut object, "Password", &self.password, ctx);
+        write_json_field(&mut object, "EncryptionAlgorithm", &self.encryption_algorithm, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(UserNameIdentityToken {
+            policy_id: read_json_field(object, "PolicyId", decoding_limits)?,
+            user_name: read_json_field(object, "UserName", decoding_limits)?,
+            password: read_json_field(object, "Password", decoding_limits)?,
+            encryption_algorithm: read_json_field(object, "EncryptionAlgorithm", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -70,3 +71,26 @@ impl BinaryEncoder<UserTokenPolicy> for UserTokenPolicy {
         })
     }
 }
+
+impl JsonEncoder<UserTokenPolicy> for UserTokenPolicy {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "PolicyId", &self.policy_id, ctx);
+        write_json_field(&mut object, "TokenType", &self.token_type, ctx);
+        write_json_field(&mut object, "IssuedTokenType", &self.issued_token_type, ctx);
+        write_json_field(&mut object, "IssuerEndpointUrl", &self.issuer_endpoint_url, ctx);
+        write_json_field(&mut object, "SecurityPolicyUri", &self.security_policy_uri, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(UserTokenPolicy {
+            policy_id: read_json_field(object, "PolicyId", decoding_limits)?,
+            token_type: read_json_field(object, "TokenType", decoding_limits)?,
+            issued_token_type: read_json_field(object, "IssuedTokenType", decoding_limits)?,
+            issuer_endpoint_url: read_json_field(object, "IssuerEndpointUrl", decoding_limits)?,
+            security_policy_uri: read_json_field(object, "SecurityPolicyUri", decoding_limits)?,
+        })
+    }
+}
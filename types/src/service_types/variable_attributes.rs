@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     localized_text::LocalizedText,
     variant::Variant,
@@ -103,3 +104,42 @@ impl BinaryEncoder<VariableAttributes> for VariableAttributes {
         })
     }
 }
+
+impl JsonEncoder<VariableAttributes> for VariableAttributes {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "SpecifiedAttributes", &self.specified_attributes, ctx);
+        write_json_field(&mut object, "DisplayName", &self.display_name, ctx);
+        write_json_field(&mut object, "Description", &self.description, ctx);
+        write_json_field(&mut object, "WriteMask", &self.write_mask, ctx);
+        write_json_field(&mut object, "UserWriteMask", &self.user_write_mask, ctx);
+        write_json_field(&mut object, "Value", &self.value, ctx);
+        write_json_field(&mut object, "DataType", &self.data_type, ctx);
+        write_json_field(&mut object, "ValueRank", &self.value_rank, ctx);
+        write_json_field(&mut object, "ArrayDimensions", &self.array_dimensions, ctx);
+        write_json_field(&mut object, "AccessLevel", &self.access_level, ctx);
+        write_json_field(&mut object, "UserAccessLevel", &self.user_access_level, ctx);
+        write_json_field(&mut object, "MinimumSamplingInterval", &self.minimum_sampling_interval, ctx);
+        write_json_field(&mut object, "Historizing", &self.historizing, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(VariableAttributes {
+            specified_attributes: read_json_field(object, "SpecifiedAttributes", decoding_limits)?,
+            display_name: read_json_field(object, "DisplayName", decoding_limits)?,
+            description: read_json_field(object, "Description", decoding_limits)?,
+            write_mask: read_json_field(object, "WriteMask", decoding_limits)?,
+            user_write_mask: read_json_field(object, "UserWriteMask", decoding_limits)?,
+            value: read_json_field(object, "Value", decoding_limits)?,
+            data_type: read_json_field(object, "DataType", decoding_limits)?,
+            value_rank: read_json_field(object, "ValueRank", decoding_limits)?,
+            array_dimensions: read_json_field(object, "ArrayDimensions", decoding_limits)?,
+            access_level: read_json_field(object, "AccessLevel", decoding_limits)?,
+            user_access_level: read_json_field(object, "UserAccessLevel", decoding_limits)?,
+            minimum_sampling_interval: read_json_field(object, "MinimumSamplingInterval", decoding_limits)?,
+            historizing: read_json_field(object, "Historizing", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     service_types::impls::MessageInfo,
     node_ids::ObjectId,
@@ -60,3 +61,22 @@ impl BinaryEncoder<ViewDescription> for ViewDescription {
         })
     }
 }
+
+impl JsonEncoder<ViewDescription> for ViewDescription {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "ViewId", &self.view_id, ctx);
+        write_json_field(&mut object, "Timestamp", &self.timestamp, ctx);
+        write_json_field(&mut object, "ViewVersion", &self.view_version, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(ViewDescription {
+            view_id: read_json_field(object, "ViewId", decoding_limits)?,
+            timestamp: read_json_field(object, "Timestamp", decoding_limits)?,
+            view_version: read_json_field(object, "ViewVersion", decoding_limits)?,
+        })
+    }
+}
@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     string::UAString,
     service_types::enums::MessageSecurityMode,
@@ -121,3 +122,48 @@ impl BinaryEncoder<WriterGroupDataType> for WriterGroupDataType {
         })
     }
 }
+
+impl JsonEncoder<WriterGroupDataType> for WriterGroupDataType {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "Name", &self.name, ctx);
+        write_json_field(&mut object, "Enabled", &self.enabled, ctx);
+        write_json_field(&mut object, "SecurityMode", &self.security_mode, ctx);
+        write_json_field(&mut object, "SecurityGroupId", &self.security_group_id, ctx);
+        write_json_field(&mut object, "SecurityKeyServices", &self.security_key_services, ctx);
+        write_json_field(&mut object, "MaxNetworkMessageSize", &self.max_network_message_size, ctx);
+        write_json_field(&mut object, "GroupProperties", &self.group_properties, ctx);
+        write_json_field(&mut object, "WriterGroupId", &self.writer_group_id, ctx);
+        write_json_field(&mut object, "PublishingInterval", &self.publishing_interval, ctx);
+        write_json_field(&mut object, "KeepAliveTime", &self.keep_alive_time, ctx);
+        write_json_field(&mut object, "Priority", &self.priority, ctx);
+        write_json_field(&mut object, "LocaleIds", &self.locale_ids, ctx);
+        write_json_field(&mut object, "HeaderLayoutUri", &self.header_layout_uri, ctx);
+        write_json_field(&mut object, "TransportSettings", &self.transport_settings, ctx);
+        write_json_field(&mut object, "MessageSettings", &self.message_settings, ctx);
+        write_json_field(&mut object, "DataSetWriters", &self.data_set_writers, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(WriterGroupDataType {
+            name: read_json_field(object, "Name", decoding_limits)?,
+            enabled: read_json_field(object, "Enabled", decoding_limits)?,
+            security_mode: read_json_field(object, "SecurityMode", decoding_limits)?,
+            security_group_id: read_json_field(object, "SecurityGroupId", decoding_limits)?,
+            security_key_services: read_json_field(object, "SecurityKeyServices", decoding_limits)?,
+            max_network_message_size: read_json_field(object, "MaxNetworkMessageSize", decoding_limits)?,
+            group_properties: read_json_field(object, "GroupProperties", decoding_limits)?,
+            writer_group_id: read_json_field(object, "WriterGroupId", decoding_limits)?,
+            publishing_interval: read_json_field(object, "PublishingInterval", decoding_limits)?,
+            keep_alive_time: read_json_field(object, "KeepAliveTime", decoding_limits)?,
+            priority: read_json_field(object, "Priority", decoding_limits)?,
+            locale_ids: read_json_field(object, "LocaleIds", decoding_limits)?,
+            header_layout_uri: read_json_field(object, "HeaderLayoutUri", decoding_limits)?,
+            transport_settings: read_json_field(object, "TransportSettings", decoding_limits)?,
+            message_settings: read_json_field(object, "MessageSettings", decoding_limits)?,
+            data_set_writers: read_json_field(object, "DataSetWriters", decoding_limits)?,
+        })
+    }
+}
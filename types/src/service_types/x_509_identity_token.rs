@@ -10,6 +10,7 @@ use std::io::{Read, Write};
 #[allow(unused_imports)]
 use crate::{
     encoding::*,
+    json::*,
     basic_types::*,
     string::UAString,
     byte_string::ByteString,
@@ -47,3 +48,20 @@ impl BinaryEncoder<X509IdentityToken> for X509IdentityToken {
         })
     }
 }
+
+impl JsonEncoder<X509IdentityToken> for X509IdentityToken {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        let mut object = JsonObject::new();
+        write_json_field(&mut object, "PolicyId", &self.policy_id, ctx);
+        write_json_field(&mut object, "CertificateData", &self.certificate_data, ctx);
+        JsonValue::Object(object)
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        Ok(X509IdentityToken {
+            policy_id: read_json_field(object, "PolicyId", decoding_limits)?,
+            certificate_data: read_json_field(object, "CertificateData", decoding_limits)?,
+        })
+    }
+}
@@ -15,6 +15,7 @@ use serde::{
 };
 
 pub use crate::{encoding::*, status_codes::StatusCode};
+use crate::json::*;
 
 // The bitflags! macro implements Debug for StatusCode but it fouls the display because status
 // codes are a combination of bits and unique values.
@@ -45,6 +46,26 @@ impl BinaryEncoder<StatusCode> for StatusCode {
     }
 }
 
+/// Status codes are JSON numbers in the reversible form. The non-reversible form is an object with
+/// the numeric Code and its Symbol.
+impl JsonEncoder<StatusCode> for StatusCode {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        if ctx.reversible {
+            JsonValue::from(self.bits())
+        } else {
+            let mut object = JsonObject::new();
+            object.insert("Code".to_string(), JsonValue::from(self.bits()));
+            object.insert("Symbol".to_string(), JsonValue::from(self.status().name()));
+            JsonValue::Object(object)
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let code = u32::decode_json(value, decoding_limits)?;
+        Ok(StatusCode::from_bits_truncate(code))
+    }
+}
+
 impl StatusCode {
     /// Returns the bit flags of the status code, i.e. it masks out the actual status code value
     pub fn bitflags(&self) -> StatusCode {
@@ -0,0 +1,177 @@
+use crate::{
+    encoding::DecodingLimits,
+    tests::*,
+};
+
+fn field(name: &str, data_type: NodeId, value_rank: i32, is_optional: bool) -> StructureField {
+    StructureField {
+        name: UAString::from(name),
+        description: LocalizedText::null(),
+        data_type,
+        value_rank,
+        array_dimensions: None,
+        max_string_length: 0,
+        is_optional,
+    }
+}
+
+fn structure(default_encoding_id: NodeId, structure_type: StructureType, fields: Vec<StructureField>) -> DataTypeDefinition {
+    DataTypeDefinition::from(StructureDefinition {
+        default_encoding_id,
+        base_data_type: DataTypeId::Structure.into(),
+        structure_type,
+        fields: Some(fields),
+    })
+}
+
+/// Definitions that match types compiled into this crate, so the dynamic encoding can be
+/// compared with the generated one.
+fn built_in_types() -> DynamicTypes {
+    let mut types = DynamicTypes::new();
+    types.add_definition(DataTypeId::Argument, structure(ObjectId::Argument_Encoding_DefaultBinary.into(), StructureType::Structure, vec![
+        field("Name", DataTypeId::String.into(), -1, false),
+        field("DataType", DataTypeId::NodeId.into(), -1, false),
+        field("ValueRank", DataTypeId::Int32.into(), -1, false),
+        field("ArrayDimensions", DataTypeId::UInt32.into(), 1, false),
+        field("Description", DataTypeId::LocalizedText.into(), -1, false),
+    ]));
+    types.add_definition(DataTypeId::EUInformation, structure(ObjectId::EUInformation_Encoding_DefaultBinary.into(), StructureType::Structure, vec![
+        field("NamespaceUri", DataTypeId::String.into(), -1, false),
+        field("UnitId", DataTypeId::Int32.into(), -1, false),
+        field("DisplayName", DataTypeId::LocalizedText.into(), -1, false),
+        field("Description", DataTypeId::LocalizedText.into(), -1, false),
+    ]));
+    types
+}
+
+/// Custom types with nested structures, optional fields, unions and enums
+fn custom_types() -> DynamicTypes {
+    let mut types = DynamicTypes::new();
+    types.add_definition(NodeId::new(2, 3001), DataTypeDefinition::from(EnumDefinition {
+        fields: Some(vec![
+            EnumField { value: 0, display_name: LocalizedText::new("", "Off"), description: LocalizedText::null(), name: UAString::from("Off") },
+            EnumField { value: 1, display_name: LocalizedText::new("", "On"), description: LocalizedText::null(), name: UAString::from("On") },
+        ])
+    }));
+    types.add_definition(NodeId::new(2, 3002), structure(NodeId::new(2, 5002), StructureType::Structure, vec![
+        field("X", DataTypeId::Double.into(), -1, false),
+        field("Y", DataTypeId::Double.into(), -1, false),
+    ]));
+    types.add_definition(NodeId::new(2, 3003), structure(NodeId::new(2, 5003), StructureType::Union, vec![
+        field("Position", NodeId::new(2, 3002), -1, false),
+        field("Label", DataTypeId::String.into(), -1, false),
+    ]));
+    types.add_definition(NodeId::new(2, 3004), structure(NodeId::new(2, 5004), StructureType::StructureWithOptionalFields, vec![
+        field("Name", DataTypeId::String.into(), -1, false),
+        field("Mode", NodeId::new(2, 3001), -1, true),
+        field("Path", NodeId::new(2, 3002), 1, false),
+        field("Target", NodeId::new(2, 3003), -1, true),
+        field("Timeout", DataTypeId::Duration.into(), -1, true),
+        field("Extra", DataTypeId::Structure.into(), -1, false),
+    ]));
+    types
+}
+
+fn point(x: f64, y: f64) -> DynamicStructure {
+    DynamicStructure::new(NodeId::new(2, 3002)).with_field("X", x).with_field("Y", y)
+}
+
+#[test]
+fn dynamic_structure_decode_built_in() {
+    let types = built_in_types();
+    let argument = Argument {
+        name: UAString::from("arg"),
+        data_type: DataTypeId::Double.into(),
+        value_rank: 2,
+        array_dimensions: Some(vec![2, 3]),
+        description: LocalizedText::new("en", "An argument"),
+    };
+    let extension_object = ExtensionObject::from_encodable(ObjectId::Argument_Encoding_DefaultBinary, &argument);
+    let value = types.decode(&extension_object, &DecodingLimits::default()).unwrap();
+    assert_eq!(value.data_type_id, DataTypeId::Argument.into());
+    assert_eq!(value.field("Name"), Some(&DynamicValue::from("arg")));
+    let data_type: NodeId = DataTypeId::Double.into();
+    assert_eq!(value.field("DataType"), Some(&DynamicValue::from(data_type)));
+    assert_eq!(value.field("ArrayDimensions"), Some(&DynamicValue::Array(vec![2u32.into(), 3u32.into()])));
+    assert_eq!(value.field("Description"), Some(&DynamicValue::from(LocalizedText::new("en", "An argument"))));
+
+    // Encoding gives the same bytes as the generated type
+    assert_eq!(types.encode(&value).unwrap(), extension_object);
+
+    // Values are converted to the type of the field
+    let value = DynamicStructure::new(DataTypeId::EUInformation)
+        .with_field("NamespaceUri", "http://www.opcfoundation.org/UA/units/un/cefact")
+        .with_field("UnitId", 4408652u32)
+        .with_field("DisplayName", LocalizedText::new("", "°C"))
+        .with_field("Description", LocalizedText::new("", "degree Celsius"));
+    let extension_object = types.encode(&value).unwrap();
+    let eu_information = extension_object.decode_inner::<EUInformation>(&DecodingLimits::default()).unwrap();
+    assert_eq!(eu_information.unit_id, 4408652);
+    assert_eq!(eu_information.display_name.text, UAString::from("°C"));
+}
+
+#[test]
+fn dynamic_structure_round_trip() {
+    let types = custom_types();
+    let value = DynamicStructure::new(NodeId::new(2, 3004))
+        .with_field("Name", "Move")
+        .with_field("Mode", 1i32)
+        .with_field("Path", vec![DynamicValue::from(point(1.0, 2.0)), DynamicValue::from(point(3.0, 4.0))])
+        .with_field("Target", DynamicStructure::new(NodeId::new(2, 3003)).with_field("Label", "Home"))
+        .with_field("Extra", types.encode(&point(3.5, 4.5)).unwrap());
+    let extension_object = types.encode(&value).unwrap();
+    assert_eq!(extension_object.node_id, NodeId::new(2, 5004));
+    let decoded = types.decode(&extension_object, &DecodingLimits::default()).unwrap();
+    assert!(decoded.field("Timeout").is_none());
+
+    // Nested extension objects of a known type are decoded too
+    let mut expected = value.clone();
+    expected.set_field("Extra", point(3.5, 4.5));
+    assert_eq!(decoded, expected);
+
+    // A union holding a structure, and optional fields that are set
+    let value = DynamicStructure::new(NodeId::new(2, 3004))
+        .with_field("Name", "Wait")
+        .with_field("Path", Variant::Empty)
+        .with_field("Target", DynamicStructure::new(NodeId::new(2, 3003)).with_field("Position", point(5.0, 6.0)))
+        .with_field("Timeout", 250.0f64)
+        .with_field("Extra", Variant::Empty);
+    let decoded = types.decode(&types.encode(&value).unwrap(), &DecodingLimits::default()).unwrap();
+    assert!(decoded.field("Mode").is_none());
+    assert_eq!(decoded.field("Path"), Some(&DynamicValue::Array(vec![])));
+    assert_eq!(decoded.field("Target"), value.field("Target"));
+    assert_eq!(decoded.field("Timeout"), Some(&DynamicValue::from(250.0f64)));
+    assert_eq!(decoded.field("Extra"), Some(&DynamicValue::from(ExtensionObject::null())));
+}
+
+#[test]
+fn dynamic_structure_errors() {
+    let mut types = DynamicTypes::new();
+    types.add_definition(NodeId::new(2, 3005), structure(NodeId::new(2, 5005), StructureType::Structure, vec![
+        field("Point", NodeId::new(2, 3002), -1, false),
+        field("Mode", NodeId::new(2, 3001), -1, false),
+        field("Count", DataTypeId::UInt16.into(), -1, false),
+    ]));
+    let mut unknown = types.unknown_types(&NodeId::new(2, 3005));
+    unknown.sort_by_key(|id| id.to_string());
+    assert_eq!(unknown, vec![NodeId::new(2, 3001), NodeId::new(2, 3002)]);
+    assert!(types.unknown_types(&NodeId::new(2, 3009)).contains(&NodeId::new(2, 3009)));
+
+    let custom = custom_types();
+    for id in &[3001, 3002] {
+        types.add_definition(NodeId::new(2, *id), custom.definition(&NodeId::new(2, *id)).unwrap().clone());
+    }
+    assert!(types.unknown_types(&NodeId::new(2, 3005)).is_empty());
+
+    // Missing fields and values of the wrong type
+    let value = DynamicStructure::new(NodeId::new(2, 3005)).with_field("Point", point(1.0, 2.0)).with_field("Mode", 0i32);
+    assert_eq!(types.encode(&value), Err(StatusCode::BadEncodingError));
+    let value = value.with_field("Count", "many");
+    assert_eq!(types.encode(&value), Err(StatusCode::BadTypeMismatch));
+    let value = value.with_field("Count", 3u8);
+    assert!(types.encode(&value).is_ok());
+
+    // Unknown encodings
+    let extension_object = ExtensionObject::from_encodable(NodeId::new(2, 5999), &1u32);
+    assert_eq!(types.decode(&extension_object, &DecodingLimits::default()), Err(StatusCode::BadDataTypeIdUnknown));
+}
@@ -462,3 +462,45 @@ fn argument() {
         description: LocalizedText::new("foo", "bar"),
     });
 }
+
+#[test]
+fn data_type_definition() {
+    let definition = DataTypeDefinition::from(StructureDefinition {
+        default_encoding_id: NodeId::new(1, 5001),
+        base_data_type: DataTypeId::Structure.into(),
+        structure_type: StructureType::Structure,
+        fields: Some(vec![StructureField {
+            name: UAString::from("X"),
+            description: LocalizedText::new("en", "Position"),
+            data_type: DataTypeId::Double.into(),
+            value_rank: -1,
+            array_dimensions: None,
+            max_string_length: 0,
+            is_optional: false,
+        }]),
+    });
+    let extension_object = ExtensionObject::from(&definition);
+    assert_eq!(extension_object.node_id, ObjectId::StructureDefinition_Encoding_DefaultBinary.into());
+    assert_eq!(DataTypeDefinition::from_extension_object(&extension_object, &DecodingLimits::default()).unwrap(), definition);
+
+    let definition = DataTypeDefinition::from(EnumDefinition {
+        fields: Some(vec![EnumField {
+            value: 1,
+            display_name: LocalizedText::new("en", "On"),
+            description: LocalizedText::new("en", "Switched on"),
+            name: UAString::from("On"),
+        }]),
+    });
+    let extension_object = ExtensionObject::from(&definition);
+    assert_eq!(DataTypeDefinition::from_extension_object(&extension_object, &DecodingLimits::default()).unwrap(), definition);
+
+    // Other extension objects are not definitions
+    let extension_object = ExtensionObject::from_encodable(ObjectId::Argument_Encoding_DefaultBinary, &Argument {
+        name: UAString::from("arg"),
+        data_type: NodeId::null(),
+        value_rank: -1,
+        array_dimensions: None,
+        description: LocalizedText::null(),
+    });
+    assert_eq!(DataTypeDefinition::from_extension_object(&extension_object, &DecodingLimits::default()), Err(StatusCode::BadDataTypeIdUnknown));
+}
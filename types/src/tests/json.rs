@@ -0,0 +1,188 @@
+use std::str::FromStr;
+
+use crate::tests::*;
+
+fn reversible<T>(value: &T) -> String where T: JsonEncoder<T> {
+    value.to_json_string(&JsonContext::reversible())
+}
+
+fn non_reversible<T>(value: &T) -> String where T: JsonEncoder<T> {
+    value.to_json_string(&JsonContext::non_reversible(&["http://opcfoundation.org/UA/", "urn:server", "urn:model"]))
+}
+
+/// Encodes the value in its reversible form, checks the JSON and decodes it back again
+fn json_test<T>(value: T, expected_json: &str) -> T where T: JsonEncoder<T> + Debug + PartialEq {
+    let json = reversible(&value);
+    assert_eq!(json, expected_json);
+    let decoded = T::from_json_str(&json, &DecodingLimits::default()).unwrap();
+    assert_eq!(decoded, value);
+    decoded
+}
+
+#[test]
+fn json_built_in_types() {
+    json_test(true, "true");
+    json_test(-5i8, "-5");
+    json_test(u32::MAX, "4294967295");
+    json_test(i64::MIN, "\"-9223372036854775808\"");
+    json_test(u64::MAX, "\"18446744073709551615\"");
+    json_test(1.5f64, "1.5");
+    json_test(f32::INFINITY, "\"Infinity\"");
+    json_test(f64::NEG_INFINITY, "\"-Infinity\"");
+    assert!(f64::from_json_str("\"NaN\"", &DecodingLimits::default()).unwrap().is_nan());
+    json_test(UAString::from("Hello \"World\""), "\"Hello \\\"World\\\"\"");
+    json_test(UAString::null(), "null");
+    json_test(ByteString::from(&[1u8, 2, 3, 4]), "\"AQIDBA==\"");
+    json_test(DateTime::ymd_hms_nano(2020, 4, 1, 12, 30, 15, 250_000_000), "\"2020-04-01T12:30:15.250Z\"");
+    json_test(DateTime::null(), "null");
+    json_test(Guid::from_str("72962b91-fa75-4ae6-8d28-b404dc7daf63").unwrap(), "\"72962b91-fa75-4ae6-8d28-b404dc7daf63\"");
+    json_test(StatusCode::BadNodeIdUnknown, "2150891520");
+    assert_eq!(non_reversible(&StatusCode::BadNodeIdUnknown), "{\"Code\":2150891520,\"Symbol\":\"BadNodeIdUnknown\"}");
+
+    // Strings are limited by the decoding limits
+    let decoding_limits = DecodingLimits { max_string_length: 3, ..Default::default() };
+    assert_eq!(UAString::from_json_str("\"abcd\"", &decoding_limits).unwrap_err(), StatusCode::BadDecodingError);
+}
+
+#[test]
+fn json_node_id() {
+    json_test(NodeId::new(0, 2253), "\"i=2253\"");
+    json_test(NodeId::new(2, "Pump"), "\"ns=2;s=Pump\"");
+    json_test(NodeId::new(1, Guid::from_str("72962b91-fa75-4ae6-8d28-b404dc7daf63").unwrap()),
+              "\"ns=1;g=72962b91-fa75-4ae6-8d28-b404dc7daf63\"");
+    json_test(NodeId::new(3, ByteString::from(&[255u8])), "\"ns=3;b=/w==\"");
+    json_test(NodeId::null(), "null");
+
+    // Non-reversible writes the namespace uri, except for namespace 1
+    assert_eq!(non_reversible(&NodeId::new(2, "Pump")), "\"nsu=urn:model;s=Pump\"");
+    assert_eq!(non_reversible(&NodeId::new(1, 5)), "\"ns=1;i=5\"");
+    assert_eq!(NodeId::from_json_str("\"nsu=urn:model;s=Pump\"", &DecodingLimits::default()).unwrap_err(), StatusCode::BadDecodingError);
+    assert_eq!(NodeId::from_json_str("\"ns=2;x=Pump\"", &DecodingLimits::default()).unwrap_err(), StatusCode::BadDecodingError);
+
+    // The object form is still decoded
+    assert_eq!(NodeId::from_json_str("{\"IdType\":1,\"Id\":\"Pump\",\"Namespace\":2}", &DecodingLimits::default()).unwrap(), NodeId::new(2, "Pump"));
+    assert_eq!(NodeId::from_json_str("{\"Id\":2253}", &DecodingLimits::default()).unwrap(), NodeId::new(0, 2253));
+
+    let mut node_id = ExpandedNodeId::from(NodeId::new(0, 5));
+    node_id.namespace_uri = UAString::from("urn:else;where");
+    node_id.server_index = 2;
+    json_test(node_id.clone(), "\"svr=2;nsu=urn:else%3bwhere;i=5\"");
+    assert_eq!(ExpandedNodeId::from_json_str("{\"Id\":5,\"Namespace\":\"urn:else;where\",\"ServerUri\":2}", &DecodingLimits::default()).unwrap(), node_id);
+    json_test(ExpandedNodeId::from(NodeId::new(4, 5)), "\"ns=4;i=5\"");
+    json_test(ExpandedNodeId::from(NodeId::new(0, 5)), "\"i=5\"");
+    let mut node_id = ExpandedNodeId::from(NodeId::new(4, "Pump"));
+    node_id.server_index = 1;
+    json_test(node_id, "\"svr=1;ns=4;s=Pump\"");
+}
+
+#[test]
+fn json_names_and_text() {
+    json_test(QualifiedName::new(2, "Pump"), "{\"Name\":\"Pump\",\"Uri\":2}");
+    json_test(QualifiedName::new(0, "Pump"), "{\"Name\":\"Pump\"}");
+    assert_eq!(non_reversible(&QualifiedName::new(2, "Pump")), "{\"Name\":\"Pump\",\"Uri\":\"urn:model\"}");
+
+    json_test(LocalizedText::new("en", "Hello"), "{\"Locale\":\"en\",\"Text\":\"Hello\"}");
+    assert_eq!(non_reversible(&LocalizedText::new("en", "Hello")), "\"Hello\"");
+}
+
+#[test]
+fn json_variant() {
+    json_test(Variant::Empty, "null");
+    json_test(Variant::from(5i32), "{\"Type\":6,\"Body\":5}");
+    json_test(Variant::from(5u64), "{\"Type\":9,\"Body\":\"5\"}");
+    json_test(Variant::from(LocalizedText::new("", "Hi")), "{\"Type\":21,\"Body\":{\"Locale\":\"\",\"Text\":\"Hi\"}}");
+    json_test(Variant::from(vec![1u16, 2, 3]), "{\"Type\":5,\"Body\":[1,2,3]}");
+    let multi = Variant::from((vec![Variant::from(1), Variant::from(2), Variant::from(3),
+                                    Variant::from(4), Variant::from(5), Variant::from(6)], vec![2u32, 3]));
+    json_test(multi.clone(), "{\"Type\":6,\"Body\":[1,2,3,4,5,6],\"Dimensions\":[2,3]}");
+
+    // Non-reversible is just the value, with nested arrays for dimensions
+    assert_eq!(non_reversible(&Variant::from(5i32)), "5");
+    assert_eq!(non_reversible(&multi), "[[1,2,3],[4,5,6]]");
+    assert_eq!(non_reversible(&Variant::from(StatusCode::Good)), "{\"Code\":0,\"Symbol\":\"Good\"}");
+}
+
+#[test]
+fn json_data_value() {
+    let mut data_value = DataValue {
+        value: Some(Variant::from(100.5f64)),
+        status: None,
+        source_timestamp: Some(DateTime::ymd(2020, 4, 1)),
+        source_picoseconds: None,
+        server_timestamp: Some(DateTime::ymd(2020, 4, 1)),
+        server_picoseconds: None,
+    };
+    json_test(data_value.clone(), "{\"Value\":{\"Type\":11,\"Body\":100.5},\"SourceTimestamp\":\"2020-04-01T00:00:00Z\",\"ServerTimestamp\":\"2020-04-01T00:00:00Z\"}");
+    data_value.status = Some(StatusCode::UncertainLastUsableValue);
+    data_value.server_picoseconds = Some(10);
+    json_test(data_value.clone(), "{\"Value\":{\"Type\":11,\"Body\":100.5},\"Status\":1083179008,\"SourceTimestamp\":\"2020-04-01T00:00:00Z\",\"ServerTimestamp\":\"2020-04-01T00:00:00Z\",\"ServerPicoseconds\":10}");
+
+    // A Good status and zero picoseconds are the defaults so they are omitted
+    data_value.status = Some(StatusCode::Good);
+    data_value.server_picoseconds = Some(0);
+    assert_eq!(reversible(&data_value), "{\"Value\":{\"Type\":11,\"Body\":100.5},\"SourceTimestamp\":\"2020-04-01T00:00:00Z\",\"ServerTimestamp\":\"2020-04-01T00:00:00Z\"}");
+
+    let diagnostic_info = DiagnosticInfo {
+        symbolic_id: Some(3),
+        namespace_uri: None,
+        locale: None,
+        localized_text: None,
+        additional_info: Some(UAString::from("More")),
+        inner_status_code: None,
+        inner_diagnostic_info: Some(Box::new(DiagnosticInfo { symbolic_id: Some(4), ..DiagnosticInfo::null() })),
+    };
+    json_test(diagnostic_info, "{\"SymbolicId\":3,\"AdditionalInfo\":\"More\",\"InnerDiagnosticInfo\":{\"SymbolicId\":4}}");
+}
+
+#[test]
+fn json_extension_object() {
+    let read_value_id = ReadValueId {
+        node_id: NodeId::new(2, "Pump"),
+        attribute_id: AttributeId::Value as u32,
+        index_range: UAString::null(),
+        data_encoding: QualifiedName::null(),
+    };
+    json_test(read_value_id.clone(), "{\"NodeId\":\"ns=2;s=Pump\",\"AttributeId\":13}");
+
+    // A known type is written as JSON with the id of its JSON encoding and read back to its binary
+    // encoding
+    let extension_object = ExtensionObject::from_encodable(ObjectId::ReadValueId_Encoding_DefaultBinary, &read_value_id);
+    json_test(extension_object.clone(), "{\"TypeId\":\"i=15256\",\"Body\":{\"NodeId\":\"ns=2;s=Pump\",\"AttributeId\":13}}");
+    assert_eq!(non_reversible(&extension_object), "{\"NodeId\":\"nsu=urn:model;s=Pump\",\"AttributeId\":13}");
+
+    // Other types are written as they are
+    let unknown = ExtensionObject::from_encodable(NodeId::new(2, 5001), &1u8);
+    json_test(unknown, "{\"TypeId\":\"ns=2;i=5001\",\"Encoding\":1,\"Body\":\"AQ==\"}");
+    let xml = ExtensionObject { node_id: NodeId::new(2, 5002), body: ExtensionObjectEncoding::XmlElement(XmlElement::from("<A/>")) };
+    json_test(xml, "{\"TypeId\":\"ns=2;i=5002\",\"Encoding\":2,\"Body\":\"<A/>\"}");
+    json_test(ExtensionObject::null(), "null");
+
+    // A JSON body of an unknown type cannot be read
+    assert_eq!(ExtensionObject::from_json_str("{\"TypeId\":{\"Id\":5001,\"Namespace\":2},\"Body\":{}}", &DecodingLimits::default()).unwrap_err(), StatusCode::BadDecodingError);
+}
+
+#[test]
+fn json_service_types() {
+    let request = ReadRequest {
+        request_header: RequestHeader::new(&NodeId::new(0, 99), &DateTime::ymd(2020, 1, 1), 5),
+        max_age: 0f64,
+        timestamps_to_return: TimestampsToReturn::Both,
+        nodes_to_read: Some(vec![ReadValueId {
+            node_id: NodeId::new(2, "Pump"),
+            attribute_id: AttributeId::Value as u32,
+            index_range: UAString::null(),
+            data_encoding: QualifiedName::null(),
+        }]),
+    };
+    let request = json_test(request, "{\"RequestHeader\":{\"AuthenticationToken\":\"i=99\",\"Timestamp\":\"2020-01-01T00:00:00Z\",\"RequestHandle\":5,\"ReturnDiagnostics\":0,\"TimeoutHint\":0},\
+\"MaxAge\":0.0,\"TimestampsToReturn\":2,\"NodesToRead\":[{\"NodeId\":\"ns=2;s=Pump\",\"AttributeId\":13}]}");
+
+    // Enums are written as their names in the non-reversible form
+    assert!(non_reversible(&request).contains("\"TimestampsToReturn\":\"Both_2\""));
+
+    // Omitted fields decode as their defaults
+    let request = ReadRequest::from_json_str("{\"TimestampsToReturn\":1}", &DecodingLimits::default()).unwrap();
+    assert_eq!(request.timestamps_to_return, TimestampsToReturn::Server);
+    assert!(request.nodes_to_read.is_none());
+    assert!(request.request_header.authentication_token.is_null());
+}
@@ -1,7 +1,11 @@
 mod encoding;
+mod json;
+mod namespace_map;
 mod date_time;
+mod dynamic_structure;
 mod node_id;
 mod serde;
+mod type_registry;
 mod variant;
 
 use std::fmt::Debug;
@@ -0,0 +1,83 @@
+use std::str::FromStr;
+
+use crate::{
+    namespace_map::*,
+    node_id::{ExpandedNodeId, NodeId},
+    node_ids::ObjectId,
+    status_code::StatusCode,
+    string::UAString,
+};
+
+#[test]
+fn namespace_map_add() {
+    let mut map = NamespaceMap::new();
+    assert_eq!(map.len(), 1);
+    assert_eq!(map.uri(0), Some(OPC_UA_NAMESPACE_URI));
+    assert_eq!(map.add("urn:server").unwrap(), 1);
+    assert_eq!(map.add("urn:model").unwrap(), 2);
+    // A uri keeps its index when it is added again
+    assert_eq!(map.add("urn:server").unwrap(), 1);
+    assert_eq!(map.add("").unwrap_err(), StatusCode::BadInvalidArgument);
+    assert_eq!(map.len(), 3);
+    assert_eq!(map.index_of("urn:model"), Some(2));
+    assert_eq!(map.index_of("urn:other"), None);
+    assert_eq!(map.uri(3), None);
+    assert_eq!(map, NamespaceMap::from_uris(&[OPC_UA_NAMESPACE_URI, "urn:server", "urn:model"]));
+}
+
+#[test]
+fn namespace_map_resolve() {
+    let map = NamespaceMap::from_uris(&[OPC_UA_NAMESPACE_URI, "urn:server", "urn:model"]);
+
+    let node_id = ExpandedNodeId {
+        node_id: NodeId::new(0, "Tag"),
+        namespace_uri: UAString::from("urn:model"),
+        server_index: 0,
+    };
+    assert_eq!(map.resolve(&node_id), Some(NodeId::new(2, "Tag")));
+    assert_eq!(map.to_expanded_node_id(&NodeId::new(2, "Tag")), node_id);
+
+    // Node ids without a uri are unchanged
+    assert_eq!(map.resolve(&ExpandedNodeId::new(NodeId::new(1, 100))), Some(NodeId::new(1, 100)));
+    assert_eq!(map.to_expanded_node_id(&NodeId::new(7, 100)), ExpandedNodeId::new(NodeId::new(7, 100)));
+    assert_eq!(map.to_expanded_node_id(&ObjectId::Server.into()), ExpandedNodeId::new(ObjectId::Server));
+
+    // Unknown uris and other servers do not resolve
+    let mut other = node_id.clone();
+    other.namespace_uri = UAString::from("urn:other");
+    assert_eq!(map.resolve(&other), None);
+    let mut other = node_id;
+    other.server_index = 1;
+    assert_eq!(map.resolve(&other), None);
+}
+
+#[test]
+fn namespace_map_remap() {
+    let map = NamespaceMap::from_uris(&[OPC_UA_NAMESPACE_URI, "urn:server", "urn:model"]);
+    let other = NamespaceMap::from_uris(&[OPC_UA_NAMESPACE_URI, "urn:model"]);
+    assert_eq!(map.remap(&NodeId::new(2, "Tag"), &other), Some(NodeId::new(1, "Tag")));
+    assert_eq!(other.remap(&NodeId::new(1, "Tag"), &map), Some(NodeId::new(2, "Tag")));
+    assert_eq!(map.remap(&NodeId::new(0, 2253), &other), Some(NodeId::new(0, 2253)));
+    assert_eq!(map.remap(&NodeId::new(1, "Tag"), &other), None);
+    assert_eq!(map.remap(&NodeId::new(3, "Tag"), &other), None);
+}
+
+#[test]
+fn namespace_map_parse_node_id() {
+    let map = NamespaceMap::from_uris(&[OPC_UA_NAMESPACE_URI, "urn:server", "urn:my;model"]);
+    assert_eq!(map.parse_node_id("i=2253").unwrap(), NodeId::new(0, 2253));
+    assert_eq!(map.parse_node_id("ns=1;s=Tag").unwrap(), NodeId::new(1, "Tag"));
+    assert_eq!(map.parse_node_id("nsu=urn:server;s=Tag").unwrap(), NodeId::new(1, "Tag"));
+    assert_eq!(map.parse_node_id("nsu=urn:my%3bmodel;i=5").unwrap(), NodeId::new(2, 5));
+    assert_eq!(map.parse_node_id("svr=0;nsu=urn:server;s=Tag").unwrap(), NodeId::new(1, "Tag"));
+
+    // The parsed node id round trips through its expanded form
+    let node_id = map.parse_node_id("nsu=urn:my%3bmodel;s=Tag").unwrap();
+    let expanded = map.to_expanded_node_id(&node_id);
+    assert_eq!(ExpandedNodeId::from_str(&expanded.to_string()).unwrap(), expanded);
+
+    assert_eq!(map.parse_node_id("nsu=urn:other;s=Tag").unwrap_err(), StatusCode::BadNodeIdUnknown);
+    assert_eq!(map.parse_node_id("svr=1;nsu=urn:server;s=Tag").unwrap_err(), StatusCode::BadNodeIdUnknown);
+    assert_eq!(map.parse_node_id("nsu=urn:server").unwrap_err(), StatusCode::BadNodeIdInvalid);
+    assert_eq!(map.parse_node_id("x=1").unwrap_err(), StatusCode::BadNodeIdInvalid);
+}
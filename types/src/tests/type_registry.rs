@@ -0,0 +1,118 @@
+use std::io::{Read, Write};
+
+use crate::{
+    encoding::*,
+    tests::*,
+};
+
+/// A structure from an imaginary companion specification
+#[derive(Debug, Clone, PartialEq)]
+struct Temperature {
+    sensor: UAString,
+    celsius: f64,
+}
+
+impl BinaryEncoder<Temperature> for Temperature {
+    fn byte_len(&self) -> usize {
+        self.sensor.byte_len() + self.celsius.byte_len()
+    }
+
+    fn encode<S: Write>(&self, stream: &mut S) -> EncodingResult<usize> {
+        let mut size = self.sensor.encode(stream)?;
+        size += self.celsius.encode(stream)?;
+        Ok(size)
+    }
+
+    fn decode<S: Read>(stream: &mut S, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let sensor = UAString::decode(stream, decoding_limits)?;
+        let celsius = f64::decode(stream, decoding_limits)?;
+        Ok(Temperature { sensor, celsius })
+    }
+}
+
+fn temperature() -> Temperature {
+    Temperature { sensor: UAString::from("Boiler"), celsius: 81.5 }
+}
+
+#[test]
+fn register_and_decode() {
+    let decoding_limits = DecodingLimits::default();
+    let namespaces = NamespaceMap::new();
+    let encoding_id = NodeId::new(3, 5001);
+
+    let mut registry = TypeRegistry::new();
+    assert!(registry.encode(&temperature(), &namespaces).is_none());
+    registry.register::<Temperature, _>(encoding_id.clone());
+    assert!(registry.is_registered(&encoding_id, &namespaces));
+    assert!(registry.type_name(&encoding_id, &namespaces).unwrap().ends_with("Temperature"));
+    assert_eq!(registry.encoding_id::<Temperature>(&namespaces), Some(encoding_id.clone()));
+
+    let extension_object = registry.encode(&temperature(), &namespaces).unwrap();
+    assert_eq!(extension_object.node_id, encoding_id);
+    let value = registry.decode(&extension_object, &namespaces, &decoding_limits).unwrap().unwrap();
+    assert_eq!(value.downcast_ref::<Temperature>(), Some(&temperature()));
+
+    // Unregistered ids and bodies which are not binary are not decoded
+    let other = ExtensionObject::from_encodable(NodeId::new(3, 5002), &temperature());
+    assert!(registry.decode(&other, &namespaces, &decoding_limits).is_none());
+    let empty = ExtensionObject { node_id: encoding_id.clone(), body: ExtensionObjectEncoding::None };
+    assert_eq!(registry.decode(&empty, &namespaces, &decoding_limits).unwrap().unwrap_err(), StatusCode::BadDecodingError);
+
+    registry.unregister(&encoding_id);
+    assert!(!registry.is_registered(&encoding_id, &namespaces));
+}
+
+#[test]
+fn register_with_namespace_uri() {
+    let mut registry = TypeRegistry::new();
+    registry.register_with_namespace_uri::<Temperature, _>("urn:temperature", 5001);
+    assert!(registry.encoding_id::<Temperature>(&NamespaceMap::new()).is_none());
+
+    // Each server has its own namespace table, and both can be used at the same time
+    let server1 = NamespaceMap::from_uris(&["http://opcfoundation.org/UA/", "urn:server", "urn:temperature"]);
+    let server2 = NamespaceMap::from_uris(&["http://opcfoundation.org/UA/", "urn:temperature"]);
+    assert_eq!(registry.encoding_id::<Temperature>(&server1), Some(NodeId::new(2, 5001)));
+    assert_eq!(registry.encoding_id::<Temperature>(&server2), Some(NodeId::new(1, 5001)));
+    assert!(registry.is_registered(&NodeId::new(2, 5001), &server1));
+    assert!(!registry.is_registered(&NodeId::new(1, 5001), &server1));
+    assert!(registry.is_registered(&NodeId::new(1, 5001), &server2));
+    assert!(!registry.is_registered(&NodeId::new(2, 5001), &server2));
+
+    // An explicit id takes precedence
+    registry.register::<Temperature, _>(NodeId::new(4, "Temperature"));
+    assert_eq!(registry.encoding_id::<Temperature>(&server1), Some(NodeId::new(4, "Temperature")));
+
+    registry.unregister_with_namespace_uri("urn:temperature", 5001);
+    assert!(!registry.is_registered(&NodeId::new(1, 5001), &server2));
+}
+
+#[test]
+fn variant_with_registered_type() {
+    // The process wide registry is shared with other tests so this one uses its own namespace
+    let decoding_limits = DecodingLimits::default();
+    let namespaces = NamespaceMap::new();
+    let encoding_id = NodeId::new(901, 5001);
+    type_registry().write().unwrap().register::<Temperature, _>(encoding_id.clone());
+
+    let extension_object = ExtensionObject::from_registered(&temperature(), &namespaces).unwrap();
+    assert_eq!(extension_object.node_id, encoding_id);
+    let value = serialize_test_and_return(Variant::from(extension_object));
+    if let Variant::ExtensionObject(ref value) = value {
+        let value = value.decode_registered(&namespaces, &decoding_limits).unwrap().unwrap();
+        assert_eq!(*value.downcast::<Temperature>().unwrap(), temperature());
+    } else {
+        panic!("Expected an extension object, got {:?}", value);
+    }
+
+    // A body which is not a temperature still decodes as part of the variant, and is only
+    // rejected when it is decoded as the registered type
+    let bad = Variant::from(ExtensionObject::from_encodable(encoding_id.clone(), &1u8));
+    let bad = serialize_test_and_return(bad);
+    if let Variant::ExtensionObject(ref value) = bad {
+        assert_eq!(value.decode_registered(&namespaces, &decoding_limits).unwrap().unwrap_err(), StatusCode::BadDecodingError);
+    } else {
+        panic!("Expected an extension object, got {:?}", bad);
+    }
+
+    type_registry().write().unwrap().unregister(&encoding_id);
+}
@@ -920,3 +920,19 @@ fn variant_bytestring_to_bytearray() {
 }
 
 // TODO arrays
+
+#[test]
+fn variant_try_into_scalar() {
+    assert_eq!(f64::try_from(&Variant::Double(1.5)), Ok(1.5));
+    // Implicit conversions are allowed but not explicit casts
+    assert_eq!(f64::try_from(&Variant::Int32(2)), Ok(2.0));
+    assert_eq!(i64::try_from(&Variant::Byte(7)), Ok(7));
+    assert!(i32::try_from(&Variant::Double(2.0)).is_err());
+    assert!(i32::try_from(&Variant::Empty).is_err());
+
+    assert_eq!(String::try_from(&Variant::from("hello")), Ok(String::from("hello")));
+    assert_eq!(String::try_from(&Variant::String(UAString::null())), Ok(String::new()));
+    assert_eq!(NodeId::try_from(&Variant::from(NodeId::new(2, 1))), Ok(NodeId::new(2, 1)));
+    assert_eq!(LocalizedText::try_from(&Variant::from(LocalizedText::new("en", "x"))), Ok(LocalizedText::new("en", "x")));
+    assert!(DateTime::try_from(&Variant::from(1u32)).is_err());
+}
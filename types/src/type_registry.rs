@@ -0,0 +1,207 @@
+// OPCUA for Rust
+// SPDX-License-Identifier: MPL-2.0
+// Copyright (C) 2017-2020 Adam Lock
+
+//! Contains the `TypeRegistry` which maps the encoding ids of structures that are not part of
+//! namespace 0, e.g. those defined by a companion specification, onto the Rust types that decode
+//! and encode them.
+//!
+//! A process wide registry is returned by `type_registry()`. It holds no namespace table of its
+//! own, so types registered against a namespace uri are resolved through the `NamespaceMap` of
+//! the server or session that the extension object came from, and bodies are only decoded when
+//! `ExtensionObject::decode_registered()` is called.
+
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    io::Cursor,
+    sync::RwLock,
+};
+
+use crate::{
+    encoding::{BinaryEncoder, DecodingLimits, EncodingResult},
+    extension_object::{ExtensionObject, ExtensionObjectEncoding},
+    namespace_map::NamespaceMap,
+    node_id::{Identifier, NodeId},
+    status_codes::StatusCode,
+};
+
+/// A value decoded through the registry. Use `downcast_ref()` or `downcast()` to get at the
+/// registered type.
+pub type RegisteredValue = Box<dyn Any + Send + Sync>;
+
+/// Decodes the byte string body of an extension object into a registered type
+type DecodeFn = fn(&[u8], &DecodingLimits) -> EncodingResult<RegisteredValue>;
+
+fn decode_registered_type<T>(body: &[u8], decoding_limits: &DecodingLimits) -> EncodingResult<RegisteredValue>
+    where T: BinaryEncoder<T> + Any + Send + Sync {
+    let mut stream = Cursor::new(body);
+    let value = T::decode(&mut stream, decoding_limits)?;
+    Ok(Box::new(value))
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RegisteredType {
+    type_id: TypeId,
+    type_name: &'static str,
+    decode: DecodeFn,
+}
+
+impl RegisteredType {
+    fn new<T>() -> RegisteredType where T: BinaryEncoder<T> + Any + Send + Sync {
+        RegisteredType {
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+            decode: decode_registered_type::<T>,
+        }
+    }
+}
+
+/// Maps encoding ids onto the types which implement `BinaryEncoder` for them.
+///
+/// Types may be registered against an explicit encoding id, or against a namespace uri and
+/// identifier. The latter are looked up through a `NamespaceMap` since the namespace index of a
+/// uri differs from server to server.
+#[derive(Debug, Default)]
+pub struct TypeRegistry {
+    /// Types registered against an explicit encoding id
+    types: HashMap<NodeId, RegisteredType>,
+    /// Types registered against a namespace uri and an identifier
+    types_by_uri: HashMap<(String, Identifier), RegisteredType>,
+}
+
+lazy_static! {
+    static ref TYPE_REGISTRY: RwLock<TypeRegistry> = RwLock::new(TypeRegistry::new());
+}
+
+/// Returns the process wide type registry. Hold the write lock to register types and the read
+/// lock for everything else. Do not hold the lock while decoding since decoding consults it.
+pub fn type_registry() -> &'static RwLock<TypeRegistry> {
+    &TYPE_REGISTRY
+}
+
+impl TypeRegistry {
+    pub fn new() -> TypeRegistry {
+        TypeRegistry::default()
+    }
+
+    /// Registers `T` as the type encoded by extension objects with the supplied encoding id. Any
+    /// previous registration of the id is replaced.
+    pub fn register<T, N>(&mut self, encoding_id: N) where T: BinaryEncoder<T> + Any + Send + Sync, N: Into<NodeId> {
+        let encoding_id = encoding_id.into();
+        if encoding_id.is_null() {
+            error!("Cannot register type {} against a null encoding id", std::any::type_name::<T>());
+        } else {
+            self.types.insert(encoding_id, RegisteredType::new::<T>());
+        }
+    }
+
+    /// Registers `T` as the type encoded by extension objects with the supplied identifier in the
+    /// namespace with the supplied uri. Any previous registration of the uri and identifier is
+    /// replaced.
+    pub fn register_with_namespace_uri<T, I>(&mut self, namespace_uri: &str, identifier: I) where T: BinaryEncoder<T> + Any + Send + Sync, I: Into<Identifier> {
+        self.types_by_uri.insert((namespace_uri.to_string(), identifier.into()), RegisteredType::new::<T>());
+    }
+
+    /// Removes the registration of the encoding id
+    pub fn unregister(&mut self, encoding_id: &NodeId) {
+        self.types.remove(encoding_id);
+    }
+
+    /// Removes the registration of the identifier in the namespace with the supplied uri
+    pub fn unregister_with_namespace_uri<I>(&mut self, namespace_uri: &str, identifier: I) where I: Into<Identifier> {
+        self.types_by_uri.remove(&(namespace_uri.to_string(), identifier.into()));
+    }
+
+    fn find(&self, encoding_id: &NodeId, namespaces: &NamespaceMap) -> Option<RegisteredType> {
+        self.types.get(encoding_id).copied().or_else(|| {
+            let namespace_uri = namespaces.uri(encoding_id.namespace)?;
+            self.types_by_uri.get(&(namespace_uri.to_string(), encoding_id.identifier.clone())).copied()
+        })
+    }
+
+    /// Tests if a type is registered for the encoding id, resolving its namespace through the
+    /// namespace table
+    pub fn is_registered(&self, encoding_id: &NodeId, namespaces: &NamespaceMap) -> bool {
+        self.find(encoding_id, namespaces).is_some()
+    }
+
+    /// Returns the name of the Rust type registered for the encoding id
+    pub fn type_name(&self, encoding_id: &NodeId, namespaces: &NamespaceMap) -> Option<&'static str> {
+        self.find(encoding_id, namespaces).map(|t| t.type_name)
+    }
+
+    /// Returns the encoding id that `T` is registered against, with the namespace index from the
+    /// namespace table if it is registered by uri. If `T` is registered more than once, explicit
+    /// ids take precedence over those registered by uri.
+    pub fn encoding_id<T>(&self, namespaces: &NamespaceMap) -> Option<NodeId> where T: Any {
+        let type_id = TypeId::of::<T>();
+        // Lowest id wins so the result does not depend on hash map order
+        let lowest = |ids: Vec<NodeId>| ids.into_iter().min_by_key(|encoding_id| (encoding_id.namespace, format!("{}", encoding_id.identifier)));
+        let explicit = self.types.iter()
+            .filter(|(_, t)| t.type_id == type_id)
+            .map(|(encoding_id, _)| encoding_id.clone())
+            .collect();
+        lowest(explicit).or_else(|| {
+            let by_uri = self.types_by_uri.iter()
+                .filter(|(_, t)| t.type_id == type_id)
+                .filter_map(|((namespace_uri, identifier), _)| {
+                    namespaces.index_of(namespace_uri).map(|namespace| NodeId::new(namespace, identifier.clone()))
+                })
+                .collect();
+            lowest(by_uri)
+        })
+    }
+
+    /// Decodes the body of the extension object as the type registered for its encoding id.
+    /// Returns `None` if nothing is registered for the id.
+    pub fn decode(&self, extension_object: &ExtensionObject, namespaces: &NamespaceMap, decoding_limits: &DecodingLimits) -> Option<EncodingResult<RegisteredValue>> {
+        let registered_type = self.find(&extension_object.node_id, namespaces)?;
+        Some(Self::decode_body(registered_type, extension_object, decoding_limits))
+    }
+
+    fn decode_body(registered_type: RegisteredType, extension_object: &ExtensionObject, decoding_limits: &DecodingLimits) -> EncodingResult<RegisteredValue> {
+        match extension_object.body {
+            ExtensionObjectEncoding::ByteString(ref body) => {
+                let body = body.value.as_deref().unwrap_or(&[]);
+                (registered_type.decode)(body, decoding_limits)
+            }
+            _ => {
+                error!("Extension object {} for registered type {} does not have a binary body", extension_object.node_id, registered_type.type_name);
+                Err(StatusCode::BadDecodingError)
+            }
+        }
+    }
+
+    /// Encodes the value as an extension object using the encoding id that `T` is registered
+    /// against. Returns `None` if `T` is not registered.
+    pub fn encode<T>(&self, value: &T, namespaces: &NamespaceMap) -> Option<ExtensionObject> where T: BinaryEncoder<T> + Any {
+        self.encoding_id::<T>(namespaces).map(|encoding_id| ExtensionObject::from_encodable(encoding_id, value))
+    }
+}
+
+impl ExtensionObject {
+    /// Decodes the body with the type registered in the process wide registry for the node id,
+    /// resolving its namespace through the namespace table of the server it came from. Returns
+    /// `None` if nothing is registered for the id.
+    pub fn decode_registered(&self, namespaces: &NamespaceMap, decoding_limits: &DecodingLimits) -> Option<EncodingResult<RegisteredValue>> {
+        // The lock is released before decoding since the body may contain extension objects of
+        // its own
+        let registered_type = read_registry().find(&self.node_id, namespaces)?;
+        Some(TypeRegistry::decode_body(registered_type, self, decoding_limits))
+    }
+
+    /// Creates an extension object from a value whose type is registered in the process wide
+    /// registry, with the namespace index the namespace table has for it. Returns `None` if the
+    /// type is not registered.
+    pub fn from_registered<T>(value: &T, namespaces: &NamespaceMap) -> Option<ExtensionObject> where T: BinaryEncoder<T> + Any {
+        let encoding_id = read_registry().encoding_id::<T>(namespaces)?;
+        Some(ExtensionObject::from_encodable(encoding_id, value))
+    }
+}
+
+fn read_registry() -> std::sync::RwLockReadGuard<'static, TypeRegistry> {
+    // A panic while holding the write lock leaves the registry in a usable state so poisoning
+    // is ignored
+    type_registry().read().unwrap_or_else(|err| err.into_inner())
+}
@@ -16,6 +16,7 @@ use crate::{
     date_time::DateTime,
     encoding::*,
     extension_object::ExtensionObject,
+    json::*,
     guid::Guid,
     localized_text::LocalizedText,
     node_id::{ExpandedNodeId, Identifier, NodeId},
@@ -123,6 +124,7 @@ impl TryFrom<&NodeId> for VariantTypeId {
             if let Identifier::Numeric(type_id) = value.identifier {
                 match type_id {
                     type_id if type_id == DataTypeId::Boolean as u32 => Ok(VariantTypeId::Boolean),
+                    type_id if type_id == DataTypeId::SByte as u32 => Ok(VariantTypeId::SByte),
                     type_id if type_id == DataTypeId::Byte as u32 => Ok(VariantTypeId::Byte),
                     type_id if type_id == DataTypeId::Int16 as u32 => Ok(VariantTypeId::Int16),
                     type_id if type_id == DataTypeId::UInt16 as u32 => Ok(VariantTypeId::UInt16),
@@ -480,6 +482,72 @@ try_from_variant_to_array_impl!(u32, UInt32);
 try_from_variant_to_array_impl!(f32, Float);
 try_from_variant_to_array_impl!(f64, Double);
 
+/// This macro tries to return a scalar `foo` from a `Variant::Foo`, e.g. an `f64` from a
+/// `Variant::Double`. A variant of another type is converted implicitly first, so an `Int32` can
+/// be taken as an `f64` but a `Double` cannot be taken as an `i32`.
+macro_rules! try_from_variant_to_scalar_impl {
+    ($rtype: ident, $vtype: ident) => {
+        impl TryFrom<&Variant> for $rtype {
+            type Error = ();
+
+            fn try_from(value: &Variant) -> Result<Self, Self::Error> {
+                match value.convert(VariantTypeId::$vtype) {
+                    Variant::$vtype(v) => Ok(v),
+                    _ => Err(())
+                }
+            }
+        }
+    }
+}
+
+try_from_variant_to_scalar_impl!(bool, Boolean);
+try_from_variant_to_scalar_impl!(i8, SByte);
+try_from_variant_to_scalar_impl!(u8, Byte);
+try_from_variant_to_scalar_impl!(i16, Int16);
+try_from_variant_to_scalar_impl!(u16, UInt16);
+try_from_variant_to_scalar_impl!(i32, Int32);
+try_from_variant_to_scalar_impl!(u32, UInt32);
+try_from_variant_to_scalar_impl!(i64, Int64);
+try_from_variant_to_scalar_impl!(u64, UInt64);
+try_from_variant_to_scalar_impl!(f32, Float);
+try_from_variant_to_scalar_impl!(f64, Double);
+try_from_variant_to_scalar_impl!(UAString, String);
+try_from_variant_to_scalar_impl!(StatusCode, StatusCode);
+try_from_variant_to_scalar_impl!(ByteString, ByteString);
+
+/// This macro tries to return a scalar `Foo` from a `Variant::Foo` that holds it boxed
+macro_rules! try_from_boxed_variant_to_scalar_impl {
+    ($rtype: ident) => {
+        impl TryFrom<&Variant> for $rtype {
+            type Error = ();
+
+            fn try_from(value: &Variant) -> Result<Self, Self::Error> {
+                match value {
+                    Variant::$rtype(v) => Ok(*v.clone()),
+                    _ => Err(())
+                }
+            }
+        }
+    }
+}
+
+try_from_boxed_variant_to_scalar_impl!(DateTime);
+try_from_boxed_variant_to_scalar_impl!(Guid);
+try_from_boxed_variant_to_scalar_impl!(QualifiedName);
+try_from_boxed_variant_to_scalar_impl!(LocalizedText);
+try_from_boxed_variant_to_scalar_impl!(NodeId);
+try_from_boxed_variant_to_scalar_impl!(ExpandedNodeId);
+try_from_boxed_variant_to_scalar_impl!(ExtensionObject);
+
+/// A `String` is taken from a `Variant::String`, where a null string is empty
+impl TryFrom<&Variant> for String {
+    type Error = ();
+
+    fn try_from(value: &Variant) -> Result<Self, Self::Error> {
+        UAString::try_from(value).map(|v| v.as_ref().to_string())
+    }
+}
+
 impl BinaryEncoder<Variant> for Variant {
     fn byte_len(&self) -> usize {
         let mut size: usize = 0;
@@ -639,6 +707,73 @@ impl BinaryEncoder<Variant> for Variant {
     }
 }
 
+/// Variants are JSON objects with the built-in Type id and the value in the Body. Multi dimension
+/// arrays are flattened in the Body and have a Dimensions member. The non-reversible form is just
+/// the value, with multi dimension arrays written as nested arrays. An empty variant is null.
+impl JsonEncoder<Variant> for Variant {
+    fn encode_json(&self, ctx: &JsonContext) -> JsonValue {
+        match self {
+            Variant::Empty => JsonValue::Null,
+            Variant::Array(array) => {
+                let values = array.values.iter().map(|v| v.encode_json_body(ctx)).collect::<Vec<_>>();
+                if ctx.reversible {
+                    let mut object = JsonObject::new();
+                    object.insert("Type".to_string(), JsonValue::from(self.encoding_mask() & !(ARRAY_VALUES_BIT | ARRAY_DIMENSIONS_BIT)));
+                    object.insert("Body".to_string(), JsonValue::Array(values));
+                    if array.has_dimensions() {
+                        object.insert("Dimensions".to_string(), JsonValue::from(array.dimensions.clone()));
+                    }
+                    JsonValue::Object(object)
+                } else if array.dimensions.len() > 1 {
+                    Self::nest_json_array(&array.dimensions, &mut values.into_iter())
+                } else {
+                    JsonValue::Array(values)
+                }
+            }
+            _ => {
+                if ctx.reversible {
+                    let mut object = JsonObject::new();
+                    object.insert("Type".to_string(), JsonValue::from(self.encoding_mask()));
+                    let body = self.encode_json_body(ctx);
+                    if !body.is_null() {
+                        object.insert("Body".to_string(), body);
+                    }
+                    JsonValue::Object(object)
+                } else {
+                    self.encode_json_body(ctx)
+                }
+            }
+        }
+    }
+
+    fn decode_json(value: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+        let object = json_object(value)?;
+        let type_id: u8 = read_json_field(object, "Type", decoding_limits)?;
+        let body = object.get("Body").unwrap_or(&JsonValue::Null);
+        if type_id == 0 {
+            Ok(Variant::Empty)
+        } else if let JsonValue::Array(_) = body {
+            let values = json_array(body, decoding_limits)?.unwrap()
+                .iter()
+                .map(|v| Self::decode_json_body(type_id, v, decoding_limits))
+                .collect::<EncodingResult<Vec<Variant>>>()?;
+            let dimensions: Option<Vec<u32>> = read_json_field(object, "Dimensions", decoding_limits)?;
+            let array = match dimensions {
+                Some(dimensions) => Array::new_multi(values, dimensions),
+                None => Array::new_single(values),
+            };
+            if array.is_valid() {
+                Ok(Variant::from(array))
+            } else {
+                error!("JSON variant array is not valid");
+                Err(StatusCode::BadDecodingError)
+            }
+        } else {
+            Self::decode_json_body(type_id, body, decoding_limits)
+        }
+    }
+}
+
 impl Default for Variant {
     fn default() -> Self {
         Variant::Empty
@@ -670,6 +805,78 @@ impl fmt::Display for Variant {
 }
 
 impl Variant {
+    /// Encodes a scalar value as the Body of its JSON form
+    fn encode_json_body(&self, ctx: &JsonContext) -> JsonValue {
+        match self {
+            Variant::Empty | Variant::Array(_) => JsonValue::Null,
+            Variant::Boolean(v) => v.encode_json(ctx),
+            Variant::SByte(v) => v.encode_json(ctx),
+            Variant::Byte(v) => v.encode_json(ctx),
+            Variant::Int16(v) => v.encode_json(ctx),
+            Variant::UInt16(v) => v.encode_json(ctx),
+            Variant::Int32(v) => v.encode_json(ctx),
+            Variant::UInt32(v) => v.encode_json(ctx),
+            Variant::Int64(v) => v.encode_json(ctx),
+            Variant::UInt64(v) => v.encode_json(ctx),
+            Variant::Float(v) => v.encode_json(ctx),
+            Variant::Double(v) => v.encode_json(ctx),
+            Variant::String(v) => v.encode_json(ctx),
+            Variant::DateTime(v) => v.encode_json(ctx),
+            Variant::Guid(v) => v.encode_json(ctx),
+            Variant::StatusCode(v) => v.encode_json(ctx),
+            Variant::ByteString(v) => v.encode_json(ctx),
+            Variant::XmlElement(v) => v.encode_json(ctx),
+            Variant::QualifiedName(v) => v.encode_json(ctx),
+            Variant::LocalizedText(v) => v.encode_json(ctx),
+            Variant::NodeId(v) => v.encode_json(ctx),
+            Variant::ExpandedNodeId(v) => v.encode_json(ctx),
+            Variant::ExtensionObject(v) => v.encode_json(ctx),
+        }
+    }
+
+    /// Decodes a scalar value of the built-in type from the Body of its JSON form
+    fn decode_json_body(type_id: u8, body: &JsonValue, decoding_limits: &DecodingLimits) -> EncodingResult<Variant> {
+        let value = match type_id {
+            1 => Variant::from(bool::decode_json(body, decoding_limits)?),
+            2 => Variant::from(i8::decode_json(body, decoding_limits)?),
+            3 => Variant::from(u8::decode_json(body, decoding_limits)?),
+            4 => Variant::from(i16::decode_json(body, decoding_limits)?),
+            5 => Variant::from(u16::decode_json(body, decoding_limits)?),
+            6 => Variant::from(i32::decode_json(body, decoding_limits)?),
+            7 => Variant::from(u32::decode_json(body, decoding_limits)?),
+            8 => Variant::from(i64::decode_json(body, decoding_limits)?),
+            9 => Variant::from(u64::decode_json(body, decoding_limits)?),
+            10 => Variant::from(f32::decode_json(body, decoding_limits)?),
+            11 => Variant::from(f64::decode_json(body, decoding_limits)?),
+            12 => Variant::from(UAString::decode_json(body, decoding_limits)?),
+            13 => Variant::from(DateTime::decode_json(body, decoding_limits)?),
+            14 => Variant::from(Guid::decode_json(body, decoding_limits)?),
+            15 => Variant::from(ByteString::decode_json(body, decoding_limits)?),
+            16 => Variant::XmlElement(XmlElement::decode_json(body, decoding_limits)?),
+            17 => Variant::from(NodeId::decode_json(body, decoding_limits)?),
+            18 => Variant::from(ExpandedNodeId::decode_json(body, decoding_limits)?),
+            19 => Variant::from(StatusCode::decode_json(body, decoding_limits)?),
+            20 => Variant::from(QualifiedName::decode_json(body, decoding_limits)?),
+            21 => Variant::from(LocalizedText::decode_json(body, decoding_limits)?),
+            22 => Variant::from(ExtensionObject::decode_json(body, decoding_limits)?),
+            type_id => {
+                error!("JSON variant type {} is not supported", type_id);
+                return Err(StatusCode::BadDecodingError);
+            }
+        };
+        Ok(value)
+    }
+
+    /// Arranges the flattened values of a multi dimension array into nested JSON arrays
+    fn nest_json_array(dimensions: &[u32], values: &mut dyn Iterator<Item=JsonValue>) -> JsonValue {
+        if dimensions.is_empty() {
+            values.next().unwrap_or(JsonValue::Null)
+        } else {
+            let nested = (0..dimensions[0]).map(|_| Self::nest_json_array(&dimensions[1..], values)).collect();
+            JsonValue::Array(nested)
+        }
+    }
+
     /// Test the flag (convenience method)
     pub fn test_encoding_flag(encoding_mask: u8, data_type_id: DataTypeId) -> bool {
         encoding_mask == data_type_id as u8
@@ -709,7 +916,7 @@ impl Variant {
     }
 
     /// Encodes just the value, not the encoding flag
-    fn encode_variant_value<S: Write>(stream: &mut S, value: &Variant) -> EncodingResult<usize> {
+    pub(crate) fn encode_variant_value<S: Write>(stream: &mut S, value: &Variant) -> EncodingResult<usize> {
         match value {
             Variant::Empty => Ok(0),
             Variant::Boolean(value) => value.encode(stream),
@@ -742,7 +949,7 @@ impl Variant {
     }
 
     /// Reads just the variant value from the stream
-    fn decode_variant_value<S: Read>(stream: &mut S, encoding_mask: u8, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
+    pub(crate) fn decode_variant_value<S: Read>(stream: &mut S, encoding_mask: u8, decoding_limits: &DecodingLimits) -> EncodingResult<Self> {
         let result = if encoding_mask == 0 {
             Variant::Empty
         } else if Self::test_encoding_flag(encoding_mask, DataTypeId::Boolean) {